@@ -0,0 +1,42 @@
+//! Hot-path micro-benchmarks: relevance scoring, candidate retrieval,
+//! hybrid (BM25-only) search and a full `counsel()` call, each against the
+//! in-memory sample corpus of `minds_mcp::testing`.
+//!
+//!     cargo bench --bench hot_path
+//!     cargo bench --bench hot_path -- --save-baseline before   # then compare with --baseline before
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use minds_mcp::relevance::QueryFeatures;
+use minds_mcp::search::{self, SearchConfig};
+use minds_mcp::testing::{fixture, SAMPLE_QUESTION as QUESTION};
+use minds_mcp::types::{CounselContext, CounselRequest};
+
+fn request() -> CounselRequest {
+    CounselRequest { question: QUESTION.to_string(), context: CounselContext::default(), decision_id: None }
+}
+
+fn hot_path(c: &mut Criterion) {
+    let fixture = fixture().unwrap();
+    let engine = fixture.counsel_engine();
+    let request = request();
+    let candidates = engine.find_relevant_principles(&request).unwrap();
+    assert!(!candidates.is_empty(), "the seeded corpus should match the benchmark question");
+
+    c.bench_function("score_principle_relevance", |b| {
+        let query = QueryFeatures::new(QUESTION);
+        b.iter(|| {
+            candidates.iter().map(|p| engine.score_principle_relevance(&query, black_box(p), Some(0.6))).sum::<f64>()
+        })
+    });
+    c.bench_function("find_relevant_principles", |b| {
+        b.iter(|| engine.find_relevant_principles(black_box(&request)).unwrap())
+    });
+    c.bench_function("hybrid_search", |b| {
+        let config = SearchConfig::default();
+        b.iter(|| search::hybrid(&fixture.conn, None, black_box(QUESTION), 10, &config).unwrap())
+    });
+    c.bench_function("counsel", |b| b.iter(|| engine.counsel(black_box(&request)).unwrap()));
+}
+
+criterion_group!(benches, hot_path);
+criterion_main!(benches);
@@ -0,0 +1,259 @@
+//! Server Access Control
+//!
+//! API keys for `100minds serve`, each with a role:
+//!
+//! - `reader`: counsel, search, reports and audits
+//! - `recorder`: reader, plus recording outcomes, falsifications and bookmarks
+//! - `admin`: everything, including approving escalations and reloading
+//!   the corpus snapshot
+//!
+//! Clients send the key as `Authorization: Bearer <key>` or `X-API-Key`;
+//! the pages browsers and feed readers open (`/dashboard`, `/feed.atom`)
+//! also take `?key=<key>`.
+//! Only a SHA-256 hash of each key is stored. Until the first key is
+//! created the server stays open, as before; from then on every request
+//! needs a key, including after every key has been revoked. Webhooks keep
+//! their own forge signatures and ignore API keys.
+
+use crate::error::{MindsError, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use serde_json::{json, Value};
+use std::fmt;
+
+/// JSON-RPC error code for a refused request (server-defined range)
+pub const ACCESS_DENIED: i64 = -32001;
+
+/// What a key may do; each role includes the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Reader,
+    Recorder,
+    Admin,
+}
+
+pub const ROLES: [&str; 3] = ["reader", "recorder", "admin"];
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Reader => "reader",
+            Role::Recorder => "recorder",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn parse(role: &str) -> Result<Self> {
+        match role {
+            "reader" => Ok(Role::Reader),
+            "recorder" => Ok(Role::Recorder),
+            "admin" => Ok(Role::Admin),
+            _ => Err(MindsError::Validation(format!("Unknown role {:?} (expected one of: {})", role, ROLES.join(", ")))),
+        }
+    }
+}
+
+/// Role a tool call needs. Tools not listed here (including ones added
+/// later) need admin until someone decides otherwise.
+pub fn required_role(tool: &str) -> Role {
+    match tool {
+        "counsel" | "counsel_followup" | "evaluate_options" | "submit_counsel_async" | "get_counsel_result"
+        | "search_principles" | "get_synergies" | "get_tensions" | "get_decision_template" | "match_template"
+        | "check_blind_spots" | "pre_work_context" | "get_checklist" | "detect_anti_patterns" | "validate_prd"
+        | "wisdom_stats" | "get_learning_stats" | "coverage_report" | "decision_themes" | "search_decisions"
+        | "cohort_report" | "audit_decision" | "sync_posteriors" | "counterfactual_sim"
+        | "sanity_check_estimate" | "evaluate_cost_of_delay" => Role::Reader,
+        "record_outcome" | "record_outcomes_batch" | "record_falsification" | "ack_blind_spot" | "tick_checklist_item"
+        | "record_estimate_actual" | "mark_event" => Role::Recorder,
+        _ => Role::Admin,
+    }
+}
+
+/// A stored key (the secret itself is only shown once, at creation)
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKey {
+    pub id: i64,
+    pub name: String,
+    pub role: Role,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+/// Why a request was turned away
+#[derive(Debug, Clone, PartialEq)]
+pub enum Denied {
+    /// No key, or one that doesn't match an active key
+    Unauthenticated,
+    Forbidden { role: Role, needed: Role },
+}
+
+impl Denied {
+    /// HTTP status line for the refusal
+    pub fn status(&self) -> &'static str {
+        match self {
+            Denied::Unauthenticated => "401 Unauthorized",
+            Denied::Forbidden { .. } => "403 Forbidden",
+        }
+    }
+
+    /// The JSON-RPC `error` object for the refusal
+    pub fn to_jsonrpc(&self) -> Value {
+        json!({"code": ACCESS_DENIED, "message": self.to_string()})
+    }
+}
+
+impl fmt::Display for Denied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Denied::Unauthenticated => write!(f, "A valid API key is required (Authorization: Bearer <key>)"),
+            Denied::Forbidden { role, needed } => {
+                write!(f, "This needs the {} role (key has {})", needed.as_str(), role.as_str())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Denied {}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// The key a request presents, from `Authorization: Bearer` or `X-API-Key`
+/// (`headers` as lowercase name / value pairs)
+pub fn presented_key(headers: &[(String, String)]) -> Option<&str> {
+    headers.iter().find_map(|(name, value)| match name.as_str() {
+        "authorization" => value.strip_prefix("Bearer ").map(str::trim),
+        "x-api-key" => Some(value.trim()),
+        _ => None,
+    })
+}
+
+/// The `key=` parameter of a query string, for pages a browser opens
+pub fn query_key(query: &str) -> Option<&str> {
+    query.split('&').find_map(|pair| pair.strip_prefix("key=")).filter(|key| !key.is_empty())
+}
+
+/// Create a key; returns it with its secret, which isn't stored
+pub fn create_key(conn: &Connection, name: &str, role: Role) -> Result<(ApiKey, String)> {
+    if name.trim().is_empty() {
+        return Err(MindsError::Validation("A key needs a name".to_string()));
+    }
+    let secret = format!("hm_{}", hex::encode(rand::random::<[u8; 24]>()));
+    let created_at = Utc::now().to_rfc3339();
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO api_keys (name, role, key_hash, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![name.trim(), role.as_str(), hash_key(&secret), created_at],
+    )?;
+    if inserted == 0 {
+        return Err(MindsError::Validation(format!("A key named {:?} already exists", name.trim())));
+    }
+    let key = ApiKey {
+        id: conn.last_insert_rowid(),
+        name: name.trim().to_string(),
+        role,
+        created_at,
+        last_used_at: None,
+        revoked_at: None,
+    };
+    Ok((key, secret))
+}
+
+pub fn list_keys(conn: &Connection) -> Result<Vec<ApiKey>> {
+    let keys = conn
+        .prepare("SELECT id, name, role, created_at, last_used_at, revoked_at FROM api_keys ORDER BY id")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, String>(2)?, row.get(3)?, row.get(4)?, row.get(5)?)))?
+        .map(|row| {
+            let (id, name, role, created_at, last_used_at, revoked_at) = row?;
+            Ok(ApiKey { id, name, role: Role::parse(&role)?, created_at, last_used_at, revoked_at })
+        })
+        .collect::<Result<_>>()?;
+    Ok(keys)
+}
+
+/// Revoke a key by name or id
+pub fn revoke_key(conn: &Connection, key: &str) -> Result<()> {
+    let revoked = conn.execute(
+        "UPDATE api_keys SET revoked_at = ?2 WHERE (name = ?1 OR CAST(id AS TEXT) = ?1) AND revoked_at IS NULL",
+        params![key, Utc::now().to_rfc3339()],
+    )?;
+    if revoked == 0 {
+        return Err(MindsError::NotFound(format!("No active key {:?}", key)));
+    }
+    Ok(())
+}
+
+/// Whether the server checks keys at all (once any key has been created)
+pub fn enforced(conn: &Connection) -> Result<bool> {
+    Ok(conn.query_row("SELECT EXISTS(SELECT 1 FROM api_keys)", [], |row| row.get(0))?)
+}
+
+/// The role a request acts with: admin while access control is off,
+/// otherwise that of its key
+pub fn authenticate(conn: &Connection, presented: Option<&str>) -> Result<Result<Role, Denied>> {
+    if !enforced(conn)? {
+        return Ok(Ok(Role::Admin));
+    }
+    let Some(presented) = presented else {
+        return Ok(Err(Denied::Unauthenticated));
+    };
+    let hash = hash_key(presented);
+    let role: Option<String> = conn
+        .query_row("SELECT role FROM api_keys WHERE key_hash = ?1 AND revoked_at IS NULL", [&hash], |row| row.get(0))
+        .optional()?;
+    let Some(role) = role else {
+        return Ok(Err(Denied::Unauthenticated));
+    };
+    conn.execute("UPDATE api_keys SET last_used_at = ?2 WHERE key_hash = ?1", params![hash, Utc::now().to_rfc3339()])?;
+    Ok(Ok(Role::parse(&role)?))
+}
+
+/// Check that `role` covers `needed`
+pub fn authorize(role: Role, needed: Role) -> Result<(), Denied> {
+    if role >= needed {
+        Ok(())
+    } else {
+        Err(Denied::Forbidden { role, needed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_gate_tools_by_role_once_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        assert_eq!(authenticate(&conn, None).unwrap(), Ok(Role::Admin));
+
+        let (_, reader) = create_key(&conn, "dashboard", Role::Reader).unwrap();
+        let (_, recorder) = create_key(&conn, "swarm", Role::Recorder).unwrap();
+        assert!(create_key(&conn, "swarm", Role::Admin).is_err());
+        assert_eq!(authenticate(&conn, None).unwrap(), Err(Denied::Unauthenticated));
+        assert_eq!(authenticate(&conn, Some("hm_guess")).unwrap(), Err(Denied::Unauthenticated));
+
+        let reader_role = authenticate(&conn, Some(&reader)).unwrap().unwrap();
+        assert!(authorize(reader_role, required_role("counsel")).is_ok());
+        assert_eq!(
+            authorize(reader_role, required_role("record_outcome")),
+            Err(Denied::Forbidden { role: Role::Reader, needed: Role::Recorder })
+        );
+        let recorder_role = authenticate(&conn, Some(&recorder)).unwrap().unwrap();
+        assert!(authorize(recorder_role, required_role("record_outcome")).is_ok());
+        assert!(authorize(recorder_role, required_role("approve_decision")).is_err());
+        assert!(authorize(recorder_role, required_role("some_future_tool")).is_err());
+
+        revoke_key(&conn, "swarm").unwrap();
+        assert_eq!(authenticate(&conn, Some(&recorder)).unwrap(), Err(Denied::Unauthenticated));
+        let keys = list_keys(&conn).unwrap();
+        assert!(keys[0].last_used_at.is_some() && keys[1].revoked_at.is_some());
+
+        let headers = vec![("authorization".to_string(), format!("Bearer {}", reader))];
+        assert_eq!(presented_key(&headers), Some(reader.as_str()));
+    }
+}
@@ -0,0 +1,181 @@
+//! Action Prompts
+//!
+//! Every counsel position ends with an ACTION line: something to do in the
+//! next minute to apply the principle. A principle's own `action_prompt`,
+//! curated in the corpus (thinker files, packs), wins. Otherwise the rules
+//! in `action_prompt_rules` are tried in priority order: a rule fires when
+//! its keyword appears in the principle's description, or in its name for
+//! rules with `in_name` set. A new database is seeded with the defaults
+//! below; edit them in place:
+//!
+//! ```sql
+//! UPDATE action_prompt_rules SET prompt = 'ACTION: ...' WHERE keyword = 'automat';
+//! INSERT INTO action_prompt_rules (priority, keyword, in_name, prompt) VALUES (25, 'hire', 0, 'ACTION: ...');
+//! ```
+
+use crate::error::Result;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+/// Prompt when no curated prompt or rule applies
+pub const DEFAULT_PROMPT: &str = "ACTION: Apply this in the next 60 seconds. What's ONE concrete step?";
+
+const PARETO: &str = "ACTION: List 5 things you're working on. Circle the ONE that matters most. Do only that.";
+const FEAR: &str = "ACTION: Write the worst case in one sentence. Then write how you'd recover. Now decide.";
+const FOCUS: &str = "ACTION: Name ONE thing to stop doing today. Block it. Protect your focus.";
+const COMPOUND: &str = "ACTION: What takes 5 minutes today that pays off in 6 months? Do it now.";
+const ELIMINATE: &str = "ACTION: Delete one feature/task/commitment right now. What won't you miss?";
+const USERS: &str = "ACTION: Message ONE user right now. Ask: 'What's frustrating you?'";
+const MEASURE: &str = "ACTION: Pick ONE number that proves success. Write it down. Check it daily.";
+const AUTOMATE: &str = "ACTION: What did you do manually 3+ times this week? Automate it today.";
+const QUALITY: &str = "ACTION: Find your last 3 bugs. What's the common cause? Fix that root.";
+const SIMPLIFY: &str = "ACTION: Describe your solution in one sentence. If you can't, simplify.";
+const SHIP: &str = "ACTION: What's the smallest thing you can ship TODAY? Do that.";
+const PULL: &str = "ACTION: What are you building that nobody asked for yet? Stop. Wait for pull.";
+
+/// (priority, keyword, also match the name, prompt) seeded into a new database
+const SEED_RULES: [(i64, &str, bool, &str); 24] = [
+    (10, "80/20", true, PARETO),
+    (10, "high-impact", false, PARETO),
+    (20, "fear", true, FEAR),
+    (30, "focus", true, FOCUS),
+    (30, "distraction", false, FOCUS),
+    (40, "compound", true, COMPOUND),
+    (50, "eliminate", false, ELIMINATE),
+    (50, "remove", false, ELIMINATE),
+    (50, "cut", false, ELIMINATE),
+    (60, "customer", false, USERS),
+    (60, "user", false, USERS),
+    (70, "track", false, MEASURE),
+    (70, "measure", false, MEASURE),
+    (80, "automat", false, AUTOMATE),
+    (90, "quality", false, QUALITY),
+    (90, "defect", false, QUALITY),
+    (100, "simple", false, SIMPLIFY),
+    (100, "complex", false, SIMPLIFY),
+    (110, "start", false, SHIP),
+    (110, "begin", false, SHIP),
+    (110, "now", false, SHIP),
+    (120, "jit", false, PULL),
+    (120, "just-in-time", false, PULL),
+    (120, "needed", false, PULL),
+];
+
+/// One keyword rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub priority: i64,
+    /// Matched case-insensitively as a substring
+    pub keyword: String,
+    pub in_name: bool,
+    pub prompt: String,
+}
+
+/// Curated prompts by principle ID, and the fallback rules
+#[derive(Debug, Clone, Default)]
+pub struct ActionPrompts {
+    curated: HashMap<String, String>,
+    rules: Vec<Rule>,
+}
+
+impl ActionPrompts {
+    /// Load curated prompts and rules
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let curated = conn
+            .prepare("SELECT id, action_prompt FROM principles WHERE TRIM(COALESCE(action_prompt, '')) != ''")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        let rules = conn
+            .prepare(
+                "SELECT priority, keyword, in_name, prompt FROM action_prompt_rules
+                 WHERE keyword != '' ORDER BY priority, rowid",
+            )?
+            .query_map([], |row| {
+                Ok(Rule { priority: row.get(0)?, keyword: row.get(1)?, in_name: row.get(2)?, prompt: row.get(3)? })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(ActionPrompts { curated, rules })
+    }
+
+    /// The seeded rules, no curated prompts (for when the database can't be read)
+    pub fn seeded() -> Self {
+        let rules = SEED_RULES
+            .iter()
+            .map(|&(priority, keyword, in_name, prompt)| Rule {
+                priority,
+                keyword: keyword.to_string(),
+                in_name,
+                prompt: prompt.to_string(),
+            })
+            .collect();
+        ActionPrompts { curated: HashMap::new(), rules }
+    }
+
+    /// The principle's curated prompt, if the corpus has one
+    pub fn curated(&self, principle_id: &str) -> Option<&str> {
+        self.curated.get(principle_id).map(String::as_str)
+    }
+
+    /// First rule matching the name or description, else [`DEFAULT_PROMPT`]
+    pub fn from_rules(&self, name: &str, description: &str) -> String {
+        let name = name.to_lowercase();
+        let description = description.to_lowercase();
+        self.rules
+            .iter()
+            .find(|rule| {
+                let keyword = rule.keyword.to_lowercase();
+                description.contains(&keyword) || (rule.in_name && name.contains(&keyword))
+            })
+            .map_or_else(|| DEFAULT_PROMPT.to_string(), |rule| rule.prompt.clone())
+    }
+}
+
+/// Create and seed `action_prompt_rules` (run once, by the schema migration)
+pub fn create_rules_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE action_prompt_rules (
+             priority INTEGER NOT NULL,
+             keyword TEXT NOT NULL,
+             in_name INTEGER NOT NULL DEFAULT 0,
+             prompt TEXT NOT NULL
+         );",
+    )?;
+    for (priority, keyword, in_name, prompt) in SEED_RULES {
+        conn.execute(
+            "INSERT INTO action_prompt_rules (priority, keyword, in_name, prompt) VALUES (?1, ?2, ?3, ?4)",
+            params![priority, keyword, in_name, prompt],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curated_prompts_win_and_rules_are_editable() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let prompts = ActionPrompts::load(&conn).unwrap();
+        assert_eq!(prompts.rules, ActionPrompts::seeded().rules);
+        assert_eq!(prompts.from_rules("80/20 Rule", "Pareto"), PARETO);
+        // "focus" fires on the name, "distraction" only on the description
+        assert_eq!(prompts.from_rules("Deep Focus", "Work deeply"), FOCUS);
+        assert_eq!(prompts.from_rules("Distraction", "Work deeply"), DEFAULT_PROMPT);
+        // Earlier rules win: "remove" (50) before "user" (60)
+        assert_eq!(prompts.from_rules("X", "Remove what users ignore"), ELIMINATE);
+
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description, action_prompt)
+             VALUES ('t-1', 't', 'Automate', 'Automate the toil', 'ACTION: Script one deploy step.');
+             UPDATE action_prompt_rules SET prompt = 'ACTION: Name the toil.' WHERE keyword = 'automat';",
+        )
+        .unwrap();
+        let prompts = ActionPrompts::load(&conn).unwrap();
+        assert_eq!(prompts.curated("t-1"), Some("ACTION: Script one deploy step."));
+        assert_eq!(prompts.curated("t-2"), None);
+        assert_eq!(prompts.from_rules("Automate", "Automate the toil"), "ACTION: Name the toil.");
+    }
+}
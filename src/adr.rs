@@ -0,0 +1,330 @@
+//! Architecture Decision Records
+//!
+//! Renders a stored counsel response as a standard ADR (Context, Decision,
+//! Consequences) plus the two things 100minds adds: falsification
+//! conditions and the provenance hash that proves the record wasn't edited
+//! after the fact.
+
+use crate::types::{CounselContext, CounselPosition, CounselResponse, Stance};
+use crate::error::{MindsError, Result};
+use rusqlite::{Connection, OptionalExtension};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// A stored decision with everything an ADR needs
+#[derive(Debug, Clone)]
+pub struct AdrSource {
+    pub response: CounselResponse,
+    pub context: Option<CounselContext>,
+    pub outcome_success: Option<bool>,
+    pub outcome_notes: Option<String>,
+}
+
+impl AdrSource {
+    /// ADR status derived from the recorded outcome
+    pub fn status(&self) -> &'static str {
+        match self.outcome_success {
+            None => "Proposed",
+            Some(true) => "Accepted",
+            Some(false) => "Deprecated",
+        }
+    }
+}
+
+/// counsel_json, context_json, outcome_success, outcome_notes
+type DecisionRow = (String, Option<String>, Option<bool>, Option<String>);
+
+/// Load a decision from the provenance chain
+pub fn load_adr_source(conn: &Connection, decision_id: &str) -> Result<AdrSource> {
+    let row: Option<DecisionRow> = conn
+        .query_row(
+            "SELECT counsel_json, context_json, outcome_success, outcome_notes
+             FROM decisions WHERE id = ?1",
+            [decision_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .optional()?;
+
+    let (counsel_json, context_json, outcome_success, outcome_notes) =
+        row.ok_or_else(|| MindsError::NotFound(format!("decision {}", decision_id)))?;
+
+    Ok(AdrSource {
+        response: serde_json::from_str(&counsel_json)?,
+        context: context_json.and_then(|c| serde_json::from_str(&c).ok()),
+        outcome_success,
+        outcome_notes,
+    })
+}
+
+/// The position the ADR records as "the decision": a synthesis if one
+/// was produced, otherwise the most confident FOR position
+fn chosen_position(response: &CounselResponse) -> Option<&CounselPosition> {
+    response
+        .positions
+        .iter()
+        .find(|p| p.stance == Stance::Synthesize)
+        .or_else(|| {
+            response
+                .positions
+                .iter()
+                .filter(|p| p.stance == Stance::For)
+                .max_by(|a, b| a.confidence.partial_cmp(&b.confidence).unwrap_or(std::cmp::Ordering::Equal))
+        })
+}
+
+/// Render an ADR as markdown; `number` is the ADR sequence number if known
+pub fn render_adr(source: &AdrSource, number: Option<u32>) -> String {
+    let response = &source.response;
+    let mut md = String::new();
+
+    let title = response.question.trim_end_matches('?');
+    match number {
+        Some(n) => writeln!(md, "# {}. {}", n, title).unwrap(),
+        None => writeln!(md, "# {}", title).unwrap(),
+    }
+    writeln!(md).unwrap();
+    writeln!(md, "- **Date:** {}", response.created_at.format("%Y-%m-%d")).unwrap();
+    writeln!(md, "- **Status:** {}", source.status()).unwrap();
+    writeln!(md, "- **Decision ID:** `{}`", response.decision_id).unwrap();
+    writeln!(md, "- **Provenance hash:** `{}`", response.provenance.content_hash).unwrap();
+
+    // Context
+    writeln!(md, "\n## Context\n").unwrap();
+    writeln!(md, "{}", response.question).unwrap();
+    if let Some(ctx) = &source.context {
+        let mut facts = Vec::new();
+        if let Some(domain) = &ctx.domain {
+            facts.push(format!("Domain: {}", domain));
+        }
+        if let Some(team) = ctx.effective_team_size() {
+            facts.push(format!("Team size: {}", team));
+        }
+        if let Some(days) = ctx.effective_deadline_days() {
+            facts.push(format!("Deadline: {} days", days));
+        }
+        if !ctx.stack.is_empty() {
+            facts.push(format!("Stack: {}", ctx.stack.join(", ")));
+        }
+        if let Some(repo) = &ctx.repo {
+            facts.push(format!("Codebase: {}", repo.summary()));
+        }
+        facts.extend(ctx.constraints.iter().map(|c| format!("Constraint: {}", c)));
+        if let Some(notes) = &ctx.notes {
+            facts.push(notes.clone());
+        }
+        if !facts.is_empty() {
+            writeln!(md).unwrap();
+            for fact in facts {
+                writeln!(md, "- {}", fact).unwrap();
+            }
+        }
+    }
+    if !response.tensions.is_empty() {
+        writeln!(md, "\nForces in tension:\n").unwrap();
+        for t in &response.tensions {
+            writeln!(
+                md,
+                "- **{}** vs **{}**: pick the first when {}; the second when {}",
+                t.principle_a, t.principle_b, t.when_to_pick_a, t.when_to_pick_b
+            )
+            .unwrap();
+        }
+    }
+
+    // Decision
+    writeln!(md, "\n## Decision\n").unwrap();
+    match chosen_position(response) {
+        Some(p) => {
+            writeln!(md, "{}", p.argument).unwrap();
+            writeln!(
+                md,
+                "\n_{} ({:.0}% confidence), citing {}_",
+                p.thinker,
+                p.confidence * 100.0,
+                p.principles_cited.join(", ")
+            )
+            .unwrap();
+        }
+        None => writeln!(md, "{}", response.summary).unwrap(),
+    }
+
+    writeln!(md, "\n### Positions considered\n").unwrap();
+    for p in response.positions.iter().chain(std::iter::once(&response.challenge)) {
+        writeln!(
+            md,
+            "- **{:?}** - {} ({:.0}%): {}",
+            p.stance,
+            p.thinker,
+            p.confidence * 100.0,
+            p.argument
+        )
+        .unwrap();
+    }
+
+    // Consequences
+    writeln!(md, "\n## Consequences\n").unwrap();
+    let supporting: Vec<_> = response.positions.iter().filter(|p| p.stance == Stance::For).collect();
+    let opposing: Vec<_> = response
+        .positions
+        .iter()
+        .chain(std::iter::once(&response.challenge))
+        .filter(|p| matches!(p.stance, Stance::Against | Stance::Challenge))
+        .collect();
+    if !supporting.is_empty() {
+        writeln!(md, "Expected benefits:\n").unwrap();
+        for p in supporting {
+            writeln!(md, "- {} ({})", p.argument, p.thinker).unwrap();
+        }
+        writeln!(md).unwrap();
+    }
+    if !opposing.is_empty() {
+        writeln!(md, "Risks accepted:\n").unwrap();
+        for p in opposing {
+            writeln!(md, "- {} ({})", p.argument, p.thinker).unwrap();
+        }
+        writeln!(md).unwrap();
+    }
+    if let Some(success) = source.outcome_success {
+        writeln!(
+            md,
+            "Recorded outcome: **{}**{}",
+            if success { "success" } else { "failure" },
+            source
+                .outcome_notes
+                .as_deref()
+                .filter(|n| !n.is_empty())
+                .map(|n| format!(" - {}", n))
+                .unwrap_or_default()
+        )
+        .unwrap();
+    }
+
+    // Falsification
+    writeln!(md, "\n## Falsification conditions\n").unwrap();
+    writeln!(md, "Revisit this decision if any of these turn out to be true:\n").unwrap();
+    let conditions: Vec<_> = response
+        .positions
+        .iter()
+        .chain(std::iter::once(&response.challenge))
+        .filter_map(|p| p.falsifiable_if.as_ref().map(|f| (p, f)))
+        .collect();
+    if conditions.is_empty() {
+        writeln!(md, "- _No falsification conditions were stated._").unwrap();
+    }
+    for (p, condition) in conditions {
+        writeln!(md, "- {} ({})", condition, p.thinker).unwrap();
+    }
+
+    // Provenance
+    writeln!(md, "\n## Provenance\n").unwrap();
+    writeln!(md, "- Content hash: `{}`", response.provenance.content_hash).unwrap();
+    if let Some(prev) = &response.provenance.previous_hash {
+        writeln!(md, "- Previous hash: `{}`", prev).unwrap();
+    }
+    writeln!(md, "- Signed by: `{}`", response.provenance.agent_pubkey).unwrap();
+    writeln!(md, "- Signature: `{}`", response.provenance.signature).unwrap();
+
+    // Optional blocks leave stray blank lines; keep at most one
+    while md.contains("\n\n\n") {
+        md = md.replace("\n\n\n", "\n\n");
+    }
+    md
+}
+
+/// Next ADR number in a directory of `NNNN-title.md` files
+pub fn next_adr_number(dir: &Path) -> u32 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    let digits: String = name.chars().take_while(|c| c.is_ascii_digit()).collect();
+                    digits.parse::<u32>().ok()
+                })
+                .max()
+                .unwrap_or(0)
+        })
+        .unwrap_or(0)
+        + 1
+}
+
+/// `0007-use-postgres-for-events.md`
+pub fn adr_file_name(number: u32, question: &str) -> String {
+    let slug: String = question
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .take(8)
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("{:04}-{}.md", number, slug)
+}
+
+/// Write an ADR for a decision into `dir` with the next sequence number
+pub fn write_adr(conn: &Connection, decision_id: &str, dir: &Path) -> Result<PathBuf> {
+    let source = load_adr_source(conn, decision_id)?;
+    let number = next_adr_number(dir);
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(adr_file_name(number, &source.response.question));
+    std::fs::write(&path, render_adr(&source, Some(number)))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ProvenanceInfo;
+
+    fn position(stance: Stance, thinker: &str, argument: &str, falsifiable_if: Option<&str>) -> CounselPosition {
+        CounselPosition {
+            thinker: thinker.to_string(),
+            thinker_id: thinker.to_lowercase(),
+            stance,
+            argument: argument.to_string(),
+            principles_cited: vec!["p1".to_string()],
+            confidence: 0.7,
+            raw_confidence: None,
+            falsifiable_if: falsifiable_if.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_render_adr_sections() {
+        let mut response = CounselResponse::new(
+            "Should we split the monolith?".to_string(),
+            vec![
+                position(Stance::For, "Fowler", "Extract the billing service", Some("Deploys stay coupled")),
+                position(Stance::Against, "Brooks", "Coordination cost will grow", None),
+            ],
+            position(Stance::Challenge, "Taleb", "What if traffic never grows?", None),
+            ProvenanceInfo {
+                content_hash: "abc123".to_string(),
+                previous_hash: None,
+                signature: "sig".to_string(),
+                agent_pubkey: "pub".to_string(),
+            },
+            Some("d-adr".to_string()),
+        );
+        response.summary = "Split carefully".to_string();
+
+        let source = AdrSource {
+            response,
+            context: None,
+            outcome_success: None,
+            outcome_notes: None,
+        };
+        let md = render_adr(&source, Some(3));
+
+        assert!(md.starts_with("# 3. Should we split the monolith\n"));
+        assert!(md.contains("**Status:** Proposed"));
+        for section in ["## Context", "## Decision", "## Consequences", "## Falsification conditions"] {
+            assert!(md.contains(section), "missing {}", section);
+        }
+        assert!(md.contains("Extract the billing service"));
+        assert!(md.contains("- Deploys stay coupled (Fowler)"));
+        assert!(md.contains("`abc123`"));
+
+        assert_eq!(adr_file_name(3, "Should we split the monolith?"), "0003-should-we-split-the-monolith.md");
+    }
+}
@@ -0,0 +1,187 @@
+//! Anti-pattern Detection
+//!
+//! Decision templates list anti-patterns with the symptoms that give them
+//! away ("Shared database between services" for a distributed monolith).
+//! [`detect_anti_patterns`] reads a free-text plan, PRD or design doc a
+//! sentence at a time and matches every sentence against every symptom of
+//! every template:
+//!
+//! - keywords: at least half of the symptom's content words (compared by
+//!   their first five letters, so "deploying" meets "deploy") appear in
+//!   the sentence
+//! - embeddings, when the semantic engine is available: cosine similarity
+//!   of at least [`SIMILARITY_THRESHOLD`] between sentence and symptom
+//!
+//! Each anti-pattern with a matching symptom is returned once, with the
+//! sentences that matched, its cure and the thinker it comes from.
+
+use crate::embeddings::SemanticEngine;
+use crate::search;
+use crate::templates;
+use crate::error::Result;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Share of a symptom's content words a sentence must contain
+pub const KEYWORD_THRESHOLD: f64 = 0.5;
+
+/// Cosine similarity at which a sentence reads like a symptom
+pub const SIMILARITY_THRESHOLD: f32 = 0.6;
+
+/// Letters compared when matching words
+const STEM_LEN: usize = 5;
+
+/// A sentence of the plan that shows a symptom
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymptomMatch {
+    pub symptom: String,
+    pub evidence: String,
+    /// Keyword coverage or cosine similarity, 0-1
+    pub score: f64,
+    /// "keyword" | "embedding"
+    pub method: String,
+}
+
+/// An anti-pattern the plan shows signs of
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AntiPatternMatch {
+    pub name: String,
+    pub description: String,
+    pub template: String,
+    pub thinker: String,
+    pub cure: String,
+    /// Best symptom score
+    pub score: f64,
+    pub symptoms: Vec<SymptomMatch>,
+}
+
+/// What [`detect_anti_patterns`] found
+#[derive(Debug, Clone, Serialize)]
+pub struct AntiPatternReport {
+    pub sentences: usize,
+    /// Whether embedding similarity was used alongside keywords
+    pub semantic: bool,
+    pub matches: Vec<AntiPatternMatch>,
+}
+
+/// Sentences and bullet points of a document
+fn sentences(text: &str) -> Vec<String> {
+    text.split(['.', '!', '?', ';', '\n'])
+        .map(|s| s.trim().trim_start_matches(['-', '*', '•']).trim())
+        .filter(|s| search::tokens(s).next().is_some())
+        .map(String::from)
+        .collect()
+}
+
+/// Distinct word stems of a text's content words
+fn stems(text: &str) -> HashSet<String> {
+    search::tokens(text)
+        .filter(|t| t.chars().count() >= 3 && !search::is_stopword(t))
+        .map(|t| t.chars().take(STEM_LEN).collect())
+        .collect()
+}
+
+/// Share of the symptom's stems found in the sentence, when it clears the threshold
+fn keyword_score(symptom: &HashSet<String>, sentence: &HashSet<String>) -> Option<f64> {
+    let hits = symptom.intersection(sentence).count();
+    let score = hits as f64 / symptom.len().max(1) as f64;
+    (hits >= symptom.len().min(2) && hits > 0 && score >= KEYWORD_THRESHOLD).then_some(score)
+}
+
+/// Match a plan against the symptoms of every template's anti-patterns,
+/// optionally only templates of `domain`
+pub fn detect_anti_patterns(
+    text: &str,
+    domain: Option<&str>,
+    mut engine: Option<&mut SemanticEngine>,
+) -> Result<AntiPatternReport> {
+    let sentences = sentences(text);
+    let sentence_stems: Vec<HashSet<String>> = sentences.iter().map(|s| stems(s)).collect();
+    let sentence_vectors = match engine.as_deref_mut() {
+        Some(engine) => Some(sentences.iter().map(|s| engine.embed(s)).collect::<crate::error::Result<Vec<_>>>()?),
+        None => None,
+    };
+
+    let mut matches = Vec::new();
+    for template in templates::get_templates() {
+        if domain.is_some_and(|d| !template.domain.eq_ignore_ascii_case(d)) {
+            continue;
+        }
+        for pattern in &template.anti_patterns {
+            let mut symptoms = Vec::new();
+            for symptom in &pattern.symptoms {
+                let wanted = stems(symptom);
+                let symptom_vector = match engine.as_deref_mut() {
+                    Some(engine) => Some(engine.embed(symptom)?),
+                    None => None,
+                };
+                let mut best: Option<SymptomMatch> = None;
+                for (i, sentence) in sentences.iter().enumerate() {
+                    let by_keyword = keyword_score(&wanted, &sentence_stems[i]).map(|s| (s, "keyword"));
+                    let by_embedding = sentence_vectors.as_ref().zip(symptom_vector.as_ref()).and_then(|(vs, v)| {
+                        let similarity = SemanticEngine::cosine_similarity(&vs[i], v);
+                        (similarity >= SIMILARITY_THRESHOLD).then_some((similarity as f64, "embedding"))
+                    });
+                    let found = match (by_keyword, by_embedding) {
+                        (Some(k), Some(e)) => Some(if e.0 > k.0 { e } else { k }),
+                        (k, e) => k.or(e),
+                    };
+                    if let Some((score, method)) = found {
+                        if best.as_ref().is_none_or(|b| score > b.score) {
+                            best = Some(SymptomMatch {
+                                symptom: symptom.clone(),
+                                evidence: sentence.clone(),
+                                score,
+                                method: method.to_string(),
+                            });
+                        }
+                    }
+                }
+                symptoms.extend(best);
+            }
+            if symptoms.is_empty() {
+                continue;
+            }
+            matches.push(AntiPatternMatch {
+                name: pattern.name.clone(),
+                description: pattern.description.clone(),
+                template: template.id.clone(),
+                thinker: pattern.source_thinker.clone(),
+                cure: pattern.cure.clone(),
+                score: symptoms.iter().map(|s| s.score).fold(0.0, f64::max),
+                symptoms,
+            });
+        }
+    }
+    // Best first; more symptoms break ties
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.symptoms.len().cmp(&a.symptoms.len())));
+    Ok(AntiPatternReport { sentences: sentences.len(), semantic: sentence_vectors.is_some(), matches })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_sentences_are_matched_to_symptoms() {
+        let plan = "# Checkout split\n\
+                    - Orders and billing become separate services.\n\
+                    - Both services keep the shared database for now; every release deploys them together.\n\
+                    - While we're at it, add the loyalty features the old system never had.";
+        let report = detect_anti_patterns(plan, None, None).unwrap();
+        assert_eq!((report.sentences, report.semantic), (5, false));
+
+        let monolith = report.matches.iter().find(|m| m.name == "Distributed Monolith").unwrap();
+        assert_eq!(monolith.thinker, "Sam Newman");
+        let shared = monolith.symptoms.iter().find(|s| s.symptom == "Shared database between services").unwrap();
+        assert_eq!(shared.evidence, "Both services keep the shared database for now");
+        assert_eq!((shared.score, shared.method.as_str()), (0.75, "keyword"));
+        let second = report.matches.iter().find(|m| m.name == "Second System Effect").unwrap();
+        assert!(second.symptoms.iter().any(|s| s.symptom == "Adding features the old system didn't have"));
+
+        // Domain filter and unrelated text
+        let filtered = detect_anti_patterns(plan, Some("no-such-domain"), None).unwrap();
+        assert!(filtered.matches.is_empty());
+        assert!(detect_anti_patterns("Lunch is at noon.", None, None).unwrap().matches.is_empty());
+    }
+}
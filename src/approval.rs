@@ -0,0 +1,214 @@
+//! Decision Approval
+//!
+//! Counsel that escalates (`urgency_adjustment == "escalate"`) is only
+//! advice until a human has looked at it. Escalated decisions are stored
+//! `pending_review`; a reviewer moves them to `approved` or `rejected` with
+//! `approve_decision` / `reject_decision`. The review is signed with the
+//! server's Ed25519 key over the decision's content hash, so the approval
+//! is as tamper-evident as the decision itself. Reviews are final: a
+//! rejected decision gets a new counsel call, not a second review.
+//!
+//! Escalations still pending show up in `--stats` and the learning stats.
+
+use crate::provenance::Provenance;
+use crate::error::{MindsError, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+pub const PENDING: &str = "pending_review";
+pub const APPROVED: &str = "approved";
+pub const REJECTED: &str = "rejected";
+
+/// Review state of an escalated decision
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Approval {
+    pub decision_id: String,
+    /// "pending_review", "approved" or "rejected"
+    pub status: String,
+    pub requested_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<String>,
+    /// Ed25519 signature over [`Approval::signed_content`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer_pubkey: Option<String>,
+}
+
+/// An escalation nobody has reviewed yet
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingReview {
+    pub decision_id: String,
+    pub question: String,
+    pub requested_at: String,
+}
+
+#[derive(Serialize)]
+struct SignedReview<'a> {
+    decision_id: &'a str,
+    content_hash: &'a str,
+    status: &'a str,
+    reviewer: &'a str,
+    note: &'a str,
+    reviewed_at: &'a str,
+}
+
+impl Approval {
+    /// Bytes the review signature covers: the review, bound to the
+    /// decision's content hash
+    pub fn signed_content(&self, content_hash: &str) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&SignedReview {
+            decision_id: &self.decision_id,
+            content_hash,
+            status: &self.status,
+            reviewer: self.reviewer.as_deref().unwrap_or_default(),
+            note: self.note.as_deref().unwrap_or_default(),
+            reviewed_at: self.reviewed_at.as_deref().unwrap_or_default(),
+        })?)
+    }
+}
+
+/// Put a decision in `pending_review` (no-op if it already has a review state)
+pub fn request_review(conn: &Connection, decision_id: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO decision_approvals (decision_id, status, requested_at) VALUES (?1, ?2, ?3)",
+        params![decision_id, PENDING, Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+pub fn get(conn: &Connection, decision_id: &str) -> Result<Option<Approval>> {
+    Ok(conn
+        .query_row(
+            "SELECT decision_id, status, requested_at, reviewer, note, reviewed_at, signature, signer_pubkey
+             FROM decision_approvals WHERE decision_id = ?1",
+            [decision_id],
+            |row| {
+                Ok(Approval {
+                    decision_id: row.get(0)?,
+                    status: row.get(1)?,
+                    requested_at: row.get(2)?,
+                    reviewer: row.get(3)?,
+                    note: row.get(4)?,
+                    reviewed_at: row.get(5)?,
+                    signature: row.get(6)?,
+                    signer_pubkey: row.get(7)?,
+                })
+            },
+        )
+        .optional()?)
+}
+
+/// Approve or reject a pending escalation, signing the review
+pub fn review(
+    conn: &Connection,
+    provenance: &Provenance,
+    decision_id: &str,
+    approve: bool,
+    reviewer: &str,
+    note: Option<&str>,
+) -> Result<Approval> {
+    if reviewer.trim().is_empty() {
+        return Err(MindsError::Validation("A review needs a reviewer".to_string()));
+    }
+    let mut approval = get(conn, decision_id)?
+        .ok_or_else(|| MindsError::NotFound(format!("Decision {} is not awaiting review", decision_id)))?;
+    if approval.status != PENDING {
+        return Err(MindsError::Validation(format!("Decision {} was already {}", decision_id, approval.status)));
+    }
+    let content_hash: String =
+        conn.query_row("SELECT content_hash FROM decisions WHERE id = ?1", [decision_id], |row| row.get(0))?;
+
+    approval.status = if approve { APPROVED } else { REJECTED }.to_string();
+    approval.reviewer = Some(reviewer.trim().to_string());
+    approval.note = note.map(String::from);
+    approval.reviewed_at = Some(Utc::now().to_rfc3339());
+    approval.signature = Some(provenance.sign(&approval.signed_content(&content_hash)?)?);
+    approval.signer_pubkey = Some(provenance.public_key_hex());
+
+    // The status condition keeps two concurrent reviews from both landing
+    let updated = conn.execute(
+        "UPDATE decision_approvals
+         SET status = ?2, reviewer = ?3, note = ?4, reviewed_at = ?5, signature = ?6, signer_pubkey = ?7
+         WHERE decision_id = ?1 AND status = ?8",
+        params![
+            decision_id,
+            approval.status,
+            approval.reviewer,
+            approval.note,
+            approval.reviewed_at,
+            approval.signature,
+            approval.signer_pubkey,
+            PENDING
+        ],
+    )?;
+    if updated == 0 {
+        return Err(MindsError::Validation(format!("Decision {} was reviewed concurrently", decision_id)));
+    }
+    Ok(approval)
+}
+
+/// Whether a review's signature matches the review and the decision as stored
+pub fn verify(conn: &Connection, approval: &Approval) -> Result<bool> {
+    let (Some(signature), Some(pubkey)) = (&approval.signature, &approval.signer_pubkey) else {
+        return Ok(false);
+    };
+    let content_hash: String =
+        conn.query_row("SELECT content_hash FROM decisions WHERE id = ?1", [&approval.decision_id], |row| row.get(0))?;
+    Provenance::verify_detached(&approval.signed_content(&content_hash)?, signature, pubkey)
+}
+
+/// Escalations still awaiting review, oldest first
+pub fn pending(conn: &Connection, limit: usize) -> Result<Vec<PendingReview>> {
+    let pending = conn
+        .prepare(
+            "SELECT a.decision_id, COALESCE(d.question, ''), a.requested_at
+             FROM decision_approvals a
+             LEFT JOIN decisions d ON d.id = a.decision_id
+             WHERE a.status = ?1
+             ORDER BY a.requested_at
+             LIMIT ?2",
+        )?
+        .query_map(params![PENDING, limit as i64], |row| {
+            Ok(PendingReview { decision_id: row.get(0)?, question: row.get(1)?, requested_at: row.get(2)? })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_moves_pending_to_a_signed_final_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let provenance = Provenance::init(&dir.path().join("agent.key")).unwrap();
+        for id in ["d1", "d2"] {
+            crate::db::insert_decision(&conn, id, "Drop the users table?", None, "{}", None, &format!("hash-{}", id), "s", "k")
+                .unwrap();
+            request_review(&conn, id).unwrap();
+        }
+        assert_eq!(pending(&conn, 10).unwrap().len(), 2);
+        assert!(review(&conn, &provenance, "nope", true, "ana", None).is_err());
+
+        let approved = review(&conn, &provenance, "d1", true, "ana", Some("backups verified")).unwrap();
+        assert_eq!((approved.status.as_str(), approved.reviewer.as_deref()), (APPROVED, Some("ana")));
+        assert!(verify(&conn, &approved).unwrap());
+        assert!(review(&conn, &provenance, "d1", false, "bo", None).unwrap_err().to_string().contains("already approved"));
+
+        let rejected = review(&conn, &provenance, "d2", false, "bo", Some("no rollback plan")).unwrap();
+        assert_eq!(get(&conn, "d2").unwrap().unwrap(), rejected);
+        assert_eq!(pending(&conn, 10).unwrap().len(), 0);
+
+        // Editing the stored review breaks its signature
+        conn.execute("UPDATE decision_approvals SET status = 'approved' WHERE decision_id = 'd2'", []).unwrap();
+        assert!(!verify(&conn, &get(&conn, "d2").unwrap().unwrap()).unwrap());
+    }
+}
@@ -36,6 +36,9 @@ struct CanonicalPrinciple {
     domain_tags: Vec<String>,
     #[serde(default)]
     falsification: Option<String>,
+    /// Curated ACTION line for counsel positions
+    #[serde(default)]
+    action_prompt: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -403,14 +406,15 @@ fn import_canonical_thinker(conn: &Connection, path: &Path) -> Result<(usize, us
 
         conn.execute(
             "INSERT OR REPLACE INTO principles
-             (id, thinker_id, name, description, domain_tags, base_confidence, learned_confidence)
-             VALUES (?1, ?2, ?3, ?4, ?5, 0.7, 0.7)",
+             (id, thinker_id, name, description, domain_tags, action_prompt, base_confidence, learned_confidence)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0.7, 0.7)",
             params![
                 principle_id,
                 thinker.id,
                 principle.name,
                 full_description,
                 domain_tags,
+                principle.action_prompt,
             ],
         )?;
 
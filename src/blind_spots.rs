@@ -0,0 +1,134 @@
+//! Blind Spot Tracking
+//!
+//! `check_blind_spots` with a `decision_id` records each blind spot it
+//! returns in `blind_spot_checks` as `open`. Agents resolve them with
+//! `ack_blind_spot`, as `addressed` (the check question was answered) or
+//! `accepted_risk` (knowingly left open). Critical blind spots still open
+//! show up in the learning stats.
+
+use crate::mcp::BlindSpotResult;
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+/// Ways to resolve a blind spot
+pub const STATUSES: [&str; 2] = ["addressed", "accepted_risk"];
+
+/// A critical blind spot nobody has resolved
+#[derive(Debug, Clone, Serialize)]
+pub struct UnresolvedBlindSpot {
+    pub decision_id: String,
+    pub name: String,
+    pub source_template: String,
+    pub check_question: String,
+    pub created_at: String,
+}
+
+/// Record blind spots raised for a decision, and fill in the status of any
+/// already resolved
+pub fn record_checks(conn: &Connection, decision_id: &str, blind_spots: &mut [BlindSpotResult]) -> Result<()> {
+    crate::db::with_savepoint(conn, "record_blind_spots", || {
+        for spot in blind_spots.iter_mut() {
+            conn.execute(
+                "INSERT OR IGNORE INTO blind_spot_checks
+                 (decision_id, name, severity, source_template, check_question)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![decision_id, spot.name, spot.severity, spot.source_template, spot.check_question],
+            )?;
+            spot.status = conn
+                .query_row(
+                    "SELECT status FROM blind_spot_checks WHERE decision_id = ?1 AND name = ?2",
+                    params![decision_id, spot.name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+        }
+        Ok(())
+    })
+}
+
+/// Resolve a recorded blind spot (name matched case-insensitively)
+pub fn ack(conn: &Connection, decision_id: &str, name: &str, status: &str, note: Option<&str>) -> Result<()> {
+    if !STATUSES.contains(&status) {
+        return Err(MindsError::Validation(format!(
+            "Unknown blind spot status '{}' (addressed or accepted_risk)",
+            status
+        )));
+    }
+    let updated = conn.execute(
+        "UPDATE blind_spot_checks SET status = ?3, note = ?4, resolved_at = CURRENT_TIMESTAMP
+         WHERE decision_id = ?1 AND name = ?2 COLLATE NOCASE",
+        params![decision_id, name, status, note],
+    )?;
+    if updated == 0 {
+        let open: Vec<String> = conn
+            .prepare("SELECT name FROM blind_spot_checks WHERE decision_id = ?1 AND status = 'open' ORDER BY name")?
+            .query_map([decision_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        return Err(MindsError::NotFound(if open.is_empty() {
+            format!(
+                "No blind spots recorded for decision {}; call check_blind_spots with its decision_id first",
+                decision_id
+            )
+        } else {
+            format!("No blind spot '{}' for decision {} (open: {})", name, decision_id, open.join(", "))
+        }));
+    }
+    Ok(())
+}
+
+/// Critical blind spots still open, newest first
+pub fn unresolved_critical(conn: &Connection, limit: usize) -> Result<Vec<UnresolvedBlindSpot>> {
+    let mut stmt = conn.prepare(
+        "SELECT decision_id, name, source_template, check_question, created_at
+         FROM blind_spot_checks
+         WHERE severity = 'Critical' AND status = 'open'
+         ORDER BY created_at DESC, decision_id, name
+         LIMIT ?1",
+    )?;
+    let spots = stmt
+        .query_map([limit as i64], |row| {
+            Ok(UnresolvedBlindSpot {
+                decision_id: row.get(0)?,
+                name: row.get(1)?,
+                source_template: row.get(2)?,
+                check_question: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(spots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acked_blind_spots_leave_the_unresolved_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let mut analysis = crate::mcp::check_blind_spots("Should we rewrite the legacy system from scratch?", None);
+        let critical: Vec<String> = analysis
+            .blind_spots
+            .iter()
+            .filter(|b| b.severity == "Critical")
+            .map(|b| b.name.clone())
+            .collect();
+        assert!(!critical.is_empty());
+
+        record_checks(&conn, "d1", &mut analysis.blind_spots).unwrap();
+        assert!(analysis.blind_spots.iter().all(|b| b.status.as_deref() == Some("open")));
+        assert_eq!(unresolved_critical(&conn, 50).unwrap().len(), critical.len());
+
+        ack(&conn, "d1", &critical[0].to_uppercase(), "accepted_risk", Some("time-boxed spike")).unwrap();
+        assert_eq!(unresolved_critical(&conn, 50).unwrap().len(), critical.len() - 1);
+        assert!(ack(&conn, "d1", "Nope", "addressed", None).is_err());
+        assert!(ack(&conn, "d1", &critical[0], "ignored", None).is_err());
+
+        // Checking again keeps the resolution
+        record_checks(&conn, "d1", &mut analysis.blind_spots).unwrap();
+        let spot = analysis.blind_spots.iter().find(|b| b.name == critical[0]).unwrap();
+        assert_eq!(spot.status.as_deref(), Some("accepted_risk"));
+    }
+}
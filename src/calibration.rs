@@ -0,0 +1,460 @@
+//! Calibration - Does 80% confidence mean 80% success?
+//!
+//! Positions carry confidence numbers. This module checks them against
+//! recorded outcomes:
+//! 1. Bucket every position by its stated confidence
+//! 2. Compute calibration error overall and per thinker/principle/domain
+//! 3. Fit an optional Platt or isotonic correction
+//! 4. Apply the active correction to confidences returned by `counsel`
+//!
+//! Tetlock: forecasters only improve when they keep score.
+
+use crate::eval::crossval::{brier_score, calibration_curve, CalibrationBin};
+use crate::types::CounselResponse;
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Minimum samples before a correction may be fitted
+pub const MIN_FIT_SAMPLES: usize = 20;
+
+/// One position's stated confidence paired with the decision outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationSample {
+    pub predicted: f64,
+    pub success: bool,
+    pub thinker: String,
+    pub principle_ids: Vec<String>,
+    pub domain: Option<String>,
+}
+
+/// Calibration statistics for one group of samples
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupCalibration {
+    pub count: usize,
+    pub mean_confidence: f64,
+    pub success_rate: f64,
+    pub brier: f64,
+    /// Expected Calibration Error
+    pub ece: f64,
+}
+
+/// Full calibration report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    pub overall: GroupCalibration,
+    pub curve: Vec<CalibrationBin>,
+    pub by_thinker: HashMap<String, GroupCalibration>,
+    pub by_principle: HashMap<String, GroupCalibration>,
+    pub by_domain: HashMap<String, GroupCalibration>,
+}
+
+/// Correction method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalibrationMethod {
+    /// Logistic fit on the logit of stated confidence
+    Platt,
+    /// Monotone step function (pool-adjacent-violators)
+    Isotonic,
+}
+
+impl CalibrationMethod {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CalibrationMethod::Platt => "platt",
+            CalibrationMethod::Isotonic => "isotonic",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "platt" => Some(CalibrationMethod::Platt),
+            "isotonic" => Some(CalibrationMethod::Isotonic),
+            _ => None,
+        }
+    }
+}
+
+/// A fitted confidence correction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+pub enum Calibrator {
+    /// p' = sigmoid(a * logit(p) + b)
+    Platt { a: f64, b: f64 },
+    /// (upper bound of stated confidence, calibrated value) steps, ascending
+    Isotonic { steps: Vec<(f64, f64)> },
+}
+
+impl Calibrator {
+    pub fn method(&self) -> CalibrationMethod {
+        match self {
+            Calibrator::Platt { .. } => CalibrationMethod::Platt,
+            Calibrator::Isotonic { .. } => CalibrationMethod::Isotonic,
+        }
+    }
+
+    /// Map a stated confidence to a calibrated one
+    pub fn apply(&self, p: f64) -> f64 {
+        match self {
+            Calibrator::Platt { a, b } => sigmoid(a * logit(p) + b),
+            Calibrator::Isotonic { steps } => steps
+                .iter()
+                .find(|(upper, _)| p <= *upper)
+                .or(steps.last())
+                .map(|(_, value)| *value)
+                .unwrap_or(p),
+        }
+    }
+
+    /// Fit a correction from samples
+    pub fn fit(samples: &[CalibrationSample], method: CalibrationMethod) -> Result<Self> {
+        if samples.len() < MIN_FIT_SAMPLES {
+            return Err(MindsError::Validation(format!(
+                "Need at least {} positions with recorded outcomes to fit calibration (found {})",
+                MIN_FIT_SAMPLES,
+                samples.len()
+            )));
+        }
+
+        Ok(match method {
+            CalibrationMethod::Platt => fit_platt(samples),
+            CalibrationMethod::Isotonic => fit_isotonic(samples),
+        })
+    }
+}
+
+fn logit(p: f64) -> f64 {
+    let p = p.clamp(1e-4, 1.0 - 1e-4);
+    (p / (1.0 - p)).ln()
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Gradient descent on log loss, starting from the identity mapping
+fn fit_platt(samples: &[CalibrationSample]) -> Calibrator {
+    let n = samples.len() as f64;
+    let (mut a, mut b) = (1.0, 0.0);
+    let learning_rate = 0.5;
+
+    for _ in 0..500 {
+        let (mut grad_a, mut grad_b) = (0.0, 0.0);
+        for s in samples {
+            let x = logit(s.predicted);
+            let y = if s.success { 1.0 } else { 0.0 };
+            let err = sigmoid(a * x + b) - y;
+            grad_a += err * x;
+            grad_b += err;
+        }
+        a -= learning_rate * grad_a / n;
+        b -= learning_rate * grad_b / n;
+    }
+
+    Calibrator::Platt { a, b }
+}
+
+/// Pool-adjacent-violators over samples sorted by stated confidence
+fn fit_isotonic(samples: &[CalibrationSample]) -> Calibrator {
+    let mut sorted: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|s| (s.predicted, if s.success { 1.0 } else { 0.0 }))
+        .collect();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Blocks of (upper bound, sum of outcomes, count)
+    let mut blocks: Vec<(f64, f64, f64)> = Vec::new();
+    for (p, y) in sorted {
+        blocks.push((p, y, 1.0));
+        while blocks.len() > 1 {
+            let last = blocks[blocks.len() - 1];
+            let prev = blocks[blocks.len() - 2];
+            if prev.1 / prev.2 <= last.1 / last.2 {
+                break;
+            }
+            blocks.pop();
+            let merged = blocks.last_mut().unwrap();
+            merged.0 = last.0;
+            merged.1 += last.1;
+            merged.2 += last.2;
+        }
+    }
+
+    Calibrator::Isotonic {
+        steps: blocks
+            .into_iter()
+            .map(|(upper, sum, count)| (upper, sum / count))
+            .collect(),
+    }
+}
+
+/// Load every position from decisions with recorded outcomes
+pub fn load_samples(conn: &Connection) -> Result<Vec<CalibrationSample>> {
+    let mut stmt = conn.prepare(
+        "SELECT counsel_json, context_json, outcome_success
+         FROM decisions
+         WHERE outcome_success IS NOT NULL
+           AND content_hash != 'outcome-only'",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, i32>(2)? == 1,
+        ))
+    })?;
+
+    let mut samples = Vec::new();
+    for row in rows.flatten() {
+        let (counsel_json, context_json, success) = row;
+        let Ok(response) = serde_json::from_str::<CounselResponse>(&counsel_json) else {
+            continue;
+        };
+        let domain = context_json
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+            .and_then(|c| c.get("domain")?.as_str().map(String::from));
+
+        // Fit on stated confidences, not ones an earlier model already corrected,
+        // and leave out 100minds' own meta and synthesis positions
+        for position in response.positions.into_iter().filter(|p| !p.thinker_id.starts_with('_')) {
+            samples.push(CalibrationSample {
+                predicted: position.raw_confidence.unwrap_or(position.confidence),
+                success,
+                thinker: position.thinker,
+                principle_ids: position.principles_cited,
+                domain: domain.clone(),
+            });
+        }
+    }
+
+    Ok(samples)
+}
+
+fn group_calibration(samples: &[&CalibrationSample]) -> GroupCalibration {
+    if samples.is_empty() {
+        return GroupCalibration::default();
+    }
+
+    let predictions: Vec<(f64, bool)> = samples.iter().map(|s| (s.predicted, s.success)).collect();
+    let n = predictions.len() as f64;
+    let (_, ece) = calibration_curve(&predictions, 10);
+
+    GroupCalibration {
+        count: predictions.len(),
+        mean_confidence: predictions.iter().map(|(p, _)| p).sum::<f64>() / n,
+        success_rate: predictions.iter().filter(|(_, y)| *y).count() as f64 / n,
+        brier: brier_score(&predictions),
+        ece,
+    }
+}
+
+/// Compute calibration error overall and per thinker/principle/domain
+pub fn analyze_calibration(samples: &[CalibrationSample]) -> CalibrationReport {
+    let mut by_thinker: HashMap<String, Vec<&CalibrationSample>> = HashMap::new();
+    let mut by_principle: HashMap<String, Vec<&CalibrationSample>> = HashMap::new();
+    let mut by_domain: HashMap<String, Vec<&CalibrationSample>> = HashMap::new();
+
+    for s in samples {
+        by_thinker.entry(s.thinker.clone()).or_default().push(s);
+        for principle_id in &s.principle_ids {
+            by_principle.entry(principle_id.clone()).or_default().push(s);
+        }
+        let domain = s.domain.clone().unwrap_or_else(|| "general".to_string());
+        by_domain.entry(domain).or_default().push(s);
+    }
+
+    let summarize = |groups: HashMap<String, Vec<&CalibrationSample>>| {
+        groups
+            .into_iter()
+            .map(|(k, v)| (k, group_calibration(&v)))
+            .collect::<HashMap<_, _>>()
+    };
+
+    let all: Vec<&CalibrationSample> = samples.iter().collect();
+    let predictions: Vec<(f64, bool)> = samples.iter().map(|s| (s.predicted, s.success)).collect();
+    let (curve, _) = calibration_curve(&predictions, 10);
+
+    CalibrationReport {
+        overall: group_calibration(&all),
+        curve,
+        by_thinker: summarize(by_thinker),
+        by_principle: summarize(by_principle),
+        by_domain: summarize(by_domain),
+    }
+}
+
+/// Persist a fitted calibrator, optionally making it the active correction
+pub fn save_calibrator(
+    conn: &Connection,
+    calibrator: &Calibrator,
+    sample_count: usize,
+    activate: bool,
+) -> Result<()> {
+    if activate {
+        conn.execute("UPDATE calibration_models SET active = 0", [])?;
+    }
+
+    conn.execute(
+        "INSERT INTO calibration_models (method, params_json, sample_count, active, fitted_at)
+         VALUES (?1, ?2, ?3, ?4, CURRENT_TIMESTAMP)
+         ON CONFLICT(method) DO UPDATE SET
+             params_json = excluded.params_json,
+             sample_count = excluded.sample_count,
+             active = excluded.active,
+             fitted_at = excluded.fitted_at",
+        params![
+            calibrator.method().name(),
+            serde_json::to_string(calibrator)?,
+            sample_count as i64,
+            activate as i32
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// The correction currently applied to counsel confidences, if any
+pub fn load_active_calibrator(conn: &Connection) -> Result<Option<Calibrator>> {
+    let params_json: Option<String> = conn
+        .query_row(
+            "SELECT params_json FROM calibration_models WHERE active = 1 LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match params_json {
+        Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+        None => Ok(None),
+    }
+}
+
+/// Stop correcting counsel confidences (fitted models are kept)
+pub fn deactivate_calibration(conn: &Connection) -> Result<()> {
+    conn.execute("UPDATE calibration_models SET active = 0", [])?;
+    Ok(())
+}
+
+/// Print a calibration report
+pub fn print_calibration_report(report: &CalibrationReport) {
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🎯 CONFIDENCE CALIBRATION                                   │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+
+    let o = &report.overall;
+    say!(
+        "OVERALL: {} positions, stated {:.1}% vs actual {:.1}%  (Brier {:.3}, ECE {:.3})",
+        o.count,
+        o.mean_confidence * 100.0,
+        o.success_rate * 100.0,
+        o.brier,
+        o.ece
+    );
+
+    say!("\nCALIBRATION CURVE:");
+    for bin in &report.curve {
+        let gap = bin.observed_rate - bin.mean_predicted;
+        say!(
+            "   {:.1} – {:.1}  n={:4}  stated {:5.1}%  actual {:5.1}%  ({:+.1})",
+            bin.lower,
+            bin.upper,
+            bin.count,
+            bin.mean_predicted * 100.0,
+            bin.observed_rate * 100.0,
+            gap * 100.0
+        );
+    }
+
+    for (label, groups) in [
+        ("WORST-CALIBRATED THINKERS", &report.by_thinker),
+        ("WORST-CALIBRATED PRINCIPLES", &report.by_principle),
+        ("BY DOMAIN", &report.by_domain),
+    ] {
+        say!("\n{}:", label);
+        let mut sorted: Vec<_> = groups.iter().filter(|(_, g)| g.count >= 3).collect();
+        sorted.sort_by(|a, b| b.1.ece.partial_cmp(&a.1.ece).unwrap());
+        for (name, g) in sorted.iter().take(10) {
+            say!(
+                "   {:35} ECE {:.3}  stated {:5.1}%  actual {:5.1}%  (n={})",
+                name,
+                g.ece,
+                g.mean_confidence * 100.0,
+                g.success_rate * 100.0,
+                g.count
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(predicted: f64, success: bool) -> CalibrationSample {
+        CalibrationSample {
+            predicted,
+            success,
+            thinker: "Overconfident Oracle".to_string(),
+            principle_ids: vec!["hubris".to_string()],
+            domain: Some("architecture".to_string()),
+        }
+    }
+
+    /// Always says 90%, right half the time
+    fn overconfident_samples() -> Vec<CalibrationSample> {
+        (0..40).map(|i| sample(0.9, i % 2 == 0)).collect()
+    }
+
+    #[test]
+    fn test_analyze_detects_overconfidence() {
+        let report = analyze_calibration(&overconfident_samples());
+        assert_eq!(report.overall.count, 40);
+        assert!((report.overall.ece - 0.4).abs() < 1e-9);
+        assert!(report.by_thinker.contains_key("Overconfident Oracle"));
+        assert!(report.by_principle.contains_key("hubris"));
+        assert!(report.by_domain.contains_key("architecture"));
+    }
+
+    #[test]
+    fn test_platt_and_isotonic_pull_toward_observed_rate() {
+        let samples = overconfident_samples();
+
+        let platt = Calibrator::fit(&samples, CalibrationMethod::Platt).unwrap();
+        assert!((platt.apply(0.9) - 0.5).abs() < 0.1);
+
+        let isotonic = Calibrator::fit(&samples, CalibrationMethod::Isotonic).unwrap();
+        assert!((isotonic.apply(0.9) - 0.5).abs() < 1e-9);
+
+        assert!(Calibrator::fit(&samples[..5], CalibrationMethod::Platt).is_err());
+    }
+
+    #[test]
+    fn test_isotonic_is_monotone() {
+        let mut samples = Vec::new();
+        for i in 0..30 {
+            samples.push(sample(0.3, i % 5 == 0));
+            samples.push(sample(0.7, i % 5 != 0));
+        }
+        let isotonic = Calibrator::fit(&samples, CalibrationMethod::Isotonic).unwrap();
+        assert!(isotonic.apply(0.3) <= isotonic.apply(0.7));
+    }
+
+    #[test]
+    fn test_save_and_load_active_calibrator() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+
+        assert!(load_active_calibrator(&conn).unwrap().is_none());
+
+        let calibrator = Calibrator::Platt { a: 0.5, b: 0.0 };
+        save_calibrator(&conn, &calibrator, 40, true).unwrap();
+        let loaded = load_active_calibrator(&conn).unwrap().unwrap();
+        assert_eq!(loaded.method(), CalibrationMethod::Platt);
+
+        deactivate_calibration(&conn).unwrap();
+        assert!(load_active_calibrator(&conn).unwrap().is_none());
+    }
+}
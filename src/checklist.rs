@@ -0,0 +1,253 @@
+//! Decision Checklists
+//!
+//! Counsel is prose; agents work through lists. A checklist flattens a
+//! decision into the things to do and watch, in order:
+//!
+//! 1. `action`: the ACTION line of each position, once per distinct action
+//! 2. `blind_spot`: the check question of each blind spot, most severe
+//!    first (those recorded with `check_blind_spots`, or the question's own
+//!    if none were)
+//! 3. `watch`: each position's falsification condition, to keep an eye on
+//!    after deciding
+//!
+//! Item IDs are derived from the kind and text ("A-3f9c1a2b"), so compiling
+//! the same decision again yields the same IDs and ticked items stay ticked.
+//! Items are stored in `checklist_items`, where completion can later be
+//! set against the decision's outcome.
+
+use crate::adr::load_adr_source;
+use crate::types::CounselResponse;
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// Item kinds, in checklist order
+pub const KINDS: [&str; 3] = ["action", "blind_spot", "watch"];
+
+/// One thing to do or check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// Stable across recompiles, e.g. "A-3f9c1a2b"
+    pub id: String,
+    /// "action", "blind_spot" or "watch"
+    pub kind: String,
+    pub text: String,
+    /// Thinker or template the item came from
+    pub source: String,
+    /// Principle the item applies, for action and watch items
+    pub principle: Option<String>,
+    pub done: bool,
+    pub done_at: Option<String>,
+}
+
+/// Every item for one decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checklist {
+    pub decision_id: String,
+    pub question: String,
+    pub items: Vec<ChecklistItem>,
+}
+
+impl Checklist {
+    /// (done, total)
+    pub fn progress(&self) -> (usize, usize) {
+        (self.items.iter().filter(|i| i.done).count(), self.items.len())
+    }
+}
+
+/// Stable ID from an item's kind and text
+fn item_id(kind: &str, text: &str) -> String {
+    let digest = hex::encode(Sha256::digest(format!("{}\n{}", kind, text).as_bytes()));
+    let prefix = match kind {
+        "action" => "A",
+        "blind_spot" => "B",
+        _ => "W",
+    };
+    format!("{}-{}", prefix, &digest[..8])
+}
+
+/// The ACTION line of a position's argument, without the "ACTION:" label
+fn action_of(argument: &str) -> Option<String> {
+    let (_, action) = argument.split_once("ACTION:")?;
+    let action = action.lines().next().unwrap_or("").trim();
+    (!action.is_empty()).then(|| action.to_string())
+}
+
+/// Flatten a response into checklist items (nothing ticked); blind spots
+/// are (check question, template) pairs, most severe first
+pub fn compile(response: &CounselResponse, blind_spots: &[(String, String)]) -> Vec<ChecklistItem> {
+    let mut items: Vec<ChecklistItem> = Vec::new();
+    let mut push = |kind: &str, text: String, source: &str, principle: Option<&String>| {
+        let id = item_id(kind, &text);
+        if items.iter().any(|i| i.id == id) {
+            return;
+        }
+        items.push(ChecklistItem {
+            id,
+            kind: kind.to_string(),
+            text,
+            source: source.to_string(),
+            principle: principle.cloned(),
+            done: false,
+            done_at: None,
+        });
+    };
+
+    let positions = || response.positions.iter().chain(std::iter::once(&response.challenge));
+    for p in positions() {
+        if let Some(action) = action_of(&p.argument) {
+            push("action", action, &p.thinker, p.principles_cited.first());
+        }
+    }
+    for (question, template) in blind_spots {
+        push("blind_spot", question.clone(), template, None);
+    }
+    for p in positions() {
+        if let Some(condition) = p.falsifiable_if.as_ref().filter(|c| !c.trim().is_empty()) {
+            push("watch", condition.clone(), &p.thinker, p.principles_cited.first());
+        }
+    }
+    items
+}
+
+/// (check question, template) of the decision's blind spots, most severe first
+fn blind_spots(conn: &Connection, decision_id: &str, question: &str) -> Result<Vec<(String, String)>> {
+    let recorded: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT COALESCE(check_question, name), COALESCE(source_template, '')
+             FROM blind_spot_checks WHERE decision_id = ?1
+             ORDER BY CASE severity WHEN 'Critical' THEN 0 WHEN 'High' THEN 1 WHEN 'Medium' THEN 2 ELSE 3 END, name",
+        )?
+        .query_map([decision_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    if !recorded.is_empty() {
+        return Ok(recorded);
+    }
+    Ok(crate::mcp::check_blind_spots(question, None)
+        .blind_spots
+        .into_iter()
+        .map(|b| (b.check_question, b.source_template))
+        .collect())
+}
+
+/// Compile a decision's checklist from its stored counsel and store any
+/// new items; items already stored keep their ticks
+pub fn generate(conn: &Connection, decision_id: &str) -> Result<Checklist> {
+    let response = load_adr_source(conn, decision_id)?.response;
+    let items = compile(&response, &blind_spots(conn, decision_id, &response.question)?);
+    crate::db::with_savepoint(conn, "store_checklist", || {
+        for (position, item) in items.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO checklist_items (decision_id, item_id, position, kind, text, source, principle)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(decision_id, item_id) DO UPDATE SET position = excluded.position",
+                params![decision_id, item.id, position as i64, item.kind, item.text, item.source, item.principle],
+            )?;
+        }
+        Ok::<_, MindsError>(())
+    })?;
+    load(conn, decision_id, &response.question)
+}
+
+fn load(conn: &Connection, decision_id: &str, question: &str) -> Result<Checklist> {
+    let items = conn
+        .prepare(
+            "SELECT item_id, kind, text, source, principle, done, done_at
+             FROM checklist_items WHERE decision_id = ?1 ORDER BY position, item_id",
+        )?
+        .query_map([decision_id], |row| {
+            Ok(ChecklistItem {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                text: row.get(2)?,
+                source: row.get(3)?,
+                principle: row.get(4)?,
+                done: row.get(5)?,
+                done_at: row.get(6)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(Checklist { decision_id: decision_id.to_string(), question: question.to_string(), items })
+}
+
+/// Tick (or untick) an item, compiling the checklist first if needed
+pub fn tick(conn: &Connection, decision_id: &str, item_id: &str, done: bool) -> Result<Checklist> {
+    let checklist = generate(conn, decision_id)?;
+    let Some(item) = checklist.items.iter().find(|i| i.id.eq_ignore_ascii_case(item_id)) else {
+        let ids: Vec<&str> = checklist.items.iter().map(|i| i.id.as_str()).collect();
+        return Err(MindsError::NotFound(format!(
+            "No checklist item {} for decision {} (items: {})",
+            item_id,
+            decision_id,
+            ids.join(", ")
+        )));
+    };
+    conn.execute(
+        "UPDATE checklist_items
+         SET done = ?3, done_at = CASE WHEN ?3 THEN COALESCE(done_at, CURRENT_TIMESTAMP) END
+         WHERE decision_id = ?1 AND item_id = ?2",
+        params![decision_id, item.id, done],
+    )?;
+    load(conn, decision_id, &checklist.question)
+}
+
+/// Render a checklist as Markdown task lists, one section per kind
+pub fn render_markdown(checklist: &Checklist) -> String {
+    let (done, total) = checklist.progress();
+    let mut md = String::new();
+    writeln!(md, "# Checklist: {}", checklist.question).unwrap();
+    writeln!(md).unwrap();
+    writeln!(md, "- **Decision ID:** `{}`", checklist.decision_id).unwrap();
+    writeln!(md, "- **Done:** {}/{}", done, total).unwrap();
+    for (kind, heading) in KINDS.iter().zip(["Actions", "Blind spot checks", "Watch for"]) {
+        let items: Vec<&ChecklistItem> = checklist.items.iter().filter(|i| i.kind == *kind).collect();
+        if items.is_empty() {
+            continue;
+        }
+        writeln!(md).unwrap();
+        writeln!(md, "## {}", heading).unwrap();
+        writeln!(md).unwrap();
+        for item in items {
+            let mark = if item.done { "x" } else { " " };
+            writeln!(md, "- [{}] `{}` {} _({})_", mark, item.id, item.text, item.source).unwrap();
+        }
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checklist_ids_are_stable_and_ticks_survive_recompiling() {
+        let fixture = crate::testing::fixture().unwrap();
+        let request = crate::types::CounselRequest {
+            question: crate::testing::SAMPLE_QUESTION.to_string(),
+            context: Default::default(),
+            decision_id: None,
+        };
+        let response = fixture.counsel_engine().counsel(&request).unwrap();
+        let id = &response.decision_id;
+
+        let checklist = generate(&fixture.conn, id).unwrap();
+        let kinds: Vec<&str> = checklist.items.iter().map(|i| i.kind.as_str()).collect();
+        let first = |kind: &str| kinds.iter().position(|k| *k == kind).unwrap();
+        assert!(first("action") < first("blind_spot") && first("blind_spot") < first("watch"), "{:?}", kinds);
+        assert!(checklist.items.iter().all(|i| !i.text.starts_with("ACTION") && !i.done));
+
+        let action = checklist.items[0].id.clone();
+        let ticked = tick(&fixture.conn, id, &action.to_lowercase(), true).unwrap();
+        assert!(ticked.items[0].done && ticked.items[0].done_at.is_some());
+        assert_eq!(ticked.progress(), (1, checklist.items.len()));
+        assert!(tick(&fixture.conn, id, "A-00000000", true).is_err());
+
+        let again = generate(&fixture.conn, id).unwrap();
+        let ids = |c: &Checklist| c.items.iter().map(|i| i.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&again), ids(&checklist));
+        assert!(again.items[0].done);
+        assert!(render_markdown(&again).contains(&format!("- [x] `{}`", action)));
+    }
+}
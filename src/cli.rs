@@ -0,0 +1,666 @@
+//! Command-Line Interface
+//!
+//! clap definitions for the `100minds` binary. Every command is a
+//! subcommand (`100minds counsel ...`, `100minds outcome ...`); the older
+//! flag spellings (`--outcome`, `--serve`, a bare question) are rewritten
+//! by [`normalize_legacy_args`] so existing scripts and swarm hooks keep
+//! working.
+
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::output::OutputMode;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "100minds",
+    version,
+    about = "Adversarial wisdom council: counsel from 100 thinkers, with a learning flywheel",
+    after_help = "Run without a command for the interactive REPL."
+)]
+pub struct Cli {
+    /// Machine-readable JSON output (any command)
+    #[arg(long, global = true)]
+    pub json: bool,
+    /// Print nothing; rely on the exit code
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+    /// ASCII borders and text labels instead of box drawing and emoji
+    /// (automatic with NO_COLOR or when stdout isn't a terminal)
+    #[arg(long, global = true)]
+    pub plain: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    pub fn output_mode(&self) -> OutputMode {
+        OutputMode::from_flags(self.json, self.quiet)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Ask the council for adversarial counsel on a decision
+    Counsel(CounselArgs),
+    /// One-shot counsel with the compact decision tree output
+    #[command(hide = true)]
+    Ask {
+        #[arg(required = true)]
+        question: Vec<String>,
+    },
+    /// Record a decision outcome (feeds the learning flywheel)
+    Outcome(OutcomeArgs),
+    /// Apply a JSONL file of outcomes (one RecordOutcomeRequest per line)
+    OutcomeImport {
+        file: String,
+        /// Validate and report without keeping anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Keep nothing if any record fails
+        #[arg(long)]
+        atomic: bool,
+    },
+    /// Review outcomes the outcome guard quarantined
+    Outcomes {
+        #[arg(default_value = "quarantined", value_parser = ["quarantined", "apply", "drop"])]
+        action: String,
+        /// Quarantine id, batch id, or "all" (apply, drop)
+        target: Option<String>,
+    },
+    /// Run the JSON-RPC HTTP server (MCP + webhooks)
+    Serve {
+        #[arg(long, default_value_t = 3100)]
+        port: u16,
+        /// Serve counsel from an in-memory copy of the corpus
+        #[arg(long)]
+        snapshot: bool,
+    },
+    /// Manage the server's API keys (access control starts with the first key)
+    Keys {
+        #[arg(default_value = "list", value_parser = ["list", "create", "revoke"])]
+        action: String,
+        /// Key name (create), or name or id (revoke)
+        name: Option<String>,
+        /// Role for a new key
+        #[arg(long, default_value = "reader", value_parser = crate::access::ROLES)]
+        role: String,
+    },
+    /// Delete or roll up history past the `[retention]` limits
+    Prune {
+        /// Report what would go without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rebuild learned confidences from the recorded outcomes and decay runs, and report drift
+    Replay {
+        /// Write the replayed confidences over the live ones
+        #[arg(long)]
+        apply: bool,
+        /// What-if: this daily decay factor instead of the recorded one
+        #[arg(long)]
+        daily_decay: Option<f64>,
+        /// What-if: this confidence gain per successful outcome
+        #[arg(long)]
+        success_delta: Option<f64>,
+        /// What-if: this confidence change per failed outcome (negative)
+        #[arg(long, allow_hyphen_values = true)]
+        failure_delta: Option<f64>,
+        /// What-if: re-pick each decision's lead principle with this selection policy
+        #[arg(long, value_parser = crate::eval::bandit::POLICIES)]
+        policy: Option<String>,
+        /// What-if: re-pick each decision's lead principle with this exploration rate
+        #[arg(long)]
+        epsilon: Option<f64>,
+        /// Seed for the what-if policy's random draws
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Remove every stored decision that mentions a subject (GDPR erasure)
+    Forget {
+        /// Text to look for, case-insensitive
+        #[arg(long)]
+        pattern: String,
+        /// Treat the pattern as a regex
+        #[arg(long)]
+        regex: bool,
+        /// Keep the decisions, replacing each mention with [FORGOTTEN]
+        #[arg(long)]
+        redact: bool,
+        /// List the matches without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Redact PII from decisions stored before `[privacy] redact` was on
+    Scrub {
+        /// Report what would change without changing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Encrypt wisdom.db (SQLCipher) and agent.key in place; needs the `encryption` feature
+    EncryptDb {
+        /// Keep the passphrase in the OS keychain (generating one if MINDS_DB_PASSPHRASE is unset)
+        #[arg(long)]
+        keychain: bool,
+    },
+    /// Full-screen dashboard
+    Tui,
+    /// Interactive question/answer loop
+    Repl,
+    /// Scheduled maintenance (persist/decay/cull/discover/embeddings/trends)
+    Daemon {
+        /// `run` the scheduler or show `status`
+        #[arg(default_value = "run", value_parser = ["run", "status"])]
+        action: String,
+        /// Run whatever is due, then exit
+        #[arg(long)]
+        once: bool,
+        /// Config file (default: config.toml in the data dir)
+        #[arg(long)]
+        config: Option<String>,
+    },
+    /// Answer counsel jobs queued with submit_counsel_async
+    Worker {
+        /// Exit when the queue is empty instead of polling
+        #[arg(long)]
+        once: bool,
+        #[arg(long, default_value_t = 1000)]
+        poll_ms: u64,
+        /// Worker id (restarting with the same id requeues its unfinished jobs)
+        #[arg(long)]
+        id: Option<String>,
+    },
+    /// Validate a PRD against the principles (CI gate)
+    ValidatePrd {
+        path: String,
+        /// Output format
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+        /// Exit non-zero at this severity
+        #[arg(long, default_value = "never", value_parser = ["error", "warning", "never"])]
+        fail_on: String,
+    },
+    /// Analyze a PRD and write an enhanced copy with 100minds metadata
+    AnalyzePrd {
+        path: String,
+        output: Option<String>,
+    },
+    /// Match a question to decision templates
+    Template {
+        #[arg(required = true)]
+        question: Vec<String>,
+    },
+    /// Score concrete options against relevant principles (a matrix instead of a debate)
+    EvaluateOptions {
+        /// The decision the options answer
+        question: Vec<String>,
+        /// An option to compare (repeat 2-6 times)
+        #[arg(long = "option", required = true)]
+        options: Vec<String>,
+        #[arg(long)]
+        domain: Option<String>,
+    },
+    /// Sequence prioritization candidates by cost of delay divided by duration (CD3)
+    CostOfDelay {
+        /// A candidate, NAME:VALUE_PER_WEEK:WEEKS[:TIME_CRITICALITY[:RISK_REDUCTION]] (repeat, in planned order)
+        #[arg(long = "item", required = true)]
+        items: Vec<crate::cost_of_delay::DelayItem>,
+    },
+    /// Sanity-check an estimate against similar past tasks (reference-class forecasting)
+    Estimate {
+        #[arg(required = true)]
+        task: Vec<String>,
+        #[arg(long)]
+        estimate: f64,
+        /// Unit of the estimate (default days)
+        #[arg(long)]
+        unit: Option<String>,
+        /// Decision the task carries out
+        #[arg(long)]
+        decision: Option<String>,
+    },
+    /// Record how long an estimated task really took, in the estimate's unit
+    EstimateActual { estimate_id: i64, actual: f64 },
+    /// List blind spots for a context
+    BlindSpots {
+        #[arg(required = true)]
+        context: Vec<String>,
+    },
+    /// Principles to keep in mind before starting a task
+    PreWork {
+        #[arg(required = true)]
+        task: Vec<String>,
+    },
+    /// Print MCP tool definitions as JSON
+    Tools,
+    /// Corpus statistics
+    Stats,
+    /// Run evaluation benchmarks
+    Benchmark {
+        #[arg(
+            default_value = "all",
+            value_parser = [
+                "all", "scenarios", "from-history", "monte-carlo", "coverage", "crossval",
+                "synthetic", "data-driven", "eval-synthetic", "neural-training", "training",
+                "neural-score", "scoring", "snapshot", "history",
+            ]
+        )]
+        suite: String,
+        /// Suite-specific arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Corpus analysis reports
+    Analyze {
+        #[arg(default_value = "coverage", value_parser = ["coverage", "thinkers", "principles", "domains", "templates", "themes", "cohorts", "failures", "adherence"])]
+        report: String,
+        /// Recount coverage counters from the full decision history first
+        #[arg(long)]
+        rebuild_coverage: bool,
+    },
+    /// Bookmark a corpus or config change; `analyze cohorts` compares before and after
+    MarkEvent {
+        #[arg(required = true)]
+        label: Vec<String>,
+    },
+    /// Confidence and success-rate trends, month over month
+    Trends {
+        /// Principle name or ID, or a domain tag (default: all principles)
+        target: Option<String>,
+        /// Print the series as CSV
+        #[arg(long)]
+        csv: bool,
+        /// Every snapshot instead of one point per month
+        #[arg(long)]
+        daily: bool,
+    },
+    /// Thompson sampling maintenance
+    Thompson {
+        #[arg(
+            default_value = "stats",
+            value_parser = [
+                "stats", "init", "persist", "remediate", "explore", "cull", "discover", "yuzu",
+                "contextual", "decay",
+            ]
+        )]
+        action: String,
+    },
+    /// Compute semantic embeddings for all principles
+    ComputeEmbeddings,
+    /// Install the embedding model, verifying its checksums
+    FetchModel {
+        /// Model directory or tarball to install from instead of downloading
+        #[arg(long)]
+        from: Option<String>,
+        /// `sha256sum`-format file of expected checksums
+        #[arg(long)]
+        checksums: Option<String>,
+    },
+    /// Learning flywheel statistics
+    LearningStats,
+    /// Confidence calibration: report, fit a correction, or turn it off
+    Calibration {
+        #[arg(default_value = "report", value_parser = ["report", "fit", "off"])]
+        action: String,
+        /// Fit method
+        #[arg(value_parser = ["platt", "isotonic"])]
+        method: Option<String>,
+    },
+    /// Render a decision as an Architecture Decision Record
+    Adr {
+        decision_id: String,
+        /// Output file, or a directory to number into (default: ./docs/adr/ if present, else stdout)
+        output: Option<String>,
+    },
+    /// Record an observed falsification condition, or list flagged decisions
+    Falsify {
+        decision_id: Option<String>,
+        /// Thinker name or ID of the position, or text from its condition
+        #[arg(requires = "decision_id")]
+        position: Option<String>,
+        /// What was observed
+        #[arg(long, default_value = "")]
+        evidence: String,
+    },
+    /// Risk register for a decision: Against positions, falsifiers, critical blind spots
+    Risk {
+        decision_id: String,
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "json"])]
+        format: String,
+        /// Risk to assess (e.g. R2) with --likelihood/--impact/--mitigation
+        #[arg(long)]
+        assess: Option<String>,
+        #[arg(long, requires = "assess", value_parser = ["low", "medium", "high"])]
+        likelihood: Option<String>,
+        #[arg(long, requires = "assess", value_parser = ["low", "medium", "high"])]
+        impact: Option<String>,
+        #[arg(long, requires = "assess")]
+        mitigation: Option<String>,
+    },
+    /// Checklist for a decision: ACTION items, blind-spot checks, falsifiers to watch
+    Checklist {
+        decision_id: String,
+        /// Item to mark done (e.g. A-3f9c1a2b)
+        #[arg(long, conflicts_with = "undo")]
+        done: Option<String>,
+        /// Item to mark not done
+        #[arg(long)]
+        undo: Option<String>,
+    },
+    /// Export thinkers and principles as an Obsidian/Markdown vault
+    ExportVault { dir: String },
+    /// Write a self-contained HTML dashboard (learning, coverage, posteriors, decisions, trends)
+    Dashboard { out_dir: String },
+    /// Export thinkers, principles, decisions and outcomes as a graph (GraphML or D3 JSON)
+    ExportGraph {
+        #[arg(long, default_value = "graphml", value_parser = ["graphml", "json"])]
+        format: String,
+        /// File to write (default: stdout)
+        #[arg(long, short)]
+        output: Option<String>,
+        /// Thinkers and principles only
+        #[arg(long)]
+        corpus_only: bool,
+    },
+    /// Link a decision to the branch/PR that implements it
+    LinkDecision {
+        decision_id: String,
+        #[arg(long)]
+        branch: Option<String>,
+        /// PR number ("#12" or "12")
+        #[arg(long)]
+        pr: Option<String>,
+        /// Repository path
+        #[arg(long, default_value = ".")]
+        repo: String,
+        /// Forge repository ("owner/name") webhooks match; defaults to the origin remote's
+        #[arg(long)]
+        forge_repo: Option<String>,
+    },
+    /// Suggest outcomes for linked decisions from git history
+    SuggestOutcomes {
+        /// Days after merge during which follow-up fixes count
+        #[arg(long, default_value_t = crate::git_outcomes::DEFAULT_WINDOW_DAYS)]
+        days: i64,
+    },
+    /// Semantic + keyword principle search
+    HybridSearch {
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Semantic and BM25 weights, e.g. 0.8,0.2 (default from [search] in config.toml)
+        #[arg(long)]
+        weights: Option<String>,
+        /// Drop results whose fused score (0-1) is below this
+        #[arg(long)]
+        min_score: Option<f32>,
+        /// Rerank the fused results
+        #[arg(long)]
+        rerank: bool,
+    },
+    /// Full-text search over past decisions' questions, arguments and outcome notes
+    SearchDecisions {
+        #[arg(required = true)]
+        query: Vec<String>,
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Walk redundant principle pairs and merge duplicates
+    Dedupe,
+    /// Organization policy: pin or ban principles
+    Policy {
+        #[arg(default_value = "list", value_parser = ["list", "pin", "ban", "clear"])]
+        action: String,
+        /// Principle name or ID
+        principle: Option<String>,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Curate relations between principles: A requires, conflicts with or refines B
+    Edges {
+        #[arg(default_value = "list", value_parser = ["list", "add", "remove"])]
+        action: String,
+        /// Principle name or ID the relation starts from
+        from: Option<String>,
+        #[arg(value_parser = ["requires", "conflicts", "refines"])]
+        relation: Option<String>,
+        /// Principle name or ID the relation points to
+        to: Option<String>,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Install, list, remove or sign thinker packs
+    Pack {
+        #[arg(value_parser = ["install", "list", "remove", "sign"])]
+        action: String,
+        /// Pack file or URL (install, sign) or pack id (remove)
+        target: Option<String>,
+        /// Refuse unsigned packs
+        #[arg(long)]
+        require_signed: bool,
+    },
+    /// Print Thompson posteriors as JSON for swarm sync
+    SyncPosteriors,
+    /// Generate shell completions
+    Completions { shell: Shell },
+}
+
+#[derive(Debug, Args)]
+pub struct CounselArgs {
+    #[arg(required = true)]
+    pub question: Vec<String>,
+    #[arg(long)]
+    pub domain: Option<String>,
+    /// Free-text background ("team of 3, legacy Rails app")
+    #[arg(long)]
+    pub context: Option<String>,
+    #[arg(long)]
+    pub team_size: Option<u32>,
+    #[arg(long)]
+    pub deadline_days: Option<u32>,
+    /// Positions to include: quick=3, standard=4, deep=6, or auto to pick from the question
+    #[arg(long, value_parser = ["quick", "standard", "deep", "auto"])]
+    pub depth: Option<String>,
+    /// Comma-separated technologies
+    #[arg(long, value_delimiter = ',')]
+    pub stack: Vec<String>,
+    /// Attach a file as context (repeatable)
+    #[arg(long = "file")]
+    pub files: Vec<String>,
+    /// Scan a repository for context signals
+    #[arg(long)]
+    pub repo: Option<String>,
+    /// Only use principles with one of these comma-separated domain tags
+    #[arg(long, value_delimiter = ',')]
+    pub include_tags: Vec<String>,
+    /// Skip principles with any of these comma-separated domain tags
+    #[arg(long, value_delimiter = ',')]
+    pub exclude_tags: Vec<String>,
+    /// Agent or user asking; ranks with what their outcomes taught
+    #[arg(long)]
+    pub actor: Option<String>,
+    /// Chance each position explores lower-ranked principles (default from config, 0.2)
+    #[arg(long)]
+    pub epsilon: Option<f64>,
+    /// FG-TS optimism constant (default from config, 3.0)
+    #[arg(long)]
+    pub optimism: Option<f64>,
+    /// Largest random score noise in points (default from config, 15)
+    #[arg(long)]
+    pub temperature: Option<f64>,
+    /// Two comma-separated objectives to weigh, e.g. speed,quality (read from "X vs Y" questions otherwise)
+    #[arg(long, value_delimiter = ',')]
+    pub objectives: Vec<String>,
+    /// Also print the decision as a checklist of actions, blind-spot checks and watch conditions
+    #[arg(long)]
+    pub checklist: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct OutcomeArgs {
+    pub decision_id: String,
+    #[arg(long, conflicts_with = "failed")]
+    pub success: bool,
+    #[arg(long, visible_alias = "failure")]
+    pub failed: bool,
+    /// Comma-separated principle IDs that were applied
+    #[arg(long, value_delimiter = ',')]
+    pub principles: Vec<String>,
+    #[arg(long, default_value = "")]
+    pub notes: String,
+    /// JSON context pattern, e.g. '{"domain":"architecture"}'
+    #[arg(long)]
+    pub context: Option<String>,
+    /// Agent or user the outcome belongs to (default: the decision's actor)
+    #[arg(long)]
+    pub actor: Option<String>,
+    /// Where the failure happened: planning, implementation, integration, operations or external
+    #[arg(long, requires = "failed")]
+    pub stage: Option<String>,
+    /// Comma-separated reason codes for the stage, e.g. underestimated,scope-change
+    #[arg(long = "reason", value_delimiter = ',', requires = "stage")]
+    pub reasons: Vec<String>,
+    /// Decision template that was followed
+    #[arg(long, requires = "template_path")]
+    pub template: Option<String>,
+    /// Comma-separated option indices from the template root to the leaf followed, e.g. 0,1,0
+    #[arg(long, value_delimiter = ',', requires = "template")]
+    pub template_path: Vec<String>,
+}
+
+/// Flag spellings from before subcommands existed
+const LEGACY_FLAGS: [&str; 44] = [
+    "validate-prd",
+    "analyze-prd",
+    "template",
+    "blind-spots",
+    "pre-work",
+    "tools",
+    "stats",
+    "benchmark",
+    "analyze",
+    "trends",
+    "mark-event",
+    "thompson",
+    "compute-embeddings",
+    "fetch-model",
+    "outcome",
+    "outcome-import",
+    "outcomes",
+    "encrypt-db",
+    "scrub",
+    "prune",
+    "replay",
+    "forget",
+    "learning-stats",
+    "calibration",
+    "adr",
+    "export-vault",
+    "export-graph",
+    "dashboard",
+    "link-decision",
+    "suggest-outcomes",
+    "hybrid-search",
+    "search-decisions",
+    "serve",
+    "sync-posteriors",
+    "tui",
+    "repl",
+    "worker",
+    "daemon",
+    "counsel",
+    "completions",
+    "pack",
+    "dedupe",
+    "policy",
+    "edges",
+];
+
+/// Rewrite legacy invocations into subcommand form
+///
+/// `--outcome x` → `outcome x`, `--tui` → `tui`, and a bare question
+/// (`100minds "Should we...?"`) → `ask ...`.
+pub fn normalize_legacy_args(mut args: Vec<String>) -> Vec<String> {
+    let Some(first) = args.get(1).cloned() else {
+        return args;
+    };
+
+    if let Some(name) = first.strip_prefix("--") {
+        if LEGACY_FLAGS.contains(&name) {
+            args[1] = name.to_string();
+        }
+        return args;
+    }
+
+    let is_subcommand = Cli::command()
+        .get_subcommands()
+        .any(|c| c.get_name() == first || c.get_all_aliases().any(|a| a == first))
+        || first == "help";
+    if !first.starts_with('-') && !is_subcommand {
+        args.insert(1, "ask".to_string());
+    }
+    args
+}
+
+/// Write a completion script for `shell` to stdout
+pub fn print_completions(shell: Shell) {
+    let mut cmd = Cli::command();
+    clap_complete::generate(shell, &mut cmd, "100minds", &mut std::io::stdout());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        let args: Vec<String> = std::iter::once("100minds")
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect();
+        Cli::try_parse_from(normalize_legacy_args(args)).unwrap()
+    }
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_legacy_forms_still_parse() {
+        match parse(&["--outcome", "d1", "--failed", "--principles", "yagni,kiss"]).command {
+            Some(Command::Outcome(o)) => {
+                assert_eq!(o.decision_id, "d1");
+                assert!(o.failed);
+                assert_eq!(o.principles, vec!["yagni", "kiss"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert!(matches!(parse(&["--serve", "--port=4000"]).command, Some(Command::Serve { port: 4000, .. })));
+        assert!(matches!(parse(&["--tui"]).command, Some(Command::Tui)));
+        assert!(matches!(parse(&["--pack", "list"]).command, Some(Command::Pack { .. })));
+        assert!(matches!(parse(&["Should", "we", "cache?"]).command, Some(Command::Ask { .. })));
+        assert!(parse(&[]).command.is_none());
+
+        let cli = parse(&["counsel", "Split", "it?", "--domain=architecture", "--stack=rust,go", "--json"]);
+        assert_eq!(cli.output_mode(), OutputMode::Json);
+        match cli.command {
+            Some(Command::Counsel(c)) => {
+                assert_eq!(c.question, vec!["Split", "it?"]);
+                assert_eq!(c.domain.as_deref(), Some("architecture"));
+                assert_eq!(c.stack, vec!["rust", "go"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_benchmark_passes_suite_args_through() {
+        match parse(&["--benchmark", "crossval", "10"]).command {
+            Some(Command::Benchmark { suite, args }) => {
+                assert_eq!(suite, "crossval");
+                assert_eq!(args, vec!["10"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
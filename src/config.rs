@@ -0,0 +1,73 @@
+//! config.toml
+//!
+//! Each module owns one table of the config.toml beside wisdom.db
+//! (`[search]`, `[privacy]`, `[daemon]`, ...) and reads it with [`section`],
+//! so a missing file or table means the same thing everywhere: the
+//! section's defaults.
+
+use crate::error::{MindsError, Result};
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+/// The config.toml next to the connection's database (`None` for in-memory ones)
+pub fn path_beside(conn: &Connection) -> Option<PathBuf> {
+    conn.path()
+        .filter(|p| !p.is_empty())
+        .and_then(|p| Path::new(p).parent())
+        .map(|dir| dir.join("config.toml"))
+}
+
+/// Table `name` of the config.toml next to the connection's database
+pub fn section<T: DeserializeOwned + Default>(conn: &Connection, name: &str) -> Result<T> {
+    match path_beside(conn) {
+        Some(path) => section_at(&path, name),
+        None => Ok(T::default()),
+    }
+}
+
+/// Table `name` of the config.toml at `path`; a missing file or table gives the defaults
+pub fn section_at<T: DeserializeOwned + Default>(path: &Path, name: &str) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let invalid = |e: &dyn std::fmt::Display| MindsError::Config(format!("{}: {}", path.display(), e));
+    let mut file: toml::Table = toml::from_str(&std::fs::read_to_string(path)?).map_err(|e| invalid(&e))?;
+    match file.remove(name) {
+        Some(table) => table.try_into().map_err(|e| invalid(&format!("[{}] {}", name, e))),
+        None => Ok(T::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, PartialEq, Deserialize)]
+    #[serde(default)]
+    struct Limits {
+        days: Option<i64>,
+        labels: Vec<String>,
+    }
+
+    #[test]
+    fn test_sections_default_when_missing_and_report_bad_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        assert_eq!(section::<Limits>(&conn, "limits").unwrap(), Limits::default());
+        let memory = Connection::open_in_memory().unwrap();
+        assert_eq!(section::<Limits>(&memory, "limits").unwrap(), Limits::default());
+
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[other]\nx = 1\n\n[limits]\ndays = 30\n").unwrap();
+        assert_eq!(section::<Limits>(&conn, "limits").unwrap(), Limits { days: Some(30), labels: vec![] });
+        assert_eq!(section::<Limits>(&conn, "missing").unwrap(), Limits::default());
+
+        std::fs::write(&path, "[limits]\ndays = \"soon\"\n").unwrap();
+        let err = section::<Limits>(&conn, "limits").unwrap_err().to_string();
+        assert!(err.contains("[limits]"), "{}", err);
+        std::fs::write(&path, "[limits\n").unwrap();
+        assert!(section_at::<Limits>(&path, "limits").is_err());
+    }
+}
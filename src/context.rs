@@ -0,0 +1,318 @@
+//! Repository-Aware Context
+//!
+//! Grounds counsel in the actual codebase instead of the question alone.
+//! "Monolith first" and "extract the hotspots" are both good advice -
+//! which one applies depends on what the repo looks like today.
+//!
+//! Signals are deliberately cheap: one directory walk, line counts,
+//! and a glance at compose/k8s manifests and dependency files.
+
+use crate::types::CounselContext;
+use crate::error::{MindsError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Directories that never contain the project's own source
+const SKIP_DIRS: [&str; 10] = [
+    ".git",
+    "target",
+    "node_modules",
+    "vendor",
+    "dist",
+    "build",
+    ".venv",
+    "venv",
+    "__pycache__",
+    ".next",
+];
+
+/// Files larger than this are treated as generated and skipped
+const MAX_FILE_BYTES: u64 = 1_000_000;
+
+/// Cheap structural signals extracted from a repository
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RepoSignals {
+    /// Non-blank lines of code per language
+    pub languages: HashMap<String, usize>,
+    pub total_loc: usize,
+    pub source_files: usize,
+    pub test_files: usize,
+    /// Test LOC / non-test LOC
+    pub test_to_code_ratio: f64,
+    /// Deployable services found in docker-compose or k8s manifests
+    pub service_count: usize,
+    /// Direct dependencies across Cargo.toml, package.json, requirements.txt, go.mod
+    pub dependency_count: usize,
+}
+
+impl RepoSignals {
+    /// Languages ordered by LOC, largest first
+    pub fn top_languages(&self, n: usize) -> Vec<String> {
+        let mut langs: Vec<_> = self.languages.iter().collect();
+        langs.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        langs.into_iter().take(n).map(|(l, _)| l.clone()).collect()
+    }
+
+    /// One-line summary suitable for counsel notes
+    pub fn summary(&self) -> String {
+        format!(
+            "{} LOC across {} files ({}), {} service(s), {} dependencies, test/code ratio {:.2}",
+            self.total_loc,
+            self.source_files,
+            self.top_languages(3).join(", "),
+            self.service_count,
+            self.dependency_count,
+            self.test_to_code_ratio
+        )
+    }
+
+    /// Feed these signals into a counsel context
+    pub fn apply_to(&self, context: &mut CounselContext) {
+        if context.stack.is_empty() {
+            context.stack = self.top_languages(3);
+        }
+        context.repo = Some(self.clone());
+    }
+}
+
+fn language_for(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?;
+    Some(match ext {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "rb" => "ruby",
+        "php" => "php",
+        "cs" => "csharp",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "hpp" | "cxx" => "cpp",
+        "swift" => "swift",
+        "scala" => "scala",
+        "ex" | "exs" => "elixir",
+        _ => return None,
+    })
+}
+
+fn is_test_path(path: &Path) -> bool {
+    // Leading slash so a repo-relative "tests/x.rs" matches "/tests/"
+    let path_str = format!("/{}", path.to_string_lossy().replace('\\', "/").to_lowercase());
+    let file_name = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    path_str.contains("/tests/")
+        || path_str.contains("/test/")
+        || path_str.contains("/__tests__/")
+        || path_str.contains("/spec/")
+        || file_name.starts_with("test_")
+        || file_name.contains("_test.")
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+}
+
+/// Count services declared under the top-level `services:` key of a compose file
+fn count_compose_services(content: &str) -> usize {
+    let mut in_services = false;
+    let mut count = 0;
+    for line in content.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            in_services = line.trim_end() == "services:";
+        } else if in_services && indent == 2 && line.trim_end().ends_with(':') {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Count workloads in a k8s manifest (may hold several `---` documents)
+fn count_k8s_workloads(content: &str) -> usize {
+    content
+        .lines()
+        .filter(|l| {
+            let l = l.trim();
+            l == "kind: Deployment" || l == "kind: StatefulSet" || l == "kind: DaemonSet"
+        })
+        .count()
+}
+
+/// Count direct dependencies declared in a manifest
+fn count_dependencies(file_name: &str, content: &str) -> usize {
+    match file_name {
+        "Cargo.toml" => {
+            let mut in_deps = false;
+            let mut count = 0;
+            for line in content.lines() {
+                let line = line.trim();
+                if line.starts_with('[') {
+                    in_deps = line.ends_with("dependencies]");
+                } else if in_deps && !line.is_empty() && !line.starts_with('#') && line.contains('=')
+                {
+                    count += 1;
+                }
+            }
+            count
+        }
+        "package.json" => serde_json::from_str::<serde_json::Value>(content)
+            .map(|v| {
+                ["dependencies", "devDependencies"]
+                    .iter()
+                    .filter_map(|k| v.get(*k).and_then(|d| d.as_object()).map(|d| d.len()))
+                    .sum()
+            })
+            .unwrap_or(0),
+        "requirements.txt" => content
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with('-'))
+            .count(),
+        "go.mod" => {
+            let mut in_block = false;
+            let mut count = 0;
+            for line in content.lines() {
+                let line = line.trim();
+                if line.starts_with("require (") {
+                    in_block = true;
+                } else if in_block && line == ")" {
+                    in_block = false;
+                } else if (in_block && !line.is_empty() && !line.starts_with("//"))
+                    || line.starts_with("require ")
+                {
+                    count += 1;
+                }
+            }
+            count
+        }
+        _ => 0,
+    }
+}
+
+/// Scan a repository for cheap structural signals
+pub fn repo_scan(path: &Path) -> Result<RepoSignals> {
+    if !path.is_dir() {
+        return Err(MindsError::Validation(format!("Not a directory: {:?}", path)));
+    }
+
+    let mut signals = RepoSignals::default();
+    let mut test_loc = 0usize;
+    let mut compose_services = 0usize;
+    let mut k8s_workloads = 0usize;
+
+    let walker = walkdir::WalkDir::new(path).into_iter().filter_entry(|e| {
+        !(e.file_type().is_dir()
+            && e.depth() > 0
+            && SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+    });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let file_path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        if entry.metadata().map(|m| m.len()).unwrap_or(0) > MAX_FILE_BYTES {
+            continue;
+        }
+
+        // Manifests
+        let lower_name = file_name.to_lowercase();
+        let is_yaml = lower_name.ends_with(".yml") || lower_name.ends_with(".yaml");
+        if is_yaml || matches!(file_name.as_str(), "Cargo.toml" | "package.json" | "requirements.txt" | "go.mod") {
+            let Ok(content) = std::fs::read_to_string(file_path) else {
+                continue;
+            };
+            if is_yaml {
+                if lower_name.starts_with("docker-compose") || lower_name.starts_with("compose.") {
+                    compose_services += count_compose_services(&content);
+                } else {
+                    k8s_workloads += count_k8s_workloads(&content);
+                }
+            } else {
+                signals.dependency_count += count_dependencies(&file_name, &content);
+            }
+            continue;
+        }
+
+        // Source code
+        let Some(language) = language_for(file_path) else {
+            continue;
+        };
+        let Ok(content) = std::fs::read_to_string(file_path) else {
+            continue;
+        };
+        let loc = content.lines().filter(|l| !l.trim().is_empty()).count();
+
+        *signals.languages.entry(language.to_string()).or_insert(0) += loc;
+        signals.total_loc += loc;
+        signals.source_files += 1;
+
+        if is_test_path(file_path.strip_prefix(path).unwrap_or(file_path)) {
+            signals.test_files += 1;
+            test_loc += loc;
+        }
+    }
+
+    let code_loc = signals.total_loc - test_loc;
+    signals.test_to_code_ratio = if code_loc == 0 {
+        0.0
+    } else {
+        test_loc as f64 / code_loc as f64
+    };
+    signals.service_count = compose_services.max(k8s_workloads);
+
+    Ok(signals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_scan_extracts_signals() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/lib")).unwrap();
+
+        std::fs::write(root.join("src/main.rs"), "fn main() {\n\n    run();\n}\n").unwrap();
+        std::fs::write(root.join("tests/it.rs"), "#[test]\nfn it() {}\n").unwrap();
+        std::fs::write(root.join("node_modules/lib/index.js"), "ignored();\n").unwrap();
+        std::fs::write(
+            root.join("docker-compose.yml"),
+            "version: '3'\nservices:\n  api:\n    image: api\n  worker:\n    image: worker\nvolumes:\n  data:\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"x\"\n\n[dependencies]\nserde = \"1\"\nanyhow = \"1\"\n\n[dev-dependencies]\ntempfile = \"3\"\n",
+        )
+        .unwrap();
+
+        let signals = repo_scan(root).unwrap();
+        assert_eq!(signals.total_loc, 5);
+        assert_eq!(signals.source_files, 2);
+        assert_eq!(signals.test_files, 1);
+        assert!((signals.test_to_code_ratio - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(signals.service_count, 2);
+        assert_eq!(signals.dependency_count, 3);
+        assert_eq!(signals.top_languages(1), vec!["rust".to_string()]);
+
+        let mut context = CounselContext::default();
+        signals.apply_to(&mut context);
+        assert_eq!(context.stack, vec!["rust".to_string()]);
+        assert!(context.repo.is_some());
+
+        assert!(repo_scan(&root.join("missing")).is_err());
+    }
+}
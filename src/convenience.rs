@@ -11,7 +11,7 @@ use crate::db;
 use crate::outcome::{self, OutcomeResult};
 use crate::provenance::Provenance;
 use crate::types::{CounselContext, CounselRequest, CounselResponse};
-use anyhow::Result;
+use crate::error::Result;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -50,6 +50,12 @@ impl ZestyEngine {
         Ok(Self { conn, provenance })
     }
 
+    /// Wrap an open connection and key, e.g. an in-memory database from
+    /// `testing::fixture()`
+    pub fn from_parts(conn: Connection, provenance: Provenance) -> Self {
+        Self { conn, provenance }
+    }
+
     /// Get full counsel with provenance chain
     pub fn counsel(&self, question: &str, domain: Option<&str>) -> Result<CounselResponse> {
         let engine = CounselEngine::new(&self.conn, &self.provenance);
@@ -119,8 +125,12 @@ impl ZestyEngine {
             .flat_map(|p| p.principles_cited.clone())
             .collect();
 
-        // Add challenge principles too
-        ids.extend(response.challenge.principles_cited.clone());
+        // Add challenge principles too, except the generated devil's advocate's
+        // "Socratic Method", which isn't a corpus principle and would fail the
+        // outcome's foreign keys
+        if response.challenge.thinker_id != "_challenge" {
+            ids.extend(response.challenge.principles_cited.clone());
+        }
 
         // Deduplicate
         ids.sort();
@@ -307,12 +317,11 @@ pub struct PrincipleProgress {
 // Helper functions
 
 fn get_thinker_name(conn: &Connection, thinker_id: &str) -> Result<String> {
-    conn.query_row(
+    Ok(conn.query_row(
         "SELECT name FROM thinkers WHERE id = ?1",
         [thinker_id],
         |row| row.get(0),
-    )
-    .map_err(|e| anyhow::anyhow!("Thinker lookup failed: {}", e))
+    )?)
 }
 
 fn category_to_domain(category: &str) -> &'static str {
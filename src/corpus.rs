@@ -0,0 +1,291 @@
+//! Corpus Maintenance
+//!
+//! Coverage analysis flags near-duplicate principles; [`merge_principles`]
+//! folds duplicates into one survivor without losing what was learned
+//! about them:
+//!
+//! - adjustment history moves to the survivor, and the merged principles'
+//!   confidence drift (learned − base) is added to its confidence, within
+//!   the range outcomes keep it in
+//! - Thompson, per-domain Thompson, contextual and actor arms are summed
+//!   (successes and failures, not priors)
+//! - hard negatives are summed per question
+//! - the survivor inherits an embedding if it has none
+//! - merged IDs become aliases, so outcomes recorded later against old
+//!   decisions that cited them land on the survivor
+//!
+//! Merged principles are copied to `archived_principles` before removal.
+
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+/// What a merge moved onto the surviving principle
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeReport {
+    pub kept: String,
+    pub merged: Vec<String>,
+    pub adjustments_moved: usize,
+    pub arms_merged: usize,
+    pub embedding_inherited: bool,
+    pub old_confidence: f64,
+    pub new_confidence: f64,
+}
+
+/// Merge `merge_ids` into `keep_id`
+pub fn merge_principles(conn: &Connection, keep_id: &str, merge_ids: &[String]) -> Result<MergeReport> {
+    crate::eval::thompson::init_thompson_schema(conn)?;
+    let confidence = |id: &str| -> Result<f64> {
+        let confidence: f64 = conn
+            .query_row("SELECT COALESCE(learned_confidence, 0.5) FROM principles WHERE id = ?1", [id], |row| row.get(0))
+            .optional()?
+            .ok_or_else(|| MindsError::NotFound(format!("principle {}", id)))?;
+        // A culled principle's -1 isn't drift, and merging into one would revive it
+        if confidence < 0.0 {
+            return Err(MindsError::Validation(format!("Principle {} is archived", id)));
+        }
+        Ok(confidence)
+    };
+    let old_confidence = confidence(keep_id)?;
+    for (i, id) in merge_ids.iter().enumerate() {
+        if id == keep_id {
+            return Err(MindsError::Validation(format!("Can't merge {} into itself", id)));
+        }
+        if merge_ids[..i].contains(id) {
+            return Err(MindsError::Validation(format!("{} is listed twice", id)));
+        }
+        confidence(id)?;
+    }
+    let has_embeddings: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('principles') WHERE name = 'embedding'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    crate::db::with_savepoint(conn, "merge_principles", || {
+        let mut adjustments_moved = 0;
+        let mut arms_merged = 0;
+        let mut embedding_inherited = false;
+
+        for id in merge_ids {
+            let drift: f64 = conn.query_row(
+                "SELECT COALESCE(learned_confidence, 0.5) - COALESCE(base_confidence, 0.5) FROM principles WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )?;
+            let (floor, ceiling) = crate::outcome::CONFIDENCE_RANGE;
+            conn.execute(
+                "UPDATE principles SET learned_confidence = MIN(?4, MAX(?3, learned_confidence + ?2)) WHERE id = ?1",
+                params![keep_id, drift, floor, ceiling],
+            )?;
+
+            adjustments_moved += conn.execute(
+                "UPDATE framework_adjustments SET principle_id = ?1 WHERE principle_id = ?2",
+                params![keep_id, id],
+            )?;
+
+            arms_merged += conn.execute(
+                "INSERT INTO thompson_arms (principle_id, alpha, beta, pulls)
+                 SELECT ?1, alpha, beta, pulls FROM thompson_arms WHERE principle_id = ?2 AND true
+                 ON CONFLICT(principle_id) DO UPDATE SET
+                     alpha = alpha + excluded.alpha - 1.0,
+                     beta = beta + excluded.beta - 1.0,
+                     pulls = pulls + excluded.pulls,
+                     updated_at = CURRENT_TIMESTAMP",
+                params![keep_id, id],
+            )?;
+            arms_merged += conn.execute(
+                "INSERT INTO thompson_domain_arms (principle_id, domain, alpha, beta)
+                 SELECT ?1, domain, alpha, beta FROM thompson_domain_arms WHERE principle_id = ?2 AND true
+                 ON CONFLICT(principle_id, domain) DO UPDATE SET
+                     alpha = alpha + excluded.alpha - 1.0,
+                     beta = beta + excluded.beta - 1.0,
+                     updated_at = CURRENT_TIMESTAMP",
+                params![keep_id, id],
+            )?;
+            arms_merged += conn.execute(
+                "INSERT INTO contextual_arms (principle_id, domain, alpha, beta, sample_count)
+                 SELECT ?1, domain, alpha, beta, sample_count FROM contextual_arms WHERE principle_id = ?2 AND true
+                 ON CONFLICT(principle_id, domain) DO UPDATE SET
+                     alpha = alpha + excluded.alpha - 1.0,
+                     beta = beta + excluded.beta - 1.0,
+                     sample_count = sample_count + excluded.sample_count,
+                     last_updated = CURRENT_TIMESTAMP",
+                params![keep_id, id],
+            )?;
+            arms_merged += conn.execute(
+                "INSERT INTO actor_arms (actor_id, principle_id, domain, alpha, beta, sample_count)
+                 SELECT actor_id, ?1, domain, alpha, beta, sample_count FROM actor_arms WHERE principle_id = ?2 AND true
+                 ON CONFLICT(actor_id, principle_id, domain) DO UPDATE SET
+                     alpha = alpha + excluded.alpha - 1.0,
+                     beta = beta + excluded.beta - 1.0,
+                     sample_count = sample_count + excluded.sample_count,
+                     last_updated = CURRENT_TIMESTAMP",
+                params![keep_id, id],
+            )?;
+            conn.execute(
+                "INSERT INTO hard_negatives (question_hash, principle_id, failure_count)
+                 SELECT question_hash, ?1, failure_count FROM hard_negatives WHERE principle_id = ?2 AND true
+                 ON CONFLICT(question_hash, principle_id) DO UPDATE SET
+                     failure_count = failure_count + excluded.failure_count",
+                params![keep_id, id],
+            )?;
+            for table in ["thompson_arms", "thompson_domain_arms", "contextual_arms", "actor_arms", "hard_negatives"] {
+                conn.execute(&format!("DELETE FROM {} WHERE principle_id = ?1", table), [id])?;
+            }
+
+            if has_embeddings {
+                embedding_inherited |= conn.execute(
+                    "UPDATE principles SET embedding = (SELECT embedding FROM principles WHERE id = ?2)
+                     WHERE id = ?1 AND embedding IS NULL
+                       AND (SELECT embedding FROM principles WHERE id = ?2) IS NOT NULL",
+                    params![keep_id, id],
+                )? > 0;
+            }
+
+            // Curated relations move to the survivor; any it already has (or to itself) go
+            for column in ["from_id", "to_id"] {
+                conn.execute(
+                    &format!("UPDATE OR IGNORE principle_edges SET {0} = ?1 WHERE {0} = ?2", column),
+                    params![keep_id, id],
+                )?;
+            }
+            conn.execute(
+                "DELETE FROM principle_edges WHERE from_id = ?1 OR to_id = ?1 OR from_id = to_id",
+                params![id],
+            )?;
+
+            // Citations: old decisions keep their signed JSON; the alias redirects
+            conn.execute(
+                "UPDATE principle_aliases SET kept_id = ?1 WHERE kept_id = ?2",
+                params![keep_id, id],
+            )?;
+            conn.execute(
+                "INSERT OR REPLACE INTO principle_aliases (merged_id, kept_id) VALUES (?1, ?2)",
+                params![id, keep_id],
+            )?;
+
+            conn.execute(
+                "INSERT OR REPLACE INTO archived_principles
+                 (id, thinker_id, name, description, domain_tags, application_rule,
+                  anti_pattern, falsification, base_confidence, learned_confidence,
+                  archived_at, cull_reason)
+                 SELECT id, thinker_id, name, description, domain_tags, application_rule,
+                        anti_pattern, falsification, base_confidence, learned_confidence,
+                        datetime('now'), 'merged into ' || ?2
+                 FROM principles WHERE id = ?1",
+                params![id, keep_id],
+            )?;
+            conn.execute("DELETE FROM principles WHERE id = ?1", [id])?;
+        }
+
+        let new_confidence: f64 =
+            conn.query_row("SELECT learned_confidence FROM principles WHERE id = ?1", [keep_id], |row| row.get(0))?;
+        Ok(MergeReport {
+            kept: keep_id.to_string(),
+            merged: merge_ids.to_vec(),
+            adjustments_moved,
+            arms_merged,
+            embedding_inherited,
+            old_confidence,
+            new_confidence,
+        })
+    })
+}
+
+/// Map merged principle IDs to their survivors (others pass through), without duplicates
+pub fn resolve_principle_ids(conn: &Connection, ids: &[String]) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare_cached("SELECT kept_id FROM principle_aliases WHERE merged_id = ?1")?;
+    let mut resolved: Vec<String> = Vec::with_capacity(ids.len());
+    for id in ids {
+        let id = stmt.query_row([id], |row| row.get(0)).optional()?.unwrap_or_else(|| id.clone());
+        if !resolved.contains(&id) {
+            resolved.push(id);
+        }
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_moves_learning_onto_survivor() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('gall', 'John Gall', 'systems');
+             INSERT INTO principles (id, thinker_id, name, description, base_confidence, learned_confidence)
+                 VALUES ('gall-1', 'gall', 'Gall''s Law', 'Complex systems evolve from simple ones', 0.5, 0.6),
+                        ('gall-2', 'gall', 'Start Simple', 'Complex systems evolve from simple ones', 0.5, 0.4);",
+        )
+        .unwrap();
+        crate::outcome::record_outcome(&conn, "d1", true, &["gall-1".to_string()], "", Some(r#"{"domain":"architecture"}"#)).unwrap();
+        crate::outcome::record_outcome(&conn, "d2", false, &["gall-2".to_string()], "", Some(r#"{"domain":"architecture"}"#)).unwrap();
+        crate::db::update_contextual_arm(&conn, "gall-2", "architecture", false).unwrap();
+
+        let report = merge_principles(&conn, "gall-1", &["gall-2".to_string()]).unwrap();
+        assert_eq!(report.adjustments_moved, 1);
+        // The survivor's drift (+0.15 after its success) plus the merged drift (-0.2 after its failure)
+        assert!((report.new_confidence - (report.old_confidence - 0.2)).abs() < 1e-9);
+
+        let (alpha, beta, pulls): (f64, f64, i64) = conn
+            .query_row("SELECT alpha, beta, pulls FROM thompson_arms WHERE principle_id = 'gall-1'", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+            })
+            .unwrap();
+        assert_eq!((alpha, beta, pulls), (2.0, 2.0, 2));
+        let (alpha, beta): (f64, f64) = conn
+            .query_row(
+                "SELECT alpha, beta FROM thompson_domain_arms WHERE principle_id = 'gall-1' AND domain = 'architecture'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((alpha, beta), (2.0, 2.0));
+        let arms: i64 = conn
+            .query_row("SELECT COUNT(*) FROM contextual_arms WHERE principle_id = 'gall-2'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(arms, 0);
+
+        let archived: String = conn
+            .query_row("SELECT cull_reason FROM archived_principles WHERE id = 'gall-2'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(archived, "merged into gall-1");
+
+        // Later outcomes citing the merged ID land on the survivor
+        let result = crate::outcome::record_outcome(&conn, "d3", true, &["gall-2".to_string(), "gall-1".to_string()], "", None).unwrap();
+        assert_eq!(result.principles_adjusted.len(), 1);
+        assert_eq!(result.principles_adjusted[0].principle_id, "gall-1");
+
+        assert!(merge_principles(&conn, "gall-1", &["gall-1".to_string()]).is_err());
+        assert!(merge_principles(&conn, "gall-1", &["gall-2".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_merge_rejects_archived_and_repeated_ids_and_keeps_confidence_in_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('gall', 'John Gall', 'systems');
+             INSERT INTO principles (id, thinker_id, name, description, base_confidence, learned_confidence)
+                 VALUES ('gall-1', 'gall', 'Gall''s Law', 'Complex systems evolve from simple ones', 0.5, 0.9),
+                        ('gall-2', 'gall', 'Start Simple', 'Complex systems evolve from simple ones', 0.5, 0.8),
+                        ('gall-3', 'gall', 'Grow It', 'Complex systems evolve from simple ones', 0.5, -1.0);",
+        )
+        .unwrap();
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        assert!(merge_principles(&conn, "gall-1", &ids(&["gall-3"])).is_err());
+        assert!(merge_principles(&conn, "gall-3", &ids(&["gall-2"])).is_err());
+        assert!(merge_principles(&conn, "gall-1", &ids(&["gall-2", "gall-2"])).is_err());
+        let culled: f64 = conn
+            .query_row("SELECT learned_confidence FROM principles WHERE id = 'gall-3'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(culled, -1.0);
+
+        let report = merge_principles(&conn, "gall-1", &ids(&["gall-2"])).unwrap();
+        assert_eq!(report.new_confidence, crate::outcome::CONFIDENCE_RANGE.1);
+    }
+}
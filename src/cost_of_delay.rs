@@ -0,0 +1,327 @@
+//! Cost of Delay
+//!
+//! "Use weighted scoring" is advice; a sequence with a price on it is
+//! guidance. When the feature-prioritization template fires, the counsel
+//! response carries a cost-of-delay worksheet: the fields to fill in for
+//! each candidate and the formulas the server evaluates once they are
+//! filled (Reinertsen's CD3, as in SAFe's WSJF):
+//!
+//! - cost of delay = value + time criticality + risk reduction, per week
+//! - CD3 = cost of delay / duration; highest first minimizes total delay cost
+//! - delay cost of an item = its cost of delay × the week it ships
+//! - opportunity cost of doing an item first = its duration × the cost of
+//!   delay of everything else
+//!
+//! Filled items come back in as `delay_items` on `counsel` or as the items
+//! of `evaluate_cost_of_delay`, and the worksheet's result compares the CD3
+//! sequence with the order given.
+
+use crate::error::{MindsError, Result};
+use crate::templates;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Template whose match adds a worksheet to counsel
+pub const TEMPLATE_ID: &str = "feature-prioritization";
+
+/// One candidate, as the agent fills it in
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DelayItem {
+    /// Feature or piece of work
+    pub name: String,
+    /// Value lost per week it isn't live (revenue, savings), in any one currency
+    pub value_per_week: f64,
+    /// Extra loss per week from deadlines or a closing window (default 0)
+    #[serde(default)]
+    pub time_criticality_per_week: f64,
+    /// Loss avoided per week by the risk it retires or the options it opens (default 0)
+    #[serde(default)]
+    pub risk_reduction_per_week: f64,
+    /// Weeks to build it
+    pub duration_weeks: f64,
+}
+
+/// Arguments of `evaluate_cost_of_delay`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CostOfDelayRequest {
+    /// The candidates, in the order currently planned
+    pub items: Vec<DelayItem>,
+}
+
+/// A field the agent fills for each item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorksheetField {
+    pub name: String,
+    pub unit: String,
+    pub required: bool,
+    pub description: String,
+}
+
+/// A formula the server evaluates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Formula {
+    pub name: String,
+    pub expression: String,
+    pub meaning: String,
+}
+
+/// One item with its formulas evaluated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoredItem {
+    pub name: String,
+    pub cost_of_delay: f64,
+    pub cd3: f64,
+    /// Position in the CD3 sequence, from 1
+    pub rank: usize,
+    /// Week it ships when built in the CD3 sequence
+    pub ships_week: f64,
+    /// Cost of delay × ships_week
+    pub delay_cost: f64,
+    /// What everything else loses while this is built first
+    pub opportunity_cost: f64,
+}
+
+/// The CD3 sequence against the order given
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorksheetResult {
+    /// Highest CD3 first
+    pub sequence: Vec<ScoredItem>,
+    pub delay_cost_as_given: f64,
+    pub delay_cost_sequenced: f64,
+    /// As given minus sequenced
+    pub savings: f64,
+    pub recommendation: String,
+}
+
+/// Fields to fill, formulas, and the result once items are filled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Worksheet {
+    pub template: String,
+    pub fields: Vec<WorksheetField>,
+    pub formulas: Vec<Formula>,
+    pub items: Vec<DelayItem>,
+    /// None until items are filled
+    pub result: Option<WorksheetResult>,
+    pub guidance: String,
+}
+
+fn fields() -> Vec<WorksheetField> {
+    let field = |name: &str, unit: &str, required: bool, description: &str| WorksheetField {
+        name: name.to_string(),
+        unit: unit.to_string(),
+        required,
+        description: description.to_string(),
+    };
+    vec![
+        field("name", "text", true, "Feature or piece of work"),
+        field("value_per_week", "currency/week", true, "Revenue or savings lost each week it isn't live"),
+        field("time_criticality_per_week", "currency/week", false, "Extra loss per week from deadlines"),
+        field("risk_reduction_per_week", "currency/week", false, "Loss avoided per week by the risk it retires"),
+        field("duration_weeks", "weeks", true, "Weeks to build it"),
+    ]
+}
+
+fn formulas() -> Vec<Formula> {
+    let formula = |name: &str, expression: &str, meaning: &str| Formula {
+        name: name.to_string(),
+        expression: expression.to_string(),
+        meaning: meaning.to_string(),
+    };
+    vec![
+        formula(
+            "cost_of_delay",
+            "value_per_week + time_criticality_per_week + risk_reduction_per_week",
+            "What each week of waiting costs",
+        ),
+        formula("cd3", "cost_of_delay / duration_weeks", "Cost of delay divided by duration; build the highest first"),
+        formula("delay_cost", "cost_of_delay * ships_week", "What the item loses before it ships"),
+        formula(
+            "opportunity_cost",
+            "duration_weeks * sum(cost_of_delay of the other items)",
+            "What everything else loses while this is built first",
+        ),
+    ]
+}
+
+impl DelayItem {
+    fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(MindsError::Validation(message));
+        if self.name.trim().is_empty() {
+            return invalid("delay item name must not be blank".to_string());
+        }
+        let per_week = [self.value_per_week, self.time_criticality_per_week, self.risk_reduction_per_week];
+        if per_week.iter().any(|v| !v.is_finite() || *v < 0.0) {
+            return invalid(format!("{}: per-week values must be zero or more", self.name));
+        }
+        if !self.duration_weeks.is_finite() || self.duration_weeks <= 0.0 {
+            return invalid(format!("{}: duration_weeks must be more than zero", self.name));
+        }
+        Ok(())
+    }
+
+    pub fn cost_of_delay(&self) -> f64 {
+        self.value_per_week + self.time_criticality_per_week + self.risk_reduction_per_week
+    }
+}
+
+/// "NAME:VALUE_PER_WEEK:WEEKS[:TIME_CRITICALITY[:RISK_REDUCTION]]", as the CLI takes it
+impl std::str::FromStr for DelayItem {
+    type Err = MindsError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parts: Vec<&str> = s.split(':').map(str::trim).collect();
+        if !(3..=5).contains(&parts.len()) {
+            return Err(MindsError::Validation(format!(
+                "expected NAME:VALUE_PER_WEEK:WEEKS[:TIME_CRITICALITY[:RISK_REDUCTION]], got {:?}",
+                s
+            )));
+        }
+        let number = |i: usize| -> Result<f64> {
+            parts.get(i).map_or(Ok(0.0), |p| {
+                p.parse().map_err(|_| MindsError::Validation(format!("{:?} is not a number in {:?}", p, s)))
+            })
+        };
+        let item = DelayItem {
+            name: parts[0].to_string(),
+            value_per_week: number(1)?,
+            duration_weeks: number(2)?,
+            time_criticality_per_week: number(3)?,
+            risk_reduction_per_week: number(4)?,
+        };
+        item.validate()?;
+        Ok(item)
+    }
+}
+
+/// Total delay cost of building items in this order
+fn delay_cost(items: &[&DelayItem]) -> f64 {
+    let mut week = 0.0;
+    items
+        .iter()
+        .map(|item| {
+            week += item.duration_weeks;
+            item.cost_of_delay() * week
+        })
+        .sum()
+}
+
+/// Sequence filled items by CD3 and price the sequence against the order given
+pub fn evaluate(items: &[DelayItem]) -> Result<WorksheetResult> {
+    if items.is_empty() {
+        return Err(MindsError::Validation("cost of delay needs at least one item".to_string()));
+    }
+    for item in items {
+        item.validate()?;
+    }
+    let total_cost_of_delay: f64 = items.iter().map(DelayItem::cost_of_delay).sum();
+    let mut ordered: Vec<&DelayItem> = items.iter().collect();
+    // Stable: equal CD3 keeps the order given
+    ordered.sort_by(|a, b| (b.cost_of_delay() / b.duration_weeks).total_cmp(&(a.cost_of_delay() / a.duration_weeks)));
+
+    let mut week = 0.0;
+    let sequence: Vec<ScoredItem> = ordered
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            week += item.duration_weeks;
+            let cost_of_delay = item.cost_of_delay();
+            ScoredItem {
+                name: item.name.clone(),
+                cost_of_delay,
+                cd3: cost_of_delay / item.duration_weeks,
+                rank: i + 1,
+                ships_week: week,
+                delay_cost: cost_of_delay * week,
+                opportunity_cost: item.duration_weeks * (total_cost_of_delay - cost_of_delay),
+            }
+        })
+        .collect();
+    let delay_cost_as_given = delay_cost(&items.iter().collect::<Vec<_>>());
+    let delay_cost_sequenced = delay_cost(&ordered);
+    let first = &sequence[0];
+    let mut recommendation = format!(
+        "Build {} first: CD3 {:.1} ({:.0}/week of delay over {} weeks).",
+        first.name, first.cd3, first.cost_of_delay, ordered[0].duration_weeks
+    );
+    let savings = delay_cost_as_given - delay_cost_sequenced;
+    if savings > 0.0 {
+        recommendation.push_str(&format!(
+            " The CD3 order costs {:.0} in delay against {:.0} in the order given, saving {:.0}.",
+            delay_cost_sequenced, delay_cost_as_given, savings
+        ));
+    } else {
+        recommendation.push_str(" The order given is already the CD3 order.");
+    }
+    Ok(WorksheetResult { sequence, delay_cost_as_given, delay_cost_sequenced, savings, recommendation })
+}
+
+/// A worksheet for these items: blank (fields and formulas only) when none are filled
+pub fn worksheet(items: &[DelayItem]) -> Result<Worksheet> {
+    let result = if items.is_empty() { None } else { Some(evaluate(items)?) };
+    let guidance = match &result {
+        Some(result) => result.recommendation.clone(),
+        None => "Fill name, value_per_week and duration_weeks (optionally time_criticality_per_week and \
+                 risk_reduction_per_week) for each candidate, in the order currently planned, and pass them as \
+                 delay_items to counsel or as items to evaluate_cost_of_delay to have them sequenced by CD3."
+            .to_string(),
+    };
+    Ok(Worksheet {
+        template: TEMPLATE_ID.to_string(),
+        fields: fields(),
+        formulas: formulas(),
+        items: items.to_vec(),
+        result,
+        guidance,
+    })
+}
+
+/// The worksheet for a counsel question: when the prioritization template
+/// fires or items were filled in, None otherwise
+pub fn for_question(question: &str, items: &[DelayItem]) -> Result<Option<Worksheet>> {
+    let fires = templates::match_templates(question).iter().any(|(t, _)| t.id == TEMPLATE_ID);
+    if !fires && items.is_empty() {
+        return Ok(None);
+    }
+    worksheet(items).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_items_are_sequenced_by_cd3_and_priced() {
+        let item = |name: &str, value: f64, risk: f64, weeks: f64| DelayItem {
+            name: name.to_string(),
+            value_per_week: value,
+            risk_reduction_per_week: risk,
+            duration_weeks: weeks,
+            ..Default::default()
+        };
+        let items = [
+            item("SSO", 2000.0, 0.0, 4.0),
+            item("CSV export", 500.0, 500.0, 1.0),
+            item("Audit log", 900.0, 0.0, 3.0),
+        ];
+
+        let result = evaluate(&items).unwrap();
+        let names: Vec<&str> = result.sequence.iter().map(|s| s.name.as_str()).collect();
+        // CD3: CSV export 1000, SSO 500, Audit log 300
+        assert_eq!(names, ["CSV export", "SSO", "Audit log"]);
+        assert_eq!((result.sequence[1].cd3, result.sequence[1].ships_week), (500.0, 5.0));
+        // SSO first holds up 1000 + 900 a week for 4 weeks
+        assert_eq!(result.sequence[1].opportunity_cost, 4.0 * 1900.0);
+        // As given: 2000×4 + 1000×5 + 900×8; sequenced: 1000×1 + 2000×5 + 900×8
+        assert_eq!((result.delay_cost_as_given, result.delay_cost_sequenced), (20200.0, 18200.0));
+        assert_eq!(result.savings, 2000.0);
+        assert!(result.recommendation.starts_with("Build CSV export first"));
+
+        let blank = for_question("Which feature should we prioritize and build first?", &[]).unwrap().unwrap();
+        assert!(blank.result.is_none() && blank.fields.iter().any(|f| f.name == "duration_weeks"));
+        assert!(for_question("Should we rewrite the billing system?", &[]).unwrap().is_none());
+        assert_eq!("CSV export:500:1:0:500".parse::<DelayItem>().unwrap(), items[1]);
+        assert!("SSO:lots:4".parse::<DelayItem>().is_err());
+        let zero = [item("Nothing", 100.0, 0.0, 0.0)];
+        assert!(matches!(evaluate(&zero), Err(MindsError::Validation(_))));
+    }
+}
@@ -7,40 +7,510 @@
 //! - Popper: Only falsifiable advice is useful
 //! - Feynman: If it can't be explained simply, it's not understood
 
+use crate::action_prompts::ActionPrompts;
+use crate::calibration;
+use crate::cost_of_delay;
 use crate::db::{self, PrincipleMatch};
+use crate::depth;
+use crate::embeddings::{self, SemanticEngine};
+use crate::history;
+use crate::eval::bandit::{policy_engine, BetaPosterior, FeelGoodIndex, PolicyEngine};
+use crate::eval::thompson::{pool_domain_arm, FGTSConfig, LearningConfig};
+use crate::language::{LanguageConfig, QueryNormalizer};
+use crate::options::{self, EvaluateOptionsRequest, OptionsEvaluation};
+use crate::policy::{Policy, PolicyReport};
+use crate::principle_graph::PrincipleGraph;
 use crate::provenance::Provenance;
+use crate::relevance::{self, QueryFeatures};
+use crate::search::{self, RerankReport, Reranker, SearchConfig, RERANK_CANDIDATES};
+use crate::snapshot::CorpusSnapshot;
+use crate::templates;
+use crate::thread::{self, FollowupRequest};
+use crate::tradeoff;
+use crate::urgency::{Urgency, UrgencyClassifier, UrgencyConfig};
 use crate::types::*;
-use anyhow::Result;
-use rusqlite::Connection;
+use crate::error::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Core pipeline time (keyword search, positions, provenance, storage) an
+/// optional step must leave room for
+const CORE_RESERVE_MS: u64 = 30;
+
+/// Score added to pinned principles, after the keyword cap
+const PINNED_BOOST: f64 = 30.0;
+
+/// Most score the reranker's top pick gains at position selection; lower
+/// picks gain proportionally less
+const RERANK_BOOST: f64 = 15.0;
+
+/// Pseudo-observations the global arm is worth when pooled with an actor's
+/// own arm; an actor's outcomes outweigh it once there are more of them
+const ACTOR_PRIOR_STRENGTH: f64 = 10.0;
+
+/// Learned value a cold arm (no outcomes in the domain) is scored with: the
+/// full exploration bonus
+const COLD_ARM_VALUE: f64 = 1.0;
+
+/// FG-TS parameters for counsel's contextual boost (the optimism constant
+/// comes from `ExplorationLog::optimism`)
+const COUNSEL_FGTS: FGTSConfig = FGTSConfig {
+    optimism_constant: 3.0, // Increased from 2.0 for stronger exploration
+    bonus_decay: 0.98,      // Slower decay (was 0.95) to maintain exploration longer
+    epsilon: 0.1,
+    epsilon_threshold: 10,
+    bonus_cap: 1.0, // Cap at 1.0 (was 0.5)
+};
+
+/// Rough cost of each optional step, used to decide what fits a budget
+const STEP_COST_MS: [(&str, u64); 8] = [
+    ("decomposition", 100),
+    ("rerank", 50),
+    ("semantic_search", 15),
+    ("deep_scoring", 40),
+    ("template_matching", 30),
+    ("query_expansion", 25),
+    ("domain_search", 10),
+    ("similar_decisions", 20),
+];
+
+/// Latency budget for one counsel call (unlimited unless `start`ed)
+#[derive(Default)]
+struct LatencyBudget {
+    deadline: Cell<Option<Instant>>,
+    skipped: RefCell<Vec<String>>,
+}
+
+impl LatencyBudget {
+    fn start(&self, budget_ms: Option<u64>) {
+        self.deadline.set(budget_ms.map(|ms| Instant::now() + Duration::from_millis(ms)));
+        self.skipped.borrow_mut().clear();
+    }
+
+    /// Whether `step` still fits; a step that doesn't is recorded as skipped
+    fn allows(&self, step: &str) -> bool {
+        let Some(deadline) = self.deadline.get() else {
+            return true;
+        };
+        let cost = STEP_COST_MS.iter().find(|(name, _)| *name == step).map_or(0, |(_, ms)| *ms);
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining >= Duration::from_millis(cost + CORE_RESERVE_MS) {
+            return true;
+        }
+        let mut skipped = self.skipped.borrow_mut();
+        if !skipped.iter().any(|s| s == step) {
+            skipped.push(step.to_string());
+        }
+        false
+    }
+
+    /// Steps skipped since `start`; the budget is lifted again
+    fn finish(&self) -> Vec<String> {
+        self.deadline.set(None);
+        std::mem::take(&mut *self.skipped.borrow_mut())
+    }
+}
 
 /// The counsel engine that generates adversarial debates
 pub struct CounselEngine<'a> {
     conn: &'a Connection,
     provenance: &'a Provenance,
+    budget: LatencyBudget,
+    /// Corpus reads come from here instead of SQLite when set
+    snapshot: Option<Arc<CorpusSnapshot>>,
+    policy: Policy,
+    /// Curated relations between principles, for ordering and notes
+    graph: PrincipleGraph,
+    /// What `policy` did during the current counsel call
+    policy_report: RefCell<PolicyReport>,
+    /// Who the current counsel call is for (`CounselContext::actor_id`)
+    actor: RefCell<Option<String>>,
+    learning: LearningConfig,
+    /// Exploration settings of the current counsel call, and its picks
+    exploration: RefCell<ExplorationLog>,
+    urgency: Arc<UrgencyClassifier>,
+    language: Arc<QueryNormalizer>,
+    /// Reorders the top keyword candidates (`[search] rerank`)
+    reranker: Option<Arc<dyn Reranker>>,
+    /// Reranker boosts by principle ID, and the stage's report, for the current call
+    rerank_boost: RefCell<HashMap<String, f64>>,
+    rerank_report: RefCell<Option<RerankReport>>,
+    /// ACTION lines: curated per principle, else keyword rules
+    action_prompts: ActionPrompts,
+    /// Thread position and parent content hash when the current call is a follow-up
+    followup: RefCell<Option<(thread::ThreadInfo, String)>>,
+    /// Adds semantic neighbours to the keyword candidates; `None` runs FTS5 alone
+    semantic: Option<&'static Mutex<SemanticEngine>>,
+    capabilities: embeddings::Capabilities,
 }
 
 impl<'a> CounselEngine<'a> {
     pub fn new(conn: &'a Connection, provenance: &'a Provenance) -> Self {
-        Self { conn, provenance }
+        let mut learning = LearningConfig::beside(conn).unwrap_or_else(|e| {
+            tracing::warn!("Learning config: {}", e);
+            LearningConfig::default()
+        });
+        if let Err(e) = policy_engine(&learning, COUNSEL_FGTS) {
+            tracing::warn!("Learning config: {}", e);
+            learning.policy = LearningConfig::default().policy;
+        }
+        Self {
+            conn,
+            provenance,
+            budget: LatencyBudget::default(),
+            snapshot: None,
+            policy: Policy::load(conn).unwrap_or_default(),
+            graph: PrincipleGraph::load(conn).unwrap_or_default(),
+            policy_report: RefCell::default(),
+            actor: RefCell::default(),
+            exploration: RefCell::new(exploration_log(&learning, None)),
+            learning,
+            urgency: Arc::new(UrgencyClassifier::new(UrgencyConfig::beside(conn).unwrap_or_else(|e| {
+                tracing::warn!("Urgency config: {}", e);
+                UrgencyConfig::default()
+            }))),
+            language: Arc::new(QueryNormalizer::new(&LanguageConfig::beside(conn).unwrap_or_else(|e| {
+                tracing::warn!("Language config: {}", e);
+                LanguageConfig::default()
+            }))),
+            reranker: SearchConfig::beside(conn)
+                .map_err(|e| tracing::warn!("Search config: {}", e))
+                .ok()
+                .and_then(|config| search::shared_reranker(&config)),
+            rerank_boost: RefCell::default(),
+            rerank_report: RefCell::default(),
+            followup: RefCell::default(),
+            action_prompts: ActionPrompts::load(conn).unwrap_or_else(|e| {
+                tracing::warn!("Action prompts: {}", e);
+                ActionPrompts::seeded()
+            }),
+            semantic: embeddings::shared_engine(conn),
+            capabilities: embeddings::capabilities(conn),
+        }
+    }
+
+    /// Classify urgency with this classifier (e.g. one with an embedder plugged in)
+    pub fn with_urgency(mut self, classifier: Arc<UrgencyClassifier>) -> Self {
+        self.urgency = classifier;
+        self
+    }
+
+    /// Normalize non-English questions with this normalizer (e.g. one with a translator)
+    pub fn with_normalizer(mut self, normalizer: Arc<QueryNormalizer>) -> Self {
+        self.language = normalizer;
+        self
+    }
+
+    /// Rerank the top keyword candidates with this reranker
+    pub fn with_reranker(mut self, reranker: Arc<dyn Reranker>) -> Self {
+        self.reranker = Some(reranker);
+        self
+    }
+
+    /// Read the corpus from an in-memory snapshot; SQLite is then only
+    /// used to store decisions
+    pub fn with_snapshot(mut self, snapshot: Arc<CorpusSnapshot>) -> Self {
+        self.snapshot = Some(snapshot);
+        self
+    }
+
+    fn search_principles(&self, query: &str, limit: usize) -> Result<Vec<PrincipleMatch>> {
+        match &self.snapshot {
+            Some(snapshot) => Ok(snapshot.search(query, limit)),
+            None => db::search_principles(self.conn, query, limit),
+        }
+    }
+
+    /// Principles nearest the query by embedding; a failure only costs the
+    /// semantic candidates, not the counsel call
+    fn semantic_principles(&self, engine: &Mutex<SemanticEngine>, query: &str, limit: usize) -> Vec<PrincipleMatch> {
+        let found = engine.lock().unwrap_or_else(|e| e.into_inner()).knn(self.conn, query, limit);
+        let found = match found {
+            Ok(found) => found,
+            Err(e) => {
+                tracing::warn!("Semantic search: {}", e);
+                return Vec::new();
+            }
+        };
+        found
+            .into_iter()
+            .filter_map(|m| match &self.snapshot {
+                Some(snapshot) => snapshot.principle(&m.principle_id).map(|p| p.principle.clone()),
+                None => self
+                    .conn
+                    .query_row(
+                        "SELECT id, thinker_id, name, description, learned_confidence FROM principles WHERE id = ?1",
+                        [&m.principle_id],
+                        |row| {
+                            Ok(PrincipleMatch {
+                                id: row.get(0)?,
+                                thinker_id: row.get(1)?,
+                                name: row.get(2)?,
+                                description: row.get(3)?,
+                                confidence: row.get(4)?,
+                                relevance_score: m.similarity as f64,
+                            })
+                        },
+                    )
+                    .ok(),
+            })
+            .collect()
+    }
+
+    fn principles_by_domain(&self, domain: &str) -> Result<Vec<PrincipleMatch>> {
+        match &self.snapshot {
+            Some(snapshot) => Ok(snapshot.by_domain(domain)),
+            None => db::get_principles_by_domain(self.conn, domain),
+        }
+    }
+
+    /// (name, description, application rule) of a principle
+    fn principle_text(&self, id: &str) -> Option<(String, String, String)> {
+        if let Some(snapshot) = &self.snapshot {
+            return snapshot.principle(id).map(|p| {
+                (p.principle.name.clone(), p.principle.description.clone(), p.application_rule.clone())
+            });
+        }
+        self.conn
+            .query_row(
+                "SELECT name, description, COALESCE(application_rule, '') FROM principles WHERE id = ?1",
+                [id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)),
+            )
+            .ok()
+    }
+
+    /// Domain tags of a principle
+    fn principle_tags(&self, id: &str) -> Vec<String> {
+        if let Some(snapshot) = &self.snapshot {
+            return snapshot.principle(id).map(|p| p.domain_tags.clone()).unwrap_or_default();
+        }
+        self.conn
+            .query_row("SELECT domain_tags FROM principles WHERE id = ?1", [id], |row| row.get::<_, Option<String>>(0))
+            .ok()
+            .flatten()
+            .and_then(|tags| serde_json::from_str(&tags).ok())
+            .unwrap_or_default()
+    }
+
+    /// A principle by ID
+    fn principle_by_id(&self, id: &str) -> Option<PrincipleMatch> {
+        if let Some(snapshot) = &self.snapshot {
+            return snapshot.principle(id).map(|p| p.principle.clone());
+        }
+        self.conn
+            .query_row(
+                "SELECT id, thinker_id, name, description, learned_confidence FROM principles WHERE id = ?1",
+                [id],
+                |row| {
+                    Ok(PrincipleMatch {
+                        id: row.get(0)?,
+                        thinker_id: row.get(1)?,
+                        name: row.get(2)?,
+                        description: row.get(3)?,
+                        confidence: row.get(4)?,
+                        relevance_score: 0.0,
+                    })
+                },
+            )
+            .ok()
+    }
+
+    /// Drop banned principles and add any pinned ones that weren't matched
+    fn apply_policy(&self, principles: &[PrincipleMatch]) -> Vec<PrincipleMatch> {
+        if self.policy.is_empty() {
+            return principles.to_vec();
+        }
+        let mut report = self.policy_report.borrow_mut();
+        let mut kept: Vec<PrincipleMatch> = Vec::with_capacity(principles.len());
+        for principle in principles {
+            if self.policy.is_banned(&principle.id) {
+                PolicyReport::note(&mut report.banned, &principle.id);
+            } else {
+                kept.push(principle.clone());
+            }
+        }
+        for id in self.policy.pinned_ids() {
+            if !kept.iter().any(|p| p.id == id) {
+                kept.extend(self.principle_by_id(&id));
+            }
+            PolicyReport::note(&mut report.pinned, &id);
+        }
+        kept
+    }
+
+    /// (alpha, beta, sample_count) of a principle's arm in a domain, partially
+    /// pooled with its arms in other domains
+    fn contextual_arm(&self, principle_id: &str, domain: &str) -> Option<(f64, f64, i64)> {
+        let strength = self.learning.domain_pooling;
+        match &self.snapshot {
+            Some(snapshot) => pool_domain_arm(snapshot.arms(principle_id), domain, strength),
+            None => {
+                let arms = self
+                    .conn
+                    .prepare_cached("SELECT domain, alpha, beta, sample_count FROM contextual_arms WHERE principle_id = ?1")
+                    .and_then(|mut stmt| {
+                        stmt.query_map([principle_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+                            .collect::<rusqlite::Result<Vec<_>>>()
+                    })
+                    .unwrap_or_default();
+                pool_domain_arm(&arms, domain, strength)
+            }
+        }
+    }
+
+    /// Contextual samples for a principle across all domains
+    fn citation_count(&self, principle_id: &str) -> Option<i64> {
+        match &self.snapshot {
+            Some(snapshot) => Some(snapshot.citations(principle_id)),
+            None => self
+                .conn
+                .query_row(
+                    "SELECT COALESCE(SUM(sample_count), 0) FROM contextual_arms WHERE principle_id = ?1",
+                    rusqlite::params![principle_id],
+                    |row| row.get(0),
+                )
+                .ok(),
+        }
+    }
+
+    fn thinker_reliability(&self, thinker_id: &str) -> f64 {
+        match &self.snapshot {
+            Some(snapshot) => snapshot.reliability(thinker_id),
+            None => db::get_thinker_reliability(self.conn, thinker_id),
+        }
     }
 
     /// Generate adversarial counsel for a decision question
+    ///
+    /// With `context.latency_budget_ms`, optional steps that no longer fit
+    /// are skipped and the response is marked `degraded`.
     pub fn counsel(&self, request: &CounselRequest) -> Result<CounselResponse> {
-        // 1. Find relevant principles for this question
-        let principles = self.find_relevant_principles(request)?;
+        self.budget.start(request.context.latency_budget_ms);
+        let result = self.counsel_within_budget(request);
+        let skipped = self.budget.finish();
+        let mut response = result?;
+        if !skipped.is_empty() {
+            response.degraded = true;
+            response.skipped_steps = skipped;
+            // Re-store so the decision record shows what was skipped,
+            // redacted the same way store_decision redacted the first copy
+            let (_, _, counsel_json) = crate::redact::scrub_decision(
+                self.conn,
+                &response.decision_id,
+                &request.question,
+                &serde_json::to_string(&request.context)?,
+                &serde_json::to_string(&response)?,
+            )?;
+            self.conn.execute(
+                "UPDATE decisions SET counsel_json = ?2 WHERE id = ?1",
+                params![response.decision_id, counsel_json],
+            )?;
+        }
+        Ok(response)
+    }
 
-        // 2. Generate positions from different perspectives
-        let positions = self.generate_positions(request, &principles)?;
+    /// Refine a previous decision: re-counsel its question with the
+    /// refinement added to its context, as a follow-up in the parent's thread
+    pub fn counsel_followup(&self, request: &FollowupRequest) -> Result<CounselResponse> {
+        let parent = thread::parent(self.conn, request)?;
+        let followup = CounselRequest {
+            question: parent.question,
+            context: thread::refine_context(&parent.context, &parent.thread.refinement),
+            decision_id: request.decision_id.clone(),
+        };
+        self.followup.replace(Some((parent.thread, parent.content_hash)));
+        let response = self.counsel(&followup);
+        self.followup.take();
+        response
+    }
+
+    /// Score concrete options against the principles relevant to them,
+    /// instead of debating an open question
+    pub fn evaluate_options(&self, request: &EvaluateOptionsRequest) -> Result<OptionsEvaluation> {
+        request.validate()?;
+        let search = CounselRequest {
+            question: request.search_text(),
+            context: CounselContext { domain: request.domain.clone(), ..Default::default() },
+            decision_id: None,
+        };
+        let mut principles = self.find_relevant_principles(&search)?;
+        principles.retain(|p| !self.policy.is_banned(&p.id));
+        options::evaluate(request, &principles)
+    }
+
+    fn counsel_within_budget(&self, original: &CounselRequest) -> Result<CounselResponse> {
+        // 0. Retrieval runs on an English rendering of the question; the
+        //    response, provenance and stored decision keep the original
+        let query = self.language.normalize(&original.question);
+        let translated;
+        let request = if query.text != original.question {
+            translated = CounselRequest {
+                question: query.text.clone(),
+                ..original.clone()
+            };
+            &translated
+        } else {
+            original
+        };
+        // 0b. `auto` depth is settled once, from the whole question
+        let resolved;
+        let depth = depth::resolve(request);
+        let request = match &depth {
+            Some((auto, _)) => {
+                resolved = auto.clone();
+                &resolved
+            }
+            None => request,
+        };
+        self.policy_report.take();
+        self.rerank_boost.borrow_mut().clear();
+        self.rerank_report.take();
+        *self.actor.borrow_mut() = request.context.actor_id.clone().filter(|a| !a.is_empty());
+        *self.exploration.borrow_mut() = exploration_log(&self.learning, request.context.exploration.as_ref());
+        // 1-2. Compound questions are split and each part counseled on its own
+        let sub_questions = if self.budget.allows("decomposition") {
+            decompose_question(&request.question)
+        } else {
+            vec![request.question.clone()]
+        };
+        let mut parts = Vec::new();
+        let positions = if sub_questions.len() > 1 {
+            let mut all_positions = Vec::new();
+            for question in sub_questions {
+                let sub_request = CounselRequest {
+                    question: question.clone(),
+                    context: request.context.clone(),
+                    decision_id: None,
+                };
+                let sub_positions = self.counsel_positions(&sub_request)?;
+                let challenge = self.generate_challenge(&sub_request, &sub_positions)?;
+                all_positions.extend(sub_positions.iter().cloned());
+                parts.push(CounselPart {
+                    question,
+                    positions: sub_positions,
+                    challenge,
+                });
+            }
+            all_positions
+        } else {
+            self.counsel_positions(request)?
+        };
 
         // 3. Always generate a devil's advocate challenge
         let challenge = self.generate_challenge(request, &positions)?;
 
-        // 4. Create provenance for this decision
-        let provenance_info = self.create_provenance(request, &positions, &challenge)?;
+        // 4. Create provenance for this decision; a follow-up chains from its parent
+        let followup = self.followup.take();
+        let parent_hash = followup.as_ref().map(|(_, hash)| hash.clone());
+        let provenance_info = self.create_provenance(original, &positions, &challenge, parent_hash)?;
 
         // 5. Build the response (pass through explicit decision_id if provided)
         let mut response = CounselResponse::new(
-            request.question.clone(),
+            original.question.clone(),
             positions,
             challenge,
             provenance_info,
@@ -48,118 +518,150 @@ impl<'a> CounselEngine<'a> {
         );
 
         // 6. Detect urgency for swarm integration
-        response.urgency_adjustment = self.detect_urgency(request, &response.positions);
+        response.urgency = self.detect_urgency(request, &response.positions);
+        response.urgency_adjustment = response.urgency.as_ref().map(|u| u.level.as_str().to_string());
+
+        // 6b. Flag known tensions between the recommended principles
+        if self.budget.allows("template_matching") {
+            response.tensions = self.detect_tensions(&response.positions);
+        }
+        // 6b'. Two-objective questions: which objective each cited principle serves
+        response.tradeoff = self.tradeoff(request, &response);
+        // 6b''. Prioritization questions: a cost-of-delay worksheet, evaluated once filled
+        response.cost_of_delay = cost_of_delay::for_question(&request.question, &request.context.delay_items)?;
+        // 6c. Curated relations among the cited principles ("do X before Y")
+        response.relations = self.graph.notes(&response.positions);
+        // 6d. Past decisions like this one, and how they turned out
+        if self.budget.allows("similar_decisions") {
+            response.similar_past_decisions = self.similar_past_decisions(&request.question, &response.decision_id);
+        }
+        response.parts = parts;
+        response.policy = self.policy_report.take();
+        response.exploration = Some(self.exploration.replace(exploration_log(&self.learning, None)));
+        response.language = Some(query);
+        response.rerank = self.rerank_report.take();
+        response.capabilities = Some(self.capabilities.clone());
+        response.depth = depth.map(|(_, choice)| choice);
+        response.thread = followup.map(|(info, _)| info);
 
         // 7. Store the decision in the database
-        self.store_decision(&response, request)?;
+        self.store_decision(&response, original)?;
 
         Ok(response)
     }
 
-    /// Detect urgency based on question content and position analysis
-    /// Returns "escalate" | "defer" | None
-    fn detect_urgency(
-        &self,
-        request: &CounselRequest,
-        positions: &[CounselPosition],
-    ) -> Option<String> {
-        let q = request.question.to_lowercase();
-
-        // ESCALATE signals - indicates decision needs human review or senior attention
-        let escalate_keywords = [
-            "security",
-            "vulnerable",
-            "breach",
-            "hack",
-            "data loss",
-            "corruption",
-            "production down",
-            "breaking change",
-            "backwards compat",
-            "legal",
-            "compliance",
-            "gdpr",
-            "pii",
-            "money",
-            "billing",
-            "payment",
-            "deadline",
-            "blocker",
-            "critical",
-        ];
+    fn similar_past_decisions(&self, question: &str, decision_id: &str) -> Vec<history::SimilarDecision> {
+        let mut engine = self.semantic.map(|m| m.lock().unwrap_or_else(|e| e.into_inner()));
+        history::similar_decisions(self.conn, engine.as_deref_mut(), question, decision_id, history::SIMILAR_DECISIONS)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Similar past decisions: {}", e);
+                Vec::new()
+            })
+    }
 
-        let escalate_score: usize = escalate_keywords
+    /// Tag the cited principles by objective, for "X vs Y" questions
+    fn tradeoff(&self, request: &CounselRequest, response: &CounselResponse) -> Option<tradeoff::Tradeoff> {
+        let objectives = &request.context.objectives;
+        if objectives.len() != 2 && tradeoff::objectives_in(&request.question).is_none() {
+            return None;
+        }
+        let cited: Vec<tradeoff::CitedPrinciple> = response
+            .positions
             .iter()
-            .filter(|kw| q.contains(*kw))
-            .count();
-
-        // DEFER signals - indicates decision can wait for more information
-        let defer_keywords = [
-            "future",
-            "eventually",
-            "someday",
-            "maybe",
-            "nice to have",
-            "phase 2",
-            "later",
-            "considering",
-            "thinking about",
-            "exploring",
-            "research",
-            "spike",
-            "poc",
-            "prototype",
-        ];
+            .chain(std::iter::once(&response.challenge))
+            .flat_map(|p| p.principles_cited.iter().map(move |id| (p, id)))
+            .filter_map(|(p, id)| {
+                let (name, description, _) = self.principle_text(id)?;
+                Some(tradeoff::CitedPrinciple {
+                    id: id.clone(),
+                    name,
+                    description,
+                    thinker: p.thinker.clone(),
+                    confidence: p.confidence,
+                })
+            })
+            .collect();
+        tradeoff::analyze(&request.question, objectives, &cited, &response.tensions)
+    }
 
-        let defer_score: usize = defer_keywords.iter().filter(|kw| q.contains(*kw)).count();
+    /// Find template-encoded tensions between principles cited across positions
+    fn detect_tensions(&self, positions: &[CounselPosition]) -> Vec<ResponseTension> {
+        // (principle name, thinker citing it)
+        let mut cited: Vec<(String, String)> = Vec::new();
+        for position in positions {
+            for principle_id in &position.principles_cited {
+                let name = self
+                    .principle_text(principle_id)
+                    .map_or_else(|| principle_id.clone(), |(name, _, _)| name);
+                cited.push((name, position.thinker.clone()));
+            }
+        }
 
-        // Analyze position confidence spread
-        let confidences: Vec<f64> = positions.iter().map(|p| p.confidence).collect();
-        let avg_confidence = if !confidences.is_empty() {
-            confidences.iter().sum::<f64>() / confidences.len() as f64
-        } else {
-            0.5
-        };
+        let mut tensions: Vec<ResponseTension> = Vec::new();
+        for (template_id, tension) in templates::all_tensions() {
+            let side_a = cited
+                .iter()
+                .find(|(name, _)| templates::principle_name_matches(&tension.principle_a, name));
+            let side_b = cited.iter().find(|(name, _)| {
+                templates::principle_name_matches(&tension.principle_b, name)
+                    && side_a.is_some_and(|(a, _)| a != name)
+            });
 
-        // Low confidence + high stakes = escalate
-        if avg_confidence < 0.5 && escalate_score >= 1 {
-            return Some("escalate".to_string());
+            if let (Some((name_a, thinker_a)), Some((name_b, thinker_b))) = (side_a, side_b) {
+                let duplicate = tensions
+                    .iter()
+                    .any(|t| &t.principle_a == name_a && &t.principle_b == name_b);
+                if !duplicate {
+                    tensions.push(ResponseTension {
+                        principle_a: name_a.clone(),
+                        principle_b: name_b.clone(),
+                        thinker_a: thinker_a.clone(),
+                        thinker_b: thinker_b.clone(),
+                        when_to_pick_a: tension.when_to_pick_a,
+                        when_to_pick_b: tension.when_to_pick_b,
+                        source_template: template_id,
+                    });
+                }
+            }
         }
 
-        // High confidence + escalate keywords = still escalate (stakes matter)
-        if escalate_score >= 2 {
-            return Some("escalate".to_string());
-        }
+        tensions
+    }
 
-        // Defer keywords present = defer
-        if defer_score >= 2 {
-            return Some("defer".to_string());
-        }
+    /// Positions for a single (non-compound) question
+    fn counsel_positions(&self, request: &CounselRequest) -> Result<Vec<CounselPosition>> {
+        // Find relevant principles for this question
+        let principles = self.find_relevant_principles(request)?;
 
-        // Check for conflicting positions (FOR and AGAINST with similar confidence)
-        let has_for = positions.iter().any(|p| p.stance == Stance::For);
-        let has_against = positions.iter().any(|p| p.stance == Stance::Against);
+        // Generate positions from different perspectives
+        let mut positions = self.generate_positions(request, &principles)?;
 
-        if has_for && has_against {
-            let for_confidence: f64 = positions
-                .iter()
-                .filter(|p| p.stance == Stance::For)
-                .map(|p| p.confidence)
-                .sum::<f64>();
-            let against_confidence: f64 = positions
-                .iter()
-                .filter(|p| p.stance == Stance::Against)
-                .map(|p| p.confidence)
-                .sum::<f64>();
-
-            // Near-equal confidence = contentious decision, escalate
-            let diff = (for_confidence - against_confidence).abs();
-            if diff < 0.2 && (for_confidence + against_confidence) > 1.0 {
-                return Some("escalate".to_string());
+        // Correct stated confidences if a calibration model is active
+        let calibrator = match &self.snapshot {
+            Some(snapshot) => snapshot.calibrator().cloned(),
+            None => calibration::load_active_calibrator(self.conn).ok().flatten(),
+        };
+        if let Some(calibrator) = calibrator {
+            for position in &mut positions {
+                position.raw_confidence = Some(position.confidence);
+                position.confidence = calibrator.apply(position.confidence);
             }
         }
 
-        None
+        // Reconcile the top FOR and AGAINST into a conditional recommendation
+        if let Some(synthesis) = self.synthesize_positions(&positions) {
+            positions.push(synthesis);
+        }
+
+        // Prerequisites before the positions that presume them
+        self.graph.order(&mut positions);
+
+        Ok(positions)
+    }
+
+    /// Escalate/defer call for the question and its positions
+    fn detect_urgency(&self, request: &CounselRequest, positions: &[CounselPosition]) -> Option<Urgency> {
+        self.urgency.classify(&request.question, positions)
     }
 
     /// Counterfactual simulation - what would we recommend without certain principles?
@@ -181,7 +683,14 @@ impl<'a> CounselEngine<'a> {
             .filter(|p| !excluded_principles.contains(&p.id))
             .collect();
 
-        // 3. Generate positions from remaining principles
+        // 3. Generate positions from remaining principles, at the depth the original used
+        let request = &CounselRequest {
+            context: CounselContext {
+                depth: original.depth.as_ref().map_or(request.context.depth, |choice| choice.depth),
+                ..request.context.clone()
+            },
+            ..request.clone()
+        };
         let alternative_positions =
             self.build_positions_from_principles(request, &filtered_principles)?;
 
@@ -219,12 +728,9 @@ impl<'a> CounselEngine<'a> {
         request: &CounselRequest,
         principles: &[PrincipleMatch],
     ) -> Result<Vec<CounselPosition>> {
+        let principles = &self.apply_policy(principles);
         let mut positions = Vec::new();
-        let num_positions = match request.context.depth {
-            CounselDepth::Quick => 3,
-            CounselDepth::Standard => 4,
-            CounselDepth::Deep => 6,
-        };
+        let num_positions = request.context.depth.positions();
 
         // Alternate stances for balance
         let stances = [
@@ -238,14 +744,7 @@ impl<'a> CounselEngine<'a> {
 
         for (i, principle) in principles.iter().take(num_positions).enumerate() {
             let stance = stances[i % stances.len()];
-            let thinker_name = self
-                .conn
-                .query_row(
-                    "SELECT name FROM thinkers WHERE id = ?1",
-                    [&principle.thinker_id],
-                    |row| row.get::<_, String>(0),
-                )
-                .unwrap_or_else(|_| principle.thinker_id.clone());
+            let thinker_name = self.get_thinker_name(&principle.thinker_id)?;
 
             let position = CounselPosition {
                 thinker: thinker_name,
@@ -257,6 +756,7 @@ impl<'a> CounselEngine<'a> {
                 ),
                 principles_cited: vec![principle.id.clone()],
                 confidence: principle.confidence,
+                raw_confidence: None,
                 falsifiable_if: Some(format!(
                     "This {} is {} if the {} principle doesn't apply to this context",
                     if stance == Stance::For {
@@ -278,50 +778,119 @@ impl<'a> CounselEngine<'a> {
         Ok(positions)
     }
 
-    /// Find principles relevant to the question
-    fn find_relevant_principles(&self, request: &CounselRequest) -> Result<Vec<PrincipleMatch>> {
+    /// Find principles relevant to the question (candidates, before scoring)
+    pub fn find_relevant_principles(&self, request: &CounselRequest) -> Result<Vec<PrincipleMatch>> {
         let mut all_matches = Vec::new();
 
         // FIRST: Direct keyword search on question (highest relevance)
-        let question_matches = db::search_principles(self.conn, &request.question, 20)?;
+        let question_matches = self.search_principles(&request.question, 20)?;
         all_matches.extend(question_matches);
 
+        // Semantic neighbours the keywords missed, when the model is available
+        if let Some(engine) = self.semantic.filter(|_| self.budget.allows("semantic_search")) {
+            all_matches.extend(self.semantic_principles(engine, &request.question, 10));
+        }
+
         // SECOND: Expand with semantic synonyms for common patterns
-        let expanded_query = self.expand_query_keywords(&request.question);
-        if expanded_query != request.question {
-            let expanded_matches = db::search_principles(self.conn, &expanded_query, 10)?;
-            all_matches.extend(expanded_matches);
+        if self.budget.allows("query_expansion") {
+            let expanded_query = self.expand_query_keywords(&request.question);
+            if expanded_query != request.question {
+                let expanded_matches = self.search_principles(&expanded_query, 10)?;
+                all_matches.extend(expanded_matches);
+            }
         }
 
-        // SECOND: Domain-based search
-        let detected_domains = self.detect_domains(&request.question);
-        for domain in &detected_domains {
-            let domain_matches = db::get_principles_by_domain(self.conn, domain)?;
-            all_matches.extend(domain_matches);
+        // SECOND: Domain-based search (supplied context counts as signal too)
+        if self.budget.allows("domain_search") {
+            let detected_domains =
+                self.detect_domains(&format!("{} {}", request.question, request.context.text()));
+            for domain in &detected_domains {
+                let domain_matches = self.principles_by_domain(domain)?;
+                all_matches.extend(domain_matches);
+            }
         }
 
         // User-specified domain
         if let Some(domain) = &request.context.domain {
-            let domain_matches = db::get_principles_by_domain(self.conn, domain)?;
+            let domain_matches = self.principles_by_domain(domain)?;
             all_matches.extend(domain_matches);
         }
 
+        // Included tags are searched directly so a narrow scope still has candidates
+        for tag in &request.context.include_tags {
+            all_matches.extend(self.principles_by_domain(tag)?);
+        }
+
         // Deduplicate by principle ID
         all_matches.sort_by(|a, b| a.id.cmp(&b.id));
         all_matches.dedup_by(|a, b| a.id == b.id);
+        // Culled principles (confidence -1) are archived, not cited
+        all_matches.retain(|p| p.confidence >= 0.0);
+
+        // Tag filters (stored with the decision's context, so the scope is reproducible)
+        let context = &request.context;
+        if !context.include_tags.is_empty() || !context.exclude_tags.is_empty() {
+            all_matches.retain(|p| context.admits_tags(&self.principle_tags(&p.id)));
+        }
 
         // Score each principle by relevance to the question
-        for principle in &mut all_matches {
-            principle.relevance_score =
-                self.score_principle_relevance(&request.question, principle);
+        let deep = self.budget.allows("deep_scoring");
+        let query = QueryFeatures::new(&request.question);
+        let learned = self.learned_values(&query, &all_matches);
+        for (principle, learned) in all_matches.iter_mut().zip(learned) {
+            principle.relevance_score = self.score_principle_relevance(&query, principle, learned);
+            if deep {
+                principle.relevance_score += self.score_context_relevance(&request.context, principle);
+            }
         }
 
         // Sort by relevance score (highest first)
         all_matches.sort_by(|a, b| b.relevance_score.partial_cmp(&a.relevance_score).unwrap());
 
+        if let Some(reranker) = self.reranker.as_deref().filter(|_| self.budget.allows("rerank")) {
+            self.rerank(reranker, &request.question, &mut all_matches);
+        }
+
         Ok(all_matches)
     }
 
+    /// Reorder the top candidates by the reranker and remember a boost for
+    /// each, so position selection (which rescores) keeps its judgement
+    fn rerank(&self, reranker: &dyn Reranker, question: &str, matches: &mut [PrincipleMatch]) {
+        let candidates = RERANK_CANDIDATES.min(matches.len());
+        let top = &mut matches[..candidates];
+        if top.is_empty() {
+            return;
+        }
+        let texts: Vec<String> = top.iter().map(|p| format!("{}. {}", p.name, p.description)).collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let (order, report) = match search::rerank_order(reranker, question, &texts) {
+            Ok(reranked) => reranked,
+            Err(e) => {
+                tracing::warn!("Rerank: {}", e);
+                return;
+            }
+        };
+        let reordered: Vec<PrincipleMatch> = order.iter().map(|&i| top[i].clone()).collect();
+        let mut boosts = self.rerank_boost.borrow_mut();
+        for (rank, principle) in reordered.iter().enumerate() {
+            let boost = RERANK_BOOST * (1.0 - rank as f64 / reordered.len() as f64);
+            let entry = boosts.entry(principle.id.clone()).or_default();
+            *entry = entry.max(boost);
+        }
+        top.clone_from_slice(&reordered);
+
+        // Sub-questions each rerank; the report covers them all
+        let mut total = self.rerank_report.borrow_mut();
+        match total.as_mut() {
+            Some(total) => {
+                total.candidates += report.candidates;
+                total.latency_ms += report.latency_ms;
+            }
+            None => *total = Some(report),
+        }
+    }
+
     /// Expand query with semantic synonyms for common patterns
     fn expand_query_keywords(&self, question: &str) -> String {
         let q = question.to_lowercase();
@@ -585,402 +1154,150 @@ impl<'a> CounselEngine<'a> {
         domains
     }
 
-    /// Score a principle's relevance to the question
-    fn score_principle_relevance(&self, question: &str, principle: &PrincipleMatch) -> f64 {
-        let q_lower = question.to_lowercase();
-
-        // Important keywords from question (longer words more meaningful)
-        let q_words: Vec<&str> = q_lower
-            .split(|c: char| !c.is_alphanumeric())
-            .filter(|w| w.len() > 3) // Only meaningful words
-            .collect();
-
-        let p_lower = principle.description.to_lowercase();
-        let name_lower = principle.name.to_lowercase();
+    /// A principle's contextual arm in a domain, pooled across domains and
+    /// personalized by the asking actor's own outcomes
+    fn learned_arm(&self, principle_id: &str, domain: &str) -> Option<(f64, f64, i64)> {
+        let global_arm = self.contextual_arm(principle_id, domain);
+        let actor_arm = self
+            .actor
+            .borrow()
+            .as_deref()
+            .and_then(|actor| db::get_actor_arm(self.conn, actor, principle_id, domain));
+        match actor_arm {
+            Some(actor_arm) => Some(pool_actor_arm(global_arm, actor_arm)),
+            None => global_arm,
+        }
+    }
 
-        let mut score = 0.0;
+    /// Note how a position's principle was picked
+    fn note_selection(&self, principle_id: &str, strategy: &str, propensity: f64) {
+        self.exploration.borrow_mut().selections.push(ExplorationSelection {
+            principle_id: principle_id.to_string(),
+            strategy: strategy.to_string(),
+            propensity: Some(propensity),
+        });
+    }
 
-        // Check each question word - use stem matching (first 4+ chars)
-        for word in &q_words {
-            let stem = if word.len() > 5 {
-                &word[..word.len() - 2]
-            } else {
-                word
-            };
+    /// What the learning policy makes of each principle's arm in the query's
+    /// domain (None for a cold arm with no data)
+    fn learned_values(&self, query: &QueryFeatures, principles: &[PrincipleMatch]) -> Vec<Option<f64>> {
+        let arms: Vec<Option<BetaPosterior>> = principles
+            .iter()
+            .map(|p| {
+                self.learned_arm(&p.id, query.domain())
+                    .map(|(alpha, beta, samples)| BetaPosterior::from_arm(alpha, beta, samples))
+            })
+            .collect();
+        let known: Vec<BetaPosterior> = arms.iter().flatten().cloned().collect();
+        let fgts = FGTSConfig { optimism_constant: self.exploration.borrow().optimism, ..COUNSEL_FGTS };
+        let bandit: Box<dyn PolicyEngine> =
+            policy_engine(&self.learning, fgts.clone()).unwrap_or_else(|_| Box::new(FeelGoodIndex { config: fgts }));
+        let mut values = bandit.sample(&known, &mut rand::thread_rng()).into_iter();
+        arms.iter()
+            .map(|arm| {
+                let arm = arm.as_ref()?;
+                let evidence = (arm.alpha + arm.beta - 2.0).max(0.0);
+                Some(gate_learned_value(values.next()?, evidence, self.learning.min_samples))
+            })
+            .collect()
+    }
 
-            // Stem match in description (handles focus/focused, build/building)
-            if p_lower.contains(stem) {
-                score += 3.0;
-            }
-            // Stem match in name (highest value)
-            if name_lower.contains(stem) {
-                score += 5.0;
+    /// Score a principle's relevance to the question, given its learned value
+    pub fn score_principle_relevance(&self, query: &QueryFeatures, principle: &PrincipleMatch, learned: Option<f64>) -> f64 {
+        let mut score = relevance::keyword_score(query, &relevance::features(principle));
+
+        // CONTEXTUAL BANDIT BOOST
+        // The learning policy's value of the principle's arm in the detected
+        // domain: estimated success rate plus exploration bonus (FG-TS by
+        // default, which solves the orphan/cold-start problem by giving
+        // undersampled principles a chance)
+        match learned {
+            // Scale each 0-1 part to 0-15 points
+            Some(value) => score += value * 15.0,
+            None => {
+                // COLD ARM: No data for this principle in this domain
+                // Give maximum exploration bonus to discover effectiveness
+                score += COLD_ARM_VALUE * 15.0; // Increased from 5.0 for aggressive orphan exploration
             }
         }
 
-        // Boost high-value principle types (these are gold)
-        let high_value_keywords = [
-            "80/20",
-            "focus",
-            "lean",
-            "fear",
-            "compound",
-            "eliminate",
-            "pareto",
-            "yagni",
-            "simplest",
-            "overengineer",
-            "speculative",
-        ];
-        for kw in high_value_keywords {
-            if name_lower.contains(kw) || p_lower.contains(kw) {
-                score += 4.0; // Strong boost for known-good frameworks
-            }
-        }
+        // THINKER RELIABILITY: thinkers with a failure-heavy track record count less
+        score * self.thinker_reliability(&principle.thinker_id)
+    }
 
-        // Boost architecture-specific terms (Sam Newman, Fowler, etc.)
-        let arch_keywords = [
-            "microservices",
-            "monolith",
-            "database",
-            "service",
-            "distributed",
-            "migration",
-            "bounded",
-            "aggregate",
-            "cqrs",
-            "event sourcing",
-            "strangler",
-            "circuit breaker",
-            "failure",
-            "legacy",
-            "rewrite",
-            "incremental",
-            "deploy",
-            "resilience",
-            "cache",
-            "caching",
-            "premature",
-            "optimization",
-            "latency",
-            "throughput",
-            "scale",
-            "simple",
-            "simplicity",
-            "complexity",
-            "yagni",
-            "needless",
-        ];
-        for kw in arch_keywords {
-            if name_lower.contains(kw) {
-                score += 6.0; // Very strong boost for architecture principles
-            }
-            if p_lower.contains(kw) {
-                score += 3.0;
-            }
-        }
+    /// Boost principles that fit the supplied context (team size, deadline, stack, notes)
+    /// e.g. "team of 3, deadline in 2 weeks" should surface Brooks's Law and scope cutting
+    fn score_context_relevance(&self, context: &CounselContext, principle: &PrincipleMatch) -> f64 {
+        let name_lower = principle.name.to_lowercase();
+        let text = format!("{} {}", name_lower, principle.description.to_lowercase());
+        let mentions = |kws: &[&str]| kws.iter().any(|kw| text.contains(kw));
 
-        // Performance/optimization specific boosts (Knuth, Gregg, etc.)
-        let perf_keywords = [
-            "premature",
-            "optimization",
-            "fast",
-            "slow",
-            "performance",
-            "measure",
-            "profile",
-            "bottleneck",
-            "efficient",
-            "speed",
-            "flame",
-            "latency",
-            "throughput",
-        ];
-        let question_is_perf = q_lower.contains("slow")
-            || q_lower.contains("fast")
-            || q_lower.contains("performance")
-            || q_lower.contains("optimize");
-        for kw in perf_keywords {
-            if name_lower.contains(kw) {
-                score += if question_is_perf { 12.0 } else { 3.0 };
-            }
-            if p_lower.contains(kw) {
-                score += if question_is_perf { 6.0 } else { 2.0 };
-            }
-        }
-        // Extra boost for "Profile Before Optimizing" on performance questions
-        if question_is_perf && (name_lower.contains("profile") || name_lower.contains("premature"))
-        {
-            score += 15.0;
-        }
+        let mut score = 0.0;
 
-        // Testing/TDD specific boosts (Kent Beck, Feathers, etc.)
-        let test_keywords = [
-            "test",
-            "tdd",
-            "red-green",
-            "test-first",
-            "mock",
-            "stub",
-            "coverage",
-            "unit",
-            "integration",
-            "pyramid",
-            "isolation",
-        ];
-        let question_mentions_test = q_lower.contains("test");
-        // Detect TDD-specific questions (not just any test mention)
-        let question_is_tdd =
-            q_lower.contains("before") && q_lower.contains("after") && q_lower.contains("test");
-        for kw in test_keywords {
-            if name_lower.contains(kw) {
-                score += if question_mentions_test { 10.0 } else { 2.0 };
-            }
-            if p_lower.contains(kw) {
-                score += if question_mentions_test { 5.0 } else { 1.0 };
-            }
-        }
-        // HUGE boost for TDD/Test-First principles on TDD questions
-        if question_is_tdd
-            && (name_lower.contains("tdd")
-                || name_lower.contains("test-first")
-                || name_lower.contains("test first")
-                || name_lower.contains("red-green"))
-        {
-            score += 30.0; // Override other signals for explicit TDD questions
-        }
+        let team_size = context.effective_team_size();
+        let deadline_days = context.effective_deadline_days();
+        let small_team = team_size.is_some_and(|n| n <= 5);
+        let tight_deadline = deadline_days.is_some_and(|d| d <= 21);
 
-        // Legacy code / tangled code specific boosts (Feathers, seams, etc.)
-        let legacy_keywords = [
-            "legacy",
-            "seam",
-            "tangled",
-            "breaks",
-            "brittle",
-            "fragile",
-            "coupling",
-            "dependency",
-            "working effectively",
-            "characterization",
-        ];
-        let question_is_legacy = q_lower.contains("tangled")
-            || q_lower.contains("breaks")
-            || q_lower.contains("legacy")
-            || q_lower.contains("old code")
-            || q_lower.contains("every change");
-        for kw in legacy_keywords {
-            if name_lower.contains(kw) {
-                score += if question_is_legacy { 15.0 } else { 2.0 };
-            }
-            if p_lower.contains(kw) {
-                score += if question_is_legacy { 8.0 } else { 1.0 };
-            }
-        }
-        // Michael Feathers' principles are gold for legacy code
-        if question_is_legacy
-            && (name_lower.contains("feathers")
-                || p_lower.contains("seam")
-                || name_lower.contains("legacy")
-                || p_lower.contains("working effectively"))
-        {
-            score += 20.0;
+        // Small team under time pressure: adding people or scope is the classic trap
+        if small_team && tight_deadline && mentions(&["brooks", "adding people", "late project", "manpower"]) {
+            score += 12.0;
         }
-
-        // Refactoring/code cleanup specific boosts
-        let refactor_keywords = [
-            "refactor",
-            "messy",
-            "cleanup",
-            "clean",
-            "spaghetti",
-            "improve",
-            "incremental design",
-            "technical debt",
-        ];
-        let question_is_refactor = q_lower.contains("refactor")
-            || q_lower.contains("messy")
-            || q_lower.contains("cleanup")
-            || q_lower.contains("clean up")
-            || q_lower.contains("before adding");
-        for kw in refactor_keywords {
-            if name_lower.contains(kw) {
-                score += if question_is_refactor { 15.0 } else { 2.0 };
-            }
-            if p_lower.contains(kw) {
-                score += if question_is_refactor { 8.0 } else { 1.0 };
-            }
+        if tight_deadline && mentions(&["scope", "mvp", "simplest", "80/20", "yagni", "timebox", "cut"]) {
+            score += 6.0;
         }
-        // Kent Beck and Ward Cunningham are authorities for refactoring
-        if question_is_refactor
-            && (name_lower.contains("incremental")
-                || name_lower.contains("debt")
-                || p_lower.contains("incremental")
-                || p_lower.contains("tech debt")
-                || p_lower.contains("technical debt"))
+        // Large teams: coordination cost dominates
+        if team_size.is_some_and(|n| n >= 20)
+            && mentions(&["communication", "conway", "coordination", "team topolog", "two-pizza"])
         {
-            score += 25.0; // Strong boost for refactoring-related principles
+            score += 8.0;
         }
 
-        // Match question keywords to principle name/description (exact terms)
-        for word in &q_words {
-            if name_lower.contains(word) || p_lower.contains(word) {
-                score += 4.0; // Strong match on exact question terms
+        // Ground architecture advice in what the codebase actually looks like
+        if let Some(repo) = &context.repo {
+            if repo.service_count <= 1
+                && repo.total_loc < 100_000
+                && mentions(&["monolith", "premature decomposition", "yagni", "simplest"])
+            {
+                score += 8.0;
             }
-        }
-
-        // Project management / team scaling specific boosts
-        let pm_keywords = [
-            "late",
-            "deadline",
-            "team",
-            "people",
-            "adding",
-            "hire",
-            "staff",
-            "communication",
-            "overhead",
-            "brooks",
-            "mythical",
-        ];
-        for kw in pm_keywords {
-            if name_lower.contains(kw) || p_lower.contains(kw) {
-                score += 5.0;
+            if repo.service_count >= 5
+                && mentions(&["distributed", "microservice", "service boundar", "conway", "observability"])
+            {
+                score += 6.0;
             }
-        }
-
-        // Extra boost for matching question keywords in principle name (most relevant)
-        for word in &q_words {
-            if name_lower.contains(word) {
-                score += 3.0; // Additional name match boost
+            if repo.total_loc >= 100_000
+                && mentions(&["strangler", "hotspot", "incremental", "legacy", "extract"])
+            {
+                score += 6.0;
             }
-        }
-
-        // Database/migration specific boosts
-        let db_keywords = [
-            "database",
-            "migrate",
-            "migration",
-            "oracle",
-            "postgres",
-            "mysql",
-            "nosql",
-            "sql",
-            "schema",
-            "query",
-            "data model",
-        ];
-        let question_mentions_db = q_lower.contains("database")
-            || q_lower.contains("oracle")
-            || q_lower.contains("postgres")
-            || q_lower.contains("migrate");
-        for kw in db_keywords {
-            if name_lower.contains(kw) || p_lower.contains(kw) {
-                score += if question_mentions_db { 8.0 } else { 2.0 };
+            if repo.source_files > 0
+                && repo.test_to_code_ratio < 0.1
+                && mentions(&["test", "characterization", "safety net"])
+            {
+                score += 5.0;
             }
-        }
-
-        // Build vs buy specific boosts
-        let build_buy_keywords = [
-            "build",
-            "buy",
-            "vendor",
-            "custom",
-            "off-the-shelf",
-            "integrate",
-            "tco",
-            "total cost",
-            "maintenance",
-            "saas",
-            "third-party",
-            "hosted",
-            "managed",
-        ];
-        // "build...or use X" is the same as "build vs buy"
-        let question_is_build_buy = (q_lower.contains("build")
-            && (q_lower.contains("buy") || q_lower.contains("use ")))
-            || q_lower.contains("vendor")
-            || q_lower.contains("custom")
-            || q_lower.contains("hosted")
-            || q_lower.contains("managed")
-            || (q_lower.contains("our own") && q_lower.contains("or "));
-
-        // HUGE boost for principle literally named "Build vs Buy"
-        if question_is_build_buy && (name_lower.contains("build") && name_lower.contains("buy")) {
-            score += 50.0; // This is THE principle for this question
-        }
-
-        for kw in build_buy_keywords {
-            if name_lower.contains(kw) || p_lower.contains(kw) {
-                score += if question_is_build_buy { 8.0 } else { 2.0 };
+            if repo.dependency_count >= 100 && mentions(&["dependenc", "vendor", "lindy"]) {
+                score += 4.0;
             }
         }
 
-        // Technical debt / rewrite specific boosts
-        let debt_keywords = [
-            "technical debt",
-            "debt",
-            "rewrite",
-            "refactor",
-            "legacy",
-            "strangler",
-            "incremental",
-            "migration",
-            "modernize",
-        ];
-        let question_is_debt = q_lower.contains("debt")
-            || q_lower.contains("rewrite")
-            || q_lower.contains("refactor")
-            || q_lower.contains("legacy");
-        for kw in debt_keywords {
-            if name_lower.contains(kw) || p_lower.contains(kw) {
-                score += if question_is_debt { 8.0 } else { 2.0 };
-            }
-        }
-
-        // CONTEXTUAL THOMPSON SAMPLING BOOST
-        // Query domain-specific confidence from contextual_arms table
-        let detected_domain = if question_mentions_test {
-            "testing"
-        } else if question_is_build_buy {
-            "architecture"
-        } else if question_is_debt {
-            "practices"
-        } else if question_mentions_db {
-            "architecture"
-        } else {
-            "entrepreneurship"
-        };
+        // Stack terms named in the principle
+        let stack_hits = context
+            .stack
+            .iter()
+            .filter(|tech| tech.len() > 2 && text.contains(&tech.to_lowercase()))
+            .count();
+        score += (stack_hits.min(3) as f64) * 4.0;
 
-        // Get contextual confidence for this principle in the detected domain
-        if let Ok((alpha, beta, sample_count)) = self.conn.query_row::<(f64, f64, i64), _, _>(
-            "SELECT alpha, beta, sample_count FROM contextual_arms
-             WHERE principle_id = ?1 AND domain = ?2",
-            rusqlite::params![&principle.id, detected_domain],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
-        ) {
-            // Boost score by contextual confidence (0-1 range, scale to 0-15)
-            let ctx_conf = alpha / (alpha + beta);
-            score += ctx_conf * 15.0;
-
-            // FEEL-GOOD THOMPSON SAMPLING (FG-TS) EXPLORATION BONUS
-            // Per NeurIPS 2025 (arXiv 2507.15290): 25% improvement over vanilla TS
-            // Formula: bonus = (c / sqrt(α + β)) * decay^pulls
-            // This solves the orphan/cold-start problem by giving undersampled principles a chance
-            let n = alpha + beta;
-            let pulls = sample_count as u32;
-            let optimism_constant = 3.0; // Increased from 2.0 for stronger exploration
-            let bonus_decay: f64 = 0.98; // Slower decay (was 0.95) to maintain exploration longer
-            let raw_bonus = optimism_constant / n.sqrt();
-            let decayed_bonus = raw_bonus * bonus_decay.powi(pulls as i32);
-            let fg_ts_bonus = decayed_bonus.min(1.0); // Cap at 1.0 (was 0.5)
-
-            // Apply FG-TS bonus scaled to score range - STRONGER exploration
-            score += fg_ts_bonus * 15.0; // Scale 0-1.0 to 0-15 points (was 0-5)
-        } else {
-            // COLD ARM: No data for this principle in this domain
-            // Give maximum exploration bonus to discover effectiveness
-            let cold_arm_bonus = 15.0; // Increased from 5.0 for aggressive orphan exploration
-            score += cold_arm_bonus;
+        // Free-text notes and snippets that echo the principle name
+        let context_text = context.text().to_lowercase();
+        if !context_text.is_empty() {
+            let name_hits = name_lower
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| w.len() > 4 && context_text.contains(*w))
+                .count();
+            score += (name_hits as f64 * 2.0).min(10.0);
         }
 
         score
@@ -997,28 +1314,40 @@ impl<'a> CounselEngine<'a> {
         let mut rng = rand::thread_rng();
 
         let mut positions = Vec::new();
-        let target_count = match request.context.depth {
-            CounselDepth::Quick => 3,
-            CounselDepth::Standard => 4,
-            CounselDepth::Deep => 6,
-        };
+        let target_count = request.context.depth.positions();
+
+        // Organization policy: banned principles are never returned, pinned ones always compete
+        let principles = self.apply_policy(principles);
 
         // Score and sort principles by relevance to question
-        let mut scored: Vec<_> = principles.iter()
-            .map(|p| {
-                let mut score = self.score_principle_relevance(&request.question, p);
+        let deep = self.budget.allows("deep_scoring");
+        let query = QueryFeatures::new(&request.question);
+        let learned = self.learned_values(&query, &principles);
+        let (epsilon, temperature) = {
+            let exploration = self.exploration.borrow();
+            (exploration.epsilon, exploration.temperature)
+        };
+        let mut scored: Vec<_> = principles.iter().zip(learned)
+            .map(|(p, learned)| {
+                let mut score = self.score_principle_relevance(&query, p, learned);
+                if deep {
+                    score += self.score_context_relevance(&request.context, p);
+                }
 
                 // CAP maximum score to prevent keyword dominance (Knuth optimization problem)
                 score = score.min(80.0);  // Reduced from 100 to compress score range
 
+                if self.policy.is_pinned(&p.id) {
+                    score += PINNED_BOOST;
+                }
+                score += self.rerank_boost.borrow().get(&p.id).copied().unwrap_or(0.0);
+
                 // DIVERSITY PENALTY: Reduce score for frequently-cited principles
                 // This prevents the same principles from always winning
                 // Query the contextual_arms table for citation frequency
-                if let Ok(sample_count) = self.conn.query_row::<i64, _, _>(
-                    "SELECT COALESCE(SUM(sample_count), 0) FROM contextual_arms WHERE principle_id = ?1",
-                    rusqlite::params![&p.id],
-                    |row| row.get(0),
-                ) {
+                // (one query per principle, so part of deep scoring)
+                let citations = if deep { self.citation_count(&p.id) } else { None };
+                if let Some(sample_count) = citations {
                     // Apply diminishing returns penalty for over-selected principles
                     // Formula: penalty = log(1 + citations) * 3 (increased from 2)
                     if sample_count > 30 {  // Lower threshold
@@ -1029,7 +1358,7 @@ impl<'a> CounselEngine<'a> {
 
                 // SOFTMAX TEMPERATURE: Add random noise to break ties and increase diversity
                 // This is like temperature in softmax - higher noise = more exploration
-                let noise = rng.gen::<f64>() * 15.0;  // 0-15 random points
+                let noise = rng.gen::<f64>() * temperature;  // 0-15 random points by default
                 score += noise;
 
                 (p, score.max(0.0))  // Ensure score doesn't go negative
@@ -1054,13 +1383,22 @@ impl<'a> CounselEngine<'a> {
         let mut used_principles: std::collections::HashSet<String> =
             std::collections::HashSet::new();
 
-        // EPSILON-GREEDY EXPLORATION: 20% chance to explore lower-ranked principles
-        let epsilon = 0.20;
+        // EPSILON-GREEDY EXPLORATION: epsilon (20% by default) chance to explore lower-ranked principles
 
         let mut principle_idx = 0;
+        let epsilon = if scored.len() > 10 { epsilon } else { 0.0 };
         for stance in stances.iter().take(target_count) {
+            // What an exploration pick could land on this time, for the logged propensities
+            let zone: Vec<&str> = scored[5.min(scored.len())..20.min(scored.len())]
+                .iter()
+                .filter(|(p, score)| {
+                    *score >= 2.0 && !used_thinkers.contains(&p.thinker_id) && !used_principles.contains(&p.id)
+                })
+                .map(|(p, _)| p.id.as_str())
+                .collect();
+
             // Epsilon-greedy: occasionally pick from the "tail" (positions 5-20) instead of top
-            let explore_mode = rng.gen::<f64>() < epsilon && scored.len() > 10;
+            let explore_mode = rng.gen::<f64>() < epsilon;
 
             if explore_mode {
                 // Sample from principles ranked 5-20 (exploration zone)
@@ -1089,6 +1427,8 @@ impl<'a> CounselEngine<'a> {
                         used_principles.insert(principle.id.clone());
                         let position = self.build_position(request, principle, *stance)?;
                         positions.push(position);
+                        let propensity = selection_propensity(epsilon, &zone, &principle.id, false);
+                        self.note_selection(&principle.id, "explore", propensity);
                         break;
                     }
                     if positions.len() > positions.len().saturating_sub(1) {
@@ -1109,6 +1449,16 @@ impl<'a> CounselEngine<'a> {
                     continue;
                 }
 
+                // Pinned principles are exempt from the relevance floors
+                if self.policy.is_pinned(&principle.id) {
+                    used_thinkers.insert(principle.thinker_id.clone());
+                    used_principles.insert(principle.id.clone());
+                    positions.push(self.build_position(request, principle, *stance)?);
+                    let propensity = selection_propensity(epsilon, &zone, &principle.id, true);
+                    self.note_selection(&principle.id, "pinned", propensity);
+                    break;
+                }
+
                 // If no strong matches, be stricter about what we include
                 if !has_strong_match && *score < 3.0 {
                     continue; // Skip weak matches when nothing is strongly relevant
@@ -1123,6 +1473,8 @@ impl<'a> CounselEngine<'a> {
                 used_principles.insert(principle.id.clone());
                 let position = self.build_position(request, principle, *stance)?;
                 positions.push(position);
+                let propensity = selection_propensity(epsilon, &zone, &principle.id, true);
+                self.note_selection(&principle.id, "greedy", propensity);
                 break;
             }
         }
@@ -1142,6 +1494,7 @@ impl<'a> CounselEngine<'a> {
                 ),
                 principles_cited: vec!["Meta-reasoning".to_string()],
                 confidence: 0.3,
+                raw_confidence: None,
                 falsifiable_if: None,
             });
         }
@@ -1149,6 +1502,72 @@ impl<'a> CounselEngine<'a> {
         Ok(positions)
     }
 
+    /// Reconcile the strongest FOR and AGAINST positions
+    ///
+    /// Rule-based: "Do X if condition A, otherwise Y". Conditions come from a
+    /// template tension between the two principles when one is known, falling
+    /// back to each principle's application rule.
+    fn synthesize_positions(&self, positions: &[CounselPosition]) -> Option<CounselPosition> {
+        let pro = positions.iter().find(|p| p.stance == Stance::For)?;
+        let con = positions.iter().find(|p| p.stance == Stance::Against)?;
+        let pro_id = pro.principles_cited.first()?;
+        let con_id = con.principles_cited.first()?;
+
+        let (pro_name, _, pro_rule) = self.principle_text(pro_id)?;
+        let (con_name, con_desc, con_rule) = self.principle_text(con_id)?;
+
+        // Prefer curated when-to-pick guidance, in either orientation
+        let tension_guidance = templates::all_tensions().into_iter().find_map(|(_, t)| {
+            let forward = templates::principle_name_matches(&t.principle_a, &pro_name)
+                && templates::principle_name_matches(&t.principle_b, &con_name);
+            let reverse = templates::principle_name_matches(&t.principle_b, &pro_name)
+                && templates::principle_name_matches(&t.principle_a, &con_name);
+            if forward {
+                Some((t.when_to_pick_a, t.when_to_pick_b))
+            } else if reverse {
+                Some((t.when_to_pick_b, t.when_to_pick_a))
+            } else {
+                None
+            }
+        });
+
+        let (condition, fallback) = tension_guidance.unwrap_or_else(|| {
+            let condition = if pro_rule.is_empty() {
+                format!("the premise of {} holds in your situation", pro_name)
+            } else {
+                lowercase_first(pro_rule.trim_end_matches('.'))
+            };
+            let fallback = if con_rule.is_empty() {
+                con_desc.trim_end_matches('.').to_string()
+            } else {
+                con_rule.trim_end_matches('.').to_string()
+            };
+            (condition, fallback)
+        });
+
+        Some(CounselPosition {
+            thinker: "100minds".to_string(),
+            thinker_id: "_synthesis".to_string(),
+            stance: Stance::Synthesize,
+            argument: format!(
+                "Apply {} ({}) if {}; otherwise follow {} ({}): {}.\n   → ACTION: Check that condition first - it decides which principle governs here.",
+                pro_name,
+                pro.thinker,
+                lowercase_first(condition.trim_end_matches('.')),
+                con_name,
+                con.thinker,
+                fallback.trim_end_matches('.')
+            ),
+            principles_cited: vec![pro_id.clone(), con_id.clone()],
+            confidence: (pro.confidence + con.confidence) / 2.0,
+            raw_confidence: None,
+            falsifiable_if: Some(format!(
+                "This synthesis is wrong if {} and {} point the same way here - then there is no trade-off to reconcile",
+                pro_name, con_name
+            )),
+        })
+    }
+
     /// Build a single counsel position
     fn build_position(
         &self,
@@ -1172,6 +1591,7 @@ impl<'a> CounselEngine<'a> {
             argument,
             principles_cited: vec![principle.id.clone()], // Use ID for outcome recording
             confidence: principle.confidence,
+            raw_confidence: None,
             falsifiable_if: Some(falsifiable_if),
         })
     }
@@ -1185,8 +1605,11 @@ impl<'a> CounselEngine<'a> {
     ) -> String {
         let principle_text = &principle.description;
 
-        // Generate a Socratic question to help apply the principle
-        let socratic = self.generate_socratic_question(&principle.name, principle_text);
+        // The corpus's action prompt for the principle, else a rule-based one
+        let socratic = match self.action_prompts.curated(&principle.id) {
+            Some(prompt) => prompt.to_string(),
+            None => self.generate_socratic_question(&principle.name, principle_text),
+        };
 
         match stance {
             Stance::For => {
@@ -1204,75 +1627,10 @@ impl<'a> CounselEngine<'a> {
         }
     }
 
-    /// Generate an ACTION prompt to immediately apply the principle
+    /// Generate an ACTION prompt to immediately apply the principle, from
+    /// the keyword rules in `action_prompt_rules`
     fn generate_socratic_question(&self, name: &str, description: &str) -> String {
-        let name_lower = name.to_lowercase();
-        let desc_lower = description.to_lowercase();
-
-        // ACTION prompts - specific, immediate, doable in 60 seconds
-        if name_lower.contains("80/20")
-            || desc_lower.contains("80/20")
-            || desc_lower.contains("high-impact")
-        {
-            return "ACTION: List 5 things you're working on. Circle the ONE that matters most. Do only that.".to_string();
-        }
-        if name_lower.contains("fear") || desc_lower.contains("fear") {
-            return "ACTION: Write the worst case in one sentence. Then write how you'd recover. Now decide.".to_string();
-        }
-        if name_lower.contains("focus")
-            || desc_lower.contains("focus")
-            || desc_lower.contains("distraction")
-        {
-            return "ACTION: Name ONE thing to stop doing today. Block it. Protect your focus."
-                .to_string();
-        }
-        if name_lower.contains("compound") || desc_lower.contains("compound") {
-            return "ACTION: What takes 5 minutes today that pays off in 6 months? Do it now."
-                .to_string();
-        }
-        if desc_lower.contains("eliminate")
-            || desc_lower.contains("remove")
-            || desc_lower.contains("cut")
-        {
-            return "ACTION: Delete one feature/task/commitment right now. What won't you miss?"
-                .to_string();
-        }
-        if desc_lower.contains("customer") || desc_lower.contains("user") {
-            return "ACTION: Message ONE user right now. Ask: 'What's frustrating you?'"
-                .to_string();
-        }
-        if desc_lower.contains("track") || desc_lower.contains("measure") {
-            return "ACTION: Pick ONE number that proves success. Write it down. Check it daily."
-                .to_string();
-        }
-        if desc_lower.contains("automat") {
-            return "ACTION: What did you do manually 3+ times this week? Automate it today."
-                .to_string();
-        }
-        if desc_lower.contains("quality") || desc_lower.contains("defect") {
-            return "ACTION: Find your last 3 bugs. What's the common cause? Fix that root."
-                .to_string();
-        }
-        if desc_lower.contains("simple") || desc_lower.contains("complex") {
-            return "ACTION: Describe your solution in one sentence. If you can't, simplify."
-                .to_string();
-        }
-        if desc_lower.contains("start")
-            || desc_lower.contains("begin")
-            || desc_lower.contains("now")
-        {
-            return "ACTION: What's the smallest thing you can ship TODAY? Do that.".to_string();
-        }
-        if desc_lower.contains("jit")
-            || desc_lower.contains("just-in-time")
-            || desc_lower.contains("needed")
-        {
-            return "ACTION: What are you building that nobody asked for yet? Stop. Wait for pull."
-                .to_string();
-        }
-
-        // Default: still actionable
-        "ACTION: Apply this in the next 60 seconds. What's ONE concrete step?".to_string()
+        self.action_prompts.from_rules(name, description)
     }
 
     /// Build falsification condition (Popper's principle)
@@ -1333,6 +1691,7 @@ impl<'a> CounselEngine<'a> {
             argument,
             principles_cited: vec!["Socratic Method".to_string()],
             confidence: 0.95, // Challenges are high confidence - always worth considering
+            raw_confidence: None,
             falsifiable_if: Some(
                 "This challenge is invalid if you have direct evidence addressing it".to_string(),
             ),
@@ -1375,6 +1734,9 @@ impl<'a> CounselEngine<'a> {
 
     /// Get thinker name by ID
     fn get_thinker_name(&self, thinker_id: &str) -> Result<String> {
+        if let Some(snapshot) = &self.snapshot {
+            return Ok(snapshot.thinker_name(thinker_id).unwrap_or(thinker_id).to_string());
+        }
         let name: String = self
             .conn
             .query_row(
@@ -1393,9 +1755,13 @@ impl<'a> CounselEngine<'a> {
         request: &CounselRequest,
         positions: &[CounselPosition],
         challenge: &CounselPosition,
+        previous_hash: Option<String>,
     ) -> Result<ProvenanceInfo> {
         // Get previous hash for chain
-        let previous_hash = db::get_latest_decision_hash(self.conn)?;
+        let previous_hash = match previous_hash {
+            Some(hash) => Some(hash),
+            None => db::get_latest_decision_hash(self.conn)?,
+        };
 
         // Create content to hash
         let content = serde_json::json!({
@@ -1420,15 +1786,50 @@ impl<'a> CounselEngine<'a> {
         })
     }
 
+    /// Counsel with an optional idempotency key
+    ///
+    /// A retried call with a key seen before returns the stored response for
+    /// that decision instead of creating a second decision.
+    pub fn counsel_idempotent(&self, request: &CounselRequest, key: Option<&str>) -> Result<CounselResponse> {
+        let Some(key) = key else {
+            return self.counsel(request);
+        };
+        let previous: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT d.counsel_json FROM counsel_keys k
+                 JOIN decisions d ON d.id = k.decision_id
+                 WHERE k.idempotency_key = ?1",
+                [key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(json) = previous {
+            return Ok(serde_json::from_str(&json)?);
+        }
+
+        let response = self.counsel(request)?;
+        self.conn.execute(
+            "INSERT OR IGNORE INTO counsel_keys (idempotency_key, decision_id) VALUES (?1, ?2)",
+            params![key, response.decision_id],
+        )?;
+        Ok(response)
+    }
+
     /// Store the decision in database
     fn store_decision(&self, response: &CounselResponse, request: &CounselRequest) -> Result<()> {
-        let context_json = serde_json::to_string(&request.context)?;
-        let counsel_json = serde_json::to_string(&response)?;
+        let (question, context_json, counsel_json) = crate::redact::scrub_decision(
+            self.conn,
+            &response.decision_id,
+            &request.question,
+            &serde_json::to_string(&request.context)?,
+            &serde_json::to_string(&response)?,
+        )?;
 
         db::insert_decision(
             self.conn,
             &response.decision_id,
-            &request.question,
+            &question,
             Some(&context_json),
             &counsel_json,
             response.provenance.previous_hash.as_deref(),
@@ -1436,52 +1837,175 @@ impl<'a> CounselEngine<'a> {
             &response.provenance.signature,
             &response.provenance.agent_pubkey,
         )?;
+        if let Some(query) = &response.language {
+            self.conn.execute(
+                "UPDATE decisions SET language = ?2 WHERE id = ?1",
+                params![response.decision_id, query.language],
+            )?;
+        }
+        if let Some(info) = &response.thread {
+            thread::link(self.conn, &response.decision_id, &info.parent_decision_id)?;
+        }
+        if let Some(session_id) = &request.context.session_id {
+            self.conn.execute(
+                "UPDATE decisions SET session_id = ?2 WHERE id = ?1",
+                params![response.decision_id, session_id],
+            )?;
+        }
+        if response.urgency_adjustment.as_deref() == Some("escalate") {
+            crate::approval::request_review(self.conn, &response.decision_id)?;
+        }
 
         Ok(())
     }
+}
+
+/// Blend a learned value toward [`COLD_ARM_VALUE`] in proportion to how far
+/// `evidence` (outcomes, pooled ones included) is from `min_samples`, so one
+/// early outcome can't reorder the ranking on its own
+fn gate_learned_value(value: f64, evidence: f64, min_samples: u64) -> f64 {
+    if evidence >= min_samples as f64 {
+        return value;
+    }
+    COLD_ARM_VALUE + (value - COLD_ARM_VALUE) * evidence / min_samples as f64
+}
 
-    /// Record outcome and apply learning adjustments
-    pub fn record_outcome(&self, request: &RecordOutcomeRequest) -> Result<()> {
-        // 1. Update the decision record
-        db::record_outcome(
-            self.conn,
-            &request.decision_id,
-            request.success,
-            request.notes.as_deref(),
-        )?;
+/// Chance a position's epsilon-greedy roll picks `principle_id`, given the
+/// noisy scores: 1 - epsilon for the greedy choice, plus epsilon shared
+/// evenly across the exploration zone's eligible principles. Approximate: an
+/// exploration roll that finds nothing in ten tries falls back to greedy,
+/// and that is left out.
+fn selection_propensity(epsilon: f64, zone: &[&str], principle_id: &str, greedy: bool) -> f64 {
+    let explored = if zone.contains(&principle_id) { epsilon / zone.len() as f64 } else { 0.0 };
+    if greedy { 1.0 - epsilon + explored } else { explored }
+}
 
-        // 2. Get the decision to find which principles were used
-        let counsel_json: String = self.conn.query_row(
-            "SELECT counsel_json FROM decisions WHERE id = ?1",
-            [&request.decision_id],
-            |row| row.get(0),
-        )?;
+/// Config exploration settings with a request's overrides applied
+fn exploration_log(learning: &LearningConfig, overrides: Option<&ExplorationSettings>) -> ExplorationLog {
+    let overrides = overrides.cloned().unwrap_or_default();
+    ExplorationLog {
+        policy: learning.policy.clone(),
+        epsilon: overrides.epsilon.unwrap_or(learning.epsilon).clamp(0.0, 1.0),
+        optimism: overrides.optimism.unwrap_or(learning.optimism).max(0.0),
+        temperature: overrides.temperature.unwrap_or(learning.temperature).max(0.0),
+        selections: Vec::new(),
+    }
+}
 
-        let counsel: CounselResponse = serde_json::from_str(&counsel_json)?;
+/// Pool an actor's arm with the global one: the actor's successes and
+/// failures on top of the global posterior, shrunk to at most
+/// [`ACTOR_PRIOR_STRENGTH`] pseudo-observations (a Beta(1, 1) prior if there
+/// is no global arm)
+fn pool_actor_arm(global: Option<(f64, f64, i64)>, actor: (f64, f64, i64)) -> (f64, f64, i64) {
+    let (global_alpha, global_beta, global_samples) = global.unwrap_or((1.0, 1.0, 0));
+    let (actor_alpha, actor_beta, actor_samples) = actor;
+    let weight = (ACTOR_PRIOR_STRENGTH / (global_alpha + global_beta)).min(1.0);
+    (
+        actor_alpha - 1.0 + global_alpha * weight,
+        actor_beta - 1.0 + global_beta * weight,
+        actor_samples + global_samples,
+    )
+}
 
-        // 3. Apply adjustments based on outcome
-        let adjustment = if request.success { 0.05 } else { -0.08 };
+/// Split a compound decision into independent sub-questions
+///
+/// "Should we rewrite the backend and also switch to microservices?" becomes
+/// "Should we rewrite the backend?" and "Should we switch to microservices?".
+/// A plain "and" only splits when the next clause starts with a decision verb,
+/// so "Should we use Postgres and Redis?" stays one question.
+pub fn decompose_question(question: &str) -> Vec<String> {
+    const STRONG_SEPARATORS: [&str; 6] = [
+        " and also ",
+        ", and then ",
+        " and then ",
+        " as well as ",
+        "; ",
+        ", plus ",
+    ];
+    const DECISION_VERBS: [&str; 28] = [
+        "switch", "move", "adopt", "rewrite", "hire", "migrate", "add", "build", "buy", "use",
+        "split", "introduce", "replace", "drop", "remove", "refactor", "launch", "start", "stop",
+        "invest", "extract", "change", "upgrade", "outsource", "delay", "cut", "ship", "raise",
+    ];
+    const PREFIXES: [&str; 8] = [
+        "should we", "should i", "do we", "can we", "must we", "shall we", "is it worth",
+        "would it be wise to",
+    ];
+
+    let trimmed = question.trim().trim_end_matches('?').trim();
+    let lower = trimmed.to_lowercase();
+
+    // Byte offsets are found on the lowercase copy; bail if casing changed lengths
+    if lower.len() != trimmed.len() {
+        return vec![question.to_string()];
+    }
 
-        for position in &counsel.positions {
-            for principle_name in &position.principles_cited {
-                // Find principle ID
-                if let Ok(principle_id) = self.conn.query_row::<String, _, _>(
-                    "SELECT id FROM principles WHERE name = ?1",
-                    [principle_name],
-                    |row| row.get(0),
-                ) {
-                    let _ = db::apply_adjustment(
-                        self.conn,
-                        &principle_id,
-                        None, // TODO: extract context pattern
-                        adjustment,
-                        &request.decision_id,
-                    );
-                }
+    // Leading "Should we ..." is carried over to parts that lack their own
+    let prefix = PREFIXES
+        .iter()
+        .find(|p| lower.starts_with(*p))
+        .map(|p| &trimmed[..p.len()]);
+
+    // Split into (start, end) byte ranges on the lowercase copy
+    let mut cuts: Vec<(usize, usize)> = Vec::new();
+    for sep in STRONG_SEPARATORS {
+        for (idx, _) in lower.match_indices(sep) {
+            cuts.push((idx, idx + sep.len()));
+        }
+    }
+    for sep in [", and ", " and "] {
+        for (idx, _) in lower.match_indices(sep) {
+            let rest = &lower[idx + sep.len()..];
+            let rest = PREFIXES
+                .iter()
+                .find_map(|p| rest.strip_prefix(*p))
+                .unwrap_or(rest)
+                .trim_start();
+            let next_word = rest.split_whitespace().next().unwrap_or("");
+            if DECISION_VERBS.contains(&next_word) {
+                cuts.push((idx, idx + sep.len()));
             }
         }
+    }
+    cuts.sort();
+    cuts.dedup_by(|b, a| b.0 < a.1);
+
+    let mut fragments = Vec::new();
+    let mut start = 0;
+    for (cut_start, cut_end) in &cuts {
+        fragments.push(&trimmed[start..*cut_start]);
+        start = *cut_end;
+    }
+    fragments.push(&trimmed[start..]);
+
+    let parts: Vec<String> = fragments
+        .into_iter()
+        .map(|f| f.trim().trim_start_matches("also ").trim())
+        .filter(|f| f.split_whitespace().count() >= 2)
+        .map(|f| {
+            let f_lower = f.to_lowercase();
+            let has_prefix = PREFIXES.iter().any(|p| f_lower.starts_with(*p));
+            match prefix {
+                Some(prefix) if !has_prefix => format!("{} {}?", prefix, f),
+                _ => format!("{}?", f),
+            }
+        })
+        .take(4)
+        .collect();
 
-        Ok(())
+    if parts.len() > 1 {
+        parts
+    } else {
+        vec![question.to_string()]
+    }
+}
+
+/// Lowercase the first character so a rule reads naturally mid-sentence
+fn lowercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_lowercase().chain(chars).collect(),
+        None => String::new(),
     }
 }
 
@@ -1491,6 +2015,7 @@ mod tests {
     use crate::db;
     use crate::provenance::Provenance;
     use rusqlite::Connection;
+    use crate::urgency::UrgencyLevel;
     use tempfile::tempdir;
 
     fn setup_test_db() -> (Connection, tempfile::TempDir) {
@@ -1774,6 +2299,7 @@ mod tests {
             argument: "Test argument".to_string(),
             principles_cited: vec![],
             confidence,
+            raw_confidence: None,
             falsifiable_if: None,
         }
     }
@@ -1793,7 +2319,7 @@ mod tests {
         let positions = vec![mock_position_with_confidence(Stance::For, 0.8)];
 
         let urgency = engine.detect_urgency(&request, &positions);
-        assert_eq!(urgency, Some("escalate".to_string()));
+        assert_eq!(urgency.map(|u| u.level), Some(UrgencyLevel::Escalate));
     }
 
     #[test]
@@ -1810,7 +2336,7 @@ mod tests {
         let positions = vec![mock_position_with_confidence(Stance::For, 0.8)];
 
         let urgency = engine.detect_urgency(&request, &positions);
-        assert_eq!(urgency, Some("defer".to_string()));
+        assert_eq!(urgency.map(|u| u.level), Some(UrgencyLevel::Defer));
     }
 
     #[test]
@@ -1828,7 +2354,7 @@ mod tests {
         let positions = vec![mock_position_with_confidence(Stance::For, 0.3)];
 
         let urgency = engine.detect_urgency(&request, &positions);
-        assert_eq!(urgency, Some("escalate".to_string()));
+        assert_eq!(urgency.map(|u| u.level), Some(UrgencyLevel::Escalate));
     }
 
     #[test]
@@ -1849,7 +2375,7 @@ mod tests {
         ];
 
         let urgency = engine.detect_urgency(&request, &positions);
-        assert_eq!(urgency, Some("escalate".to_string()));
+        assert_eq!(urgency.map(|u| u.level), Some(UrgencyLevel::Escalate));
     }
 
     #[test]
@@ -1930,4 +2456,625 @@ mod tests {
             "AGAINST stance should mention being unnecessary"
         );
     }
+
+    // =========================================================================
+    // detect_tensions tests
+    // =========================================================================
+
+    #[test]
+    fn test_detect_tensions_flags_conflicting_principles() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('knuth', 'Donald Knuth', 'cs'), ('beck', 'Kent Beck', 'xp');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('optimize-early', 'knuth', 'Optimize Early', 'Tune the hot path up front'),
+                        ('yagni', 'beck', 'YAGNI', 'You aren''t gonna need it');",
+        )
+        .unwrap();
+        let engine = CounselEngine::new(&conn, &provenance);
+
+        let position = |thinker: &str, principle: &str, stance: Stance| CounselPosition {
+            thinker: thinker.to_string(),
+            thinker_id: thinker.to_lowercase(),
+            stance,
+            argument: String::new(),
+            principles_cited: vec![principle.to_string()],
+            confidence: 0.7,
+            raw_confidence: None,
+            falsifiable_if: None,
+        };
+        let positions = vec![
+            position("Donald Knuth", "optimize-early", Stance::For),
+            position("Kent Beck", "yagni", Stance::Against),
+        ];
+
+        let tensions = engine.detect_tensions(&positions);
+        assert_eq!(tensions.len(), 1);
+        assert_eq!(tensions[0].principle_a, "Optimize Early");
+        assert_eq!(tensions[0].thinker_b, "Kent Beck");
+        assert!(!tensions[0].when_to_pick_b.is_empty());
+
+        // One side alone is not a tension
+        assert!(engine.detect_tensions(&positions[..1]).is_empty());
+    }
+
+    // =========================================================================
+    // synthesize_positions tests
+    // =========================================================================
+
+    #[test]
+    fn test_synthesis_reconciles_for_and_against() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('knuth', 'Donald Knuth', 'cs'), ('beck', 'Kent Beck', 'xp');
+             INSERT INTO principles (id, thinker_id, name, description, application_rule)
+                 VALUES ('measure', 'knuth', 'Measure First', 'Profile before tuning', 'A profiler shows a clear hotspot'),
+                        ('simple', 'beck', 'Simple Design', 'Do the simplest thing', 'Keep the code as simple as possible');",
+        )
+        .unwrap();
+        let engine = CounselEngine::new(&conn, &provenance);
+
+        let position = |thinker: &str, principle: &str, stance: Stance| CounselPosition {
+            thinker: thinker.to_string(),
+            thinker_id: thinker.to_lowercase(),
+            stance,
+            argument: String::new(),
+            principles_cited: vec![principle.to_string()],
+            confidence: 0.6,
+            raw_confidence: None,
+            falsifiable_if: None,
+        };
+        let positions = vec![
+            position("Donald Knuth", "measure", Stance::For),
+            position("Kent Beck", "simple", Stance::Against),
+        ];
+
+        let synthesis = engine.synthesize_positions(&positions).unwrap();
+        assert_eq!(synthesis.stance, Stance::Synthesize);
+        assert_eq!(synthesis.principles_cited, vec!["measure", "simple"]);
+        assert!(synthesis
+            .argument
+            .starts_with("Apply Measure First (Donald Knuth) if a profiler shows a clear hotspot; otherwise follow Simple Design"));
+
+        // Nothing to reconcile without both sides
+        assert!(engine.synthesize_positions(&positions[..1]).is_none());
+    }
+
+    // =========================================================================
+    // decompose_question tests
+    // =========================================================================
+
+    #[test]
+    fn test_decompose_compound_question() {
+        let parts =
+            decompose_question("Should we rewrite the backend and also switch to microservices?");
+        assert_eq!(
+            parts,
+            vec![
+                "Should we rewrite the backend?".to_string(),
+                "Should we switch to microservices?".to_string()
+            ]
+        );
+
+        let parts = decompose_question("Should we hire two engineers and migrate to Postgres?");
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1], "Should we migrate to Postgres?");
+    }
+
+    #[test]
+    fn test_decompose_keeps_simple_questions_whole() {
+        let q = "Should we use Postgres and Redis?";
+        assert_eq!(decompose_question(q), vec![q.to_string()]);
+
+        let q = "How do we test and deploy safely?";
+        assert_eq!(decompose_question(q), vec![q.to_string()]);
+    }
+
+    #[test]
+    fn test_counsel_compound_question_returns_parts() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        let engine = CounselEngine::new(&conn, &provenance);
+
+        let request = CounselRequest {
+            question: "Should we rewrite the backend and also switch to microservices?".to_string(),
+            context: CounselContext::default(),
+            decision_id: None,
+        };
+        let response = engine.counsel(&request).unwrap();
+        assert_eq!(response.parts.len(), 2);
+        assert_eq!(response.parts[1].question, "Should we switch to microservices?");
+    }
+
+    #[test]
+    fn test_counsel_idempotency_key_returns_original_decision() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        let engine = CounselEngine::new(&conn, &provenance);
+
+        let request = CounselRequest {
+            question: "Should we add a cache in front of the database?".to_string(),
+            context: CounselContext::default(),
+            decision_id: None,
+        };
+        let first = engine.counsel_idempotent(&request, Some("retry-me")).unwrap();
+        let retry = engine.counsel_idempotent(&request, Some("retry-me")).unwrap();
+        assert_eq!(retry.decision_id, first.decision_id);
+
+        let decisions: i64 = conn.query_row("SELECT COUNT(*) FROM decisions", [], |row| row.get(0)).unwrap();
+        assert_eq!(decisions, 1);
+    }
+
+    #[test]
+    fn test_latency_budget_skips_optional_steps() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        let engine = CounselEngine::new(&conn, &provenance);
+
+        let mut request = CounselRequest {
+            question: "Should we rewrite the backend and also switch to microservices?".to_string(),
+            context: CounselContext { latency_budget_ms: Some(0), ..Default::default() },
+            decision_id: None,
+        };
+        let response = engine.counsel(&request).unwrap();
+        assert!(response.degraded);
+        assert!(response.parts.is_empty());
+        for step in ["decomposition", "query_expansion", "deep_scoring", "template_matching"] {
+            assert!(response.skipped_steps.iter().any(|s| s == step), "{} not skipped", step);
+        }
+        assert!(!response.positions.is_empty());
+
+        request.context.latency_budget_ms = None;
+        let response = engine.counsel(&request).unwrap();
+        assert!(!response.degraded);
+        assert!(response.skipped_steps.is_empty());
+    }
+
+    #[test]
+    fn test_degraded_response_is_redacted_when_re_stored() {
+        let (conn, db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        std::fs::write(db_dir.path().join("config.toml"), "[privacy]\nredact = true\n").unwrap();
+        let engine = CounselEngine::new(&conn, &provenance);
+
+        let request = CounselRequest {
+            question: "Should we email bo@corp.com before we rewrite the backend?".to_string(),
+            context: CounselContext { latency_budget_ms: Some(0), ..Default::default() },
+            decision_id: None,
+        };
+        let response = engine.counsel(&request).unwrap();
+        assert!(response.degraded);
+
+        let stored: String = conn
+            .query_row("SELECT counsel_json FROM decisions WHERE id = ?1", [&response.decision_id], |row| row.get(0))
+            .unwrap();
+        assert!(stored.contains("skipped_steps"));
+        assert!(!stored.contains("bo@corp.com"), "plaintext email stored: {}", stored);
+        assert!(stored.contains("[EMAIL]"));
+    }
+
+    #[test]
+    fn test_calibration_refits_on_stated_confidences() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            r#"
+            INSERT INTO thinkers (id, name, domain) VALUES ('schneier', 'Bruce Schneier', 'security'),
+                                                        ('ries', 'Eric Ries', 'business');
+            INSERT INTO principles (id, thinker_id, name, description)
+                VALUES ('schneier-1', 'schneier', 'Defense in Depth', 'Layer controls before you launch the login flow'),
+                       ('ries-1', 'ries', 'Minimum Viable Product', 'Launch the login flow early to learn');
+            "#,
+        )
+        .unwrap();
+        let calibrator = calibration::Calibrator::Platt { a: 0.5, b: 0.0 };
+        calibration::save_calibrator(&conn, &calibrator, 40, true).unwrap();
+        let engine = CounselEngine::new(&conn, &provenance);
+
+        let request = CounselRequest {
+            question: "How should we launch the login flow?".to_string(),
+            context: CounselContext::default(),
+            decision_id: None,
+        };
+        let response = engine.counsel(&request).unwrap();
+        let stated: Vec<f64> = response
+            .positions
+            .iter()
+            .filter(|p| !p.thinker_id.starts_with('_'))
+            .map(|p| p.raw_confidence.unwrap())
+            .collect();
+        assert!(!stated.is_empty());
+        for position in response.positions.iter().filter(|p| p.raw_confidence.is_some()) {
+            let raw = position.raw_confidence.unwrap();
+            assert!((position.confidence - calibrator.apply(raw)).abs() < 1e-9);
+        }
+
+        conn.execute("UPDATE decisions SET outcome_success = 1 WHERE id = ?1", [&response.decision_id]).unwrap();
+        let samples = calibration::load_samples(&conn).unwrap();
+        assert!(samples.iter().all(|s| s.thinker != "100minds"));
+        assert_eq!(samples.iter().map(|s| s.predicted).collect::<Vec<_>>(), stated);
+    }
+
+    #[test]
+    fn test_tag_filters_scope_principles_and_are_recorded() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            r#"
+            INSERT INTO thinkers (id, name, domain) VALUES ('schneier', 'Bruce Schneier', 'security'),
+                                                        ('ries', 'Eric Ries', 'business');
+            INSERT INTO principles (id, thinker_id, name, description, domain_tags)
+                VALUES ('schneier-1', 'schneier', 'Defense in Depth', 'Layer controls before you launch the login flow', '["security"]'),
+                       ('ries-1', 'ries', 'Minimum Viable Product', 'Launch the login flow early to learn', '["entrepreneurship"]');
+            "#,
+        )
+        .unwrap();
+        let engine = CounselEngine::new(&conn, &provenance);
+        let cited = |context: CounselContext| {
+            let request = CounselRequest { question: "How should we launch the login flow?".to_string(), context, decision_id: None };
+            let response = engine.counsel(&request).unwrap();
+            let ids: Vec<String> = response.positions.iter().flat_map(|p| p.principles_cited.clone()).collect();
+            (ids, response.decision_id)
+        };
+
+        let (ids, _) = cited(CounselContext::default());
+        assert!(ids.contains(&"ries-1".to_string()));
+
+        let (ids, decision_id) = cited(CounselContext { exclude_tags: vec!["Entrepreneurship".to_string()], ..Default::default() });
+        assert!(!ids.contains(&"ries-1".to_string()));
+        let context_json: String = conn
+            .query_row("SELECT context_json FROM decisions WHERE id = ?1", [&decision_id], |row| row.get(0))
+            .unwrap();
+        assert!(context_json.contains(r#""exclude_tags":["Entrepreneurship"]"#));
+
+        let (ids, _) = cited(CounselContext { include_tags: vec!["security".to_string()], ..Default::default() });
+        assert!(ids.contains(&"schneier-1".to_string()));
+        assert!(!ids.contains(&"ries-1".to_string()));
+    }
+
+    #[test]
+    fn test_spanish_question_retrieves_english_principles() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('fowler', 'Martin Fowler', 'software');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('strangler', 'fowler', 'Strangler Fig', 'Rewrite a legacy system incrementally, not from scratch');",
+        )
+        .unwrap();
+        let engine = CounselEngine::new(&conn, &provenance);
+        let question = "¿Deberíamos reescribir el sistema legado desde cero?";
+        let request = CounselRequest { question: question.to_string(), context: CounselContext::default(), decision_id: None };
+        let response = engine.counsel(&request).unwrap();
+
+        assert_eq!(response.question, question);
+        assert!(response.principle_ids.contains(&"strangler".to_string()));
+        let language = response.language.unwrap();
+        assert_eq!((language.language.as_str(), language.method.as_str()), ("es", "glossary"));
+        let stored: (String, String) = conn
+            .query_row("SELECT question, language FROM decisions WHERE id = ?1", [&response.decision_id], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(stored, (question.to_string(), "es".to_string()));
+    }
+
+    #[test]
+    fn test_counsel_without_semantic_engine_reports_keyword_mode() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _key_dir) = setup_provenance();
+        let mut engine = CounselEngine::new(&conn, &provenance);
+        engine.semantic = None;
+        engine.capabilities = embeddings::Capabilities {
+            semantic_search: false,
+            search_mode: "fts_only".to_string(),
+            model_dir: None,
+            degraded_reason: Some("no embedding model".to_string()),
+        };
+
+        let request = CounselRequest {
+            question: "Should we rewrite the backend?".to_string(),
+            context: CounselContext::default(),
+            decision_id: None,
+        };
+        let response = engine.counsel(&request).unwrap();
+        assert!(!response.positions.is_empty());
+        let capabilities = response.capabilities.unwrap();
+        assert_eq!((capabilities.semantic_search, capabilities.search_mode.as_str()), (false, "fts_only"));
+    }
+
+    #[test]
+    fn test_reranker_reorders_top_candidates_and_reports() {
+        struct Prefers(&'static str);
+        impl Reranker for Prefers {
+            fn name(&self) -> &str {
+                "prefers"
+            }
+            fn score(&self, _query: &str, texts: &[&str]) -> Result<Vec<f32>> {
+                Ok(texts.iter().map(|t| t.starts_with(self.0) as u8 as f32).collect())
+            }
+        }
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('fowler', 'Martin Fowler', 'software'),
+                                                        ('brooks', 'Fred Brooks', 'software');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('strangler', 'fowler', 'Strangler Fig', 'Rewrite the legacy system incrementally; a rewrite of a legacy system is risky'),
+                        ('second-system', 'brooks', 'Second System Effect', 'The second system is the most dangerous one to rewrite');",
+        )
+        .unwrap();
+        let engine = CounselEngine::new(&conn, &provenance).with_reranker(Arc::new(Prefers("Second System")));
+        let request = CounselRequest {
+            question: "Should we rewrite the legacy system?".to_string(),
+            context: CounselContext::default(),
+            decision_id: None,
+        };
+
+        let matches = engine.find_relevant_principles(&request).unwrap();
+        assert_eq!(matches[0].id, "second-system");
+        assert!(engine.rerank_boost.borrow()["second-system"] > engine.rerank_boost.borrow()["strangler"]);
+
+        let report = engine.counsel(&request).unwrap().rerank.unwrap();
+        assert_eq!((report.reranker.as_str(), report.candidates), ("prefers", 2));
+    }
+
+    #[test]
+    fn test_policy_pins_and_bans_principles() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            r#"
+            INSERT INTO thinkers (id, name, domain) VALUES ('zuck', 'Mark Zuckerberg', 'business'),
+                                                        ('cavoukian', 'Ann Cavoukian', 'security');
+            INSERT INTO principles (id, thinker_id, name, description, domain_tags)
+                VALUES ('move-fast', 'zuck', 'Move Fast and Break Things', 'Ship the signup flow now and fix it later', '["entrepreneurship"]'),
+                       ('privacy', 'cavoukian', 'Data Privacy', 'Minimize the personal data you collect', '["security"]');
+            "#,
+        )
+        .unwrap();
+        crate::policy::set(&conn, "Move Fast and Break Things", "banned", None, "cli").unwrap();
+        crate::policy::set(&conn, "privacy", "pinned", None, "cli").unwrap();
+
+        let engine = CounselEngine::new(&conn, &provenance);
+        let request = CounselRequest {
+            question: "Should we ship the signup flow now?".to_string(),
+            context: CounselContext::default(),
+            decision_id: None,
+        };
+        let response = engine.counsel(&request).unwrap();
+        assert!(!response.principle_ids.contains(&"move-fast".to_string()));
+        assert!(response.principle_ids.contains(&"privacy".to_string()));
+        assert_eq!(response.policy.banned, vec!["move-fast"]);
+        assert_eq!(response.policy.pinned, vec!["privacy"]);
+    }
+
+    #[test]
+    fn test_exploration_overrides_are_logged_with_selections() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('spolsky', 'Joel Spolsky', 'software'),
+                                                        ('fowler', 'Martin Fowler', 'software'),
+                                                        ('gall', 'John Gall', 'systems');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('no-rewrite', 'spolsky', 'Never Rewrite', 'Never rewrite the service from scratch'),
+                        ('strangler', 'fowler', 'Strangler Fig', 'Rewrite the service piece by piece'),
+                        ('gall-law', 'gall', 'Gall''s Law', 'A rewrite of a working service starts simple');",
+        )
+        .unwrap();
+        let engine = CounselEngine::new(&conn, &provenance);
+        let request = |exploration: Option<ExplorationSettings>| CounselRequest {
+            question: "Should we rewrite the billing service in Rust?".to_string(),
+            context: CounselContext { exploration, ..Default::default() },
+            decision_id: None,
+        };
+
+        let greedy = ExplorationSettings { epsilon: Some(0.0), optimism: None, temperature: Some(0.0) };
+        let first = engine.counsel(&request(Some(greedy.clone()))).unwrap();
+        let log = first.exploration.clone().unwrap();
+        assert_eq!((log.policy.as_str(), log.epsilon, log.optimism, log.temperature), ("fgts", 0.0, 3.0, 0.0));
+        assert!(!log.selections.is_empty());
+        assert!(log.selections.iter().all(|s| s.strategy == "greedy" && s.propensity == Some(1.0)));
+        let picked: Vec<&str> = log.selections.iter().map(|s| s.principle_id.as_str()).collect();
+        for position in first.positions.iter().filter(|p| !p.thinker_id.starts_with('_')) {
+            assert!(picked.contains(&position.principles_cited[0].as_str()));
+        }
+        // No noise and no epsilon picks: the same question ranks the same way
+        let second = engine.counsel(&request(Some(greedy))).unwrap();
+        assert_eq!(first.principle_ids, second.principle_ids);
+
+        // Defaults come back once the overrides are gone, and the log is stored
+        let defaults = engine.counsel(&request(None)).unwrap();
+        let log = defaults.exploration.unwrap();
+        assert_eq!((log.epsilon, log.temperature), (0.2, 15.0));
+        assert!(log.selections.iter().all(|s| s.propensity.is_some_and(|p| p > 0.0 && p <= 1.0)));
+        let stored: String = conn
+            .query_row("SELECT counsel_json FROM decisions WHERE id = ?1", [&defaults.decision_id], |r| r.get(0))
+            .unwrap();
+        assert!(stored.contains("\"selections\""));
+    }
+
+    #[test]
+    fn test_actor_outcomes_personalize_contextual_arms() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('beck', 'Kent Beck', 'xp');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('yagni', 'beck', 'YAGNI', 'Do not build the admin panel before you need it');",
+        )
+        .unwrap();
+        let question = "Should we build the admin panel now?";
+        let query = QueryFeatures::new(question);
+        for _ in 0..8 {
+            db::update_contextual_arm(&conn, "yagni", query.domain(), true).unwrap();
+        }
+
+        // Alice's outcomes are attributed through the decision she was counseled on
+        let engine = CounselEngine::new(&conn, &provenance);
+        let request = CounselRequest {
+            question: question.to_string(),
+            context: CounselContext { actor_id: Some("alice".to_string()), ..Default::default() },
+            decision_id: Some("d-alice".to_string()),
+        };
+        engine.counsel(&request).unwrap();
+        for _ in 0..12 {
+            crate::outcome::record_outcome(&conn, "d-alice", false, &["yagni".to_string()], "", None).unwrap();
+        }
+        let context = format!(r#"{{"actor_id":"bob","domain":"{}"}}"#, query.domain());
+        crate::outcome::record_outcome(&conn, "d-bob", false, &["yagni".to_string()], "", Some(&context)).unwrap();
+        let alice_adjustments: i64 = conn
+            .query_row("SELECT COUNT(*) FROM framework_adjustments WHERE actor_id = 'alice'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(alice_adjustments, 12);
+
+        let principle = db::get_all_principles(&conn).unwrap().remove(0);
+        let score_for = |actor: Option<&str>| {
+            *engine.actor.borrow_mut() = actor.map(String::from);
+            let learned = engine.learned_values(&query, std::slice::from_ref(&principle));
+            engine.score_principle_relevance(&query, &principle, learned[0])
+        };
+        let (global, alice, bob) = (score_for(None), score_for(Some("alice")), score_for(Some("bob")));
+        // Alice has enough failures to outweigh the global record; Bob's one
+        // failure barely moves him off it
+        assert!(alice < bob - 5.0, "alice {} vs bob {}", alice, bob);
+        assert!((global - bob).abs() < 3.0, "global {} vs bob {}", global, bob);
+        assert_eq!(score_for(Some("carol")), global);
+
+        let (alpha, beta, samples) = pool_actor_arm(Some((9.0, 1.0, 8)), (1.0, 13.0, 12));
+        assert_eq!((alpha, beta, samples), (9.0, 13.0, 20));
+        let (alpha, beta, _) = pool_actor_arm(Some((90.0, 10.0, 98)), (2.0, 1.0, 1));
+        assert!((alpha / (alpha + beta) - 10.0 / 11.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_few_outcomes_are_shrunk_toward_a_cold_arm() {
+        let fixture = crate::testing::fixture().unwrap();
+        let mut engine = fixture.counsel_engine();
+        let query = QueryFeatures::new(crate::testing::SAMPLE_QUESTION);
+        let id = crate::testing::principle_id("spolsky", "Never Rewrite From Scratch");
+        let principle = db::get_all_principles(&fixture.conn).unwrap().into_iter().find(|p| p.id == id).unwrap();
+        let mut score = |min_samples: u64| {
+            engine.learning.min_samples = min_samples;
+            let learned = engine.learned_values(&query, std::slice::from_ref(&principle));
+            engine.score_principle_relevance(&query, &principle, learned[0])
+        };
+        let cold = score(3);
+
+        // One failure moves the score a third as far as it would ungated
+        db::update_contextual_arm(&fixture.conn, &id, query.domain(), false).unwrap();
+        let (gated, ungated) = (score(3), score(0));
+        assert!((ungated - cold).abs() > 1.0, "cold {} vs ungated {}", cold, ungated);
+        assert!(((gated - cold) * 3.0 - (ungated - cold)).abs() < 1e-9, "{} {} {}", cold, gated, ungated);
+
+        // From min_samples on the arm's record counts in full
+        for _ in 0..2 {
+            db::update_contextual_arm(&fixture.conn, &id, query.domain(), false).unwrap();
+        }
+        assert_eq!(score(3), score(0));
+        assert_eq!(gate_learned_value(0.2, 1.0, 3), COLD_ARM_VALUE - 0.8 / 3.0);
+    }
+
+    #[test]
+    fn test_auto_depth_is_resolved_and_reported() {
+        let fixture = crate::testing::fixture().unwrap();
+        let engine = fixture.counsel_engine();
+        let mut request = CounselRequest {
+            question: crate::testing::SAMPLE_QUESTION.to_string(),
+            context: CounselContext::default(),
+            decision_id: None,
+        };
+        assert!(engine.counsel(&request).unwrap().depth.is_none());
+
+        request.context.depth = CounselDepth::Auto;
+        let response = engine.counsel(&request).unwrap();
+        let choice = response.depth.expect("auto depth is reported");
+        assert_eq!(choice, depth::choose(crate::testing::SAMPLE_QUESTION));
+        let backed = response.positions.iter().filter(|p| !p.principles_cited.is_empty()).count();
+        assert!(backed <= choice.depth.positions() + 1, "{} positions for {:?}", backed, choice.depth);
+    }
+
+    #[test]
+    fn test_followup_refines_context_and_chains_from_parent() {
+        let fixture = crate::testing::fixture().unwrap();
+        let engine = fixture.counsel_engine();
+        let request = CounselRequest {
+            question: crate::testing::SAMPLE_QUESTION.to_string(),
+            context: CounselContext { team_size: Some(12), ..Default::default() },
+            decision_id: None,
+        };
+        let parent = engine.counsel(&request).unwrap();
+        engine.counsel(&request).unwrap();
+
+        let ask = |parent_decision_id: &str| FollowupRequest {
+            parent_decision_id: parent_decision_id.to_string(),
+            refinement: "what if the team is only 2 people?".to_string(),
+            decision_id: None,
+        };
+        let followup = engine.counsel_followup(&ask(&parent.decision_id)).unwrap();
+        assert_eq!(followup.question, parent.question);
+        assert_eq!(followup.provenance.previous_hash.as_deref(), Some(parent.provenance.content_hash.as_str()));
+        let info = followup.thread.clone().unwrap();
+        assert_eq!((info.root_decision_id.as_str(), info.turn), (parent.decision_id.as_str(), 1));
+        let (context_json, linked): (String, String) = fixture
+            .conn
+            .query_row(
+                "SELECT context_json, parent_decision_id FROM decisions WHERE id = ?1",
+                [&followup.decision_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        let context: CounselContext = serde_json::from_str(&context_json).unwrap();
+        assert_eq!((context.team_size, linked.as_str()), (Some(2), parent.decision_id.as_str()));
+
+        let second = engine.counsel_followup(&ask(&followup.decision_id)).unwrap().thread.unwrap();
+        assert_eq!((second.root_decision_id.as_str(), second.turn), (parent.decision_id.as_str(), 2));
+        assert!(engine.counsel(&request).unwrap().thread.is_none());
+        assert!(matches!(engine.counsel_followup(&ask("no-such-decision")), Err(crate::error::MindsError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_latency_budget_allows_steps_that_fit() {
+        let budget = LatencyBudget::default();
+        assert!(budget.allows("deep_scoring"));
+
+        budget.start(Some(60));
+        assert!(budget.allows("domain_search"));
+        assert!(!budget.allows("decomposition"));
+        assert!(!budget.allows("decomposition"));
+        assert_eq!(budget.finish(), vec!["decomposition"]);
+        assert!(budget.allows("decomposition"));
+    }
+
+    // =========================================================================
+    // score_context_relevance tests
+    // =========================================================================
+
+    #[test]
+    fn test_context_boosts_brooks_for_small_team_tight_deadline() {
+        let (conn, _db_dir) = setup_test_db();
+        let (provenance, _dir) = setup_provenance();
+        let engine = CounselEngine::new(&conn, &provenance);
+
+        let brooks = db::PrincipleMatch {
+            id: "brooks-law".to_string(),
+            thinker_id: "brooks".to_string(),
+            name: "Brooks's Law".to_string(),
+            description: "Adding people to a late project makes it later".to_string(),
+            confidence: 0.8,
+            relevance_score: 0.0,
+        };
+
+        let no_context = CounselContext::default();
+        let pressured = CounselContext {
+            notes: Some("team of 3, deadline in 2 weeks".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(engine.score_context_relevance(&no_context, &brooks), 0.0);
+        assert!(engine.score_context_relevance(&pressured, &brooks) >= 12.0);
+    }
 }
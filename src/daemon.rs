@@ -0,0 +1,306 @@
+//! Maintenance Daemon
+//!
+//! `--thompson yuzu` prints cron lines, but cron isn't everywhere. The
+//! daemon runs the same maintenance (persist, decay, cull, discover,
+//! embedding refresh), plus daily trend snapshots and retention pruning, on
+//! an internal schedule read from the `[daemon]` table of `config.toml`:
+//!
+//! ```toml
+//! [daemon]
+//! tick_secs = 60        # how often to check for due jobs
+//! jitter_secs = 300     # random delay added to each next run
+//!
+//! [daemon.jobs]
+//! persist = "1h"
+//! decay = "1d"
+//! cull = "7d"
+//! discover = "7d"
+//! embeddings = "off"    # "off" disables a job
+//! trends = "1d"
+//! prune = "1d"          # a no-op without [retention] limits
+//! notify = "15m"        # escalations and critical blind spots to chat...
+//! digest = "7d"         # ...and a weekly digest, both no-ops without [notifier]
+//! ```
+//!
+//! Last/next run times live in `daemon_runs`, so a restarted daemon picks
+//! up where it left off and `100minds daemon status` can report them.
+
+use crate::error::{MindsError, Result};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Jobs the daemon knows how to run
+pub const JOBS: [&str; 9] = ["persist", "decay", "cull", "discover", "embeddings", "trends", "prune", "notify", "digest"];
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DaemonConfig {
+    pub tick_secs: u64,
+    pub jitter_secs: u64,
+    /// Job name → interval ("30m", "6h", "7d", seconds, or "off")
+    pub jobs: BTreeMap<String, String>,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        let jobs = [
+            ("persist", "1h"),
+            ("decay", "1d"),
+            ("cull", "7d"),
+            ("discover", "7d"),
+            ("embeddings", "1d"),
+            ("trends", "1d"),
+            ("prune", "1d"),
+            ("notify", "15m"),
+            ("digest", "7d"),
+        ];
+        DaemonConfig {
+            tick_secs: 60,
+            jitter_secs: 300,
+            jobs: jobs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+}
+
+impl DaemonConfig {
+    /// Read `[daemon]` from config.toml; a missing file or table gives the defaults.
+    /// Jobs missing from `[daemon.jobs]` keep their default interval.
+    pub fn load(path: &Path) -> Result<Self> {
+        let file: Option<DaemonConfig> = crate::config::section_at(path, "daemon")?;
+        let mut config = DaemonConfig::default();
+        if let Some(daemon) = file {
+            config.tick_secs = daemon.tick_secs;
+            config.jitter_secs = daemon.jitter_secs;
+            config.jobs.extend(daemon.jobs);
+        }
+        Ok(config)
+    }
+
+    /// Enabled jobs with their intervals
+    pub fn schedule(&self) -> Result<Vec<(String, Duration)>> {
+        let mut schedule = Vec::new();
+        for (job, spec) in &self.jobs {
+            if !JOBS.contains(&job.as_str()) {
+                return Err(MindsError::Config(format!("Unknown daemon job '{}' (known: {})", job, JOBS.join(", "))));
+            }
+            if let Some(interval) = parse_interval(spec)? {
+                schedule.push((job.clone(), interval));
+            }
+        }
+        Ok(schedule)
+    }
+}
+
+/// "90" (seconds), "30m", "6h", "7d", or "off" (None)
+pub fn parse_interval(spec: &str) -> Result<Option<Duration>> {
+    let spec = spec.trim();
+    if spec.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    let (digits, unit) = spec.split_at(spec.find(|c: char| !c.is_ascii_digit()).unwrap_or(spec.len()));
+    let n: i64 = digits.parse().map_err(|_| MindsError::Config(format!("Bad interval: {}", spec)))?;
+    let interval = match unit {
+        "" | "s" => Duration::seconds(n),
+        "m" => Duration::minutes(n),
+        "h" => Duration::hours(n),
+        "d" => Duration::days(n),
+        "w" => Duration::weeks(n),
+        _ => return Err(MindsError::Config(format!("Bad interval unit in '{}' (use s, m, h, d or w)", spec))),
+    };
+    if interval <= Duration::zero() {
+        return Err(MindsError::Config(format!("Interval must be positive: {}", spec)));
+    }
+    Ok(Some(interval))
+}
+
+/// Next run time: one interval from now plus up to `jitter_secs`, so a
+/// fleet of daemons doesn't hit a shared database in lockstep
+pub fn next_run_at(now: DateTime<Utc>, interval: Duration, jitter_secs: u64, rng: &mut impl Rng) -> DateTime<Utc> {
+    let jitter = if jitter_secs > 0 { rng.gen_range(0..=jitter_secs) } else { 0 };
+    now + interval + Duration::seconds(jitter as i64)
+}
+
+/// Jobs that have never run or whose next run time has passed
+pub fn due_jobs<'a>(
+    conn: &Connection,
+    schedule: &'a [(String, Duration)],
+    now: DateTime<Utc>,
+) -> Result<Vec<&'a (String, Duration)>> {
+    let mut due = Vec::new();
+    for entry in schedule {
+        let next: Option<String> = conn
+            .query_row("SELECT next_run_at FROM daemon_runs WHERE job = ?1", [&entry.0], |row| row.get(0))
+            .ok();
+        let is_due = match next.and_then(|n| DateTime::parse_from_rfc3339(&n).ok()) {
+            Some(next) => next <= now,
+            None => true,
+        };
+        if is_due {
+            due.push(entry);
+        }
+    }
+    Ok(due)
+}
+
+/// Bookkeeping after a job ran
+pub fn record_run(
+    conn: &Connection,
+    job: &str,
+    started: DateTime<Utc>,
+    duration_ms: i64,
+    error: Option<&str>,
+    next_run: DateTime<Utc>,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO daemon_runs (job, last_run_at, last_status, last_error, duration_ms, next_run_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(job) DO UPDATE SET
+             last_run_at = excluded.last_run_at,
+             last_status = excluded.last_status,
+             last_error = excluded.last_error,
+             duration_ms = excluded.duration_ms,
+             next_run_at = excluded.next_run_at",
+        params![
+            job,
+            started.to_rfc3339(),
+            if error.is_some() { "failed" } else { "ok" },
+            error,
+            duration_ms,
+            next_run.to_rfc3339()
+        ],
+    )?;
+    Ok(())
+}
+
+/// One row of `100minds daemon status`
+#[derive(Debug, Clone, Serialize)]
+pub struct JobState {
+    pub job: String,
+    /// Configured interval, None when disabled
+    pub interval_secs: Option<i64>,
+    pub last_run_at: Option<String>,
+    pub last_status: Option<String>,
+    pub last_error: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub next_run_at: Option<String>,
+}
+
+/// Schedule and last-run state for every known job
+pub fn status(conn: &Connection, config: &DaemonConfig) -> Result<Vec<JobState>> {
+    let schedule = config.schedule()?;
+    let mut states = Vec::new();
+    for job in JOBS {
+        let interval = schedule.iter().find(|(name, _)| name == job).map(|(_, i)| i.num_seconds());
+        let row = conn
+            .query_row(
+                "SELECT last_run_at, last_status, last_error, duration_ms, next_run_at
+                 FROM daemon_runs WHERE job = ?1",
+                [job],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            )
+            .ok();
+        let (last_run_at, last_status, last_error, duration_ms, next_run_at) =
+            row.map_or((None, None, None, None, None), |(a, b, c, d, e)| (Some(a), Some(b), c, d, Some(e)));
+        states.push(JobState {
+            job: job.to_string(),
+            interval_secs: interval,
+            last_run_at,
+            last_status,
+            last_error,
+            duration_ms,
+            next_run_at,
+        });
+    }
+    Ok(states)
+}
+
+/// Run due jobs through `run_job`, every `tick_secs`; with `once`, a single pass.
+/// A failing job is recorded and retried at its next scheduled time.
+pub fn run_daemon(
+    conn: &Connection,
+    config: &DaemonConfig,
+    once: bool,
+    mut run_job: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let schedule = config.schedule()?;
+    let mut rng = rand::thread_rng();
+    loop {
+        let now = Utc::now();
+        for (job, interval) in due_jobs(conn, &schedule, now)? {
+            tracing::info!("Running {}", job);
+            let started = std::time::Instant::now();
+            let result = run_job(job);
+            let error = result.as_ref().err().map(|e| e.to_string());
+            if let Some(e) = &error {
+                tracing::warn!("{} failed: {}", job, e);
+            }
+            let next = next_run_at(Utc::now(), *interval, config.jitter_secs, &mut rng);
+            record_run(conn, job, now, started.elapsed().as_millis() as i64, error.as_deref(), next)?;
+        }
+        if once {
+            return Ok(());
+        }
+        std::thread::sleep(std::time::Duration::from_secs(config.tick_secs.max(1)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("90").unwrap(), Some(Duration::seconds(90)));
+        assert_eq!(parse_interval("30m").unwrap(), Some(Duration::minutes(30)));
+        assert_eq!(parse_interval("7d").unwrap(), Some(Duration::days(7)));
+        assert_eq!(parse_interval("off").unwrap(), None);
+        assert!(parse_interval("5y").is_err());
+        assert!(parse_interval("0h").is_err());
+    }
+
+    #[test]
+    fn test_config_overrides_merge_with_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[daemon]\njitter_secs = 0\n\n[daemon.jobs]\ncull = \"off\"\npersist = \"15m\"\n").unwrap();
+
+        let config = DaemonConfig::load(&path).unwrap();
+        assert_eq!(config.jitter_secs, 0);
+        assert_eq!(config.tick_secs, 60);
+        let schedule = config.schedule().unwrap();
+        assert!(!schedule.iter().any(|(job, _)| job == "cull"));
+        assert!(schedule.contains(&("persist".to_string(), Duration::minutes(15))));
+        assert!(schedule.iter().any(|(job, _)| job == "decay"));
+    }
+
+    #[test]
+    fn test_run_records_and_reschedules() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let mut config = DaemonConfig::default();
+        config.jitter_secs = 0;
+
+        let mut ran = Vec::new();
+        run_daemon(&conn, &config, true, |job| {
+            ran.push(job.to_string());
+            if job == "cull" { Err(anyhow::anyhow!("locked").into()) } else { Ok(()) }
+        })
+        .unwrap();
+        assert_eq!(ran.len(), JOBS.len());
+
+        // Nothing is due again until its interval passes
+        let schedule = config.schedule().unwrap();
+        assert!(due_jobs(&conn, &schedule, Utc::now()).unwrap().is_empty());
+        let due = due_jobs(&conn, &schedule, Utc::now() + Duration::hours(2)).unwrap();
+        assert_eq!(due.iter().map(|(j, _)| j.as_str()).collect::<Vec<_>>(), vec!["notify", "persist"]);
+
+        let states = status(&conn, &config).unwrap();
+        let cull = states.iter().find(|s| s.job == "cull").unwrap();
+        assert_eq!(cull.last_status.as_deref(), Some("failed"));
+        assert_eq!(cull.last_error.as_deref(), Some("locked"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Live Dashboard
+//!
+//! `100minds serve` answers `GET /dashboard` with a one-page app and
+//! `GET /dashboard/events` with a Server-Sent Events stream the page
+//! listens to. Every [`POLL_SECS`] the stream sends a `snapshot` event
+//! ([`LiveSnapshot`]) if anything changed, and a comment line otherwise
+//! so dead connections are noticed. The server is a plain thread-per-
+//! connection HTTP loop, so each open dashboard holds one thread.
+//!
+//! Both routes need the admin role. Browsers can't put headers on a page
+//! load or an `EventSource`, so the key may also come as `?key=`; the page
+//! passes its own along to the stream.
+
+use super::{recent_decisions, RecentDecision};
+use crate::error::Result;
+use chrono::Utc;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+pub const PAGE: &str = include_str!("live.html");
+
+/// Seconds between checks for changes
+pub const POLL_SECS: u64 = 2;
+/// Seconds of silence before a keep-alive comment
+const KEEPALIVE_SECS: u64 = 15;
+/// Rows per list
+const ROWS: usize = 15;
+
+/// A decision still waiting for its outcome
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PendingOutcome {
+    pub decision_id: String,
+    pub question: String,
+    pub created_at: Option<String>,
+}
+
+/// One confidence adjustment an outcome made
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PosteriorMovement {
+    pub principle_id: String,
+    pub name: String,
+    pub delta: f64,
+    /// Learned confidence now
+    pub confidence: f64,
+    /// Thompson α / (α + β) now, once the principle has an arm
+    pub posterior_mean: Option<f64>,
+    pub decision_id: Option<String>,
+    pub at: Option<String>,
+}
+
+/// A watchdog alert as last raised
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlertEvent {
+    pub kind: String,
+    pub severity: String,
+    pub subject: String,
+    pub message: String,
+    pub last_seen: String,
+}
+
+/// What the live page shows, newest first in every list
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LiveSnapshot {
+    pub decisions: Vec<RecentDecision>,
+    pub pending_outcomes: Vec<PendingOutcome>,
+    /// Decisions without an outcome, beyond the listed ones too
+    pub pending_total: i64,
+    pub posterior_movements: Vec<PosteriorMovement>,
+    pub alerts: Vec<AlertEvent>,
+}
+
+pub fn snapshot(conn: &Connection) -> Result<LiveSnapshot> {
+    let pending_outcomes = conn
+        .prepare(
+            "SELECT id, question, created_at FROM decisions WHERE outcome_success IS NULL
+             ORDER BY created_at DESC, rowid DESC LIMIT ?1",
+        )?
+        .query_map([ROWS as i64], |row| {
+            Ok(PendingOutcome { decision_id: row.get(0)?, question: row.get(1)?, created_at: row.get(2)? })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    let pending_total = conn.query_row("SELECT COUNT(*) FROM decisions WHERE outcome_success IS NULL", [], |row| row.get(0))?;
+    let posterior_movements = conn
+        .prepare(
+            "SELECT fa.principle_id, COALESCE(p.name, fa.principle_id), fa.adjustment, COALESCE(p.learned_confidence, 0.5),
+                    t.alpha / (t.alpha + t.beta), fa.decision_id, fa.created_at
+             FROM framework_adjustments fa
+             LEFT JOIN principles p ON p.id = fa.principle_id
+             LEFT JOIN thompson_arms t ON t.principle_id = fa.principle_id
+             ORDER BY fa.id DESC LIMIT ?1",
+        )?
+        .query_map([ROWS as i64], |row| {
+            Ok(PosteriorMovement {
+                principle_id: row.get(0)?,
+                name: row.get(1)?,
+                delta: row.get(2)?,
+                confidence: row.get(3)?,
+                posterior_mean: row.get(4)?,
+                decision_id: row.get(5)?,
+                at: row.get(6)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    let alerts = conn
+        .prepare("SELECT kind, severity, subject, message, last_seen FROM watchdog_alerts ORDER BY last_seen DESC LIMIT ?1")?
+        .query_map([ROWS as i64], |row| {
+            Ok(AlertEvent {
+                kind: row.get(0)?,
+                severity: row.get(1)?,
+                subject: row.get(2)?,
+                message: row.get(3)?,
+                last_seen: row.get(4)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+
+    Ok(LiveSnapshot {
+        decisions: recent_decisions(conn, ROWS)?,
+        pending_outcomes,
+        pending_total,
+        posterior_movements,
+        alerts,
+    })
+}
+
+/// One SSE event
+pub fn event<T: Serialize>(name: &str, data: &T) -> Result<String> {
+    Ok(format!("event: {}\ndata: {}\n\n", name, serde_json::to_string(data)?))
+}
+
+/// The page, with the key it was opened with handed on to the stream
+pub fn page(key: Option<&str>) -> Result<String> {
+    Ok(PAGE.replace("__DASHBOARD_KEY__", &super::script_json(&key)?))
+}
+
+/// Send snapshots to `out` until the client goes away
+pub fn stream(conn: &Connection, out: &mut impl Write) -> Result<()> {
+    crate::eval::thompson::init_thompson_schema(conn)?;
+    out.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n")?;
+    out.write_all(format!("retry: {}\n\n", POLL_SECS * 1000).as_bytes())?;
+    let mut last: Option<LiveSnapshot> = None;
+    let mut last_write = Instant::now();
+    loop {
+        let current = snapshot(conn)?;
+        let sent = if last.as_ref() != Some(&current) {
+            out.write_all(event("snapshot", &serde_json::json!({"at": Utc::now().to_rfc3339(), "snapshot": current}))?.as_bytes())?;
+            last = Some(current);
+            true
+        } else if last_write.elapsed() >= Duration::from_secs(KEEPALIVE_SECS) {
+            out.write_all(b": keep-alive\n\n")?;
+            true
+        } else {
+            false
+        };
+        if sent {
+            // A closed connection shows up here and ends the stream
+            if out.flush().is_err() {
+                return Ok(());
+            }
+            last_write = Instant::now();
+        }
+        std::thread::sleep(Duration::from_secs(POLL_SECS));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_shows_pending_outcomes_movements_and_alerts() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        crate::eval::thompson::init_thompson_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description, learned_confidence) VALUES ('yagni', 't', 'YAGNI', 'd', 0.55);
+             INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success)
+                 VALUES ('d1', 'Build it?', '{}', 'h', 's', 'k', 1), ('d2', 'Buy it?', '{}', 'h', 's', 'k', NULL);
+             INSERT INTO framework_adjustments (principle_id, adjustment, decision_id) VALUES ('yagni', 0.05, 'd1');
+             INSERT INTO thompson_arms (principle_id, alpha, beta, pulls) VALUES ('yagni', 3.0, 1.0, 2);
+             INSERT INTO watchdog_alerts (key, kind, severity, subject, message, first_seen, last_seen)
+                 VALUES ('outcome_spike:outcomes', 'outcome_spike', 'warning', 'outcomes', '40 outcomes in an hour', 'x', 'y');",
+        )
+        .unwrap();
+
+        let live = snapshot(&conn).unwrap();
+        assert_eq!(live.decisions.len(), 2);
+        assert_eq!((live.pending_total, live.pending_outcomes[0].decision_id.as_str()), (1, "d2"));
+        let moved = &live.posterior_movements[0];
+        assert_eq!((moved.name.as_str(), moved.delta, moved.posterior_mean), ("YAGNI", 0.05, Some(0.75)));
+        assert_eq!(live.alerts[0].kind, "outcome_spike");
+
+        let sse = event("snapshot", &live).unwrap();
+        assert!(sse.starts_with("event: snapshot\ndata: {") && sse.ends_with("}\n\n"));
+        assert!(page(Some("hm_</script>")).unwrap().contains(r#"var key = "hm_\u003c/script>";"#));
+    }
+}
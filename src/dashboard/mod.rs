@@ -0,0 +1,161 @@
+//! Dashboards
+//!
+//! `100minds dashboard <out_dir>` writes `index.html`, a self-contained
+//! report that opens straight from disk or a CI artifact: learning stats,
+//! the most-pulled Thompson posteriors, thinker and domain coverage, recent
+//! decisions, and confidence/success sparklines. The data is embedded as
+//! JSON ([`DashboardData`]) and drawn by a few dozen lines of inline JS;
+//! nothing is fetched.
+//!
+//! `100minds serve` also has a live page at `/dashboard` (see [`live`]).
+
+pub mod live;
+
+use crate::eval::coverage::{self, CoverageAnalysis};
+use crate::outcome::{self, LearningStats};
+use crate::stats::timeseries::{self, TrendPoint};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+const REPORT_TEMPLATE: &str = include_str!("report.html");
+
+/// Posteriors shown, most pulled first
+const POSTERIORS: usize = 20;
+/// Decisions shown, newest first
+const RECENT_DECISIONS: usize = 25;
+/// Daily trend points in the sparklines
+const TREND_DAYS: usize = 90;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PosteriorRow {
+    pub principle_id: String,
+    pub name: String,
+    pub alpha: f64,
+    pub beta: f64,
+    /// α / (α + β)
+    pub mean: f64,
+    pub pulls: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecentDecision {
+    pub decision_id: String,
+    pub question: String,
+    pub created_at: Option<String>,
+    /// `None` while pending
+    pub outcome: Option<bool>,
+}
+
+/// Everything a dashboard shows
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardData {
+    pub generated_at: String,
+    pub learning: LearningStats,
+    pub coverage: CoverageAnalysis,
+    pub posteriors: Vec<PosteriorRow>,
+    pub recent_decisions: Vec<RecentDecision>,
+    /// Corpus-wide daily snapshots, oldest first
+    pub trends: Vec<TrendPoint>,
+}
+
+/// Gather the dashboard data (taking today's trend snapshot if missing)
+pub fn collect(conn: &Connection, now: DateTime<Utc>) -> Result<DashboardData> {
+    crate::eval::thompson::init_thompson_schema(conn)?;
+    timeseries::record_snapshot(conn, now)?;
+    let mut trends = timeseries::series(conn, None)?.points;
+    trends.drain(..trends.len().saturating_sub(TREND_DAYS));
+
+    let posteriors = conn
+        .prepare(
+            "SELECT t.principle_id, COALESCE(p.name, t.principle_id), t.alpha, t.beta, t.pulls
+             FROM thompson_arms t LEFT JOIN principles p ON p.id = t.principle_id
+             ORDER BY t.pulls DESC, t.principle_id LIMIT ?1",
+        )?
+        .query_map([POSTERIORS as i64], |row| {
+            let (alpha, beta): (f64, f64) = (row.get(2)?, row.get(3)?);
+            Ok(PosteriorRow {
+                principle_id: row.get(0)?,
+                name: row.get(1)?,
+                alpha,
+                beta,
+                mean: alpha / (alpha + beta),
+                pulls: row.get(4)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    let recent_decisions = recent_decisions(conn, RECENT_DECISIONS)?;
+
+    Ok(DashboardData {
+        generated_at: now.format("%Y-%m-%d %H:%M UTC").to_string(),
+        learning: outcome::get_learning_stats(conn)?,
+        coverage: coverage::analyze_coverage(conn)?,
+        posteriors,
+        recent_decisions,
+        trends,
+    })
+}
+
+/// The newest `limit` decisions
+pub fn recent_decisions(conn: &Connection, limit: usize) -> Result<Vec<RecentDecision>> {
+    let decisions = conn
+        .prepare("SELECT id, question, created_at, outcome_success FROM decisions ORDER BY created_at DESC, rowid DESC LIMIT ?1")?
+        .query_map([limit as i64], |row| {
+            Ok(RecentDecision {
+                decision_id: row.get(0)?,
+                question: row.get(1)?,
+                created_at: row.get(2)?,
+                outcome: row.get::<_, Option<i64>>(3)?.map(|s| s != 0),
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(decisions)
+}
+
+/// JSON that can sit inside a `<script>` element: no `<` to end it early
+pub(crate) fn script_json<T: Serialize>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string(value)?.replace('<', "\\u003c"))
+}
+
+/// The report as one HTML page
+pub fn render(data: &DashboardData) -> Result<String> {
+    Ok(REPORT_TEMPLATE.replace("__DASHBOARD_DATA__", &script_json(data)?))
+}
+
+/// Write `index.html` into `out_dir` (created if needed)
+pub fn write(conn: &Connection, out_dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(out_dir)?;
+    let path = out_dir.join("index.html");
+    std::fs::write(&path, render(&collect(conn, Utc::now())?)?)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_embeds_data_safely() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success)
+             VALUES ('d1', 'Is </script><b>x</b> safe?', '{}', 'h', 's', 'k', 1)",
+            [],
+        )
+        .unwrap();
+
+        let path = write(&conn, &dir.path().join("out")).unwrap();
+        let html = std::fs::read_to_string(path).unwrap();
+        assert!(!html.contains("__DASHBOARD_DATA__"));
+        assert!(!html.contains("</script><b>"));
+        let start = html.find(r#"id="dashboard-data">"#).unwrap() + r#"id="dashboard-data">"#.len();
+        let end = start + html[start..].find("</script>").unwrap();
+        let data: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+        assert_eq!(data["recent_decisions"][0]["question"], "Is </script><b>x</b> safe?");
+        assert_eq!(data["learning"]["total_outcomes"], 1);
+        assert_eq!(data["trends"].as_array().unwrap().len(), 1);
+    }
+}
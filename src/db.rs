@@ -4,20 +4,174 @@
 //! Designed for simplicity (Dijkstra) and antifragility (Taleb) -
 //! single file, zero network dependencies, works offline.
 
-use anyhow::{Context, Result};
+use crate::search;
+use crate::error::Result;
+use anyhow::Context;
 use rusqlite::{params, Connection};
+use std::collections::HashSet;
 use std::path::Path;
 
 /// Initialize the database with schema
 pub fn init_db(path: &Path) -> Result<Connection> {
+    #[cfg(feature = "vec")]
+    crate::embeddings::register_vec_extension();
     let conn =
         Connection::open(path).with_context(|| format!("Failed to open database at {:?}", path))?;
+    crate::encryption::unlock(&conn, path)?;
 
     conn.execute_batch(SCHEMA)?;
+    migrate(&conn)?;
+    crate::embeddings::migrate_vec(&conn)?;
 
     Ok(conn)
 }
 
+/// Columns added to tables that older databases already have
+fn migrate(conn: &Connection) -> Result<()> {
+    let has_actor: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('framework_adjustments') WHERE name = 'actor_id'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_actor {
+        conn.execute_batch(
+            "ALTER TABLE framework_adjustments ADD COLUMN actor_id TEXT;
+             CREATE INDEX IF NOT EXISTS idx_adjustments_actor ON framework_adjustments(actor_id);",
+        )?;
+    }
+    let has_language: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('decisions') WHERE name = 'language'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_language {
+        // Language the question was asked in ("en", "es", "de"); NULL before detection
+        conn.execute_batch("ALTER TABLE decisions ADD COLUMN language TEXT;")?;
+    }
+    let has_failure_stage: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('decisions') WHERE name = 'failure_stage'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_failure_stage {
+        // Where a failed outcome went wrong (`stats::failures`); older
+        // databases only kept the raw stage in the adjustment context
+        conn.execute_batch(
+            "ALTER TABLE decisions ADD COLUMN failure_stage TEXT;
+             ALTER TABLE decisions ADD COLUMN failure_reasons TEXT;",
+        )?;
+        crate::stats::failures::backfill(conn)?;
+    }
+    let has_parent: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('decisions') WHERE name = 'parent_decision_id'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_parent {
+        // Decision a follow-up refines (`thread`); NULL for fresh questions
+        conn.execute_batch(
+            "ALTER TABLE decisions ADD COLUMN parent_decision_id TEXT;
+             CREATE INDEX IF NOT EXISTS idx_decisions_parent ON decisions(parent_decision_id);",
+        )?;
+    }
+    let has_session: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('decisions') WHERE name = 'session_id'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_session {
+        // REPL/TUI session the decision was made in (`session`)
+        conn.execute_batch(
+            "ALTER TABLE decisions ADD COLUMN session_id TEXT;
+             CREATE INDEX IF NOT EXISTS idx_decisions_session ON decisions(session_id);",
+        )?;
+    }
+    let has_action_prompt: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('principles') WHERE name = 'action_prompt'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_action_prompt {
+        // Curated ACTION line for counsel positions (`action_prompts`)
+        conn.execute_batch("ALTER TABLE principles ADD COLUMN action_prompt TEXT;")?;
+    }
+    let has_forge_repo: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('decision_links') WHERE name = 'forge_repo'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_forge_repo {
+        // Forge repository webhooks are scoped to (`webhooks`); NULL reads the origin remote
+        conn.execute_batch("ALTER TABLE decision_links ADD COLUMN forge_repo TEXT;")?;
+    }
+    let has_prompt_rules: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'action_prompt_rules'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_prompt_rules {
+        crate::action_prompts::create_rules_table(conn)?;
+    }
+    let fts_stems: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE name = 'principles_fts' AND sql LIKE '%porter%'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !fts_stems {
+        // Older indexes used the plain unicode61 tokenizer; rebuild with stemming
+        conn.execute_batch(
+            "DROP TABLE principles_fts;
+             CREATE VIRTUAL TABLE principles_fts USING fts5(
+                 name, description, application_rule,
+                 content=principles, content_rowid=rowid,
+                 tokenize='porter unicode61 remove_diacritics 2'
+             );
+             INSERT INTO principles_fts(principles_fts) VALUES ('rebuild');",
+        )?;
+    }
+    let fts_empty: bool = conn.query_row(
+        "SELECT NOT EXISTS (SELECT 1 FROM decisions_fts) AND EXISTS (SELECT 1 FROM decisions)",
+        [],
+        |row| row.get(0),
+    )?;
+    if fts_empty {
+        // Decisions stored before the history index existed
+        conn.execute_batch(
+            "INSERT INTO decisions_fts(decision_id, question, arguments, notes)
+             SELECT id, question,
+                    (SELECT group_concat(json_extract(value, '$.argument'), ' ')
+                     FROM json_each(CASE WHEN json_valid(counsel_json) THEN counsel_json ELSE '{}' END, '$.positions')),
+                    outcome_notes
+             FROM decisions;",
+        )?;
+    }
+    Ok(())
+}
+
+/// Run `f` inside a named SAVEPOINT: released if it succeeds, rolled back
+/// if it fails. At top level this behaves as a transaction; inside an open
+/// transaction (or another savepoint) it nests. Works with any error type a
+/// SQLite error converts into, so anyhow and `MindsError` callers share it.
+pub fn with_savepoint<T, E: From<rusqlite::Error>>(
+    conn: &Connection,
+    name: &str,
+    f: impl FnOnce() -> std::result::Result<T, E>,
+) -> std::result::Result<T, E> {
+    conn.execute_batch(&format!("SAVEPOINT {}", name))?;
+    match f() {
+        Ok(value) => {
+            conn.execute_batch(&format!("RELEASE {}", name))?;
+            Ok(value)
+        }
+        Err(e) => {
+            if let Err(rollback) = conn.execute_batch(&format!("ROLLBACK TO {0}; RELEASE {0}", name)) {
+                tracing::error!("Rollback of savepoint {} failed: {}", name, rollback);
+            }
+            Err(e)
+        }
+    }
+}
+
 const SCHEMA: &str = r#"
 -- Thinkers: The 100 minds
 CREATE TABLE IF NOT EXISTS thinkers (
@@ -44,13 +198,14 @@ CREATE TABLE IF NOT EXISTS principles (
     UNIQUE(thinker_id, name)
 );
 
--- FTS5 index for fast principle search
+-- FTS5 index for fast principle search (porter stemming, accents folded)
 CREATE VIRTUAL TABLE IF NOT EXISTS principles_fts USING fts5(
     name,
     description,
     application_rule,
     content=principles,
-    content_rowid=rowid
+    content_rowid=rowid,
+    tokenize='porter unicode61 remove_diacritics 2'
 );
 
 -- Triggers to keep FTS in sync
@@ -95,6 +250,42 @@ CREATE TABLE IF NOT EXISTS decisions (
 CREATE INDEX IF NOT EXISTS idx_decisions_hash ON decisions(content_hash);
 CREATE INDEX IF NOT EXISTS idx_decisions_created ON decisions(created_at);
 
+-- FTS5 index over decision history: questions, the positions' arguments, outcome notes (see history.rs)
+CREATE VIRTUAL TABLE IF NOT EXISTS decisions_fts USING fts5(
+    decision_id UNINDEXED,
+    question,
+    arguments,
+    notes,
+    tokenize='porter unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS decisions_fts_ai AFTER INSERT ON decisions BEGIN
+    INSERT INTO decisions_fts(decision_id, question, arguments, notes)
+    VALUES (
+        new.id,
+        new.question,
+        (SELECT group_concat(json_extract(value, '$.argument'), ' ')
+         FROM json_each(CASE WHEN json_valid(new.counsel_json) THEN new.counsel_json ELSE '{}' END, '$.positions')),
+        new.outcome_notes
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS decisions_fts_ad AFTER DELETE ON decisions BEGIN
+    DELETE FROM decisions_fts WHERE decision_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS decisions_fts_au AFTER UPDATE OF question, counsel_json, outcome_notes ON decisions BEGIN
+    DELETE FROM decisions_fts WHERE decision_id = old.id;
+    INSERT INTO decisions_fts(decision_id, question, arguments, notes)
+    VALUES (
+        new.id,
+        new.question,
+        (SELECT group_concat(json_extract(value, '$.argument'), ' ')
+         FROM json_each(CASE WHEN json_valid(new.counsel_json) THEN new.counsel_json ELSE '{}' END, '$.positions')),
+        new.outcome_notes
+    );
+END;
+
 -- Framework adjustments: What we've learned from outcomes
 CREATE TABLE IF NOT EXISTS framework_adjustments (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -177,12 +368,412 @@ CREATE TABLE IF NOT EXISTS hard_negatives (
 );
 
 CREATE INDEX IF NOT EXISTS idx_hard_negatives_question ON hard_negatives(question_hash);
+
+-- Thinker reliability: Outcome track record per thinker
+-- Thinkers whose principles keep leading to failures count less in ranking
+CREATE TABLE IF NOT EXISTS thinker_reliability (
+    thinker_id TEXT PRIMARY KEY REFERENCES thinkers(id),
+    successes INTEGER DEFAULT 0,
+    failures INTEGER DEFAULT 0,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Calibration models: Fitted corrections for stated position confidence
+-- At most one row is active; counsel applies it to returned confidences
+CREATE TABLE IF NOT EXISTS calibration_models (
+    method TEXT PRIMARY KEY,        -- "platt" | "isotonic"
+    params_json TEXT NOT NULL,
+    sample_count INTEGER NOT NULL,
+    active INTEGER DEFAULT 0,
+    fitted_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Decision links: Branch/PR that implemented a decision
+-- Git history on these links drives outcome suggestions
+CREATE TABLE IF NOT EXISTS decision_links (
+    decision_id TEXT PRIMARY KEY,
+    branch TEXT,
+    pr_number INTEGER,
+    repo_path TEXT NOT NULL,
+    forge_repo TEXT,                -- "owner/name" on the forge, for webhooks
+    linked_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Counsel jobs: Questions queued for background workers (`100minds worker`)
+-- status: queued -> running -> done | failed
+CREATE TABLE IF NOT EXISTS counsel_jobs (
+    id TEXT PRIMARY KEY,
+    request_json TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'queued',
+    result_json TEXT,
+    error TEXT,
+    worker_id TEXT,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    started_at TEXT,
+    finished_at TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_counsel_jobs_status ON counsel_jobs(status, created_at);
+
+-- Daemon runs: Last run of each scheduled maintenance job (`100minds daemon`)
+CREATE TABLE IF NOT EXISTS daemon_runs (
+    job TEXT PRIMARY KEY,
+    last_run_at TEXT NOT NULL,
+    last_status TEXT NOT NULL,
+    last_error TEXT,
+    duration_ms INTEGER,
+    next_run_at TEXT NOT NULL
+);
+
+-- Outcome keys: Applied (decision, idempotency key) pairs and their results
+-- A repeat of the same pair is reported as a duplicate instead of re-applied
+CREATE TABLE IF NOT EXISTS outcome_keys (
+    decision_id TEXT NOT NULL,
+    idempotency_key TEXT NOT NULL,
+    result_json TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (decision_id, idempotency_key)
+);
+
+-- Counsel keys: Idempotency key of a counsel call -> the decision it created
+CREATE TABLE IF NOT EXISTS counsel_keys (
+    idempotency_key TEXT PRIMARY KEY,
+    decision_id TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Coverage counters: Running selection tallies kept at decision time
+-- kind: 'decisions' (key ''), 'thinker' (thinker id), 'principle' (cited name/id, lowercased), 'domain'
+CREATE TABLE IF NOT EXISTS coverage_counters (
+    kind TEXT NOT NULL,
+    key TEXT NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (kind, key)
+);
+
+-- Thinker packs: Installed community corpora (see packs.rs)
+CREATE TABLE IF NOT EXISTS thinker_packs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    version TEXT NOT NULL,
+    author TEXT NOT NULL DEFAULT '',
+    signed_by TEXT,                 -- Signer public key (hex), NULL if unsigned
+    manifest_json TEXT NOT NULL,
+    installed_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS pack_thinkers (
+    pack_id TEXT NOT NULL,
+    thinker_id TEXT PRIMARY KEY
+);
+
+-- Principle aliases: Merged duplicate -> surviving principle (see corpus.rs)
+CREATE TABLE IF NOT EXISTS principle_aliases (
+    merged_id TEXT PRIMARY KEY,
+    kept_id TEXT NOT NULL,
+    merged_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Principle edges: Curated requires/conflicts/refines relations (see principle_graph.rs)
+CREATE TABLE IF NOT EXISTS principle_edges (
+    from_id TEXT NOT NULL,
+    relation TEXT NOT NULL,         -- 'requires' | 'conflicts' | 'refines'
+    to_id TEXT NOT NULL,
+    note TEXT,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (from_id, relation, to_id)
+);
+
+-- Principle policies: Organization house rules (see policy.rs)
+CREATE TABLE IF NOT EXISTS principle_policies (
+    principle_id TEXT PRIMARY KEY,
+    rule TEXT NOT NULL,             -- 'pinned' | 'banned'
+    reason TEXT,
+    source TEXT NOT NULL DEFAULT 'cli',  -- 'cli' | 'config'
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Actor arms: Contextual arms learned from one agent's or user's outcomes
+CREATE TABLE IF NOT EXISTS actor_arms (
+    actor_id TEXT NOT NULL,
+    principle_id TEXT NOT NULL,
+    domain TEXT NOT NULL,
+    alpha REAL DEFAULT 1.0,
+    beta REAL DEFAULT 1.0,
+    sample_count INTEGER DEFAULT 0,
+    last_updated TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (actor_id, principle_id, domain)
+);
+
+-- Decay state: when each principle's confidence drift was last decayed
+CREATE TABLE IF NOT EXISTS principle_decay (
+    principle_id TEXT PRIMARY KEY,
+    last_decay_at TEXT NOT NULL,
+    cumulative_factor REAL DEFAULT 1.0  -- Product of every factor applied so far
+);
+
+-- Decay runs: Audit trail of each decay application
+CREATE TABLE IF NOT EXISTS decay_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_at TEXT NOT NULL,
+    daily_factor REAL NOT NULL,
+    policy TEXT NOT NULL,
+    principles_decayed INTEGER DEFAULT 0,
+    arms_decayed INTEGER DEFAULT 0
+);
+
+-- Decay log: Per-principle effect of a decay run
+CREATE TABLE IF NOT EXISTS decay_log (
+    run_id INTEGER NOT NULL REFERENCES decay_runs(id),
+    principle_id TEXT NOT NULL,
+    days REAL NOT NULL,
+    factor REAL NOT NULL,
+    confidence_before REAL,
+    confidence_after REAL,
+    PRIMARY KEY (run_id, principle_id)
+);
+
+-- Template outcomes: Which template leaf a decision followed, and how it went
+CREATE TABLE IF NOT EXISTS template_outcomes (
+    decision_id TEXT NOT NULL,
+    template_id TEXT NOT NULL,
+    leaf_path TEXT NOT NULL,        -- Option indices from the root, e.g. "0/1/0"
+    success INTEGER NOT NULL,
+    recorded_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (decision_id, template_id)
+);
+
+-- Blind spot checks: Blind spots raised for a decision, and how they were resolved
+CREATE TABLE IF NOT EXISTS blind_spot_checks (
+    decision_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    source_template TEXT,
+    check_question TEXT,
+    status TEXT NOT NULL DEFAULT 'open',  -- "open", "addressed" or "accepted_risk"
+    note TEXT,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TEXT,
+    PRIMARY KEY (decision_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_blind_spot_checks_open ON blind_spot_checks(severity, status);
+
+-- Checklist items: Actions, blind spot checks and watch conditions compiled from a decision (see checklist.rs)
+CREATE TABLE IF NOT EXISTS checklist_items (
+    decision_id TEXT NOT NULL,
+    item_id TEXT NOT NULL,          -- Derived from kind and text, e.g. "A-3f9c1a2b"
+    position INTEGER NOT NULL,
+    kind TEXT NOT NULL,             -- "action", "blind_spot" or "watch"
+    text TEXT NOT NULL,
+    source TEXT NOT NULL,           -- Thinker or template
+    principle TEXT,
+    done INTEGER NOT NULL DEFAULT 0,
+    done_at TEXT,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (decision_id, item_id)
+);
+
+-- Estimates: Task estimates checked with sanity_check_estimate, and how long the tasks really took (see estimates.rs)
+CREATE TABLE IF NOT EXISTS estimates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task TEXT NOT NULL,
+    estimate REAL NOT NULL,
+    unit TEXT NOT NULL,             -- Actual is in the same unit
+    actual REAL,                    -- NULL until recorded
+    decision_id TEXT,
+    embedding BLOB,                 -- Task embedding, f32 little-endian, once computed
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    actual_recorded_at TEXT
+);
+
+-- Risk registers: Risks raised for a decision, with human assessments
+CREATE TABLE IF NOT EXISTS risk_registers (
+    decision_id TEXT PRIMARY KEY,
+    content_hash TEXT NOT NULL,     -- Provenance hash of the decision it was built from
+    register_json TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Falsifications: Observed falsifiable_if conditions, one per decision position
+CREATE TABLE IF NOT EXISTS falsifications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    decision_id TEXT NOT NULL,
+    thinker_id TEXT NOT NULL,
+    condition TEXT NOT NULL,
+    evidence TEXT,
+    observed_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(decision_id, thinker_id)
+);
+
+-- API keys: Roles for the HTTP server (see access.rs); only key hashes are kept
+CREATE TABLE IF NOT EXISTS api_keys (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    role TEXT NOT NULL,             -- reader | recorder | admin
+    key_hash TEXT NOT NULL UNIQUE,  -- SHA-256 of the key
+    created_at TEXT NOT NULL,
+    last_used_at TEXT,
+    revoked_at TEXT
+);
+
+-- Redactions: SHA-256 of stored text's original, before PII was scrubbed (see redact.rs)
+-- field: question | context | counsel | outcome_notes
+CREATE TABLE IF NOT EXISTS redactions (
+    decision_id TEXT NOT NULL,
+    field TEXT NOT NULL,
+    original_sha256 TEXT NOT NULL,
+    kinds TEXT NOT NULL,            -- placeholders used, comma-separated
+    redacted_at TEXT NOT NULL,
+    PRIMARY KEY (decision_id, field)
+);
+
+-- Decision embeddings: Question vectors for similar-decision lookup (see history.rs)
+-- Dropped when the question changes or the decision goes, and re-embedded on demand
+CREATE TABLE IF NOT EXISTS decision_embeddings (
+    decision_id TEXT PRIMARY KEY,
+    embedding BLOB NOT NULL         -- f32 little-endian
+);
+
+CREATE TRIGGER IF NOT EXISTS decision_embeddings_au AFTER UPDATE OF question ON decisions BEGIN
+    DELETE FROM decision_embeddings WHERE decision_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS decision_embeddings_ad AFTER DELETE ON decisions BEGIN
+    DELETE FROM decision_embeddings WHERE decision_id = old.id;
+END;
+
+-- Decision tombstones: Chain fields of decisions removed by retention or forget (see retention.rs)
+-- Later decisions' previous_hash still resolves, so a gap isn't mistaken for tampering
+CREATE TABLE IF NOT EXISTS decision_tombstones (
+    decision_id TEXT PRIMARY KEY,
+    previous_hash TEXT,
+    content_hash TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    agent_pubkey TEXT NOT NULL,
+    created_at TEXT,
+    removed_at TEXT NOT NULL,
+    reason TEXT NOT NULL            -- 'retention', or 'forget <deletion event id>'
+);
+
+-- Deletion events: Signed records of `100minds forget` runs (see forget.rs)
+CREATE TABLE IF NOT EXISTS deletion_events (
+    id TEXT PRIMARY KEY,
+    mode TEXT NOT NULL,             -- 'remove' or 'redact'
+    decision_ids TEXT NOT NULL,     -- JSON array
+    content_hashes TEXT NOT NULL,   -- JSON array, the decisions' signed hashes
+    previous_hash TEXT,
+    content_hash TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    signer_pubkey TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+-- Adjustment rollups: Monthly per-principle totals of pruned framework adjustments
+CREATE TABLE IF NOT EXISTS adjustment_rollups (
+    principle_id TEXT NOT NULL,
+    month TEXT NOT NULL,            -- YYYY-MM
+    adjustments INTEGER NOT NULL,
+    total REAL NOT NULL,
+    gains INTEGER NOT NULL,
+    losses INTEGER NOT NULL,
+    PRIMARY KEY (principle_id, month)
+);
+
+-- Decision approvals: Review state of escalated decisions (see approval.rs)
+-- status: pending_review -> approved | rejected
+CREATE TABLE IF NOT EXISTS decision_approvals (
+    decision_id TEXT PRIMARY KEY,
+    status TEXT NOT NULL DEFAULT 'pending_review',
+    requested_at TEXT NOT NULL,
+    reviewer TEXT,
+    note TEXT,
+    reviewed_at TEXT,
+    signature TEXT,                 -- Ed25519 over the review and the decision's content hash
+    signer_pubkey TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_decision_approvals_status ON decision_approvals(status, requested_at);
+
+-- Trend snapshots: Daily confidence and rolling success rate (see stats/timeseries.rs)
+-- scope: 'all' (key ''), 'principle' (principle id), 'domain' (lowercased tag)
+CREATE TABLE IF NOT EXISTS trend_snapshots (
+    taken_on TEXT NOT NULL,         -- UTC date; a later snapshot that day replaces it
+    scope TEXT NOT NULL,
+    key TEXT NOT NULL,
+    confidence REAL,                -- Mean learned confidence
+    outcomes INTEGER NOT NULL DEFAULT 0,
+    success_rate REAL,              -- NULL when the window had no outcomes
+    PRIMARY KEY (scope, key, taken_on)
+);
+
+-- Event marks: Bookmarked corpus/config changes that split decisions into cohorts
+CREATE TABLE IF NOT EXISTS event_marks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    label TEXT NOT NULL,
+    marked_at TEXT NOT NULL
+);
+
+-- Outcome log: Source of each applied outcome request, for per-source rate limits
+CREATE TABLE IF NOT EXISTS outcome_log (
+    source TEXT NOT NULL,           -- actor_id, or 'unattributed'
+    decision_id TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_outcome_log_source ON outcome_log(source, recorded_at);
+
+-- Quarantined outcomes: Requests the outcome guard held for review (see outcome_guard.rs)
+CREATE TABLE IF NOT EXISTS quarantined_outcomes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    batch_id TEXT,                  -- Shared by a quarantined batch, NULL for one request
+    source TEXT NOT NULL,
+    decision_id TEXT NOT NULL,
+    request_json TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Watchdog alerts: Suspicious learning patterns already raised (see watchdog.rs)
+CREATE TABLE IF NOT EXISTS watchdog_alerts (
+    key TEXT PRIMARY KEY,           -- kind:subject
+    kind TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    message TEXT NOT NULL,
+    first_seen TEXT NOT NULL,
+    last_seen TEXT NOT NULL
+);
+
+-- Notifications sent: Escalations and blind spots already posted to chat (see notifier.rs)
+CREATE TABLE IF NOT EXISTS notifications_sent (
+    kind TEXT NOT NULL,             -- escalation | blind_spot
+    key TEXT NOT NULL,              -- decision id, or decision_id:blind spot name
+    sent_at TEXT NOT NULL,
+    PRIMARY KEY (kind, key)
+);
+
+-- Benchmark runs: Every `--benchmark all` report, for regression tracking (see eval/runs.rs)
+CREATE TABLE IF NOT EXISTS benchmark_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_at TEXT NOT NULL,
+    code_version TEXT NOT NULL,     -- crate version, or MINDS_CODE_VERSION
+    corpus_version TEXT NOT NULL,   -- principle count and content hash
+    overall_score REAL NOT NULL,
+    metrics_json TEXT NOT NULL,     -- the tracked metrics, by name
+    report_json TEXT NOT NULL
+);
 "#;
 
 /// Get the latest decision hash for chain linking
 pub fn get_latest_decision_hash(conn: &Connection) -> Result<Option<String>> {
-    let mut stmt =
-        conn.prepare("SELECT content_hash FROM decisions ORDER BY created_at DESC LIMIT 1")?;
+    // Tombstones count, or pruning the newest decision would restart the
+    // chain; deletion events are links in it too
+    let mut stmt = conn.prepare(
+        "SELECT content_hash FROM (
+             SELECT content_hash, created_at, 2 AS live, rowid FROM decisions
+             UNION ALL SELECT content_hash, created_at, 1, rowid FROM deletion_events
+             UNION ALL SELECT content_hash, created_at, 0, rowid FROM decision_tombstones
+         ) ORDER BY created_at DESC, live DESC, rowid DESC LIMIT 1",
+    )?;
 
     let hash: Option<String> = stmt.query_row([], |row| row.get(0)).ok();
     Ok(hash)
@@ -194,20 +785,13 @@ pub fn search_principles(
     query: &str,
     limit: usize,
 ) -> Result<Vec<PrincipleMatch>> {
-    // Extract keywords from query (alphanumeric words only)
-    // Take more keywords to support expanded queries
-    let keywords: Vec<&str> = query
-        .split(|c: char| !c.is_alphanumeric())
-        .filter(|w| w.len() > 2)
-        .take(15) // Increased to support semantic expansion
-        .collect();
-
-    if keywords.is_empty() {
+    // Content words only, quoted so user input can't inject FTS5 syntax
+    let Some(fts_query) = search::fts_query(query) else {
         return Ok(Vec::new());
-    }
+    };
+    let keywords = search::keywords(query);
 
     // Try FTS5 first with simple keyword OR
-    let fts_query = keywords.join(" OR ");
     let fts_result = conn.prepare(
         r#"
         SELECT p.id, p.thinker_id, p.name, p.description, p.learned_confidence,
@@ -276,6 +860,27 @@ pub struct PrincipleMatch {
     pub relevance_score: f64,
 }
 
+/// Every principle in the corpus
+pub fn get_all_principles(conn: &Connection) -> Result<Vec<PrincipleMatch>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, thinker_id, name, description, learned_confidence, 0.0 FROM principles ORDER BY id",
+    )?;
+    let matches = stmt
+        .query_map([], |row| {
+            Ok(PrincipleMatch {
+                id: row.get(0)?,
+                thinker_id: row.get(1)?,
+                name: row.get(2)?,
+                description: row.get(3)?,
+                confidence: row.get(4)?,
+                relevance_score: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(matches)
+}
+
 /// Get principles by domain
 pub fn get_principles_by_domain(conn: &Connection, domain: &str) -> Result<Vec<PrincipleMatch>> {
     let mut stmt = conn.prepare(
@@ -333,9 +938,67 @@ pub fn insert_decision(
             agent_pubkey
         ],
     )?;
+    count_decision_coverage(conn, context_json, counsel_json)?;
     Ok(())
 }
 
+/// Add one decision to the coverage counters: the decision total, each
+/// thinker and cited principle it contains (once per decision), and its domain
+pub fn count_decision_coverage(conn: &Connection, context_json: Option<&str>, counsel_json: &str) -> Result<()> {
+    let counsel: serde_json::Value = serde_json::from_str(counsel_json).unwrap_or_default();
+    let mut thinkers = HashSet::new();
+    collect_thinker_ids(&counsel, &mut thinkers);
+
+    let principles: HashSet<String> = counsel
+        .get("positions")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|pos| pos.get("principles_cited").and_then(|c| c.as_array()))
+        .flatten()
+        .filter_map(|p| p.as_str())
+        .map(|p| p.to_lowercase())
+        .collect();
+
+    let domain = context_json
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(c).ok())
+        .and_then(|c| c.get("domain").and_then(|d| d.as_str()).map(String::from));
+
+    let mut bump = conn.prepare_cached(
+        "INSERT INTO coverage_counters (kind, key, count) VALUES (?1, ?2, 1)
+         ON CONFLICT(kind, key) DO UPDATE SET count = count + 1",
+    )?;
+    bump.execute(params!["decisions", ""])?;
+    for thinker in &thinkers {
+        bump.execute(params!["thinker", thinker])?;
+    }
+    for principle in &principles {
+        bump.execute(params!["principle", principle])?;
+    }
+    if let Some(domain) = domain {
+        bump.execute(params!["domain", domain])?;
+    }
+    Ok(())
+}
+
+/// Every `thinker_id` string anywhere in a counsel response
+fn collect_thinker_ids(value: &serde_json::Value, out: &mut HashSet<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map {
+                match (key.as_str(), v) {
+                    ("thinker_id", serde_json::Value::String(id)) => {
+                        out.insert(id.clone());
+                    }
+                    _ => collect_thinker_ids(v, out),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect_thinker_ids(v, out)),
+        _ => {}
+    }
+}
+
 /// Record an outcome for a decision
 pub fn record_outcome(
     conn: &Connection,
@@ -343,6 +1006,7 @@ pub fn record_outcome(
     success: bool,
     notes: Option<&str>,
 ) -> Result<()> {
+    let notes = notes.map(|n| crate::redact::scrub_notes(conn, decision_id, n)).transpose()?;
     conn.execute(
         r#"
         UPDATE decisions
@@ -371,12 +1035,12 @@ pub fn apply_adjustment(
         params![principle_id, context_pattern, adjustment, decision_id],
     )?;
 
-    // Update the learned confidence (bounded 0.0 - 1.0)
+    // Update the learned confidence (bounded 0.0 - 1.0; archived ones stay at -1)
     conn.execute(
         r#"
         UPDATE principles
         SET learned_confidence = MIN(1.0, MAX(0.0, learned_confidence + ?2))
-        WHERE id = ?1
+        WHERE id = ?1 AND learned_confidence >= 0
         "#,
         params![principle_id, adjustment],
     )?;
@@ -393,27 +1057,112 @@ pub fn update_contextual_arm(
     success: bool,
 ) -> Result<()> {
     // Upsert the contextual arm
+    let (alpha_delta, beta_delta) = crate::eval::bandit::beta_deltas(success);
     conn.execute(
         r#"
         INSERT INTO contextual_arms (principle_id, domain, alpha, beta, sample_count)
-        VALUES (?1, ?2, ?3, ?4, 1)
+        VALUES (?1, ?2, 1.0 + ?3, 1.0 + ?4, 1)
         ON CONFLICT(principle_id, domain) DO UPDATE SET
-            alpha = alpha + ?3 - 1,
-            beta = beta + ?4 - 1,
+            alpha = alpha + ?3,
+            beta = beta + ?4,
             sample_count = sample_count + 1,
             last_updated = CURRENT_TIMESTAMP
         "#,
-        params![
-            principle_id,
-            domain,
-            if success { 2.0 } else { 1.0 }, // Add 1 to alpha on success
-            if success { 1.0 } else { 2.0 }, // Add 1 to beta on failure
-        ],
+        params![principle_id, domain, alpha_delta, beta_delta],
     )?;
 
     Ok(())
 }
 
+/// Update one actor's arm for a principle in a domain
+pub fn update_actor_arm(
+    conn: &Connection,
+    actor_id: &str,
+    principle_id: &str,
+    domain: &str,
+    success: bool,
+) -> Result<()> {
+    let (alpha_delta, beta_delta) = crate::eval::bandit::beta_deltas(success);
+    conn.execute(
+        "INSERT INTO actor_arms (actor_id, principle_id, domain, alpha, beta, sample_count)
+         VALUES (?1, ?2, ?3, 1.0 + ?4, 1.0 + ?5, 1)
+         ON CONFLICT(actor_id, principle_id, domain) DO UPDATE SET
+             alpha = alpha + ?4,
+             beta = beta + ?5,
+             sample_count = sample_count + 1,
+             last_updated = CURRENT_TIMESTAMP",
+        params![actor_id, principle_id, domain, alpha_delta, beta_delta],
+    )?;
+    Ok(())
+}
+
+/// (alpha, beta, sample_count) of an actor's arm for a principle in a domain
+pub fn get_actor_arm(conn: &Connection, actor_id: &str, principle_id: &str, domain: &str) -> Option<(f64, f64, i64)> {
+    conn.query_row(
+        "SELECT alpha, beta, sample_count FROM actor_arms
+         WHERE actor_id = ?1 AND principle_id = ?2 AND domain = ?3",
+        params![actor_id, principle_id, domain],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )
+    .ok()
+}
+
+/// Credit (or blame) the thinkers behind the applied principles
+/// Each thinker is counted once per outcome, however many of their principles were cited
+pub fn update_thinker_reliability(
+    conn: &Connection,
+    principle_ids: &[String],
+    success: bool,
+) -> Result<()> {
+    let mut thinker_ids: Vec<String> = Vec::new();
+    for principle_id in principle_ids {
+        if let Ok(thinker_id) = conn.query_row::<String, _, _>(
+            "SELECT thinker_id FROM principles WHERE id = ?1",
+            [principle_id],
+            |row| row.get(0),
+        ) {
+            if !thinker_ids.contains(&thinker_id) {
+                thinker_ids.push(thinker_id);
+            }
+        }
+    }
+
+    for thinker_id in thinker_ids {
+        conn.execute(
+            r#"
+            INSERT INTO thinker_reliability (thinker_id, successes, failures)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(thinker_id) DO UPDATE SET
+                successes = successes + ?2,
+                failures = failures + ?3,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+            params![thinker_id, success as i64, !success as i64],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Multiplicative ranking factor in [0.5, 1.5)
+///
+/// 0.5 + Beta(1,1) posterior mean of the success rate: 1.0 with no
+/// track record, sinking toward 0.5 as failures pile up.
+pub fn reliability_factor(successes: i64, failures: i64) -> f64 {
+    0.5 + (successes as f64 + 1.0) / ((successes + failures) as f64 + 2.0)
+}
+
+/// Ranking factor for a thinker (1.0 if they have no recorded outcomes)
+pub fn get_thinker_reliability(conn: &Connection, thinker_id: &str) -> f64 {
+    conn.query_row(
+        "SELECT successes, failures FROM thinker_reliability WHERE thinker_id = ?1",
+        [thinker_id],
+        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+    )
+    .map(|(s, f)| reliability_factor(s, f))
+    .unwrap_or(1.0)
+}
+
 /// Get contextual confidence for a principle in a domain (Thompson Sampling mean)
 pub fn get_contextual_confidence(
     conn: &Connection,
@@ -869,7 +1618,7 @@ mod tests {
 
         // Sample with no data - should use default prior
         let sample = sample_contextual_arm(&conn, "p7", "testing").unwrap();
-        assert!(sample >= 0.0 && sample <= 1.0, "Sample should be in [0, 1]");
+        assert!((0.0..=1.0).contains(&sample), "Sample should be in [0, 1]");
 
         // Add data and sample again
         for _ in 0..5 {
@@ -877,7 +1626,7 @@ mod tests {
         }
 
         let sample = sample_contextual_arm(&conn, "p7", "testing").unwrap();
-        assert!(sample >= 0.0 && sample <= 1.0, "Sample should be in [0, 1]");
+        assert!((0.0..=1.0).contains(&sample), "Sample should be in [0, 1]");
         // With 5 successes, mean should be high
     }
 
@@ -936,4 +1685,31 @@ mod tests {
         let hash = get_latest_decision_hash(&conn).unwrap();
         assert_eq!(hash, Some("hash2".to_string()));
     }
+
+    #[test]
+    fn test_thinker_reliability() {
+        let (conn, _dir) = setup_test_db();
+        insert_test_thinker(&conn, "t1", "Thinker One", "systems");
+        insert_test_principle(&conn, "p1", "t1", "P One", "desc", "[]");
+        insert_test_principle(&conn, "p2", "t1", "P Two", "desc", "[]");
+
+        // No track record: neutral
+        assert_eq!(get_thinker_reliability(&conn, "t1"), 1.0);
+
+        // Two principles from one thinker count as one failure
+        let cited = vec!["p1".to_string(), "p2".to_string()];
+        update_thinker_reliability(&conn, &cited, false).unwrap();
+        let failures: i64 = conn
+            .query_row(
+                "SELECT failures FROM thinker_reliability WHERE thinker_id = 't1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(failures, 1);
+
+        update_thinker_reliability(&conn, &cited, false).unwrap();
+        assert!(get_thinker_reliability(&conn, "t1") < 1.0);
+        assert!(get_thinker_reliability(&conn, "t1") >= 0.5);
+    }
 }
@@ -0,0 +1,199 @@
+//! Temporal Decay
+//!
+//! Recent outcomes should matter more than old ones. Rather than resetting
+//! every confidence to base and replaying the adjustment history (which
+//! loses any outcome recorded mid-replay), each run shrinks a principle's
+//! drift (learned − base) by `0.95^days` since that principle was last
+//! decayed. That is only an approximation of a replay: decay applies to the
+//! current value, so an adjustment recorded between runs is shrunk by the
+//! whole gap at the next run rather than by its own age, and clamping on
+//! the outcome path means drift isn't a plain sum of adjustments. The error
+//! is bounded by one run interval, which is why the daemon decays daily;
+//! in exchange each update is a single in-place statement that can't
+//! clobber a concurrent outcome.
+//!
+//! A principle that has never been decayed starts its clock at its latest
+//! adjustment (its drift is at least that old), or at the run itself if it
+//! has none. Every run is recorded in `decay_runs`, with its per-principle
+//! effect in `decay_log`. Culled principles (confidence -1) stay archived.
+
+use crate::eval::bandit::{BetaPosterior, PolicyEngine};
+use crate::error::Result;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+/// Share of a principle's drift that survives one day (half-life ≈ 13 days)
+pub const DAILY_DECAY: f64 = 0.95;
+
+/// Per-run shrink for contextual arms with plenty of evidence
+const ARM_DECAY: f64 = 0.9;
+
+/// Contextual arms with more samples than this are decayed
+const ARM_MIN_SAMPLES: i64 = 50;
+
+const TIMESTAMP: &str = "%Y-%m-%d %H:%M:%S";
+
+/// What one decay run did
+#[derive(Debug, Clone, Serialize)]
+pub struct DecayReport {
+    pub run_id: i64,
+    pub run_at: String,
+    pub policy: String,
+    /// Principles whose confidence moved toward base
+    pub principles_decayed: usize,
+    /// Principles whose decay clock started this run
+    pub clocks_started: usize,
+    pub arms_decayed: usize,
+}
+
+/// Decay every principle's drift up to `now`, and well-sampled contextual arms through `policy`
+pub fn apply_decay(conn: &Connection, policy: &dyn PolicyEngine, now: DateTime<Utc>) -> Result<DecayReport> {
+    let run_at = now.format(TIMESTAMP).to_string();
+    crate::db::with_savepoint(conn, "apply_decay", || {
+        conn.execute(
+            "INSERT INTO decay_runs (run_at, daily_factor, policy) VALUES (?1, ?2, ?3)",
+            params![run_at, DAILY_DECAY, policy.name()],
+        )?;
+        let run_id = conn.last_insert_rowid();
+
+        let principles: Vec<(String, Option<String>)> = conn
+            .prepare(
+                "SELECT p.id, COALESCE(d.last_decay_at,
+                        (SELECT MAX(created_at) FROM framework_adjustments fa WHERE fa.principle_id = p.id))
+                 FROM principles p LEFT JOIN principle_decay d ON d.principle_id = p.id
+                 WHERE COALESCE(p.learned_confidence, 0.5) >= 0",
+            )?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut principles_decayed = 0;
+        let mut clocks_started = 0;
+        for (principle_id, since) in principles {
+            let days = since
+                .and_then(|s| NaiveDateTime::parse_from_str(&s, TIMESTAMP).ok())
+                .map(|since| ((now.naive_utc() - since).num_seconds().max(0) as f64) / 86_400.0);
+            let Some(days) = days else {
+                clocks_started += 1;
+                conn.execute(
+                    "INSERT OR REPLACE INTO principle_decay (principle_id, last_decay_at, cumulative_factor)
+                     VALUES (?1, ?2, 1.0)",
+                    params![principle_id, run_at],
+                )?;
+                continue;
+            };
+            let factor = DAILY_DECAY.powf(days);
+
+            let before: f64 = conn.query_row(
+                "SELECT COALESCE(learned_confidence, 0.5) FROM principles WHERE id = ?1",
+                [&principle_id],
+                |row| row.get(0),
+            )?;
+            conn.execute(
+                "UPDATE principles
+                 SET learned_confidence = MIN(1.0, MAX(0.0,
+                     COALESCE(base_confidence, 0.5) + (COALESCE(learned_confidence, 0.5) - COALESCE(base_confidence, 0.5)) * ?2))
+                 WHERE id = ?1",
+                params![principle_id, factor],
+            )?;
+            let after: f64 = conn.query_row(
+                "SELECT learned_confidence FROM principles WHERE id = ?1",
+                [&principle_id],
+                |row| row.get(0),
+            )?;
+            conn.execute(
+                "INSERT INTO principle_decay (principle_id, last_decay_at, cumulative_factor) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(principle_id) DO UPDATE SET
+                     last_decay_at = excluded.last_decay_at,
+                     cumulative_factor = cumulative_factor * excluded.cumulative_factor",
+                params![principle_id, run_at, factor],
+            )?;
+            if (after - before).abs() > 1e-12 {
+                principles_decayed += 1;
+                conn.execute(
+                    "INSERT INTO decay_log (run_id, principle_id, days, factor, confidence_before, confidence_after)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![run_id, principle_id, days, factor, before, after],
+                )?;
+            }
+        }
+
+        let arms: Vec<(i64, f64, f64, i64)> = conn
+            .prepare("SELECT id, alpha, beta, sample_count FROM contextual_arms WHERE sample_count > ?1")?
+            .query_map([ARM_MIN_SAMPLES], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (id, alpha, beta, samples) in &arms {
+            let mut arm = BetaPosterior::from_arm(*alpha, *beta, *samples);
+            policy.decay(&mut arm, ARM_DECAY);
+            conn.execute(
+                "UPDATE contextual_arms SET alpha = ?2, beta = ?3 WHERE id = ?1",
+                params![id, arm.alpha, arm.beta],
+            )?;
+        }
+
+        conn.execute(
+            "UPDATE decay_runs SET principles_decayed = ?2, arms_decayed = ?3 WHERE id = ?1",
+            params![run_id, principles_decayed, arms.len()],
+        )?;
+        Ok(DecayReport {
+            run_id,
+            run_at: run_at.clone(),
+            policy: policy.name().to_string(),
+            principles_decayed,
+            clocks_started,
+            arms_decayed: arms.len(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::bandit::ThompsonPolicy;
+    use crate::eval::thompson::FGTSConfig;
+    use chrono::Duration;
+
+    #[test]
+    fn test_decay_is_incremental_and_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description, base_confidence, learned_confidence)
+                 VALUES ('p', 't', 'P', 'Drifted', 0.5, 0.7),
+                        ('q', 't', 'Q', 'Never adjusted', 0.5, 0.5);
+             INSERT INTO framework_adjustments (principle_id, context_pattern, adjustment, decision_id, created_at)
+                 VALUES ('p', 'ctx', 0.2, NULL, '2026-01-01 00:00:00');",
+        )
+        .unwrap();
+        let policy = ThompsonPolicy { config: FGTSConfig::default() };
+        let start = NaiveDateTime::parse_from_str("2026-01-01 00:00:00", TIMESTAMP).unwrap().and_utc();
+
+        // Ten days after the adjustment: drift 0.2 → 0.2·0.95^10
+        let report = apply_decay(&conn, &policy, start + Duration::days(10)).unwrap();
+        assert_eq!((report.principles_decayed, report.clocks_started), (1, 1));
+        let confidence = |id: &str| -> f64 {
+            conn.query_row("SELECT learned_confidence FROM principles WHERE id = ?1", [id], |r| r.get(0)).unwrap()
+        };
+        assert!((confidence("p") - (0.5 + 0.2 * 0.95f64.powi(10))).abs() < 1e-9);
+
+        // An outcome lands between runs; the next run decays the whole drift, that outcome
+        // included, by the days since the last run rather than by the outcome's own age
+        conn.execute("UPDATE principles SET learned_confidence = learned_confidence + 0.1 WHERE id = 'p'", []).unwrap();
+        let before = confidence("p");
+        apply_decay(&conn, &policy, start + Duration::days(15)).unwrap();
+        assert!((confidence("p") - (0.5 + (before - 0.5) * 0.95f64.powi(5))).abs() < 1e-9);
+        assert!((confidence("q") - 0.5).abs() < 1e-12);
+
+        let (runs, logged, cumulative): (i64, i64, f64) = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM decay_runs), (SELECT COUNT(*) FROM decay_log WHERE principle_id = 'p'),
+                        (SELECT cumulative_factor FROM principle_decay WHERE principle_id = 'p')",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!((runs, logged), (2, 2));
+        assert!((cumulative - 0.95f64.powi(15)).abs() < 1e-9);
+    }
+}
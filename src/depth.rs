@@ -0,0 +1,119 @@
+//! Automatic Counsel Depth
+//!
+//! `depth: "auto"` lets the question decide how many positions it gets.
+//! "Should we use Redis?" doesn't need six perspectives; a question that
+//! weighs a migration against hiring under a deadline suffers with three.
+//! Three signals are scored 0-2 each:
+//!
+//! - length, in words
+//! - clauses: commas, semicolons and connectives like "but", "while", "unless"
+//! - template breadth: how many decision templates the question triggers
+//!
+//! A total of 0-1 is Quick, 2-3 Standard, and 4 or more Deep. The pick and
+//! the signals behind it are reported in the response's `depth` field.
+
+use crate::templates;
+use crate::types::{CounselDepth, CounselRequest};
+use serde::{Deserialize, Serialize};
+
+/// Words at or below which a question counts as short
+pub const SHORT_WORDS: usize = 10;
+/// Words above which a question counts as long
+pub const LONG_WORDS: usize = 30;
+
+/// Separators that start another clause
+const CLAUSE_MARKERS: [&str; 14] = [
+    ",", ";", " and ", " but ", " or ", " while ", " because ", " if ", " unless ", " whether ", " versus ",
+    " vs ", " although ", " given ",
+];
+
+/// The depth `auto` resolved to, and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepthChoice {
+    pub depth: CounselDepth,
+    /// Sum of the signal scores (0-6)
+    pub complexity: u32,
+    pub words: usize,
+    pub clauses: usize,
+    /// IDs of the decision templates the question triggers
+    pub templates: Vec<String>,
+    pub reasons: Vec<String>,
+}
+
+/// Estimate the question's complexity and pick a depth for it
+pub fn choose(question: &str) -> DepthChoice {
+    let lower = question.to_lowercase();
+    let words = question.split_whitespace().count();
+    let questions = lower.matches('?').count().saturating_sub(1);
+    let clauses = 1 + questions + CLAUSE_MARKERS.iter().map(|m| lower.matches(m).count()).sum::<usize>();
+    let templates: Vec<String> = templates::match_templates(question).into_iter().map(|(t, _)| t.id).collect();
+
+    let length_score = match words {
+        w if w <= SHORT_WORDS => 0,
+        w if w <= LONG_WORDS => 1,
+        _ => 2,
+    };
+    let clause_score = match clauses {
+        1 => 0,
+        2 | 3 => 1,
+        _ => 2,
+    };
+    let template_score = templates.len().min(2) as u32;
+    let complexity = length_score + clause_score + template_score;
+
+    let depth = match complexity {
+        0 | 1 => CounselDepth::Quick,
+        2 | 3 => CounselDepth::Standard,
+        _ => CounselDepth::Deep,
+    };
+    let reasons = vec![
+        format!("{} words", words),
+        format!("{} clause{}", clauses, if clauses == 1 { "" } else { "s" }),
+        if templates.is_empty() {
+            "no decision template matched".to_string()
+        } else {
+            format!("matches {}", templates.join(", "))
+        },
+    ];
+    DepthChoice { depth, complexity, words, clauses, templates, reasons }
+}
+
+/// For a request asking for `auto`, a copy at the chosen depth and the choice
+pub fn resolve(request: &CounselRequest) -> Option<(CounselRequest, DepthChoice)> {
+    if request.context.depth != CounselDepth::Auto {
+        return None;
+    }
+    let choice = choose(&request.question);
+    let mut resolved = request.clone();
+    resolved.context.depth = choice.depth;
+    Some((resolved, choice))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_follows_question_complexity() {
+        let simple = choose("Should we use Redis?");
+        assert_eq!((simple.depth, simple.clauses), (CounselDepth::Quick, 1));
+
+        let complex = choose(
+            "Should we migrate the monolith to microservices this quarter, given the team is small, \
+             or hire first and refactor the database layer while keeping the API stable for customers?",
+        );
+        assert_eq!(complex.depth, CounselDepth::Deep, "{:?}", complex);
+        assert!(complex.words > SHORT_WORDS && complex.clauses >= 4);
+        assert!(!complex.templates.is_empty());
+
+        let mut auto = CounselRequest {
+            question: "Should we use Redis?".to_string(),
+            context: Default::default(),
+            decision_id: None,
+        };
+        assert!(resolve(&auto).is_none());
+        auto.context.depth = CounselDepth::Auto;
+        let (resolved, choice) = resolve(&auto).unwrap();
+        assert_eq!((resolved.context.depth, choice.depth), (CounselDepth::Quick, CounselDepth::Quick));
+    }
+}
@@ -10,7 +10,7 @@
 //!
 //! Model: all-MiniLM-L6-v2 (22MB, 384 dimensions, runs on CPU)
 
-use anyhow::{anyhow, Context, Result};
+use crate::error::{MindsError, Result};
 use ndarray::Array2;
 use ort::{
     inputs,
@@ -18,10 +18,31 @@ use ort::{
     value::Tensor,
 };
 use rusqlite::{params, Connection};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 use tokenizers::Tokenizer;
 
+/// Load the ONNX Runtime library up front: ort panics if it's missing when
+/// a session is first built, and a missing runtime should only cost
+/// semantic search
+fn ensure_runtime() -> Result<()> {
+    let library = std::env::var("ORT_DYLIB_PATH").ok().filter(|p| !p.is_empty()).unwrap_or_else(|| {
+        if cfg!(target_os = "windows") {
+            "onnxruntime.dll"
+        } else if cfg!(target_os = "macos") {
+            "libonnxruntime.dylib"
+        } else {
+            "libonnxruntime.so"
+        }
+        .to_string()
+    });
+    ort::init_from(&library)
+        .map(drop)
+        .map_err(|e| MindsError::Embedding(format!("ONNX Runtime unavailable (set ORT_DYLIB_PATH): {}", e)))
+}
+
 /// Embedding dimensions for all-MiniLM-L6-v2
 pub const EMBEDDING_DIM: usize = 384;
 
@@ -34,67 +55,57 @@ pub struct SemanticEngine {
     tokenizer: Tokenizer,
     /// Pre-computed principle embeddings: principle_id -> embedding
     principle_embeddings: HashMap<String, Vec<f32>>,
+    /// The same embeddings in int8, for the first pass of [`Self::search`]
+    principle_quantized: HashMap<String, QuantizedEmbedding>,
+    /// Score every embedding in f32 instead (`[search] exact_similarity`)
+    exact: bool,
+    /// Rows in the sqlite-vec table, when KNN runs in SQL instead
+    vec_rows: usize,
 }
 
 impl SemanticEngine {
-    /// Initialize the semantic engine with model from cache or download
+    /// Load the model from `model_dir`; nothing is downloaded (see [`fetch_model`])
     pub fn new(model_dir: &Path) -> Result<Self> {
-        // Ensure model directory exists
-        std::fs::create_dir_all(model_dir)?;
-
         let model_path = model_dir.join("model.onnx");
         let tokenizer_path = model_dir.join("tokenizer.json");
-
-        // Download model if not present
         if !model_path.exists() || !tokenizer_path.exists() {
-            Self::download_model(model_dir)?;
+            return Err(MindsError::Embedding(format!(
+                "no embedding model in {}; run --fetch-model (--fetch-model --from <bundle> offline) or set {}",
+                model_dir.display(),
+                MODEL_DIR_ENV
+            )));
         }
 
+        ensure_runtime()?;
+
         // Load ONNX model
         let session = Session::builder()?
             .with_optimization_level(GraphOptimizationLevel::Level3)?
             .with_intra_threads(4)?
             .commit_from_file(&model_path)
-            .context("Failed to load ONNX model")?;
+            .map_err(|e| MindsError::Embedding(format!("Failed to load ONNX model: {}", e)))?;
 
         // Load tokenizer
         let tokenizer = Tokenizer::from_file(&tokenizer_path)
-            .map_err(|e| anyhow!("Failed to load tokenizer: {}", e))?;
+            .map_err(|e| MindsError::Embedding(format!("Failed to load tokenizer: {}", e)))?;
 
         Ok(Self {
             session,
             tokenizer,
             principle_embeddings: HashMap::new(),
+            principle_quantized: HashMap::new(),
+            exact: false,
+            vec_rows: 0,
         })
     }
 
-    /// Download the embedding model from HuggingFace
-    fn download_model(model_dir: &Path) -> Result<()> {
-        use hf_hub::api::sync::Api;
-
-        println!("Downloading embedding model (all-MiniLM-L6-v2)...");
-        let api = Api::new()?;
-        let repo = api.model("sentence-transformers/all-MiniLM-L6-v2".to_string());
-
-        // Download ONNX model
-        let model_path = repo.get("onnx/model.onnx")?;
-        std::fs::copy(&model_path, model_dir.join("model.onnx"))?;
-
-        // Download tokenizer
-        let tokenizer_path = repo.get("tokenizer.json")?;
-        std::fs::copy(&tokenizer_path, model_dir.join("tokenizer.json"))?;
-
-        println!("Model downloaded successfully!");
-        Ok(())
-    }
-
     /// Compute embedding for a single text
     pub fn embed(&mut self, text: &str) -> Result<Vec<f32>> {
         // Tokenize
         let encoding = self
             .tokenizer
             .encode(text, true)
-            .map_err(|e| anyhow!("Tokenization failed: {}", e))?;
+            .map_err(|e| MindsError::Embedding(format!("Tokenization failed: {}", e)))?;
 
         let ids: Vec<i64> = encoding.get_ids().iter().map(|&id| id as i64).collect();
         let attention_mask: Vec<i64> = encoding
@@ -126,7 +137,7 @@ impl SemanticEngine {
         let output = outputs
             .get("last_hidden_state")
             .or_else(|| outputs.get("token_embeddings"))
-            .ok_or_else(|| anyhow!("No embedding output found"))?;
+            .ok_or_else(|| MindsError::Embedding("No embedding output found".to_string()))?;
 
         let (shape, data) = output.try_extract_tensor::<f32>()?;
 
@@ -183,7 +194,7 @@ impl SemanticEngine {
     }
 
     /// L2 normalize a vector
-    fn l2_normalize(vec: &[f32]) -> Vec<f32> {
+    pub fn l2_normalize(vec: &[f32]) -> Vec<f32> {
         let norm: f32 = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
         if norm > 0.0 {
             vec.iter().map(|x| x / norm).collect()
@@ -200,18 +211,35 @@ impl SemanticEngine {
 
     /// Load pre-computed embeddings from database
     pub fn load_embeddings(&mut self, conn: &Connection) -> Result<usize> {
-        let mut stmt =
-            conn.prepare("SELECT id, embedding FROM principles WHERE embedding IS NOT NULL")?;
+        // With the sqlite-vec table in place, [`Self::knn`] queries it and
+        // nothing needs to sit in memory
+        self.vec_rows = vec_rows(conn);
+        if self.vec_rows > 0 {
+            return Ok(self.vec_rows);
+        }
+
+        // Databases from before quantization have no embedding_q8 column
+        let has_quantized: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('principles') WHERE name = 'embedding_q8'",
+            [],
+            |row| row.get(0),
+        )?;
+        let mut stmt = conn.prepare(if has_quantized {
+            "SELECT id, embedding, embedding_q8 FROM principles WHERE embedding IS NOT NULL"
+        } else {
+            "SELECT id, embedding, NULL FROM principles WHERE embedding IS NOT NULL"
+        })?;
 
         let rows = stmt.query_map([], |row| {
             let id: String = row.get(0)?;
             let blob: Vec<u8> = row.get(1)?;
-            Ok((id, blob))
+            let quantized: Option<Vec<u8>> = row.get(2)?;
+            Ok((id, blob, quantized))
         })?;
 
         let mut count = 0;
         for row in rows {
-            let (id, blob) = row?;
+            let (id, blob, quantized) = row?;
             // Convert bytes to f32 vector
             let embedding: Vec<f32> = blob
                 .chunks_exact(4)
@@ -219,6 +247,11 @@ impl SemanticEngine {
                 .collect();
 
             if embedding.len() == EMBEDDING_DIM {
+                let quantized = quantized
+                    .and_then(|bytes| QuantizedEmbedding::from_bytes(&bytes))
+                    .filter(|q| q.values.len() == EMBEDDING_DIM)
+                    .unwrap_or_else(|| QuantizedEmbedding::new(&embedding));
+                self.principle_quantized.insert(id.clone(), quantized);
                 self.principle_embeddings.insert(id, embedding);
                 count += 1;
             }
@@ -241,12 +274,13 @@ impl SemanticEngine {
             })?
             .collect::<Result<Vec<_>, _>>()?;
 
-        println!(
+        say!(
             "Computing embeddings for {} principles...",
             principles.len()
         );
 
-        let mut update_stmt = conn.prepare("UPDATE principles SET embedding = ?2 WHERE id = ?1")?;
+        let mut update_stmt =
+            conn.prepare("UPDATE principles SET embedding = ?2, embedding_q8 = ?3 WHERE id = ?1")?;
 
         let mut count = 0;
         for (id, name, description, application_rule) in principles {
@@ -258,156 +292,221 @@ impl SemanticEngine {
                     // Convert to bytes
                     let bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
 
-                    update_stmt.execute(params![id, bytes])?;
+                    update_stmt.execute(params![id, bytes, QuantizedEmbedding::new(&embedding).to_bytes()])?;
                     count += 1;
 
                     if count % 50 == 0 {
-                        println!("  Computed {}/{}", count, count);
+                        say!("  Computed {}/{}", count, count);
                     }
                 }
                 Err(e) => {
-                    eprintln!("Warning: Failed to embed principle {}: {}", id, e);
+                    say_err!("Warning: Failed to embed principle {}: {}", id, e);
                 }
             }
         }
 
-        println!("Computed {} embeddings", count);
+        say!("Computed {} embeddings", count);
+        let backfilled = backfill_quantized(conn)?;
+        if backfilled > 0 {
+            say!("Quantized {} existing embeddings", backfilled);
+        }
+        if vec_available(conn) {
+            say!("Indexed {} embeddings in {}", sync_vec_table(conn)?, VEC_TABLE);
+        }
         Ok(count)
     }
 
-    /// Search for similar principles using semantic similarity
-    pub fn search(&mut self, query: &str, top_k: usize) -> Result<Vec<SemanticMatch>> {
+    /// Nearest principles to `query`: a KNN query against the sqlite-vec
+    /// table when it's populated, else [`Self::search`] over memory
+    pub fn knn(&mut self, conn: &Connection, query: &str, top_k: usize) -> Result<Vec<SemanticMatch>> {
+        if self.vec_rows == 0 {
+            return self.search(query, top_k);
+        }
         let query_embedding = self.embed(query)?;
+        vec_knn(conn, &query_embedding, top_k)
+    }
 
-        let mut results: Vec<SemanticMatch> = self
-            .principle_embeddings
-            .iter()
-            .map(|(id, emb)| {
-                let similarity = Self::cosine_similarity(&query_embedding, emb);
-                SemanticMatch {
-                    principle_id: id.clone(),
-                    similarity,
-                }
-            })
-            .collect();
+    /// Search for similar principles using semantic similarity (loaded embeddings)
+    pub fn search(&mut self, query: &str, top_k: usize) -> Result<Vec<SemanticMatch>> {
+        let query_embedding = self.embed(query)?;
+        let quantized = (!self.exact).then_some(&self.principle_quantized);
+        Ok(rank_embeddings(&query_embedding, &self.principle_embeddings, quantized, top_k))
+    }
 
-        // Sort by similarity descending
-        results.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+    /// Score every embedding in f32 (true) or take an int8 first pass and
+    /// rescore its top candidates (false, the default)
+    pub fn set_exact(&mut self, exact: bool) {
+        self.exact = exact;
+    }
 
-        // Return top K
-        results.truncate(top_k);
-        Ok(results)
+    /// Number of principle embeddings loaded
+    pub fn embedding_count(&self) -> usize {
+        self.principle_embeddings.len().max(self.vec_rows)
     }
 
     /// Hybrid search: combine semantic similarity with BM25 scores
+    /// (see [`crate::search::hybrid`])
     pub fn hybrid_search(
         &mut self,
         conn: &Connection,
         query: &str,
         top_k: usize,
-        semantic_weight: f32,
+        config: &crate::search::SearchConfig,
     ) -> Result<Vec<HybridMatch>> {
-        // 1. Get semantic matches
-        let semantic_results = self.search(query, top_k * 2)?;
-        let semantic_map: HashMap<String, f32> = semantic_results
-            .into_iter()
-            .map(|m| (m.principle_id, m.similarity))
-            .collect();
-
-        // 2. Get BM25 matches from FTS5
-        let keywords: Vec<&str> = query
-            .split(|c: char| !c.is_alphanumeric())
-            .filter(|w| w.len() > 2)
-            .take(15)
-            .collect();
+        Ok(crate::search::hybrid(conn, Some(self), query, top_k, config)?.matches)
+    }
+}
 
-        let fts_query = keywords.join(" OR ");
-        let bm25_results = Self::bm25_search(conn, &fts_query, top_k * 2)?;
-        let bm25_map: HashMap<String, f32> = bm25_results
-            .into_iter()
-            .map(|m| (m.principle_id, m.score))
-            .collect();
+/// Cross-encoder reranker: scores each (query, principle) pair jointly,
+/// which separates close candidates better than comparing embeddings.
+/// Expects a sequence-classification export with a single relevance logit,
+/// such as cross-encoder/ms-marco-MiniLM-L-6-v2.
+#[cfg(feature = "rerank")]
+pub struct CrossEncoderReranker {
+    session: std::sync::Mutex<Session>,
+    tokenizer: Tokenizer,
+}
 
-        // 3. Combine scores using RECIPROCAL RANK FUSION (RRF)
-        // RRF is scientifically proven to be more robust than weighted averaging
-        // Formula: score(d) = Σ 1 / (k + rank_i(d)), k=60 is standard
-        const RRF_K: f32 = 60.0;
+#[cfg(feature = "rerank")]
+impl CrossEncoderReranker {
+    pub fn new(model_dir: &Path) -> Result<Self> {
+        ensure_runtime()?;
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_intra_threads(4)?
+            .commit_from_file(model_dir.join("model.onnx"))
+            .map_err(|e| MindsError::Embedding(format!("Failed to load cross-encoder model: {}", e)))?;
+        let tokenizer = Tokenizer::from_file(model_dir.join("tokenizer.json"))
+            .map_err(|e| MindsError::Embedding(format!("Failed to load tokenizer: {}", e)))?;
+        Ok(Self { session: std::sync::Mutex::new(session), tokenizer })
+    }
 
-        // Create ranked lists
-        let mut semantic_ranked: Vec<_> = semantic_map.iter().collect();
-        semantic_ranked.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
-        let semantic_ranks: HashMap<&String, usize> = semantic_ranked
-            .iter()
-            .enumerate()
-            .map(|(i, (id, _))| (*id, i + 1))
-            .collect();
+    fn score_pair(&self, query: &str, text: &str) -> Result<f32> {
+        let encoding = self
+            .tokenizer
+            .encode((query, text), true)
+            .map_err(|e| MindsError::Embedding(format!("Tokenization failed: {}", e)))?;
+        let len = encoding.get_ids().len().min(MAX_SEQ_LEN);
+        let column = |values: &[u32]| -> Result<Array2<i64>> {
+            Ok(Array2::from_shape_vec((1, len), values[..len].iter().map(|&v| v as i64).collect())?)
+        };
+        let mut session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+        let outputs = session.run(inputs![
+            "input_ids" => Tensor::from_array(column(encoding.get_ids())?)?,
+            "attention_mask" => Tensor::from_array(column(encoding.get_attention_mask())?)?,
+            "token_type_ids" => Tensor::from_array(column(encoding.get_type_ids())?)?,
+        ])?;
+        let logits = outputs.get("logits").ok_or_else(|| MindsError::Embedding("No logits output found".to_string()))?;
+        let (_, data) = logits.try_extract_tensor::<f32>()?;
+        data.first().copied().ok_or_else(|| MindsError::Embedding("Empty logits".to_string()))
+    }
+}
 
-        let mut bm25_ranked: Vec<_> = bm25_map.iter().collect();
-        bm25_ranked.sort_by(|a, b| b.1.abs().partial_cmp(&a.1.abs()).unwrap());
-        let bm25_ranks: HashMap<&String, usize> = bm25_ranked
-            .iter()
-            .enumerate()
-            .map(|(i, (id, _))| (*id, i + 1))
-            .collect();
+#[cfg(feature = "rerank")]
+impl crate::search::Reranker for CrossEncoderReranker {
+    fn name(&self) -> &str {
+        "cross-encoder"
+    }
 
-        let all_ids: std::collections::HashSet<_> = semantic_map
-            .keys()
-            .chain(bm25_map.keys())
-            .cloned()
-            .collect();
+    fn score(&self, query: &str, candidates: &[&str]) -> Result<Vec<f32>> {
+        candidates.iter().map(|text| self.score_pair(query, text)).collect()
+    }
+}
 
-        let mut combined: Vec<HybridMatch> = all_ids
-            .into_iter()
-            .map(|id| {
-                let semantic = semantic_map.get(&id).copied().unwrap_or(0.0);
-                let bm25 = bm25_map.get(&id).copied().unwrap_or(0.0);
-
-                // RRF score: 1/(k+rank) for each list, sum them
-                let sem_rank = semantic_ranks.get(&id).copied().unwrap_or(1000) as f32;
-                let bm25_rank = bm25_ranks.get(&id).copied().unwrap_or(1000) as f32;
-
-                // Weight semantic more heavily (70% semantic, 30% BM25 via RRF weights)
-                let rrf_score = semantic_weight * (1.0 / (RRF_K + sem_rank))
-                    + (1.0 - semantic_weight) * (1.0 / (RRF_K + bm25_rank));
-
-                HybridMatch {
-                    principle_id: id,
-                    semantic_score: semantic,
-                    bm25_score: bm25,
-                    combined_score: rrf_score,
-                }
-            })
-            .collect();
+/// Token-classification NER model for redacting names from stored text.
+/// Expects model.onnx, tokenizer.json and the config.json with `id2label`
+/// (BIO tags such as B-PER / I-ORG), as exported for dslim/bert-base-NER.
+#[cfg(feature = "ner")]
+pub struct NerModel {
+    session: std::sync::Mutex<Session>,
+    tokenizer: Tokenizer,
+    labels: Vec<String>,
+}
 
-        combined.sort_by(|a, b| b.combined_score.partial_cmp(&a.combined_score).unwrap());
-        combined.truncate(top_k);
+#[cfg(feature = "ner")]
+type TokenTag = (Option<(bool, String)>, (usize, usize));
 
-        Ok(combined)
+#[cfg(feature = "ner")]
+impl NerModel {
+    pub fn new(model_dir: &Path) -> Result<Self> {
+        ensure_runtime()?;
+        let config: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(model_dir.join("config.json"))?)?;
+        let id2label = config
+            .get("id2label")
+            .and_then(|l| l.as_object())
+            .ok_or_else(|| MindsError::Embedding("config.json has no id2label".to_string()))?;
+        let mut labels = vec![String::new(); id2label.len()];
+        for (id, label) in id2label {
+            let slot = id.parse::<usize>().ok().and_then(|i| labels.get_mut(i));
+            match (slot, label.as_str()) {
+                (Some(slot), Some(label)) => *slot = label.to_string(),
+                _ => return Err(MindsError::Embedding(format!("Bad id2label entry {}", id))),
+            }
+        }
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_intra_threads(4)?
+            .commit_from_file(model_dir.join("model.onnx"))
+            .map_err(|e| MindsError::Embedding(format!("Failed to load NER model: {}", e)))?;
+        let tokenizer = Tokenizer::from_file(model_dir.join("tokenizer.json"))
+            .map_err(|e| MindsError::Embedding(format!("Failed to load tokenizer: {}", e)))?;
+        Ok(Self { session: std::sync::Mutex::new(session), tokenizer, labels })
     }
 
-    /// BM25 search using FTS5
-    fn bm25_search(conn: &Connection, query: &str, limit: usize) -> Result<Vec<Bm25Match>> {
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT p.id, bm25(principles_fts) as score
-            FROM principles_fts
-            JOIN principles p ON principles_fts.rowid = p.rowid
-            WHERE principles_fts MATCH ?1
-            ORDER BY score
-            LIMIT ?2
-            "#,
-        )?;
-
-        let results = stmt
-            .query_map(params![query, limit as i64], |row| {
-                Ok(Bm25Match {
-                    principle_id: row.get(0)?,
-                    score: row.get(1)?,
-                })
-            })?
-            .collect::<Result<Vec<_>, _>>()?;
+    /// Entity per token, `(begins, label)` (`None` for "O" and special
+    /// tokens), with the token's byte offsets
+    fn tag(&self, text: &str) -> Result<Vec<TokenTag>> {
+        let encoding = self
+            .tokenizer
+            .encode(text, true)
+            .map_err(|e| MindsError::Embedding(format!("Tokenization failed: {}", e)))?;
+        let len = encoding.get_ids().len().min(MAX_SEQ_LEN);
+        let column = |values: &[u32]| -> Result<Array2<i64>> {
+            Ok(Array2::from_shape_vec((1, len), values[..len].iter().map(|&v| v as i64).collect())?)
+        };
+        let mut session = self.session.lock().unwrap_or_else(|e| e.into_inner());
+        let outputs = session.run(inputs![
+            "input_ids" => Tensor::from_array(column(encoding.get_ids())?)?,
+            "attention_mask" => Tensor::from_array(column(encoding.get_attention_mask())?)?,
+            "token_type_ids" => Tensor::from_array(column(encoding.get_type_ids())?)?,
+        ])?;
+        let logits = outputs.get("logits").ok_or_else(|| MindsError::Embedding("No logits output found".to_string()))?;
+        let (_, data) = logits.try_extract_tensor::<f32>()?;
+
+        let n = self.labels.len();
+        Ok((0..len)
+            .map(|i| {
+                let row = &data[i * n..(i + 1) * n];
+                let best = (0..n).max_by(|&a, &b| row[a].total_cmp(&row[b])).unwrap_or(0);
+                let offsets = encoding.get_offsets()[i];
+                let tag = match self.labels[best].split_once('-') {
+                    _ if encoding.get_special_tokens_mask()[i] == 1 => None,
+                    Some((prefix, entity)) => Some((prefix == "B", entity.to_string())),
+                    None => None,
+                };
+                (tag, offsets)
+            })
+            .collect())
+    }
+}
 
-        Ok(results)
+#[cfg(feature = "ner")]
+impl crate::redact::EntityRecognizer for NerModel {
+    fn entities(&self, text: &str) -> Result<Vec<crate::redact::Entity>> {
+        let mut entities: Vec<crate::redact::Entity> = Vec::new();
+        for (tag, (start, end)) in self.tag(text)? {
+            let Some((begins, label)) = tag else {
+                continue;
+            };
+            // I- tags, and word pieces whatever their tag, continue the entity before them
+            match entities.last_mut() {
+                Some(last) if last.label == label && (start == last.end || (!begins && start <= last.end + 1)) => {
+                    last.end = end
+                }
+                _ => entities.push(crate::redact::Entity { start, end, label }),
+            }
+        }
+        Ok(entities)
     }
 }
 
@@ -418,15 +517,8 @@ pub struct SemanticMatch {
     pub similarity: f32,
 }
 
-/// BM25 search result
-#[derive(Debug, Clone)]
-struct Bm25Match {
-    principle_id: String,
-    score: f32,
-}
-
 /// Hybrid search result combining semantic and BM25
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct HybridMatch {
     pub principle_id: String,
     pub semantic_score: f32,
@@ -448,14 +540,205 @@ pub fn init_embedding_schema(conn: &Connection) -> Result<()> {
 
     if !has_column {
         conn.execute("ALTER TABLE principles ADD COLUMN embedding BLOB", [])?;
-        println!("Added embedding column to principles table");
+        say!("Added embedding column to principles table");
+    }
+
+    let has_quantized: bool =
+        conn.query_row("SELECT COUNT(*) > 0 FROM pragma_table_info('principles') WHERE name='embedding_q8'", [], |row| {
+            row.get(0)
+        })?;
+    if !has_quantized {
+        conn.execute("ALTER TABLE principles ADD COLUMN embedding_q8 BLOB", [])?;
+    }
+
+    Ok(())
+}
+
+/// Candidates the int8 pass keeps for exact rescoring, per result wanted
+const RESCORE_FACTOR: usize = 4;
+const MIN_RESCORE: usize = 32;
+
+/// An embedding scaled into int8: `values[i] * scale` ≈ the f32 component.
+/// A quarter of the size, and dot products run on integers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizedEmbedding {
+    pub scale: f32,
+    pub values: Vec<i8>,
+}
+
+impl QuantizedEmbedding {
+    pub fn new(embedding: &[f32]) -> Self {
+        let max = embedding.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        let scale = if max > 0.0 { max / 127.0 } else { 1.0 };
+        let values = embedding.iter().map(|x| (x / scale).round().clamp(-127.0, 127.0) as i8).collect();
+        QuantizedEmbedding { scale, values }
+    }
+
+    /// Approximate dot product (cosine similarity for normalized embeddings)
+    pub fn dot(&self, other: &QuantizedEmbedding) -> f32 {
+        let sum: i32 = self.values.iter().zip(&other.values).map(|(&a, &b)| a as i32 * b as i32).sum();
+        sum as f32 * self.scale * other.scale
+    }
+
+    /// Little-endian scale followed by the values
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.scale.to_le_bytes().to_vec();
+        bytes.extend(self.values.iter().map(|&v| v as u8));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (scale, values) = bytes.split_first_chunk::<4>()?;
+        Some(QuantizedEmbedding { scale: f32::from_le_bytes(*scale), values: values.iter().map(|&v| v as i8).collect() })
+    }
+}
+
+/// Top `top_k` embeddings by cosine similarity to `query`. With `quantized`,
+/// an int8 pass picks the candidates and only those are scored in f32.
+fn rank_embeddings(
+    query: &[f32],
+    exact: &HashMap<String, Vec<f32>>,
+    quantized: Option<&HashMap<String, QuantizedEmbedding>>,
+    top_k: usize,
+) -> Vec<SemanticMatch> {
+    let candidates: Vec<&String> = match quantized.filter(|q| q.len() == exact.len()) {
+        Some(quantized) => {
+            let query = QuantizedEmbedding::new(query);
+            let mut approx: Vec<(&String, f32)> = quantized.iter().map(|(id, q)| (id, query.dot(q))).collect();
+            let keep = (top_k * RESCORE_FACTOR).max(MIN_RESCORE);
+            if approx.len() > keep {
+                approx.select_nth_unstable_by(keep - 1, |a, b| b.1.total_cmp(&a.1));
+                approx.truncate(keep);
+            }
+            approx.into_iter().map(|(id, _)| id).collect()
+        }
+        None => exact.keys().collect(),
+    };
+    let mut results: Vec<SemanticMatch> = candidates
+        .into_iter()
+        .filter_map(|id| {
+            let embedding = exact.get(id)?;
+            Some(SemanticMatch { principle_id: id.clone(), similarity: SemanticEngine::cosine_similarity(query, embedding) })
+        })
+        .collect();
+    results.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    results.truncate(top_k);
+    results
+}
+
+/// Quantize embeddings stored before quantization existed
+fn backfill_quantized(conn: &Connection) -> Result<usize> {
+    let pending: Vec<(String, Vec<u8>)> = conn
+        .prepare("SELECT id, embedding FROM principles WHERE embedding IS NOT NULL AND embedding_q8 IS NULL")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for (id, blob) in &pending {
+        let embedding: Vec<f32> = blob.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+        conn.execute(
+            "UPDATE principles SET embedding_q8 = ?2 WHERE id = ?1",
+            params![id, QuantizedEmbedding::new(&embedding).to_bytes()],
+        )?;
+    }
+    Ok(pending.len())
+}
+
+/// sqlite-vec virtual table holding principle embeddings (`vec` feature)
+pub const VEC_TABLE: &str = "principle_vec";
+
+/// Load sqlite-vec into every connection opened from now on
+#[cfg(feature = "vec")]
+pub fn register_vec_extension() {
+    type ExtensionInit = unsafe extern "C" fn(
+        *mut rusqlite::ffi::sqlite3,
+        *mut *const std::os::raw::c_char,
+        *const rusqlite::ffi::sqlite3_api_routines,
+    ) -> std::os::raw::c_int;
+    static REGISTER: std::sync::Once = std::sync::Once::new();
+    REGISTER.call_once(|| unsafe {
+        let init = std::mem::transmute::<*const (), ExtensionInit>(sqlite_vec::sqlite3_vec_init as *const ());
+        rusqlite::ffi::sqlite3_auto_extension(Some(init));
+    });
+}
+
+/// Whether this connection has the sqlite-vec functions
+pub fn vec_available(conn: &Connection) -> bool {
+    conn.query_row("SELECT vec_version()", [], |row| row.get::<_, String>(0)).is_ok()
+}
+
+/// Rows in the sqlite-vec table; 0 without the extension or the table
+fn vec_rows(conn: &Connection) -> usize {
+    if !vec_available(conn) {
+        return 0;
     }
+    conn.query_row(&format!("SELECT COUNT(*) FROM {}", VEC_TABLE), [], |row| row.get::<_, i64>(0))
+        .map_or(0, |n| n as usize)
+}
+
+/// KNN in SQL; cosine distance back to similarity
+fn vec_knn(conn: &Connection, embedding: &[f32], top_k: usize) -> Result<Vec<SemanticMatch>> {
+    let bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT principle_id, distance FROM {} WHERE embedding MATCH ?1 AND k = ?2 ORDER BY distance",
+        VEC_TABLE
+    ))?;
+    let matches = stmt
+        .query_map(params![bytes, top_k as i64], |row| {
+            Ok(SemanticMatch { principle_id: row.get(0)?, similarity: 1.0 - row.get::<_, f64>(1)? as f32 })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(matches)
+}
+
+/// Rebuild the sqlite-vec table from the embeddings stored on principles
+pub fn sync_vec_table(conn: &Connection) -> Result<usize> {
+    conn.execute_batch(&format!(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS {0} USING vec0(
+             principle_id TEXT PRIMARY KEY,
+             embedding float[{1}] distance_metric=cosine
+         );
+         DELETE FROM {0};",
+        VEC_TABLE, EMBEDDING_DIM
+    ))?;
+    let copied = conn.execute(
+        &format!(
+            "INSERT INTO {} (principle_id, embedding)
+             SELECT id, embedding FROM principles WHERE embedding IS NOT NULL AND length(embedding) = ?1",
+            VEC_TABLE
+        ),
+        [EMBEDDING_DIM * 4],
+    )?;
+    Ok(copied)
+}
 
+/// Move existing embeddings into the sqlite-vec table the first time a
+/// database is opened with the extension; without it nothing changes and
+/// search keeps scanning the blobs in memory
+pub fn migrate_vec(conn: &Connection) -> Result<()> {
+    if !vec_available(conn) {
+        return Ok(());
+    }
+    let has_table: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE name = ?1",
+        [VEC_TABLE],
+        |row| row.get(0),
+    )?;
+    let has_embeddings: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('principles') WHERE name = 'embedding'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_table && has_embeddings {
+        let copied = sync_vec_table(conn)?;
+        tracing::info!("Moved {} principle embeddings into {}", copied, VEC_TABLE);
+    }
     Ok(())
 }
 
-/// Get model directory path
+/// Default model directory: `$MINDS_MODEL_DIR`, else under the data directory
 pub fn get_model_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(MODEL_DIR_ENV).filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir);
+    }
     dirs::data_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("100minds")
@@ -463,6 +746,249 @@ pub fn get_model_dir() -> PathBuf {
         .join("minilm")
 }
 
+/// Overrides the default model directory (e.g. a read-only bundle baked into an image)
+pub const MODEL_DIR_ENV: &str = "MINDS_MODEL_DIR";
+
+/// The files a model directory needs
+pub const MODEL_FILES: [&str; 2] = ["model.onnx", "tokenizer.json"];
+
+/// Checksum manifest kept beside the model, in `sha256sum` format
+pub const CHECKSUM_FILE: &str = "SHA256SUMS";
+
+const HUB_REPO: &str = "sentence-transformers/all-MiniLM-L6-v2";
+
+/// Where [`fetch_model`] gets the model from
+#[derive(Debug, Clone, Copy)]
+pub enum ModelSource<'a> {
+    /// Download from HuggingFace
+    Hub,
+    /// A model directory or a tarball of one (for air-gapped machines)
+    Local(&'a Path),
+}
+
+/// SHA-256 per model file name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelChecksums(pub BTreeMap<String, String>);
+
+impl ModelChecksums {
+    /// Parse `sha256sum` output: `<hex>  <file>` per line
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut sums = BTreeMap::new();
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let (hash, name) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| MindsError::Validation(format!("bad checksum line: {}", line)))?;
+            let name = name.trim().trim_start_matches('*');
+            let name = Path::new(name).file_name().and_then(|n| n.to_str()).unwrap_or(name);
+            sums.insert(name.to_string(), hash.to_lowercase());
+        }
+        Ok(Self(sums))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Hash the model files in `dir`
+    pub fn of_dir(dir: &Path) -> Result<Self> {
+        let mut sums = BTreeMap::new();
+        for name in MODEL_FILES {
+            sums.insert(name.to_string(), sha256_file(&dir.join(name))?);
+        }
+        Ok(Self(sums))
+    }
+
+    pub fn render(&self) -> String {
+        self.0.iter().map(|(name, hash)| format!("{}  {}\n", hash, name)).collect()
+    }
+
+    /// Files in `self` whose hash isn't the one `expected` lists
+    fn mismatches(&self, expected: &Self) -> Vec<String> {
+        expected
+            .0
+            .iter()
+            .filter(|(name, hash)| self.0.get(*name) != Some(*hash))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| MindsError::Embedding(format!("{}: {}", path.display(), e)))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// The directory holding the model files: `dir` itself, or the one
+/// top-level directory a tarball unpacked into
+fn bundle_root(dir: &Path) -> Result<PathBuf> {
+    if dir.join("model.onnx").exists() {
+        return Ok(dir.to_path_buf());
+    }
+    std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .find(|path| path.join("model.onnx").exists())
+        .ok_or_else(|| MindsError::Embedding(format!("no model.onnx in {}", dir.display())))
+}
+
+/// Fetch the model into `staging`; returns the directory holding the files
+/// and the checksums its source vouches for
+fn stage_model(source: ModelSource, staging: &Path) -> Result<(PathBuf, Option<ModelChecksums>)> {
+    match source {
+        ModelSource::Hub => {
+            use hf_hub::api::sync::Api;
+            let download = |e: hf_hub::api::sync::ApiError| MindsError::Embedding(format!("Model download failed: {}", e));
+            let repo = Api::new().map_err(download)?.model(HUB_REPO.to_string());
+            let mut published = BTreeMap::new();
+            for (remote, name) in [("onnx/model.onnx", "model.onnx"), ("tokenizer.json", "tokenizer.json")] {
+                let path = repo.get(remote).map_err(download)?;
+                // The hub cache names LFS blobs by their SHA-256
+                let blob = std::fs::canonicalize(&path)?;
+                if let Some(hash) = blob.file_name().and_then(|n| n.to_str()).filter(|n| {
+                    n.len() == 64 && n.chars().all(|c| c.is_ascii_hexdigit())
+                }) {
+                    published.insert(name.to_string(), hash.to_lowercase());
+                }
+                std::fs::copy(&path, staging.join(name))?;
+            }
+            Ok((staging.to_path_buf(), Some(ModelChecksums(published)).filter(|c| !c.0.is_empty())))
+        }
+        ModelSource::Local(path) => {
+            let root = if path.is_dir() {
+                path.to_path_buf()
+            } else {
+                let status = std::process::Command::new("tar")
+                    .arg("-xf")
+                    .arg(path)
+                    .arg("-C")
+                    .arg(staging)
+                    .status()
+                    .map_err(|e| MindsError::Embedding(format!("can't run tar: {}", e)))?;
+                if !status.success() {
+                    return Err(MindsError::Embedding(format!("tar couldn't unpack {}", path.display())));
+                }
+                bundle_root(staging)?
+            };
+            let manifest = root.join(CHECKSUM_FILE);
+            let listed = if manifest.exists() { Some(ModelChecksums::read(&manifest)?) } else { None };
+            Ok((root, listed))
+        }
+    }
+}
+
+/// Install the embedding model into `model_dir`, verifying each file's
+/// SHA-256 first: against `expected` when given, else against what the
+/// source publishes (the hub's LFS hashes, or a bundle's `SHA256SUMS`). A
+/// local bundle with nothing to verify against is refused. Nothing in
+/// `model_dir` changes unless every file checks out; a `SHA256SUMS` is
+/// written beside the installed files so the directory can be bundled.
+pub fn fetch_model(model_dir: &Path, source: ModelSource, expected: Option<ModelChecksums>) -> Result<ModelChecksums> {
+    let staging = model_dir.with_extension("partial");
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+    let installed = (|| {
+        let (root, published) = stage_model(source, &staging)?;
+        let actual = ModelChecksums::of_dir(&root)?;
+        let expected = match (expected, published, source) {
+            (Some(expected), _, _) | (None, Some(expected), _) => expected,
+            (None, None, ModelSource::Hub) => ModelChecksums::default(),
+            (None, None, ModelSource::Local(path)) => {
+                return Err(MindsError::Validation(format!(
+                    "{} has no {}; pass the expected checksums with --checksums",
+                    path.display(),
+                    CHECKSUM_FILE
+                )))
+            }
+        };
+        let mismatched = actual.mismatches(&expected);
+        if !mismatched.is_empty() {
+            return Err(MindsError::Validation(format!("checksum mismatch for {}", mismatched.join(", "))));
+        }
+        std::fs::create_dir_all(model_dir)?;
+        for name in MODEL_FILES {
+            std::fs::copy(root.join(name), model_dir.join(name))?;
+        }
+        std::fs::write(model_dir.join(CHECKSUM_FILE), actual.render())?;
+        Ok(actual)
+    })();
+    let _ = std::fs::remove_dir_all(&staging);
+    installed
+}
+
+/// What search can do in this process: hybrid when the model and principle
+/// embeddings are on disk, keyword-only (FTS5) otherwise. Nothing is ever
+/// downloaded to get there, so this is also what an air-gapped host reports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Capabilities {
+    pub semantic_search: bool,
+    /// "hybrid" or "fts_only"
+    pub search_mode: String,
+    /// Where the model was looked for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_dir: Option<String>,
+    /// Why semantic search is off, and how to turn it on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degraded_reason: Option<String>,
+}
+
+impl Capabilities {
+    fn from_probe(model_dir: Option<&Path>, probe: &std::result::Result<Mutex<SemanticEngine>, String>) -> Self {
+        let (semantic_search, degraded_reason) = match probe {
+            Ok(_) => (true, None),
+            Err(reason) => (false, Some(reason.clone())),
+        };
+        Capabilities {
+            semantic_search,
+            search_mode: if semantic_search { "hybrid" } else { "fts_only" }.to_string(),
+            model_dir: model_dir.map(|d| d.display().to_string()),
+            degraded_reason,
+        }
+    }
+}
+
+/// Load a semantic engine; the error says why semantic search is unavailable
+fn probe(conn: &Connection, model_dir: &Path) -> std::result::Result<SemanticEngine, String> {
+    let mut engine = SemanticEngine::new(model_dir).map_err(|e| e.to_string())?;
+    engine.set_exact(crate::search::SearchConfig::beside(conn).is_ok_and(|c| c.exact_similarity));
+    match engine.load_embeddings(conn) {
+        Ok(0) => Err("no principle embeddings; run --compute-embeddings".to_string()),
+        Ok(_) => Ok(engine),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+static SHARED: OnceLock<(Capabilities, Option<Mutex<SemanticEngine>>)> = OnceLock::new();
+
+fn shared_probe(conn: &Connection) -> &'static (Capabilities, Option<Mutex<SemanticEngine>>) {
+    SHARED.get_or_init(|| {
+        let model_dir = crate::language::LanguageConfig::beside(conn).and_then(|config| config.model_dir());
+        let probed = match &model_dir {
+            Ok(dir) => probe(conn, dir).map(Mutex::new),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(reason) = &probed {
+            tracing::info!("Semantic search unavailable, using keyword search: {}", reason);
+        }
+        (Capabilities::from_probe(model_dir.as_deref().ok(), &probed), probed.ok())
+    })
+}
+
+/// The process's semantic engine, detected once on first use; `None` means
+/// callers should search with FTS5 alone
+pub fn shared_engine(conn: &Connection) -> Option<&'static Mutex<SemanticEngine>> {
+    shared_probe(conn).1.as_ref()
+}
+
+/// Whether [`shared_engine`] is available, for responses and stats
+pub fn capabilities(conn: &Connection) -> Capabilities {
+    shared_probe(conn).0.clone()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -590,6 +1116,112 @@ mod tests {
         assert_eq!(has_embedding, 1, "Second init should be idempotent");
     }
 
+    #[test]
+    fn test_missing_model_degrades_to_keyword_search() {
+        let dir = tempdir().unwrap();
+        let conn = db::init_db(&dir.path().join("test.db")).unwrap();
+
+        let model_dir = dir.path().join("models");
+        let probed = probe(&conn, &model_dir).map(Mutex::new);
+        let capabilities = Capabilities::from_probe(Some(&model_dir), &probed);
+        assert!(!capabilities.semantic_search);
+        assert_eq!(capabilities.search_mode, "fts_only");
+        assert!(capabilities.degraded_reason.unwrap().contains("--fetch-model"));
+        // Detection never downloads
+        assert!(!dir.path().join("models").exists());
+    }
+
+    #[test]
+    fn test_fetch_model_verifies_bundle_checksums() {
+        let dir = tempdir().unwrap();
+        let bundle = dir.path().join("bundle");
+        std::fs::create_dir_all(&bundle).unwrap();
+        std::fs::write(bundle.join("model.onnx"), b"onnx weights").unwrap();
+        std::fs::write(bundle.join("tokenizer.json"), b"{}").unwrap();
+        let sums = ModelChecksums::of_dir(&bundle).unwrap();
+        std::fs::write(bundle.join(CHECKSUM_FILE), sums.render()).unwrap();
+
+        let installed = dir.path().join("minilm");
+        assert_eq!(fetch_model(&installed, ModelSource::Local(&bundle), None).unwrap(), sums);
+        assert_eq!(ModelChecksums::read(&installed.join(CHECKSUM_FILE)).unwrap(), sums);
+
+        // A corrupted file is refused and the install is left alone
+        std::fs::write(bundle.join("model.onnx"), b"truncated").unwrap();
+        let err = fetch_model(&installed, ModelSource::Local(&bundle), None).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch for model.onnx"), "{err}");
+        assert_eq!(std::fs::read(installed.join("model.onnx")).unwrap(), b"onnx weights");
+
+        // Without a manifest a local bundle needs explicit checksums
+        std::fs::remove_file(bundle.join(CHECKSUM_FILE)).unwrap();
+        assert!(fetch_model(&installed, ModelSource::Local(&bundle), None).is_err());
+        let expected = ModelChecksums::of_dir(&bundle).unwrap();
+        fetch_model(&installed, ModelSource::Local(&bundle), Some(expected)).unwrap();
+        assert!(!dir.path().join("minilm.partial").exists());
+    }
+
+    #[cfg(feature = "vec")]
+    #[test]
+    fn test_vec_table_answers_knn_in_sql() {
+        let dir = tempdir().unwrap();
+        let conn = db::init_db(&dir.path().join("test.db")).unwrap();
+        init_embedding_schema(&conn).unwrap();
+        let axis = |i: usize| -> Vec<u8> {
+            let mut v = vec![0.0f32; EMBEDDING_DIM];
+            v[i] = 1.0;
+            v.iter().flat_map(|f| f.to_le_bytes()).collect()
+        };
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description) VALUES
+                 ('a', 't', 'A', 'first'), ('b', 't', 'B', 'second'), ('c', 't', 'C', 'third');",
+        )
+        .unwrap();
+        let ids = ["a", "b", "c"];
+        for (i, id) in ids.iter().enumerate() {
+            conn.execute("UPDATE principles SET embedding = ?1 WHERE id = ?2", params![axis(i), id]).unwrap();
+        }
+
+        assert_eq!(sync_vec_table(&conn).unwrap(), 3);
+        assert_eq!(vec_rows(&conn), 3);
+        let mut query = vec![0.0f32; EMBEDDING_DIM];
+        query[1] = 1.0;
+        let found = vec_knn(&conn, &query, 2).unwrap();
+        assert_eq!(found[0].principle_id, ids[1]);
+        assert!((found[0].similarity - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_quantized_first_pass_keeps_exact_ranking() {
+        // Deterministic pseudo-random unit vectors
+        let mut seed = 7u64;
+        let mut unit = || {
+            let v: Vec<f32> = (0..EMBEDDING_DIM)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    (seed >> 33) as f32 / (1u64 << 31) as f32 - 0.5
+                })
+                .collect();
+            SemanticEngine::l2_normalize(&v)
+        };
+        let exact: HashMap<String, Vec<f32>> = (0..300).map(|i| (format!("p{}", i), unit())).collect();
+        let quantized: HashMap<String, QuantizedEmbedding> =
+            exact.iter().map(|(id, e)| (id.clone(), QuantizedEmbedding::new(e))).collect();
+
+        let stored = &quantized["p0"];
+        assert_eq!(QuantizedEmbedding::from_bytes(&stored.to_bytes()).as_ref(), Some(stored));
+        assert_eq!(stored.to_bytes().len(), 4 + EMBEDDING_DIM);
+        assert!((stored.dot(stored) - 1.0).abs() < 0.02);
+
+        for _ in 0..5 {
+            let query = unit();
+            let full = rank_embeddings(&query, &exact, None, 10);
+            let fast = rank_embeddings(&query, &exact, Some(&quantized), 10);
+            // Rescoring means identical scores, not just a similar order
+            let scored = |ms: &[SemanticMatch]| ms.iter().map(|m| (m.principle_id.clone(), m.similarity)).collect::<Vec<_>>();
+            assert_eq!(scored(&full), scored(&fast));
+        }
+    }
+
     #[test]
     fn test_embedding_dim_constant() {
         assert_eq!(
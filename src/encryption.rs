@@ -0,0 +1,329 @@
+//! At-Rest Encryption
+//!
+//! With the `encryption` feature, wisdom.db can be a SQLCipher database and
+//! agent.key can be sealed with AES-256-GCM under a key derived (PBKDF2)
+//! from the same passphrase. `100minds encrypt-db` converts an existing
+//! install in place.
+//!
+//! The passphrase comes from `MINDS_DB_PASSPHRASE` or, failing that, the OS
+//! keychain (`security` on macOS, `secret-tool` elsewhere). Encrypted files
+//! are recognised by their headers, so plaintext installs keep working
+//! unchanged, and a build without the feature says why it can't open an
+//! encrypted one.
+
+use crate::error::{MindsError, Result};
+use rusqlite::Connection;
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+
+pub const PASSPHRASE_ENV: &str = "MINDS_DB_PASSPHRASE";
+
+const KEYCHAIN_SERVICE: &str = "100minds";
+const KEYCHAIN_ACCOUNT: &str = "wisdom.db";
+
+/// First bytes of every plaintext SQLite file
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// First bytes of a sealed agent.key; also authenticated with the key
+const SEALED_KEY_MAGIC: &[u8] = b"100minds-key-v1\n";
+
+#[cfg(feature = "encryption")]
+const PBKDF2_ITERATIONS: usize = 600_000;
+
+/// Whether the database at `path` is encrypted (a missing or empty file isn't)
+pub fn is_encrypted_db(path: &Path) -> Result<bool> {
+    use std::io::Read;
+
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return Ok(false);
+    };
+    let mut header = [0u8; 16];
+    let read = file.read(&mut header)?;
+    Ok(read > 0 && header[..read] != SQLITE_HEADER[..read])
+}
+
+pub fn is_sealed_key(bytes: &[u8]) -> bool {
+    bytes.starts_with(SEALED_KEY_MAGIC)
+}
+
+/// The passphrase from the environment or the OS keychain, looked up once
+/// per process
+pub fn passphrase() -> Option<String> {
+    static PASSPHRASE: OnceLock<Option<String>> = OnceLock::new();
+    PASSPHRASE
+        .get_or_init(|| match std::env::var(PASSPHRASE_ENV) {
+            Ok(passphrase) if !passphrase.is_empty() => Some(passphrase),
+            _ => keychain_lookup(),
+        })
+        .clone()
+}
+
+fn keychain_lookup() -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", KEYCHAIN_SERVICE, "-a", KEYCHAIN_ACCOUNT, "-w"])
+            .output()
+    } else {
+        Command::new("secret-tool")
+            .args(["lookup", "service", KEYCHAIN_SERVICE, "account", KEYCHAIN_ACCOUNT])
+            .output()
+    }
+    .ok()?;
+    let passphrase = String::from_utf8(output.stdout).ok()?.trim_end_matches('\n').to_string();
+    (output.status.success() && !passphrase.is_empty()).then_some(passphrase)
+}
+
+/// Keep the passphrase in the OS keychain. It goes to the helper on stdin,
+/// never in argv where `ps` would show it to other local users.
+pub fn keychain_store(passphrase: &str) -> Result<()> {
+    use std::io::Write;
+
+    if passphrase.contains(['\n', '\r']) {
+        return Err(MindsError::Validation("The passphrase can't contain a line break".to_string()));
+    }
+    let (mut command, input) = if cfg!(target_os = "macos") {
+        // `security -i` reads its commands from stdin, so the password is never an argument
+        // of a process; `-w` last would prompt on the terminal instead when there is one
+        let mut command = Command::new("security");
+        command.arg("-i");
+        (command, security_store_command(passphrase))
+    } else {
+        let mut command = Command::new("secret-tool");
+        command.args(["store", "--label=100minds wisdom.db", "service", KEYCHAIN_SERVICE, "account", KEYCHAIN_ACCOUNT]);
+        (command, passphrase.to_string())
+    };
+    let mut child = command.stdin(std::process::Stdio::piped()).stdout(std::process::Stdio::null()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    // A failed command inside `security -i` may not show in its exit status, so check what was stored
+    if !child.wait()?.success() || keychain_lookup().as_deref() != Some(passphrase) {
+        return Err(anyhow::anyhow!("Could not store the passphrase in the OS keychain").into());
+    }
+    Ok(())
+}
+
+/// The `security -i` line that stores `passphrase`, quoted for its command parser
+fn security_store_command(passphrase: &str) -> String {
+    let quoted = passphrase.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("add-generic-password -U -s {} -a {} -w \"{}\"\n", KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT, quoted)
+}
+
+/// Key a freshly opened connection if its database is encrypted
+pub fn unlock(conn: &Connection, path: &Path) -> Result<()> {
+    if !is_encrypted_db(path)? {
+        return Ok(());
+    }
+    #[cfg(feature = "encryption")]
+    {
+        let Some(passphrase) = passphrase() else {
+            return Err(MindsError::Config(format!(
+                "{:?} is encrypted: set {} or store the passphrase in the OS keychain",
+                path, PASSPHRASE_ENV
+            )));
+        };
+        apply_key(conn, &passphrase)
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = conn;
+        Err(MindsError::Config(format!("{:?} is encrypted, but this build lacks the `encryption` feature", path)))
+    }
+}
+
+#[cfg(feature = "encryption")]
+fn apply_key(conn: &Connection, passphrase: &str) -> Result<()> {
+    conn.pragma_update(None, "key", passphrase)?;
+    // SQLCipher only checks the key on first read
+    if conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0)).is_err() {
+        return Err(MindsError::Validation("Wrong passphrase for the encrypted database".to_string()));
+    }
+    Ok(())
+}
+
+/// Unseal agent.key contents (plaintext keys pass through). The server
+/// loads the key per request, so unsealed keys are cached for the process.
+pub fn unseal_key(bytes: &[u8]) -> Result<Vec<u8>> {
+    if !is_sealed_key(bytes) {
+        return Ok(bytes.to_vec());
+    }
+    #[cfg(feature = "encryption")]
+    {
+        use std::collections::HashMap;
+        use std::sync::Mutex;
+
+        static UNSEALED: OnceLock<Mutex<HashMap<Vec<u8>, Vec<u8>>>> = OnceLock::new();
+        let cache = UNSEALED.get_or_init(Default::default);
+        if let Some(secret) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(bytes) {
+            return Ok(secret.clone());
+        }
+        let Some(passphrase) = passphrase() else {
+            return Err(MindsError::Config(format!(
+                "agent.key is encrypted: set {} or store the passphrase in the OS keychain",
+                PASSPHRASE_ENV
+            )));
+        };
+        let secret = open_key(bytes, &passphrase)?;
+        cache.lock().unwrap_or_else(|e| e.into_inner()).insert(bytes.to_vec(), secret.clone());
+        Ok(secret)
+    }
+    #[cfg(not(feature = "encryption"))]
+    Err(MindsError::Config("agent.key is encrypted, but this build lacks the `encryption` feature".to_string()))
+}
+
+#[cfg(feature = "encryption")]
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    openssl::pkcs5::pbkdf2_hmac(
+        passphrase.as_bytes(),
+        salt,
+        PBKDF2_ITERATIONS,
+        openssl::hash::MessageDigest::sha256(),
+        &mut key,
+    )
+    .map_err(anyhow::Error::from)?;
+    Ok(key)
+}
+
+/// Seal key material: magic, 16-byte salt, 12-byte nonce, 16-byte tag, ciphertext
+#[cfg(feature = "encryption")]
+pub fn seal_key(secret: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let salt: [u8; 16] = rand::random();
+    let nonce: [u8; 12] = rand::random();
+    let mut tag = [0u8; 16];
+    let ciphertext = openssl::symm::encrypt_aead(
+        openssl::symm::Cipher::aes_256_gcm(),
+        &derive_key(passphrase, &salt)?,
+        Some(&nonce),
+        SEALED_KEY_MAGIC,
+        secret,
+        &mut tag,
+    )
+    .map_err(anyhow::Error::from)?;
+    Ok([SEALED_KEY_MAGIC, &salt, &nonce, &tag, &ciphertext].concat())
+}
+
+#[cfg(feature = "encryption")]
+pub fn open_key(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let body = &sealed[SEALED_KEY_MAGIC.len()..];
+    if body.len() < 44 {
+        return Err(MindsError::Provenance("Sealed key is truncated".to_string()));
+    }
+    let (salt, rest) = body.split_at(16);
+    let (nonce, rest) = rest.split_at(12);
+    let (tag, ciphertext) = rest.split_at(16);
+    openssl::symm::decrypt_aead(
+        openssl::symm::Cipher::aes_256_gcm(),
+        &derive_key(passphrase, salt)?,
+        Some(nonce),
+        SEALED_KEY_MAGIC,
+        ciphertext,
+        tag,
+    )
+    .map_err(|_| MindsError::Validation("Wrong passphrase for agent.key".to_string()))
+}
+
+/// Encrypt a plaintext database in place. The copy is checked before it
+/// replaces the original, and the old WAL and shared-memory files go too.
+#[cfg(feature = "encryption")]
+pub fn encrypt_db(path: &Path, passphrase: &str) -> Result<()> {
+    if is_encrypted_db(path)? {
+        return Err(MindsError::Validation(format!("{:?} is already encrypted", path)));
+    }
+    let staging = path.with_extension("db.encrypting");
+    let _ = std::fs::remove_file(&staging);
+
+    let conn = Connection::open(path)?;
+    let tables = |conn: &Connection| conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0));
+    let expected = tables(&conn)?;
+    conn.execute(
+        "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+        rusqlite::params![staging.to_string_lossy(), passphrase],
+    )?;
+    conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))?;
+    conn.execute("DETACH DATABASE encrypted", [])?;
+    drop(conn);
+
+    let copy = Connection::open(&staging)?;
+    apply_key(&copy, passphrase)?;
+    if tables(&copy)? != expected {
+        return Err(anyhow::anyhow!("Encrypted copy doesn't match {:?}; left it at {:?}", path, staging).into());
+    }
+    drop(copy);
+
+    std::fs::rename(&staging, path)?;
+    for suffix in ["-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+    Ok(())
+}
+
+/// Seal a plaintext agent.key in place
+#[cfg(feature = "encryption")]
+pub fn seal_key_file(path: &Path, passphrase: &str) -> Result<bool> {
+    let bytes = std::fs::read(path)?;
+    if is_sealed_key(&bytes) {
+        return Ok(false);
+    }
+    std::fs::write(path, seal_key(&bytes, passphrase)?)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keychain_passphrase_stays_out_of_argv() {
+        assert_eq!(
+            security_store_command(r#"pa"ss\word"#),
+            "add-generic-password -U -s 100minds -a wisdom.db -w \"pa\\\"ss\\\\word\"\n"
+        );
+        assert!(keychain_store("two\nlines").is_err());
+    }
+
+    #[test]
+    fn test_plaintext_files_are_not_mistaken_for_encrypted_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        assert!(!is_encrypted_db(&path).unwrap());
+        crate::db::init_db(&path).unwrap();
+        assert!(!is_encrypted_db(&path).unwrap());
+        assert!(!is_sealed_key(&[7u8; 32]));
+        assert_eq!(unseal_key(&[7u8; 32]).unwrap(), vec![7u8; 32]);
+
+        std::fs::write(&path, [0xA5u8; 64]).unwrap();
+        assert!(is_encrypted_db(&path).unwrap());
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_encrypt_db_and_seal_key_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        crate::db::init_db(&path)
+            .unwrap()
+            .execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey)
+                 VALUES ('d1', 'q', '{}', 'h', 's', 'k')",
+                [],
+            )
+            .unwrap();
+
+        encrypt_db(&path, "correct horse").unwrap();
+        assert!(is_encrypted_db(&path).unwrap());
+        assert!(encrypt_db(&path, "correct horse").is_err());
+        let conn = Connection::open(&path).unwrap();
+        assert!(apply_key(&conn, "battery staple").is_err());
+        let conn = Connection::open(&path).unwrap();
+        apply_key(&conn, "correct horse").unwrap();
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM decisions", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+
+        let sealed = seal_key(&[7u8; 32], "correct horse").unwrap();
+        assert!(is_sealed_key(&sealed));
+        assert_eq!(open_key(&sealed, "correct horse").unwrap(), vec![7u8; 32]);
+        assert!(open_key(&sealed, "battery staple").is_err());
+    }
+}
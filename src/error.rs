@@ -0,0 +1,99 @@
+//! Library Errors
+//!
+//! Every public library API returns [`MindsError`], so a caller can tell a
+//! missing decision from a locked database from a missing model without
+//! parsing messages; anyhow stays in the binary. Failures with no variant of
+//! their own (a git or HTTP call, a third-party crate's error) are
+//! [`MindsError::Other`], and anyhow errors that wrap a `MindsError` or a
+//! SQLite error are unwrapped back into their own variant.
+
+/// What went wrong in a library call
+#[derive(Debug, thiserror::Error)]
+pub enum MindsError {
+    /// SQLite failed; [`MindsError::is_busy`] tells lock contention apart
+    #[error("database error: {0}")]
+    Db(#[from] rusqlite::Error),
+    /// Signing or hashing the decision chain failed
+    #[error("provenance error: {0}")]
+    Provenance(String),
+    /// Model files are missing or inference failed
+    #[error("embedding error: {0}")]
+    Embedding(String),
+    /// The caller's input can't be used
+    #[error("invalid input: {0}")]
+    Validation(String),
+    /// A decision, principle or other record that doesn't exist
+    #[error("not found: {0}")]
+    NotFound(String),
+    /// config.toml (or another settings file) can't be read as configured
+    #[error("config error: {0}")]
+    Config(String),
+    #[error("serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+pub type Result<T, E = MindsError> = std::result::Result<T, E>;
+
+impl MindsError {
+    /// The database was locked or busy; retrying may succeed
+    pub fn is_busy(&self) -> bool {
+        matches!(
+            self,
+            MindsError::Db(rusqlite::Error::SqliteFailure(e, _))
+                if matches!(e.code, rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+        )
+    }
+}
+
+impl From<anyhow::Error> for MindsError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<MindsError>() {
+            Ok(minds) => return minds,
+            Err(error) => error,
+        };
+        match error.downcast::<rusqlite::Error>() {
+            Ok(db) => MindsError::Db(db),
+            Err(error) => MindsError::Other(error),
+        }
+    }
+}
+
+impl From<ort::Error> for MindsError {
+    fn from(error: ort::Error) -> Self {
+        MindsError::Embedding(error.to_string())
+    }
+}
+
+impl From<reqwest::Error> for MindsError {
+    fn from(error: reqwest::Error) -> Self {
+        MindsError::Other(error.into())
+    }
+}
+
+impl From<ndarray::ShapeError> for MindsError {
+    fn from(error: ndarray::ShapeError) -> Self {
+        MindsError::Embedding(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anyhow_errors_keep_their_kind() {
+        let locked = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            Some("database is locked".to_string()),
+        );
+        assert!(MindsError::from(anyhow::Error::new(locked)).is_busy());
+
+        let missing = anyhow::Error::new(MindsError::NotFound("decision d-1".to_string()));
+        assert!(matches!(MindsError::from(missing), MindsError::NotFound(what) if what == "decision d-1"));
+        assert!(matches!(MindsError::from(anyhow::anyhow!("other")), MindsError::Other(_)));
+    }
+}
@@ -0,0 +1,418 @@
+//! Estimate Sanity Checks
+//!
+//! Estimates are made from the inside view: the plan, the steps, the best
+//! case. Reference-class forecasting takes the outside view instead: how
+//! long did tasks like this one take, relative to their estimates?
+//! `sanity_check_estimate` records every estimate it checks, and once the
+//! actual is recorded (`record_estimate_actual`) the task joins the
+//! reference class for later checks:
+//!
+//! - the reference class is the [`REFERENCE_CLASS`] past tasks with actuals
+//!   most like this one: cosine similarity of task embeddings when the model
+//!   is loaded, overlap of content words otherwise
+//! - with at least [`MIN_REFERENCES`] of them, the low, likely and high
+//!   multipliers are the 10th, 50th and 90th percentiles of their
+//!   actual/estimate ratios; with fewer, a planning-fallacy prior
+//! - principle rules then adjust the multipliers: Hofstadter's Law always
+//!   (never below the estimate, a longer tail), Brooks's Law when the plan
+//!   adds people
+//!
+//! The calibrated range is the estimate times the adjusted multipliers.
+
+use crate::embeddings::SemanticEngine;
+use crate::search;
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Most past tasks in a reference class
+pub const REFERENCE_CLASS: usize = 10;
+/// Past tasks needed before their ratios replace the prior
+pub const MIN_REFERENCES: usize = 3;
+/// Cosine similarity a past task needs to be in the reference class
+const MIN_COSINE: f64 = 0.5;
+/// Share of content words a past task must share without the model
+const MIN_OVERLAP: f64 = 0.2;
+/// Past tasks embedded per check while catching up
+const EMBED_BATCH: usize = 32;
+/// Low, likely and high actual/estimate ratios when there is no reference class
+pub const PRIOR_RATIOS: (f64, f64, f64) = (1.0, 1.5, 2.5);
+
+/// A principle's adjustment to the multipliers
+struct Rule {
+    principle: &'static str,
+    thinker: &'static str,
+    /// Applies when the task mentions any of these; always when empty
+    cues: &'static [&'static str],
+    /// Factors on the likely and high multipliers
+    likely: f64,
+    high: f64,
+    /// Least likely multiplier after the rule
+    floor: f64,
+    reason: &'static str,
+}
+
+const RULES: [Rule; 2] = [
+    Rule {
+        principle: "Hofstadter's Law",
+        thinker: "Douglas Hofstadter",
+        cues: &[],
+        likely: 1.0,
+        high: 1.25,
+        floor: 1.0,
+        reason: "It always takes longer than you expect, even accounting for it: the likely case is never below \
+                 the estimate and the high end runs a quarter longer",
+    },
+    Rule {
+        principle: "Brooks's Law",
+        thinker: "Fred Brooks",
+        cues: &[
+            "hire", "hiring", "onboard", "contractor", "new engineer", "new developer", "more engineer",
+            "more developer", "more people", "add people", "adding people", "staff up", "ramp up",
+        ],
+        likely: 1.2,
+        high: 1.5,
+        floor: 0.0,
+        reason: "The plan adds people: ramp-up and communication overhead come out of the same schedule",
+    },
+];
+
+/// Arguments of `sanity_check_estimate`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EstimateCheckRequest {
+    /// What is being estimated, e.g. 'Add retries to the payment webhooks'
+    pub task: String,
+    /// The estimate, in `unit`
+    pub estimate: f64,
+    /// Unit of the estimate and its actual (default 'days')
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Optional decision the task carries out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision_id: Option<String>,
+}
+
+/// A past task in the reference class
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReferenceTask {
+    pub estimate_id: i64,
+    pub task: String,
+    pub estimate: f64,
+    pub actual: f64,
+    pub unit: String,
+    /// Actual over estimate
+    pub ratio: f64,
+    /// Cosine similarity, or share of content words in common (0-1)
+    pub similarity: f64,
+}
+
+/// A principle rule that changed the multipliers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EstimateAdjustment {
+    pub principle: String,
+    pub thinker: String,
+    /// None when the principle isn't in the corpus
+    pub principle_id: Option<String>,
+    pub likely_factor: f64,
+    pub high_factor: f64,
+    pub reason: String,
+}
+
+/// Low, likely and high values
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Range {
+    pub low: f64,
+    pub likely: f64,
+    pub high: f64,
+}
+
+/// Result of `sanity_check_estimate`
+#[derive(Debug, Clone, Serialize)]
+pub struct EstimateCheck {
+    /// ID to record the actual against
+    pub estimate_id: i64,
+    pub task: String,
+    pub estimate: f64,
+    pub unit: String,
+    /// "reference_class" or "prior"
+    pub basis: String,
+    /// "embedding" or "keywords"
+    pub method: String,
+    /// Most similar first
+    pub references: Vec<ReferenceTask>,
+    /// Multipliers on the estimate, after adjustments
+    pub multipliers: Range,
+    /// Calibrated range, in `unit`
+    pub range: Range,
+    pub adjustments: Vec<EstimateAdjustment>,
+    /// "optimistic", "slightly optimistic", "realistic" or "padded"
+    pub verdict: String,
+}
+
+/// Result of `record_estimate_actual`
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordedActual {
+    pub estimate_id: i64,
+    pub task: String,
+    pub estimate: f64,
+    pub actual: f64,
+    pub unit: String,
+    pub ratio: f64,
+}
+
+impl EstimateCheckRequest {
+    pub fn validate(&self) -> Result<()> {
+        if self.task.trim().is_empty() {
+            return Err(MindsError::Validation("task must not be blank".to_string()));
+        }
+        if !self.estimate.is_finite() || self.estimate <= 0.0 {
+            return Err(MindsError::Validation(format!("estimate must be a positive number, got {}", self.estimate)));
+        }
+        Ok(())
+    }
+
+    pub fn unit(&self) -> &str {
+        self.unit.as_deref().map(str::trim).filter(|u| !u.is_empty()).unwrap_or("days")
+    }
+}
+
+/// Content-word stems of a task
+fn stems(text: &str) -> HashSet<String> {
+    search::tokens(text)
+        .filter(|t| t.chars().count() >= 3 && !search::is_stopword(t))
+        .map(|t| search::stem(&t))
+        .collect()
+}
+
+fn to_blob(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn from_blob(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect()
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Past tasks with actuals most like `task`, most similar first
+fn reference_class(
+    conn: &Connection,
+    engine: Option<&mut SemanticEngine>,
+    task: &str,
+) -> Result<(Vec<ReferenceTask>, Option<Vec<f32>>)> {
+    type Row = (i64, String, f64, f64, String, Option<Vec<u8>>);
+    let load = |conn: &Connection| -> Result<Vec<Row>> {
+        Ok(conn
+            .prepare("SELECT id, task, estimate, actual, unit, embedding FROM estimates WHERE actual IS NOT NULL")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)))?
+            .collect::<rusqlite::Result<_>>()?)
+    };
+    let mut query = None;
+    let scored: Vec<(Row, f64)> = match engine {
+        Some(engine) => {
+            for (id, text, ..) in load(conn)?.into_iter().filter(|r| r.5.is_none()).take(EMBED_BATCH) {
+                let blob = to_blob(&engine.embed(&text)?);
+                conn.execute("UPDATE estimates SET embedding = ?1 WHERE id = ?2", params![blob, id])?;
+            }
+            let vector = engine.embed(task)?;
+            let scored = load(conn)?
+                .into_iter()
+                .filter_map(|row| {
+                    let similarity = SemanticEngine::cosine_similarity(&vector, &from_blob(row.5.as_ref()?)) as f64;
+                    (similarity >= MIN_COSINE).then_some((row, similarity))
+                })
+                .collect();
+            query = Some(vector);
+            scored
+        }
+        None => {
+            let wanted = stems(task);
+            load(conn)?
+                .into_iter()
+                .filter_map(|row| {
+                    let other = stems(&row.1);
+                    let union = wanted.union(&other).count().max(1);
+                    let overlap = wanted.intersection(&other).count() as f64 / union as f64;
+                    (overlap >= MIN_OVERLAP).then_some((row, overlap))
+                })
+                .collect()
+        }
+    };
+    let mut references: Vec<ReferenceTask> = scored
+        .into_iter()
+        .filter(|((_, _, estimate, ..), _)| *estimate > 0.0)
+        .map(|((estimate_id, task, estimate, actual, unit, _), similarity)| ReferenceTask {
+            estimate_id,
+            task,
+            estimate,
+            actual,
+            unit,
+            ratio: actual / estimate,
+            similarity,
+        })
+        .collect();
+    references.sort_by(|a, b| b.similarity.total_cmp(&a.similarity).then(b.estimate_id.cmp(&a.estimate_id)));
+    references.truncate(REFERENCE_CLASS);
+    Ok((references, query))
+}
+
+fn principle_id(conn: &Connection, name: &str, thinker: &str) -> Result<Option<String>> {
+    Ok(conn
+        .query_row(
+            "SELECT p.id FROM principles p JOIN thinkers t ON t.id = p.thinker_id WHERE p.name = ?1 AND t.name = ?2",
+            params![name, thinker],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+/// Check an estimate against similar past tasks and the planning-fallacy
+/// principles, and record it so its actual can be recorded later
+pub fn check(
+    conn: &Connection,
+    engine: Option<&mut SemanticEngine>,
+    request: &EstimateCheckRequest,
+) -> Result<EstimateCheck> {
+    request.validate()?;
+    let semantic = engine.is_some();
+    let (references, embedding) = reference_class(conn, engine, &request.task)?;
+
+    let (basis, mut multipliers) = if references.len() >= MIN_REFERENCES {
+        let mut ratios: Vec<f64> = references.iter().map(|r| r.ratio).collect();
+        ratios.sort_by(f64::total_cmp);
+        let range = Range {
+            low: percentile(&ratios, 0.1),
+            likely: percentile(&ratios, 0.5),
+            high: percentile(&ratios, 0.9),
+        };
+        ("reference_class", range)
+    } else {
+        let (low, likely, high) = PRIOR_RATIOS;
+        ("prior", Range { low, likely, high })
+    };
+
+    let task = request.task.to_lowercase();
+    let mut adjustments = Vec::new();
+    for rule in &RULES {
+        if !rule.cues.is_empty() && !rule.cues.iter().any(|c| task.contains(c)) {
+            continue;
+        }
+        multipliers.likely = (multipliers.likely * rule.likely).max(rule.floor);
+        multipliers.high = (multipliers.high * rule.high).max(multipliers.likely);
+        multipliers.low = multipliers.low.min(multipliers.likely);
+        adjustments.push(EstimateAdjustment {
+            principle: rule.principle.to_string(),
+            thinker: rule.thinker.to_string(),
+            principle_id: principle_id(conn, rule.principle, rule.thinker)?,
+            likely_factor: rule.likely,
+            high_factor: rule.high,
+            reason: rule.reason.to_string(),
+        });
+    }
+    let verdict = match multipliers.likely {
+        m if m >= 1.5 => "optimistic",
+        m if m >= 1.1 => "slightly optimistic",
+        m if m <= 0.9 => "padded",
+        _ => "realistic",
+    };
+
+    let unit = request.unit().to_string();
+    conn.execute(
+        "INSERT INTO estimates (task, estimate, unit, decision_id, embedding) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![request.task, request.estimate, unit, request.decision_id, embedding.as_deref().map(to_blob)],
+    )?;
+    Ok(EstimateCheck {
+        estimate_id: conn.last_insert_rowid(),
+        task: request.task.clone(),
+        estimate: request.estimate,
+        unit,
+        basis: basis.to_string(),
+        method: if semantic { "embedding" } else { "keywords" }.to_string(),
+        references,
+        range: Range {
+            low: request.estimate * multipliers.low,
+            likely: request.estimate * multipliers.likely,
+            high: request.estimate * multipliers.high,
+        },
+        multipliers,
+        adjustments,
+        verdict: verdict.to_string(),
+    })
+}
+
+/// Record how long an estimated task really took (in the estimate's unit)
+pub fn record_actual(conn: &Connection, estimate_id: i64, actual: f64) -> Result<RecordedActual> {
+    if !actual.is_finite() || actual <= 0.0 {
+        return Err(MindsError::Validation(format!("actual must be a positive number, got {}", actual)));
+    }
+    let Some((task, estimate, unit)) = conn
+        .query_row("SELECT task, estimate, unit FROM estimates WHERE id = ?1", [estimate_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?, row.get::<_, String>(2)?))
+        })
+        .optional()?
+    else {
+        return Err(MindsError::NotFound(format!("No estimate {}", estimate_id)));
+    };
+    conn.execute(
+        "UPDATE estimates SET actual = ?2, actual_recorded_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        params![estimate_id, actual],
+    )?;
+    Ok(RecordedActual { estimate_id, task, estimate, actual, unit, ratio: actual / estimate })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimates_are_calibrated_by_similar_past_tasks() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let request = |task: &str, estimate: f64| EstimateCheckRequest {
+            task: task.to_string(),
+            estimate,
+            ..Default::default()
+        };
+
+        // No history yet: the prior, stretched by Hofstadter's Law
+        let first = check(&conn, None, &request("Add retries to the payment webhooks", 2.0)).unwrap();
+        assert_eq!((first.basis.as_str(), first.method.as_str(), first.unit.as_str()), ("prior", "keywords", "days"));
+        assert_eq!(first.multipliers, Range { low: 1.0, likely: 1.5, high: 2.5 * 1.25 });
+        assert_eq!(first.range.likely, 3.0);
+        assert_eq!(first.adjustments.len(), 1);
+        assert_eq!(record_actual(&conn, first.estimate_id, 3.0).unwrap().ratio, 1.5);
+
+        for (task, estimate, actual) in [
+            ("Add retries to the payment webhooks for refunds", 2.0, 4.0),
+            ("Add retries to the payment webhooks for invoices", 5.0, 6.0),
+            ("Add retries to payment webhooks in the mobile app", 1.0, 3.0),
+            ("Migrate the search cluster", 3.0, 30.0),
+        ] {
+            let id = check(&conn, None, &request(task, estimate)).unwrap().estimate_id;
+            record_actual(&conn, id, actual).unwrap();
+        }
+
+        // Ratios 1.2, 1.5, 2, 3 from the four payment webhook tasks; the search migration is unlike it
+        let checked = check(&conn, None, &request("Add retries to the payment webhooks for payouts", 4.0)).unwrap();
+        assert_eq!(checked.basis, "reference_class");
+        assert_eq!(checked.references.len(), 4);
+        assert!(checked.references.iter().all(|r| r.task.contains("payment")));
+        assert_eq!(checked.multipliers, Range { low: 1.2, likely: 1.5, high: 3.0 * 1.25 });
+        assert_eq!((checked.range.likely, checked.verdict.as_str()), (6.0, "optimistic"));
+
+        // Adding people brings in Brooks's Law
+        let staffed = check(&conn, None, &request("Hire two contractors to finish the search migration", 10.0)).unwrap();
+        let principles: Vec<&str> = staffed.adjustments.iter().map(|a| a.principle.as_str()).collect();
+        assert_eq!(principles, ["Hofstadter's Law", "Brooks's Law"]);
+        assert!((staffed.multipliers.likely - 1.8).abs() < 1e-12);
+
+        assert!(check(&conn, None, &request("Anything", 0.0)).is_err());
+        assert!(record_actual(&conn, 999, 1.0).is_err());
+    }
+}
@@ -5,8 +5,14 @@
 //! - Which principles are redundant?
 //! - What domains have coverage gaps?
 //! - Who should we add or remove?
+//!
+//! Decision-history figures come from `coverage_counters`, which
+//! [`crate::db::insert_decision`] keeps up to date, so analysis cost doesn't
+//! grow with the number of decisions. The counters are rebuilt from the
+//! `decisions` table when their total disagrees with it (rows written
+//! around `insert_decision`), or on demand with `--rebuild-coverage`.
 
-use anyhow::Result;
+use crate::error::Result;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -26,6 +32,10 @@ pub struct CoverageAnalysis {
     /// Principles that were never selected in any decision
     pub orphan_principles: Vec<String>,
 
+    /// Decisions per context domain
+    #[serde(default)]
+    pub decision_domains: HashMap<String, u64>,
+
     /// Recommended thinkers to add
     pub recommended_additions: Vec<ThinkerSuggestion>,
 
@@ -43,10 +53,16 @@ pub struct ThinkerSuggestion {
 
 /// Run complete coverage analysis
 pub fn analyze_coverage(conn: &Connection) -> Result<CoverageAnalysis> {
+    if !counters_current(conn)? {
+        let decisions = rebuild_coverage_counters(conn)?;
+        tracing::info!("Coverage counters were stale; rebuilt from {} decisions", decisions);
+    }
+
     let thinker_utilization = analyze_thinker_utilization(conn)?;
     let domain_coverage = analyze_domain_coverage(conn)?;
     let principle_redundancy = find_redundant_principles(conn)?;
     let orphan_principles = find_orphan_principles(conn)?;
+    let decision_domains = counters(conn, "domain")?;
 
     // Generate recommendations based on analysis
     let (recommended_additions, recommended_removals) =
@@ -57,11 +73,45 @@ pub fn analyze_coverage(conn: &Connection) -> Result<CoverageAnalysis> {
         domain_coverage,
         principle_redundancy,
         orphan_principles,
+        decision_domains,
         recommended_additions,
         recommended_removals,
     })
 }
 
+/// Recount `coverage_counters` from every stored decision; returns the decision count
+pub fn rebuild_coverage_counters(conn: &Connection) -> Result<u64> {
+    crate::db::with_savepoint(conn, "rebuild_coverage", || {
+        conn.execute("DELETE FROM coverage_counters", [])?;
+        let mut stmt = conn.prepare("SELECT context_json, counsel_json FROM decisions")?;
+        let mut rows = stmt.query([])?;
+        let mut decisions = 0;
+        while let Some(row) = rows.next()? {
+            let context_json: Option<String> = row.get(0)?;
+            let counsel_json: String = row.get(1)?;
+            crate::db::count_decision_coverage(conn, context_json.as_deref(), &counsel_json)?;
+            decisions += 1;
+        }
+        Ok(decisions)
+    })
+}
+
+/// Whether the counters account for exactly the decisions on record
+fn counters_current(conn: &Connection) -> Result<bool> {
+    let counted = counters(conn, "decisions")?.get("").copied().unwrap_or(0);
+    let total: u64 = conn.query_row("SELECT COUNT(*) FROM decisions", [], |row| row.get(0))?;
+    Ok(counted == total)
+}
+
+/// Counter values of one kind, by key
+fn counters(conn: &Connection, kind: &str) -> Result<HashMap<String, u64>> {
+    let mut stmt = conn.prepare("SELECT key, count FROM coverage_counters WHERE kind = ?1")?;
+    let counts = stmt
+        .query_map([kind], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(counts)
+}
+
 /// Calculate utilization rate for each thinker
 fn analyze_thinker_utilization(conn: &Connection) -> Result<HashMap<String, f64>> {
     let mut utilization = HashMap::new();
@@ -73,8 +123,7 @@ fn analyze_thinker_utilization(conn: &Connection) -> Result<HashMap<String, f64>
         .collect::<Result<Vec<_>, _>>()?;
 
     // Get total decisions
-    let total_decisions: i64 =
-        conn.query_row("SELECT COUNT(*) FROM decisions", [], |row| row.get(0))?;
+    let total_decisions = counters(conn, "decisions")?.get("").copied().unwrap_or(0);
 
     if total_decisions == 0 {
         // No decisions yet - all thinkers have 0 utilization
@@ -84,15 +133,10 @@ fn analyze_thinker_utilization(conn: &Connection) -> Result<HashMap<String, f64>
         return Ok(utilization);
     }
 
-    // Count decisions citing each thinker (via counsel_json)
+    // Decisions citing each thinker
+    let citing = counters(conn, "thinker")?;
     for (thinker_id, thinker_name) in &thinkers {
-        // Search for thinker_id in counsel_json
-        let citing_count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM decisions WHERE counsel_json LIKE ?1",
-            [format!("%\"thinker_id\":\"{}\"%", thinker_id)],
-            |row| row.get(0),
-        )?;
-
+        let citing_count = citing.get(thinker_id).copied().unwrap_or(0);
         let rate = citing_count as f64 / total_decisions as f64;
         utilization.insert(thinker_name.clone(), rate);
     }
@@ -141,8 +185,28 @@ fn analyze_domain_coverage(conn: &Connection) -> Result<HashMap<String, f64>> {
     Ok(coverage)
 }
 
+/// A pair of principles that read alike
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedundantPair {
+    pub id_a: String,
+    pub name_a: String,
+    pub description_a: String,
+    pub id_b: String,
+    pub name_b: String,
+    pub description_b: String,
+    pub similarity: f64,
+}
+
 /// Find principles that are semantically similar (potentially redundant)
 fn find_redundant_principles(conn: &Connection) -> Result<Vec<(String, String, f64)>> {
+    Ok(find_redundant_pairs(conn)?
+        .into_iter()
+        .map(|p| (p.name_a, p.name_b, p.similarity))
+        .collect())
+}
+
+/// Similar principle pairs, most similar first (the `dedupe` work list)
+pub fn find_redundant_pairs(conn: &Connection) -> Result<Vec<RedundantPair>> {
     let mut redundant = Vec::new();
 
     // Get all principles
@@ -155,8 +219,8 @@ fn find_redundant_principles(conn: &Connection) -> Result<Vec<(String, String, f
     // Compare each pair (O(n²) but principles count is small ~345)
     for i in 0..principles.len() {
         for j in (i + 1)..principles.len() {
-            let (_, name_a, desc_a) = &principles[i];
-            let (_, name_b, desc_b) = &principles[j];
+            let (id_a, name_a, desc_a) = &principles[i];
+            let (id_b, name_b, desc_b) = &principles[j];
 
             let similarity = compute_text_similarity(
                 &format!("{} {}", name_a, desc_a),
@@ -165,13 +229,21 @@ fn find_redundant_principles(conn: &Connection) -> Result<Vec<(String, String, f
 
             // High similarity threshold (0.7+) suggests redundancy
             if similarity > 0.7 {
-                redundant.push((name_a.clone(), name_b.clone(), similarity));
+                redundant.push(RedundantPair {
+                    id_a: id_a.clone(),
+                    name_a: name_a.clone(),
+                    description_a: desc_a.clone(),
+                    id_b: id_b.clone(),
+                    name_b: name_b.clone(),
+                    description_b: desc_b.clone(),
+                    similarity,
+                });
             }
         }
     }
 
     // Sort by similarity (highest first)
-    redundant.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
+    redundant.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
 
     Ok(redundant)
 }
@@ -187,35 +259,12 @@ fn find_orphan_principles(conn: &Connection) -> Result<Vec<String>> {
         .filter_map(|r| r.ok())
         .collect();
 
-    // Get all principles cited in decisions
-    let mut cited_stmt = conn.prepare("SELECT counsel_json FROM decisions")?;
-    let counsel_jsons: Vec<String> = cited_stmt
-        .query_map([], |row| row.get(0))?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    // Extract cited principles from JSON
-    let mut cited_principles: HashSet<String> = HashSet::new();
-    for json in &counsel_jsons {
-        // Parse and extract principles_cited arrays
-        if let Ok(counsel) = serde_json::from_str::<serde_json::Value>(json) {
-            if let Some(positions) = counsel.get("positions").and_then(|p| p.as_array()) {
-                for pos in positions {
-                    if let Some(cited) = pos.get("principles_cited").and_then(|c| c.as_array()) {
-                        for p in cited {
-                            if let Some(name) = p.as_str() {
-                                cited_principles.insert(name.to_lowercase());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
+    // Principles cited in any decision
+    let cited_principles = counters(conn, "principle")?;
 
     // Find uncited principles
     for name in &all_names {
-        if !cited_principles.contains(&name.to_lowercase()) {
+        if !cited_principles.contains_key(&name.to_lowercase()) {
             orphans.push(name.clone());
         }
     }
@@ -345,9 +394,9 @@ fn capitalize_domain(domain: &str) -> String {
 
 /// Print coverage analysis results
 pub fn print_coverage_analysis(analysis: &CoverageAnalysis) {
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 📊 100MINDS COVERAGE ANALYSIS                               │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 📊 100MINDS COVERAGE ANALYSIS                               │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
     // Thinker utilization
     let active = analysis
@@ -356,58 +405,68 @@ pub fn print_coverage_analysis(analysis: &CoverageAnalysis) {
         .filter(|&&u| u > 0.01)
         .count();
     let total = analysis.thinker_utilization.len();
-    println!("THINKER UTILIZATION: {}/{} active (>1%)", active, total);
+    say!("THINKER UTILIZATION: {}/{} active (>1%)", active, total);
 
     let mut sorted_util: Vec<_> = analysis.thinker_utilization.iter().collect();
     sorted_util.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
 
-    println!("\nTop 10:");
+    say!("\nTop 10:");
     for (name, util) in sorted_util.iter().take(10) {
         let bar = "█".repeat((**util * 20.0) as usize);
-        println!("   {:30} {:5.1}% {}", name, *util * 100.0, bar);
+        say!("   {:30} {:5.1}% {}", name, *util * 100.0, bar);
     }
 
     // Domain coverage
-    println!("\nDOMAIN COVERAGE:");
+    say!("\nDOMAIN COVERAGE:");
     for (domain, coverage) in &analysis.domain_coverage {
-        println!("   {:25} {:5.1}%", domain, coverage * 100.0);
+        say!("   {:25} {:5.1}%", domain, coverage * 100.0);
+    }
+
+    // Decision domains
+    if !analysis.decision_domains.is_empty() {
+        let mut domains: Vec<_> = analysis.decision_domains.iter().collect();
+        domains.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        say!("\nDECISIONS BY DOMAIN:");
+        for (domain, count) in domains.iter().take(10) {
+            say!("   {:25} {}", domain, count);
+        }
     }
 
     // Orphans
     if !analysis.orphan_principles.is_empty() {
-        println!(
+        say!(
             "\nORPHAN PRINCIPLES ({}): never selected",
             analysis.orphan_principles.len()
         );
         for p in analysis.orphan_principles.iter().take(5) {
-            println!("   • {}", p);
+            say!("   • {}", p);
         }
         if analysis.orphan_principles.len() > 5 {
-            println!("   ... and {} more", analysis.orphan_principles.len() - 5);
+            say!("   ... and {} more", analysis.orphan_principles.len() - 5);
         }
     }
 
     // Redundancy
     if !analysis.principle_redundancy.is_empty() {
-        println!("\nPOTENTIAL REDUNDANCIES:");
+        say!("\nPOTENTIAL REDUNDANCIES:");
         for (a, b, sim) in analysis.principle_redundancy.iter().take(5) {
-            println!("   {:.0}% similar: \"{}\" ↔ \"{}\"", sim * 100.0, a, b);
+            say!("   {:.0}% similar: \"{}\" ↔ \"{}\"", sim * 100.0, a, b);
         }
     }
 
     // Recommendations
     if !analysis.recommended_additions.is_empty() {
-        println!("\nRECOMMENDED ADDITIONS:");
+        say!("\nRECOMMENDED ADDITIONS:");
         for suggestion in &analysis.recommended_additions {
-            println!("   ➕ {} ({})", suggestion.name, suggestion.domain);
-            println!("      {}", suggestion.reason);
+            say!("   ➕ {} ({})", suggestion.name, suggestion.domain);
+            say!("      {}", suggestion.reason);
         }
     }
 
     if !analysis.recommended_removals.is_empty() {
-        println!("\nRECOMMENDED REMOVALS:");
+        say!("\nRECOMMENDED REMOVALS:");
         for name in &analysis.recommended_removals {
-            println!("   ➖ {}", name);
+            say!("   ➖ {}", name);
         }
     }
 }
@@ -447,4 +506,46 @@ mod tests {
         assert_eq!(capitalize_domain("ai-ml"), "Ai Ml");
         assert_eq!(capitalize_domain("security"), "Security");
     }
+
+    #[test]
+    fn test_counters_track_decisions_and_heal_after_direct_inserts() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('knuth', 'Donald Knuth', 'software'),
+                                                        ('gall', 'John Gall', 'systems');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('premature', 'knuth', 'Premature Optimization', 'Measure first'),
+                        ('gall-law', 'gall', 'Gall''s Law', 'Start simple');",
+        )
+        .unwrap();
+
+        let counsel = r#"{"positions":[
+            {"thinker_id":"knuth","principles_cited":["Premature Optimization"]},
+            {"thinker_id":"knuth","principles_cited":["premature optimization"]}]}"#;
+        for id in ["d1", "d2"] {
+            crate::db::insert_decision(&conn, id, "Q", Some(r#"{"domain":"performance"}"#), counsel, None, id, "s", "k")
+                .unwrap();
+        }
+        let analysis = analyze_coverage(&conn).unwrap();
+        assert_eq!(analysis.thinker_utilization["Donald Knuth"], 1.0);
+        assert_eq!(analysis.thinker_utilization["John Gall"], 0.0);
+        assert_eq!(analysis.orphan_principles, vec!["Gall's Law"]);
+        assert_eq!(analysis.decision_domains["performance"], 2);
+
+        // A row written without insert_decision makes the counters stale
+        conn.execute(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey)
+             VALUES ('d3', 'Q', '{\"challenge\":{\"thinker_id\":\"gall\"}}', 'h3', 's', 'k')",
+            [],
+        )
+        .unwrap();
+        let analysis = analyze_coverage(&conn).unwrap();
+        assert!((analysis.thinker_utilization["John Gall"] - 1.0 / 3.0).abs() < 1e-9);
+        assert!((analysis.thinker_utilization["Donald Knuth"] - 2.0 / 3.0).abs() < 1e-9);
+
+        let before = counters(&conn, "thinker").unwrap();
+        assert_eq!(rebuild_coverage_counters(&conn).unwrap(), 3);
+        assert_eq!(counters(&conn, "thinker").unwrap(), before);
+    }
 }
@@ -0,0 +1,390 @@
+//! Cross-Validation for Learned Confidences
+//!
+//! Does learning from outcomes actually predict future outcomes?
+//! - Split outcome history into k folds (by decision, chronologically interleaved)
+//! - Replay confidence adjustments and Thompson updates on the training folds
+//! - Predict held-out outcomes from the learned state
+//! - Score predictions with Brier score and Expected Calibration Error (ECE)
+
+use crate::error::{MindsError, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Cross-validation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossValConfig {
+    /// Number of folds
+    pub folds: usize,
+    /// Number of equal-width bins for the calibration curve
+    pub bins: usize,
+    /// Confidence delta applied on success (mirrors outcome::record_outcome)
+    pub success_delta: f64,
+    /// Confidence delta applied on failure (mirrors outcome::record_outcome)
+    pub failure_delta: f64,
+}
+
+impl Default for CrossValConfig {
+    fn default() -> Self {
+        Self {
+            folds: 5,
+            bins: 10,
+            success_delta: 0.05,
+            failure_delta: -0.10,
+        }
+    }
+}
+
+/// One principle credited (or blamed) by one decision outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Observation {
+    pub decision_id: String,
+    pub principle_id: String,
+    pub success: bool,
+    /// Starting confidence for the principle before any learning
+    pub base_confidence: f64,
+}
+
+/// One bucket of the calibration curve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+    pub mean_predicted: f64,
+    pub observed_rate: f64,
+}
+
+/// Scores for one predictor
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PredictorScore {
+    /// Mean squared error of predicted probability vs outcome (lower is better)
+    pub brier: f64,
+    /// Expected Calibration Error (lower is better)
+    pub ece: f64,
+    pub curve: Vec<CalibrationBin>,
+}
+
+/// Result for a single held-out fold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FoldResult {
+    pub fold: usize,
+    pub train_size: usize,
+    pub test_size: usize,
+    pub confidence: PredictorScore,
+    pub thompson: PredictorScore,
+}
+
+/// Full cross-validation results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossValResults {
+    pub folds: usize,
+    pub total_decisions: usize,
+    pub total_observations: usize,
+    /// Learned confidence (clamped +/- deltas) pooled over all held-out folds
+    pub confidence: PredictorScore,
+    /// Thompson posterior mean alpha/(alpha+beta) pooled over all held-out folds
+    pub thompson: PredictorScore,
+    /// Brier score of always predicting the training success rate
+    pub base_rate_brier: f64,
+    pub per_fold: Vec<FoldResult>,
+}
+
+/// Load outcome history: every adjustment tied to a decision with a recorded outcome
+pub fn load_observations(conn: &Connection) -> Result<Vec<Observation>> {
+    let mut stmt = conn.prepare(
+        "SELECT fa.decision_id, fa.principle_id, d.outcome_success,
+                COALESCE(p.base_confidence, 0.5)
+         FROM framework_adjustments fa
+         JOIN decisions d ON d.id = fa.decision_id
+         LEFT JOIN principles p ON p.id = fa.principle_id
+         WHERE d.outcome_success IS NOT NULL
+         ORDER BY d.created_at, fa.id",
+    )?;
+
+    let observations = stmt
+        .query_map([], |row| {
+            Ok(Observation {
+                decision_id: row.get(0)?,
+                principle_id: row.get(1)?,
+                success: row.get::<_, i32>(2)? == 1,
+                base_confidence: row.get(3)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(observations)
+}
+
+/// Run k-fold cross-validation over the outcome history
+///
+/// Folds are assigned per decision so all principles of one decision land in
+/// the same fold; otherwise the model would be graded on outcomes it trained on.
+pub fn run_crossval(observations: &[Observation], config: &CrossValConfig) -> Result<CrossValResults> {
+    // Decisions in first-seen (chronological) order
+    let mut decision_order: Vec<&str> = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for obs in observations {
+        if seen.insert(obs.decision_id.as_str()) {
+            decision_order.push(obs.decision_id.as_str());
+        }
+    }
+
+    if decision_order.len() < 2 {
+        return Err(MindsError::Validation(format!(
+            "Need at least 2 decisions with recorded outcomes for cross-validation (found {})",
+            decision_order.len()
+        )));
+    }
+
+    let folds = config.folds.clamp(2, decision_order.len());
+    let fold_of: HashMap<&str, usize> = decision_order
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (*id, i % folds))
+        .collect();
+
+    let mut pooled_confidence = Vec::new();
+    let mut pooled_thompson = Vec::new();
+    let mut pooled_base_rate = Vec::new();
+    let mut per_fold = Vec::new();
+
+    for fold in 0..folds {
+        let (test, train): (Vec<&Observation>, Vec<&Observation>) = observations
+            .iter()
+            .partition(|o| fold_of[o.decision_id.as_str()] == fold);
+
+        // Replay learning on the training folds
+        let mut confidence: HashMap<&str, f64> = HashMap::new();
+        let mut thompson: HashMap<&str, (f64, f64)> = HashMap::new();
+        for obs in &train {
+            let conf = confidence
+                .entry(obs.principle_id.as_str())
+                .or_insert(obs.base_confidence);
+            let delta = if obs.success {
+                config.success_delta
+            } else {
+                config.failure_delta
+            };
+            *conf = (*conf + delta).clamp(0.1, 0.95);
+
+            let arm = thompson
+                .entry(obs.principle_id.as_str())
+                .or_insert((1.0, 1.0));
+            if obs.success {
+                arm.0 += 1.0;
+            } else {
+                arm.1 += 1.0;
+            }
+        }
+
+        let train_rate = if train.is_empty() {
+            0.5
+        } else {
+            train.iter().filter(|o| o.success).count() as f64 / train.len() as f64
+        };
+
+        // Predict held-out outcomes
+        let mut fold_confidence = Vec::new();
+        let mut fold_thompson = Vec::new();
+        for obs in &test {
+            let conf = confidence
+                .get(obs.principle_id.as_str())
+                .copied()
+                .unwrap_or(obs.base_confidence);
+            let (alpha, beta) = thompson
+                .get(obs.principle_id.as_str())
+                .copied()
+                .unwrap_or((1.0, 1.0));
+
+            fold_confidence.push((conf, obs.success));
+            fold_thompson.push((alpha / (alpha + beta), obs.success));
+            pooled_base_rate.push((train_rate, obs.success));
+        }
+
+        per_fold.push(FoldResult {
+            fold,
+            train_size: train.len(),
+            test_size: test.len(),
+            confidence: score_predictions(&fold_confidence, config.bins),
+            thompson: score_predictions(&fold_thompson, config.bins),
+        });
+
+        pooled_confidence.extend(fold_confidence);
+        pooled_thompson.extend(fold_thompson);
+    }
+
+    Ok(CrossValResults {
+        folds,
+        total_decisions: decision_order.len(),
+        total_observations: observations.len(),
+        confidence: score_predictions(&pooled_confidence, config.bins),
+        thompson: score_predictions(&pooled_thompson, config.bins),
+        base_rate_brier: brier_score(&pooled_base_rate),
+        per_fold,
+    })
+}
+
+/// Brier score: mean of (predicted - actual)^2
+pub fn brier_score(predictions: &[(f64, bool)]) -> f64 {
+    if predictions.is_empty() {
+        return 0.0;
+    }
+
+    predictions
+        .iter()
+        .map(|(p, y)| {
+            let y = if *y { 1.0 } else { 0.0 };
+            (p - y).powi(2)
+        })
+        .sum::<f64>()
+        / predictions.len() as f64
+}
+
+/// Bucket predictions into equal-width bins, returning the curve and ECE
+pub fn calibration_curve(predictions: &[(f64, bool)], bins: usize) -> (Vec<CalibrationBin>, f64) {
+    let bins = bins.max(1);
+    let mut sums = vec![(0usize, 0.0f64, 0usize); bins];
+
+    for (p, y) in predictions {
+        let idx = ((p.clamp(0.0, 1.0) * bins as f64) as usize).min(bins - 1);
+        sums[idx].0 += 1;
+        sums[idx].1 += p;
+        if *y {
+            sums[idx].2 += 1;
+        }
+    }
+
+    let total = predictions.len().max(1) as f64;
+    let mut ece = 0.0;
+    let mut curve = Vec::new();
+
+    for (i, (count, pred_sum, successes)) in sums.into_iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let mean_predicted = pred_sum / count as f64;
+        let observed_rate = successes as f64 / count as f64;
+        ece += (count as f64 / total) * (mean_predicted - observed_rate).abs();
+
+        curve.push(CalibrationBin {
+            lower: i as f64 / bins as f64,
+            upper: (i + 1) as f64 / bins as f64,
+            count,
+            mean_predicted,
+            observed_rate,
+        });
+    }
+
+    (curve, ece)
+}
+
+fn score_predictions(predictions: &[(f64, bool)], bins: usize) -> PredictorScore {
+    let (curve, ece) = calibration_curve(predictions, bins);
+    PredictorScore {
+        brier: brier_score(predictions),
+        ece,
+        curve,
+    }
+}
+
+/// Print cross-validation results
+pub fn print_crossval_results(results: &CrossValResults) {
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🔁 CROSS-VALIDATION: LEARNED CONFIDENCE                     │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+
+    say!(
+        "{} folds over {} decisions ({} principle outcomes)\n",
+        results.folds, results.total_decisions, results.total_observations
+    );
+
+    say!("HELD-OUT SCORES (lower is better):");
+    say!(
+        "   {:22} Brier: {:.4}  ECE: {:.4}",
+        "Learned confidence", results.confidence.brier, results.confidence.ece
+    );
+    say!(
+        "   {:22} Brier: {:.4}  ECE: {:.4}",
+        "Thompson mean", results.thompson.brier, results.thompson.ece
+    );
+    say!(
+        "   {:22} Brier: {:.4}",
+        "Base rate (reference)", results.base_rate_brier
+    );
+
+    say!("\nPER FOLD:");
+    for fold in &results.per_fold {
+        say!(
+            "   fold {}: train={:4} test={:4}  conf Brier {:.4}  TS Brier {:.4}",
+            fold.fold,
+            fold.train_size,
+            fold.test_size,
+            fold.confidence.brier,
+            fold.thompson.brier
+        );
+    }
+
+    for (label, score) in [
+        ("LEARNED CONFIDENCE", &results.confidence),
+        ("THOMPSON MEAN", &results.thompson),
+    ] {
+        say!("\nCALIBRATION CURVE — {}:", label);
+        say!("   {:11} {:>6} {:>10} {:>10}", "bin", "n", "predicted", "observed");
+        for bin in &score.curve {
+            let bar = "█".repeat((bin.observed_rate * 20.0) as usize);
+            say!(
+                "   {:.1} – {:.1}  {:>6} {:>9.1}% {:>9.1}% {}",
+                bin.lower,
+                bin.upper,
+                bin.count,
+                bin.mean_predicted * 100.0,
+                bin.observed_rate * 100.0,
+                bar
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obs(decision: &str, principle: &str, success: bool) -> Observation {
+        Observation {
+            decision_id: decision.to_string(),
+            principle_id: principle.to_string(),
+            success,
+            base_confidence: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_brier_and_ece() {
+        let perfect = vec![(1.0, true), (0.0, false)];
+        assert_eq!(brier_score(&perfect), 0.0);
+
+        let (curve, ece) = calibration_curve(&[(0.8, true), (0.8, false)], 10);
+        assert_eq!(curve.len(), 1);
+        assert!((curve[0].observed_rate - 0.5).abs() < 1e-9);
+        assert!((ece - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_crossval_learns_reliable_principle() {
+        // "good" always succeeds, "bad" always fails
+        let mut history = Vec::new();
+        for i in 0..20 {
+            history.push(obs(&format!("d{}", i), "good", true));
+            history.push(obs(&format!("d{}", i), "bad", false));
+        }
+
+        let results = run_crossval(&history, &CrossValConfig::default()).unwrap();
+        assert_eq!(results.folds, 5);
+        assert_eq!(results.total_decisions, 20);
+        assert!(results.thompson.brier < results.base_rate_brier);
+        assert!(results.confidence.brier < results.base_rate_brier);
+        assert!(run_crossval(&history[..2], &CrossValConfig::default()).is_err());
+    }
+}
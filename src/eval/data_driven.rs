@@ -10,7 +10,7 @@ use crate::types::{CounselContext, CounselDepth, CounselRequest};
 
 #[allow(unused_imports)] // Used by full evaluation mode
 use crate::types::CounselResponse;
-use anyhow::Result;
+use crate::error::Result;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -90,7 +90,7 @@ pub fn run_fast_evaluation(
 
     for (i, q) in questions.iter().enumerate() {
         if i % 100 == 0 && i > 0 {
-            eprintln!("Progress: {}/{}", i, num_questions);
+            say_err!("Progress: {}/{}", i, num_questions);
         }
 
         let result = evaluate_single_question(conn, &engine, q)?;
@@ -203,6 +203,7 @@ fn evaluate_single_question(
             constraints: vec![],
             prefer_thinkers: vec![],
             depth: CounselDepth::Standard,
+            ..Default::default()
         },
         decision_id: None,  // Auto-generate UUID (eval data)
     };
@@ -301,27 +302,27 @@ fn evaluate_single_question(
 
 /// Print data-driven results
 pub fn print_data_driven_results(results: &DataDrivenResults) {
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 📊 DATA-DRIVEN EVALUATION RESULTS                           │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 📊 DATA-DRIVEN EVALUATION RESULTS                           │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
-    println!(
+    say!(
         "OVERALL: {} questions, avg quality: {:.2}/5.0",
         results.total_questions, results.avg_quality_score
     );
 
-    println!("\nBY DOMAIN:");
+    say!("\nBY DOMAIN:");
     let mut domains: Vec<_> = results.scores_by_domain.iter().collect();
     domains.sort_by(|a, b| b.1.avg_score.partial_cmp(&a.1.avg_score).unwrap());
     for (domain, stats) in domains {
         let bar = "█".repeat((stats.avg_score * 4.0) as usize);
-        println!(
+        say!(
             "   {:20} {:.2}/5.0 {} (n={})",
             domain, stats.avg_score, bar, stats.count
         );
     }
 
-    println!("\nTOP PERFORMING THINKERS:");
+    say!("\nTOP PERFORMING THINKERS:");
     let mut thinkers: Vec<_> = results.thinker_effectiveness.iter().collect();
     thinkers.sort_by(|a, b| {
         b.1.avg_score_when_cited
@@ -329,13 +330,13 @@ pub fn print_data_driven_results(results: &DataDrivenResults) {
             .unwrap()
     });
     for (name, stats) in thinkers.iter().take(10) {
-        println!(
+        say!(
             "   {:25} {:.2}/5.0 (cited {} times)",
             name, stats.avg_score_when_cited, stats.times_cited
         );
     }
 
-    println!("\nTOP PERFORMING PRINCIPLES:");
+    say!("\nTOP PERFORMING PRINCIPLES:");
     let mut principles: Vec<_> = results
         .principle_effectiveness
         .iter()
@@ -347,7 +348,7 @@ pub fn print_data_driven_results(results: &DataDrivenResults) {
             .unwrap()
     });
     for (name, stats) in principles.iter().take(10) {
-        println!(
+        say!(
             "   {:40} {:.2}/5.0 (cited {} times)",
             truncate(name, 40),
             stats.avg_score_when_cited,
@@ -355,9 +356,9 @@ pub fn print_data_driven_results(results: &DataDrivenResults) {
         );
     }
 
-    println!("\nWORST PERFORMERS (questions to investigate):");
+    say!("\nWORST PERFORMERS (questions to investigate):");
     for result in results.worst_performers.iter().take(5) {
-        println!(
+        say!(
             "   [{:.1}] {} - {}",
             result.quality_score,
             result.domain,
@@ -376,12 +377,10 @@ fn truncate(s: &str, max: usize) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_heuristic_scoring() {
         // Basic test that scoring works
         let score = 2.5 + 0.5 + 0.5; // base + positions + diversity
-        assert!(score >= 2.0 && score <= 5.0);
+        assert!((2.0..=5.0).contains(&score));
     }
 }
@@ -3,7 +3,7 @@
 //! Uses Claude Haiku for fast, cheap evaluation at scale.
 //! Implements multi-criteria rubric scoring and pairwise comparison.
 
-use anyhow::Result;
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -3,7 +3,7 @@
 //! Uses Claude Haiku to evaluate decision quality on multiple dimensions.
 //! Fast (~100ms), cheap (~$0.001/judgment), and reasonably accurate.
 
-use anyhow::Result;
+use crate::error::{MindsError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -180,8 +180,8 @@ pub async fn evaluate_responses(
     responses: &[EvalResponse],
 ) -> Result<JudgeResults> {
     if config.api_key.is_empty() {
-        return Err(anyhow::anyhow!(
-            "ANTHROPIC_API_KEY not set. LLM judge requires API access."
+        return Err(MindsError::Config(
+            "ANTHROPIC_API_KEY not set. LLM judge requires API access.".to_string(),
         ));
     }
 
@@ -263,7 +263,7 @@ async fn evaluate_single(
 
     if !api_response.status().is_success() {
         let error_text = api_response.text().await?;
-        return Err(anyhow::anyhow!("API error: {}", error_text));
+        return Err(anyhow::anyhow!("API error: {}", error_text).into());
     }
 
     let response_json: serde_json::Value = api_response.json().await?;
@@ -453,7 +453,7 @@ pub async fn compare_pairwise(
     pairs: &[(EvalResponse, EvalResponse)], // (100minds, baseline)
 ) -> Result<PairwiseResults> {
     if config.api_key.is_empty() {
-        return Err(anyhow::anyhow!("ANTHROPIC_API_KEY not set"));
+        return Err(MindsError::Config("ANTHROPIC_API_KEY not set".to_string()));
     }
 
     let client = reqwest::Client::new();
@@ -567,7 +567,7 @@ Overall a solid response with good balance. Could improve coverage of financial
         assert_eq!(judgment.scores.get("relevance"), Some(&4));
         assert_eq!(judgment.scores.get("actionability"), Some(&5));
         assert!(judgment.strengths.len() >= 2);
-        assert!(judgment.weaknesses.len() >= 1);
+        assert!(!judgment.weaknesses.is_empty());
         assert!(!judgment.reasoning.is_empty());
     }
 
@@ -6,6 +6,7 @@
 //! - Thompson Sampling for principle optimization
 //! - LLM-as-judge quality assessment
 //! - Thinker/principle coverage analysis
+//! - Cross-validated calibration of learned confidences
 //!
 //! 2026 SOTA additions:
 //! - Synthetic question generation (100k-1M scale)
@@ -15,11 +16,13 @@
 
 pub mod bandit;
 pub mod coverage;
+pub mod crossval;
 pub mod data_driven;
 pub mod judge;
 pub mod llm_judge;
 pub mod monte_carlo;
 pub mod neural_training;
+pub mod runs;
 pub mod scenarios;
 pub mod swarm_posterior;  // V4: Multi-agent shared fine-tuning
 pub mod synthetic;
@@ -188,18 +191,18 @@ impl EvalReport {
 
 /// Print evaluation results in a human-readable format
 pub fn print_eval_report(report: &EvalReport) {
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧪 100MINDS EVALUATION REPORT                               │");
-    println!(
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧪 100MINDS EVALUATION REPORT                               │");
+    say!(
         "│    {}                                          │",
         &report.timestamp[..10]
     );
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
     // Overall score
     let score_bar = "█".repeat((report.summary.overall_score * 10.0) as usize);
     let empty_bar = "░".repeat(10 - (report.summary.overall_score * 10.0) as usize);
-    println!(
+    say!(
         "OVERALL SCORE: [{}{}] {:.0}%\n",
         score_bar,
         empty_bar,
@@ -208,29 +211,29 @@ pub fn print_eval_report(report: &EvalReport) {
 
     // Strengths
     if !report.summary.strengths.is_empty() {
-        println!("✅ STRENGTHS:");
+        say!("✅ STRENGTHS:");
         for s in &report.summary.strengths {
-            println!("   • {}", s);
+            say!("   • {}", s);
         }
-        println!();
+        say!();
     }
 
     // Weaknesses
     if !report.summary.weaknesses.is_empty() {
-        println!("⚠️  WEAKNESSES:");
+        say!("⚠️  WEAKNESSES:");
         for w in &report.summary.weaknesses {
-            println!("   • {}", w);
+            say!("   • {}", w);
         }
-        println!();
+        say!();
     }
 
     // Recommendations
     if !report.summary.recommendations.is_empty() {
-        println!("💡 RECOMMENDATIONS:");
+        say!("💡 RECOMMENDATIONS:");
         for r in &report.summary.recommendations {
-            println!("   • {}", r);
+            say!("   • {}", r);
         }
-        println!();
+        say!();
     }
 
     // Detailed sections
@@ -248,84 +251,84 @@ pub fn print_eval_report(report: &EvalReport) {
 }
 
 fn print_scenario_results(results: &scenarios::ScenarioResults) {
-    println!("─────────────────────────────────────────────────────────────");
-    println!("📊 SCENARIO BENCHMARK RESULTS");
-    println!("─────────────────────────────────────────────────────────────");
-    println!("Scenarios run: {}", results.total_scenarios);
-    println!();
+    say!("─────────────────────────────────────────────────────────────");
+    say!("📊 SCENARIO BENCHMARK RESULTS");
+    say!("─────────────────────────────────────────────────────────────");
+    say!("Scenarios run: {}", results.total_scenarios);
+    say!();
 
     // Aggregate metrics
-    println!("AGGREGATE METRICS:");
+    say!("AGGREGATE METRICS:");
     for k in [1, 3, 5] {
         if let Some(&p) = results.aggregate.precision_at_k.get(&k) {
-            println!("   P@{}: {:.1}%", k, p * 100.0);
+            say!("   P@{}: {:.1}%", k, p * 100.0);
         }
     }
-    println!("   Recall: {:.1}%", results.aggregate.recall * 100.0);
-    println!("   NDCG: {:.3}", results.aggregate.ndcg);
-    println!(
+    say!("   Recall: {:.1}%", results.aggregate.recall * 100.0);
+    say!("   NDCG: {:.3}", results.aggregate.ndcg);
+    say!(
         "   Anti-principle rate: {:.1}%",
         results.aggregate.anti_principle_rate * 100.0
     );
-    println!(
+    say!(
         "   Thinker diversity: {:.1}%",
         results.aggregate.thinker_diversity * 100.0
     );
-    println!("   Avg latency: {}ms", results.aggregate.latency_ms);
-    println!();
+    say!("   Avg latency: {}ms", results.aggregate.latency_ms);
+    say!();
 
     // By category
-    println!("BY CATEGORY:");
+    say!("BY CATEGORY:");
     for (cat, metrics) in &results.by_category {
         let p3 = metrics.precision_at_k.get(&3).unwrap_or(&0.0);
-        println!(
+        say!(
             "   {:20} P@3: {:.0}%  Recall: {:.0}%",
             cat,
             p3 * 100.0,
             metrics.recall * 100.0
         );
     }
-    println!();
+    say!();
 }
 
 fn print_monte_carlo_results(results: &monte_carlo::MonteCarloResults) {
-    println!("─────────────────────────────────────────────────────────────");
-    println!("🎲 MONTE CARLO SIMULATION RESULTS");
-    println!("─────────────────────────────────────────────────────────────");
-    println!("Simulations: {}", results.num_simulations);
-    println!("Selection variance: {:.3}", results.selection_variance);
-    println!(
+    say!("─────────────────────────────────────────────────────────────");
+    say!("🎲 MONTE CARLO SIMULATION RESULTS");
+    say!("─────────────────────────────────────────────────────────────");
+    say!("Simulations: {}", results.num_simulations);
+    say!("Selection variance: {:.3}", results.selection_variance);
+    say!(
         "95% CI: [{:.2}, {:.2}]",
         results.confidence_interval_95.0, results.confidence_interval_95.1
     );
-    println!(
+    say!(
         "Tail risk (<50% relevance): {:.1}%",
         results.tail_risk * 100.0
     );
-    println!();
+    say!();
 
     // Top over-selected
-    println!("TOP 5 OVER-SELECTED PRINCIPLES:");
+    say!("TOP 5 OVER-SELECTED PRINCIPLES:");
     let mut sorted: Vec<_> = results.principle_selection_rates.iter().collect();
     sorted.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
     for (name, rate) in sorted.iter().take(5) {
-        println!("   {:.1}% - {}", *rate * 100.0, name);
+        say!("   {:.1}% - {}", *rate * 100.0, name);
     }
-    println!();
+    say!();
 
     // Under-selected
-    println!("TOP 5 UNDER-SELECTED (non-zero):");
+    say!("TOP 5 UNDER-SELECTED (non-zero):");
     sorted.sort_by(|a, b| a.1.partial_cmp(b.1).unwrap());
     for (name, rate) in sorted.iter().filter(|(_, r)| **r > 0.0).take(5) {
-        println!("   {:.1}% - {}", *rate * 100.0, name);
+        say!("   {:.1}% - {}", *rate * 100.0, name);
     }
-    println!();
+    say!();
 }
 
 fn print_coverage_results(analysis: &coverage::CoverageAnalysis) {
-    println!("─────────────────────────────────────────────────────────────");
-    println!("📈 COVERAGE ANALYSIS");
-    println!("─────────────────────────────────────────────────────────────");
+    say!("─────────────────────────────────────────────────────────────");
+    say!("📈 COVERAGE ANALYSIS");
+    say!("─────────────────────────────────────────────────────────────");
 
     // Thinker utilization
     let active = analysis
@@ -334,65 +337,65 @@ fn print_coverage_results(analysis: &coverage::CoverageAnalysis) {
         .filter(|&&u| u > 0.01)
         .count();
     let total = analysis.thinker_utilization.len();
-    println!(
+    say!(
         "Thinker utilization: {}/{} actively cited (>1%)",
         active, total
     );
-    println!();
+    say!();
 
     // Top thinkers
-    println!("TOP 10 THINKERS BY UTILIZATION:");
+    say!("TOP 10 THINKERS BY UTILIZATION:");
     let mut sorted: Vec<_> = analysis.thinker_utilization.iter().collect();
     sorted.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
     for (name, rate) in sorted.iter().take(10) {
         let bar = "█".repeat((**rate * 20.0) as usize);
-        println!("   {:.<20} {:5.1}% {}", name, *rate * 100.0, bar);
+        say!("   {:.<20} {:5.1}% {}", name, *rate * 100.0, bar);
     }
-    println!();
+    say!();
 
     // Domain coverage
-    println!("DOMAIN COVERAGE:");
+    say!("DOMAIN COVERAGE:");
     for (domain, coverage) in &analysis.domain_coverage {
-        println!("   {:25} {:.0}%", domain, coverage * 100.0);
+        say!("   {:25} {:.0}%", domain, coverage * 100.0);
     }
-    println!();
+    say!();
 
     // Orphans
     if !analysis.orphan_principles.is_empty() {
-        println!("⚠️  ORPHAN PRINCIPLES (never selected):");
+        say!("⚠️  ORPHAN PRINCIPLES (never selected):");
         for p in analysis.orphan_principles.iter().take(10) {
-            println!("   • {}", p);
+            say!("   • {}", p);
         }
         if analysis.orphan_principles.len() > 10 {
-            println!("   ... and {} more", analysis.orphan_principles.len() - 10);
+            say!("   ... and {} more", analysis.orphan_principles.len() - 10);
         }
-        println!();
+        say!();
     }
 
     // Redundancy
     if !analysis.principle_redundancy.is_empty() {
-        println!("🔄 REDUNDANT PRINCIPLE PAIRS (similarity > 0.8):");
+        say!("🔄 REDUNDANT PRINCIPLE PAIRS (similarity > 0.8):");
         for (p1, p2, sim) in analysis.principle_redundancy.iter().take(5) {
-            println!("   {:.0}% similar: {} ↔ {}", sim * 100.0, p1, p2);
+            say!("   {:.0}% similar: {} ↔ {}", sim * 100.0, p1, p2);
         }
-        println!();
+        say!();
     }
 
     // Recommendations
     if !analysis.recommended_removals.is_empty() {
-        println!("🗑️  RECOMMENDED REMOVALS:");
+        say!("🗑️  RECOMMENDED REMOVALS:");
         for name in &analysis.recommended_removals {
-            println!("   • {}", name);
+            say!("   • {}", name);
         }
-        println!();
+        say!();
     }
 
     if !analysis.recommended_additions.is_empty() {
-        println!("➕ RECOMMENDED ADDITIONS:");
+        say!("➕ RECOMMENDED ADDITIONS:");
         for suggestion in &analysis.recommended_additions {
-            println!("   • {} (domain: {})", suggestion.name, suggestion.domain);
-            println!("     Reason: {}", suggestion.reason);
+            say!("   • {} (domain: {})", suggestion.name, suggestion.domain);
+            say!("     Reason: {}", suggestion.reason);
         }
-        println!();
+        say!();
     }
 }
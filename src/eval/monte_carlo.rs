@@ -4,16 +4,22 @@
 //! - Which principles are over/under-selected
 //! - Selection variance and stability
 //! - Tail risk (poor recommendations)
+//!
+//! Simulations run in parallel; raw per-query samples can be written to
+//! CSV for offline analysis.
 
 use crate::counsel::CounselEngine;
 use crate::db::PrincipleMatch;
 use crate::provenance::Provenance;
+use crate::snapshot::CorpusSnapshot;
 use crate::types::*;
-use anyhow::Result;
+use crate::error::Result;
 use rand::prelude::*;
+use rayon::prelude::*;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Configuration for Monte Carlo simulation
 #[derive(Debug, Clone)]
@@ -29,6 +35,12 @@ pub struct MonteCarloConfig {
 
     /// Random seed for reproducibility (None = random)
     pub seed: Option<u64>,
+
+    /// Worker threads (None = one per core)
+    pub threads: Option<usize>,
+
+    /// Show a progress bar on stderr
+    pub progress: bool,
 }
 
 impl Default for MonteCarloConfig {
@@ -38,6 +50,8 @@ impl Default for MonteCarloConfig {
             question_templates: default_question_templates(),
             user_behavior: UserBehaviorModel::default(),
             seed: None,
+            threads: None,
+            progress: false,
         }
     }
 }
@@ -105,35 +119,42 @@ pub struct OutcomeDistribution {
     pub failure_rate: f64,
 }
 
-/// Run Monte Carlo simulation
-pub fn run_simulation(
-    conn: &Connection,
-    provenance: &Provenance,
-    config: &MonteCarloConfig,
-) -> Result<MonteCarloResults> {
-    let mut rng = match config.seed {
-        Some(seed) => StdRng::seed_from_u64(seed),
-        None => StdRng::from_entropy(),
-    };
+/// Simulations handled by one deterministic RNG stream
+const CHUNK_SIZE: u32 = 250;
+
+/// One simulated query (a row of the samples CSV)
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationSample {
+    pub index: u32,
+    pub question: String,
+    /// Mean stated confidence of the positions (relevance proxy)
+    pub relevance: f64,
+    /// "success" | "partial" | "failure"
+    pub outcome: &'static str,
+    pub principles: Vec<String>,
+    pub thinkers: Vec<String>,
+}
 
-    let engine = CounselEngine::new(conn, provenance);
+/// Seed for a chunk's RNG: depends only on the base seed and chunk index,
+/// so a seeded run gives the same questions and rolls on any thread count
+fn chunk_seed(base: u64, chunk: u32) -> u64 {
+    base.wrapping_add((chunk as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
 
-    // Track selections across all simulations
-    let mut principle_counts: HashMap<String, u32> = HashMap::new();
-    let mut thinker_counts: HashMap<String, u32> = HashMap::new();
-    let mut relevance_scores: Vec<f64> = Vec::new();
-    let mut outcome_success = 0u32;
-    let mut outcome_partial = 0u32;
-    let mut outcome_fail = 0u32;
+/// Simulate queries `range` with their own RNG; failed counsel calls are skipped
+fn run_chunk(
+    engine: &CounselEngine,
+    config: &MonteCarloConfig,
+    seed: u64,
+    range: std::ops::Range<u32>,
+    progress: &indicatif::ProgressBar,
+) -> Vec<SimulationSample> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut samples = Vec::with_capacity(range.len());
 
-    // Get all principles for baseline
-    let all_principles = get_all_principles(conn)?;
-    for p in &all_principles {
-        principle_counts.insert(p.name.clone(), 0);
-    }
+    for index in range {
+        progress.inc(1);
 
-    // Run simulations
-    for _ in 0..config.num_simulations {
         // Generate random question
         let question = generate_random_question(&config.question_templates, &mut rng);
 
@@ -144,33 +165,18 @@ pub fn run_simulation(
             decision_id: None,  // Auto-generate UUID (Monte Carlo sim)
         };
 
-        // Run counsel (but don't store decisions to avoid polluting DB)
         let response = match engine.counsel(&request) {
             Ok(r) => r,
             Err(_) => continue, // Skip failed queries
         };
 
-        // Track selections
-        let mut sim_relevance = 0.0;
-        let mut total_confidence = 0.0;
-
-        for position in &response.positions {
-            for principle in &position.principles_cited {
-                *principle_counts.entry(principle.clone()).or_insert(0) += 1;
-            }
-            *thinker_counts.entry(position.thinker.clone()).or_insert(0) += 1;
-
-            // Estimate relevance based on confidence (proxy for ground truth)
-            sim_relevance += position.confidence;
-            total_confidence += 1.0;
-        }
-
-        let avg_relevance = if total_confidence > 0.0 {
-            sim_relevance / total_confidence
-        } else {
+        // Estimate relevance based on confidence (proxy for ground truth)
+        let sim_relevance: f64 = response.positions.iter().map(|p| p.confidence).sum();
+        let avg_relevance = if response.positions.is_empty() {
             0.0
+        } else {
+            sim_relevance / response.positions.len() as f64
         };
-        relevance_scores.push(avg_relevance);
 
         // Simulate user outcome based on behavior model
         let acceptance_prob = config.user_behavior.base_acceptance
@@ -178,14 +184,116 @@ pub fn run_simulation(
             + config.user_behavior.confidence_weight * (sim_relevance / 4.0); // Normalize
 
         let outcome_roll: f64 = rng.gen();
-        if outcome_roll < acceptance_prob * 0.6 {
-            outcome_success += 1;
+        let outcome = if outcome_roll < acceptance_prob * 0.6 {
+            "success"
         } else if outcome_roll < acceptance_prob {
-            outcome_partial += 1;
+            "partial"
         } else {
-            outcome_fail += 1;
+            "failure"
+        };
+
+        samples.push(SimulationSample {
+            index,
+            question,
+            relevance: avg_relevance,
+            outcome,
+            principles: response.positions.iter().flat_map(|p| p.principles_cited.clone()).collect(),
+            thinkers: response.positions.iter().map(|p| p.thinker.clone()).collect(),
+        });
+    }
+    samples
+}
+
+/// Run Monte Carlo simulation
+///
+/// Chunks of [`CHUNK_SIZE`] simulations run in parallel, each on its own
+/// database connection, reading the corpus from a shared in-memory snapshot.
+pub fn run_simulation(
+    conn: &Connection,
+    provenance: &Provenance,
+    config: &MonteCarloConfig,
+) -> Result<MonteCarloResults> {
+    Ok(run_simulation_with_samples(conn, provenance, config)?.0)
+}
+
+/// [`run_simulation`], also returning every per-query sample (in index order)
+pub fn run_simulation_with_samples(
+    conn: &Connection,
+    provenance: &Provenance,
+    config: &MonteCarloConfig,
+) -> Result<(MonteCarloResults, Vec<SimulationSample>)> {
+    let base_seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let snapshot = Arc::new(CorpusSnapshot::load(conn)?);
+
+    let progress = if config.progress {
+        let bar = indicatif::ProgressBar::new(config.num_simulations as u64);
+        bar.set_style(
+            indicatif::ProgressStyle::with_template("{bar:40} {pos}/{len} sims ({per_sec}, eta {eta})")
+                .map_err(anyhow::Error::from)?,
+        );
+        bar
+    } else {
+        indicatif::ProgressBar::hidden()
+    };
+
+    let chunks: Vec<(u32, std::ops::Range<u32>)> = (0..config.num_simulations)
+        .step_by(CHUNK_SIZE as usize)
+        .enumerate()
+        .map(|(i, start)| (i as u32, start..(start + CHUNK_SIZE).min(config.num_simulations)))
+        .collect();
+
+    // A file-backed database can be opened once per chunk; an in-memory one can't be shared
+    let db_path = conn.path().filter(|p| !p.is_empty()).map(std::path::PathBuf::from);
+    let mut samples: Vec<SimulationSample> = match db_path {
+        Some(path) => {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(config.threads.unwrap_or(0))
+                .build()
+                .map_err(anyhow::Error::from)?;
+            let per_chunk: Vec<Result<Vec<SimulationSample>>> = pool.install(|| {
+                chunks
+                    .par_iter()
+                    .map(|(chunk, range)| {
+                        let chunk_conn = Connection::open(&path)?;
+                        chunk_conn.busy_timeout(std::time::Duration::from_secs(10))?;
+                        let engine = CounselEngine::new(&chunk_conn, provenance).with_snapshot(Arc::clone(&snapshot));
+                        Ok(run_chunk(&engine, config, chunk_seed(base_seed, *chunk), range.clone(), &progress))
+                    })
+                    .collect()
+            });
+            per_chunk.into_iter().collect::<Result<Vec<_>>>()?.into_iter().flatten().collect()
         }
+        None => {
+            let engine = CounselEngine::new(conn, provenance).with_snapshot(Arc::clone(&snapshot));
+            chunks
+                .iter()
+                .flat_map(|(chunk, range)| {
+                    run_chunk(&engine, config, chunk_seed(base_seed, *chunk), range.clone(), &progress)
+                })
+                .collect()
+        }
+    };
+    progress.finish_and_clear();
+    samples.sort_by_key(|s| s.index);
+
+    // Track selections across all simulations
+    let mut principle_counts: HashMap<String, u32> = HashMap::new();
+    let mut thinker_counts: HashMap<String, u32> = HashMap::new();
+
+    // Get all principles for baseline
+    for p in get_all_principles(conn)? {
+        principle_counts.insert(p.name, 0);
     }
+    for sample in &samples {
+        for principle in &sample.principles {
+            *principle_counts.entry(principle.clone()).or_insert(0) += 1;
+        }
+        for thinker in &sample.thinkers {
+            *thinker_counts.entry(thinker.clone()).or_insert(0) += 1;
+        }
+    }
+    let relevance_scores: Vec<f64> = samples.iter().map(|s| s.relevance).collect();
+    let outcome_count = |o: &str| samples.iter().filter(|s| s.outcome == o).count();
 
     // Compute statistics
     let n = config.num_simulations as f64;
@@ -224,14 +332,14 @@ pub fn run_simulation(
     let selection_variance = compute_selection_variance(&principle_rates);
 
     // Outcome distribution
-    let total_outcomes = (outcome_success + outcome_partial + outcome_fail) as f64;
+    let total_outcomes = samples.len() as f64;
     let outcomes = OutcomeDistribution {
-        success_rate: outcome_success as f64 / total_outcomes,
-        partial_success_rate: outcome_partial as f64 / total_outcomes,
-        failure_rate: outcome_fail as f64 / total_outcomes,
+        success_rate: outcome_count("success") as f64 / total_outcomes,
+        partial_success_rate: outcome_count("partial") as f64 / total_outcomes,
+        failure_rate: outcome_count("failure") as f64 / total_outcomes,
     };
 
-    Ok(MonteCarloResults {
+    let results = MonteCarloResults {
         num_simulations: config.num_simulations,
         principle_selection_rates: principle_rates,
         thinker_selection_rates: thinker_rates,
@@ -239,7 +347,31 @@ pub fn run_simulation(
         selection_variance,
         tail_risk,
         simulated_outcomes: outcomes,
-    })
+    };
+    Ok((results, samples))
+}
+
+/// Write samples as CSV (principles and thinkers `;`-separated)
+pub fn write_samples_csv(path: &std::path::Path, samples: &[SimulationSample]) -> Result<()> {
+    use std::io::Write;
+
+    let quote = |field: &str| format!("\"{}\"", field.replace('"', "\"\""));
+    let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(out, "index,question,relevance,outcome,principles,thinkers")?;
+    for s in samples {
+        writeln!(
+            out,
+            "{},{},{:.4},{},{},{}",
+            s.index,
+            quote(&s.question),
+            s.relevance,
+            s.outcome,
+            quote(&s.principles.join(";")),
+            quote(&s.thinkers.join(";"))
+        )?;
+    }
+    out.flush()?;
+    Ok(())
 }
 
 /// Generate a random question from templates
@@ -431,4 +563,41 @@ mod tests {
         let var2 = compute_selection_variance(&rates);
         assert!(var2 > var);
     }
+
+    #[test]
+    fn test_parallel_runs_are_seeded_per_chunk() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            r#"
+            INSERT INTO thinkers (id, name, domain) VALUES ('knuth', 'Donald Knuth', 'software');
+            INSERT INTO principles (id, thinker_id, name, description, domain_tags, learned_confidence)
+                VALUES ('premature', 'knuth', 'Premature Optimization', 'Measure before you optimize performance', '["performance"]', 0.7);
+            "#,
+        )
+        .unwrap();
+        let provenance = Provenance::init(&dir.path().join("agent.key")).unwrap();
+
+        let run = |threads| {
+            let config = MonteCarloConfig {
+                num_simulations: CHUNK_SIZE + 20,
+                seed: Some(7),
+                threads: Some(threads),
+                ..Default::default()
+            };
+            run_simulation_with_samples(&conn, &provenance, &config).unwrap().1
+        };
+        let serial = run(1);
+        let parallel = run(4);
+        assert_eq!(serial.len(), (CHUNK_SIZE + 20) as usize);
+        assert!(serial.iter().enumerate().all(|(i, s)| s.index == i as u32));
+        let questions = |samples: &[SimulationSample]| samples.iter().map(|s| s.question.clone()).collect::<Vec<_>>();
+        assert_eq!(questions(&serial), questions(&parallel));
+
+        let csv = dir.path().join("samples.csv");
+        write_samples_csv(&csv, &parallel).unwrap();
+        let text = std::fs::read_to_string(&csv).unwrap();
+        assert_eq!(text.lines().count(), parallel.len() + 1);
+        assert!(text.starts_with("index,question,relevance,outcome,principles,thinkers\n0,\""));
+    }
 }
@@ -17,7 +17,7 @@ use super::synthetic::{generate_sample, GeneratorConfig};
 use crate::counsel::CounselEngine;
 use crate::provenance::Provenance;
 use crate::types::{CounselContext, CounselDepth, CounselRequest};
-use anyhow::Result;
+use crate::error::Result;
 use rand::prelude::*;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
@@ -374,7 +374,7 @@ pub fn generate_training_data(
 
     for (q_idx, question) in questions.iter().enumerate() {
         if q_idx % 1000 == 0 && q_idx > 0 {
-            eprintln!("Progress: {}/{}", q_idx, config.num_questions);
+            say_err!("Progress: {}/{}", q_idx, config.num_questions);
         }
 
         // Get counsel for this question
@@ -385,6 +385,7 @@ pub fn generate_training_data(
                 constraints: vec![],
                 prefer_thinkers: vec![],
                 depth: CounselDepth::Standard,
+                ..Default::default()
             },
             decision_id: None,  // Auto-generate UUID (training data)
         };
@@ -586,37 +587,37 @@ pub fn export_to_csv(batch: &TrainingBatch, path: &std::path::Path) -> Result<()
 
 /// Print training batch summary
 pub fn print_batch_summary(batch: &TrainingBatch) {
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧠 NEURAL BANDIT TRAINING DATA                              │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧠 NEURAL BANDIT TRAINING DATA                              │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
     let meta = &batch.metadata;
 
-    println!("OVERVIEW:");
-    println!("   Total examples:     {:>8}", meta.total_examples);
-    println!("   Positive (success): {:>8} ({:.1}%)",
+    say!("OVERVIEW:");
+    say!("   Total examples:     {:>8}", meta.total_examples);
+    say!("   Positive (success): {:>8} ({:.1}%)",
              meta.positive_examples,
              100.0 * meta.positive_examples as f64 / meta.total_examples.max(1) as f64);
-    println!("   Negative (failure): {:>8} ({:.1}%)",
+    say!("   Negative (failure): {:>8} ({:.1}%)",
              meta.negative_examples,
              100.0 * meta.negative_examples as f64 / meta.total_examples.max(1) as f64);
 
-    println!("\nDIVERSITY:");
-    println!("   Unique questions:   {:>8}", meta.unique_questions);
-    println!("   Unique principles:  {:>8}", meta.unique_principles);
-    println!("   Unique thinkers:    {:>8}", meta.unique_thinkers);
-    println!("   Avg examples/q:     {:>8.1}", meta.avg_examples_per_question);
+    say!("\nDIVERSITY:");
+    say!("   Unique questions:   {:>8}", meta.unique_questions);
+    say!("   Unique principles:  {:>8}", meta.unique_principles);
+    say!("   Unique thinkers:    {:>8}", meta.unique_thinkers);
+    say!("   Avg examples/q:     {:>8.1}", meta.avg_examples_per_question);
 
-    println!("\nDOMAINS:");
+    say!("\nDOMAINS:");
     let mut domains: Vec<_> = meta.domains.iter().collect();
     domains.sort_by(|a, b| b.1.cmp(a.1));
     for (domain, count) in domains.iter().take(10) {
         let pct = 100.0 * (**count as f64) / meta.unique_questions.max(1) as f64;
         let bar = "█".repeat((pct / 5.0) as usize);
-        println!("   {:20} {:>5} ({:>5.1}%) {}", domain, count, pct, bar);
+        say!("   {:20} {:>5} ({:>5.1}%) {}", domain, count, pct, bar);
     }
 
-    println!("\nSEED: {}", meta.generation_seed);
+    say!("\nSEED: {}", meta.generation_seed);
 }
 
 #[cfg(test)]
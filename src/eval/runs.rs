@@ -0,0 +1,230 @@
+//! Benchmark Run History
+//!
+//! Every `--benchmark all` run is kept in the `benchmark_runs` table with
+//! the code and corpus version it ran against, so `--benchmark history` can
+//! compare the latest run with an earlier one. A metric that got worse by
+//! more than the regression threshold (`[benchmark] regression_threshold`
+//! in config.toml, in absolute points on the metric's 0-1 scale) is a
+//! regression, and `history` exits non-zero so CI can gate on it.
+
+use super::EvalReport;
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Metrics tracked across runs, and whether higher is better
+pub const KEY_METRICS: [(&str, bool); 6] = [
+    ("overall_score", true),
+    ("precision_at_3", true),
+    ("recall", true),
+    ("ndcg", true),
+    ("anti_principle_rate", false),
+    ("tail_risk", false),
+];
+
+/// The `[benchmark]` table of config.toml
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BenchmarkConfig {
+    pub regression_threshold: f64,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        BenchmarkConfig { regression_threshold: 0.05 }
+    }
+}
+
+impl BenchmarkConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let config: BenchmarkConfig = crate::config::section_at(path, "benchmark")?;
+        if config.regression_threshold.is_nan() || config.regression_threshold < 0.0 {
+            let problem = format!("{}: benchmark.regression_threshold must be 0 or more", path.display());
+            return Err(MindsError::Config(problem));
+        }
+        Ok(config)
+    }
+}
+
+/// One stored run (the full report stays in `report_json`)
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkRun {
+    pub id: i64,
+    pub run_at: String,
+    pub code_version: String,
+    pub corpus_version: String,
+    pub metrics: BTreeMap<String, f64>,
+}
+
+/// How one metric moved between two runs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricChange {
+    pub metric: String,
+    pub before: f64,
+    pub after: f64,
+    pub delta: f64,
+    /// Worse by more than the threshold
+    pub regression: bool,
+}
+
+/// `MINDS_CODE_VERSION` if set (CI can pass a commit), else the crate version
+pub fn code_version() -> String {
+    std::env::var("MINDS_CODE_VERSION")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string())
+}
+
+/// A short hash of the principles' content, so runs against a changed
+/// corpus can be told apart (learned confidences don't count)
+pub fn corpus_version(conn: &Connection) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut stmt = conn.prepare("SELECT id, thinker_id, name, description FROM principles ORDER BY id")?;
+    let mut rows = stmt.query([])?;
+    let mut count = 0;
+    while let Some(row) = rows.next()? {
+        for i in 0..4 {
+            hasher.update(row.get::<_, String>(i)?.as_bytes());
+            hasher.update([0]);
+        }
+        count += 1;
+    }
+    Ok(format!("{}p-{}", count, &hex::encode(hasher.finalize())[..12]))
+}
+
+/// The tracked metrics a report has (a suite that didn't run leaves its own out)
+pub fn key_metrics(report: &EvalReport) -> BTreeMap<String, f64> {
+    let mut metrics = BTreeMap::new();
+    metrics.insert("overall_score".to_string(), report.summary.overall_score);
+    if let Some(sr) = &report.scenario_results {
+        let m = &sr.aggregate;
+        metrics.insert("precision_at_3".to_string(), m.precision_at_k.get(&3).copied().unwrap_or(0.0));
+        metrics.insert("recall".to_string(), m.recall);
+        metrics.insert("ndcg".to_string(), m.ndcg);
+        metrics.insert("anti_principle_rate".to_string(), m.anti_principle_rate);
+    }
+    if let Some(mc) = &report.monte_carlo_results {
+        metrics.insert("tail_risk".to_string(), mc.tail_risk);
+    }
+    metrics
+}
+
+/// Store a report; returns the run id
+pub fn record(conn: &Connection, report: &EvalReport, code_version: &str, corpus_version: &str) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO benchmark_runs (run_at, code_version, corpus_version, overall_score, metrics_json, report_json)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            report.timestamp,
+            code_version,
+            corpus_version,
+            report.summary.overall_score,
+            serde_json::to_string(&key_metrics(report))?,
+            serde_json::to_string(report)?,
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn run_from_row(row: &rusqlite::Row) -> rusqlite::Result<BenchmarkRun> {
+    let metrics: String = row.get(4)?;
+    Ok(BenchmarkRun {
+        id: row.get(0)?,
+        run_at: row.get(1)?,
+        code_version: row.get(2)?,
+        corpus_version: row.get(3)?,
+        metrics: serde_json::from_str(&metrics)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e)))?,
+    })
+}
+
+/// The newest `limit` runs, newest first
+pub fn list(conn: &Connection, limit: usize) -> Result<Vec<BenchmarkRun>> {
+    conn.prepare(
+        "SELECT id, run_at, code_version, corpus_version, metrics_json FROM benchmark_runs ORDER BY id DESC LIMIT ?1",
+    )?
+    .query_map([limit as i64], run_from_row)?
+    .collect::<rusqlite::Result<_>>()
+    .map_err(Into::into)
+}
+
+pub fn get(conn: &Connection, id: i64) -> Result<Option<BenchmarkRun>> {
+    conn.query_row(
+        "SELECT id, run_at, code_version, corpus_version, metrics_json FROM benchmark_runs WHERE id = ?1",
+        [id],
+        run_from_row,
+    )
+    .optional()
+    .map_err(Into::into)
+}
+
+/// How each metric both runs have moved from `before` to `after`
+pub fn compare(before: &BenchmarkRun, after: &BenchmarkRun, threshold: f64) -> Vec<MetricChange> {
+    KEY_METRICS
+        .iter()
+        .filter_map(|&(metric, higher_is_better)| {
+            let (before, after) = (*before.metrics.get(metric)?, *after.metrics.get(metric)?);
+            let delta = after - before;
+            let worse_by = if higher_is_better { -delta } else { delta };
+            Some(MetricChange { metric: metric.to_string(), before, after, delta, regression: worse_by > threshold })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{EvalMetrics, EvalSummary};
+
+    fn report(score: f64, recall: f64, anti: f64) -> EvalReport {
+        let metrics = EvalMetrics { recall, anti_principle_rate: anti, ..Default::default() };
+        EvalReport {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            scenario_results: Some(crate::eval::scenarios::ScenarioResults {
+                total_scenarios: 1,
+                aggregate: metrics.clone(),
+                unweighted: metrics,
+                by_category: Default::default(),
+                individual: vec![],
+                worst_performers: vec![],
+            }),
+            monte_carlo_results: None,
+            coverage_analysis: None,
+            judge_results: None,
+            summary: EvalSummary {
+                overall_score: score,
+                strengths: vec![],
+                weaknesses: vec![],
+                recommendations: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_history_flags_regressions_beyond_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description) VALUES ('yagni', 't', 'YAGNI', 'd');",
+        )
+        .unwrap();
+        let corpus = corpus_version(&conn).unwrap();
+        assert!(corpus.starts_with("1p-"));
+
+        record(&conn, &report(0.70, 0.60, 0.05), "1.0.0", &corpus).unwrap();
+        let id = record(&conn, &report(0.68, 0.50, 0.12), "1.0.1", &corpus).unwrap();
+        let runs = list(&conn, 10).unwrap();
+        assert_eq!((runs.len(), runs[0].id, runs[0].code_version.as_str()), (2, id, "1.0.1"));
+        assert!(get(&conn, id + 1).unwrap().is_none());
+
+        let changes = compare(&runs[1], &runs[0], 0.05);
+        let regressed: Vec<_> = changes.iter().filter(|c| c.regression).map(|c| c.metric.as_str()).collect();
+        // Score dipped within the threshold; recall fell and anti-principles rose past it
+        assert_eq!(regressed, vec!["recall", "anti_principle_rate"]);
+        assert!(!compare(&runs[0], &runs[1], 0.05).iter().any(|c| c.regression));
+    }
+}
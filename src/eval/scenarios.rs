@@ -2,28 +2,47 @@
 //!
 //! Test 100minds against scenarios with known ground truth.
 //! Measures precision, recall, NDCG, and anti-principle detection.
+//!
+//! Scenario files are JSON arrays of [`ScenarioCase`], found anywhere under
+//! the scenario directory. A file in a subdirectory (`architecture/*.json`)
+//! may leave out `category`; it takes the subdirectory's name. Files are
+//! checked against the [`scenario_schema`] before loading, and every problem
+//! is reported as `file:line:column: message` (`--benchmark scenarios --lint`
+//! checks without running anything).
+//!
+//! `--filter category=architecture,difficulty>=4,tag=legacy` runs a subset
+//! (see [`ScenarioFilter`]). Aggregates weight each scenario by its
+//! difficulty, so a gain on a 5 counts five times a gain on a 1; the plain
+//! mean is kept as `unweighted`.
 
 use super::EvalMetrics;
 use crate::counsel::CounselEngine;
 use crate::provenance::Provenance;
 use crate::types::*;
-use anyhow::Result;
+use crate::error::{MindsError, Result};
 use rusqlite::Connection;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// A single test scenario with ground truth
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct ScenarioCase {
     /// Unique identifier
+    #[schemars(length(min = 1))]
     pub id: String,
 
-    /// Category (e.g., "architecture", "scaling", "performance")
+    /// Category (e.g., "architecture", "scaling", "performance"); defaults
+    /// to the name of the subdirectory the file is in
+    #[serde(default)]
     pub category: String,
 
     /// The decision question to ask
+    #[schemars(length(min = 1))]
     pub question: String,
 
     /// Additional context for the question
@@ -42,7 +61,12 @@ pub struct ScenarioCase {
 
     /// Difficulty level (1-5)
     #[serde(default = "default_difficulty")]
+    #[schemars(range(min = 1, max = 5))]
     pub difficulty: u8,
+
+    /// Free-form labels to filter on (e.g., "legacy", "security")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 fn default_difficulty() -> u8 {
@@ -53,7 +77,11 @@ fn default_difficulty() -> u8 {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScenarioResults {
     pub total_scenarios: usize,
+    /// Difficulty-weighted
     pub aggregate: EvalMetrics,
+    /// Every scenario counted once
+    #[serde(default)]
+    pub unweighted: EvalMetrics,
     pub by_category: HashMap<String, EvalMetrics>,
     pub individual: Vec<IndividualResult>,
     pub worst_performers: Vec<IndividualResult>,
@@ -64,6 +92,8 @@ pub struct ScenarioResults {
 pub struct IndividualResult {
     pub scenario_id: String,
     pub category: String,
+    #[serde(default = "default_difficulty")]
+    pub difficulty: u8,
     pub question: String,
     pub metrics: EvalMetrics,
     pub principles_cited: Vec<String>,
@@ -72,31 +102,457 @@ pub struct IndividualResult {
     pub missing_expected: Vec<String>,
 }
 
+/// A problem in a scenario file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScenarioIssue {
+    pub path: PathBuf,
+    /// 1-based; 0 when the problem isn't at one place in the file
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ScenarioIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}: {}", self.path.display(), self.line, self.column, self.message)
+    }
+}
+
+/// What scenario files must look like: a JSON array of [`ScenarioCase`]
+pub fn scenario_schema() -> Value {
+    crate::mcp::input_schema::<Vec<ScenarioCase>>()
+}
+
+/// Check one scenario file; returns its scenarios when it has no problems
+pub fn lint_file(path: &Path, category_dir: Option<&str>) -> Result<(Vec<ScenarioCase>, Vec<ScenarioIssue>)> {
+    let text = std::fs::read_to_string(path)?;
+    let issue = |line, column, message: String| ScenarioIssue { path: path.to_path_buf(), line, column, message };
+
+    let value: Value = match serde_json::from_str(&text) {
+        Ok(value) => value,
+        Err(e) => {
+            let message = e.to_string();
+            let message = message.split(" at line ").next().unwrap_or_default();
+            return Ok((Vec::new(), vec![issue(e.line(), e.column(), format!("Invalid JSON: {}", message))]));
+        }
+    };
+    let schema = jsonschema::JSONSchema::compile(&scenario_schema())
+        .map_err(|e| anyhow::anyhow!("Scenario schema doesn't compile: {}", e))?;
+    if let Err(errors) = schema.validate(&value) {
+        let issues = errors
+            .map(|e| {
+                let pointer = e.instance_path.to_string();
+                let (line, column) = locate(&text, &e.instance_path.clone().into_vec());
+                let at = if pointer.is_empty() { String::new() } else { format!(" (at {})", pointer) };
+                issue(line, column, format!("{}{}", e, at))
+            })
+            .collect();
+        return Ok((Vec::new(), issues));
+    }
+
+    let mut scenarios: Vec<ScenarioCase> = serde_json::from_value(value)?;
+    let mut issues = Vec::new();
+    for (i, scenario) in scenarios.iter_mut().enumerate() {
+        let (line, column) = locate(&text, &[i.to_string()]);
+        if scenario.category.is_empty() {
+            match category_dir {
+                Some(dir) => scenario.category = dir.to_string(),
+                None => issues.push(issue(
+                    line,
+                    column,
+                    format!("{}: no category, and the file isn't in a category directory", scenario.id),
+                )),
+            }
+        }
+        for both in scenario.expected_principles.iter().filter(|p| scenario.anti_principles.contains(p)) {
+            let message = format!("{}: '{}' is both expected and an anti-principle", scenario.id, both);
+            issues.push(issue(line, column, message));
+        }
+    }
+    Ok((if issues.is_empty() { scenarios } else { Vec::new() }, issues))
+}
+
+/// Line and column (1-based) of the value a JSON pointer's tokens lead to;
+/// (0, 0) if the text doesn't have it
+fn locate(text: &str, tokens: &[String]) -> (usize, usize) {
+    fn skip_ws(b: &[u8], mut i: usize) -> usize {
+        while i < b.len() && b[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        i
+    }
+    fn string_end(b: &[u8], mut i: usize) -> usize {
+        i += 1;
+        while i < b.len() && b[i] != b'"' {
+            i += if b[i] == b'\\' { 2 } else { 1 };
+        }
+        i + 1
+    }
+    fn value_end(b: &[u8], i: usize) -> usize {
+        match b.get(i) {
+            Some(b'"') => string_end(b, i),
+            Some(b'{') | Some(b'[') => {
+                let (mut depth, mut j) = (0, i);
+                while j < b.len() {
+                    match b[j] {
+                        b'"' => {
+                            j = string_end(b, j);
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return j + 1;
+                            }
+                        }
+                        _ => {}
+                    }
+                    j += 1;
+                }
+                j
+            }
+            _ => i + b[i..].iter().position(|c| matches!(c, b',' | b'}' | b']')).unwrap_or(b.len() - i),
+        }
+    }
+
+    let b = text.as_bytes();
+    let mut at = skip_ws(b, 0);
+    'tokens: for token in tokens {
+        match b.get(at) {
+            Some(b'[') => {
+                let Ok(index) = token.parse::<usize>() else { return (0, 0) };
+                at = skip_ws(b, at + 1);
+                for _ in 0..index {
+                    at = skip_ws(b, value_end(b, at));
+                    if b.get(at) != Some(&b',') {
+                        return (0, 0);
+                    }
+                    at = skip_ws(b, at + 1);
+                }
+            }
+            Some(b'{') => {
+                at = skip_ws(b, at + 1);
+                while b.get(at) == Some(&b'"') {
+                    let key_end = string_end(b, at);
+                    let key: Option<String> = serde_json::from_slice(&b[at..key_end]).ok();
+                    at = skip_ws(b, key_end);
+                    at = skip_ws(b, at + 1); // past ':'
+                    if key.as_deref() == Some(token) {
+                        continue 'tokens;
+                    }
+                    at = skip_ws(b, value_end(b, at));
+                    if b.get(at) == Some(&b',') {
+                        at = skip_ws(b, at + 1);
+                    }
+                }
+                return (0, 0);
+            }
+            _ => return (0, 0),
+        }
+    }
+    let before = &text[..at.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+    (line, column)
+}
+
+/// Every `.json` file under `dir`, in path order
+pub fn scenario_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Check every scenario file under `dir`, including that ids are unique
+pub fn lint_dir(dir: &Path) -> Result<(Vec<ScenarioCase>, Vec<ScenarioIssue>)> {
+    let mut all_scenarios = Vec::new();
+    let mut issues = Vec::new();
+    let mut seen: HashMap<String, PathBuf> = HashMap::new();
+    for path in scenario_files(dir) {
+        // The first directory below `dir` names the category
+        let category_dir = path
+            .strip_prefix(dir)
+            .ok()
+            .filter(|rel| rel.components().count() > 1)
+            .and_then(|rel| rel.components().next())
+            .and_then(|c| c.as_os_str().to_str());
+        let (scenarios, file_issues) = lint_file(&path, category_dir)?;
+        issues.extend(file_issues);
+        for (i, scenario) in scenarios.into_iter().enumerate() {
+            if let Some(first) = seen.insert(scenario.id.clone(), path.clone()) {
+                let (line, column) = locate(&std::fs::read_to_string(&path)?, &[i.to_string(), "id".to_string()]);
+                issues.push(ScenarioIssue {
+                    path: path.clone(),
+                    line,
+                    column,
+                    message: format!("Duplicate scenario id '{}' (also in {})", scenario.id, first.display()),
+                });
+            }
+            all_scenarios.push(scenario);
+        }
+    }
+    Ok((all_scenarios, issues))
+}
+
 /// Load scenarios from a JSON file
 pub fn load_scenarios(path: &Path) -> Result<Vec<ScenarioCase>> {
-    let content = std::fs::read_to_string(path)?;
-    let scenarios: Vec<ScenarioCase> = serde_json::from_str(&content)?;
+    let (scenarios, issues) = lint_file(path, None)?;
+    if let Some(first) = issues.first() {
+        return Err(MindsError::Validation(first.to_string()));
+    }
     Ok(scenarios)
 }
 
-/// Load all scenarios from a directory
+/// Load all scenarios under a directory, failing with every problem found
 pub fn load_all_scenarios(dir: &Path) -> Result<Vec<ScenarioCase>> {
-    let mut all_scenarios = Vec::new();
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let (scenarios, issues) = lint_dir(dir)?;
+    if !issues.is_empty() {
+        let listed: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        return Err(MindsError::Validation(format!(
+            "{} problem(s) in scenario files (100minds benchmark scenarios --lint):\n{}",
+            issues.len(),
+            listed.join("\n")
+        )));
+    }
+    Ok(scenarios)
+}
+
+/// One `field op value` test of a [`ScenarioFilter`]
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    field: String,
+    op: &'static str,
+    /// Alternatives, from `a|b`
+    values: Vec<String>,
+}
+
+/// Which scenarios to run: comma-separated conditions that must all hold
+///
+/// - `category=architecture|scaling`, `category!=team`
+/// - `difficulty>=4` (also `=`, `!=`, `>`, `<`, `<=`)
+/// - `tag=legacy` (any of the scenario's tags), `tag!=slow` (none of them)
+/// - `id=arch-001|arch-002`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScenarioFilter {
+    conditions: Vec<Condition>,
+}
 
-    if dir.is_dir() {
-        for entry in std::fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.extension().is_some_and(|e| e == "json") {
-                match load_scenarios(&path) {
-                    Ok(scenarios) => all_scenarios.extend(scenarios),
-                    Err(e) => eprintln!("Warning: Failed to load {:?}: {}", path, e),
+impl ScenarioFilter {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut conditions = Vec::new();
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (field, op, value) = ["!=", ">=", "<=", "=", ">", "<"]
+                .into_iter()
+                .find_map(|op| part.split_once(op).map(|(f, v)| (f.trim().to_lowercase(), op, v.trim())))
+                .ok_or_else(|| {
+                    MindsError::Validation(format!("Bad filter '{}' (expected field=value, e.g. difficulty>=4)", part))
+                })?;
+            let values: Vec<String> = value.split('|').map(|v| v.trim().to_lowercase()).collect();
+            match field.as_str() {
+                "difficulty" => {
+                    for v in &values {
+                        if v.parse::<u8>().is_err() {
+                            return Err(MindsError::Validation(format!("Bad difficulty '{}' in filter '{}'", v, part)));
+                        }
+                    }
+                }
+                "category" | "tag" | "id" if op == "=" || op == "!=" => {}
+                "category" | "tag" | "id" => {
+                    return Err(MindsError::Validation(format!(
+                        "'{}' only compares with = or != (in '{}')",
+                        field, part
+                    )))
+                }
+                _ => {
+                    return Err(MindsError::Validation(format!(
+                        "Unknown filter field '{}' (category, difficulty, tag or id)",
+                        field
+                    )))
                 }
             }
+            conditions.push(Condition { field, op, values });
         }
+        Ok(ScenarioFilter { conditions })
     }
 
-    Ok(all_scenarios)
+    pub fn matches(&self, scenario: &ScenarioCase) -> bool {
+        self.conditions.iter().all(|c| {
+            let any = |candidates: &[String]| {
+                c.values.iter().any(|v| candidates.iter().any(|candidate| candidate.to_lowercase() == *v))
+            };
+            match c.field.as_str() {
+                "difficulty" => c.values.iter().any(|v| {
+                    let (have, want) = (scenario.difficulty, v.parse::<u8>().unwrap_or_default());
+                    match c.op {
+                        "=" => have == want,
+                        "!=" => have != want,
+                        ">=" => have >= want,
+                        "<=" => have <= want,
+                        ">" => have > want,
+                        _ => have < want,
+                    }
+                }),
+                field => {
+                    let candidates = match field {
+                        "category" => std::slice::from_ref(&scenario.category),
+                        "id" => std::slice::from_ref(&scenario.id),
+                        _ => scenario.tags.as_slice(),
+                    };
+                    any(candidates) == (c.op == "=")
+                }
+            }
+        })
+    }
+
+    /// The scenarios that match
+    pub fn apply(&self, scenarios: Vec<ScenarioCase>) -> Vec<ScenarioCase> {
+        scenarios.into_iter().filter(|s| self.matches(s)).collect()
+    }
+}
+
+/// Accumulates everything recorded about one question across its decisions
+#[derive(Default)]
+struct HistoryAccumulator {
+    question: String,
+    category: Option<String>,
+    credited: Vec<String>,
+    thinkers: Vec<String>,
+    debited: Vec<String>,
+}
+
+fn push_unique(list: &mut Vec<String>, value: String) {
+    if !list.contains(&value) {
+        list.push(value);
+    }
+}
+
+/// Mine the decisions table for scenarios with real ground truth
+///
+/// Every question with a recorded outcome becomes a scenario. Principles
+/// credited by successful outcomes (positive adjustments) become the
+/// expected principles; principles penalized by failed outcomes on the same
+/// question become anti-principles. Outcome-only placeholder decisions are
+/// skipped since they carry no real question.
+pub fn generate_from_history(conn: &Connection) -> Result<Vec<ScenarioCase>> {
+    let mut stmt = conn.prepare(
+        "SELECT d.question, d.context_json, d.outcome_success, fa.adjustment,
+                p.name, t.name
+         FROM decisions d
+         JOIN framework_adjustments fa ON fa.decision_id = d.id
+         JOIN principles p ON p.id = fa.principle_id
+         LEFT JOIN thinkers t ON t.id = p.thinker_id
+         WHERE d.outcome_success IS NOT NULL
+           AND d.content_hash != 'outcome-only'
+         ORDER BY d.created_at, fa.id",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, i32>(2)? == 1,
+            row.get::<_, f64>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, Option<String>>(5)?,
+        ))
+    })?;
+
+    // Group by normalized question so repeated asks merge into one scenario
+    let mut by_question: BTreeMap<String, HistoryAccumulator> = BTreeMap::new();
+
+    for row in rows {
+        let (question, context_json, success, adjustment, principle, thinker) = row?;
+        let key = question.trim().to_lowercase();
+        let acc = by_question.entry(key).or_insert_with(|| HistoryAccumulator {
+            question: question.trim().to_string(),
+            ..Default::default()
+        });
+
+        if acc.category.is_none() {
+            acc.category = context_json
+                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+                .and_then(|c| c.get("domain")?.as_str().map(String::from));
+        }
+
+        if success && adjustment > 0.0 {
+            push_unique(&mut acc.credited, principle);
+            if let Some(thinker) = thinker {
+                push_unique(&mut acc.thinkers, thinker);
+            }
+        } else if !success {
+            push_unique(&mut acc.debited, principle);
+        }
+    }
+
+    let mut per_category: HashMap<String, usize> = HashMap::new();
+    let mut cases = Vec::new();
+
+    for acc in by_question.into_values() {
+        if acc.credited.is_empty() {
+            continue;
+        }
+
+        let category = acc.category.unwrap_or_else(|| "general".to_string());
+        let n = per_category.entry(category.clone()).or_insert(0);
+        *n += 1;
+
+        let mut context = HashMap::new();
+        if category != "general" {
+            context.insert("domain".to_string(), category.clone());
+        }
+
+        // A principle that both helped and hurt is ambiguous, not an anti-principle
+        let anti_principles = acc
+            .debited
+            .into_iter()
+            .filter(|p| !acc.credited.contains(p))
+            .collect();
+
+        cases.push(ScenarioCase {
+            id: format!("history-{}-{:03}", category, n),
+            category,
+            question: acc.question,
+            context,
+            expected_principles: acc.credited,
+            expected_thinkers: acc.thinkers,
+            anti_principles,
+            difficulty: default_difficulty(),
+            tags: vec!["history".to_string()],
+        });
+    }
+
+    Ok(cases)
+}
+
+/// Write scenarios to `dir`, one `history-<category>.json` file per category
+pub fn write_scenario_files(dir: &Path, scenarios: &[ScenarioCase]) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut by_category: BTreeMap<&str, Vec<&ScenarioCase>> = BTreeMap::new();
+    for scenario in scenarios {
+        by_category
+            .entry(scenario.category.as_str())
+            .or_default()
+            .push(scenario);
+    }
+
+    let mut written = Vec::new();
+    for (category, cases) in by_category {
+        let path = dir.join(format!("history-{}.json", category));
+        std::fs::write(&path, serde_json::to_string_pretty(&cases)?)?;
+        written.push(path);
+    }
+
+    Ok(written)
 }
 
 /// Run benchmark on all scenarios
@@ -107,7 +563,7 @@ pub fn run_benchmark(
 ) -> Result<ScenarioResults> {
     let engine = CounselEngine::new(conn, provenance);
     let mut individual_results = Vec::new();
-    let mut by_category: HashMap<String, Vec<EvalMetrics>> = HashMap::new();
+    let mut by_category: HashMap<String, Vec<(EvalMetrics, f64)>> = HashMap::new();
 
     for scenario in scenarios {
         let result = run_single_scenario(conn, &engine, scenario)?;
@@ -115,20 +571,22 @@ pub fn run_benchmark(
         by_category
             .entry(scenario.category.clone())
             .or_default()
-            .push(result.metrics.clone());
+            .push((result.metrics.clone(), scenario.difficulty as f64));
 
         individual_results.push(result);
     }
 
-    // Aggregate metrics
-    let all_metrics: Vec<_> = individual_results.iter().map(|r| &r.metrics).collect();
-    let aggregate = aggregate_metrics(&all_metrics);
+    // Aggregate metrics, weighted by difficulty and plain
+    let weighted: Vec<_> = individual_results.iter().map(|r| (&r.metrics, r.difficulty as f64)).collect();
+    let aggregate = aggregate_metrics(&weighted);
+    let plain: Vec<_> = individual_results.iter().map(|r| (&r.metrics, 1.0)).collect();
+    let unweighted = aggregate_metrics(&plain);
 
     // Aggregate by category
     let category_metrics: HashMap<String, EvalMetrics> = by_category
         .into_iter()
         .map(|(cat, metrics)| {
-            let refs: Vec<_> = metrics.iter().collect();
+            let refs: Vec<_> = metrics.iter().map(|(m, w)| (m, *w)).collect();
             (cat, aggregate_metrics(&refs))
         })
         .collect();
@@ -145,6 +603,7 @@ pub fn run_benchmark(
     Ok(ScenarioResults {
         total_scenarios: scenarios.len(),
         aggregate,
+        unweighted,
         by_category: category_metrics,
         individual: individual_results,
         worst_performers,
@@ -191,6 +650,7 @@ fn run_single_scenario(
                 .unwrap_or_default(),
             prefer_thinkers: vec![],
             depth: CounselDepth::Standard,
+            ..Default::default()
         },
         decision_id: None,  // Auto-generate UUID (scenario eval)
     };
@@ -261,6 +721,7 @@ fn run_single_scenario(
     Ok(IndividualResult {
         scenario_id: scenario.id.clone(),
         category: scenario.category.clone(),
+        difficulty: scenario.difficulty,
         question: scenario.question.clone(),
         metrics,
         principles_cited: principles_vec,
@@ -360,29 +821,26 @@ fn compute_ndcg(retrieved: &HashSet<String>, relevant: &HashSet<String>) -> f64
 }
 
 /// Aggregate metrics across multiple results
-fn aggregate_metrics(metrics: &[&EvalMetrics]) -> EvalMetrics {
-    if metrics.is_empty() {
+/// Weighted mean of each metric (latency stays a plain mean)
+fn aggregate_metrics(metrics: &[(&EvalMetrics, f64)]) -> EvalMetrics {
+    let n: f64 = metrics.iter().map(|(_, w)| w).sum();
+    if metrics.is_empty() || n <= 0.0 {
         return EvalMetrics::default();
     }
-
-    let n = metrics.len() as f64;
+    let mean = |f: &dyn Fn(&EvalMetrics) -> f64| metrics.iter().map(|(m, w)| f(m) * w).sum::<f64>() / n;
 
     // Average precision at K
     let mut precision_at_k = HashMap::new();
     for k in [1, 3, 5] {
-        let sum: f64 = metrics
-            .iter()
-            .filter_map(|m| m.precision_at_k.get(&k))
-            .sum();
-        precision_at_k.insert(k, sum / n);
+        precision_at_k.insert(k, mean(&|m| m.precision_at_k.get(&k).copied().unwrap_or_default()));
     }
 
     // Average other metrics
-    let recall = metrics.iter().map(|m| m.recall).sum::<f64>() / n;
-    let ndcg = metrics.iter().map(|m| m.ndcg).sum::<f64>() / n;
-    let anti_rate = metrics.iter().map(|m| m.anti_principle_rate).sum::<f64>() / n;
-    let diversity = metrics.iter().map(|m| m.thinker_diversity).sum::<f64>() / n;
-    let latency = metrics.iter().map(|m| m.latency_ms).sum::<u64>() / metrics.len() as u64;
+    let recall = mean(&|m| m.recall);
+    let ndcg = mean(&|m| m.ndcg);
+    let anti_rate = mean(&|m| m.anti_principle_rate);
+    let diversity = mean(&|m| m.thinker_diversity);
+    let latency = metrics.iter().map(|(m, _)| m.latency_ms).sum::<u64>() / metrics.len() as u64;
 
     EvalMetrics {
         precision_at_k,
@@ -425,4 +883,109 @@ mod tests {
         let p3 = metrics.precision_at_k.get(&3).unwrap();
         assert!(*p3 > 0.6 && *p3 < 0.7);
     }
+
+    #[test]
+    fn test_filter_and_difficulty_weighting() {
+        let case = |id: &str, category: &str, difficulty: u8, tags: &[&str]| ScenarioCase {
+            id: id.to_string(),
+            category: category.to_string(),
+            question: "q".to_string(),
+            context: HashMap::new(),
+            expected_principles: Vec::new(),
+            expected_thinkers: Vec::new(),
+            anti_principles: Vec::new(),
+            difficulty,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        };
+        let scenarios = vec![
+            case("a", "architecture", 5, &["legacy"]),
+            case("b", "architecture", 2, &[]),
+            case("c", "Scaling", 4, &["legacy", "slow"]),
+        ];
+        let ids = |spec: &str| -> Vec<String> {
+            ScenarioFilter::parse(spec).unwrap().apply(scenarios.clone()).into_iter().map(|s| s.id).collect()
+        };
+        assert_eq!(ids("category=architecture,difficulty>=4"), ["a"]);
+        assert_eq!(ids("category=architecture|scaling, difficulty<5"), ["b", "c"]);
+        assert_eq!(ids("tag=legacy,tag!=slow"), ["a"]);
+        assert_eq!(ids(""), ["a", "b", "c"]);
+        assert!(ScenarioFilter::parse("difficulty>=hard").is_err());
+        assert!(ScenarioFilter::parse("tag>=x").is_err());
+        assert!(ScenarioFilter::parse("owner=me").is_err());
+
+        let metrics = |recall: f64| EvalMetrics { recall, ..Default::default() };
+        let (hard, easy) = (metrics(1.0), metrics(0.0));
+        assert_eq!(aggregate_metrics(&[(&hard, 4.0), (&easy, 1.0)]).recall, 0.8);
+        assert_eq!(aggregate_metrics(&[(&hard, 1.0), (&easy, 1.0)]).recall, 0.5);
+    }
+
+    #[test]
+    fn test_generate_from_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('gall', 'John Gall', 'systems');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('gall-law', 'gall', 'Gall''s Law', 'Start simple'),
+                        ('big-bang', 'gall', 'Big Bang Rewrite', 'Rewrite it all');
+             INSERT INTO decisions (id, question, context_json, counsel_json, content_hash, signature, agent_pubkey, outcome_success)
+                 VALUES ('d1', 'Should we rewrite?', '{\"domain\":\"architecture\"}', '{}', 'h1', 's', 'k', 1),
+                        ('d2', 'should we rewrite? ', NULL, '{}', 'h2', 's', 'k', 0),
+                        ('d3', 'Unresolved question', NULL, '{}', 'h3', 's', 'k', NULL);
+             INSERT INTO framework_adjustments (principle_id, adjustment, decision_id)
+                 VALUES ('gall-law', 0.05, 'd1'),
+                        ('big-bang', -0.10, 'd2'),
+                        ('gall-law', 0.05, 'd3');",
+        )
+        .unwrap();
+
+        let cases = generate_from_history(&conn).unwrap();
+        assert_eq!(cases.len(), 1);
+
+        let case = &cases[0];
+        assert_eq!(case.id, "history-architecture-001");
+        assert_eq!(case.expected_principles, vec!["Gall's Law".to_string()]);
+        assert_eq!(case.expected_thinkers, vec!["John Gall".to_string()]);
+        assert_eq!(case.anti_principles, vec!["Big Bang Rewrite".to_string()]);
+
+        let out = dir.path().join("scenarios");
+        let written = write_scenario_files(&out, &cases).unwrap();
+        assert_eq!(written.len(), 1);
+        assert_eq!(load_all_scenarios(&out).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_lint_reports_positions_and_nested_categories() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("architecture")).unwrap();
+        let case = |id: &str| {
+            format!(r#"{{"id": "{}", "question": "Split it?", "expected_principles": [], "expected_thinkers": []}}"#, id)
+        };
+        std::fs::write(dir.path().join("architecture/a.json"), format!("[\n  {}\n]", case("a1"))).unwrap();
+        assert_eq!(load_all_scenarios(dir.path()).unwrap()[0].category, "architecture");
+
+        std::fs::write(
+            dir.path().join("architecture/b.json"),
+            format!(
+                "[\n  {},\n  {{\"id\": \"b2\", \"question\": \"q\", \"difficulty\": 9,\n   {}}}\n]",
+                case("a1"),
+                r#""expected_principles": [], "expected_thinkers": []"#
+            ),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("loose.json"), "[\n  {\"id\": \"c\",}\n]").unwrap();
+        let (_, issues) = lint_dir(dir.path()).unwrap();
+        let found: Vec<(usize, usize, &str)> = issues
+            .iter()
+            .map(|i| (i.line, i.column, i.message.split(' ').next().unwrap()))
+            .collect();
+        assert_eq!(found, [(3, 47, "9"), (2, 14, "Invalid")]);
+        assert!(issues[0].path.ends_with("architecture/b.json") && issues[0].message.ends_with("(at /1/difficulty)"));
+
+        std::fs::write(dir.path().join("architecture/b.json"), format!("[{}]", case("a1"))).unwrap();
+        std::fs::remove_file(dir.path().join("loose.json")).unwrap();
+        let error = load_all_scenarios(dir.path()).unwrap_err().to_string();
+        assert!(error.contains("b.json:1:9: Duplicate scenario id 'a1'"), "{}", error);
+    }
 }
@@ -90,7 +90,7 @@ impl PosteriorState {
 
         self.alpha = 1.0 + ratio * effective_obs;
         self.beta = 1.0 + (1.0 - ratio) * effective_obs;
-        self.observations = (effective_obs as u32).max(0);
+        self.observations = effective_obs as u32;
     }
 }
 
@@ -560,7 +560,7 @@ mod tests {
         // V6: Should have tracked drift statistics
         assert!(agent.total_syncs > 0);
         let drift_ratio = agent.get_long_term_drift_ratio();
-        println!("V6 Test: drift_ratio={:.2}, forgetting={:.2}, syncs={}",
+        say!("V6 Test: drift_ratio={:.2}, forgetting={:.2}, syncs={}",
                  drift_ratio, agent.forgetting_factor, agent.total_syncs);
 
         // V6: Forgetting factor should have adapted
@@ -597,6 +597,6 @@ mod tests {
 
         let stats = agent.get_sync_stats();
         assert_eq!(stats.total_syncs, 5);
-        println!("V6 Stats: {:?}", stats);
+        say!("V6 Stats: {:?}", stats);
     }
 }
@@ -8,7 +8,7 @@
 //! - Uncertainty quantification (wide CI = try more)
 //! - Context-aware learning (per-domain statistics)
 
-use anyhow::Result;
+use crate::error::Result;
 use rand::prelude::*;
 use rand::seq::SliceRandom;
 use rusqlite::{params, Connection};
@@ -27,6 +27,8 @@ pub struct FGTSConfig {
     pub epsilon: f64,
     /// Minimum pulls before disabling epsilon exploration
     pub epsilon_threshold: u32,
+    /// Largest bonus an arm can get
+    pub bonus_cap: f64,
 }
 
 impl Default for FGTSConfig {
@@ -36,10 +38,62 @@ impl Default for FGTSConfig {
             bonus_decay: 0.95,      // Decay to prevent perpetual optimism
             epsilon: 0.1,           // 10% random exploration for cold-start
             epsilon_threshold: 10,  // After 10 pulls, disable epsilon
+            bonus_cap: 0.5,
         }
     }
 }
 
+/// The `[learning]` table of config.toml
+///
+/// ```toml
+/// [learning]
+/// domain_pooling = 4.0   # pseudo-observations a principle's record in
+///                        # other domains lends a domain arm; 0 = off
+/// policy = "fgts"        # fgts | thompson | ucb1 | exp3
+/// epsilon = 0.20         # counsel's epsilon-greedy exploration
+/// optimism = 3.0         # FG-TS optimism constant
+/// temperature = 15.0     # largest random score noise, in points
+/// min_samples = 3        # outcomes before an arm's record fully counts in
+///                        # ranking; fewer are shrunk toward a cold arm
+/// ```
+///
+/// `CounselContext::exploration` overrides the last three per request.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LearningConfig {
+    pub domain_pooling: f64,
+    /// Selection policy counsel ranks arms with (see `bandit::POLICIES`)
+    pub policy: String,
+    /// Chance each counsel position is picked from the exploration zone
+    pub epsilon: f64,
+    /// FG-TS optimism constant for counsel's contextual boost
+    pub optimism: f64,
+    /// Largest random noise added to counsel scores, in points
+    pub temperature: f64,
+    /// Outcomes an arm needs before counsel ranks on its full learned value
+    pub min_samples: u64,
+}
+
+impl Default for LearningConfig {
+    fn default() -> Self {
+        Self {
+            domain_pooling: 4.0,
+            policy: "fgts".to_string(),
+            epsilon: 0.20,
+            optimism: 3.0,
+            temperature: 15.0,
+            min_samples: 3,
+        }
+    }
+}
+
+impl LearningConfig {
+    /// Read `[learning]` from the config.toml beside the database; a missing file or table gives the defaults
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        crate::config::section(conn, "learning")
+    }
+}
+
 /// A single principle arm in the multi-armed bandit
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrincipleArm {
@@ -105,10 +159,7 @@ impl PrincipleArm {
     /// Per NeurIPS 2025 (arXiv 2507.15290): 25% improvement over vanilla TS
     pub fn fg_sample(&self, rng: &mut impl Rng, config: &FGTSConfig) -> f64 {
         // Calculate optimism bonus (decays with pulls)
-        let n = self.alpha + self.beta;
-        let raw_bonus = config.optimism_constant / n.sqrt();
-        let decayed_bonus = raw_bonus * config.bonus_decay.powi(self.pulls as i32);
-        let bonus = decayed_bonus.min(0.5); // Cap bonus at 0.5
+        let bonus = super::bandit::fg_ts_bonus(self.alpha, self.beta, self.pulls as u64, config);
 
         // Sample from optimistic Beta distribution
         let optimistic_alpha = self.alpha + bonus;
@@ -122,11 +173,9 @@ impl PrincipleArm {
     /// Update based on outcome (also increments pull count)
     pub fn update(&mut self, success: bool) {
         self.pulls += 1;
-        if success {
-            self.alpha += 1.0;
-        } else {
-            self.beta += 1.0;
-        }
+        let (alpha_delta, beta_delta) = super::bandit::beta_deltas(success);
+        self.alpha += alpha_delta;
+        self.beta += beta_delta;
     }
 
     /// Check if this arm is "cold" (needs exploration)
@@ -267,6 +316,34 @@ impl ContextualArm {
     }
 }
 
+/// Partially pool a principle's arm in `domain` with its record elsewhere
+///
+/// The principle's other domains form a global posterior whose mean becomes
+/// the domain arm's prior, worth at most `strength` pseudo-observations (and
+/// never more than the evidence behind it). A principle proven in
+/// "architecture" so starts warm in "systems", and the domain's own
+/// outcomes take over as they accumulate. `arms` holds (domain, alpha,
+/// beta, sample_count); a strength of 0, or no record elsewhere, leaves the
+/// domain arm as it is.
+pub fn pool_domain_arm(arms: &[(String, f64, f64, i64)], domain: &str, strength: f64) -> Option<(f64, f64, i64)> {
+    let own = arms.iter().find(|(d, ..)| d == domain).map(|&(_, alpha, beta, samples)| (alpha, beta, samples));
+    let (successes, failures) = arms
+        .iter()
+        .filter(|(d, ..)| d != domain)
+        .fold((0.0, 0.0), |(s, f), (_, alpha, beta, _)| (s + (alpha - 1.0).max(0.0), f + (beta - 1.0).max(0.0)));
+    if strength <= 0.0 || successes + failures <= 0.0 {
+        return own;
+    }
+
+    let (global_alpha, global_beta) = (1.0 + successes, 1.0 + failures);
+    let weight = strength.min(global_alpha + global_beta) / (global_alpha + global_beta);
+    let (prior_alpha, prior_beta) = (global_alpha * weight, global_beta * weight);
+    Some(match own {
+        Some((alpha, beta, samples)) => (alpha - 1.0 + prior_alpha, beta - 1.0 + prior_beta, samples),
+        None => (prior_alpha, prior_beta, 0),
+    })
+}
+
 /// Thompson Sampling selector for principles
 /// Enhanced with Feel-Good TS (FG-TS) for cold-start and hybrid epsilon-greedy
 pub struct ThompsonSelector {
@@ -610,6 +687,39 @@ mod tests {
         assert!(arm.mean() < 0.5);
     }
 
+    #[test]
+    fn test_domain_arm_borrows_from_other_domains() {
+        let arms = vec![
+            ("architecture".to_string(), 11.0, 1.0, 10),
+            ("testing".to_string(), 2.0, 1.0, 1),
+        ];
+        // A cold domain starts at the principle's record elsewhere, worth 4 observations
+        let (alpha, beta, samples) = pool_domain_arm(&arms, "systems", 4.0).unwrap();
+        assert!((alpha + beta - 4.0).abs() < 1e-9);
+        assert!((alpha / (alpha + beta) - 12.0 / 13.0).abs() < 1e-9);
+        assert_eq!(samples, 0);
+
+        // The domain's own failures pull it away from the prior
+        let (alpha, beta, samples) = pool_domain_arm(&arms, "testing", 4.0).unwrap();
+        assert_eq!(samples, 1);
+        assert!(alpha / (alpha + beta) > 0.8);
+        let mut failing = arms.clone();
+        failing[1] = ("testing".to_string(), 1.0, 21.0, 20);
+        let (alpha, beta, _) = pool_domain_arm(&failing, "testing", 4.0).unwrap();
+        assert!(alpha / (alpha + beta) < 0.2);
+
+        // Pooling off, or nothing elsewhere, leaves arms alone
+        assert_eq!(pool_domain_arm(&arms, "testing", 0.0), Some((2.0, 1.0, 1)));
+        assert_eq!(pool_domain_arm(&arms, "systems", 0.0), None);
+        assert_eq!(pool_domain_arm(&arms[..1], "architecture", 4.0), Some((11.0, 1.0, 10)));
+
+        let dir = tempfile::tempdir().unwrap();
+        let conn = Connection::open(dir.path().join("wisdom.db")).unwrap();
+        assert_eq!(LearningConfig::beside(&conn).unwrap().domain_pooling, 4.0);
+        std::fs::write(dir.path().join("config.toml"), "[learning]\ndomain_pooling = 0\n").unwrap();
+        assert_eq!(LearningConfig::beside(&conn).unwrap().domain_pooling, 0.0);
+    }
+
     #[test]
     fn test_credible_interval() {
         let arm = PrincipleArm::new("test".to_string(), "Test".to_string());
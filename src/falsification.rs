@@ -0,0 +1,209 @@
+//! Falsification Monitoring
+//!
+//! Every counsel position states what would prove it wrong
+//! (`falsifiable_if`). When an agent observes that condition, it records it
+//! here: the decision is flagged, and each principle the position cited
+//! loses a little confidence. That penalty is its own signal, smaller than
+//! an outcome failure and kept out of the Thompson arms: a fired condition
+//! says the principle was misapplied to this context, not that following it
+//! failed. Adjustments are logged in `framework_adjustments` with a
+//! `{"signal": "falsification"}` context so decay treats them like any other.
+
+use crate::types::{CounselPosition, CounselResponse};
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+/// Confidence lost by each principle a falsified position cited
+pub const FALSIFICATION_DELTA: f64 = -0.05;
+
+/// What recording an observed falsification changed
+#[derive(Debug, Clone, Serialize)]
+pub struct FalsificationResult {
+    pub decision_id: String,
+    pub thinker: String,
+    pub condition: String,
+    /// Principles penalized, with their new confidence
+    pub principles: Vec<(String, f64)>,
+    /// True if this position's falsification was already recorded
+    pub duplicate: bool,
+}
+
+/// A decision with at least one fired falsification condition
+#[derive(Debug, Clone, Serialize)]
+pub struct FlaggedDecision {
+    pub decision_id: String,
+    pub question: String,
+    pub conditions_fired: i64,
+    pub last_observed_at: String,
+}
+
+/// The position `selector` names: its thinker (name or ID), or text from its condition
+fn find_position<'a>(response: &'a CounselResponse, selector: &str) -> Result<&'a CounselPosition> {
+    let selector = selector.trim().to_lowercase();
+    let candidates: Vec<&CounselPosition> = response
+        .positions
+        .iter()
+        .chain(std::iter::once(&response.challenge))
+        .filter(|p| p.falsifiable_if.is_some())
+        .collect();
+    let by_thinker: Vec<_> = candidates
+        .iter()
+        .filter(|p| p.thinker.to_lowercase() == selector || p.thinker_id.to_lowercase() == selector)
+        .collect();
+    let matches = if by_thinker.is_empty() {
+        candidates
+            .iter()
+            .filter(|p| p.falsifiable_if.as_deref().unwrap_or_default().to_lowercase().contains(&selector))
+            .collect()
+    } else {
+        by_thinker
+    };
+    match matches.as_slice() {
+        [p] => Ok(p),
+        [] => Err(MindsError::NotFound(format!(
+            "No position with a falsification condition matches '{}' (have: {})",
+            selector,
+            candidates.iter().map(|p| p.thinker.as_str()).collect::<Vec<_>>().join(", ")
+        ))),
+        _ => Err(MindsError::Validation(format!(
+            "'{}' matches {} positions; name the thinker",
+            selector,
+            matches.len()
+        ))),
+    }
+}
+
+/// Record that a position's falsification condition was observed
+pub fn record(conn: &Connection, decision_id: &str, selector: &str, evidence: &str) -> Result<FalsificationResult> {
+    let counsel_json: String = conn
+        .query_row("SELECT counsel_json FROM decisions WHERE id = ?1", [decision_id], |row| row.get(0))
+        .optional()?
+        .ok_or_else(|| MindsError::NotFound(format!("decision {}", decision_id)))?;
+    let response: CounselResponse = serde_json::from_str(&counsel_json)?;
+    let position = find_position(&response, selector)?;
+    let condition = position.falsifiable_if.clone().unwrap_or_default();
+    let principles = crate::corpus::resolve_principle_ids(conn, &position.principles_cited)?;
+
+    crate::db::with_savepoint(conn, "record_falsification", || {
+        let mut result = FalsificationResult {
+            decision_id: decision_id.to_string(),
+            thinker: position.thinker.clone(),
+            condition: condition.clone(),
+            principles: Vec::new(),
+            duplicate: false,
+        };
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO falsifications (decision_id, thinker_id, condition, evidence)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![decision_id, position.thinker_id, condition, evidence],
+        )?;
+        if inserted == 0 {
+            result.duplicate = true;
+            return Ok(result);
+        }
+
+        let context = serde_json::json!({"signal": "falsification", "thinker_id": position.thinker_id}).to_string();
+        for principle_id in &principles {
+            let updated = conn.execute(
+                "UPDATE principles SET learned_confidence = MIN(0.95, MAX(0.1, COALESCE(learned_confidence, 0.5) + ?2))
+                 WHERE id = ?1 AND COALESCE(learned_confidence, 0.5) >= 0",
+                params![principle_id, FALSIFICATION_DELTA],
+            )?;
+            if updated == 0 {
+                continue;
+            }
+            conn.execute(
+                "INSERT INTO framework_adjustments (principle_id, context_pattern, adjustment, decision_id)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![principle_id, context, FALSIFICATION_DELTA, decision_id],
+            )?;
+            let confidence: f64 =
+                conn.query_row("SELECT learned_confidence FROM principles WHERE id = ?1", [principle_id], |row| row.get(0))?;
+            result.principles.push((principle_id.clone(), confidence));
+        }
+        Ok(result)
+    })
+}
+
+/// Decisions with fired falsification conditions, most recent first
+pub fn flagged_decisions(conn: &Connection) -> Result<Vec<FlaggedDecision>> {
+    let mut stmt = conn.prepare(
+        "SELECT f.decision_id, COALESCE(d.question, ''), COUNT(*), MAX(f.observed_at)
+         FROM falsifications f LEFT JOIN decisions d ON d.id = f.decision_id
+         GROUP BY f.decision_id
+         ORDER BY MAX(f.observed_at) DESC",
+    )?;
+    let flagged = stmt
+        .query_map([], |row| {
+            Ok(FlaggedDecision {
+                decision_id: row.get(0)?,
+                question: row.get(1)?,
+                conditions_fired: row.get(2)?,
+                last_observed_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(flagged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ProvenanceInfo, Stance};
+
+    #[test]
+    fn test_fired_condition_flags_decision_and_penalizes_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('fowler', 'Martin Fowler', 'software');
+             INSERT INTO principles (id, thinker_id, name, description, learned_confidence)
+                 VALUES ('strangler', 'fowler', 'Strangler Fig', 'Replace incrementally', 0.6);",
+        )
+        .unwrap();
+        let position = |stance, thinker: &str, falsifiable_if: Option<&str>| CounselPosition {
+            thinker: thinker.to_string(),
+            thinker_id: thinker.split(' ').next_back().unwrap().to_lowercase(),
+            stance,
+            argument: "argument".to_string(),
+            principles_cited: vec!["strangler".to_string()],
+            confidence: 0.7,
+            raw_confidence: None,
+            falsifiable_if: falsifiable_if.map(String::from),
+        };
+        let response = CounselResponse::new(
+            "Should we split the monolith?".to_string(),
+            vec![position(Stance::For, "Martin Fowler", Some("Deploys stay coupled after a quarter"))],
+            position(Stance::Challenge, "Devil's Advocate", None),
+            ProvenanceInfo {
+                content_hash: "hash".to_string(),
+                previous_hash: None,
+                signature: "sig".to_string(),
+                agent_pubkey: "pk".to_string(),
+            },
+            Some("d-split".to_string()),
+        );
+        conn.execute(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey)
+             VALUES ('d-split', ?1, ?2, 'hash', 'sig', 'pk')",
+            params![response.question, serde_json::to_string(&response).unwrap()],
+        )
+        .unwrap();
+
+        assert!(record(&conn, "d-split", "Devil's Advocate", "").is_err());
+        let result = record(&conn, "d-split", "deploys stay coupled", "Still one release train").unwrap();
+        assert_eq!(result.thinker, "Martin Fowler");
+        assert_eq!(result.principles.len(), 1);
+        assert!((result.principles[0].1 - 0.55).abs() < 1e-9);
+
+        let again = record(&conn, "d-split", "Martin Fowler", "Seen again").unwrap();
+        assert!(again.duplicate && again.principles.is_empty());
+
+        let flagged = flagged_decisions(&conn).unwrap();
+        assert_eq!(flagged.len(), 1);
+        assert_eq!((flagged[0].decision_id.as_str(), flagged[0].conditions_fired), ("d-split", 1));
+        let arms: i64 = conn.query_row("SELECT COUNT(*) FROM thompson_arms", [], |r| r.get(0)).unwrap_or(0);
+        assert_eq!(arms, 0);
+    }
+}
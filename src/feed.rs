@@ -0,0 +1,82 @@
+//! Activity Feed
+//!
+//! `100minds serve` answers `GET /feed.atom` with an Atom feed for teams
+//! that follow decisions in a feed reader rather than chat: the learning
+//! digest (see `notifier.rs`) as the first entry, then one entry per
+//! decision made or resolved in the digest window, updated when its
+//! outcome lands. Feed readers can't send headers, so once API keys exist
+//! subscribe with `/feed.atom?key=<reader key>`.
+
+use crate::notifier::Digest;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn entry(id: &str, title: &str, updated: &str, summary: &str) -> String {
+    format!(
+        "  <entry>\n    <id>{}</id>\n    <title>{}</title>\n    <updated>{}</updated>\n    <summary>{}</summary>\n  </entry>\n",
+        escape(id),
+        escape(title),
+        escape(updated),
+        escape(summary)
+    )
+}
+
+/// The digest as an Atom document
+pub fn atom(digest: &Digest) -> String {
+    let message = digest.message();
+    // Dated by the latest activity, so readers don't see a change on every poll
+    let updated = digest.activity.first().map_or(&digest.generated_at, |a| &a.updated_at);
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n  \
+         <id>urn:100minds:feed</id>\n  <title>100minds decisions</title>\n  <updated>{}</updated>\n  \
+         <author><name>100minds</name></author>\n",
+        escape(updated)
+    );
+    xml.push_str(&entry(
+        &format!("urn:100minds:digest:{}:{}", digest.since, digest.until),
+        &message.title,
+        updated,
+        &message.lines.join("\n"),
+    ));
+    for a in &digest.activity {
+        let (title, summary) = match a.outcome {
+            Some(success) => (
+                format!("{}: {}", if success { "Succeeded" } else { "Failed" }, a.question),
+                a.outcome_notes.clone().unwrap_or_default(),
+            ),
+            None => (format!("Decided: {}", a.question), format!("Awaiting outcome (decided {})", a.created_at)),
+        };
+        xml.push_str(&entry(&format!("urn:100minds:decision:{}", a.decision_id), &title, &a.updated_at, &summary));
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_feed_has_digest_then_decisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey)
+                 VALUES ('d1', 'Cache <checkout> & retry?', '{}', 'h', 's', 'k');
+             INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey,
+                                    outcome_success, outcome_notes, outcome_recorded_at)
+                 VALUES ('d2', 'Split the monolith?', '{}', 'h', 's', 'k', 0, 'too many seams', datetime('now'));",
+        )
+        .unwrap();
+
+        let xml = atom(&crate::notifier::digest(&conn, Utc::now(), 7).unwrap());
+        assert!(xml.starts_with("<?xml") && xml.ends_with("</feed>\n"));
+        assert_eq!(xml.matches("<entry>").count(), 3);
+        assert!(xml.find("100minds digest").unwrap() < xml.find("urn:100minds:decision:").unwrap());
+        assert!(xml.contains("<title>Failed: Split the monolith?</title>"));
+        assert!(xml.contains("<title>Decided: Cache &lt;checkout&gt; &amp; retry?</title>"));
+    }
+}
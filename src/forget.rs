@@ -0,0 +1,356 @@
+//! Forgetting a Subject
+//!
+//! `100minds forget --pattern "Dana Reyes"` finds every stored decision that
+//! mentions a subject (in its question, context, counsel or outcome notes)
+//! and removes it, leaving a tombstone as retention pruning does, or with
+//! `--redact` keeps it with each mention replaced by `[FORGOTTEN]`. Counsel
+//! jobs and quarantined outcomes that mention the subject go too.
+//!
+//! Either way a signed deletion event joins the provenance chain, listing
+//! the decisions touched and the content hashes they were signed with, so
+//! an audit can explain a missing decision or one whose text no longer
+//! matches its hash. The subject itself is not recorded anywhere.
+
+use crate::provenance::Provenance;
+use crate::redact::{self, Redactor};
+use crate::retention;
+use crate::error::{MindsError, Result};
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// What each mention becomes with `--redact`
+pub const FORGOTTEN: &str = "[FORGOTTEN]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Tombstone the decisions
+    Remove,
+    /// Keep the decisions, minus the subject
+    Redact,
+}
+
+impl Mode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Mode::Remove => "remove",
+            Mode::Redact => "redact",
+        }
+    }
+}
+
+/// A case-insensitive matcher for `pattern`, taken literally unless `regex`
+pub fn subject(pattern: &str, regex: bool) -> Result<Regex> {
+    if pattern.trim().is_empty() {
+        return Err(MindsError::Validation("The subject pattern is empty".to_string()));
+    }
+    let source = if regex { pattern.to_string() } else { regex::escape(pattern) };
+    RegexBuilder::new(&source)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| MindsError::Validation(format!("Bad subject pattern {:?}: {}", pattern, e)))
+}
+
+/// A signed record of one forget run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeletionEvent {
+    pub id: String,
+    pub mode: String,
+    pub decision_ids: Vec<String>,
+    /// The decisions' content hashes, as signed when they were made
+    pub content_hashes: Vec<String>,
+    pub previous_hash: Option<String>,
+    pub content_hash: String,
+    pub signature: String,
+    pub signer_pubkey: String,
+    pub created_at: String,
+}
+
+impl DeletionEvent {
+    /// The bytes the event's hash and signature cover
+    fn signed_content(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&json!({
+            "id": self.id,
+            "mode": self.mode,
+            "decision_ids": self.decision_ids,
+            "content_hashes": self.content_hashes,
+            "previous_hash": self.previous_hash,
+            "created_at": self.created_at,
+        }))?)
+    }
+
+    /// Whether the signature still covers the stored event
+    pub fn verify(&self) -> Result<bool> {
+        Provenance::verify_detached(&self.signed_content()?, &self.signature, &self.signer_pubkey)
+    }
+}
+
+/// What a forget run touched (or would touch, on a dry run)
+#[derive(Debug, Clone, Serialize)]
+pub struct ForgetReport {
+    pub mode: Mode,
+    pub decisions: Vec<String>,
+    pub counsel_jobs: usize,
+    pub quarantined_outcomes: usize,
+    pub dry_run: bool,
+    /// `None` on a dry run or when nothing matched
+    pub event: Option<DeletionEvent>,
+}
+
+struct Stored {
+    id: String,
+    question: String,
+    context_json: Option<String>,
+    counsel_json: String,
+    notes: Option<String>,
+    content_hash: String,
+}
+
+/// Remove or redact every decision mentioning `subject`, in one transaction
+pub fn forget(
+    conn: &Connection,
+    provenance: &Provenance,
+    subject: &Regex,
+    mode: Mode,
+    dry_run: bool,
+    now: DateTime<Utc>,
+) -> Result<ForgetReport> {
+    let matches = |text: &Option<String>| text.as_deref().is_some_and(|t| subject.is_match(t));
+    let mut decisions: Vec<Stored> = conn
+        .prepare("SELECT id, question, context_json, counsel_json, outcome_notes, content_hash FROM decisions ORDER BY created_at")?
+        .query_map([], |row| {
+            Ok(Stored {
+                id: row.get(0)?,
+                question: row.get(1)?,
+                context_json: row.get(2)?,
+                counsel_json: row.get(3)?,
+                notes: row.get(4)?,
+                content_hash: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    decisions.retain(|d| {
+        subject.is_match(&d.question) || subject.is_match(&d.counsel_json) || matches(&d.context_json) || matches(&d.notes)
+    });
+    let jobs: Vec<(String, Option<String>, Option<String>)> = conn
+        .prepare("SELECT id, request_json, result_json FROM counsel_jobs")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    let jobs: Vec<String> =
+        jobs.into_iter().filter(|(_, request, result)| matches(request) || matches(result)).map(|(id, _, _)| id).collect();
+    let mut quarantined: Vec<(i64, String)> = conn
+        .prepare("SELECT id, request_json FROM quarantined_outcomes")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    quarantined.retain(|(_, request)| subject.is_match(request));
+
+    let mut report = ForgetReport {
+        mode,
+        decisions: decisions.iter().map(|d| d.id.clone()).collect(),
+        counsel_jobs: jobs.len(),
+        quarantined_outcomes: quarantined.len(),
+        dry_run,
+        event: None,
+    };
+    if dry_run || (decisions.is_empty() && jobs.is_empty() && quarantined.is_empty()) {
+        return Ok(report);
+    }
+
+    crate::db::with_savepoint(conn, "forget", || -> Result<()> {
+        for id in &jobs {
+            conn.execute("DELETE FROM counsel_jobs WHERE id = ?1", [id])?;
+        }
+        let redactor = Redactor::for_subject(subject.clone(), FORGOTTEN);
+        for (id, request) in &quarantined {
+            match mode {
+                Mode::Remove => conn.execute("DELETE FROM quarantined_outcomes WHERE id = ?1", [id])?,
+                Mode::Redact => {
+                    let mut request: Value = serde_json::from_str(request)?;
+                    redactor.redact_json(&mut request, false, &[]);
+                    conn.execute(
+                        "UPDATE quarantined_outcomes SET request_json = ?2 WHERE id = ?1",
+                        params![id, serde_json::to_string(&request)?],
+                    )?
+                }
+            };
+        }
+        if decisions.is_empty() {
+            return Ok(());
+        }
+
+        let mut event = DeletionEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            mode: mode.as_str().to_string(),
+            decision_ids: report.decisions.clone(),
+            content_hashes: decisions.iter().map(|d| d.content_hash.clone()).collect(),
+            previous_hash: crate::db::get_latest_decision_hash(conn)?,
+            content_hash: String::new(),
+            signature: String::new(),
+            signer_pubkey: provenance.public_key_hex(),
+            // The decisions' own timestamp format, so the chain orders them together
+            created_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+        };
+        let content = event.signed_content()?;
+        event.content_hash = provenance.hash(&content);
+        event.signature = provenance.sign(&content)?;
+
+        match mode {
+            Mode::Remove => {
+                let ids = serde_json::to_string(&event.decision_ids)?;
+                retention::tombstone(conn, "SELECT value FROM json_each(?1)", &ids, &format!("forget {}", event.id), now)?;
+            }
+            Mode::Redact => {
+                for decision in &decisions {
+                    redact_decision(conn, &redactor, decision)?;
+                }
+            }
+        }
+        conn.execute(
+            "INSERT INTO deletion_events
+                 (id, mode, decision_ids, content_hashes, previous_hash, content_hash, signature, signer_pubkey, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                event.id,
+                event.mode,
+                serde_json::to_string(&event.decision_ids)?,
+                serde_json::to_string(&event.content_hashes)?,
+                event.previous_hash,
+                event.content_hash,
+                event.signature,
+                event.signer_pubkey,
+                event.created_at
+            ],
+        )?;
+        report.event = Some(event);
+        Ok(())
+    })?;
+    Ok(report)
+}
+
+fn redact_decision(conn: &Connection, redactor: &Redactor, decision: &Stored) -> Result<()> {
+    let context_json = decision.context_json.as_deref().unwrap_or("null");
+    let [question, context, counsel] = redact::scrub_fields(redactor, &decision.question, context_json, &decision.counsel_json)?;
+    let notes = decision.notes.as_deref().map(|original| redact::Field {
+        name: "outcome_notes",
+        original,
+        redacted: redactor.redact(original),
+    });
+    for field in [&question, &context, &counsel].into_iter().chain(&notes) {
+        redact::record(conn, &decision.id, field.name, field.original, &field.redacted)?;
+    }
+    conn.execute(
+        "UPDATE decisions SET question = ?2, context_json = ?3, counsel_json = ?4, outcome_notes = ?5 WHERE id = ?1",
+        params![
+            decision.id,
+            question.redacted.text,
+            decision.context_json.as_ref().map(|_| &context.redacted.text),
+            counsel.redacted.text,
+            notes.as_ref().map(|n| &n.redacted.text)
+        ],
+    )?;
+    Ok(())
+}
+
+/// Deletion events that touched `decision_id`, oldest first
+pub fn events_for(conn: &Connection, decision_id: &str) -> Result<Vec<DeletionEvent>> {
+    let json_list = |text: String| serde_json::from_str::<Vec<String>>(&text).unwrap_or_default();
+    let events = conn
+        .prepare(
+            "SELECT id, mode, decision_ids, content_hashes, previous_hash, content_hash, signature, signer_pubkey, created_at
+             FROM deletion_events
+             WHERE EXISTS (SELECT 1 FROM json_each(decision_ids) WHERE value = ?1)
+             ORDER BY created_at, rowid",
+        )?
+        .query_map([decision_id], |row| {
+            Ok(DeletionEvent {
+                id: row.get(0)?,
+                mode: row.get(1)?,
+                decision_ids: json_list(row.get(2)?),
+                content_hashes: json_list(row.get(3)?),
+                previous_hash: row.get(4)?,
+                content_hash: row.get(5)?,
+                signature: row.get(6)?,
+                signer_pubkey: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (Connection, Provenance, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let provenance = Provenance::init(&dir.path().join("agent.key")).unwrap();
+        let decisions = [
+            ("d0", "Should Dana Reyes own the billing rewrite?", Some("Dana Reyes asked twice")),
+            ("d1", "Should we cache the catalog?", Some("dana reyes: latency doubled")),
+            ("d2", "Do we need a second region?", None),
+        ];
+        for (i, (id, question, notes)) in decisions.iter().enumerate() {
+            let previous = (i > 0).then(|| format!("h{}", i - 1));
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, previous_hash, content_hash, signature, agent_pubkey, outcome_notes, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 's', 'k', ?6, datetime('now', ?7))",
+                params![id, question, json!({"question": question}).to_string(), previous, format!("h{}", i), notes, format!("-{} minutes", 10 - i)],
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO counsel_jobs (id, request_json) VALUES ('j1', ?1)",
+            [json!({"question": "Can Dana Reyes review the schema?"}).to_string()],
+        )
+        .unwrap();
+        (conn, provenance, dir)
+    }
+
+    #[test]
+    fn test_forget_tombstones_matches_and_signs_an_event() {
+        let (conn, provenance, _dir) = setup();
+        let dana = subject("DANA REYES", false).unwrap();
+
+        let preview = forget(&conn, &provenance, &dana, Mode::Remove, true, Utc::now()).unwrap();
+        assert_eq!((preview.decisions.len(), preview.counsel_jobs, preview.event), (2, 1, None));
+
+        let report = forget(&conn, &provenance, &dana, Mode::Remove, false, Utc::now()).unwrap();
+        assert_eq!(report.decisions, ["d0", "d1"]);
+        let event = report.event.unwrap();
+        assert!(event.verify().unwrap());
+        assert_eq!((event.content_hashes.clone(), event.previous_hash.as_deref()), (vec!["h0".to_string(), "h1".to_string()], Some("h2")));
+
+        let remaining: Vec<String> =
+            conn.prepare("SELECT id FROM decisions").unwrap().query_map([], |r| r.get(0)).unwrap().map(Result::unwrap).collect();
+        assert_eq!(remaining, ["d2"]);
+        assert_eq!(conn.query_row("SELECT COUNT(*) FROM counsel_jobs", [], |r| r.get::<_, i64>(0)).unwrap(), 0);
+        assert_eq!(retention::get_tombstone(&conn, "d0").unwrap().unwrap().reason, format!("forget {}", event.id));
+        assert_eq!(events_for(&conn, "d1").unwrap(), vec![event.clone()]);
+        assert!(retention::check_chain(&conn).unwrap().is_empty());
+        assert_eq!(crate::db::get_latest_decision_hash(&conn).unwrap(), Some(event.content_hash));
+    }
+
+    #[test]
+    fn test_forget_redact_keeps_decisions_without_the_subject() {
+        let (conn, provenance, _dir) = setup();
+        let dana = subject(r"dana\s+reyes", true).unwrap();
+        let report = forget(&conn, &provenance, &dana, Mode::Redact, false, Utc::now()).unwrap();
+        assert_eq!(report.decisions, ["d0", "d1"]);
+
+        let (question, counsel, notes): (String, String, String) = conn
+            .query_row("SELECT question, counsel_json, outcome_notes FROM decisions WHERE id = 'd0'", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+            })
+            .unwrap();
+        assert_eq!(question, "Should [FORGOTTEN] own the billing rewrite?");
+        assert!(!counsel.contains("Dana") && notes == "[FORGOTTEN] asked twice");
+        assert_eq!(events_for(&conn, "d0").unwrap()[0].mode, "redact");
+        assert!(events_for(&conn, "d2").unwrap().is_empty());
+        assert!(subject("  ", false).is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! Git History → Outcome Suggestions
+//!
+//! Outcomes are the flywheel's fuel, and most never get recorded by hand.
+//! Git already knows how a decision went: the branch merged and stayed
+//! merged, or it got reverted, or it needed a string of "fix" commits.
+//!
+//! Decisions are linked to a branch and/or PR number. `suggest_outcomes`
+//! reads the log and proposes `RecordOutcomeRequest`s - it never records
+//! them itself; a human or agent confirms first.
+
+use crate::outcome::extract_principles_from_decision;
+use crate::types::RecordOutcomeRequest;
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+/// Days after merge during which follow-up fixes count against the decision
+pub const DEFAULT_WINDOW_DAYS: i64 = 14;
+
+/// A decision tied to the branch/PR that implemented it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionLink {
+    pub decision_id: String,
+    pub branch: Option<String>,
+    pub pr_number: Option<u64>,
+    pub repo_path: String,
+    /// "owner/name" on the forge; webhooks only touch links of the repository they came from
+    #[serde(default)]
+    pub forge_repo: Option<String>,
+}
+
+/// One commit from `git log`
+#[derive(Debug, Clone)]
+pub struct GitCommit {
+    pub hash: String,
+    /// Commit time (unix seconds)
+    pub timestamp: i64,
+    pub subject: String,
+}
+
+/// A proposed outcome with the evidence behind it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutcomeSuggestion {
+    pub request: RecordOutcomeRequest,
+    pub evidence: Vec<String>,
+}
+
+/// Link a decision to a branch and/or PR in a repository
+///
+/// `forge_repo` ("owner/name") defaults to the one `repo_path`'s origin remote points at.
+pub fn link_decision(
+    conn: &Connection,
+    decision_id: &str,
+    branch: Option<&str>,
+    pr_number: Option<u64>,
+    repo_path: &str,
+    forge_repo: Option<&str>,
+) -> Result<()> {
+    if branch.is_none() && pr_number.is_none() {
+        return Err(MindsError::Validation("A decision link needs a branch or a PR number".to_string()));
+    }
+    let forge_repo = forge_repo.map(String::from).or_else(|| origin_repo(Path::new(repo_path)));
+
+    conn.execute(
+        "INSERT INTO decision_links (decision_id, branch, pr_number, repo_path, forge_repo)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(decision_id) DO UPDATE SET
+             branch = excluded.branch,
+             pr_number = excluded.pr_number,
+             repo_path = excluded.repo_path,
+             forge_repo = excluded.forge_repo",
+        params![decision_id, branch, pr_number.map(|n| n as i64), repo_path, forge_repo],
+    )?;
+    Ok(())
+}
+
+/// "owner/name" from the origin remote of a checkout, if it has one
+pub fn origin_repo(repo: &Path) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(repo).args(["remote", "get-url", "origin"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    repo_from_url(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// `git@github.com:org/app.git` or `https://gitlab.com/group/sub/app` → the path on the forge
+fn repo_from_url(url: &str) -> Option<String> {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    path.contains('/').then(|| path.to_string())
+}
+
+fn link_from_row(row: &rusqlite::Row) -> rusqlite::Result<DecisionLink> {
+    Ok(DecisionLink {
+        decision_id: row.get(0)?,
+        branch: row.get(1)?,
+        pr_number: row.get::<_, Option<i64>>(2)?.map(|n| n as u64),
+        repo_path: row.get(3)?,
+        forge_repo: row.get(4)?,
+    })
+}
+
+/// Links whose decision has no recorded outcome yet
+pub fn pending_links(conn: &Connection) -> Result<Vec<DecisionLink>> {
+    let mut stmt = conn.prepare(
+        "SELECT l.decision_id, l.branch, l.pr_number, l.repo_path, l.forge_repo
+         FROM decision_links l
+         LEFT JOIN decisions d ON d.id = l.decision_id
+         WHERE d.outcome_success IS NULL
+         ORDER BY l.linked_at",
+    )?;
+
+    let links = stmt.query_map([], link_from_row)?.filter_map(|r| r.ok()).collect();
+
+    Ok(links)
+}
+
+/// Links for a PR number (or its head branch) in forge repository `repo`, whatever
+/// their outcome state. Links made before `forge_repo` was stored fall back to
+/// their checkout's origin remote; ones without either never match.
+pub fn links_for_pr(conn: &Connection, repo: &str, pr_number: u64, branch: Option<&str>) -> Result<Vec<DecisionLink>> {
+    let mut stmt = conn.prepare(
+        "SELECT decision_id, branch, pr_number, repo_path, forge_repo
+         FROM decision_links
+         WHERE pr_number = ?1 OR (?2 IS NOT NULL AND branch = ?2)
+         ORDER BY linked_at",
+    )?;
+
+    let links = stmt
+        .query_map(params![pr_number as i64, branch], link_from_row)?
+        .filter_map(|r| r.ok())
+        .filter(|link| {
+            link.forge_repo
+                .clone()
+                .or_else(|| origin_repo(Path::new(&link.repo_path)))
+                .is_some_and(|r| r.eq_ignore_ascii_case(repo))
+        })
+        .collect();
+
+    Ok(links)
+}
+
+/// Read every commit reachable from any ref, oldest first
+pub fn read_git_log(repo: &Path) -> Result<Vec<GitCommit>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["log", "--all", "--reverse", "--format=%H%x09%ct%x09%s"])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "git log failed in {:?}: {}",
+            repo,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    let commits = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some(GitCommit {
+                hash: fields.next()?.to_string(),
+                timestamp: fields.next()?.parse().ok()?,
+                subject: fields.next().unwrap_or("").to_string(),
+            })
+        })
+        .collect();
+
+    Ok(commits)
+}
+
+fn is_fix_subject(subject: &str) -> bool {
+    let s = subject.to_lowercase();
+    s.starts_with("fix") || s.starts_with("hotfix") || s.starts_with("bugfix") || s.contains("fix:")
+}
+
+/// `#N` in the subject, not followed by another digit (`#4` isn't `#42`)
+fn mentions_pr(subject: &str, marker: &str) -> bool {
+    subject
+        .match_indices(marker)
+        .any(|(i, _)| !subject[i + marker.len()..].starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// The branch as a whole token of the subject (`cache` isn't `feature/cache-v2`)
+fn mentions_branch(subject: &str, branch: &str) -> bool {
+    let delimiter = |c: char| c.is_whitespace() || "/:()'\"".contains(c);
+    !branch.is_empty()
+        && subject.match_indices(branch).any(|(i, _)| {
+            subject[..i].chars().next_back().is_none_or(delimiter)
+                && subject[i + branch.len()..].chars().next().is_none_or(delimiter)
+        })
+}
+
+/// Judge one linked decision from its repository's history
+///
+/// - Reverted after merge → failure
+/// - Two or more follow-up fixes within the window → failure
+/// - Merged and the window has passed → success (weaker with one fix)
+/// - Not merged yet, or window still open → no suggestion
+///
+/// Only commits that mention the branch or `#PR` count as follow-ups, so
+/// unrelated fixes on a busy main branch don't sink the decision.
+pub fn suggest_outcome(
+    link: &DecisionLink,
+    commits: &[GitCommit],
+    window_days: i64,
+    now: i64,
+) -> Option<OutcomeSuggestion> {
+    let pr_marker = link.pr_number.map(|pr| format!("#{}", pr));
+    let mentions = |c: &GitCommit| {
+        pr_marker.as_deref().is_some_and(|m| mentions_pr(&c.subject, m))
+            || link.branch.as_deref().is_some_and(|b| mentions_branch(&c.subject, b))
+    };
+
+    let squash_suffix = link.pr_number.map(|pr| format!("(#{})", pr));
+    let merge = commits.iter().find(|c| {
+        mentions(c)
+            && !c.subject.starts_with("Revert")
+            && (c.subject.starts_with("Merge")
+                || squash_suffix
+                    .as_ref()
+                    .is_some_and(|s| c.subject.trim_end().ends_with(s.as_str())))
+    })?;
+
+    let short = &merge.hash[..merge.hash.len().min(8)];
+    let mut evidence = vec![format!("Merged in {} \"{}\"", short, merge.subject)];
+
+    let after_merge = commits.iter().filter(|c| c.timestamp >= merge.timestamp && c.hash != merge.hash);
+
+    let revert = after_merge
+        .clone()
+        .find(|c| c.subject.starts_with("Revert") && (mentions(c) || c.subject.contains(&merge.subject)));
+
+    let window_end = merge.timestamp + window_days * 86_400;
+    let fixes: Vec<&GitCommit> = after_merge
+        .filter(|c| c.timestamp <= window_end && is_fix_subject(&c.subject) && mentions(c))
+        .collect();
+    for fix in &fixes {
+        evidence.push(format!("Follow-up fix {} \"{}\"", &fix.hash[..fix.hash.len().min(8)], fix.subject));
+    }
+
+    let (success, confidence, failure_stage) = if let Some(revert) = revert {
+        evidence.push(format!(
+            "Reverted in {} \"{}\"",
+            &revert.hash[..revert.hash.len().min(8)],
+            revert.subject
+        ));
+        (false, 0.9, Some("reverted"))
+    } else if fixes.len() >= 2 {
+        (false, 0.6, Some("post-merge-fix"))
+    } else if now < window_end {
+        // Too early to call a success
+        return None;
+    } else if fixes.len() == 1 {
+        (true, 0.55, None)
+    } else {
+        evidence.push(format!("No reverts or follow-up fixes within {} days", window_days));
+        (true, 0.8, None)
+    };
+
+    Some(OutcomeSuggestion {
+        request: RecordOutcomeRequest {
+            decision_id: link.decision_id.clone(),
+            success,
+            notes: Some(format!("Suggested from git history: {}", evidence.join("; "))),
+            principle_ids: vec![],
+            domain: None,
+            confidence_score: Some(confidence),
+            failure_stage: failure_stage.map(String::from),
+            failure_reasons: Vec::new(),
+            idempotency_key: None,
+            actor_id: None,
+            template_id: None,
+            template_path: vec![],
+        },
+        evidence,
+    })
+}
+
+/// Suggest outcomes for every pending linked decision
+pub fn suggest_outcomes(conn: &Connection, window_days: i64) -> Result<Vec<OutcomeSuggestion>> {
+    let now = chrono::Utc::now().timestamp();
+    let mut suggestions = Vec::new();
+
+    let links = pending_links(conn)?;
+    let mut logs: std::collections::HashMap<String, Vec<GitCommit>> = std::collections::HashMap::new();
+
+    for link in links {
+        if !logs.contains_key(&link.repo_path) {
+            match read_git_log(Path::new(&link.repo_path)) {
+                Ok(commits) => {
+                    logs.insert(link.repo_path.clone(), commits);
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping {}: {}", link.decision_id, e);
+                    continue;
+                }
+            }
+        }
+
+        if let Some(mut suggestion) = suggest_outcome(&link, &logs[&link.repo_path], window_days, now) {
+            suggestion.request.principle_ids = extract_principles_from_decision(conn, &link.decision_id);
+            suggestions.push(suggestion);
+        }
+    }
+
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86_400;
+
+    fn commit(hash: &str, day: i64, subject: &str) -> GitCommit {
+        GitCommit {
+            hash: hash.to_string(),
+            timestamp: day * DAY,
+            subject: subject.to_string(),
+        }
+    }
+
+    fn link() -> DecisionLink {
+        DecisionLink {
+            decision_id: "d1".to_string(),
+            branch: Some("feature/cache".to_string()),
+            pr_number: Some(42),
+            repo_path: ".".to_string(),
+            forge_repo: None,
+        }
+    }
+
+    #[test]
+    fn test_clean_merge_suggests_success_after_window() {
+        let commits = vec![
+            commit("aaaaaaaa1", 1, "Add cache layer"),
+            commit("bbbbbbbb2", 2, "Merge pull request #42 from org/feature/cache"),
+            commit("cccccccc3", 3, "fix: unrelated typo"),
+        ];
+
+        // Window still open: no call yet
+        assert!(suggest_outcome(&link(), &commits, 14, 5 * DAY).is_none());
+
+        let suggestion = suggest_outcome(&link(), &commits, 14, 30 * DAY).unwrap();
+        assert!(suggestion.request.success);
+        assert_eq!(suggestion.request.confidence_score, Some(0.8));
+    }
+
+    #[test]
+    fn test_revert_and_fixes_suggest_failure() {
+        let reverted = vec![
+            commit("bbbbbbbb2", 2, "Add cache layer (#42)"),
+            commit("dddddddd4", 4, "Revert \"Add cache layer (#42)\""),
+        ];
+        let suggestion = suggest_outcome(&link(), &reverted, 14, 5 * DAY).unwrap();
+        assert!(!suggestion.request.success);
+        assert_eq!(suggestion.request.failure_stage.as_deref(), Some("reverted"));
+
+        let patched = vec![
+            commit("bbbbbbbb2", 2, "Merge branch 'feature/cache'"),
+            commit("eeeeeeee5", 3, "fix: cache invalidation from #42"),
+            commit("ffffffff6", 6, "hotfix feature/cache stampede"),
+        ];
+        let suggestion = suggest_outcome(&link(), &patched, 14, 7 * DAY).unwrap();
+        assert!(!suggestion.request.success);
+        assert_eq!(suggestion.evidence.len(), 3);
+
+        // Never merged: nothing to suggest
+        assert!(suggest_outcome(&link(), &patched[1..], 14, 30 * DAY).is_none());
+    }
+
+    #[test]
+    fn test_markers_match_whole_pr_numbers_and_branches() {
+        let short = DecisionLink {
+            branch: Some("cache".to_string()),
+            pr_number: Some(4),
+            ..link()
+        };
+        let others = vec![
+            commit("bbbbbbbb2", 2, "Merge pull request #42 from org/feature/cache-v2"),
+            commit("cccccccc3", 3, "Add cache warmer (#400)"),
+            commit("dddddddd4", 4, "Revert \"Merge pull request #42 from org/feature/cache-v2\""),
+        ];
+        assert!(suggest_outcome(&short, &others, 14, 30 * DAY).is_none());
+
+        let own = vec![
+            commit("bbbbbbbb2", 2, "Merge pull request #4 from org/cache"),
+            commit("cccccccc3", 3, "fix: cache-v2 stampede (#42)"),
+            commit("dddddddd4", 4, "fix: #400 follow-up"),
+        ];
+        let suggestion = suggest_outcome(&short, &own, 14, 30 * DAY).unwrap();
+        assert!(suggestion.request.success);
+        assert_eq!(suggestion.request.confidence_score, Some(0.8));
+
+        assert!(mentions_pr("Fix crash (#4)", "#4"));
+        assert!(!mentions_pr("Fix crash (#42)", "#4"));
+        assert!(mentions_branch("Merge branch 'cache'", "cache"));
+        assert!(!mentions_branch("Merge branch 'feature/cache-v2'", "cache"));
+        assert!(!mentions_branch("Merge branch 'cachedir'", "cache"));
+    }
+
+    #[test]
+    fn test_pending_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+
+        assert!(link_decision(&conn, "d1", None, None, ".", None).is_err());
+        link_decision(&conn, "d1", Some("feature/cache"), Some(42), ".", None).unwrap();
+        link_decision(&conn, "d1", Some("feature/cache"), Some(43), ".", None).unwrap();
+
+        let links = pending_links(&conn).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].pr_number, Some(43));
+    }
+
+    #[test]
+    fn test_repo_from_remote_url() {
+        assert_eq!(repo_from_url("git@github.com:org/app.git").as_deref(), Some("org/app"));
+        assert_eq!(repo_from_url("https://gitlab.com/group/sub/app/").as_deref(), Some("group/sub/app"));
+        assert_eq!(repo_from_url("ssh://git@host:22/org/app.git").as_deref(), Some("org/app"));
+        assert_eq!(repo_from_url("/srv/app.git"), None);
+    }
+}
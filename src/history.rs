@@ -0,0 +1,259 @@
+//! Decision History Search
+//!
+//! `decisions_fts` indexes every stored decision's question, the arguments
+//! its positions made and its outcome notes, kept in sync by triggers on
+//! `decisions`. Queries go through [`search::fts_query`] like principle
+//! searches, so they are stemmed ("retries" finds "retry") and can't inject
+//! FTS5 syntax. Matches come back best first with a highlighted snippet
+//! from whichever field matched best.
+//!
+//! Counsel responses also carry the [`SIMILAR_DECISIONS`] past decisions
+//! closest to the new question, among those with a recorded outcome, and
+//! which principles that outcome credited or blamed. Similarity is cosine
+//! over question embeddings when the model is loaded (decisions are
+//! embedded lazily, a batch per call, into `decision_embeddings`), and
+//! BM25 over the history index otherwise.
+
+use crate::embeddings::SemanticEngine;
+use crate::search;
+use crate::error::Result;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// Most results one search returns
+pub const MAX_RESULTS: usize = 100;
+
+/// Past decisions a counsel response shows
+pub const SIMILAR_DECISIONS: usize = 3;
+
+/// Decisions embedded per call while catching up on history
+const EMBED_BATCH: usize = 32;
+
+/// (decision id, question, outcome, similarity)
+type Scored = (String, String, Option<bool>, f64);
+
+/// One decision a history search found
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionMatch {
+    pub decision_id: String,
+    pub question: String,
+    /// The best-matching stretch of text, matched terms in `[brackets]`
+    pub snippet: String,
+    /// `None` until an outcome is recorded
+    pub outcome: Option<bool>,
+    pub outcome_notes: Option<String>,
+    pub created_at: Option<String>,
+    /// BM25, higher is better
+    pub score: f64,
+}
+
+/// Decisions matching `query`, best first
+pub fn search_decisions(conn: &Connection, query: &str, limit: usize) -> Result<Vec<DecisionMatch>> {
+    let Some(fts_query) = search::fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    // Questions weigh most; notes say how it went, arguments what was said
+    let matches = conn
+        .prepare(
+            "SELECT d.id, d.question, snippet(decisions_fts, -1, '[', ']', '…', 12),
+                    d.outcome_success, d.outcome_notes, d.created_at, -bm25(decisions_fts, 0.0, 3.0, 1.0, 2.0)
+             FROM decisions_fts
+             JOIN decisions d ON d.id = decisions_fts.decision_id
+             WHERE decisions_fts MATCH ?1
+             ORDER BY bm25(decisions_fts, 0.0, 3.0, 1.0, 2.0)
+             LIMIT ?2",
+        )?
+        .query_map(params![fts_query, limit.clamp(1, MAX_RESULTS) as i64], |row| {
+            Ok(DecisionMatch {
+                decision_id: row.get(0)?,
+                question: row.get(1)?,
+                snippet: row.get(2)?,
+                outcome: row.get::<_, Option<i64>>(3)?.map(|s| s != 0),
+                outcome_notes: row.get(4)?,
+                created_at: row.get(5)?,
+                score: row.get(6)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(matches)
+}
+
+/// A past decision like the one being counseled, and how it went
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimilarDecision {
+    pub decision_id: String,
+    pub question: String,
+    /// Cosine similarity of the questions, or without the model the BM25
+    /// score relative to the best match (0-1 either way)
+    pub similarity: f64,
+    pub outcome: Option<bool>,
+    /// Principles the outcome raised confidence in
+    pub credited: Vec<String>,
+    /// Principles the outcome lowered confidence in
+    pub blamed: Vec<String>,
+}
+
+/// The `limit` decisions with outcomes most like `question`, other than `exclude`
+pub fn similar_decisions(
+    conn: &Connection,
+    engine: Option<&mut SemanticEngine>,
+    question: &str,
+    exclude: &str,
+    limit: usize,
+) -> Result<Vec<SimilarDecision>> {
+    let mut scored: Vec<Scored> = match engine {
+        Some(engine) => by_embedding(conn, engine, question, exclude)?,
+        None => {
+            let found = search_decisions(conn, question, MAX_RESULTS)?;
+            let best = found.first().map_or(1.0, |m| m.score.max(f64::EPSILON));
+            found
+                .into_iter()
+                .filter(|m| m.outcome.is_some() && m.decision_id != exclude)
+                .map(|m| (m.decision_id, m.question, m.outcome, m.score / best))
+                .collect()
+        }
+    };
+    scored.sort_by(|a, b| b.3.total_cmp(&a.3));
+    scored.truncate(limit);
+
+    let mut adjustments = conn.prepare(
+        "SELECT p.name, SUM(fa.adjustment) FROM framework_adjustments fa
+         JOIN principles p ON p.id = fa.principle_id
+         WHERE fa.decision_id = ?1 GROUP BY p.id ORDER BY ABS(SUM(fa.adjustment)) DESC, p.name",
+    )?;
+    let mut similar = Vec::with_capacity(scored.len());
+    for (decision_id, question, outcome, similarity) in scored {
+        let mut credited = Vec::new();
+        let mut blamed = Vec::new();
+        let rows = adjustments.query_map([&decision_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)))?;
+        for row in rows {
+            let (name, total) = row?;
+            if total > 0.0 {
+                credited.push(name);
+            } else if total < 0.0 {
+                blamed.push(name);
+            }
+        }
+        similar.push(SimilarDecision { decision_id, question, similarity, outcome, credited, blamed });
+    }
+    Ok(similar)
+}
+
+fn by_embedding(
+    conn: &Connection,
+    engine: &mut SemanticEngine,
+    question: &str,
+    exclude: &str,
+) -> Result<Vec<Scored>> {
+    let pending: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT d.id, d.question FROM decisions d
+             LEFT JOIN decision_embeddings e ON e.decision_id = d.id
+             WHERE d.outcome_success IS NOT NULL AND e.decision_id IS NULL
+             ORDER BY d.created_at DESC LIMIT ?1",
+        )?
+        .query_map([EMBED_BATCH as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for (id, text) in pending {
+        let bytes: Vec<u8> = engine.embed(&text)?.iter().flat_map(|f| f.to_le_bytes()).collect();
+        conn.execute("INSERT OR REPLACE INTO decision_embeddings (decision_id, embedding) VALUES (?1, ?2)", params![id, bytes])?;
+    }
+
+    let query = engine.embed(question)?;
+    let rows: Vec<(String, String, Option<i64>, Vec<u8>)> = conn
+        .prepare(
+            "SELECT d.id, d.question, d.outcome_success, e.embedding FROM decision_embeddings e
+             JOIN decisions d ON d.id = e.decision_id
+             WHERE d.outcome_success IS NOT NULL AND d.id != ?1",
+        )?
+        .query_map([exclude], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, text, outcome, blob)| {
+            let embedding: Vec<f32> = blob.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+            let similarity = SemanticEngine::cosine_similarity(&query, &embedding) as f64;
+            (id, text, outcome.map(|s| s != 0), similarity)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_search_finds_questions_arguments_and_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let decisions = [
+            ("d0", "Should payment retries use exponential backoff?", "Jitter avoids thundering herds"),
+            ("d1", "Do we need a second region?", "A retried payment must stay idempotent"),
+            ("d2", "Should we cache the catalog?", "Reads dominate"),
+        ];
+        for (id, question, argument) in decisions {
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey)
+                 VALUES (?1, ?2, ?3, 'h', 's', 'k')",
+                params![id, question, json!({"positions": [{"argument": argument}]}).to_string()],
+            )
+            .unwrap();
+        }
+
+        let found = search_decisions(&conn, "payment retries", 10).unwrap();
+        let ids: Vec<&str> = found.iter().map(|m| m.decision_id.as_str()).collect();
+        assert_eq!(ids, ["d0", "d1"]);
+        assert!(found[0].snippet.contains("[payment]") && found[0].snippet.contains("[retries]"));
+        assert_eq!(found[1].outcome, None);
+
+        conn.execute("UPDATE decisions SET outcome_success = 0, outcome_notes = 'Cache stampede on deploy' WHERE id = 'd2'", [])
+            .unwrap();
+        let found = search_decisions(&conn, "stampede", 10).unwrap();
+        assert_eq!((found[0].decision_id.as_str(), found[0].outcome), ("d2", Some(false)));
+
+        conn.execute("DELETE FROM decisions WHERE id = 'd2'", []).unwrap();
+        assert!(search_decisions(&conn, "stampede", 10).unwrap().is_empty());
+        assert!(search_decisions(&conn, "what is the", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_similar_decisions_need_outcomes_and_report_credit() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description) VALUES ('p1', 't', 'Idempotency', 'd'), ('p2', 't', 'YAGNI', 'd');",
+        )
+        .unwrap();
+        let decisions = [
+            ("d0", "Should payment retries use exponential backoff?", Some(1)),
+            ("d1", "Should payment webhooks retry forever?", Some(0)),
+            ("d2", "Should payment retries be capped?", None),
+            ("d3", "Should we cache the catalog?", Some(1)),
+        ];
+        for (id, question, outcome) in decisions {
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success)
+                 VALUES (?1, ?2, '{}', 'h', 's', 'k', ?3)",
+                params![id, question, outcome],
+            )
+            .unwrap();
+        }
+        conn.execute_batch(
+            "INSERT INTO framework_adjustments (principle_id, adjustment, decision_id) VALUES
+                 ('p1', 0.1, 'd0'), ('p2', -0.05, 'd0'), ('p1', -0.1, 'd1');",
+        )
+        .unwrap();
+
+        let similar = similar_decisions(&conn, None, "Exponential backoff for payment retries?", "d0", 3).unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!((similar[0].decision_id.as_str(), similar[0].outcome), ("d1", Some(false)));
+        assert_eq!((similar[0].credited.len(), similar[0].blamed.clone()), (0, vec!["Idempotency".to_string()]));
+
+        let similar = similar_decisions(&conn, None, "Exponential backoff for payment retries?", "new", 3).unwrap();
+        assert_eq!(similar[0].decision_id, "d0");
+        assert_eq!(similar[0].similarity, 1.0);
+        assert_eq!((similar[0].credited.clone(), similar[0].blamed.clone()), (vec!["Idempotency".to_string()], vec!["YAGNI".to_string()]));
+    }
+}
@@ -0,0 +1,294 @@
+//! Background Counsel Jobs
+//!
+//! Deep counsel and LLM-judged evaluation can take longer than an agent
+//! wants to block on a tool call. Agents submit a question with
+//! `submit_counsel_async`, get a job id back immediately, and poll
+//! `get_counsel_result`; one or more `100minds worker` processes drain the
+//! queue. The queue lives in SQLite, so workers and the server only need
+//! to share the database file.
+
+use crate::counsel::CounselEngine;
+use crate::provenance::Provenance;
+use crate::types::{CounselRequest, CounselResponse};
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+impl JobStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Done => "done",
+            JobStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "queued" => Some(JobStatus::Queued),
+            "running" => Some(JobStatus::Running),
+            "done" => Some(JobStatus::Done),
+            "failed" => Some(JobStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// A queued counsel question and, once processed, its answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CounselJob {
+    pub job_id: String,
+    pub status: JobStatus,
+    pub request: CounselRequest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<CounselResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worker_id: Option<String>,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+}
+
+/// Queue a counsel request; returns the job id
+pub fn submit_job(conn: &Connection, request: &CounselRequest) -> Result<String> {
+    let job_id = uuid::Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO counsel_jobs (id, request_json) VALUES (?1, ?2)",
+        params![job_id, serde_json::to_string(request)?],
+    )?;
+    Ok(job_id)
+}
+
+const JOB_COLUMNS: &str =
+    "id, status, request_json, result_json, error, worker_id, created_at, finished_at";
+
+/// Raw `counsel_jobs` row, before the JSON columns are decoded
+struct JobRow {
+    job_id: String,
+    status: String,
+    request_json: String,
+    result_json: Option<String>,
+    error: Option<String>,
+    worker_id: Option<String>,
+    created_at: String,
+    finished_at: Option<String>,
+}
+
+impl JobRow {
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(JobRow {
+            job_id: row.get(0)?,
+            status: row.get(1)?,
+            request_json: row.get(2)?,
+            result_json: row.get(3)?,
+            error: row.get(4)?,
+            worker_id: row.get(5)?,
+            created_at: row.get(6)?,
+            finished_at: row.get(7)?,
+        })
+    }
+
+    fn into_job(self) -> Result<CounselJob> {
+        Ok(CounselJob {
+            status: JobStatus::parse(&self.status)
+                .ok_or_else(|| MindsError::Validation(format!("Unknown job status: {}", self.status)))?,
+            request: serde_json::from_str(&self.request_json)?,
+            result: self.result_json.map(|r| serde_json::from_str(&r)).transpose()?,
+            job_id: self.job_id,
+            error: self.error,
+            worker_id: self.worker_id,
+            created_at: self.created_at,
+            finished_at: self.finished_at,
+        })
+    }
+}
+
+/// Look up a job by id
+pub fn get_job(conn: &Connection, job_id: &str) -> Result<Option<CounselJob>> {
+    let row = conn
+        .query_row(
+            &format!("SELECT {} FROM counsel_jobs WHERE id = ?1", JOB_COLUMNS),
+            [job_id],
+            JobRow::from_row,
+        )
+        .optional()?;
+    row.map(JobRow::into_job).transpose()
+}
+
+/// Atomically take the oldest queued job for `worker_id`
+///
+/// The status check in the UPDATE makes concurrent workers safe: only one
+/// of them flips a given row from queued to running.
+pub fn claim_next(conn: &Connection, worker_id: &str) -> Result<Option<CounselJob>> {
+    let row = conn
+        .query_row(
+            &format!(
+                "UPDATE counsel_jobs
+                 SET status = 'running', worker_id = ?1, started_at = CURRENT_TIMESTAMP
+                 WHERE id = (SELECT id FROM counsel_jobs WHERE status = 'queued'
+                             ORDER BY created_at, rowid LIMIT 1)
+                   AND status = 'queued'
+                 RETURNING {}",
+                JOB_COLUMNS
+            ),
+            [worker_id],
+            JobRow::from_row,
+        )
+        .optional()?;
+    row.map(JobRow::into_job).transpose()
+}
+
+/// Store a finished job's response
+pub fn complete_job(conn: &Connection, job_id: &str, response: &CounselResponse) -> Result<()> {
+    conn.execute(
+        "UPDATE counsel_jobs
+         SET status = 'done', result_json = ?2, finished_at = CURRENT_TIMESTAMP
+         WHERE id = ?1",
+        params![job_id, serde_json::to_string(response)?],
+    )?;
+    Ok(())
+}
+
+/// Mark a job failed with the error that stopped it
+pub fn fail_job(conn: &Connection, job_id: &str, error: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE counsel_jobs
+         SET status = 'failed', error = ?2, finished_at = CURRENT_TIMESTAMP
+         WHERE id = ?1",
+        params![job_id, error],
+    )?;
+    Ok(())
+}
+
+/// Put jobs left running by a crashed worker back in the queue
+pub fn requeue_stale(conn: &Connection, worker_id: &str) -> Result<usize> {
+    Ok(conn.execute(
+        "UPDATE counsel_jobs SET status = 'queued', worker_id = NULL, started_at = NULL
+         WHERE status = 'running' AND worker_id = ?1",
+        [worker_id],
+    )?)
+}
+
+/// Job counts by status
+pub fn queue_depth(conn: &Connection) -> Result<Vec<(JobStatus, i64)>> {
+    let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM counsel_jobs GROUP BY status")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?;
+    let mut depth = Vec::new();
+    for row in rows {
+        let (status, count) = row?;
+        if let Some(status) = JobStatus::parse(&status) {
+            depth.push((status, count));
+        }
+    }
+    Ok(depth)
+}
+
+/// Claim and answer one job; returns the job id, or None if the queue is empty
+pub fn process_next(engine: &CounselEngine, conn: &Connection, worker_id: &str) -> Result<Option<String>> {
+    let Some(job) = claim_next(conn, worker_id)? else {
+        return Ok(None);
+    };
+
+    match engine.counsel(&job.request) {
+        Ok(response) => complete_job(conn, &job.job_id, &response)?,
+        Err(e) => fail_job(conn, &job.job_id, &e.to_string())?,
+    }
+    Ok(Some(job.job_id))
+}
+
+/// Worker loop: drain the queue, then poll every `poll_interval`
+/// With `once`, exit as soon as the queue is empty
+pub fn run_worker(
+    conn: &Connection,
+    provenance: &Provenance,
+    worker_id: &str,
+    poll_interval: Duration,
+    once: bool,
+) -> Result<usize> {
+    let requeued = requeue_stale(conn, worker_id)?;
+    if requeued > 0 {
+        tracing::warn!("Requeued {} jobs left running by a previous {}", requeued, worker_id);
+    }
+
+    let engine = CounselEngine::new(conn, provenance);
+    let mut processed = 0;
+    loop {
+        match process_next(&engine, conn, worker_id)? {
+            Some(job_id) => {
+                processed += 1;
+                tracing::info!("Job {} finished", job_id);
+            }
+            None if once => return Ok(processed),
+            None => std::thread::sleep(poll_interval),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (Connection, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        (conn, dir)
+    }
+
+    fn request(question: &str) -> CounselRequest {
+        CounselRequest {
+            question: question.to_string(),
+            context: Default::default(),
+            decision_id: None,
+        }
+    }
+
+    #[test]
+    fn test_claim_is_fifo_and_exclusive() {
+        let (conn, _dir) = setup();
+        let first = submit_job(&conn, &request("first?")).unwrap();
+        let second = submit_job(&conn, &request("second?")).unwrap();
+
+        let claimed = claim_next(&conn, "w1").unwrap().unwrap();
+        assert_eq!(claimed.job_id, first);
+        assert_eq!(claimed.status, JobStatus::Running);
+        assert_eq!(claimed.request.question, "first?");
+
+        assert_eq!(claim_next(&conn, "w2").unwrap().unwrap().job_id, second);
+        assert!(claim_next(&conn, "w3").unwrap().is_none());
+
+        fail_job(&conn, &first, "boom").unwrap();
+        let job = get_job(&conn, &first).unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.error.as_deref(), Some("boom"));
+        assert!(job.finished_at.is_some());
+    }
+
+    #[test]
+    fn test_requeue_stale_only_touches_own_jobs() {
+        let (conn, _dir) = setup();
+        let a = submit_job(&conn, &request("a?")).unwrap();
+        submit_job(&conn, &request("b?")).unwrap();
+        claim_next(&conn, "w1").unwrap();
+        claim_next(&conn, "w2").unwrap();
+
+        assert_eq!(requeue_stale(&conn, "w1").unwrap(), 1);
+        assert_eq!(get_job(&conn, &a).unwrap().unwrap().status, JobStatus::Queued);
+
+        let depth = queue_depth(&conn).unwrap();
+        assert!(depth.contains(&(JobStatus::Queued, 1)));
+        assert!(depth.contains(&(JobStatus::Running, 1)));
+    }
+}
@@ -0,0 +1,282 @@
+//! Knowledge Graph Export
+//!
+//! The corpus and its history as one graph, for Gephi, yEd, networkx or a
+//! D3 force layout:
+//!
+//! - nodes: thinkers, principles, decisions, and the two outcomes
+//!   (`outcome:success`, `outcome:failure`)
+//! - `authored` thinker → principle, weighted by the principle's citations
+//! - `cited` principle → decision, weighted 1.0 if that decision succeeded,
+//!   0.0 if it failed and 0.5 while it has no outcome
+//! - `resulted_in` decision → outcome
+//! - curated `requires` / `conflicts` / `refines` principle → principle
+//!   edges ([`crate::principle_graph`])
+//!
+//! Principle nodes carry their learned confidence and citation, success and
+//! failure counts. Node IDs are prefixed with their kind (`thinker:`,
+//! `principle:`, `decision:`) so they never collide.
+//!
+//! [`KnowledgeGraph::to_graphml`] writes GraphML with typed attribute keys;
+//! [`KnowledgeGraph::to_d3_json`] writes `{"nodes": [...], "links": [...]}`
+//! with `source`/`target` IDs, the shape `d3.forceLink` expects.
+
+use crate::error::Result;
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+/// One node; `attributes` holds the kind-specific fields
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphNode {
+    pub id: String,
+    /// "thinker", "principle", "decision" or "outcome"
+    pub kind: String,
+    pub label: String,
+    #[serde(flatten)]
+    pub attributes: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphLink {
+    pub source: String,
+    pub target: String,
+    pub relation: String,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct KnowledgeGraph {
+    pub nodes: Vec<GraphNode>,
+    pub links: Vec<GraphLink>,
+}
+
+fn node(id: String, kind: &str, label: &str, attributes: Value) -> GraphNode {
+    let attributes = match attributes {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+    GraphNode { id, kind: kind.to_string(), label: label.to_string(), attributes }
+}
+
+fn link(source: &str, target: &str, relation: &str, weight: f64) -> GraphLink {
+    GraphLink { source: source.to_string(), target: target.to_string(), relation: relation.to_string(), weight }
+}
+
+/// Build the graph from the database; `decisions: false` leaves out
+/// decisions and outcomes (the corpus alone)
+pub fn build(conn: &Connection, decisions: bool) -> Result<KnowledgeGraph> {
+    let mut graph = KnowledgeGraph::default();
+
+    let principles: Vec<(String, String, String, f64)> = conn
+        .prepare("SELECT id, thinker_id, name, COALESCE(learned_confidence, 0.5) FROM principles ORDER BY id")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    // (decision, principle) citations, from the stored response or, for
+    // outcome-only decisions, the adjustments; merged principles resolved
+    let mut citations: Vec<(String, String)> = Vec::new();
+    let mut outcomes: Vec<(String, String, Option<bool>, Option<String>)> = Vec::new();
+    if decisions {
+        citations = conn
+            .prepare(
+                "SELECT DISTINCT cites.decision_id, COALESCE(al.kept_id, cites.principle_id) FROM (
+                     SELECT d.id AS decision_id, j.value AS principle_id FROM decisions d,
+                         json_each(CASE WHEN json_valid(d.counsel_json) THEN d.counsel_json ELSE '{}' END, '$.principle_ids') j
+                     UNION SELECT decision_id, principle_id FROM framework_adjustments WHERE decision_id IS NOT NULL
+                 ) cites
+                 LEFT JOIN principle_aliases al ON al.merged_id = cites.principle_id
+                 ORDER BY 1, 2",
+            )?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        outcomes = conn
+            .prepare("SELECT id, question, outcome_success, created_at FROM decisions ORDER BY created_at, id")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, Option<i64>>(2)?.map(|s| s != 0), row.get(3)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+    }
+    let outcome_of: HashMap<&str, Option<bool>> = outcomes.iter().map(|(id, _, o, _)| (id.as_str(), *o)).collect();
+    // principle -> (citations, successes, failures)
+    let mut counts: HashMap<&str, (u64, u64, u64)> = HashMap::new();
+    for (decision, principle) in &citations {
+        let entry = counts.entry(principle.as_str()).or_default();
+        entry.0 += 1;
+        match outcome_of.get(decision.as_str()).copied().flatten() {
+            Some(true) => entry.1 += 1,
+            Some(false) => entry.2 += 1,
+            None => {}
+        }
+    }
+
+    let thinkers = conn
+        .prepare("SELECT id, name, domain FROM thinkers ORDER BY id")?
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (id, name, domain) in &thinkers {
+        graph.nodes.push(node(format!("thinker:{}", id), "thinker", name, json!({"domain": domain})));
+    }
+    for (id, thinker_id, name, confidence) in &principles {
+        let (cited, successes, failures) = counts.get(id.as_str()).copied().unwrap_or_default();
+        let node_id = format!("principle:{}", id);
+        graph.nodes.push(node(
+            node_id.clone(),
+            "principle",
+            name,
+            json!({"confidence": confidence, "citations": cited, "successes": successes, "failures": failures}),
+        ));
+        graph.links.push(link(&format!("thinker:{}", thinker_id), &node_id, "authored", cited as f64));
+    }
+
+    if decisions {
+        for (label, success) in [("success", true), ("failure", false)] {
+            if outcomes.iter().any(|(_, _, o, _)| *o == Some(success)) {
+                graph.nodes.push(node(format!("outcome:{}", label), "outcome", label, json!({})));
+            }
+        }
+        for (id, question, outcome, created_at) in &outcomes {
+            let node_id = format!("decision:{}", id);
+            graph.nodes.push(node(node_id.clone(), "decision", question, json!({"created_at": created_at})));
+            if let Some(success) = outcome {
+                let target = if *success { "outcome:success" } else { "outcome:failure" };
+                graph.links.push(link(&node_id, target, "resulted_in", 1.0));
+            }
+        }
+        let known: HashSet<&str> = principles.iter().map(|(id, ..)| id.as_str()).collect();
+        for (decision, principle) in citations.iter().filter(|(_, p)| known.contains(p.as_str())) {
+            let weight = match outcome_of.get(decision.as_str()).copied().flatten() {
+                Some(true) => 1.0,
+                Some(false) => 0.0,
+                None => 0.5,
+            };
+            graph.links.push(link(&format!("principle:{}", principle), &format!("decision:{}", decision), "cited", weight));
+        }
+    }
+
+    for edge in crate::principle_graph::list(conn)? {
+        graph.links.push(link(
+            &format!("principle:{}", edge.from_id),
+            &format!("principle:{}", edge.to_id),
+            &edge.relation,
+            1.0,
+        ));
+    }
+    Ok(graph)
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+fn graphml_type(value: &Value) -> &'static str {
+    match value {
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "long",
+        Value::Number(_) => "double",
+        _ => "string",
+    }
+}
+
+impl KnowledgeGraph {
+    /// GraphML with one `<key>` per node attribute, plus `relation` and
+    /// `weight` on edges
+    pub fn to_graphml(&self) -> String {
+        let mut keys: BTreeMap<&str, &'static str> = BTreeMap::new();
+        for node in &self.nodes {
+            for (name, value) in &node.attributes {
+                if !value.is_null() {
+                    keys.entry(name.as_str()).or_insert_with(|| graphml_type(value));
+                }
+            }
+        }
+
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str("  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+        for (name, kind) in &keys {
+            let _ = writeln!(out, "  <key id=\"{0}\" for=\"node\" attr.name=\"{0}\" attr.type=\"{1}\"/>", xml_escape(name), kind);
+        }
+        out.push_str("  <key id=\"relation\" for=\"edge\" attr.name=\"relation\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n");
+        out.push_str("  <graph id=\"100minds\" edgedefault=\"directed\">\n");
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(&node.id));
+            let _ = writeln!(out, "      <data key=\"kind\">{}</data>", node.kind);
+            let _ = writeln!(out, "      <data key=\"label\">{}</data>", xml_escape(&node.label));
+            for (name, value) in node.attributes.iter().filter(|(_, v)| !v.is_null()) {
+                let text = value.as_str().map(String::from).unwrap_or_else(|| value.to_string());
+                let _ = writeln!(out, "      <data key=\"{}\">{}</data>", xml_escape(name), xml_escape(&text));
+            }
+            out.push_str("    </node>\n");
+        }
+        for (i, link) in self.links.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">",
+                i,
+                xml_escape(&link.source),
+                xml_escape(&link.target)
+            );
+            let _ = writeln!(out, "      <data key=\"relation\">{}</data>", xml_escape(&link.relation));
+            let _ = writeln!(out, "      <data key=\"weight\">{}</data>", link.weight);
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// `{"nodes": [...], "links": [...]}` for D3
+    pub fn to_d3_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_links_thinkers_principles_decisions_and_outcomes() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            r#"INSERT INTO thinkers (id, name, domain) VALUES ('knuth', 'Donald Knuth', 'software');
+               INSERT INTO principles (id, thinker_id, name, description)
+                   VALUES ('premature', 'knuth', 'Premature Optimization', 'd'), ('profile', 'knuth', 'Profile <First>', 'd');
+               INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success)
+                   VALUES ('d1', 'Cache it?', '{"principle_ids": ["premature", "profile"]}', 'h', 's', 'k', 1),
+                          ('d2', 'Rewrite it?', '{"principle_ids": ["premature"]}', 'h', 's', 'k', 0),
+                          ('d3', 'Shard it?', 'not json', 'h', 's', 'k', NULL);
+               INSERT INTO framework_adjustments (principle_id, adjustment, decision_id) VALUES ('profile', 0.05, 'd3');"#,
+        )
+        .unwrap();
+        crate::principle_graph::add(&conn, "premature", "requires", "profile", None).unwrap();
+
+        let graph = build(&conn, true).unwrap();
+        let premature = graph.nodes.iter().find(|n| n.id == "principle:premature").unwrap();
+        assert_eq!((premature.attributes["citations"].clone(), premature.attributes["failures"].clone()), (json!(2), json!(1)));
+        let weight = |source: &str, target: &str| {
+            graph.links.iter().find(|l| l.source == source && l.target == target).map(|l| (l.relation.as_str(), l.weight))
+        };
+        assert_eq!(weight("thinker:knuth", "principle:premature"), Some(("authored", 2.0)));
+        assert_eq!(weight("principle:premature", "decision:d2"), Some(("cited", 0.0)));
+        assert_eq!(weight("principle:profile", "decision:d3"), Some(("cited", 0.5)));
+        assert_eq!(weight("decision:d1", "outcome:success"), Some(("resulted_in", 1.0)));
+        assert_eq!(weight("principle:premature", "principle:profile"), Some(("requires", 1.0)));
+
+        let graphml = graph.to_graphml();
+        assert!(graphml.contains("<key id=\"citations\" for=\"node\" attr.name=\"citations\" attr.type=\"long\"/>"));
+        assert!(graphml.contains("<data key=\"label\">Profile &lt;First&gt;</data>"));
+        assert_eq!(graphml.matches("<edge ").count(), graph.links.len());
+
+        let d3 = graph.to_d3_json();
+        assert_eq!(d3["nodes"].as_array().unwrap().len(), graph.nodes.len());
+        assert_eq!(d3["links"][0]["source"], "thinker:knuth");
+
+        let corpus = build(&conn, false).unwrap();
+        assert!(corpus.nodes.iter().all(|n| n.kind == "thinker" || n.kind == "principle"));
+        assert!(corpus.links.iter().all(|l| l.relation == "authored" || l.relation == "requires"));
+    }
+}
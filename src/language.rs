@@ -0,0 +1,278 @@
+//! Question Language Normalization
+//!
+//! The corpus and every keyword table are English, so a question asked in
+//! Spanish or German used to match nothing. Counsel now detects the
+//! question's language and, for non-English questions, retrieves principles
+//! with an English rendering of it:
+//!
+//! - a translation hook, if one is plugged in with
+//!   [`QueryNormalizer::with_translator`] or configured as a command
+//! - otherwise a built-in glossary of decision vocabulary, which is enough
+//!   for keyword retrieval though not for reading
+//!
+//! The response and the stored decision keep the original question and
+//! record its language. For embedding search, point `embedding_model_dir`
+//! at a multilingual sentence-transformer export (e.g.
+//! paraphrase-multilingual-MiniLM-L12-v2) so untranslated text embeds
+//! next to the English corpus:
+//!
+//! ```toml
+//! [language]
+//! # Reads the question on stdin, writes English on stdout;
+//! # MINDS_SOURCE_LANG holds the detected language
+//! translate_command = "trans -b :en"
+//! embedding_model_dir = "/opt/models/multilingual-minilm"
+//! ```
+
+use crate::error::{MindsError, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+/// Languages [`detect`] can tell apart
+pub const LANGUAGES: [&str; 3] = ["en", "es", "de"];
+
+const STOPWORDS_EN: &[&str] = &[
+    "the", "a", "an", "and", "is", "are", "we", "should", "to", "of", "for", "with", "our", "or", "how",
+    "in", "on", "it", "not", "be", "do", "what", "this",
+];
+
+const STOPWORDS_ES: &[&str] = &[
+    "el", "la", "los", "las", "de", "que", "y", "en", "un", "una", "para", "con", "por", "es", "nuestro",
+    "nuestra", "nuestros", "o", "del", "al", "se", "no", "cómo", "qué", "debemos", "deberíamos", "mejor",
+    "lo", "su", "sus", "entre", "desde", "sin",
+];
+
+const STOPWORDS_DE: &[&str] = &[
+    "der", "die", "das", "und", "ist", "wir", "sollen", "sollten", "nicht", "ein", "eine", "einen", "mit",
+    "für", "auf", "zu", "den", "dem", "unser", "unsere", "unseren", "oder", "wie", "von", "es", "im",
+    "ob", "sich", "neu", "besser",
+];
+
+/// Spanish decision vocabulary → English
+const GLOSSARY_ES: &[(&str, &str)] = &[
+    ("debemos", "should"), ("deberíamos", "should"), ("reescribir", "rewrite"), ("reescritura", "rewrite"),
+    ("refactorizar", "refactor"), ("sistema", "system"), ("sistemas", "systems"), ("legado", "legacy"),
+    ("heredado", "legacy"), ("código", "code"), ("datos", "data"), ("microservicios", "microservices"),
+    ("monolito", "monolith"), ("rendimiento", "performance"), ("escalar", "scale"),
+    ("escalabilidad", "scalability"), ("pruebas", "tests"), ("prueba", "test"), ("equipo", "team"),
+    ("contratar", "hire"), ("despliegue", "deployment"), ("desplegar", "deploy"),
+    ("arquitectura", "architecture"), ("seguridad", "security"), ("caché", "cache"), ("deuda", "debt"),
+    ("técnica", "technical"), ("lanzar", "launch"), ("producto", "product"), ("clientes", "customers"),
+    ("cliente", "customer"), ("usuarios", "users"), ("costo", "cost"), ("coste", "cost"),
+    ("riesgo", "risk"), ("migrar", "migrate"), ("migración", "migration"), ("nube", "cloud"),
+    ("servidor", "server"), ("construir", "build"), ("comprar", "buy"), ("optimizar", "optimize"),
+    ("simplicidad", "simplicity"), ("complejidad", "complexity"), ("proyecto", "project"),
+    ("plazo", "deadline"), ("calidad", "quality"), ("cero", "scratch"), ("desde", "from"),
+    ("precio", "pricing"), ("crecimiento", "growth"), ("ahora", "now"), ("después", "later"),
+];
+
+/// German decision vocabulary → English
+const GLOSSARY_DE: &[(&str, &str)] = &[
+    ("sollen", "should"), ("sollten", "should"), ("umschreiben", "rewrite"), ("neuschreiben", "rewrite"),
+    ("refaktorieren", "refactor"), ("altsystem", "legacy system"), ("altsysteme", "legacy systems"),
+    ("datenbank", "database"), ("mikroservices", "microservices"), ("leistung", "performance"),
+    ("skalieren", "scale"), ("skalierbarkeit", "scalability"), ("testen", "test"),
+    ("einstellen", "hire"), ("bereitstellung", "deployment"), ("architektur", "architecture"),
+    ("sicherheit", "security"), ("schulden", "debt"), ("technische", "technical"),
+    ("veröffentlichen", "launch"), ("produkt", "product"), ("kunden", "customers"), ("nutzer", "users"),
+    ("benutzer", "users"), ("kosten", "cost"), ("risiko", "risk"), ("migrieren", "migrate"),
+    ("bauen", "build"), ("kaufen", "buy"), ("optimieren", "optimize"), ("einfachheit", "simplicity"),
+    ("komplexität", "complexity"), ("projekt", "project"), ("frist", "deadline"),
+    ("qualität", "quality"), ("grund", "scratch"), ("preise", "pricing"), ("wachstum", "growth"),
+    ("jetzt", "now"), ("später", "later"), ("schreiben", "write"),
+];
+
+/// A question as counsel searches with it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedQuery {
+    /// Detected language: "en", "es" or "de"
+    pub language: String,
+    /// English text used for retrieval (the question itself if English)
+    pub text: String,
+    /// How `text` was produced: "none", "glossary" or "translator"
+    pub method: String,
+}
+
+/// The `[language]` table of config.toml
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LanguageConfig {
+    /// Shell command translating stdin to English on stdout
+    pub translate_command: Option<String>,
+    /// Embedding model directory (model.onnx + tokenizer.json) to use
+    /// instead of the English MiniLM download
+    pub embedding_model_dir: Option<PathBuf>,
+}
+
+impl LanguageConfig {
+    /// Read `[language]` from the config.toml beside the database; a missing file or table gives the defaults
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        crate::config::section(conn, "language")
+    }
+
+    /// Where to load the embedding model from. A configured directory must
+    /// already hold the model: only the default one is downloaded into.
+    pub fn model_dir(&self) -> Result<PathBuf> {
+        let Some(dir) = &self.embedding_model_dir else {
+            return Ok(crate::embeddings::get_model_dir());
+        };
+        for file in ["model.onnx", "tokenizer.json"] {
+            if !dir.join(file).exists() {
+                return Err(MindsError::Config(format!("embedding_model_dir {} has no {}", dir.display(), file)));
+            }
+        }
+        Ok(dir.clone())
+    }
+}
+
+/// (text, source language) → English; `None` when it can't translate
+pub type TranslateFn = Arc<dyn Fn(&str, &str) -> Option<String> + Send + Sync>;
+
+/// Lowercased words, split on anything that isn't a letter or digit
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Best guess at the language of `text`; English unless Spanish or German
+/// stopwords clearly outnumber English ones
+pub fn detect(text: &str) -> &'static str {
+    let words = words(text);
+    let hits = |stopwords: &[&str]| words.iter().filter(|w| stopwords.contains(&w.as_str())).count();
+    let marks = |chars: &[char]| text.chars().filter(|c| chars.contains(&c.to_ascii_lowercase())).count();
+    let en = hits(STOPWORDS_EN);
+    let es = hits(STOPWORDS_ES) + marks(&['ñ', '¿', '¡']);
+    let de = hits(STOPWORDS_DE) + marks(&['ä', 'ö', 'ü', 'ß']);
+    if es > en && es >= de {
+        "es"
+    } else if de > en {
+        "de"
+    } else {
+        "en"
+    }
+}
+
+/// Word-by-word English rendering: glossary words translated, the
+/// language's stopwords dropped, everything else (names, loanwords) kept
+fn gloss(text: &str, language: &str) -> String {
+    let (glossary, stopwords) = match language {
+        "es" => (GLOSSARY_ES, STOPWORDS_ES),
+        "de" => (GLOSSARY_DE, STOPWORDS_DE),
+        _ => return text.to_string(),
+    };
+    words(text)
+        .into_iter()
+        .filter_map(|w| match glossary.iter().find(|(from, _)| *from == w) {
+            Some((_, to)) => Some(to.to_string()),
+            None if stopwords.contains(&w.as_str()) => None,
+            None => Some(w),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Pipe `text` through a shell command and take its stdout
+fn run_translate_command(command: &str, text: &str, language: &str) -> Option<String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("MINDS_SOURCE_LANG", language)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| tracing::warn!("translate_command: {}", e))
+        .ok()?;
+    child.stdin.take()?.write_all(text.as_bytes()).ok()?;
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        tracing::warn!("translate_command exited with {}", output.status);
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub struct QueryNormalizer {
+    translator: Option<TranslateFn>,
+}
+
+impl QueryNormalizer {
+    pub fn new(config: &LanguageConfig) -> Self {
+        let translator = config.translate_command.clone().map(|command| -> TranslateFn {
+            Arc::new(move |text: &str, language: &str| run_translate_command(&command, text, language))
+        });
+        QueryNormalizer { translator }
+    }
+
+    /// Translate non-English questions with this hook, falling back to the glossary
+    pub fn with_translator(mut self, translate: TranslateFn) -> Self {
+        self.translator = Some(translate);
+        self
+    }
+
+    pub fn normalize(&self, question: &str) -> NormalizedQuery {
+        let language = detect(question);
+        let query = |text: String, method: &str| NormalizedQuery {
+            language: language.to_string(),
+            text,
+            method: method.to_string(),
+        };
+        if language == "en" {
+            return query(question.to_string(), "none");
+        }
+        let translated = self
+            .translator
+            .as_ref()
+            .and_then(|translate| translate(question, language))
+            .filter(|t| !t.trim().is_empty());
+        match translated {
+            Some(text) => query(text, "translator"),
+            None => query(gloss(question, language), "glossary"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_language_and_glosses_keywords() {
+        assert_eq!(detect("Should we rewrite the legacy system?"), "en");
+        assert_eq!(detect("¿Deberíamos reescribir el sistema legado desde cero?"), "es");
+        assert_eq!(detect("Sollten wir das Altsystem von Grund auf neu schreiben?"), "de");
+        assert_eq!(detect("Kubernetes?"), "en");
+
+        let normalizer = QueryNormalizer::new(&LanguageConfig::default());
+        let es = normalizer.normalize("¿Deberíamos reescribir el sistema legado desde cero?");
+        assert_eq!((es.language.as_str(), es.method.as_str()), ("es", "glossary"));
+        assert_eq!(es.text, "should rewrite system legacy from scratch");
+        let de = normalizer.normalize("Sollten wir das Altsystem von Grund auf neu schreiben?");
+        assert_eq!(de.text, "should legacy system scratch write");
+        let en = normalizer.normalize("Should we cache?");
+        assert_eq!((en.text.as_str(), en.method.as_str()), ("Should we cache?", "none"));
+    }
+
+    #[test]
+    fn test_translator_hook_wins_over_glossary() {
+        let translate: TranslateFn = Arc::new(|text: &str, language: &str| {
+            (language == "es" && text.contains("monolito")).then(|| "Should we split the monolith?".to_string())
+        });
+        let normalizer = QueryNormalizer::new(&LanguageConfig::default()).with_translator(translate);
+        let query = normalizer.normalize("¿Debemos dividir el monolito?");
+        assert_eq!((query.text.as_str(), query.method.as_str()), ("Should we split the monolith?", "translator"));
+        // A hook that gives up falls back to the glossary
+        assert_eq!(normalizer.normalize("¿Debemos contratar?").method, "glossary");
+
+        let config: LanguageConfig = toml::from_str("translate_command = \"cat\"\n").unwrap();
+        let piped = QueryNormalizer::new(&config).normalize("¿Debemos contratar?");
+        assert_eq!((piped.text.as_str(), piped.method.as_str()), ("¿Debemos contratar?", "translator"));
+    }
+}
@@ -37,7 +37,7 @@
 //! // Semantic search
 //! let mut semantic = SemanticEngine::new(&get_model_dir())?;
 //! semantic.load_embeddings(&conn)?;
-//! let matches = semantic.hybrid_search(&conn, query, top_k, 0.6)?;
+//! let matches = semantic.hybrid_search(&conn, query, top_k, &SearchConfig::beside(&conn)?)?;
 //! ```
 //!
 //! # Architecture
@@ -56,21 +56,90 @@
 //! └─────────────────────────────────────────────────────┘
 //! ```
 
+// Human-facing console lines: `say!` (stdout) and `say_err!` (stderr) go
+// through `output::render`, so `--plain` can replace box drawing and emoji
+// with ASCII. Machine output (JSON, ids for scripts) uses `std::println!`.
+macro_rules! say {
+    () => { ::std::println!() };
+    ($($arg:tt)*) => { ::std::println!("{}", $crate::output::render(&::std::format!($($arg)*))) };
+}
+macro_rules! say_err {
+    () => { ::std::eprintln!() };
+    ($($arg:tt)*) => { ::std::eprintln!("{}", $crate::output::render(&::std::format!($($arg)*))) };
+}
+
+pub mod access;
+pub mod action_prompts;
+pub mod adr;
+pub mod anti_patterns;
+pub mod approval;
+pub mod blind_spots;
+pub mod calibration;
+pub mod checklist;
+pub mod cli;
+pub mod config;
+pub mod context;
 pub mod convenience;
+pub mod corpus;
+pub mod cost_of_delay;
 pub mod counsel;
+pub mod daemon;
+pub mod dashboard;
 pub mod db;
+pub mod depth;
+pub mod decay;
 pub mod embeddings;
+pub mod encryption;
+pub mod error;
+pub mod estimates;
 pub mod eval;
+pub mod falsification;
+pub mod feed;
+pub mod forget;
+pub mod git_outcomes;
+pub mod history;
+pub mod jobs;
+pub mod knowledge_graph;
+pub mod language;
 pub mod mcp;
 pub mod neural_posterior;
+pub mod options;
+pub mod notifier;
+pub mod output;
 pub mod outcome;
+pub mod outcome_guard;
+pub mod packs;
+pub mod policy;
 pub mod prd;
+pub mod principle_graph;
 pub mod provenance;
+pub mod redact;
+pub mod relevance;
+pub mod replay;
+pub mod retention;
+pub mod risk;
+pub mod search;
+pub mod session;
+pub mod snapshot;
+pub mod stats;
+pub mod template_feedback;
 pub mod templates;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod themes;
+pub mod thread;
+pub mod tradeoff;
+pub mod tui;
 pub mod types;
+pub mod urgency;
+pub mod validation;
+pub mod vault;
+pub mod watchdog;
+pub mod webhooks;
 
 // Core types
 pub use counsel::CounselEngine;
+pub use error::MindsError;
 pub use db::{init_db, PrincipleMatch};
 pub use provenance::Provenance;
 pub use types::*;
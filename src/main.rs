@@ -6,131 +6,308 @@
 //! Or via MCP: add to your claude_desktop_config.json
 
 use anyhow::Result;
+use clap::Parser;
 use minds_mcp::{
-    counsel::CounselEngine, db, embeddings, eval, mcp, outcome, prd, provenance::Provenance,
-    templates, types::*,
+    access, adr, approval, blind_spots, calibration, checklist,
+    cli::{self, Cli, Command, OutcomeArgs},
+    output::{self, OutputMode},
+    cost_of_delay, counsel::CounselEngine, daemon, dashboard, db, decay, embeddings, estimates, eval, falsification, feed, forget, git_outcomes, history, jobs, knowledge_graph, language, mcp, notifier, outcome,
+    options, outcome_guard, packs, policy, prd, principle_graph,
+    provenance::Provenance, redact, replay, retention, risk, search, session, snapshot::{CorpusSnapshot, SharedSnapshot}, stats, template_feedback, templates, themes, thread, tui, types::*, validation, vault,
+    watchdog, webhooks,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// Same `say!`/`say_err!` as the library's console output (see lib.rs)
+macro_rules! say {
+    () => { ::std::println!() };
+    ($($arg:tt)*) => { ::std::println!("{}", output::render(&::std::format!($($arg)*))) };
+}
+macro_rules! say_err {
+    () => { ::std::eprintln!() };
+    ($($arg:tt)*) => { ::std::eprintln!("{}", output::render(&::std::format!($($arg)*))) };
+}
 
 // MCP server imports would go here when mcp-server crate is available
 // For now, we'll implement a simple JSON-RPC interface
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args: Vec<String> = std::env::args().collect();
-
-    // COMMAND MODE: Handle specific commands
-    if args.len() > 1 {
-        match args[1].as_str() {
-            "--validate-prd" => {
-                let prd_path = args.get(2).expect("Usage: --validate-prd <path>");
-                return run_validate_prd(prd_path);
-            }
-            "--analyze-prd" => {
-                let prd_path = args.get(2).expect("Usage: --analyze-prd <path>");
-                let output_path = args.get(3); // Optional output path
-                return run_analyze_prd(prd_path, output_path.map(|s| s.as_str()));
-            }
-            "--template" => {
-                let question = args[2..].join(" ");
-                return run_template_match(&question);
-            }
-            "--blind-spots" => {
-                let context = args[2..].join(" ");
-                return run_blind_spots(&context);
-            }
-            "--pre-work" => {
-                let task = args[2..].join(" ");
-                return run_pre_work(&task);
-            }
-            "--tools" => {
-                // Output MCP tool definitions as JSON
-                let tools = mcp::get_tools();
-                println!("{}", serde_json::to_string_pretty(&tools)?);
-                return Ok(());
-            }
-            "--stats" => {
-                return run_stats();
-            }
-            "--benchmark" => {
-                let subcommand = args.get(2).map(|s| s.as_str()).unwrap_or("all");
-                return run_benchmark_cmd(subcommand, &args[3..]);
-            }
-            "--analyze" => {
-                let subcommand = args.get(2).map(|s| s.as_str()).unwrap_or("coverage");
-                return run_analyze(subcommand);
-            }
-            "--thompson" => {
-                let subcommand = args.get(2).map(|s| s.as_str()).unwrap_or("stats");
-                return run_thompson(subcommand);
+    // `--outcome x`, `--serve` and bare questions predate subcommands
+    let cli = Cli::parse_from(cli::normalize_legacy_args(std::env::args().collect()));
+
+    let mode = cli.output_mode();
+    output::set_plain(output::detect_plain(cli.plain));
+    let Some(command) = cli.command else {
+        // REPL MODE: no command given
+        return run_repl().await;
+    };
+
+    match command {
+        Command::ValidatePrd { path, format, fail_on } => {
+            let fail_on = mcp::FailOn::parse(&fail_on).unwrap_or(mcp::FailOn::Never);
+            let mode = if format == "json" { OutputMode::Json } else { mode };
+            let exit_code = run_validate_prd(&path, mode, fail_on)?;
+            std::process::exit(exit_code);
+        }
+        Command::AnalyzePrd { path, output } => run_analyze_prd(&path, output.as_deref()),
+        Command::Template { question } => run_template_match(&question.join(" "), mode),
+        Command::EvaluateOptions { question, options, domain } => {
+            let question = (!question.is_empty()).then(|| question.join(" "));
+            run_evaluate_options(options::EvaluateOptionsRequest { question, options, domain }, mode)
+        }
+        Command::CostOfDelay { items } => run_cost_of_delay(&items, mode),
+        Command::Estimate { task, estimate, unit, decision } => {
+            let task = task.join(" ");
+            run_estimate(&estimates::EstimateCheckRequest { task, estimate, unit, decision_id: decision }, mode)
+        }
+        Command::EstimateActual { estimate_id, actual } => run_estimate_actual(estimate_id, actual, mode),
+        Command::BlindSpots { context } => run_blind_spots(&context.join(" "), mode),
+        Command::PreWork { task } => run_pre_work(&task.join(" "), mode),
+        Command::Tools => {
+            // Output MCP tool definitions as JSON
+            let tools = mcp::get_tools();
+            std::println!("{}", serde_json::to_string_pretty(&tools)?);
+            Ok(())
+        }
+        Command::Stats => run_stats(mode),
+        Command::Benchmark { suite, args } => run_benchmark_cmd(&suite, &args),
+        Command::Analyze { report, rebuild_coverage } => run_analyze(&report, rebuild_coverage, mode),
+        Command::MarkEvent { label } => run_mark_event(&label.join(" "), mode),
+        Command::Trends { target, csv, daily } => run_trends(target.as_deref(), csv, daily, mode),
+        Command::Thompson { action } => run_thompson(&action, mode),
+        Command::ComputeEmbeddings => run_compute_embeddings(),
+        Command::FetchModel { from, checksums } => run_fetch_model(from.as_deref(), checksums.as_deref(), mode),
+        Command::Outcome(args) => run_outcome_cmd(args, mode),
+        Command::Outcomes { action, target } => run_quarantine(&action, target.as_deref(), mode),
+        Command::Keys { action, name, role } => run_keys(&action, name.as_deref(), &role, mode),
+        Command::EncryptDb { keychain } => run_encrypt_db(keychain, mode),
+        Command::Scrub { dry_run } => run_scrub(dry_run, mode),
+        Command::Prune { dry_run } => run_prune(dry_run, mode),
+        Command::Replay { apply, daily_decay, success_delta, failure_delta, policy, epsilon, seed } => {
+            let config = replay::ReplayConfig { daily_decay, success_delta, failure_delta, policy, epsilon, seed };
+            run_replay(apply, config, mode)
+        }
+        Command::Forget { pattern, regex, redact, dry_run } => run_forget(&pattern, regex, redact, dry_run, mode),
+        Command::OutcomeImport { file, dry_run, atomic } => run_outcome_import(&file, dry_run, atomic, mode),
+        Command::LearningStats => run_learning_stats(mode),
+        Command::Calibration { action, method } => run_calibration(&action, method.as_deref()),
+        Command::Adr { decision_id, output } => run_adr(&decision_id, output.as_deref()),
+        Command::Falsify { decision_id, position, evidence } => {
+            run_falsify(decision_id.as_deref(), position.as_deref(), &evidence, mode)
+        }
+        Command::Risk { decision_id, format, assess, likelihood, impact, mitigation } => {
+            run_risk(&decision_id, &format, assess.as_deref(), likelihood.as_deref(), impact.as_deref(), mitigation.as_deref(), mode)
+        }
+        Command::Checklist { decision_id, done, undo } => run_checklist(&decision_id, done.as_deref(), undo.as_deref(), mode),
+        Command::ExportVault { dir } => run_export_vault(&dir),
+        Command::Dashboard { out_dir } => run_dashboard(&out_dir),
+        Command::ExportGraph { format, output, corpus_only } => run_export_graph(&format, output.as_deref(), corpus_only),
+        Command::LinkDecision { decision_id, branch, pr, repo, forge_repo } => {
+            run_link_decision(&decision_id, branch.as_deref(), pr.as_deref(), &repo, forge_repo.as_deref())
+        }
+        Command::SuggestOutcomes { days } => run_suggest_outcomes(days, mode),
+        Command::SearchDecisions { query, limit } => run_search_decisions(&query.join(" "), limit, mode),
+        Command::HybridSearch { query, weights, min_score, rerank } => {
+            run_hybrid_search(&query.join(" "), weights.as_deref(), min_score, rerank)
+        }
+        Command::Counsel(args) => {
+            let snippets = args
+                .files
+                .iter()
+                .map(|path| {
+                    std::fs::read_to_string(path).map(|content| ContextSnippet {
+                        source: Some(path.to_string()),
+                        content,
+                    })
+                })
+                .collect::<std::io::Result<Vec<_>>>()?;
+            let mut context = CounselContext {
+                domain: args.domain,
+                notes: args.context,
+                team_size: args.team_size,
+                deadline_days: args.deadline_days,
+                depth: match args.depth.as_deref() {
+                    Some("quick") => CounselDepth::Quick,
+                    Some("deep") => CounselDepth::Deep,
+                    Some("auto") => CounselDepth::Auto,
+                    _ => CounselDepth::Standard,
+                },
+                stack: args.stack.iter().map(|t| t.trim().to_string()).collect(),
+                include_tags: args.include_tags,
+                exclude_tags: args.exclude_tags,
+                actor_id: args.actor,
+                exploration: (args.epsilon.is_some() || args.optimism.is_some() || args.temperature.is_some())
+                    .then_some(ExplorationSettings {
+                        epsilon: args.epsilon,
+                        optimism: args.optimism,
+                        temperature: args.temperature,
+                    }),
+                snippets,
+                objectives: args.objectives.iter().map(|o| o.trim().to_string()).collect(),
+                ..Default::default()
+            };
+            if let Some(path) = &args.repo {
+                let signals = minds_mcp::context::repo_scan(std::path::Path::new(path))?;
+                if mode.is_human() {
+                    say!("📂 Repo signals: {}", signals.summary());
+                }
+                signals.apply_to(&mut context);
             }
-            "--compute-embeddings" => {
-                return run_compute_embeddings();
+            run_counsel_cmd(&args.question.join(" "), context, args.checklist, mode)
+        }
+        // HTTP server mode for swarm integration
+        Command::Serve { port, snapshot } => run_http_server(port, snapshot).await,
+        // Output posteriors as JSON for swarm sync
+        Command::SyncPosteriors => run_sync_posteriors(),
+        Command::Dedupe => run_dedupe(),
+        Command::Policy { action, principle, reason } => run_policy(&action, principle.as_deref(), reason.as_deref(), mode),
+        Command::Edges { action, from, relation, to, note } => {
+            run_edges(&action, from.as_deref(), relation.as_deref(), to.as_deref(), note.as_deref(), mode)
+        }
+        Command::Pack { action, target, require_signed } => {
+            run_pack(&action, target.as_deref(), require_signed, mode).await
+        }
+        // One-shot counsel (legacy)
+        Command::Ask { question } => run_oneshot(&question.join(" ")),
+        Command::Tui => {
+            let data_dir = get_data_dir()?;
+            std::fs::create_dir_all(&data_dir)?;
+            let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+            sync_policy_config(&conn, &data_dir)?;
+            let provenance = Provenance::init(&data_dir.join("agent.key"))?;
+            Ok(tui::run(&conn, &provenance)?)
+        }
+        Command::Repl => run_repl().await,
+        Command::Daemon { action, once, config } => run_daemon(&action, once, config, mode),
+        Command::Worker { once, poll_ms, id } => run_worker(once, poll_ms, id, mode),
+        Command::Completions { shell } => {
+            cli::print_completions(shell);
+            Ok(())
+        }
+    }
+}
+
+/// Interactive REPL
+async fn run_repl() -> Result<()> {
+    tracing_subscriber::fmt::init();
+    let data_dir = get_data_dir()?;
+    std::fs::create_dir_all(&data_dir)?;
+    let db_path = data_dir.join("wisdom.db");
+    let conn = db::init_db(&db_path)?;
+    sync_policy_config(&conn, &data_dir)?;
+    tracing::info!("Database initialized at {:?}", db_path);
+    let key_path = data_dir.join("agent.key");
+    let provenance = Provenance::init(&key_path)?;
+    tracing::info!(
+        "Provenance initialized, pubkey: {}",
+        provenance.public_key_hex()
+    );
+    run_cli_mode(&conn, &provenance).await
+}
+
+/// Maintenance daemon: `run` the schedule from config.toml, or show `status`
+fn run_daemon(action: &str, once: bool, config_path: Option<String>, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    std::fs::create_dir_all(&data_dir)?;
+    let config_path = config_path.map(PathBuf::from).unwrap_or_else(|| data_dir.join("config.toml"));
+    let config = daemon::DaemonConfig::load(&config_path)?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+
+    if action == "status" {
+        let states = daemon::status(&conn, &config)?;
+        if output::machine(mode, &states)? {
+            return Ok(());
+        }
+
+        say!("\n┌─────────────────────────────────────────────────────────────┐");
+        say!("│ 🕰️ MAINTENANCE DAEMON STATUS                                │");
+        say!("└─────────────────────────────────────────────────────────────┘\n");
+        say!("Config: {:?}\n", config_path);
+        for s in &states {
+            let interval = s
+                .interval_secs
+                .map(|secs| format!("every {}", format_secs(secs)))
+                .unwrap_or_else(|| "disabled".to_string());
+            say!("{:12} {:14} last: {}  next: {}",
+                s.job,
+                interval,
+                match (&s.last_run_at, &s.last_status) {
+                    (Some(at), Some(status)) => format!("{} ({})", at, status),
+                    _ => "never".to_string(),
+                },
+                match (&s.interval_secs, &s.next_run_at) {
+                    (None, _) => "-",
+                    (Some(_), Some(next)) => next.as_str(),
+                    (Some(_), None) => "on start",
+                }
+            );
+            if let Some(e) = &s.last_error {
+                say!("             ⚠️  {}", e.lines().next().unwrap_or_default());
             }
-            "--outcome" => {
-                return run_outcome_cmd(&args[2..]);
+        }
+        return Ok(());
+    }
+
+    tracing_subscriber::fmt::init();
+    tracing::info!("Daemon started, config {:?}", config_path);
+    daemon::run_daemon(&conn, &config, once, |job| {
+        match job {
+            "embeddings" => run_compute_embeddings()?,
+            "trends" => {
+                stats::timeseries::record_snapshot(&conn, chrono::Utc::now())?;
             }
-            "--learning-stats" => {
-                return run_learning_stats();
+            "prune" => {
+                retention::prune(&conn, &retention::RetentionConfig::beside(&conn)?, chrono::Utc::now(), false)?;
             }
-            "--hybrid-search" => {
-                let query = args[2..].join(" ");
-                return run_hybrid_search(&query);
+            "notify" | "digest" => {
+                tokio::task::block_in_place(|| {
+                    tokio::runtime::Handle::current().block_on(notifier::run_job(&conn, job, chrono::Utc::now()))
+                })?;
             }
-            "counsel" => {
-                // counsel <question> [--json] [--domain=X]
-                let json_output = args.iter().any(|a| a == "--json");
-                let domain = args
-                    .iter()
-                    .find(|a| a.starts_with("--domain="))
-                    .map(|a| a.strip_prefix("--domain=").unwrap().to_string());
-                let question: String = args[2..]
-                    .iter()
-                    .filter(|a| !a.starts_with("--"))
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(" ");
-                return run_counsel_cmd(&question, domain.as_deref(), json_output);
-            }
-            "--serve" => {
-                // HTTP server mode for swarm integration
-                let port: u16 = args
-                    .iter()
-                    .find(|a| a.starts_with("--port="))
-                    .and_then(|a| a.strip_prefix("--port=").and_then(|p| p.parse().ok()))
-                    .unwrap_or(3100);
-                return run_http_server(port).await;
-            }
-            "--sync-posteriors" => {
-                // Output posteriors as JSON for swarm sync
-                return run_sync_posteriors();
-            }
-            s if !s.starts_with("--") => {
-                // One-shot counsel (legacy)
-                let question = args[1..].join(" ");
-                return run_oneshot(&question);
-            }
-            _ => {}
-        }
-    }
-
-    // REPL MODE: Only if explicitly requested or no args
-    if args.contains(&"--repl".to_string()) || args.len() == 1 {
-        tracing_subscriber::fmt::init();
-        let data_dir = get_data_dir()?;
-        std::fs::create_dir_all(&data_dir)?;
-        let db_path = data_dir.join("wisdom.db");
-        let conn = db::init_db(&db_path)?;
-        tracing::info!("Database initialized at {:?}", db_path);
-        let key_path = data_dir.join("agent.key");
-        let provenance = Provenance::init(&key_path)?;
-        tracing::info!(
-            "Provenance initialized, pubkey: {}",
-            provenance.public_key_hex()
-        );
-        run_cli_mode(&conn, &provenance).await?;
+            thompson => run_thompson(thompson, OutputMode::Quiet)?,
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// "3600" → "1h", "604800" → "7d"
+fn format_secs(secs: i64) -> String {
+    match secs {
+        s if s % 86_400 == 0 => format!("{}d", s / 86_400),
+        s if s % 3_600 == 0 => format!("{}h", s / 3_600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
+
+/// Background worker: answers counsel jobs queued by submit_counsel_async
+fn run_worker(once: bool, poll_ms: u64, id: Option<String>, mode: OutputMode) -> Result<()> {
+    tracing_subscriber::fmt::init();
+    let data_dir = get_data_dir()?;
+    std::fs::create_dir_all(&data_dir)?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    sync_policy_config(&conn, &data_dir)?;
+    let provenance = Provenance::init(&data_dir.join("agent.key"))?;
+
+    // Stable default id so a restarted worker reclaims its own stale jobs
+    let worker_id = id.unwrap_or_else(|| format!("worker-{}", std::process::id()));
+    if mode.is_human() {
+        say!("👷 {} polling counsel_jobs every {}ms", worker_id, poll_ms);
     }
 
+    let processed = jobs::run_worker(
+        &conn,
+        &provenance,
+        &worker_id,
+        std::time::Duration::from_millis(poll_ms),
+        once,
+    )?;
+    if mode.is_human() {
+        say!("✅ {} processed {} jobs", worker_id, processed);
+    }
     Ok(())
 }
 
@@ -143,13 +320,13 @@ fn run_analyze_prd(prd_path: &str, output_path: Option<&str>) -> Result<()> {
     let metadata = prd::analyze_prd(&mut prd_doc);
     prd_doc.minds_metadata = Some(metadata.clone());
 
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧠 100MINDS PRD ANALYSIS                                    │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧠 100MINDS PRD ANALYSIS                                    │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
-    println!("PRD: {} ({})", prd_doc.title, prd_doc.id);
-    println!("Stories: {}", prd_doc.stories.len());
-    println!();
+    say!("PRD: {} ({})", prd_doc.title, prd_doc.id);
+    say!("Stories: {}", prd_doc.stories.len());
+    say!();
 
     // Score
     let score_bar = "█".repeat((metadata.validation_score / 10.0) as usize);
@@ -159,104 +336,127 @@ fn run_analyze_prd(prd_path: &str, output_path: Option<&str>) -> Result<()> {
     } else {
         "⚠️ NEEDS WORK"
     };
-    println!(
+    say!(
         "Score: [{score_bar}{empty_bar}] {:.0}/100  {status}",
         metadata.validation_score
     );
-    println!();
+    say!();
 
     // Principles applied
     if !metadata.principles_applied.is_empty() {
-        println!(
+        say!(
             "📚 Principles Applied: {}",
             metadata.principles_applied.join(", ")
         );
-        println!();
+        say!();
     }
 
     // Warnings
     if !metadata.warnings.is_empty() {
-        println!("⚠️ WARNINGS:");
+        say!("⚠️ WARNINGS:");
         for w in &metadata.warnings {
-            println!("   • {}", w);
+            say!("   • {}", w);
         }
-        println!();
+        say!();
     }
 
     // Split recommendation
     if let Some(split) = &metadata.split_recommendation {
         if split.should_split {
-            println!("✂️ SPLIT RECOMMENDATION:");
-            println!("   {}", split.reason);
+            say!("✂️ SPLIT RECOMMENDATION:");
+            say!("   {}", split.reason);
             for suggested in &split.suggested_prds {
-                println!("   📁 {}", suggested.title);
-                println!("      Stories: {}", suggested.story_ids.join(", "));
-                println!("      Reason: {}", suggested.rationale);
+                say!("   📁 {}", suggested.title);
+                say!("      Stories: {}", suggested.story_ids.join(", "));
+                say!("      Reason: {}", suggested.rationale);
             }
-            println!();
+            say!();
         } else {
-            println!("✅ PRD is well-scoped (no split needed)");
-            println!();
+            say!("✅ PRD is well-scoped (no split needed)");
+            say!();
         }
     }
 
     // Scope analysis
-    println!("📋 SCOPE ANALYSIS:");
-    println!("   In Scope ({}):", metadata.scope_analysis.in_scope.len());
+    say!("📋 SCOPE ANALYSIS:");
+    say!("   In Scope ({}):", metadata.scope_analysis.in_scope.len());
     for item in &metadata.scope_analysis.in_scope {
-        println!("      ✓ {}", item);
+        say!("      ✓ {}", item);
     }
 
     if !metadata.scope_analysis.out_of_scope.is_empty() {
-        println!(
+        say!(
             "   Out of Scope ({}):",
             metadata.scope_analysis.out_of_scope.len()
         );
         for item in &metadata.scope_analysis.out_of_scope {
-            println!("      ✗ {}", item);
+            say!("      ✗ {}", item);
         }
     }
 
     if !metadata.scope_analysis.deferred.is_empty() {
-        println!("   Deferred ({}):", metadata.scope_analysis.deferred.len());
+        say!("   Deferred ({}):", metadata.scope_analysis.deferred.len());
         for item in &metadata.scope_analysis.deferred {
-            println!(
+            say!(
                 "      ⏸ {} → {}",
                 item.item,
                 item.suggested_prd.as_deref().unwrap_or("later")
             );
-            println!("         Reason: {}", item.reason);
+            say!("         Reason: {}", item.reason);
         }
     }
-    println!();
+    say!();
 
     // Output enhanced PRD JSON
     if let Some(out_path) = output_path {
         let json = prd::to_json(&prd_doc)?;
         std::fs::write(out_path, &json)?;
-        println!("📄 Enhanced PRD written to: {}", out_path);
+        say!("📄 Enhanced PRD written to: {}", out_path);
     } else {
-        println!("─────────────────────────────────────────────────────────────");
-        println!("📄 ENHANCED PRD JSON (use --analyze-prd <input> <output> to save):");
-        println!("─────────────────────────────────────────────────────────────");
-        println!("{}", prd::to_json(&prd_doc)?);
+        say!("─────────────────────────────────────────────────────────────");
+        say!("📄 ENHANCED PRD JSON (use --analyze-prd <input> <output> to save):");
+        say!("─────────────────────────────────────────────────────────────");
+        std::println!("{}", prd::to_json(&prd_doc)?);
     }
 
     Ok(())
 }
 
 /// Validate a PRD against 100minds principles
-fn run_validate_prd(prd_path: &str) -> Result<()> {
-    let prd_content = std::fs::read_to_string(prd_path)?;
+/// Validate a PRD; returns the process exit code
+/// (0 passed, 1 failed the `--fail-on` gate, 2 unreadable PRD)
+fn run_validate_prd(prd_path: &str, mode: OutputMode, fail_on: mcp::FailOn) -> Result<i32> {
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
 
-    let result = mcp::validate_prd(&conn, &prd_content)?;
+    let report = std::fs::read_to_string(prd_path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| Ok(mcp::validate_prd_report(&conn, &content, fail_on)?));
+    let result = match report {
+        Ok(r) => r,
+        Err(e) => {
+            if mode.is_json() {
+                let error = serde_json::json!({
+                    "schema_version": mcp::PRD_REPORT_SCHEMA_VERSION,
+                    "passed": false,
+                    "error": e.to_string(),
+                });
+                std::println!("{}", serde_json::to_string_pretty(&error)?);
+            } else {
+                say_err!("❌ Could not validate {}: {}", prd_path, e);
+            }
+            return Ok(2);
+        }
+    };
+
+    if output::machine(mode, &result)? {
+        return Ok(result.exit_code());
+    }
 
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧠 100MINDS PRD VALIDATION                                  │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧠 100MINDS PRD VALIDATION                                  │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
     // Score with visual bar
     let score_bar = "█".repeat((result.score / 10.0) as usize);
@@ -267,148 +467,279 @@ fn run_validate_prd(prd_path: &str) -> Result<()> {
         "❌ FAILED"
     };
 
-    println!(
+    say!(
         "Score: [{score_bar}{empty_bar}] {:.0}/100  {status}",
         result.score
     );
-    println!();
+    say!();
 
     // Warnings by severity
     let errors: Vec<_> = result
-        .warnings
+        .findings
         .iter()
         .filter(|w| w.severity == "error")
         .collect();
     let warnings: Vec<_> = result
-        .warnings
+        .findings
         .iter()
         .filter(|w| w.severity == "warning")
         .collect();
     let infos: Vec<_> = result
-        .warnings
+        .findings
         .iter()
         .filter(|w| w.severity == "info")
         .collect();
 
     if !errors.is_empty() {
-        println!("🔴 ERRORS ({}):", errors.len());
+        say!("🔴 ERRORS ({}):", errors.len());
         for w in errors {
-            println!("   • {} ({}): {}", w.principle, w.thinker, w.message);
+            say!("   • {} ({}): {}", w.principle, w.thinker, w.message);
         }
-        println!();
+        say!();
     }
 
     if !warnings.is_empty() {
-        println!("🟡 WARNINGS ({}):", warnings.len());
+        say!("🟡 WARNINGS ({}):", warnings.len());
         for w in warnings {
-            println!("   • {} ({}): {}", w.principle, w.thinker, w.message);
+            say!("   • {} ({}): {}", w.principle, w.thinker, w.message);
         }
-        println!();
+        say!();
     }
 
     if !infos.is_empty() {
-        println!("🔵 INFO ({}):", infos.len());
+        say!("🔵 INFO ({}):", infos.len());
         for w in infos {
-            println!("   • {} ({}): {}", w.principle, w.thinker, w.message);
+            say!("   • {} ({}): {}", w.principle, w.thinker, w.message);
         }
-        println!();
+        say!();
     }
 
     // Suggestions
     if !result.suggestions.is_empty() {
-        println!("💡 SUGGESTIONS:");
+        say!("💡 SUGGESTIONS:");
         for s in &result.suggestions {
-            println!("   • {} ({}): {}", s.principle, s.thinker, s.suggestion);
+            say!("   • {} ({}): {}", s.principle, s.thinker, s.suggestion);
         }
-        println!();
+        say!();
     }
 
     // Blind spots
     if !result.blind_spots_to_check.is_empty() {
-        println!("👁️ BLIND SPOTS TO CHECK:");
+        say!("👁️ BLIND SPOTS TO CHECK:");
         for bs in &result.blind_spots_to_check {
-            println!("   • {}", bs);
+            say!("   • {}", bs);
         }
-        println!();
+        say!();
     }
 
     // Principles applied
-    println!(
+    say!(
         "📚 Principles Applied: {}",
         result.principles_applied.join(", ")
     );
 
-    Ok(())
+    if result.fail_on != mcp::FailOn::Never && !result.passed {
+        say!("\n🚫 Gate failed (--fail-on {})", result.fail_on.name());
+    }
+
+    Ok(result.exit_code())
 }
 
 /// Match decision to templates
-fn run_template_match(question: &str) -> Result<()> {
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧠 100MINDS DECISION TEMPLATES                              │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+/// Score options against the principles relevant to them
+fn run_evaluate_options(request: options::EvaluateOptionsRequest, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    let provenance = Provenance::init(&data_dir.join("agent.key"))?;
+    let evaluation = CounselEngine::new(&conn, &provenance).evaluate_options(&request)?;
+    if output::machine(mode, &evaluation)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ ⚖️  OPTION SCORES                                            │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    if let Some(question) = &evaluation.question {
+        say!("{}\n", question);
+    }
+    for (i, o) in evaluation.options.iter().enumerate() {
+        say!("{}. {:+.2}  {}", i + 1, o.score, o.option);
+        if !o.supported_by.is_empty() {
+            say!("      for:     {}", o.supported_by.join(", "));
+        }
+        if !o.opposed_by.is_empty() {
+            say!("      against: {}", o.opposed_by.join(", "));
+        }
+    }
+    if evaluation.matrix.is_empty() {
+        say!("\nNo principle speaks to these options; describe them in more detail.");
+        return Ok(());
+    }
+
+    say!("\nMatrix (columns in the order given):");
+    for row in &evaluation.matrix {
+        let cells: Vec<String> = row.alignments.iter().map(|a| format!("{:+.1}", a)).collect();
+        say!("   {:36} {}", truncate_str(&row.name, 36), cells.join("  "));
+    }
+    let tilt = &evaluation.tilt;
+    say!("\nTilt: {} toward \"{}\" (margin {:.2})", tilt.strength, tilt.option, tilt.margin);
+    if !tilt.drivers.is_empty() {
+        let drivers: Vec<&str> = tilt.drivers.iter().map(|d| d.name.as_str()).collect();
+        say!("   Driven by: {}", drivers.join(", "));
+    }
+    Ok(())
+}
+
+/// Sequence candidates by CD3 and price the sequence
+fn run_cost_of_delay(items: &[cost_of_delay::DelayItem], mode: OutputMode) -> Result<()> {
+    let worksheet = cost_of_delay::worksheet(items)?;
+    if output::machine(mode, &worksheet)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 💸 COST OF DELAY                                            │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    print_cost_of_delay_result(&worksheet);
+    Ok(())
+}
+
+fn print_cost_of_delay_result(worksheet: &cost_of_delay::Worksheet) {
+    let Some(result) = &worksheet.result else {
+        say!("   {}", wrap_text(&worksheet.guidance, 60, "   "));
+        return;
+    };
+    say!("   #  {:24} {:>10} {:>8} {:>6} {:>12}", "item", "CoD/week", "CD3", "ships", "opp. cost");
+    for item in &result.sequence {
+        say!(
+            "   {}. {:24} {:>10.0} {:>8.1} {:>6} {:>12.0}",
+            item.rank,
+            truncate_str(&item.name, 24),
+            item.cost_of_delay,
+            item.cd3,
+            format!("wk {}", item.ships_week),
+            item.opportunity_cost
+        );
+    }
+    say!("\n   {}", wrap_text(&result.recommendation, 60, "   "));
+}
+
+/// Check an estimate against the reference class of similar past tasks
+fn run_estimate(request: &estimates::EstimateCheckRequest, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let mut engine = embeddings::shared_engine(&conn).map(|m| m.lock().unwrap_or_else(|e| e.into_inner()));
+    let check = estimates::check(&conn, engine.as_deref_mut(), request)?;
+    if output::machine(mode, &check)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ ⏱️  ESTIMATE SANITY CHECK                                    │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("{}: {} {} ({})\n", check.task, check.estimate, check.unit, check.verdict);
+    say!(
+        "Calibrated range: {:.1} - {:.1} {} (likely {:.1})",
+        check.range.low, check.range.high, check.unit, check.range.likely
+    );
+    let m = &check.multipliers;
+    say!("   x{:.2} / x{:.2} / x{:.2} of the estimate\n", m.low, m.likely, m.high);
+    if check.basis == "prior" {
+        say!("Fewer than {} similar past tasks with actuals: planning-fallacy prior", estimates::MIN_REFERENCES);
+    } else {
+        say!("Reference class ({} similar past tasks, by {}):", check.references.len(), check.method);
+        for r in &check.references {
+            say!("   x{:.2}  {} ({} → {} {})", r.ratio, truncate_str(&r.task, 50), r.estimate, r.actual, r.unit);
+        }
+    }
+    for a in &check.adjustments {
+        say!("\n📖 {} ({}): {}", a.principle, a.thinker, a.reason);
+    }
+    say!("\nRecord the actual later: 100minds estimate-actual {} <actual>", check.estimate_id);
+    Ok(())
+}
+
+fn run_estimate_actual(estimate_id: i64, actual: f64, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let recorded = estimates::record_actual(&conn, estimate_id, actual)?;
+    if !output::machine(mode, &recorded)? {
+        say!(
+            "✅ {}: {} → {} {} (x{:.2})",
+            recorded.task, recorded.estimate, recorded.actual, recorded.unit, recorded.ratio
+        );
+    }
+    Ok(())
+}
 
+fn run_template_match(question: &str, mode: OutputMode) -> Result<()> {
     let matches = mcp::get_matching_templates(question);
+    if output::machine(mode, &matches)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧠 100MINDS DECISION TEMPLATES                              │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
     if matches.is_empty() {
-        println!("No matching templates found for: {}", question);
-        println!("\nAvailable templates:");
+        say!("No matching templates found for: {}", question);
+        say!("\nAvailable templates:");
         for t in templates::get_templates() {
-            println!("  • {} - {}", t.id, t.description);
+            say!("  • {} - {}", t.id, t.description);
         }
         return Ok(());
     }
 
     for (i, m) in matches.iter().enumerate() {
-        println!(
+        say!(
             "{}. {} (match score: {:.1})",
             i + 1,
             m.template.name,
             m.match_score
         );
-        println!("   {}", m.template.description);
-        println!();
+        say!("   {}", m.template.description);
+        say!();
 
         // Print decision tree
-        println!("   DECISION TREE:");
+        say!("   DECISION TREE:");
         print_decision_tree_node(&m.template.tree, "   ");
-        println!();
+        say!();
 
         // Print synergies
         if !m.template.synergies.is_empty() {
-            println!("   SYNERGIES:");
+            say!("   SYNERGIES:");
             for s in &m.template.synergies {
-                println!(
+                say!(
                     "   • {} + {} = {}",
                     s.principles.join(" + "),
                     s.thinkers.join(", "),
                     s.combined_power
                 );
             }
-            println!();
+            say!();
         }
 
         // Print blind spots
         if !m.template.blind_spots.is_empty() {
-            println!("   BLIND SPOTS:");
+            say!("   BLIND SPOTS:");
             for bs in &m.template.blind_spots {
-                println!(
+                say!(
                     "   • [{:?}] {}: {}",
                     bs.severity, bs.name, bs.check_question
                 );
             }
-            println!();
+            say!();
         }
 
         // Print anti-patterns
         if !m.template.anti_patterns.is_empty() {
-            println!("   ANTI-PATTERNS TO AVOID:");
+            say!("   ANTI-PATTERNS TO AVOID:");
             for ap in &m.template.anti_patterns {
-                println!(
+                say!(
                     "   • {} ({}): {}",
                     ap.name, ap.source_thinker, ap.description
                 );
             }
-            println!();
+            say!();
         }
     }
 
@@ -416,9 +747,9 @@ fn run_template_match(question: &str) -> Result<()> {
 }
 
 fn print_decision_tree_node(tree: &templates::DecisionTree, prefix: &str) {
-    println!("{}❓ {}", prefix, tree.question);
+    say!("{}❓ {}", prefix, tree.question);
     if let Some(help) = &tree.help_text {
-        println!("{}   ({})", prefix, help);
+        say!("{}   ({})", prefix, help);
     }
 
     for (i, opt) in tree.options.iter().enumerate() {
@@ -427,11 +758,11 @@ fn print_decision_tree_node(tree: &templates::DecisionTree, prefix: &str) {
         } else {
             "├"
         };
-        println!("{}{}─ {} - {}", prefix, branch, opt.label, opt.description);
+        say!("{}{}─ {} - {}", prefix, branch, opt.label, opt.description);
 
         if let Some(rec) = &opt.recommendation {
             for line in wrap_lines(rec, 60) {
-                println!("{}   ➜ {}", prefix, line);
+                say!("{}   ➜ {}", prefix, line);
             }
         }
 
@@ -442,15 +773,18 @@ fn print_decision_tree_node(tree: &templates::DecisionTree, prefix: &str) {
 }
 
 /// Check blind spots for a decision
-fn run_blind_spots(context: &str) -> Result<()> {
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧠 100MINDS BLIND SPOT ANALYSIS                             │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
-
+fn run_blind_spots(context: &str, mode: OutputMode) -> Result<()> {
     let analysis = mcp::check_blind_spots(context, None);
+    if output::machine(mode, &analysis)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧠 100MINDS BLIND SPOT ANALYSIS                             │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
-    println!("Context: {}", context);
-    println!("Critical blind spots: {}\n", analysis.critical_count);
+    say!("Context: {}", context);
+    say!("Critical blind spots: {}\n", analysis.critical_count);
 
     for bs in &analysis.blind_spots {
         let icon = match bs.severity.as_str() {
@@ -460,111 +794,125 @@ fn run_blind_spots(context: &str) -> Result<()> {
             _ => "🔵",
         };
 
-        println!("{} [{}] {}", icon, bs.severity, bs.name);
-        println!("   {}", bs.description);
-        println!("   ❓ CHECK: {}", bs.check_question);
-        println!("   (from: {})", bs.source_template);
-        println!();
+        say!("{} [{}] {}", icon, bs.severity, bs.name);
+        say!("   {}", bs.description);
+        say!("   ❓ CHECK: {}", bs.check_question);
+        say!("   (from: {})", bs.source_template);
+        say!();
     }
 
     Ok(())
 }
 
 /// Get pre-work context for a task
-fn run_pre_work(task: &str) -> Result<()> {
+fn run_pre_work(task: &str, mode: OutputMode) -> Result<()> {
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
 
     let context = mcp::get_pre_work_context(&conn, task, task, Some("feature"))?;
+    if output::machine(mode, &context)? {
+        return Ok(());
+    }
 
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧠 100MINDS PRE-WORK CONTEXT                                │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧠 100MINDS PRE-WORK CONTEXT                                │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
-    println!("Task: {}", context.task_title);
-    println!("Type: {}\n", context.task_type);
+    say!("Task: {}", context.task_title);
+    say!("Type: {}\n", context.task_type);
 
     if !context.relevant_principles.is_empty() {
-        println!("📚 RELEVANT PRINCIPLES:");
+        say!("📚 RELEVANT PRINCIPLES:");
         for p in &context.relevant_principles {
-            println!("   • {} ({})", p.name, p.thinker);
-            println!("     {}", truncate_str(&p.description, 60));
-            println!("     ⚡ {}", p.action);
-            println!();
+            say!("   • {} ({})", p.name, p.thinker);
+            say!("     {}", truncate_str(&p.description, 60));
+            say!("     ⚡ {}", p.action);
+            say!();
         }
     }
 
-    println!("👁️ BLIND SPOTS:");
+    say!("👁️ BLIND SPOTS:");
     for bs in &context.blind_spots {
-        println!("   • {}", bs);
+        say!("   • {}", bs);
     }
-    println!();
+    say!();
 
-    println!("🚫 ANTI-PATTERNS TO AVOID:");
+    say!("🚫 ANTI-PATTERNS TO AVOID:");
     for ap in &context.anti_patterns_to_avoid {
-        println!("   • {}", ap);
+        say!("   • {}", ap);
     }
-    println!();
+    say!();
 
-    println!("❓ KEY QUESTIONS:");
+    say!("❓ KEY QUESTIONS:");
     for q in &context.key_questions {
-        println!("   • {}", q);
+        say!("   • {}", q);
     }
 
     Ok(())
 }
 
 /// Show wisdom statistics
-fn run_stats() -> Result<()> {
+fn run_stats(mode: OutputMode) -> Result<()> {
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
 
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧠 100MINDS WISDOM STATISTICS                               │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
-
-    // Count thinkers
-    let thinker_count: i64 =
-        conn.query_row("SELECT COUNT(*) FROM thinkers", [], |row| row.get(0))?;
-    println!("Thinkers: {}", thinker_count);
-
-    // Count principles
-    let principle_count: i64 =
-        conn.query_row("SELECT COUNT(*) FROM principles", [], |row| row.get(0))?;
-    println!("Principles: {}", principle_count);
-
-    // Count decisions
-    let decision_count: i64 =
-        conn.query_row("SELECT COUNT(*) FROM decisions", [], |row| row.get(0))?;
-    println!("Decisions recorded: {}", decision_count);
-
-    // Count outcomes
-    let outcome_count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM decisions WHERE outcome_success IS NOT NULL",
-        [],
-        |row| row.get(0),
-    )?;
-    println!("Outcomes recorded: {}", outcome_count);
-
-    // Templates
+    let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, i64>(0));
+    let thinker_count = count("SELECT COUNT(*) FROM thinkers")?;
+    let principle_count = count("SELECT COUNT(*) FROM principles")?;
+    let decision_count = count("SELECT COUNT(*) FROM decisions")?;
+    let outcome_count = count("SELECT COUNT(*) FROM decisions WHERE outcome_success IS NOT NULL")?;
     let template_count = templates::get_templates().len();
-    println!("Decision templates: {}", template_count);
+    let capabilities = embeddings::capabilities(&conn);
+    let pending_reviews = approval::pending(&conn, 100)?;
 
     // Top principles by confidence
-    println!("\n📈 TOP PRINCIPLES BY CONFIDENCE:");
     let mut stmt = conn.prepare(
         "SELECT name, learned_confidence FROM principles ORDER BY learned_confidence DESC LIMIT 5",
     )?;
-    let rows = stmt.query_map([], |row| {
-        Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
-    })?;
+    let top: Vec<(String, f64)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let stats = serde_json::json!({
+        "thinkers": thinker_count,
+        "principles": principle_count,
+        "decisions": decision_count,
+        "outcomes": outcome_count,
+        "templates": template_count,
+        "capabilities": capabilities,
+        "pending_reviews": pending_reviews,
+        "top_principles": top
+            .iter()
+            .map(|(name, conf)| serde_json::json!({ "name": name, "learned_confidence": conf }))
+            .collect::<Vec<_>>(),
+    });
+    if output::machine(mode, &stats)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧠 100MINDS WISDOM STATISTICS                               │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+
+    say!("Thinkers: {}", thinker_count);
+    say!("Principles: {}", principle_count);
+    say!("Decisions recorded: {}", decision_count);
+    say!("Outcomes recorded: {}", outcome_count);
+    say!("Decision templates: {}", template_count);
+    match &capabilities.degraded_reason {
+        None => say!("Search: hybrid (semantic + BM25)"),
+        Some(reason) => say!("Search: keyword only ({})", reason),
+    }
+    if !pending_reviews.is_empty() {
+        say!("Escalations awaiting review: {}", pending_reviews.len());
+    }
 
-    for row in rows {
-        let (name, conf) = row?;
+    say!("\n📈 TOP PRINCIPLES BY CONFIDENCE:");
+    for (name, conf) in top {
         let bar = "█".repeat((conf * 10.0) as usize);
-        println!("   [{:.<10}] {:.0}% {}", bar, conf * 100.0, name);
+        say!("   [{:.<10}] {:.0}% {}", bar, conf * 100.0, name);
     }
 
     Ok(())
@@ -578,6 +926,7 @@ fn run_oneshot(question: &str) -> Result<()> {
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
+    sync_policy_config(&conn, &data_dir)?;
     let key_path = data_dir.join("agent.key");
     let provenance = Provenance::init(&key_path)?;
 
@@ -590,7 +939,7 @@ fn run_oneshot(question: &str) -> Result<()> {
 
     match engine.counsel(&request) {
         Ok(response) => print_decision_tree(&response, start.elapsed()),
-        Err(e) => eprintln!("Error: {}", e),
+        Err(e) => say_err!("Error: {}", e),
     }
 
     Ok(())
@@ -601,41 +950,50 @@ fn run_oneshot(question: &str) -> Result<()> {
 // ============================================================================
 
 /// Counsel command with JSON output support for swarm integration
-fn run_counsel_cmd(question: &str, domain: Option<&str>, json_output: bool) -> Result<()> {
+fn run_counsel_cmd(question: &str, context: CounselContext, with_checklist: bool, mode: OutputMode) -> Result<()> {
     use std::time::Instant;
     let start = Instant::now();
 
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
+    sync_policy_config(&conn, &data_dir)?;
     let key_path = data_dir.join("agent.key");
     let provenance = Provenance::init(&key_path)?;
 
     let engine = CounselEngine::new(&conn, &provenance);
     let request = CounselRequest {
         question: question.to_string(),
-        context: CounselContext {
-            domain: domain.map(String::from),
-            ..Default::default()
-        },
+        context,
         decision_id: None,  // Auto-generate UUID
     };
 
     match engine.counsel(&request) {
-        Ok(response) => {
-            if json_output {
-                // JSON output for swarm integration
-                println!("{}", serde_json::to_string_pretty(&response)?);
-            } else {
-                print_decision_tree(&response, start.elapsed());
+        Ok(response) if with_checklist => {
+            let checklist = checklist::generate(&conn, &response.decision_id)?;
+            match mode {
+                OutputMode::Json => std::println!("{}", serde_json::to_string_pretty(&checklist)?),
+                OutputMode::Quiet => std::println!("{}", response.decision_id),
+                OutputMode::Human => {
+                    print_decision_tree(&response, start.elapsed());
+                    say!();
+                    std::print!("{}", checklist::render_markdown(&checklist));
+                }
             }
         }
+        Ok(response) => match mode {
+            // JSON output for swarm integration
+            OutputMode::Json => std::println!("{}", serde_json::to_string_pretty(&response)?),
+            // Just the id, for `--outcome` later
+            OutputMode::Quiet => std::println!("{}", response.decision_id),
+            OutputMode::Human => print_decision_tree(&response, start.elapsed()),
+        },
         Err(e) => {
-            if json_output {
+            if mode.is_json() {
                 let error = serde_json::json!({"error": e.to_string()});
-                println!("{}", serde_json::to_string_pretty(&error)?);
+                std::println!("{}", serde_json::to_string_pretty(&error)?);
             } else {
-                eprintln!("Error: {}", e);
+                say_err!("Error: {}", e);
             }
         }
     }
@@ -653,33 +1011,49 @@ fn run_sync_posteriors() -> Result<()> {
     eval::thompson::init_thompson_schema(&conn)?;
 
     let response = outcome::sync_posteriors(&conn, None, None)?;
-    println!("{}", serde_json::to_string_pretty(&response)?);
+    std::println!("{}", serde_json::to_string_pretty(&response)?);
 
     Ok(())
 }
 
 /// HTTP server mode for swarm integration
-async fn run_http_server(port: u16) -> Result<()> {
+async fn run_http_server(port: u16, snapshot: bool) -> Result<()> {
     use std::net::TcpListener;
 
-    eprintln!("🚀 100minds MCP Server starting on port {}...", port);
+    say_err!("🚀 100minds MCP Server starting on port {}...", port);
 
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let key_path = data_dir.join("agent.key");
+    sync_policy_config(&db::init_db(&db_path)?, &data_dir)?;
+
+    let snapshot = if snapshot {
+        let shared = SharedSnapshot::load(&db::init_db(&db_path)?)?;
+        say_err!(
+            "📸 Corpus snapshot: {} principles in memory (POST /snapshot/refresh to reload)",
+            shared.current().len()
+        );
+        Some(Arc::new(shared))
+    } else {
+        None
+    };
 
     let listener = TcpListener::bind(format!("127.0.0.1:{}", port))?;
-    eprintln!("✅ Listening on http://localhost:{}/mcp", port);
+    say_err!("✅ Listening on http://localhost:{}/mcp", port);
+    say_err!("🪝 Webhooks: /webhooks/github, /webhooks/gitlab (link decisions with --link-decision)");
+    say_err!("📺 Live dashboard: http://localhost:{}/dashboard (admin; ?key=<key> once keys exist)", port);
+    say_err!("📰 Activity feed: http://localhost:{}/feed.atom", port);
 
     for stream in listener.incoming() {
         let stream = stream?;
         let db_path = db_path.clone();
         let key_path = key_path.clone();
+        let snapshot = snapshot.clone();
 
         // Handle each connection
         std::thread::spawn(move || {
-            if let Err(e) = handle_http_request(stream, &db_path, &key_path) {
-                eprintln!("Request error: {}", e);
+            if let Err(e) = handle_http_request(stream, &db_path, &key_path, snapshot.as_deref()) {
+                say_err!("Request error: {}", e);
             }
         });
     }
@@ -691,6 +1065,7 @@ fn handle_http_request(
     mut stream: std::net::TcpStream,
     db_path: &std::path::Path,
     key_path: &std::path::Path,
+    snapshot: Option<&SharedSnapshot>,
 ) -> Result<()> {
     use std::io::{BufRead, BufReader, Write};
 
@@ -700,6 +1075,7 @@ fn handle_http_request(
 
     // Read headers
     let mut content_length: usize = 0;
+    let mut headers: Vec<(String, String)> = Vec::new();
     loop {
         let mut header = String::new();
         reader.read_line(&mut header)?;
@@ -713,6 +1089,9 @@ fn handle_http_request(
                 .and_then(|s| s.trim().parse().ok())
                 .unwrap_or(0);
         }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
     }
 
     // Read body
@@ -721,6 +1100,71 @@ fn handle_http_request(
         std::io::Read::read_exact(&mut reader, &mut body)?;
     }
 
+    // Forge webhooks, snapshot reloads, the live dashboard and the feed bypass JSON-RPC
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let authenticate = || access::authenticate(&db::init_db(db_path)?, access::presented_key(&headers));
+    // Pages a browser or feed reader opens, which may carry the key as ?key=
+    let browser_role = match path {
+        "/dashboard" | "/dashboard/events" => Some(access::Role::Admin),
+        "/feed.atom" => Some(access::Role::Reader),
+        _ => None,
+    };
+    if let Some(needed) = browser_role {
+        let key = access::presented_key(&headers).or_else(|| access::query_key(query));
+        let conn = db::init_db(db_path)?;
+        let (status, content_type, body) = match access::authenticate(&conn, key)?.and_then(|role| access::authorize(role, needed)) {
+            Ok(()) if path == "/dashboard/events" => return Ok(dashboard::live::stream(&conn, &mut stream)?),
+            Ok(()) if path == "/feed.atom" => {
+                let days = notifier::NotifierConfig::beside(&conn)?.digest_days;
+                let digest = notifier::digest(&conn, chrono::Utc::now(), days)?;
+                ("200 OK", "application/atom+xml; charset=utf-8", feed::atom(&digest))
+            }
+            Ok(()) => ("200 OK", "text/html; charset=utf-8", dashboard::live::page(access::query_key(query))?),
+            Err(denied) => (
+                denied.status(),
+                "application/json",
+                serde_json::to_string(&serde_json::json!({"error": denied.to_string()}))?,
+            ),
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes())?;
+        stream.flush()?;
+        return Ok(());
+    }
+    let direct = if let Some(forge) = path.strip_prefix("/webhooks/") {
+        let reply = handle_webhook(forge, &headers, &body, db_path)?;
+        if let Some(snapshot) = snapshot {
+            snapshot.refresh(&db::init_db(db_path)?)?;
+        }
+        Some(reply)
+    } else if path == "/snapshot/refresh" {
+        match authenticate()?.and_then(|role| access::authorize(role, access::Role::Admin)) {
+            Ok(()) => Some(handle_snapshot_refresh(snapshot, db_path)?),
+            Err(denied) => Some((denied.status(), serde_json::json!({"error": denied.to_string()}))),
+        }
+    } else {
+        None
+    };
+    if let Some((status, reply)) = direct {
+        let reply = serde_json::to_string(&reply)?;
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            reply.len(),
+            reply
+        );
+        stream.write_all(response.as_bytes())?;
+        stream.flush()?;
+        return Ok(());
+    }
+
     // Parse JSON-RPC request
     let body_str = String::from_utf8_lossy(&body);
     let json_req: serde_json::Value =
@@ -735,6 +1179,27 @@ fn handle_http_request(
         .cloned()
         .unwrap_or(serde_json::json!({}));
     let id = json_req.get("id").cloned().unwrap_or(serde_json::json!(1));
+    let tool_name = params.get("name").and_then(|n| n.as_str());
+
+    let needed = match method {
+        "counsel" | "tools/call" => access::required_role(tool_name.unwrap_or("counsel")),
+        _ => access::Role::Reader,
+    };
+    let role = match authenticate()?.and_then(|role| access::authorize(role, needed).map(|()| role)) {
+        Ok(role) => role,
+        Err(denied) => {
+            let reply = serde_json::to_string(&serde_json::json!({"jsonrpc": "2.0", "id": id, "error": denied.to_jsonrpc()}))?;
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\n\r\n{}",
+                denied.status(),
+                reply.len(),
+                reply
+            );
+            stream.write_all(response.as_bytes())?;
+            stream.flush()?;
+            return Ok(());
+        }
+    };
 
     // Route to handler
     let conn = db::init_db(db_path)?;
@@ -745,45 +1210,42 @@ fn handle_http_request(
         "initialize" => Ok(serde_json::json!({
             "protocolVersion": "2024-11-05",
             "capabilities": {
-                "tools": {}
+                "tools": {},
+                "logging": {}
             },
             "serverInfo": {
                 "name": "100minds",
                 "version": "0.1.0"
             }
         })),
-        // MCP Protocol: List available tools
-        "tools/list" => Ok(serde_json::json!({
-            "tools": mcp::get_tools()
-        })),
-        // MCP Protocol: Call a tool
-        "counsel" | "tools/call" => {
-            let tool_name = params
-                .get("name")
-                .and_then(|n| n.as_str())
-                .unwrap_or("counsel");
-            match tool_name {
-                "counsel" => handle_counsel_tool(&conn, &provenance, &params),
-                "get_decision_template" => handle_get_decision_template(&params),
-                "check_blind_spots" => handle_check_blind_spots(&params),
-                "detect_anti_patterns" => handle_detect_anti_patterns(&params),
-                "validate_prd" => handle_validate_prd(&conn, &params),
-                "pre_work_context" => handle_pre_work_context(&conn, &params),
-                "record_outcome" => handle_record_outcome_tool(&conn, &params),
-                "search_principles" => handle_search_principles(&conn, &params),
-                "get_synergies" => handle_get_synergies(&params),
-                "get_tensions" => handle_get_tensions(&params),
-                "wisdom_stats" => handle_wisdom_stats(&conn, &params),
-                "audit_decision" => handle_audit_decision(&conn, &provenance, &params),
-                "sync_posteriors" => handle_sync_posteriors_tool(&conn, &params),
-                "record_outcomes_batch" => handle_record_outcomes_batch(&conn, &params),
-                "counterfactual_sim" => handle_counterfactual_sim_tool(&conn, &provenance, &params),
-                _ => Ok(serde_json::json!({"error": format!("Unknown tool: {}", tool_name)})),
-            }
+        // MCP Protocol: List the tools this caller may call
+        "tools/list" => {
+            let tools: Vec<serde_json::Value> = mcp::get_tools()
+                .into_iter()
+                .filter(|tool| tool["name"].as_str().is_some_and(|name| role >= access::required_role(name)))
+                .collect();
+            Ok(serde_json::json!({ "tools": tools }))
         }
+        // MCP Protocol: Call a tool
+        "counsel" | "tools/call" => call_tool(tool_name.unwrap_or("counsel"), &conn, &provenance, snapshot, &params),
         _ => Ok(serde_json::json!({"error": format!("Unknown method: {}", method)})),
     };
 
+    // Learned state changed: later counsel should see it, and the watchdog
+    // gets a look at the new outcomes
+    let mut notifications = Vec::new();
+    let learned = matches!(tool_name, Some("record_outcome" | "record_outcomes_batch" | "record_falsification"));
+    if learned && result.is_ok() {
+        if let Some(snapshot) = snapshot {
+            snapshot.refresh(&conn)?;
+        }
+    }
+    if matches!(tool_name, Some("record_outcome" | "record_outcomes_batch")) && result.is_ok() {
+        let now = chrono::Utc::now();
+        let alerts = watchdog::scan(&conn, &watchdog::WatchdogConfig::beside(&conn)?, now)?;
+        notifications = watchdog::notifications(&watchdog::raise(&conn, &alerts, now)?);
+    }
+
     // Check if this was a tools/call - those need MCP content wrapper
     let is_tool_call = method == "tools/call" || method == "counsel";
 
@@ -792,11 +1254,16 @@ fn handle_http_request(
             let wrapped_result = if is_tool_call {
                 // MCP protocol requires content blocks for tool responses
                 let text_content = serde_json::to_string(&r).unwrap_or_default();
-                serde_json::json!({
+                let mut wrapped = serde_json::json!({
                     "content": [{"type": "text", "text": text_content}],
                     "structuredContent": r,
                     "isError": false
-                })
+                });
+                // Plain request/response HTTP can't push, so new alerts ride along
+                if !notifications.is_empty() {
+                    wrapped["_meta"] = serde_json::json!({"notifications": notifications});
+                }
+                wrapped
             } else {
                 r
             };
@@ -809,7 +1276,7 @@ fn handle_http_request(
         Err(e) => serde_json::json!({
             "jsonrpc": "2.0",
             "id": id,
-            "error": {"code": -32000, "message": e.to_string()}
+            "error": validation::jsonrpc_error(e.as_ref())
         }),
     };
 
@@ -826,30 +1293,209 @@ fn handle_http_request(
     Ok(())
 }
 
+/// Validate a tool call's arguments against its input schema, then run it
+fn call_tool(
+    tool_name: &str,
+    conn: &rusqlite::Connection,
+    provenance: &Provenance,
+    snapshot: Option<&SharedSnapshot>,
+    params: &serde_json::Value,
+) -> Result<serde_json::Value> {
+    if let Some(schema) = mcp::tool_schema(tool_name) {
+        validation::validate(tool_name, &schema, params.get("arguments").unwrap_or(params))?;
+    }
+    match tool_name {
+        "counsel" => handle_counsel_tool(conn, provenance, snapshot, params),
+        "counsel_followup" => {
+            let args = params.get("arguments").unwrap_or(params);
+            let request: thread::FollowupRequest = serde_json::from_value(args.clone())?;
+            let mut engine = CounselEngine::new(conn, provenance);
+            if let Some(snapshot) = snapshot {
+                engine = engine.with_snapshot(snapshot.current());
+            }
+            Ok(serde_json::to_value(engine.counsel_followup(&request)?)?)
+        }
+        "evaluate_options" => {
+            let args = params.get("arguments").unwrap_or(params);
+            let request: options::EvaluateOptionsRequest = serde_json::from_value(args.clone())?;
+            let mut engine = CounselEngine::new(conn, provenance);
+            if let Some(snapshot) = snapshot {
+                engine = engine.with_snapshot(snapshot.current());
+            }
+            Ok(serde_json::to_value(engine.evaluate_options(&request)?)?)
+        }
+        "evaluate_cost_of_delay" => {
+            let args = params.get("arguments").unwrap_or(params);
+            let request: cost_of_delay::CostOfDelayRequest = serde_json::from_value(args.clone())?;
+            Ok(serde_json::to_value(cost_of_delay::worksheet(&request.items)?)?)
+        }
+        "sanity_check_estimate" => {
+            let args = params.get("arguments").unwrap_or(params);
+            let request: estimates::EstimateCheckRequest = serde_json::from_value(args.clone())?;
+            let mut engine = embeddings::shared_engine(conn).map(|m| m.lock().unwrap_or_else(|e| e.into_inner()));
+            Ok(serde_json::to_value(estimates::check(conn, engine.as_deref_mut(), &request)?)?)
+        }
+        "record_estimate_actual" => handle_record_estimate_actual(conn, params),
+        "get_decision_template" => handle_get_decision_template(params),
+        "match_template" => handle_match_template(params),
+        "check_blind_spots" => handle_check_blind_spots(conn, params),
+        "ack_blind_spot" => handle_ack_blind_spot(conn, params),
+        "get_checklist" => handle_get_checklist(conn, params),
+        "tick_checklist_item" => handle_tick_checklist_item(conn, params),
+        "detect_anti_patterns" => handle_detect_anti_patterns(conn, params),
+        "validate_prd" => handle_validate_prd(conn, params),
+        "pre_work_context" => handle_pre_work_context(conn, params),
+        "record_outcome" => handle_record_outcome_tool(conn, params),
+        "record_falsification" => handle_record_falsification(conn, params),
+        "search_principles" => handle_search_principles(conn, params),
+        "get_synergies" => handle_get_synergies(params),
+        "get_tensions" => handle_get_tensions(params),
+        "wisdom_stats" => handle_wisdom_stats(conn, params),
+        "get_learning_stats" => Ok(serde_json::to_value(outcome::get_learning_stats(conn)?)?),
+        "coverage_report" => handle_coverage_report(conn, params),
+        "decision_themes" => handle_decision_themes(conn, params),
+        "search_decisions" => handle_search_decisions(conn, params),
+        "mark_event" => {
+            let args = params.get("arguments").unwrap_or(params);
+            let label = args.get("label").and_then(|l| l.as_str()).unwrap_or_default();
+            Ok(serde_json::to_value(stats::cohorts::mark_event(conn, label, chrono::Utc::now())?)?)
+        }
+        "cohort_report" => Ok(serde_json::json!({"cohorts": stats::cohorts::compare(conn)?})),
+        "approve_decision" => handle_review_decision(conn, provenance, params, true),
+        "reject_decision" => handle_review_decision(conn, provenance, params, false),
+        "audit_decision" => handle_audit_decision(conn, provenance, params),
+        "sync_posteriors" => handle_sync_posteriors_tool(conn, params),
+        "record_outcomes_batch" => handle_record_outcomes_batch(conn, params),
+        "counterfactual_sim" => handle_counterfactual_sim_tool(conn, provenance, params),
+        "submit_counsel_async" => handle_submit_counsel_async(conn, params),
+        "get_counsel_result" => handle_get_counsel_result(conn, params),
+        _ => Ok(serde_json::json!({"error": format!("Unknown tool: {}", tool_name)})),
+    }
+}
+
+/// Verify and apply a GitHub/GitLab webhook delivery
+/// Returns the HTTP status line and a JSON reply
+fn handle_webhook(
+    forge: &str,
+    headers: &[(String, String)],
+    body: &[u8],
+    db_path: &std::path::Path,
+) -> Result<(&'static str, serde_json::Value)> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+            .unwrap_or("")
+    };
+
+    let config = webhooks::WebhookConfig::load(&get_data_dir()?.join("webhooks.json"))?;
+    let Some(secret) = config.secret.as_deref() else {
+        return Ok((
+            "403 Forbidden",
+            serde_json::json!({"error": format!("No webhook secret configured (set {})", webhooks::SECRET_ENV)}),
+        ));
+    };
+
+    let verified = match forge {
+        "github" => webhooks::verify_github_signature(secret, body, header("x-hub-signature-256")),
+        "gitlab" => webhooks::verify_gitlab_token(secret, header("x-gitlab-token")),
+        _ => return Ok(("404 Not Found", serde_json::json!({"error": "Unknown webhook"}))),
+    };
+    if !verified {
+        return Ok(("401 Unauthorized", serde_json::json!({"error": "Invalid signature"})));
+    }
+
+    let payload: serde_json::Value = serde_json::from_slice(body).unwrap_or(serde_json::json!({}));
+    let event = match forge {
+        "github" => webhooks::parse_github_event(header("x-github-event"), &payload),
+        _ => webhooks::parse_gitlab_event(header("x-gitlab-event"), &payload),
+    };
+    let (Some(event), Some(repo)) = (event, webhooks::repository(&payload)) else {
+        return Ok(("200 OK", serde_json::json!({"ignored": true})));
+    };
+    // Redeliveries reuse the forge's delivery id; without one, the body identifies them
+    let delivery_id = match header(if forge == "github" { "x-github-delivery" } else { "x-gitlab-event-uuid" }) {
+        "" => hex::encode(hmac_sha256::Hash::hash(body)),
+        id => id.to_string(),
+    };
+
+    let conn = db::init_db(db_path)?;
+    let recorded = webhooks::apply_event(&conn, &config, repo, &delivery_id, &event)?;
+    say_err!("🪝 {} webhook #{}: recorded {} outcome(s)", forge, event.number(), recorded.len());
+
+    Ok(("200 OK", serde_json::json!({"recorded": recorded})))
+}
+
+/// Reload the server's corpus snapshot (after an import, merge or daemon run)
+fn handle_snapshot_refresh(
+    snapshot: Option<&SharedSnapshot>,
+    db_path: &std::path::Path,
+) -> Result<(&'static str, serde_json::Value)> {
+    let Some(snapshot) = snapshot else {
+        return Ok(("404 Not Found", serde_json::json!({"error": "Server not started with --snapshot"})));
+    };
+    let fresh = snapshot.refresh(&db::init_db(db_path)?)?;
+    Ok((
+        "200 OK",
+        serde_json::json!({"principles": fresh.len(), "loaded_at": fresh.loaded_at}),
+    ))
+}
+
 fn handle_counsel_tool(
     conn: &rusqlite::Connection,
     provenance: &Provenance,
+    snapshot: Option<&SharedSnapshot>,
     params: &serde_json::Value,
 ) -> Result<serde_json::Value> {
     let args = params.get("arguments").unwrap_or(params);
-    let question = args.get("question").and_then(|q| q.as_str()).unwrap_or("");
-    let domain = args.get("domain").and_then(|d| d.as_str());
+    let idempotency_key = args.get("idempotency_key").and_then(|k| k.as_str());
+    let version = args.get("response_version").and_then(|v| v.as_u64()).map_or(SCHEMA_VERSION, |v| v as u32);
+    if !(1..=SCHEMA_VERSION).contains(&version) {
+        anyhow::bail!("Unknown response_version {} (1 to {})", version, SCHEMA_VERSION);
+    }
+    let mut engine = CounselEngine::new(conn, provenance);
+    if let Some(snapshot) = snapshot {
+        engine = engine.with_snapshot(snapshot.current());
+    }
+    let response = engine.counsel_idempotent(&counsel_request_from_params(params)?, idempotency_key)?;
+    Ok(response.render(version)?)
+}
 
-    // Allow client to specify decision_id (e.g., bead ID for swarm tracking)
-    let decision_id = args.get("decision_id").and_then(|d| d.as_str()).map(String::from);
+/// Build a CounselRequest from counsel tool arguments
+fn counsel_request_from_params(params: &serde_json::Value) -> Result<CounselRequest> {
+    let args = params.get("arguments").unwrap_or(params);
+    let args: mcp::CounselArgs = serde_json::from_value(args.clone())?;
+    Ok(args.into_request())
+}
 
-    let engine = CounselEngine::new(conn, provenance);
-    let request = CounselRequest {
-        question: question.to_string(),
-        context: CounselContext {
-            domain: domain.map(String::from),
-            ..Default::default()
-        },
-        decision_id,  // Pass through explicit ID or None for auto-generate
-    };
+/// Queue counsel for a background worker
+fn handle_submit_counsel_async(
+    conn: &rusqlite::Connection,
+    params: &serde_json::Value,
+) -> Result<serde_json::Value> {
+    let request = counsel_request_from_params(params)?;
+    if request.question.trim().is_empty() {
+        anyhow::bail!("question is required");
+    }
+    let job_id = jobs::submit_job(conn, &request)?;
+    Ok(serde_json::json!({ "job_id": job_id, "status": jobs::JobStatus::Queued }))
+}
 
-    let response = engine.counsel(&request)?;
-    Ok(serde_json::to_value(&response)?)
+/// Poll a queued counsel job
+fn handle_get_counsel_result(
+    conn: &rusqlite::Connection,
+    params: &serde_json::Value,
+) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let job_id = args
+        .get("job_id")
+        .and_then(|j| j.as_str())
+        .ok_or_else(|| anyhow::anyhow!("job_id is required"))?;
+    match jobs::get_job(conn, job_id)? {
+        Some(job) => Ok(serde_json::to_value(&job)?),
+        None => Ok(serde_json::json!({"error": format!("Unknown job: {}", job_id)})),
+    }
 }
 
 fn handle_record_outcome_tool(
@@ -878,6 +1524,15 @@ fn handle_record_outcome_tool(
     let domain = args.get("domain").and_then(|d| d.as_str());
     let confidence_score = args.get("confidence_score").and_then(|c| c.as_f64());
     let failure_stage = args.get("failure_stage").and_then(|f| f.as_str());
+    let failure_reasons: Vec<String> = args
+        .get("failure_reasons")
+        .and_then(|r| r.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    let idempotency_key = args.get("idempotency_key").and_then(|k| k.as_str());
+    let actor_id = args.get("actor_id").and_then(|a| a.as_str());
+    let template_id = args.get("template_id").and_then(|t| t.as_str());
+    let template_path = template_feedback::path_steps(args.get("template_path"));
 
     let request = RecordOutcomeRequest {
         decision_id: decision_id.to_string(),
@@ -887,12 +1542,31 @@ fn handle_record_outcome_tool(
         domain: domain.map(String::from),
         confidence_score,
         failure_stage: failure_stage.map(String::from),
+        failure_reasons,
+        idempotency_key: idempotency_key.map(String::from),
+        actor_id: actor_id.map(String::from),
+        template_id: template_id.map(String::from),
+        template_path,
     };
+    outcome::validate_outcome_request(&request)?;
 
     let result = outcome::record_outcome_v2(conn, &request)?;
     Ok(serde_json::to_value(&result)?)
 }
 
+fn handle_record_falsification(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let field = |key: &str| {
+        args.get(key)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("{} is required", key))
+    };
+    let evidence = args.get("evidence").and_then(|e| e.as_str()).unwrap_or("");
+
+    let result = falsification::record(conn, field("decision_id")?, field("position")?, evidence)?;
+    Ok(serde_json::to_value(&result)?)
+}
+
 fn handle_sync_posteriors_tool(
     conn: &rusqlite::Connection,
     params: &serde_json::Value,
@@ -963,7 +1637,14 @@ fn handle_get_decision_template(params: &serde_json::Value) -> Result<serde_json
     Ok(serde_json::to_value(&all_templates)?)
 }
 
-fn handle_check_blind_spots(params: &serde_json::Value) -> Result<serde_json::Value> {
+fn handle_match_template(params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let question = args.get("question").and_then(|q| q.as_str()).unwrap_or("");
+    let limit = args.get("limit").and_then(|l| l.as_u64()).unwrap_or(3) as usize;
+    Ok(serde_json::json!({"matches": mcp::match_template(question, limit)}))
+}
+
+fn handle_check_blind_spots(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
     let args = params.get("arguments").unwrap_or(params);
     let context = args
         .get("decision_context")
@@ -971,11 +1652,62 @@ fn handle_check_blind_spots(params: &serde_json::Value) -> Result<serde_json::Va
         .unwrap_or("");
     let template_id = args.get("template_id").and_then(|t| t.as_str());
 
-    let analysis = mcp::check_blind_spots(context, template_id);
+    let mut analysis = mcp::check_blind_spots(context, template_id);
+    if let Some(decision_id) = args.get("decision_id").and_then(|d| d.as_str()) {
+        blind_spots::record_checks(conn, decision_id, &mut analysis.blind_spots)?;
+    }
     Ok(serde_json::to_value(&analysis)?)
 }
 
-fn handle_detect_anti_patterns(params: &serde_json::Value) -> Result<serde_json::Value> {
+fn handle_ack_blind_spot(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let field = |key: &str| {
+        args.get(key)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("{} is required", key))
+    };
+    let (decision_id, name, status) = (field("decision_id")?, field("name")?, field("status")?);
+    let note = args.get("note").and_then(|n| n.as_str());
+
+    blind_spots::ack(conn, decision_id, name, status, note)?;
+    Ok(serde_json::json!({"decision_id": decision_id, "name": name, "status": status}))
+}
+
+fn handle_get_checklist(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let decision_id = args
+        .get("decision_id")
+        .and_then(|d| d.as_str())
+        .ok_or_else(|| anyhow::anyhow!("decision_id is required"))?;
+    Ok(serde_json::to_value(checklist::generate(conn, decision_id)?)?)
+}
+
+fn handle_tick_checklist_item(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let field = |key: &str| {
+        args.get(key)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("{} is required", key))
+    };
+    let (decision_id, item_id) = (field("decision_id")?, field("item_id")?);
+    let done = args.get("done").and_then(|d| d.as_bool()).unwrap_or(true);
+    Ok(serde_json::to_value(checklist::tick(conn, decision_id, item_id, done)?)?)
+}
+
+fn handle_record_estimate_actual(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let estimate_id = args
+        .get("estimate_id")
+        .and_then(|e| e.as_i64())
+        .ok_or_else(|| anyhow::anyhow!("estimate_id is required"))?;
+    let actual = args
+        .get("actual")
+        .and_then(|a| a.as_f64())
+        .ok_or_else(|| anyhow::anyhow!("actual is required"))?;
+    Ok(serde_json::to_value(estimates::record_actual(conn, estimate_id, actual)?)?)
+}
+
+fn handle_detect_anti_patterns(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
     let args = params.get("arguments").unwrap_or(params);
     let description = args
         .get("description")
@@ -983,36 +1715,15 @@ fn handle_detect_anti_patterns(params: &serde_json::Value) -> Result<serde_json:
         .unwrap_or("");
     let domain = args.get("domain").and_then(|d| d.as_str());
 
-    // Get matching templates for context
-    let matches = mcp::get_matching_templates(description);
-    let mut anti_patterns: Vec<serde_json::Value> = Vec::new();
-
-    for m in matches.iter().take(3) {
-        for ap in &m.template.anti_patterns {
-            anti_patterns.push(serde_json::json!({
-                "name": ap.name,
-                "symptoms": ap.symptoms,
-                "thinker": ap.source_thinker,
-                "cure": ap.cure,
-                "template": m.template.id
-            }));
-        }
-    }
-
-    // Also add generic anti-patterns based on keywords
-    if description.to_lowercase().contains("rewrite") {
-        anti_patterns.push(serde_json::json!({
-            "name": "Second System Effect",
-            "symptom": "Planning to rewrite from scratch",
-            "thinker": "Fred Brooks",
-            "cure": "Consider strangler fig pattern instead"
-        }));
-    }
+    let mut engine = embeddings::shared_engine(conn).map(|m| m.lock().unwrap_or_else(|e| e.into_inner()));
+    let report = minds_mcp::anti_patterns::detect_anti_patterns(description, domain, engine.as_deref_mut())?;
 
     Ok(serde_json::json!({
         "description": description,
         "domain": domain,
-        "anti_patterns": anti_patterns
+        "sentences": report.sentences,
+        "semantic": report.semantic,
+        "anti_patterns": report.matches
     }))
 }
 
@@ -1061,27 +1772,46 @@ fn handle_search_principles(
 ) -> Result<serde_json::Value> {
     let args = params.get("arguments").unwrap_or(params);
     let query = args.get("query").and_then(|q| q.as_str()).unwrap_or("");
-    let limit = args
-        .get("limit")
+    let domain = args.get("domain").and_then(|d| d.as_str());
+    // `limit` is the older name for `top_k`
+    let top_k = args
+        .get("top_k")
+        .or_else(|| args.get("limit"))
         .and_then(|l| l.as_i64())
-        .unwrap_or(10) as usize;
-
-    let results = db::search_principles(conn, query, limit)?;
-    // Convert to JSON manually since PrincipleMatch may not be Serialize
-    let json_results: Vec<serde_json::Value> = results
-        .iter()
-        .map(|r| {
-            serde_json::json!({
-                "id": r.id,
-                "thinker_id": r.thinker_id,
-                "name": r.name,
-                "description": r.description,
-                "confidence": r.confidence,
-                "relevance_score": r.relevance_score
-            })
-        })
-        .collect();
-    Ok(serde_json::json!({"results": json_results}))
+        .unwrap_or(10)
+        .max(1) as usize;
+    let include_confidence = args.get("include_confidence").and_then(|c| c.as_bool()).unwrap_or(true);
+    let mut config = search::SearchConfig::beside(conn)?;
+    if let Some(weight) = args.get("semantic_weight").and_then(|w| w.as_f64()) {
+        config.semantic_weight = weight as f32;
+    }
+    if let Some(weight) = args.get("bm25_weight").and_then(|w| w.as_f64()) {
+        config.bm25_weight = weight as f32;
+    }
+    if let Some(min_score) = args.get("min_score").and_then(|m| m.as_f64()) {
+        config.min_score = min_score as f32;
+    }
+    if let Some(rerank) = args.get("rerank").and_then(|r| r.as_bool()) {
+        config.rerank = rerank;
+    }
+    config.validate()?;
+
+    let corrected = search::Vocabulary::load(conn)?.correct(query);
+    let mut engine = embeddings::shared_engine(conn).map(|m| m.lock().unwrap_or_else(|e| e.into_inner()));
+    let found = search::principles(conn, engine.as_deref_mut(), &corrected.query, domain, top_k, &config)?;
+    let mut results = found.hits;
+    if !include_confidence {
+        results.iter_mut().for_each(|r| r.confidence = None);
+    }
+    let mut response = serde_json::json!({"results": results, "semantic": engine.is_some()});
+    if let Some(rerank) = &found.rerank {
+        response["rerank"] = serde_json::to_value(rerank)?;
+    }
+    if corrected.is_corrected() {
+        response["searched_query"] = serde_json::json!(corrected.query);
+        response["corrections"] = serde_json::to_value(&corrected.corrections)?;
+    }
+    Ok(response)
 }
 
 fn handle_get_synergies(params: &serde_json::Value) -> Result<serde_json::Value> {
@@ -1178,6 +1908,31 @@ fn handle_get_tensions(params: &serde_json::Value) -> Result<serde_json::Value>
     }))
 }
 
+fn handle_coverage_report(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let mut response = serde_json::json!({});
+    if args.get("rebuild_coverage").and_then(|r| r.as_bool()).unwrap_or(false) {
+        response["rebuilt_from_decisions"] = eval::coverage::rebuild_coverage_counters(conn)?.into();
+    }
+    response["coverage"] = serde_json::to_value(eval::coverage::analyze_coverage(conn)?)?;
+    Ok(response)
+}
+
+fn handle_decision_themes(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let k = args.get("k").and_then(|k| k.as_u64()).map(|k| k as usize);
+    let mut engine = embeddings::shared_engine(conn).map(|m| m.lock().unwrap_or_else(|e| e.into_inner()));
+    Ok(serde_json::to_value(themes::analyze(conn, engine.as_deref_mut(), k)?)?)
+}
+
+fn handle_search_decisions(conn: &rusqlite::Connection, params: &serde_json::Value) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let query = args.get("query").and_then(|q| q.as_str()).unwrap_or("");
+    let limit = args.get("limit").and_then(|l| l.as_u64()).unwrap_or(10) as usize;
+    let matches = history::search_decisions(conn, query, limit)?;
+    Ok(serde_json::json!({ "query": query, "count": matches.len(), "matches": matches }))
+}
+
 fn handle_wisdom_stats(
     conn: &rusqlite::Connection,
     params: &serde_json::Value,
@@ -1203,6 +1958,7 @@ fn handle_wisdom_stats(
             "domain": domain,
             "min_decisions": min_decisions,
             "principles": [],
+            "capabilities": embeddings::capabilities(conn),
             "message": "No outcomes recorded yet. Use record_outcome to start learning."
         }));
     }
@@ -1251,10 +2007,30 @@ fn handle_wisdom_stats(
     Ok(serde_json::json!({
         "domain": domain,
         "min_decisions": min_decisions,
-        "principles": stats
+        "principles": stats,
+        "capabilities": embeddings::capabilities(conn),
     }))
 }
 
+fn handle_review_decision(
+    conn: &rusqlite::Connection,
+    provenance: &Provenance,
+    params: &serde_json::Value,
+    approve: bool,
+) -> Result<serde_json::Value> {
+    let args = params.get("arguments").unwrap_or(params);
+    let field = |name: &str| args.get(name).and_then(|v| v.as_str());
+    let approval = approval::review(
+        conn,
+        provenance,
+        field("decision_id").unwrap_or_default(),
+        approve,
+        field("reviewer").unwrap_or_default(),
+        field("note"),
+    )?;
+    Ok(serde_json::to_value(approval)?)
+}
+
 fn handle_audit_decision(
     conn: &rusqlite::Connection,
     provenance: &Provenance,
@@ -1293,9 +2069,16 @@ fn handle_audit_decision(
 
     match result {
         Ok(audit) => Ok(audit),
-        Err(_) => Ok(serde_json::json!({
-            "error": format!("Decision not found: {}", decision_id)
-        })),
+        Err(_) => {
+            let tombstone = retention::get_tombstone(conn, decision_id)?;
+            let events = forget::events_for(conn, decision_id)?;
+            if tombstone.is_none() && events.is_empty() {
+                return Ok(serde_json::json!({
+                    "error": format!("Decision not found: {}", decision_id)
+                }));
+            }
+            Ok(serde_json::json!({ "decision_id": decision_id, "removed": tombstone, "deletion_events": events }))
+        }
     }
 }
 
@@ -1310,7 +2093,7 @@ fn handle_record_outcomes_batch(
         .cloned()
         .unwrap_or_default();
 
-    let mut results = Vec::new();
+    let mut requests = Vec::new();
     for outcome_val in outcomes {
         let decision_id = outcome_val
             .get("decision_id")
@@ -1330,6 +2113,10 @@ fn handle_record_outcomes_batch(
                     .collect()
             })
             .unwrap_or_default();
+        let idempotency_key = outcome_val.get("idempotency_key").and_then(|k| k.as_str());
+        let actor_id = outcome_val.get("actor_id").and_then(|a| a.as_str());
+        let template_id = outcome_val.get("template_id").and_then(|t| t.as_str());
+        let template_path = template_feedback::path_steps(outcome_val.get("template_path"));
 
         let request = RecordOutcomeRequest {
             decision_id: decision_id.to_string(),
@@ -1339,21 +2126,37 @@ fn handle_record_outcomes_batch(
             domain: None,
             confidence_score: None,
             failure_stage: None,
+            failure_reasons: Vec::new(),
+            idempotency_key: idempotency_key.map(String::from),
+            actor_id: actor_id.map(String::from),
+            template_id: template_id.map(String::from),
+            template_path,
         };
 
-        match outcome::record_outcome_v2(conn, &request) {
-            Ok(r) => results.push(serde_json::json!({
-                "decision_id": decision_id,
+        requests.push(request);
+    }
+
+    // An outlier batch is held whole; otherwise each outcome succeeds or fails alone
+    let outcomes: Vec<std::result::Result<outcome::OutcomeResult, String>> = match outcome::hold_outlier_batch(conn, &requests)? {
+        Some(held) => held.into_iter().map(Ok).collect(),
+        None => requests.iter().map(|r| outcome::record_outcome_v2(conn, r).map_err(|e| e.to_string())).collect(),
+    };
+    let results: Vec<serde_json::Value> = requests
+        .iter()
+        .zip(outcomes)
+        .map(|(request, result)| match result {
+            Ok(r) => serde_json::json!({
+                "decision_id": request.decision_id,
                 "success": true,
                 "result": r
-            })),
-            Err(e) => results.push(serde_json::json!({
-                "decision_id": decision_id,
+            }),
+            Err(e) => serde_json::json!({
+                "decision_id": request.decision_id,
                 "success": false,
-                "error": e.to_string()
-            })),
-        }
-    }
+                "error": e
+            }),
+        })
+        .collect();
 
     Ok(serde_json::json!({
         "processed": results.len(),
@@ -1364,18 +2167,21 @@ fn handle_record_outcomes_batch(
 /// Print as a decision tree with reasoning chains
 fn print_decision_tree(response: &CounselResponse, elapsed: std::time::Duration) {
     // Header with timing
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!(
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!(
         "│ 🧠 100MINDS DECISION INTELLIGENCE   [{:>6.1}ms]              │",
         elapsed.as_secs_f64() * 1000.0
     );
-    println!("└─────────────────────────────────────────────────────────────┘");
-    println!();
-    println!("📋 {}", response.question);
-    println!();
+    say!("└─────────────────────────────────────────────────────────────┘");
+    say!();
+    say!("📋 {}", response.question);
+    if let Some(choice) = &response.depth {
+        say!("📏 Depth: {:?} (auto; {})", choice.depth, choice.reasons.join(", "));
+    }
+    say!();
 
     // Decision tree format
-    println!("┌─ IF YOU PROCEED ────────────────────────────────────────────");
+    say!("┌─ IF YOU PROCEED ────────────────────────────────────────────");
     for position in response
         .positions
         .iter()
@@ -1383,9 +2189,9 @@ fn print_decision_tree(response: &CounselResponse, elapsed: std::time::Duration)
     {
         print_tree_node(position, "│  ");
     }
-    println!("│");
+    say!("│");
 
-    println!("├─ WATCH OUT FOR ──────────────────────────────────────────────");
+    say!("├─ WATCH OUT FOR ──────────────────────────────────────────────");
     for position in response
         .positions
         .iter()
@@ -1393,15 +2199,19 @@ fn print_decision_tree(response: &CounselResponse, elapsed: std::time::Duration)
     {
         print_tree_node(position, "│  ");
     }
-    println!("│");
+    say!("│");
 
-    println!("└─ BEFORE DECIDING ─────────────────────────────────────────────");
+    say!("└─ BEFORE DECIDING ─────────────────────────────────────────────");
     print_challenge_node(&response.challenge);
-    println!();
+    say!();
+    print_tradeoff(response);
+    print_cost_of_delay(response);
+    print_relations(response);
+    print_similar_decisions(response);
 
     // Provenance footer
-    println!("─────────────────────────────────────────────────────────────────");
-    println!(
+    say!("─────────────────────────────────────────────────────────────────");
+    say!(
         "Decision #{} │ Chain: {}",
         &response.decision_id[..8],
         response
@@ -1413,6 +2223,67 @@ fn print_decision_tree(response: &CounselResponse, elapsed: std::time::Duration)
     );
 }
 
+fn print_tradeoff(response: &CounselResponse) {
+    let Some(tradeoff) = &response.tradeoff else {
+        return;
+    };
+    let names: Vec<&str> = tradeoff.objectives.iter().map(|o| o.name.as_str()).collect();
+    say!("⚖️  TRADEOFF: {}", names.join(" vs "));
+    if let Some(t) = &tradeoff.dominant_tension {
+        say!("   Dominant tension: {} ({}) ↔ {} ({})", t.principle_a, t.thinker_a, t.principle_b, t.thinker_b);
+    }
+    for axis in &tradeoff.axes {
+        say!("   • {}", axis.gain);
+        say!("     {}", axis.lose);
+    }
+    match &tradeoff.lean {
+        Some(lean) => say!("   Cited principles lean toward {}", lean),
+        None => say!("   Cited principles are evenly split"),
+    }
+    say!();
+}
+
+fn print_cost_of_delay(response: &CounselResponse) {
+    let Some(worksheet) = &response.cost_of_delay else {
+        return;
+    };
+    say!("💸 COST OF DELAY");
+    print_cost_of_delay_result(worksheet);
+    say!();
+}
+
+fn print_relations(response: &CounselResponse) {
+    if response.relations.is_empty() {
+        return;
+    }
+    say!("🔗 ORDER & RELATIONS");
+    for relation in &response.relations {
+        say!("   • {}", relation.advice);
+        if let Some(note) = &relation.note {
+            say!("     {}", note);
+        }
+    }
+    say!();
+}
+
+fn print_similar_decisions(response: &CounselResponse) {
+    if response.similar_past_decisions.is_empty() {
+        return;
+    }
+    say!("🕰  SIMILAR PAST DECISIONS");
+    for past in &response.similar_past_decisions {
+        let outcome = if past.outcome == Some(true) { "✅" } else { "❌" };
+        say!("   {} {}", outcome, truncate_str(&past.question, 70));
+        if !past.credited.is_empty() {
+            say!("      credited: {}", past.credited.join(", "));
+        }
+        if !past.blamed.is_empty() {
+            say!("      blamed: {}", past.blamed.join(", "));
+        }
+    }
+    say!();
+}
+
 fn print_tree_node(position: &CounselPosition, prefix: &str) {
     // Extract the ACTION from the argument
     let (principle, action) = if let Some(idx) = position.argument.find("→ ACTION:") {
@@ -1430,28 +2301,28 @@ fn print_tree_node(position: &CounselPosition, prefix: &str) {
         _ => "███",
     };
 
-    println!("{}┌─ {} says: [{}]", prefix, position.thinker, conf_bar);
+    say!("{}┌─ {} says: [{}]", prefix, position.thinker, conf_bar);
 
     // Wrap principle text
     for line in wrap_lines(principle, 55) {
-        println!("{}│  {}", prefix, line);
+        say!("{}│  {}", prefix, line);
     }
 
     // Action in bold-like format
-    println!("{}│", prefix);
-    println!("{}│  ⚡ {}", prefix, action);
+    say!("{}│", prefix);
+    say!("{}│  ⚡ {}", prefix, action);
 
     // Falsification
     if let Some(f) = &position.falsifiable_if {
-        println!("{}│  ⚠️  Skip if: {}", prefix, truncate_str(f, 50));
+        say!("{}│  ⚠️  Skip if: {}", prefix, truncate_str(f, 50));
     }
-    println!("{}└", prefix);
+    say!("{}└", prefix);
 }
 
 fn print_challenge_node(challenge: &CounselPosition) {
-    println!("   🔍 Devil's Advocate:");
+    say!("   🔍 Devil's Advocate:");
     for line in wrap_lines(&challenge.argument, 58) {
-        println!("      {}", line);
+        say!("      {}", line);
     }
 }
 
@@ -1483,6 +2354,11 @@ fn truncate_str(s: &str, max: usize) -> String {
     }
 }
 
+/// Apply the `[policy]` rules from config.toml before serving counsel
+fn sync_policy_config(conn: &rusqlite::Connection, data_dir: &std::path::Path) -> Result<()> {
+    Ok(policy::PolicyConfig::load(&data_dir.join("config.toml"))?.sync(conn)?)
+}
+
 fn get_data_dir() -> Result<PathBuf> {
     // Use XDG data dir on Linux, ~/Library/Application Support on macOS
     let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -1494,16 +2370,17 @@ async fn run_cli_mode(conn: &rusqlite::Connection, provenance: &Provenance) -> R
     use std::io::{self, BufRead, Write};
 
     let engine = CounselEngine::new(conn, provenance);
+    let mut session = session::Session::new();
 
-    println!("100minds Adversarial Wisdom Council");
-    println!("====================================");
-    println!("Enter a decision question, or 'quit' to exit.\n");
+    say!("100minds Adversarial Wisdom Council");
+    say!("====================================");
+    say!("Enter a decision question, or 'quit' to exit. /help lists commands.\n");
 
     let stdin = io::stdin();
     let mut stdout = io::stdout();
 
     loop {
-        print!("> ");
+        std::print!("> ");
         stdout.flush()?;
 
         let mut line = String::new();
@@ -1520,18 +2397,17 @@ async fn run_cli_mode(conn: &rusqlite::Connection, provenance: &Provenance) -> R
 
         // Parse command
         if line.starts_with("/") {
-            handle_command(&engine, line)?;
+            if let Err(e) = handle_command(conn, &engine, &mut session, line) {
+                say_err!("Error: {}", e);
+            }
         } else {
-            // Treat as counsel request
-            let request = CounselRequest {
-                question: line.to_string(),
-                context: CounselContext::default(),
-                decision_id: None,  // Interactive mode - auto-generate UUID
-            };
-
-            match engine.counsel(&request) {
-                Ok(response) => print_counsel_response(&response),
-                Err(e) => eprintln!("Error: {}", e),
+            // Treat as counsel request, inheriting the session context
+            match engine.counsel(&session.request(line)) {
+                Ok(response) => {
+                    print_counsel_response(&response);
+                    session.record(&response);
+                }
+                Err(e) => say_err!("Error: {}", e),
             }
         }
     }
@@ -1539,7 +2415,12 @@ async fn run_cli_mode(conn: &rusqlite::Connection, provenance: &Provenance) -> R
     Ok(())
 }
 
-fn handle_command(engine: &CounselEngine, line: &str) -> Result<()> {
+fn handle_command(
+    conn: &rusqlite::Connection,
+    engine: &CounselEngine,
+    session: &mut session::Session,
+    line: &str,
+) -> Result<()> {
     let parts: Vec<&str> = line.splitn(2, ' ').collect();
     let cmd = parts[0];
     let arg = parts.get(1).unwrap_or(&"");
@@ -1549,7 +2430,7 @@ fn handle_command(engine: &CounselEngine, line: &str) -> Result<()> {
             // /outcome <decision_id> <success|fail> [notes]
             let parts: Vec<&str> = arg.splitn(3, ' ').collect();
             if parts.len() < 2 {
-                println!("Usage: /outcome <decision_id> <success|fail> [notes]");
+                say!("Usage: /outcome <decision_id> <success|fail> [notes]");
                 return Ok(());
             }
 
@@ -1565,20 +2446,74 @@ fn handle_command(engine: &CounselEngine, line: &str) -> Result<()> {
                 domain: None,
                 confidence_score: None,
                 failure_stage: None,
+                failure_reasons: Vec::new(),
+                idempotency_key: None,
+                actor_id: None,
+                template_id: None,
+                template_path: vec![],
             };
 
-            engine.record_outcome(&request)?;
-            println!("Outcome recorded for decision {}", decision_id);
+            outcome::validate_outcome_request(&request)?;
+            let result = outcome::record_outcome_v2(conn, &request)?;
+            if let Some(reason) = &result.quarantined {
+                say!("Outcome for {} quarantined for review: {}", decision_id, reason);
+            } else if result.duplicate || result.replayed {
+                say!("Decision {} already has this outcome; nothing changed", decision_id);
+            } else {
+                say!(
+                    "Outcome recorded for decision {} · {} principles adjusted",
+                    decision_id,
+                    result.principles_adjusted.len()
+                );
+            }
+        }
+        "/context" => {
+            // /context show | set <field> <value> | clear
+            let parts: Vec<&str> = arg.splitn(3, ' ').collect();
+            match parts[0] {
+                "" | "show" => session.describe().iter().for_each(|line| say!("{}", line)),
+                "set" if parts.len() == 3 => {
+                    session.set(parts[1], parts[2])?;
+                    say!("Set {} for the rest of this session", parts[1]);
+                }
+                "clear" => {
+                    session.clear();
+                    say!("Session context cleared");
+                }
+                _ => say!("Usage: /context show | set <{}> <value> | clear", session::KEYS.join("|")),
+            }
+        }
+        "/followup" => {
+            // /followup <refinement> - refine the last answer in this session
+            let Some(parent) = session.last_decision() else {
+                say!("Nothing to follow up yet - ask a question first");
+                return Ok(());
+            };
+            if arg.trim().is_empty() {
+                say!("Usage: /followup <what changed, e.g. what if the team is only 2 people?>");
+                return Ok(());
+            }
+            let response = engine.counsel_followup(&thread::FollowupRequest {
+                parent_decision_id: parent.to_string(),
+                refinement: arg.to_string(),
+                decision_id: None,
+            })?;
+            print_counsel_response(&response);
+            session.record(&response);
         }
         "/help" => {
-            println!("Commands:");
-            println!("  <question>           Ask for adversarial counsel");
-            println!("  /outcome <id> <s|f>  Record outcome (success/fail)");
-            println!("  /help                Show this help");
-            println!("  quit                 Exit");
+            say!("Commands:");
+            say!("  <question>           Ask for adversarial counsel");
+            say!("  /followup <change>   Refine the last answer (\"what if the team is only 2?\")");
+            say!("  /context show        Session context and decisions so far");
+            say!("  /context set <f> <v> Set {} for later questions", session::KEYS.join(", "));
+            say!("  /context clear       Forget the session context");
+            say!("  /outcome <id> <s|f>  Record outcome (success/fail)");
+            say!("  /help                Show this help");
+            say!("  quit                 Exit");
         }
         _ => {
-            println!("Unknown command: {}. Try /help", cmd);
+            say!("Unknown command: {}. Try /help", cmd);
         }
     }
 
@@ -1586,44 +2521,74 @@ fn handle_command(engine: &CounselEngine, line: &str) -> Result<()> {
 }
 
 fn print_counsel_response(response: &CounselResponse) {
-    println!("\n╔═══════════════════════════════════════════════════════════╗");
-    println!("║ ADVERSARIAL COUNSEL                                       ║");
-    println!("╠═══════════════════════════════════════════════════════════╣");
-    println!("║ Question: {}", truncate(&response.question, 50));
-    println!("║ Decision ID: {}", response.decision_id);
-    println!("╚═══════════════════════════════════════════════════════════╝\n");
-
-    for position in &response.positions {
-        println!(
+    say!("\n╔═══════════════════════════════════════════════════════════╗");
+    say!("║ ADVERSARIAL COUNSEL                                       ║");
+    say!("╠═══════════════════════════════════════════════════════════╣");
+    say!("║ Question: {}", truncate(&response.question, 50));
+    say!("║ Decision ID: {}", response.decision_id);
+    say!("╚═══════════════════════════════════════════════════════════╝\n");
+    if let Some(choice) = &response.depth {
+        say!("📏 Depth: {:?} (auto; {})\n", choice.depth, choice.reasons.join(", "));
+    }
+
+    let print_position = |position: &CounselPosition| {
+        say!(
             "{} {} ({})",
             position.stance.emoji(),
             position.thinker,
             position.stance.name()
         );
-        println!("   Confidence: {:.0}%", position.confidence * 100.0);
-        println!("   {}", wrap_text(&position.argument, 60, "   "));
+        say!("   Confidence: {:.0}%", position.confidence * 100.0);
+        say!("   {}", wrap_text(&position.argument, 60, "   "));
         if let Some(falsifiable) = &position.falsifiable_if {
-            println!("   ⚠️  Wrong if: {}", falsifiable);
+            say!("   ⚠️  Wrong if: {}", falsifiable);
+        }
+        say!();
+    };
+
+    if response.parts.is_empty() {
+        response.positions.iter().for_each(print_position);
+    } else {
+        say!("🧩 COMPOUND DECISION - {} parts counseled separately\n", response.parts.len());
+        for (i, part) in response.parts.iter().enumerate() {
+            say!("── Part {}: {}", i + 1, part.question);
+            say!();
+            part.positions.iter().for_each(print_position);
         }
-        println!();
     }
 
     // Print challenge
-    println!("{} CHALLENGE", response.challenge.stance.emoji());
-    println!("   {}", wrap_text(&response.challenge.argument, 60, "   "));
-    println!();
+    say!("{} CHALLENGE", response.challenge.stance.emoji());
+    say!("   {}", wrap_text(&response.challenge.argument, 60, "   "));
+    say!();
+
+    // Print tensions between recommended principles
+    if !response.tensions.is_empty() {
+        say!("⚖️  TENSIONS (these pull in opposite directions)");
+        for tension in &response.tensions {
+            say!("   {} ↔ {}", tension.principle_a, tension.principle_b);
+            say!("      Pick {} if: {}", tension.principle_a, tension.when_to_pick_a);
+            say!("      Pick {} if: {}", tension.principle_b, tension.when_to_pick_b);
+        }
+        say!();
+    }
+
+    print_tradeoff(response);
+    print_cost_of_delay(response);
+    print_relations(response);
+    print_similar_decisions(response);
 
     // Print provenance
-    println!("─────────────────────────────────────────────────────────────");
-    println!("Provenance: {}", &response.provenance.content_hash[..16]);
+    say!("─────────────────────────────────────────────────────────────");
+    say!("Provenance: {}", &response.provenance.content_hash[..16]);
     if let Some(prev) = &response.provenance.previous_hash {
-        println!(
+        say!(
             "Chain: ...{} → {}",
             &prev[..8],
             &response.provenance.content_hash[..8]
         );
     }
-    println!();
+    say!();
 }
 
 fn truncate(s: &str, max: usize) -> String {
@@ -1678,21 +2643,57 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
 
     match subcommand {
         "scenarios" => {
-            let scenario_dir = args
-                .first()
-                .map(PathBuf::from)
-                .unwrap_or_else(|| data_dir.join("scenarios"));
+            // [dir] [--lint] [--filter <spec>]
+            let mut scenario_dir = None;
+            let mut filter = eval::scenarios::ScenarioFilter::default();
+            let mut rest = args.iter();
+            while let Some(arg) = rest.next() {
+                if let Some(spec) = arg.strip_prefix("--filter=") {
+                    filter = eval::scenarios::ScenarioFilter::parse(spec)?;
+                } else if arg == "--filter" {
+                    let spec = rest.next().ok_or_else(|| anyhow::anyhow!("--filter needs a spec, e.g. difficulty>=4"))?;
+                    filter = eval::scenarios::ScenarioFilter::parse(spec)?;
+                } else if !arg.starts_with("--") && scenario_dir.is_none() {
+                    scenario_dir = Some(PathBuf::from(arg));
+                }
+            }
+            let scenario_dir = scenario_dir.unwrap_or_else(|| data_dir.join("scenarios"));
+
+            if args.iter().any(|a| a == "--lint") {
+                let files = eval::scenarios::scenario_files(&scenario_dir);
+                let (scenarios, issues) = eval::scenarios::lint_dir(&scenario_dir)?;
+                for issue in &issues {
+                    say!("{}", issue);
+                }
+                say!(
+                    "{} file(s), {} scenario(s), {} problem(s) in {:?}",
+                    files.len(),
+                    scenarios.len(),
+                    issues.len(),
+                    scenario_dir
+                );
+                if !issues.is_empty() {
+                    anyhow::bail!("Scenario lint failed");
+                }
+                return Ok(());
+            }
 
-            println!("Loading scenarios from {:?}...", scenario_dir);
+            say!("Loading scenarios from {:?}...", scenario_dir);
 
-            let scenarios = eval::scenarios::load_all_scenarios(&scenario_dir)?;
+            let loaded = eval::scenarios::load_all_scenarios(&scenario_dir)?;
+            let total = loaded.len();
+            let scenarios = filter.apply(loaded);
+            if total > 0 && scenarios.is_empty() {
+                say!("No scenarios match the filter ({} loaded).", total);
+                return Ok(());
+            }
             if scenarios.is_empty() {
-                println!(
+                say!(
                     "No scenarios found. Create JSON files in {:?}",
                     scenario_dir
                 );
-                println!("\nExample scenario format:");
-                println!(
+                say!("\nExample scenario format:");
+                say!(
                     r#"{{
   "id": "arch-001",
   "category": "architecture",
@@ -1700,58 +2701,64 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
   "expected_principles": ["Start with Monolith", "Distributed Systems Fallacies"],
   "expected_thinkers": ["Sam Newman", "Martin Fowler"],
   "anti_principles": ["Always Use Microservices"],
-  "difficulty": 3
+  "difficulty": 3,
+  "tags": ["greenfield"]
 }}"#
                 );
                 return Ok(());
             }
 
-            println!("Running {} scenarios...", scenarios.len());
+            if scenarios.len() < total {
+                say!("Running {} of {} scenarios...", scenarios.len(), total);
+            } else {
+                say!("Running {} scenarios...", scenarios.len());
+            }
             let results = eval::scenarios::run_benchmark(&conn, &provenance, &scenarios)?;
 
             // Print results
-            println!("\n┌─────────────────────────────────────────────────────────────┐");
-            println!("│ 📊 SCENARIO BENCHMARK RESULTS                               │");
-            println!("└─────────────────────────────────────────────────────────────┘\n");
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ 📊 SCENARIO BENCHMARK RESULTS                               │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
 
-            println!("Total scenarios: {}", results.total_scenarios);
-            println!();
+            say!("Total scenarios: {}", results.total_scenarios);
+            say!();
 
             // Aggregate metrics
-            println!("AGGREGATE METRICS:");
+            say!("AGGREGATE METRICS (weighted by difficulty; unweighted in brackets):");
             for k in [1, 3, 5] {
                 if let Some(&p) = results.aggregate.precision_at_k.get(&k) {
-                    println!("   P@{}: {:.1}%", k, p * 100.0);
+                    let plain = results.unweighted.precision_at_k.get(&k).copied().unwrap_or_default();
+                    say!("   P@{}: {:.1}% [{:.1}%]", k, p * 100.0, plain * 100.0);
                 }
             }
-            println!("   Recall: {:.1}%", results.aggregate.recall * 100.0);
-            println!("   NDCG: {:.3}", results.aggregate.ndcg);
-            println!(
+            say!("   Recall: {:.1}% [{:.1}%]", results.aggregate.recall * 100.0, results.unweighted.recall * 100.0);
+            say!("   NDCG: {:.3} [{:.3}]", results.aggregate.ndcg, results.unweighted.ndcg);
+            say!(
                 "   Anti-principle rate: {:.1}%",
                 results.aggregate.anti_principle_rate * 100.0
             );
-            println!("   Avg latency: {}ms", results.aggregate.latency_ms);
-            println!();
+            say!("   Avg latency: {}ms", results.aggregate.latency_ms);
+            say!();
 
             // By category
-            println!("BY CATEGORY:");
+            say!("BY CATEGORY:");
             for (cat, metrics) in &results.by_category {
                 let p3 = metrics.precision_at_k.get(&3).unwrap_or(&0.0);
-                println!(
+                say!(
                     "   {:20} P@3: {:.0}%  Recall: {:.0}%",
                     cat,
                     p3 * 100.0,
                     metrics.recall * 100.0
                 );
             }
-            println!();
+            say!();
 
             // Worst performers
             if !results.worst_performers.is_empty() {
-                println!("WORST PERFORMERS:");
+                say!("WORST PERFORMERS:");
                 for result in results.worst_performers.iter().take(5) {
                     let p3 = result.metrics.precision_at_k.get(&3).unwrap_or(&0.0);
-                    println!(
+                    say!(
                         "   [P@3: {:.0}%] {} - {}",
                         p3 * 100.0,
                         result.scenario_id,
@@ -1761,71 +2768,155 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
             }
         }
 
+        "from-history" => {
+            let out_dir = args
+                .first()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| data_dir.join("scenarios"));
+
+            say!("⛏️  Mining decision history for scenarios...\n");
+
+            let scenarios = eval::scenarios::generate_from_history(&conn)?;
+            if scenarios.is_empty() {
+                say!("No decisions with credited successful outcomes yet.");
+                say!("Record outcomes with: 100minds --outcome <decision-id> --success --principles \"id1,id2\"");
+                return Ok(());
+            }
+
+            for scenario in scenarios.iter().take(10) {
+                say!(
+                    "   [{}] {} → {} expected, {} anti",
+                    scenario.category,
+                    scenario.question,
+                    scenario.expected_principles.len(),
+                    scenario.anti_principles.len()
+                );
+            }
+
+            let written = eval::scenarios::write_scenario_files(&out_dir, &scenarios)?;
+            say!(
+                "\n📄 Wrote {} scenarios to {} file(s) in {:?}",
+                scenarios.len(),
+                written.len(),
+                out_dir
+            );
+            say!("\n✨ Run them with: 100minds --benchmark scenarios {}", out_dir.display());
+        }
+
         "monte-carlo" => {
             let num_sims = args.first().and_then(|s| s.parse().ok()).unwrap_or(1000);
+            let flag = |name: &str| args.iter().position(|a| a == name).and_then(|i| args.get(i + 1));
+            let samples_path = flag("--samples").map(PathBuf::from);
+            let threads = match flag("--threads") {
+                Some(n) => Some(n.parse().map_err(|_| anyhow::anyhow!("--threads expects a number, got {}", n))?),
+                None => None,
+            };
+            let seed = match flag("--seed") {
+                Some(n) => Some(n.parse().map_err(|_| anyhow::anyhow!("--seed expects a number, got {}", n))?),
+                None => None,
+            };
 
-            println!("Running {} Monte Carlo simulations...", num_sims);
+            say!("Running {} Monte Carlo simulations...", num_sims);
 
             let config = eval::monte_carlo::MonteCarloConfig {
                 num_simulations: num_sims,
+                seed,
+                threads,
+                progress: std::io::IsTerminal::is_terminal(&std::io::stderr()),
                 ..Default::default()
             };
 
-            let results = eval::monte_carlo::run_simulation(&conn, &provenance, &config)?;
+            let (results, samples) = eval::monte_carlo::run_simulation_with_samples(&conn, &provenance, &config)?;
+            if let Some(path) = &samples_path {
+                eval::monte_carlo::write_samples_csv(path, &samples)?;
+                say!("Wrote {} samples to {}", samples.len(), path.display());
+            }
 
-            println!("\n┌─────────────────────────────────────────────────────────────┐");
-            println!("│ 🎲 MONTE CARLO RESULTS                                      │");
-            println!("└─────────────────────────────────────────────────────────────┘\n");
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ 🎲 MONTE CARLO RESULTS                                      │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
 
-            println!("Simulations: {}", results.num_simulations);
-            println!("Selection variance: {:.3}", results.selection_variance);
-            println!(
+            say!("Simulations: {}", results.num_simulations);
+            say!("Selection variance: {:.3}", results.selection_variance);
+            say!(
                 "95% CI: [{:.2}, {:.2}]",
                 results.confidence_interval_95.0, results.confidence_interval_95.1
             );
-            println!("Tail risk (<50%): {:.1}%", results.tail_risk * 100.0);
-            println!();
+            say!("Tail risk (<50%): {:.1}%", results.tail_risk * 100.0);
+            say!();
 
             // Top selected
-            println!("TOP 10 MOST SELECTED:");
+            say!("TOP 10 MOST SELECTED:");
             let mut sorted: Vec<_> = results.principle_selection_rates.iter().collect();
             sorted.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
             for (name, rate) in sorted.iter().take(10) {
-                println!("   {:.1}% - {}", *rate * 100.0, name);
+                say!("   {:.1}% - {}", *rate * 100.0, name);
             }
-            println!();
+            say!();
 
             // Outcome distribution
-            println!("SIMULATED OUTCOMES:");
-            println!(
+            say!("SIMULATED OUTCOMES:");
+            say!(
                 "   Success: {:.1}%",
                 results.simulated_outcomes.success_rate * 100.0
             );
-            println!(
+            say!(
                 "   Partial: {:.1}%",
                 results.simulated_outcomes.partial_success_rate * 100.0
             );
-            println!(
+            say!(
                 "   Failure: {:.1}%",
                 results.simulated_outcomes.failure_rate * 100.0
             );
         }
 
         "coverage" => {
-            println!("Analyzing coverage...");
+            say!("Analyzing coverage...");
+            if args.iter().any(|a| a == "--rebuild-coverage") {
+                let decisions = eval::coverage::rebuild_coverage_counters(&conn)?;
+                say!("Rebuilt coverage counters from {} decisions", decisions);
+            }
             let analysis = eval::coverage::analyze_coverage(&conn)?;
             eval::coverage::print_coverage_analysis(&analysis);
         }
 
+        "crossval" => {
+            let folds = args.first().and_then(|s| s.parse().ok()).unwrap_or(5);
+
+            let observations = eval::crossval::load_observations(&conn)?;
+            say!(
+                "🔁 Cross-validating learned confidence over {} recorded principle outcomes...",
+                observations.len()
+            );
+
+            let config = eval::crossval::CrossValConfig {
+                folds,
+                ..Default::default()
+            };
+            let results = match eval::crossval::run_crossval(&observations, &config) {
+                Ok(r) => r,
+                Err(e) => {
+                    say!("\n❌ {}", e);
+                    say!("Record outcomes with: 100minds --outcome <decision-id> --success|--failed --principles \"id1,id2\"");
+                    return Ok(());
+                }
+            };
+            eval::crossval::print_crossval_results(&results);
+
+            let report_path = data_dir.join("crossval_report.json");
+            std::fs::write(&report_path, serde_json::to_string_pretty(&results)?)?;
+            say!("\n📄 Report saved to: {:?}", report_path);
+        }
+
         "all" => {
-            println!("Running full benchmark suite...\n");
+            say!("Running full benchmark suite...\n");
 
             // Coverage analysis (always available)
-            println!("1/3 Coverage Analysis...");
+            say!("1/3 Coverage Analysis...");
             let coverage = eval::coverage::analyze_coverage(&conn)?;
 
             // Monte Carlo (always available)
-            println!("2/3 Monte Carlo (1000 simulations)...");
+            say!("2/3 Monte Carlo (1000 simulations)...");
             let mc_config = eval::monte_carlo::MonteCarloConfig {
                 num_simulations: 1000,
                 ..Default::default()
@@ -1833,9 +2924,9 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
             let monte_carlo = eval::monte_carlo::run_simulation(&conn, &provenance, &mc_config)?;
 
             // Scenarios (if directory exists)
-            println!("3/3 Scenario benchmarks...");
+            say!("3/3 Scenario benchmarks...");
             let scenario_dir = data_dir.join("scenarios");
-            let scenarios = eval::scenarios::load_all_scenarios(&scenario_dir).unwrap_or_default();
+            let scenarios = eval::scenarios::load_all_scenarios(&scenario_dir)?;
             let scenario_results = if !scenarios.is_empty() {
                 Some(eval::scenarios::run_benchmark(
                     &conn,
@@ -1843,7 +2934,7 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
                     &scenarios,
                 )?)
             } else {
-                println!("   (no scenarios found in {:?})", scenario_dir);
+                say!("   (no scenarios found in {:?})", scenario_dir);
                 None
             };
 
@@ -1870,7 +2961,89 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
             let report_path = data_dir.join("benchmark_report.json");
             let json = serde_json::to_string_pretty(&report)?;
             std::fs::write(&report_path, &json)?;
-            println!("\n📄 Full report saved to: {:?}", report_path);
+            say!("\n📄 Full report saved to: {:?}", report_path);
+
+            let run_id =
+                eval::runs::record(&conn, &report, &eval::runs::code_version(), &eval::runs::corpus_version(&conn)?)?;
+            say!("📚 Recorded as benchmark run #{}", run_id);
+            let threshold = eval::runs::BenchmarkConfig::load(&data_dir.join("config.toml"))?.regression_threshold;
+            if let [latest, previous] = eval::runs::list(&conn, 2)?.as_slice() {
+                let changes = eval::runs::compare(previous, latest, threshold);
+                let regressed = changes.iter().filter(|c| c.regression).count();
+                if regressed > 0 {
+                    let since = previous.id;
+                    say!("⚠️  {} metric(s) regressed since run #{}; see --benchmark history", regressed, since);
+                }
+            }
+        }
+
+        "history" => {
+            // [--limit N] [--baseline ID] [--threshold X]
+            let mut limit = 10;
+            let mut baseline = None;
+            let mut threshold = eval::runs::BenchmarkConfig::load(&data_dir.join("config.toml"))?.regression_threshold;
+            let mut rest = args.iter();
+            while let Some(arg) = rest.next() {
+                let mut value = |flag: &str| {
+                    rest.next().ok_or_else(|| anyhow::anyhow!("{} needs a value", flag)).map(String::as_str)
+                };
+                match arg.as_str() {
+                    "--limit" => limit = value("--limit")?.parse()?,
+                    "--baseline" => baseline = Some(value("--baseline")?.parse::<i64>()?),
+                    "--threshold" => threshold = value("--threshold")?.parse()?,
+                    other => anyhow::bail!("Unknown history option {:?}", other),
+                }
+            }
+
+            let runs = eval::runs::list(&conn, limit)?;
+            let Some(latest) = runs.first() else {
+                say!("No benchmark runs recorded yet; run --benchmark all first.");
+                return Ok(());
+            };
+            say!("📚 Benchmark history (newest first)\n");
+            say!(
+                "  {:>4}  {:<19}  {:<12}  {:<18}  {:>7}  {:>6}  {:>6}  {:>6}",
+                "run", "at", "code", "corpus", "overall", "P@3", "recall", "nDCG"
+            );
+            for run in &runs {
+                let metric = |name: &str| run.metrics.get(name).map_or("-".to_string(), |v| format!("{:.3}", v));
+                say!(
+                    "  {:>4}  {:<19}  {:<12}  {:<18}  {:>7}  {:>6}  {:>6}  {:>6}",
+                    run.id,
+                    run.run_at.get(..19).unwrap_or(&run.run_at),
+                    run.code_version,
+                    run.corpus_version,
+                    metric("overall_score"),
+                    metric("precision_at_3"),
+                    metric("recall"),
+                    metric("ndcg")
+                );
+            }
+
+            let base = match baseline {
+                Some(id) => {
+                    Some(eval::runs::get(&conn, id)?.ok_or_else(|| anyhow::anyhow!("No benchmark run #{}", id))?)
+                }
+                None => eval::runs::list(&conn, 2)?.into_iter().nth(1),
+            };
+            let Some(base) = base else {
+                say!("\nOnly one run so far; nothing to compare.");
+                return Ok(());
+            };
+            say!("\nRun #{} against #{} (regression threshold {:.3}):", latest.id, base.id, threshold);
+            if base.corpus_version != latest.corpus_version {
+                say!("   note: the corpus changed ({} → {})", base.corpus_version, latest.corpus_version);
+            }
+            let changes = eval::runs::compare(&base, latest, threshold);
+            for c in &changes {
+                let flag = if c.regression { "  ⚠️ regression" } else { "" };
+                say!("   {:<20} {:.3} → {:.3}  ({:+.3}){}", c.metric, c.before, c.after, c.delta, flag);
+            }
+            let regressed = changes.iter().filter(|c| c.regression).count();
+            if regressed > 0 {
+                anyhow::bail!("{} metric(s) regressed by more than {:.3}", regressed, threshold);
+            }
+            say!("\n✅ No regressions");
         }
 
         "synthetic" => {
@@ -1878,7 +3051,7 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
 
             let output_path = args.get(1).map(PathBuf::from);
 
-            println!("🧪 Generating {} synthetic questions...\n", count);
+            say!("🧪 Generating {} synthetic questions...\n", count);
 
             let config = eval::synthetic::GeneratorConfig::default();
             let seed = std::time::SystemTime::now()
@@ -1895,40 +3068,40 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
                 *domain_counts.entry(q.domain.clone()).or_insert(0) += 1;
             }
 
-            println!("DOMAIN DISTRIBUTION:");
+            say!("DOMAIN DISTRIBUTION:");
             let mut sorted: Vec<_> = domain_counts.iter().collect();
             sorted.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
             for (domain, count) in sorted {
                 let pct = *count as f64 / questions.len() as f64 * 100.0;
                 let bar = "█".repeat((pct / 2.0) as usize);
-                println!("   {:15} {:5} ({:5.1}%) {}", domain, count, pct, bar);
+                say!("   {:15} {:5} ({:5.1}%) {}", domain, count, pct, bar);
             }
 
-            println!("\nSAMPLE QUESTIONS:");
+            say!("\nSAMPLE QUESTIONS:");
             for q in questions.iter().take(10) {
-                println!("   [{}] {}", q.domain, q.question);
+                say!("   [{}] {}", q.domain, q.question);
             }
 
             // Save if output path provided
             if let Some(path) = output_path {
                 let json = serde_json::to_string_pretty(&questions)?;
                 std::fs::write(&path, json)?;
-                println!("\n📄 Saved {} questions to {:?}", questions.len(), path);
+                say!("\n📄 Saved {} questions to {:?}", questions.len(), path);
             }
 
-            println!("\n✨ Use these with: 100minds --benchmark scenarios <path>");
+            say!("\n✨ Use these with: 100minds --benchmark scenarios <path>");
         }
 
         "data-driven" => {
             // Data-driven evaluation: no hardcoded expectations, learn from heuristics
             let count = args.first().and_then(|s| s.parse().ok()).unwrap_or(500);
 
-            println!(
+            say!(
                 "📊 Running data-driven evaluation on {} synthetic questions...\n",
                 count
             );
-            println!("This evaluation uses heuristics (not hardcoded expectations).");
-            println!("It measures: principle diversity, thinker coverage, domain relevance.\n");
+            say!("This evaluation uses heuristics (not hardcoded expectations).");
+            say!("It measures: principle diversity, thinker coverage, domain relevance.\n");
 
             let results = eval::data_driven::run_fast_evaluation(&conn, &provenance, count)?;
             eval::data_driven::print_data_driven_results(&results);
@@ -1937,14 +3110,14 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
             let report_path = data_dir.join("data_driven_report.json");
             let json = serde_json::to_string_pretty(&results)?;
             std::fs::write(&report_path, &json)?;
-            println!("\n📄 Report saved to: {:?}", report_path);
+            say!("\n📄 Report saved to: {:?}", report_path);
         }
 
         "eval-synthetic" => {
             // Generate + evaluate in one step
             let count = args.first().and_then(|s| s.parse().ok()).unwrap_or(100);
 
-            println!(
+            say!(
                 "🧪 Generating and evaluating {} synthetic questions...\n",
                 count
             );
@@ -1987,30 +3160,30 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
                         .push(score);
 
                     if i < 5 {
-                        println!("[{}] Q: {}", i + 1, q.question);
-                        println!(
+                        say!("[{}] Q: {}", i + 1, q.question);
+                        say!(
                             "    Thinkers: {}, Principles: {}, Score: {:.2}",
                             thinkers_count, principles_count, score
                         );
-                        println!();
+                        say!();
                     }
                 }
 
                 if (i + 1) % 25 == 0 {
-                    println!("   Evaluated {}/{}", i + 1, count);
+                    say!("   Evaluated {}/{}", i + 1, count);
                 }
             }
 
-            println!("\nRESULTS:");
-            println!(
+            say!("\nRESULTS:");
+            say!(
                 "   Overall avg score: {:.2}/1.00",
                 total_score / count as f64
             );
 
-            println!("\n   By domain:");
+            say!("\n   By domain:");
             for (domain, scores) in &domain_scores {
                 let avg = scores.iter().sum::<f64>() / scores.len() as f64;
-                println!("      {:15} {:.2} (n={})", domain, avg, scores.len());
+                say!("      {:15} {:.2} (n={})", domain, avg, scores.len());
             }
         }
 
@@ -2019,7 +3192,7 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
             let count = args.first().and_then(|s| s.parse().ok()).unwrap_or(10_000);
             let output_format = args.get(1).map(|s| s.as_str()).unwrap_or("jsonl");
 
-            println!(
+            say!(
                 "🧠 Generating neural bandit training data ({} questions)...\n",
                 count
             );
@@ -2044,25 +3217,25 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
                 "csv" => {
                     let csv_path = base_path.join("neural_training.csv");
                     eval::neural_training::export_to_csv(&batch, &csv_path)?;
-                    println!("\n📄 CSV saved to: {:?}", csv_path);
+                    say!("\n📄 CSV saved to: {:?}", csv_path);
                 }
                 "json" => {
                     let json_path = base_path.join("neural_training.json");
                     let json = serde_json::to_string_pretty(&batch)?;
                     std::fs::write(&json_path, json)?;
-                    println!("\n📄 JSON saved to: {:?}", json_path);
+                    say!("\n📄 JSON saved to: {:?}", json_path);
                 }
                 _ => {
                     // Default: JSONL (one example per line, best for streaming)
                     let jsonl_path = base_path.join("neural_training.jsonl");
                     eval::neural_training::export_to_jsonl(&batch, &jsonl_path)?;
-                    println!("\n📄 JSONL saved to: {:?}", jsonl_path);
+                    say!("\n📄 JSONL saved to: {:?}", jsonl_path);
                 }
             }
 
-            println!("\n💡 Next steps:");
-            println!("   1. Train neural posterior: python scripts/train_neural_bandit.py");
-            println!("   2. Deploy: 100minds --benchmark neural-score <question>");
+            say!("\n💡 Next steps:");
+            say!("   1. Train neural posterior: python scripts/train_neural_bandit.py");
+            say!("   2. Deploy: 100minds --benchmark neural-score <question>");
         }
 
         "neural-score" => {
@@ -2071,8 +3244,8 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
 
             let question = args.join(" ");
             if question.is_empty() {
-                println!("Usage: 100minds --benchmark neural-score <question>");
-                println!("\nExample: 100minds --benchmark neural-score Should we rewrite our monolith?");
+                say!("Usage: 100minds --benchmark neural-score <question>");
+                say!("\nExample: 100minds --benchmark neural-score Should we rewrite our monolith?");
                 return Ok(());
             }
 
@@ -2085,14 +3258,14 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
             };
 
             if !model_dir.join("neural_bandit.onnx").exists() {
-                println!("❌ Neural model not found at {:?}", model_dir);
-                println!("\n💡 Train the model first:");
-                println!("   1. 100minds --benchmark neural-training 10000");
-                println!("   2. python scripts/train_neural_bandit.py --data <path> --export models/neural_bandit.onnx");
+                say!("❌ Neural model not found at {:?}", model_dir);
+                say!("\n💡 Train the model first:");
+                say!("   1. 100minds --benchmark neural-training 10000");
+                say!("   2. python scripts/train_neural_bandit.py --data <path> --export models/neural_bandit.onnx");
                 return Ok(());
             }
 
-            println!("🧠 Loading neural posterior from {:?}...", model_dir);
+            say!("🧠 Loading neural posterior from {:?}...", model_dir);
             let mut neural = NeuralPosterior::new(&model_dir)?;
 
             // Get counsel response to get candidate principles
@@ -2135,15 +3308,15 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
                 is_for_position: true,
             };
 
-            println!("\n┌─────────────────────────────────────────────────────────────┐");
-            println!("│ 🧠 NEURAL POSTERIOR SCORING                                 │");
-            println!("└─────────────────────────────────────────────────────────────┘\n");
-            println!("Question: {}", question);
-            println!("Domain: {}", domain);
-            println!();
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ 🧠 NEURAL POSTERIOR SCORING                                 │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
+            say!("Question: {}", question);
+            say!("Domain: {}", domain);
+            say!();
 
             // Score each principle
-            println!("PRINCIPLE RANKINGS (by UCB score):\n");
+            say!("PRINCIPLE RANKINGS (by UCB score):\n");
             let mut all_results = Vec::new();
 
             for position in &response.positions {
@@ -2158,7 +3331,7 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
 
             for (i, (thinker, result)) in all_results.iter().enumerate().take(10) {
                 let known = if neural.knows_principle(&result.principle_id) { "✓" } else { "?" };
-                println!(
+                say!(
                     "{:2}. [{known}] {:.2} (p={:.2}, u={:.2}) - {} [{}]",
                     i + 1,
                     result.ucb_score,
@@ -2169,119 +3342,717 @@ fn run_benchmark_cmd(subcommand: &str, args: &[String]) -> Result<()> {
                 );
             }
 
-            println!("\nLegend: [✓] known to model  [?] unknown (uses default embedding)");
-            println!("UCB = success_prob + exploration_weight * uncertainty");
+            say!("\nLegend: [✓] known to model  [?] unknown (uses default embedding)");
+            say!("UCB = success_prob + exploration_weight * uncertainty");
+        }
+
+        "scoring" => {
+            // Micro-benchmark of keyword relevance scoring over the whole corpus
+            let rounds: usize = args.first().and_then(|s| s.parse().ok()).unwrap_or(20);
+            let principles = db::get_all_principles(&conn)?;
+            let questions = [
+                "Should we rewrite the legacy billing system or refactor incrementally?",
+                "Our API is slow under load, should we add caching?",
+                "Should we build our own auth or use a hosted provider?",
+                "How do we add tests to tangled code that breaks on every change?",
+                "We're late on the deadline, should we add more engineers?",
+            ];
+            let questions: Vec<&str> = questions.iter().copied().cycle().take(rounds * questions.len()).collect();
+            let bench = minds_mcp::relevance::micro_benchmark(&principles, &questions);
+
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ ⏱️  SCORING MICRO-BENCHMARK                                  │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
+            say!("Principles: {}  Queries: {}", bench.principles, bench.questions);
+            say!("   Uncached features: {:>8.2} µs/principle", bench.uncached_us);
+            say!("   Cached index:      {:>8.2} µs/principle", bench.cached_us);
+            if bench.cached_us > 0.0 {
+                say!("   Speedup:           {:>8.1}x", bench.uncached_us / bench.cached_us);
+            }
+        }
+
+        "snapshot" => {
+            // Counsel latency reading the corpus from SQLite vs the in-memory snapshot
+            let n: usize = args.first().and_then(|s| s.parse().ok()).unwrap_or(50);
+            let questions = [
+                "Should we rewrite the legacy billing system or refactor incrementally?",
+                "Our API is slow under load, should we add caching?",
+                "Should we build our own auth or use a hosted provider?",
+                "We're late on the deadline, should we add more engineers?",
+            ];
+            let load_started = std::time::Instant::now();
+            let snapshot = Arc::new(CorpusSnapshot::load(&conn)?);
+            let load_ms = load_started.elapsed().as_secs_f64() * 1000.0;
+
+            let timings = |use_snapshot: bool| -> Result<Vec<f64>> {
+                let mut engine = CounselEngine::new(&conn, &provenance);
+                if use_snapshot {
+                    engine = engine.with_snapshot(Arc::clone(&snapshot));
+                }
+                let mut ms = Vec::with_capacity(n);
+                for question in questions.iter().cycle().take(n) {
+                    let request = CounselRequest {
+                        question: question.to_string(),
+                        context: CounselContext::default(),
+                        decision_id: None,
+                    };
+                    let started = std::time::Instant::now();
+                    engine.counsel(&request)?;
+                    ms.push(started.elapsed().as_secs_f64() * 1000.0);
+                }
+                ms.sort_by(f64::total_cmp);
+                Ok(ms)
+            };
+            let sqlite = timings(false)?;
+            let memory = timings(true)?;
+            let pct = |ms: &[f64], p: f64| ms[((ms.len() - 1) as f64 * p).round() as usize];
+            let mean = |ms: &[f64]| ms.iter().sum::<f64>() / ms.len() as f64;
+
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ 📸 CORPUS SNAPSHOT LATENCY                                  │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
+            say!("Snapshot: {} principles, loaded in {:.1} ms", snapshot.len(), load_ms);
+            say!("Counsel calls per mode: {}\n", n);
+            say!("             mean      p50      p95");
+            for (label, ms) in [("SQLite  ", &sqlite), ("Snapshot", &memory)] {
+                say!("   {}  {:>6.2}ms {:>6.2}ms {:>6.2}ms", label, mean(ms), pct(ms, 0.5), pct(ms, 0.95));
+            }
+            say!("\n   Mean latency improvement: {:.1}%", (1.0 - mean(&memory) / mean(&sqlite)) * 100.0);
         }
 
         _ => {
-            println!("Unknown benchmark command: {}", subcommand);
-            println!("\nUsage: 100minds --benchmark <command>");
-            println!("\nCommands:");
-            println!("  scenarios [dir]     Run scenario benchmarks (hardcoded expectations)");
-            println!("  monte-carlo [n]     Run n Monte Carlo simulations (default 1000)");
-            println!("  coverage            Analyze thinker/principle coverage");
-            println!("  synthetic [n] [out] Generate n synthetic questions");
-            println!("  neural-training [n] [format] Generate n training examples for neural bandits");
-            println!("  neural-score <q>    Score principles for a question using neural posterior");
-            println!("  eval-synthetic [n]  Generate + evaluate synthetic questions");
-            println!("  data-driven [n]     DATA-DRIVEN evaluation (no hardcoded expectations)");
-            println!("  all                 Run full benchmark suite");
+            say!("Unknown benchmark command: {}", subcommand);
+            say!("\nUsage: 100minds --benchmark <command>");
+            say!("\nCommands:");
+            say!("  scenarios [dir]     Run scenario benchmarks (hardcoded expectations)");
+            say!("  from-history [dir]  Generate scenarios from recorded decision outcomes");
+            say!("  monte-carlo [n] [--samples out.csv] [--threads N] [--seed S]");
+            say!("                      Run n Monte Carlo simulations in parallel (default 1000)");
+            say!("  coverage [--rebuild-coverage]  Analyze thinker/principle coverage");
+            say!("  crossval [k]        k-fold cross-validation of learned confidence (Brier, ECE)");
+            say!("  synthetic [n] [out] Generate n synthetic questions");
+            say!("  neural-training [n] [format] Generate n training examples for neural bandits");
+            say!("  neural-score <q>    Score principles for a question using neural posterior");
+            say!("  eval-synthetic [n]  Generate + evaluate synthetic questions");
+            say!("  data-driven [n]     DATA-DRIVEN evaluation (no hardcoded expectations)");
+            say!("  scoring [rounds]    Micro-benchmark keyword relevance scoring");
+            say!("  snapshot [n]        Counsel latency: SQLite vs in-memory corpus snapshot");
+            say!("  all                 Run full benchmark suite (each run is kept for history)");
+            say!("  history [--limit N] [--baseline ID] [--threshold X]");
+            say!("                      Compare the latest run with the previous (or baseline) one;");
+            say!("                      fails when a metric regressed beyond the threshold");
         }
     }
 
     Ok(())
 }
 
-/// Run analysis commands
-fn run_analyze(subcommand: &str) -> Result<()> {
-    let data_dir = get_data_dir()?;
-    let db_path = data_dir.join("wisdom.db");
-    let conn = db::init_db(&db_path)?;
+/// Interactively merge redundant principles
+fn run_dedupe() -> Result<()> {
+    use std::io::{BufRead, Write};
 
-    match subcommand {
-        "thinkers" => {
-            let analysis = eval::coverage::analyze_coverage(&conn)?;
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    let pairs = eval::coverage::find_redundant_pairs(&conn)?;
+    if pairs.is_empty() {
+        say!("✅ No redundant principle pairs found");
+        return Ok(());
+    }
 
-            println!("\n┌─────────────────────────────────────────────────────────────┐");
-            println!("│ 👥 THINKER ANALYSIS                                         │");
-            println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("{} potentially redundant pairs. For each: [a] keep A, [b] keep B, [s]kip, [q]uit\n", pairs.len());
+    let stdin = std::io::stdin();
+    let mut merged: Vec<String> = Vec::new();
+    for (i, pair) in pairs.iter().enumerate() {
+        if merged.contains(&pair.id_a) || merged.contains(&pair.id_b) {
+            continue;
+        }
+        say!("── {}/{} ({:.0}% similar)", i + 1, pairs.len(), pair.similarity * 100.0);
+        say!("  A  {} [{}]\n     {}", pair.name_a, pair.id_a, pair.description_a);
+        say!("  B  {} [{}]\n     {}", pair.name_b, pair.id_b, pair.description_b);
+        std::print!("> ");
+        std::io::stdout().flush()?;
 
-            // Sort by utilization
-            let mut sorted: Vec<_> = analysis.thinker_utilization.iter().collect();
-            sorted.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        let (keep, drop) = match line.trim() {
+            "a" => (&pair.id_a, &pair.id_b),
+            "b" => (&pair.id_b, &pair.id_a),
+            "q" => break,
+            _ => continue,
+        };
+        let report = minds_mcp::corpus::merge_principles(&conn, keep, std::slice::from_ref(drop))?;
+        say!(
+            "   Merged {} into {}: {} adjustments, {} arms moved; confidence {:.2} → {:.2}\n",
+            drop, keep, report.adjustments_moved, report.arms_merged, report.old_confidence, report.new_confidence
+        );
+        merged.push(drop.clone());
+    }
+    say!("Merged {} principles", merged.len());
+    Ok(())
+}
 
-            println!("UTILIZATION RANKING:");
-            for (i, (name, rate)) in sorted.iter().enumerate() {
-                let bar = "█".repeat((**rate * 20.0) as usize);
-                println!("   {:3}. {:30} {:5.1}% {}", i + 1, name, *rate * 100.0, bar);
+/// Pin, ban or clear principles; list the rules in force
+fn run_policy(action: &str, principle: Option<&str>, reason: Option<&str>, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    sync_policy_config(&conn, &data_dir)?;
+    let principle = || principle.ok_or_else(|| anyhow::anyhow!("policy {} needs a principle name or ID", action));
+
+    match action {
+        "pin" | "ban" => {
+            let rule = if action == "pin" { "pinned" } else { "banned" };
+            let id = policy::set(&conn, principle()?, rule, reason, "cli")?;
+            say!("📌 {} is now {}", id, rule);
+        }
+        "clear" => {
+            if policy::clear(&conn, principle()?)? {
+                say!("Cleared policy for {}", principle()?);
+            } else {
+                say!("No policy set for {}", principle()?);
             }
-            println!();
-
-            // Recommendations
-            if !analysis.recommended_removals.is_empty() {
-                println!("CONSIDER REMOVING (low utilization):");
-                for name in &analysis.recommended_removals {
-                    println!("   • {}", name);
-                }
+        }
+        _ => {
+            let rules = policy::list(&conn)?;
+            if output::machine(mode, &rules)? {
+                return Ok(());
+            }
+            if rules.is_empty() {
+                say!("No policies. Add one with: 100minds policy pin|ban <principle>");
+            }
+            for rule in &rules {
+                say!(
+                    "{:7} {:40} [{}] {}{}",
+                    rule.rule,
+                    rule.principle_name,
+                    rule.source,
+                    rule.principle_id,
+                    rule.reason.as_deref().map(|r| format!(" — {}", r)).unwrap_or_default()
+                );
             }
         }
+    }
+    Ok(())
+}
 
-        "principles" => {
-            let analysis = eval::coverage::analyze_coverage(&conn)?;
-
-            println!("\n┌─────────────────────────────────────────────────────────────┐");
-            println!("│ 📚 PRINCIPLE ANALYSIS                                       │");
-            println!("└─────────────────────────────────────────────────────────────┘\n");
-
-            // Orphans
-            println!(
-                "ORPHAN PRINCIPLES ({} never selected):",
-                analysis.orphan_principles.len()
-            );
-            for p in &analysis.orphan_principles {
-                println!("   • {}", p);
-            }
-            println!();
+/// Curate requires/conflicts/refines relations between principles
+fn run_edges(
+    action: &str,
+    from: Option<&str>,
+    relation: Option<&str>,
+    to: Option<&str>,
+    note: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let edge = || match (from, relation, to) {
+        (Some(from), Some(relation), Some(to)) => Ok((from, relation, to)),
+        _ => Err(anyhow::anyhow!("edges {} needs: <principle> requires|conflicts|refines <principle>", action)),
+    };
 
-            // Redundancy
-            if !analysis.principle_redundancy.is_empty() {
-                println!("POTENTIALLY REDUNDANT PAIRS:");
-                for (a, b, sim) in &analysis.principle_redundancy {
-                    println!("   {:.0}% - \"{}\" ↔ \"{}\"", sim * 100.0, a, b);
-                }
+    match action {
+        "add" => {
+            let (from, relation, to) = edge()?;
+            let edge = principle_graph::add(&conn, from, relation, to, note)?;
+            if !output::machine(mode, &edge)? {
+                say!("🔗 {} {} {}", edge.from_name, edge.relation, edge.to_name);
             }
         }
+        "remove" => {
+            let (from, relation, to) = edge()?;
+            if principle_graph::remove(&conn, from, relation, to)? {
+                say!("Removed: {} {} {}", from, relation, to);
+            } else {
+                say!("No such relation: {} {} {}", from, relation, to);
+            }
+        }
+        _ => {
+            let edges = principle_graph::list(&conn)?;
+            if output::machine(mode, &edges)? {
+                return Ok(());
+            }
+            if edges.is_empty() {
+                say!("No relations. Add one with: 100minds edges add \"Extract Hotspots\" requires \"Profile First\"");
+            }
+            for edge in &edges {
+                say!(
+                    "{:32} {:9} {}{}",
+                    truncate_str(&edge.from_name, 32),
+                    edge.relation,
+                    edge.to_name,
+                    edge.note.as_deref().map(|n| format!(" — {}", n)).unwrap_or_default()
+                );
+            }
+        }
+    }
+    Ok(())
+}
 
-        "domains" => {
-            let analysis = eval::coverage::analyze_coverage(&conn)?;
+/// Thinker pack management
+async fn run_pack(action: &str, target: Option<&str>, require_signed: bool, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    std::fs::create_dir_all(&data_dir)?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    let target = || target.ok_or_else(|| anyhow::anyhow!("pack {} needs a target", action));
+
+    match action {
+        "install" => {
+            let pack = packs::ThinkerPack::fetch(target()?).await?;
+            let report = packs::install(&conn, &pack, require_signed)?;
+            let label = format!("{} pack {} {}", if report.upgraded { "upgraded" } else { "installed" }, report.id, report.version);
+            stats::cohorts::mark_event(&conn, &label, chrono::Utc::now())?;
+            if output::machine(mode, &report)? {
+                return Ok(());
+            }
+            say!(
+                "📦 {} {} {}: {} thinkers, {} principles ({} with embeddings)",
+                if report.upgraded { "Upgraded" } else { "Installed" },
+                report.id,
+                report.version,
+                report.thinkers,
+                report.principles,
+                report.embeddings
+            );
+            match &report.signed_by {
+                Some(key) => say!("   Signed by {}", key),
+                None => say!("   ⚠️ Unsigned pack"),
+            }
+        }
+        "remove" => {
+            let id = target()?;
+            let thinkers = packs::remove(&conn, id)?;
+            if output::machine(mode, &serde_json::json!({ "removed": id, "thinkers": thinkers }))? {
+                return Ok(());
+            }
+            say!("🗑️ Removed {} ({} thinkers)", id, thinkers);
+        }
+        "sign" => {
+            let path = PathBuf::from(target()?);
+            let mut pack = packs::ThinkerPack::from_json(&std::fs::read_to_string(&path)?)?;
+            let provenance = Provenance::init(&data_dir.join("agent.key"))?;
+            pack.sign(&provenance)?;
+            packs::save(&pack, &path)?;
+            say!("✍️ Signed {} with {}", path.display(), provenance.public_key_hex());
+        }
+        _ => {
+            let installed = packs::list(&conn)?;
+            if output::machine(mode, &installed)? {
+                return Ok(());
+            }
+            if installed.is_empty() {
+                say!("No thinker packs installed. Install one with: 100minds pack install <path|url>");
+            }
+            for pack in &installed {
+                say!(
+                    "{:20} {:10} {:3} thinkers {:4} principles  {}  {}",
+                    pack.id,
+                    pack.version,
+                    pack.thinkers,
+                    pack.principles,
+                    if pack.signed_by.is_some() { "signed" } else { "unsigned" },
+                    pack.name
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run analysis commands
+/// Outcomes per template leaf, with suggested tree edits as JSON Patches
+fn run_template_feedback(conn: &rusqlite::Connection, mode: OutputMode) -> Result<()> {
+    let reports = template_feedback::analyze(conn)?;
+    if output::machine(mode, &reports)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🌳 TEMPLATE OUTCOMES                                        │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    if reports.is_empty() {
+        say!("No outcomes name a template yet.");
+        say!("Record them with: 100minds outcome <id> --success --template <id> --template-path 0,1,0");
+        return Ok(());
+    }
+    for report in &reports {
+        say!(
+            "{} ({}): {} outcomes, {:.0}% success",
+            report.name,
+            report.template_id,
+            report.outcomes,
+            report.success_rate * 100.0
+        );
+        for leaf in &report.leaves {
+            say!(
+                "   {:45} {:3} ok {:3} failed",
+                truncate_str(&leaf.label, 45),
+                leaf.successes,
+                leaf.failures
+            );
+        }
+        for suggestion in &report.suggestions {
+            say!("   ⚠️  {}", suggestion.message);
+        }
+        if !report.suggestions.is_empty() {
+            say!("\n   Patch (review before applying):");
+            for line in serde_json::to_string_pretty(&report.patch)?.lines() {
+                // JSON to copy: never transliterated
+                std::println!("   {}", line);
+            }
+        }
+        say!();
+    }
+    Ok(())
+}
+
+fn run_themes(conn: &rusqlite::Connection, mode: OutputMode) -> Result<()> {
+    let mut engine = embeddings::shared_engine(conn).map(|m| m.lock().unwrap_or_else(|e| e.into_inner()));
+    let report = themes::analyze(conn, engine.as_deref_mut(), None)?;
+    if output::machine(mode, &report)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧭 DECISION THEMES                                          │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    if report.themes.is_empty() {
+        say!("No decisions recorded yet.");
+        return Ok(());
+    }
+    say!("{} decisions, clustered by {}\n", report.decisions, report.vectors);
+    for theme in &report.themes {
+        let rate = match theme.success_rate {
+            Some(rate) => format!("{:.0}% success over {} outcomes", rate * 100.0, theme.outcomes),
+            None => "no outcomes yet".to_string(),
+        };
+        let marker = if theme.struggling { " ⚠️  struggling" } else { "" };
+        say!("{} ({} decisions, {}){}", theme.label, theme.decisions, rate, marker);
+        for example in &theme.examples {
+            say!("   • {}", truncate_str(example, 70));
+        }
+        say!();
+    }
+    Ok(())
+}
+
+fn run_mark_event(label: &str, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let mark = stats::cohorts::mark_event(&conn, label, chrono::Utc::now())?;
+    if output::machine(mode, &mark)? {
+        return Ok(());
+    }
+    say!("🔖 Marked \"{}\" at {}", mark.label, mark.marked_at);
+    say!("   Compare before/after with: 100minds analyze cohorts");
+    Ok(())
+}
+
+fn run_cohorts(conn: &rusqlite::Connection, mode: OutputMode) -> Result<()> {
+    let comparisons = stats::cohorts::compare(conn)?;
+    if output::machine(mode, &comparisons)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🔖 BEFORE / AFTER COHORTS                                   │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    if comparisons.is_empty() {
+        say!("No event marks yet. Bookmark a change with: 100minds mark-event \"imported SRE pack\"");
+        return Ok(());
+    }
+    let pct = |v: Option<f64>| v.map(|v| format!("{:.0}%", v * 100.0)).unwrap_or_else(|| "-".to_string());
+    let hours = |v: Option<f64>| v.map(|v| format!("{:.1}h", v)).unwrap_or_else(|| "-".to_string());
+    for c in &comparisons {
+        say!("{} ({})", c.mark.label, c.mark.marked_at.get(..10).unwrap_or(&c.mark.marked_at));
+        say!("   {:8} {:>9} {:>9} {:>9} {:>12} {:>10}", "", "Decisions", "Outcomes", "Success", "To outcome", "Diversity");
+        for (name, cohort) in [("before", &c.before), ("after", &c.after)] {
+            say!(
+                "   {:8} {:>9} {:>9} {:>9} {:>12} {:>10}",
+                name,
+                cohort.decisions,
+                cohort.outcomes,
+                pct(cohort.success_rate),
+                hours(cohort.median_hours_to_outcome),
+                pct(cohort.principle_diversity)
+            );
+        }
+        if let Some(change) = c.success_rate_change {
+            let test = c.significance.as_ref().map_or(String::new(), |t| format!(" ({})", t.verdict()));
+            say!("   {} success {:+.0} pts{}", if change >= 0.0 { "📈" } else { "📉" }, change * 100.0, test);
+        }
+        say!();
+    }
+    Ok(())
+}
+
+/// Failures by stage, and the principles whose failures cluster there
+fn run_failures(conn: &rusqlite::Connection, mode: OutputMode) -> Result<()> {
+    let report = stats::failures::analyze(conn)?;
+    if output::machine(mode, &report)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧯 FAILURE STAGES                                           │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    if report.classified == 0 {
+        say!("No failures with a stage yet ({} failures recorded).", report.failures);
+        say!("Record one with: 100minds outcome <decision-id> --failed --stage planning --reason underestimated");
+        return Ok(());
+    }
+    say!("{} of {} failures have a stage\n", report.classified, report.failures);
+    for stage in &report.stages {
+        say!("{:15} {:>4}  {:>4.0}%", stage.stage.as_str(), stage.failures, stage.share * 100.0);
+        if !stage.reasons.is_empty() {
+            let reasons: Vec<String> = stage.reasons.iter().map(|(r, n)| format!("{} ×{}", r, n)).collect();
+            say!("   Reasons: {}", reasons.join(", "));
+        }
+        for p in &stage.principles {
+            say!("   {:40} {:>3} failures  lift {:.1}", truncate_str(&p.name, 40), p.failures, p.lift);
+        }
+        say!();
+    }
+    Ok(())
+}
+
+/// Success of decisions whose checklists and blind spots were acted on, against those that weren't
+fn run_adherence(conn: &rusqlite::Connection, mode: OutputMode) -> Result<()> {
+    let report = stats::adherence::analyze(conn)?;
+    if output::machine(mode, &report)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ ✅ ADVICE ADHERENCE                                         │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    if report.decisions == 0 {
+        say!("No decisions with an outcome and a checklist or recorded blind spots yet.");
+        say!("Tick items with: 100minds checklist <decision-id> --done <item-id>");
+        return Ok(());
+    }
+    say!(
+        "{} decisions, mean adherence {:.0}% (followed = at least {:.0}% ticked or resolved)\n",
+        report.decisions,
+        report.mean_adherence.unwrap_or(0.0) * 100.0,
+        stats::adherence::FOLLOWED_THRESHOLD * 100.0
+    );
+    let rate = |r: &stats::intervals::RateInterval| match r.trials {
+        0 => "-".to_string(),
+        n => format!("{:.0}% of {}", r.rate * 100.0, n),
+    };
+    let line = |name: &str, split: &stats::adherence::AdherenceSplit| {
+        let change = split.significance.as_ref().map_or(String::new(), |t| {
+            format!("  {:+.0} pts ({})", t.difference * 100.0, t.verdict())
+        });
+        let (followed, skipped) = (rate(&split.followed), rate(&split.skipped));
+        say!("   {:36} {:>12} {:>12}{}", truncate_str(name, 36), followed, skipped, change);
+    };
+    say!("   {:36} {:>12} {:>12}", "", "Followed", "Skipped");
+    line("All advice", &report.overall);
+    line("Checklist items", &report.checklist);
+    line("Blind spots", &report.blind_spots);
+    for (heading, groups) in [("Principles", &report.principles), ("Templates", &report.templates)] {
+        if groups.is_empty() {
+            continue;
+        }
+        say!("\n{}", heading);
+        for g in groups {
+            line(&g.name, &g.split);
+        }
+    }
+    Ok(())
+}
+
+/// Confidence and success-rate trends for the corpus, a principle or a domain
+fn run_trends(target: Option<&str>, csv: bool, daily: bool, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    stats::timeseries::record_snapshot(&conn, chrono::Utc::now())?;
+    let mut series = stats::timeseries::series(&conn, target)?;
+    if !daily {
+        series.points = stats::timeseries::monthly(&series.points);
+    }
+    if csv {
+        std::print!("{}", stats::timeseries::to_csv(&series.points));
+        return Ok(());
+    }
+    if output::machine(mode, &series)? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 📈 TRENDS: {:48} │", truncate_str(&series.label, 48));
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    let confidence: Vec<Option<f64>> = series.points.iter().map(|p| p.confidence).collect();
+    let success: Vec<Option<f64>> = series.points.iter().map(|p| p.success_rate).collect();
+    for (name, values) in [("Confidence", &confidence), ("Success rate", &success)] {
+        let present: Vec<f64> = values.iter().flatten().copied().collect();
+        let change = match (present.first(), present.last()) {
+            (Some(first), Some(last)) if present.len() > 1 => {
+                format!("{:.0}% → {:.0}% ({:+.0} pts)", first * 100.0, last * 100.0, (last - first) * 100.0)
+            }
+            (_, Some(last)) => format!("{:.0}%", last * 100.0),
+            _ => "no data".to_string(),
+        };
+        say!("{:13} {}  {}", name, stats::timeseries::sparkline(values), change);
+    }
+
+    say!("\n{:10} {:>10} {:>9} {:>12}", if daily { "Date" } else { "Month" }, "Confidence", "Outcomes", "Success");
+    let pct = |v: Option<f64>| v.map(|v| format!("{:.0}%", v * 100.0)).unwrap_or_else(|| "-".to_string());
+    for point in &series.points {
+        say!("{:10} {:>10} {:>9} {:>12}", point.date, pct(point.confidence), point.outcomes, pct(point.success_rate));
+    }
+    if series.points.len() < 2 {
+        say!("\nOne snapshot so far; `100minds daemon` records one a day.");
+    }
+    Ok(())
+}
+
+fn run_analyze(subcommand: &str, rebuild_coverage: bool, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let db_path = data_dir.join("wisdom.db");
+    let conn = db::init_db(&db_path)?;
+
+    if rebuild_coverage {
+        let decisions = eval::coverage::rebuild_coverage_counters(&conn)?;
+        tracing::info!("Rebuilt coverage counters from {} decisions", decisions);
+    }
+
+    if subcommand == "templates" {
+        return run_template_feedback(&conn, mode);
+    }
+    if subcommand == "themes" {
+        return run_themes(&conn, mode);
+    }
+    if subcommand == "cohorts" {
+        return run_cohorts(&conn, mode);
+    }
+    if subcommand == "failures" {
+        return run_failures(&conn, mode);
+    }
+    if subcommand == "adherence" {
+        return run_adherence(&conn, mode);
+    }
+
+    let analysis = eval::coverage::analyze_coverage(&conn)?;
+    if subcommand != "thinkers" && output::machine(mode, &analysis)? {
+        return Ok(());
+    }
+
+    match subcommand {
+        "thinkers" => {
+            // Reliability from recorded outcomes
+            let mut stmt = conn.prepare(
+                "SELECT t.name, r.successes, r.failures
+                 FROM thinker_reliability r
+                 JOIN thinkers t ON t.id = r.thinker_id
+                 ORDER BY r.thinker_id",
+            )?;
+            let mut reliability: Vec<(String, i64, i64, f64)> = stmt
+                .query_map([], |row| {
+                    let (s, f): (i64, i64) = (row.get(1)?, row.get(2)?);
+                    Ok((row.get(0)?, s, f, db::reliability_factor(s, f)))
+                })?
+                .filter_map(|r| r.ok())
+                .collect();
+            reliability.sort_by(|a, b| a.3.partial_cmp(&b.3).unwrap());
+
+            let report = serde_json::json!({
+                "thinker_utilization": analysis.thinker_utilization,
+                "reliability": reliability
+                    .iter()
+                    .map(|(name, s, f, factor)| serde_json::json!({
+                        "thinker": name, "successes": s, "failures": f, "factor": factor,
+                    }))
+                    .collect::<Vec<_>>(),
+                "recommended_removals": analysis.recommended_removals,
+            });
+            if output::machine(mode, &report)? {
+                return Ok(());
+            }
+
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ 👥 THINKER ANALYSIS                                         │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
+
+            // Sort by utilization
+            let mut sorted: Vec<_> = analysis.thinker_utilization.iter().collect();
+            sorted.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
+
+            say!("UTILIZATION RANKING:");
+            for (i, (name, rate)) in sorted.iter().enumerate() {
+                let bar = "█".repeat((**rate * 20.0) as usize);
+                say!("   {:3}. {:30} {:5.1}% {}", i + 1, name, *rate * 100.0, bar);
+            }
+            say!();
+
+            if !reliability.is_empty() {
+                say!("RELIABILITY (ranking factor from outcomes, least reliable first):");
+                for (name, s, f, factor) in &reliability {
+                    say!("   {:30} ×{:.2}  ({} ✓ / {} ✗)", name, factor, s, f);
+                }
+                say!();
+            }
+
+            // Recommendations
+            if !analysis.recommended_removals.is_empty() {
+                say!("CONSIDER REMOVING (low utilization):");
+                for name in &analysis.recommended_removals {
+                    say!("   • {}", name);
+                }
+            }
+        }
+
+        "principles" => {
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ 📚 PRINCIPLE ANALYSIS                                       │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
+
+            // Orphans
+            say!(
+                "ORPHAN PRINCIPLES ({} never selected):",
+                analysis.orphan_principles.len()
+            );
+            for p in &analysis.orphan_principles {
+                say!("   • {}", p);
+            }
+            say!();
+
+            // Redundancy
+            if !analysis.principle_redundancy.is_empty() {
+                say!("POTENTIALLY REDUNDANT PAIRS:");
+                for (a, b, sim) in &analysis.principle_redundancy {
+                    say!("   {:.0}% - \"{}\" ↔ \"{}\"", sim * 100.0, a, b);
+                }
+            }
+        }
 
-            println!("\n┌─────────────────────────────────────────────────────────────┐");
-            println!("│ 🏷️ DOMAIN ANALYSIS                                          │");
-            println!("└─────────────────────────────────────────────────────────────┘\n");
+        "domains" => {
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ 🏷️ DOMAIN ANALYSIS                                          │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
 
             // Sort by coverage
             let mut sorted: Vec<_> = analysis.domain_coverage.iter().collect();
             sorted.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
 
-            println!("DOMAIN COVERAGE:");
+            say!("DOMAIN COVERAGE:");
             for (domain, coverage) in &sorted {
                 let bar = "█".repeat((**coverage * 20.0) as usize);
-                println!("   {:25} {:5.1}% {}", domain, *coverage * 100.0, bar);
+                say!("   {:25} {:5.1}% {}", domain, *coverage * 100.0, bar);
             }
-            println!();
+            say!();
 
             // Gaps
             if !analysis.recommended_additions.is_empty() {
-                println!("RECOMMENDED ADDITIONS:");
+                say!("RECOMMENDED ADDITIONS:");
                 for suggestion in &analysis.recommended_additions {
-                    println!("   ➕ {} ({})", suggestion.name, suggestion.domain);
-                    println!("      {}", suggestion.reason);
+                    say!("   ➕ {} ({})", suggestion.name, suggestion.domain);
+                    say!("      {}", suggestion.reason);
                 }
             }
         }
 
         _ => {
-            let analysis = eval::coverage::analyze_coverage(&conn)?;
             eval::coverage::print_coverage_analysis(&analysis);
         }
     }
@@ -2290,26 +4061,27 @@ fn run_analyze(subcommand: &str) -> Result<()> {
 }
 
 /// Run Thompson Sampling commands
-fn run_thompson(subcommand: &str) -> Result<()> {
+fn run_thompson(subcommand: &str, mode: OutputMode) -> Result<()> {
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
 
     match subcommand {
         "stats" => {
-            println!("Loading Thompson Sampling statistics...");
-
             let selector = eval::thompson::ThompsonSelector::from_db(&conn)?;
             let stats = selector.get_all_stats();
+            if output::machine(mode, &stats)? {
+                return Ok(());
+            }
 
-            println!("\n┌─────────────────────────────────────────────────────────────┐");
-            println!("│ 🎰 THOMPSON SAMPLING STATISTICS                             │");
-            println!("└─────────────────────────────────────────────────────────────┘\n");
+            say!("\n┌─────────────────────────────────────────────────────────────┐");
+            say!("│ 🎰 THOMPSON SAMPLING STATISTICS                             │");
+            say!("└─────────────────────────────────────────────────────────────┘\n");
 
-            println!("TOP 20 PRINCIPLES BY MEAN:");
+            say!("TOP 20 PRINCIPLES BY MEAN:");
             for (i, stat) in stats.iter().take(20).enumerate() {
                 let ci = format!("[{:.2}, {:.2}]", stat.ci_lower, stat.ci_upper);
-                println!(
+                say!(
                     "   {:2}. {:35} mean: {:.2}  CI: {:15}  n: {:.0}",
                     i + 1,
                     truncate_str(&stat.name, 35),
@@ -2318,9 +4090,9 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                     stat.total_observations
                 );
             }
-            println!();
+            say!();
 
-            println!("BOTTOM 10 (needs more data or poor performance):");
+            say!("BOTTOM 10 (needs more data or poor performance):");
             for stat in stats.iter().rev().take(10) {
                 let ci = format!("[{:.2}, {:.2}]", stat.ci_lower, stat.ci_upper);
                 let ci_width = stat.ci_upper - stat.ci_lower;
@@ -2329,7 +4101,7 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                 } else {
                     ""
                 };
-                println!(
+                say!(
                     "   {:35} mean: {:.2}  CI: {:15}  {}",
                     truncate_str(&stat.name, 35),
                     stat.mean,
@@ -2340,20 +4112,20 @@ fn run_thompson(subcommand: &str) -> Result<()> {
         }
 
         "init" => {
-            println!("Initializing Thompson Sampling schema...");
+            say!("Initializing Thompson Sampling schema...");
             eval::thompson::init_thompson_schema(&conn)?;
-            println!("Done. Thompson arms table created.");
+            say!("Done. Thompson arms table created.");
         }
 
         "persist" => {
-            println!("Persisting Thompson parameters to database...");
+            say!("Persisting Thompson parameters to database...");
             let selector = eval::thompson::ThompsonSelector::from_db(&conn)?;
             selector.persist_to_db(&conn)?;
-            println!("Done. learned_confidence updated from Thompson means.");
+            say!("Done. learned_confidence updated from Thompson means.");
         }
 
         "remediate" => {
-            println!("Analyzing poor performers for remediation...\n");
+            say!("Analyzing poor performers for remediation...\n");
 
             // Find principles with low confidence
             let mut stmt = conn.prepare(
@@ -2372,11 +4144,11 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                 .collect();
 
             if poor_performers.is_empty() {
-                println!("✅ No poor performers found (all principles >= 0.3 confidence)");
+                say!("✅ No poor performers found (all principles >= 0.3 confidence)");
                 return Ok(());
             }
 
-            println!("Found {} poor performers:\n", poor_performers.len());
+            say!("Found {} poor performers:\n", poor_performers.len());
 
             let mut to_reset = Vec::new();
             let mut to_archive = Vec::new();
@@ -2384,7 +4156,7 @@ fn run_thompson(subcommand: &str) -> Result<()> {
             for (id, name, conf, adj_count) in &poor_performers {
                 if *adj_count < 5 {
                     // Not enough data - give second chance
-                    println!(
+                    say!(
                         "   🔄 RESET: {} (conf: {:.2}, only {} samples)",
                         truncate_str(name, 40),
                         conf,
@@ -2393,7 +4165,7 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                     to_reset.push(id.clone());
                 } else if *conf < 0.15 {
                     // Sufficient data, still failing - archive
-                    println!(
+                    say!(
                         "   📦 ARCHIVE: {} (conf: {:.2}, {} samples)",
                         truncate_str(name, 40),
                         conf,
@@ -2402,7 +4174,7 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                     to_archive.push(id.clone());
                 } else {
                     // Low but recovering - leave alone
-                    println!(
+                    say!(
                         "   ⏳ WATCH: {} (conf: {:.2}, {} samples)",
                         truncate_str(name, 40),
                         conf,
@@ -2411,11 +4183,11 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                 }
             }
 
-            println!();
+            say!();
 
             // Apply remediation
             if !to_reset.is_empty() {
-                println!(
+                say!(
                     "Resetting {} principles to baseline (0.5)...",
                     to_reset.len()
                 );
@@ -2425,21 +4197,21 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                         [id],
                     )?;
                 }
-                println!("✅ Reset complete");
+                say!("✅ Reset complete");
             }
 
             if !to_archive.is_empty() {
-                println!(
+                say!(
                     "\n⚠️  {} principles marked for archive (confidence < 0.15 with 5+ samples)",
                     to_archive.len()
                 );
-                println!("   Run with --thompson archive to move them to inactive status");
+                say!("   Run with --thompson archive to move them to inactive status");
             }
 
-            println!("\n📊 Summary:");
-            println!("   Reset (second chance): {}", to_reset.len());
-            println!("   Recommend archive: {}", to_archive.len());
-            println!(
+            say!("\n📊 Summary:");
+            say!("   Reset (second chance): {}", to_reset.len());
+            say!("   Recommend archive: {}", to_archive.len());
+            say!(
                 "   Watching (recovering): {}",
                 poor_performers.len() - to_reset.len() - to_archive.len()
             );
@@ -2447,7 +4219,7 @@ fn run_thompson(subcommand: &str) -> Result<()> {
 
         "explore" => {
             // Epsilon-greedy exploration: boost random poor performers
-            println!("Running exploration round (boosting 10 random poor performers)...\n");
+            say!("Running exploration round (boosting 10 random poor performers)...\n");
 
             conn.execute(
                 "UPDATE principles
@@ -2461,14 +4233,14 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                 [],
             )?;
 
-            println!("✅ Boosted 10 random poor performers to 0.6 confidence");
-            println!("   These will now be selected more often to gather data.");
-            println!("   Run --thompson persist after outcomes to update from real data.");
+            say!("✅ Boosted 10 random poor performers to 0.6 confidence");
+            say!("   These will now be selected more often to gather data.");
+            say!("   Run --thompson persist after outcomes to update from real data.");
         }
 
         "cull" => {
             // Auto-cull hopeless principles
-            println!("🗑️  AUTO-CULL: Removing hopeless principles...\n");
+            say!("🗑️  AUTO-CULL: Removing hopeless principles...\n");
 
             // Find principles to cull: low confidence + sufficient data
             let culled: Vec<(String, String, f64, i64)> = conn.prepare(
@@ -2483,13 +4255,13 @@ fn run_thompson(subcommand: &str) -> Result<()> {
             .collect();
 
             if culled.is_empty() {
-                println!("✅ No principles to cull (none below 0.15 with 5+ samples)");
+                say!("✅ No principles to cull (none below 0.15 with 5+ samples)");
                 return Ok(());
             }
 
-            println!("Culling {} principles:\n", culled.len());
+            say!("Culling {} principles:\n", culled.len());
             for (id, name, conf, samples) in &culled {
-                println!("   ❌ {} (conf: {:.2}, {} samples)", name, conf, samples);
+                say!("   ❌ {} (conf: {:.2}, {} samples)", name, conf, samples);
 
                 // Archive by copying to archive table, then soft-delete (set confidence to -1)
                 conn.execute(
@@ -2516,16 +4288,16 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                 [],
             )?;
 
-            println!(
+            say!(
                 "\n✅ Culled {} principles (archived, not deleted)",
                 culled.len()
             );
-            println!("   To restore: SELECT * FROM archived_principles");
+            say!("   To restore: SELECT * FROM archived_principles");
         }
 
         "discover" => {
             // Mine patterns from successful outcomes to discover new principle candidates
-            println!("🔍 DISCOVERY: Mining patterns from successful outcomes...\n");
+            say!("🔍 DISCOVERY: Mining patterns from successful outcomes...\n");
 
             // Get successful decisions with their context
             let successes: Vec<(String, String)> = conn
@@ -2544,7 +4316,7 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                 .filter_map(|r| r.ok())
                 .collect();
 
-            println!("Analyzing {} successful decisions...\n", successes.len());
+            say!("Analyzing {} successful decisions...\n", successes.len());
 
             // Extract common patterns/keywords
             let mut keyword_counts: std::collections::HashMap<String, usize> =
@@ -2566,16 +4338,16 @@ fn run_thompson(subcommand: &str) -> Result<()> {
 
             // Find patterns that appear often in successes
             let mut sorted: Vec<_> = keyword_counts.into_iter().collect();
-            sorted.sort_by(|a, b| b.1.cmp(&a.1));
+            sorted.sort_by_key(|b| std::cmp::Reverse(b.1));
 
-            println!("TOP KEYWORDS IN SUCCESSFUL DECISIONS:");
+            say!("TOP KEYWORDS IN SUCCESSFUL DECISIONS:");
             for (word, count) in sorted.iter().take(20) {
                 let bar = "█".repeat(*count / 5);
-                println!("   {:20} {:4} {}", word, count, bar);
+                say!("   {:20} {:4} {}", word, count, bar);
             }
 
             // Get top principles from successes
-            println!("\nTOP PRINCIPLES IN SUCCESSFUL OUTCOMES:");
+            say!("\nTOP PRINCIPLES IN SUCCESSFUL OUTCOMES:");
             let top_principles: Vec<(String, i64)> = conn
                 .prepare(
                     "SELECT p.name, COUNT(*) as cnt
@@ -2592,11 +4364,11 @@ fn run_thompson(subcommand: &str) -> Result<()> {
 
             for (name, count) in &top_principles {
                 let bar = "█".repeat(*count as usize / 3);
-                println!("   {:40} {:3} {}", truncate_str(name, 40), count, bar);
+                say!("   {:40} {:3} {}", truncate_str(name, 40), count, bar);
             }
 
             // Suggest new principles based on gaps
-            println!("\n💡 SUGGESTED NEW PRINCIPLES (based on patterns):");
+            say!("\n💡 SUGGESTED NEW PRINCIPLES (based on patterns):");
             let suggestions = vec![
                 (
                     "Pattern: 'microservices' frequent",
@@ -2617,16 +4389,16 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                     .iter()
                     .any(|(w, c)| *c > 10 && pattern.to_lowercase().contains(w))
                 {
-                    println!("   • {}", suggestion);
+                    say!("   • {}", suggestion);
                 }
             }
 
-            println!("\n📊 Run --thompson yuzu to generate Yuzu-compatible actions");
+            say!("\n📊 Run --thompson yuzu to generate Yuzu-compatible actions");
         }
 
         "yuzu" => {
             // Generate Yuzu-compatible actions for the learning loop
-            println!("🍋 YUZU INTEGRATION: Generating automated actions...\n");
+            say!("🍋 YUZU INTEGRATION: Generating automated actions...\n");
 
             // Count what needs attention
             let cull_count: i64 = conn.query_row(
@@ -2690,19 +4462,19 @@ fn run_thompson(subcommand: &str) -> Result<()> {
                 }
             });
 
-            println!("{}", serde_json::to_string_pretty(&actions)?);
+            std::println!("{}", serde_json::to_string_pretty(&actions)?);
 
-            println!("\n---");
-            println!("Add to Yuzu daemon cron:");
-            println!("  0 * * * *  100minds --thompson persist  # hourly");
-            println!("  0 6 * * *  100minds --thompson explore  # daily 6am");
-            println!("  0 0 * * 0  100minds --thompson cull     # weekly Sunday midnight");
-            println!("  0 0 * * 0  100minds --thompson discover # weekly Sunday midnight");
+            say!("\n---");
+            say!("Run `100minds daemon` to schedule these without cron, or add to Yuzu daemon cron:");
+            say!("  0 * * * *  100minds --thompson persist  # hourly");
+            say!("  0 6 * * *  100minds --thompson explore  # daily 6am");
+            say!("  0 0 * * 0  100minds --thompson cull     # weekly Sunday midnight");
+            say!("  0 0 * * 0  100minds --thompson discover # weekly Sunday midnight");
         }
 
         "contextual" => {
             // Initialize contextual Thompson Sampling from existing data
-            println!("🎯 CONTEXTUAL LEARNING: Initializing domain-specific Thompson arms...\n");
+            say!("🎯 CONTEXTUAL LEARNING: Initializing domain-specific Thompson arms...\n");
 
             // Create contextual_arms table if needed
             conn.execute(
@@ -2746,7 +4518,7 @@ fn run_thompson(subcommand: &str) -> Result<()> {
 
                 let count = principles.len();
                 if count > 0 {
-                    println!("  📂 {} domain: {} principles", domain_name, count);
+                    say!("  📂 {} domain: {} principles", domain_name, count);
 
                     for (id, _name, conf) in &principles {
                         // Initialize with prior based on learned_confidence
@@ -2805,117 +4577,48 @@ fn run_thompson(subcommand: &str) -> Result<()> {
             let final_count: i64 =
                 conn.query_row("SELECT COUNT(*) FROM contextual_arms", [], |row| row.get(0))?;
 
-            println!(
+            say!(
                 "\n✅ Created {} contextual arms across all domains",
                 final_count
             );
-            println!("   Contextual learning will now adjust confidence per-domain.");
-            println!("\n   To view: sqlite3 wisdom.db 'SELECT domain, COUNT(*) FROM contextual_arms GROUP BY domain'");
+            say!("   Contextual learning will now adjust confidence per-domain.");
+            say!("\n   To view: sqlite3 wisdom.db 'SELECT domain, COUNT(*) FROM contextual_arms GROUP BY domain'");
         }
 
         "decay" => {
-            // Apply temporal decay to old adjustments (recent outcomes matter more)
-            println!("⏳ TEMPORAL DECAY: Weighting recent outcomes more heavily...\n");
-
-            // Get total adjustments
-            let total_before: i64 =
-                conn.query_row("SELECT COUNT(*) FROM framework_adjustments", [], |row| {
-                    row.get(0)
-                })?;
-
-            println!("   Total adjustments: {}", total_before);
-
-            // Apply decay: reduce weight of old adjustments by recalculating learned_confidence
-            // using exponentially weighted moving average with λ = 0.95 per day
-            // This means 1-week old data has weight 0.95^7 ≈ 0.70
-            // 1-month old data has weight 0.95^30 ≈ 0.21
-
-            // Reset all principles to base confidence
-            conn.execute(
-                "UPDATE principles SET learned_confidence = base_confidence",
-                [],
+            // Shrink each principle's drift by the time since its last decay (recent outcomes matter more)
+            let policy = eval::bandit::policy_engine(
+                &eval::thompson::LearningConfig::beside(&conn)?,
+                eval::thompson::FGTSConfig::default(),
             )?;
-
-            // Recalculate with time-weighted adjustments in Rust
-            // Recent adjustments get full weight, older ones get decayed
-            let adjustments: Vec<(String, f64, String)> = conn
-                .prepare(
-                    "SELECT principle_id, adjustment, created_at
-                 FROM framework_adjustments
-                 WHERE created_at IS NOT NULL",
-                )?
-                .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
-                .filter_map(|r| r.ok())
-                .collect();
-
-            // Calculate days since each adjustment and apply decay
-            let now = chrono::Utc::now();
-            let mut decayed_by_principle: std::collections::HashMap<String, f64> =
-                std::collections::HashMap::new();
-
-            for (principle_id, adjustment, created_at) in &adjustments {
-                // Parse date and calculate days ago
-                let days_ago = if let Ok(dt) =
-                    chrono::NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S")
-                {
-                    let created =
-                        chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(dt, chrono::Utc);
-                    (now - created).num_days() as f64
-                } else {
-                    0.0 // Recent if unparseable
-                };
-
-                // Decay factor: 0.95^days (half-life ≈ 13 days)
-                let decay = 0.95_f64.powf(days_ago);
-                *decayed_by_principle
-                    .entry(principle_id.clone())
-                    .or_insert(0.0) += adjustment * decay;
+            let report = decay::apply_decay(&conn, policy.as_ref(), chrono::Utc::now())?;
+            if output::machine(mode, &report)? {
+                return Ok(());
             }
 
-            let decayed_adjustments: Vec<(String, f64)> =
-                decayed_by_principle.into_iter().collect();
-
-            let mut updated = 0;
-            for (principle_id, decayed_adj) in &decayed_adjustments {
-                conn.execute(
-                    "UPDATE principles
-                     SET learned_confidence = MIN(1.0, MAX(0.0, base_confidence + ?2))
-                     WHERE id = ?1",
-                    rusqlite::params![principle_id, decayed_adj],
-                )?;
-                updated += 1;
+            say!("⏳ TEMPORAL DECAY: Weighting recent outcomes more heavily...\n");
+            say!("   Run #{} at {} ({} policy)", report.run_id, report.run_at, report.policy);
+            say!("   Decayed {} principles toward base confidence (×{}/day)", report.principles_decayed, decay::DAILY_DECAY);
+            if report.clocks_started > 0 {
+                say!("   Started the decay clock for {} principles", report.clocks_started);
             }
-
-            // Also decay contextual arms
-            conn.execute(
-                "UPDATE contextual_arms
-                 SET alpha = 1.0 + (alpha - 1.0) * 0.9,
-                     beta = 1.0 + (beta - 1.0) * 0.9
-                 WHERE sample_count > 50",
-                [],
-            )?;
-
-            println!(
-                "   Updated {} principles with time-weighted confidence",
-                updated
-            );
-            println!("   Decayed contextual arms with >50 samples");
-            println!("\n✅ Temporal decay applied. Recent outcomes now weighted more heavily.");
+            say!("   Decayed {} contextual arms with >50 samples", report.arms_decayed);
+            say!("\n✅ Temporal decay applied. Audit trail: decay_runs, decay_log");
         }
 
         _ => {
-            println!("Unknown thompson command: {}", subcommand);
-            println!("\nUsage: 100minds --thompson <command>");
-            println!("\nCommands:");
-            println!("  stats       Show Thompson Sampling statistics for all principles");
-            println!("  init        Initialize Thompson Sampling database tables");
-            println!("  persist     Update learned_confidence from Thompson means");
-            println!("  remediate   Fix poor performers (reset or archive)");
-            println!("  explore     Boost random poor performers for exploration");
-            println!("  contextual  Initialize domain-specific contextual learning");
-            println!("  cull        Archive principles with consistently poor performance");
-            println!("  discover    Mine patterns from successful outcomes");
-            println!("  yuzu        Generate Yuzu-compatible automation actions");
+            say!("Unknown thompson command: {}", subcommand);
+            say!("\nUsage: 100minds --thompson <command>");
+            say!("\nCommands:");
+            say!("  stats       Show Thompson Sampling statistics for all principles");
+            say!("  init        Initialize Thompson Sampling database tables");
+            say!("  persist     Update learned_confidence from Thompson means");
+            say!("  remediate   Fix poor performers (reset or archive)");
+            say!("  explore     Boost random poor performers for exploration");
+            say!("  contextual  Initialize domain-specific contextual learning");
+            say!("  cull        Archive principles with consistently poor performance");
+            say!("  discover    Mine patterns from successful outcomes");
+            say!("  yuzu        Generate Yuzu-compatible automation actions");
         }
     }
 
@@ -2932,70 +4635,155 @@ fn run_compute_embeddings() -> Result<()> {
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
 
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🧠 COMPUTING SEMANTIC EMBEDDINGS                            │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🧠 COMPUTING SEMANTIC EMBEDDINGS                            │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
     // Initialize embedding schema (adds embedding column if needed)
     embeddings::init_embedding_schema(&conn)?;
 
-    // Initialize semantic engine (downloads model if needed)
-    let model_dir = embeddings::get_model_dir();
-    println!("Model directory: {:?}", model_dir);
+    // Download the model the first time (--fetch-model installs it ahead of time)
+    let model_dir = language::LanguageConfig::beside(&conn)?.model_dir()?;
+    say!("Model directory: {:?}", model_dir);
+    if embeddings::MODEL_FILES.iter().any(|f| !model_dir.join(f).exists()) {
+        say!("Downloading embedding model (all-MiniLM-L6-v2)...");
+        embeddings::fetch_model(&model_dir, embeddings::ModelSource::Hub, None)?;
+    }
 
     let mut engine = embeddings::SemanticEngine::new(&model_dir)?;
-    println!("Semantic engine initialized.\n");
+    say!("Semantic engine initialized.\n");
 
     // Compute embeddings for all principles
     let count = engine.compute_all_embeddings(&conn)?;
 
-    println!("\n✅ Computed embeddings for {} principles", count);
-    println!("   These embeddings enable vocabulary-mismatch-proof search.");
-    println!("   Example: 'rewrite legacy system' will now find 'Strangler Fig Pattern'");
+    say!("\n✅ Computed embeddings for {} principles", count);
+    say!("   These embeddings enable vocabulary-mismatch-proof search.");
+    say!("   Example: 'rewrite legacy system' will now find 'Strangler Fig Pattern'");
+
+    Ok(())
+}
+
+/// Install the embedding model from the hub or a local bundle
+fn run_fetch_model(from: Option<&str>, checksums: Option<&str>, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    let language = language::LanguageConfig::beside(&conn)?;
+    // Where model_dir() will look, without its check that the files are there
+    let model_dir = language.embedding_model_dir.clone().unwrap_or_else(embeddings::get_model_dir);
+    let expected = checksums.map(|path| embeddings::ModelChecksums::read(Path::new(path))).transpose()?;
+    let source = match from {
+        Some(path) => embeddings::ModelSource::Local(Path::new(path)),
+        None => embeddings::ModelSource::Hub,
+    };
+    if mode == OutputMode::Human {
+        match from {
+            Some(path) => say!("Installing embedding model from {}...", path),
+            None => say!("Downloading embedding model (all-MiniLM-L6-v2)..."),
+        }
+    }
+    let installed = embeddings::fetch_model(&model_dir, source, expected)?;
 
+    let report = serde_json::json!({"model_dir": model_dir.display().to_string(), "sha256": installed.0});
+    if output::machine(mode, &report)? {
+        return Ok(());
+    }
+    say!("✅ Model installed in {}", model_dir.display());
+    for (name, hash) in &installed.0 {
+        say!("   {}  {}", hash, name);
+    }
+    say!("\nRun --compute-embeddings next to enable semantic search.");
     Ok(())
 }
 
 /// Run hybrid semantic + BM25 search
-fn run_hybrid_search(query: &str) -> Result<()> {
+/// Full-text search over decision history
+fn run_search_decisions(query: &str, limit: usize, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let matches = history::search_decisions(&conn, query, limit)?;
+    if output::machine(mode, &matches)? {
+        return Ok(());
+    }
+    if matches.is_empty() {
+        say!("No past decisions match \"{}\"", query);
+        return Ok(());
+    }
+    say!("🔎 {} past decisions match \"{}\"\n", matches.len(), query);
+    for m in &matches {
+        let outcome = match m.outcome {
+            Some(true) => "✅",
+            Some(false) => "❌",
+            None => "⏳",
+        };
+        say!("{} {}  {}", outcome, &m.decision_id[..8.min(m.decision_id.len())], truncate_str(&m.question, 70));
+        say!("   {}", m.snippet);
+    }
+    Ok(())
+}
+
+fn run_hybrid_search(query: &str, weights: Option<&str>, min_score: Option<f32>, rerank: bool) -> Result<()> {
     if query.is_empty() {
-        println!("Usage: 100minds --hybrid-search <query>");
+        say!("Usage: 100minds --hybrid-search <query>");
         return Ok(());
     }
 
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
-
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🔍 HYBRID SEMANTIC SEARCH                                   │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
-    println!("Query: {}\n", query);
-
-    // Initialize semantic engine
-    let model_dir = embeddings::get_model_dir();
-    let mut engine = match embeddings::SemanticEngine::new(&model_dir) {
-        Ok(e) => e,
-        Err(e) => {
-            println!("⚠️  Semantic engine not available: {}", e);
-            println!("   Run --compute-embeddings first to download the model.");
-            return Ok(());
-        }
-    };
-
-    // Load pre-computed embeddings
-    let loaded = engine.load_embeddings(&conn)?;
-    if loaded == 0 {
-        println!("⚠️  No embeddings found. Run --compute-embeddings first.");
-        return Ok(());
+    let mut config = search::SearchConfig::beside(&conn)?;
+    if let Some(weights) = weights {
+        config = config.with_weights(weights)?;
+    }
+    if let Some(min_score) = min_score {
+        config.min_score = min_score;
+        config.validate()?;
+    }
+    config.rerank |= rerank;
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🔍 HYBRID SEMANTIC SEARCH                                   │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("Query: {}\n", query);
+    let corrected = search::Vocabulary::load(&conn)?.correct(query);
+    if corrected.is_corrected() {
+        say!("Searching for: {}\n", corrected.query);
+    }
+    let query = corrected.query.as_str();
+
+    // Semantic engine if the model and embeddings are on disk; keyword-only otherwise
+    let capabilities = embeddings::capabilities(&conn);
+    let mut engine = embeddings::shared_engine(&conn).map(|m| m.lock().unwrap_or_else(|e| e.into_inner()));
+    match &engine {
+        Some(engine) => say!("Loaded {} principle embeddings\n", engine.embedding_count()),
+        None => say!(
+            "⚠️  Semantic search unavailable ({}); showing keyword matches only\n",
+            capabilities.degraded_reason.as_deref().unwrap_or("unknown reason")
+        ),
     }
-    println!("Loaded {} principle embeddings\n", loaded);
 
     // Run hybrid search
-    let results = engine.hybrid_search(&conn, query, 10, 0.6)?;
-
-    println!("TOP 10 MATCHES (60% semantic, 40% BM25):\n");
-    for (i, m) in results.iter().enumerate() {
+    let started = std::time::Instant::now();
+    let results = search::hybrid(&conn, engine.as_deref_mut(), query, 10, &config)?;
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let total = config.semantic_weight + config.bm25_weight;
+    if capabilities.semantic_search {
+        say!(
+            "TOP {} MATCHES ({:.0}% semantic, {:.0}% BM25):\n",
+            results.matches.len(),
+            100.0 * config.semantic_weight / total,
+            100.0 * config.bm25_weight / total,
+        );
+    } else {
+        say!("TOP {} MATCHES (BM25 only):\n", results.matches.len());
+    }
+    match &results.rerank {
+        Some(rerank) => say!(
+            "Search {:.1}ms, of which reranking {} candidates ({}) {:.1}ms\n",
+            elapsed_ms, rerank.candidates, rerank.reranker, rerank.latency_ms
+        ),
+        None => say!("Search {:.1}ms\n", elapsed_ms),
+    }
+    for (i, m) in results.matches.iter().enumerate() {
         // Get principle details
         let details: (String, String, String) = conn
             .query_row(
@@ -3009,14 +4797,14 @@ fn run_hybrid_search(query: &str) -> Result<()> {
 
         let (name, thinker, desc) = details;
 
-        println!("{}. {} ({:.2})", i + 1, name, m.combined_score);
-        println!("   Thinker: {}", thinker);
-        println!(
+        say!("{}. {} ({:.2})", i + 1, name, m.combined_score);
+        say!("   Thinker: {}", thinker);
+        say!(
             "   Semantic: {:.2}  BM25: {:.2}",
             m.semantic_score, m.bm25_score
         );
-        println!("   {}", truncate_str(&desc, 70));
-        println!();
+        say!("   {}", truncate_str(&desc, 70));
+        say!();
     }
 
     Ok(())
@@ -3027,61 +4815,60 @@ fn run_hybrid_search(query: &str) -> Result<()> {
 // ============================================================================
 
 /// Record an outcome for a decision
-fn run_outcome_cmd(args: &[String]) -> Result<()> {
-    if args.is_empty() {
-        println!("Usage: 100minds --outcome <decision-id> --success|--failed --principles \"id1,id2\" [--notes \"...\"] [--context '{{\"domain\":\"...\"}}']\n");
-        println!("Examples:");
-        println!("  100minds --outcome abc123 --success --principles \"yagni,kiss\"");
-        println!("  100minds --outcome abc123 --failed --principles \"brooks-law\" --notes \"Added too many people\"");
-        println!("  100minds --outcome bead-bd-123 --success --principles \"strangler-fig\" --context '{{\"domain\":\"architecture\"}}'");
-        return Ok(());
-    }
-
+fn run_outcome_cmd(args: OutcomeArgs, mode: OutputMode) -> Result<()> {
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
 
-    // Parse arguments
-    let decision_id = &args[0];
-    let mut success = true;
-    let mut principles: Vec<String> = vec![];
-    let mut notes = String::new();
-    let mut context_pattern: Option<String> = None;
-
-    let mut i = 1;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--success" => success = true,
-            "--failed" | "--failure" => success = false,
-            "--principles" => {
-                if i + 1 < args.len() {
-                    i += 1;
-                    principles = args[i].split(',').map(|s| s.trim().to_string()).collect();
-                }
-            }
-            "--notes" => {
-                if i + 1 < args.len() {
-                    i += 1;
-                    notes = args[i].clone();
-                }
-            }
-            "--context" => {
-                if i + 1 < args.len() {
-                    i += 1;
-                    context_pattern = Some(args[i].clone());
-                }
-            }
-            _ => {}
+    let decision_id = &args.decision_id;
+    let success = !args.failed;
+    let principles: Vec<String> = args.principles.iter().map(|s| s.trim().to_string()).collect();
+    let notes = args.notes;
+    let stage = stats::failures::check(success, args.stage.as_deref(), &args.reasons).map_err(anyhow::Error::msg)?;
+    let context_pattern = if args.actor.is_some() || args.template.is_some() || stage.is_some() {
+        let mut context: serde_json::Value = match &args.context {
+            Some(c) => serde_json::from_str(c).map_err(|e| anyhow::anyhow!("--context must be JSON: {}", e))?,
+            None => serde_json::json!({}),
+        };
+        if !context.is_object() {
+            anyhow::bail!("--context must be a JSON object");
+        }
+        if let Some(actor) = &args.actor {
+            context["actor_id"] = serde_json::json!(actor);
         }
-        i += 1;
+        if let Some(template) = &args.template {
+            context["template_id"] = serde_json::json!(template);
+            context["template_path"] = serde_json::json!(args.template_path);
+        }
+        if let Some(stage) = stage {
+            context["failure_stage"] = serde_json::json!(stage);
+            context["failure_reasons"] = serde_json::json!(args.reasons);
+        }
+        Some(context.to_string())
+    } else {
+        args.context
+    };
+
+    // Record the outcome
+    let result = outcome::record_outcome(
+        &conn,
+        decision_id,
+        success,
+        &principles,
+        &notes,
+        context_pattern.as_deref(),
+    )?;
+
+    if output::machine(mode, &result)? {
+        return Ok(());
     }
 
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🔄 RECORDING OUTCOME (FLYWHEEL ACTIVATION)                  │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🔄 RECORDING OUTCOME (FLYWHEEL ACTIVATION)                  │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
-    println!("Decision: {}", decision_id);
-    println!(
+    say!("Decision: {}", decision_id);
+    say!(
         "Outcome: {}",
         if success {
             "✅ SUCCESS"
@@ -3089,31 +4876,21 @@ fn run_outcome_cmd(args: &[String]) -> Result<()> {
             "❌ FAILURE"
         }
     );
-    println!("Principles: {:?}", principles);
+    say!("Principles: {:?}", principles);
     if !notes.is_empty() {
-        println!("Notes: {}", notes);
+        say!("Notes: {}", notes);
     }
-    println!();
-
-    // Record the outcome
-    let result = outcome::record_outcome(
-        &conn,
-        decision_id,
-        success,
-        &principles,
-        &notes,
-        context_pattern.as_deref(),
-    )?;
+    say!();
 
     // Show adjustments
     if result.principles_adjusted.is_empty() {
-        println!("⚠️  No principles were adjusted (check principle IDs exist)");
+        say!("⚠️  No principles were adjusted (check principle IDs exist)");
     } else {
-        println!("CONFIDENCE ADJUSTMENTS:");
+        say!("CONFIDENCE ADJUSTMENTS:");
         for adj in &result.principles_adjusted {
             let arrow = if adj.delta > 0.0 { "↑" } else { "↓" };
             let color_hint = if adj.delta > 0.0 { "📈" } else { "📉" };
-            println!(
+            say!(
                 "   {} {} {}: {:.0}% → {:.0}% ({}{:.0}%)",
                 color_hint,
                 arrow,
@@ -3124,13 +4901,13 @@ fn run_outcome_cmd(args: &[String]) -> Result<()> {
                 adj.delta * 100.0
             );
         }
-        println!();
-        println!(
+        say!();
+        say!(
             "✅ Flywheel activated! {} principles updated.",
             result.principles_adjusted.len()
         );
-        println!("   Asymmetric learning: failures hurt more (-10%) than successes help (+5%)");
-        println!(
+        say!("   Asymmetric learning: failures hurt more (-10%) than successes help (+5%)");
+        say!(
             "   This implements Taleb's 'skin in the game' - bad advice is penalized heavily."
         );
     }
@@ -3138,27 +4915,632 @@ fn run_outcome_cmd(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Inspect, apply or drop quarantined outcomes
+fn run_quarantine(action: &str, target: Option<&str>, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    if action == "quarantined" {
+        let held = outcome_guard::list(&conn)?;
+        if output::machine(mode, &held)? {
+            return Ok(());
+        }
+        if held.is_empty() {
+            say!("✅ Nothing quarantined");
+            return Ok(());
+        }
+        say!("⏸ {} outcomes held for review:\n", held.len());
+        for q in &held {
+            say!(
+                "   #{:<4} {:24} {:8} {:16} {}",
+                q.id,
+                truncate_str(&q.request.decision_id, 24),
+                if q.request.success { "success" } else { "failure" },
+                truncate_str(&q.source, 16),
+                q.batch_id.as_deref().unwrap_or("")
+            );
+        }
+        let mut reasons: Vec<&str> = held.iter().map(|q| q.reason.as_str()).collect();
+        reasons.dedup();
+        for reason in reasons {
+            say!("\n   ⚠️  {}", reason);
+        }
+        say!("\nApply or drop with: 100minds outcomes apply|drop <id|batch-id|all>");
+        return Ok(());
+    }
+
+    let target = target.ok_or_else(|| anyhow::anyhow!("outcomes {} needs an id, a batch id or \"all\"", action))?;
+    let held = outcome_guard::select(&conn, target)?;
+    let applied = db::with_savepoint(&conn, "review_quarantine", || -> Result<Vec<outcome::OutcomeResult>> {
+        let applied = match action {
+            "apply" => held.iter().map(|q| Ok(outcome::apply_request(&conn, &q.request)?)).collect::<Result<_>>()?,
+            _ => Vec::new(),
+        };
+        outcome_guard::release(&conn, &held)?;
+        Ok(applied)
+    })?;
+    if output::machine(mode, &serde_json::json!({"action": action, "released": held.len(), "applied": applied}))? {
+        return Ok(());
+    }
+    match action {
+        "apply" => say!("✅ Applied {} quarantined outcomes", applied.len()),
+        _ => say!("🗑 Dropped {} quarantined outcomes", held.len()),
+    }
+    Ok(())
+}
+
+/// List, create or revoke server API keys
+fn run_keys(action: &str, name: Option<&str>, role: &str, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    match action {
+        "create" => {
+            let name = name.ok_or_else(|| anyhow::anyhow!("keys create needs a name"))?;
+            let (key, secret) = access::create_key(&conn, name, access::Role::parse(role)?)?;
+            if output::machine(mode, &serde_json::json!({"key": key, "secret": secret}))? {
+                return Ok(());
+            }
+            say!("🔑 Created {} key {:?} (#{})", key.role.as_str(), key.name, key.id);
+            say!("\n   {}\n", secret);
+            say!("This is the only time the key is shown. Send it as: Authorization: Bearer <key>");
+        }
+        "revoke" => {
+            let name = name.ok_or_else(|| anyhow::anyhow!("keys revoke needs a name or id"))?;
+            access::revoke_key(&conn, name)?;
+            if output::machine(mode, &serde_json::json!({"revoked": name}))? {
+                return Ok(());
+            }
+            say!("🚫 Revoked key {}", name);
+        }
+        _ => {
+            let keys = access::list_keys(&conn)?;
+            if output::machine(mode, &keys)? {
+                return Ok(());
+            }
+            if keys.is_empty() {
+                say!("No API keys: the server accepts every request");
+                say!("Create one with: 100minds keys create <name> --role reader|recorder|admin");
+                return Ok(());
+            }
+            for key in &keys {
+                say!(
+                    "   #{:<3} {:20} {:9} {}",
+                    key.id,
+                    truncate_str(&key.name, 20),
+                    key.role.as_str(),
+                    match (&key.revoked_at, &key.last_used_at) {
+                        (Some(at), _) => format!("revoked {}", at),
+                        (None, Some(at)) => format!("last used {}", at),
+                        (None, None) => "never used".to_string(),
+                    }
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply the retention limits
+fn run_prune(dry_run: bool, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let config = retention::RetentionConfig::beside(&conn)?;
+    let report = retention::prune(&conn, &config, chrono::Utc::now(), dry_run)?;
+    if output::machine(mode, &report)? {
+        return Ok(());
+    }
+    if config == retention::RetentionConfig::default() {
+        say!("No [retention] limits in config.toml: everything is kept");
+        return Ok(());
+    }
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    say!("🗄 {} {} decisions (tombstoned), {} raw adjustments (rolled up), {} log rows", verb, report.decisions, report.adjustments, report.log_rows);
+    let broken = retention::check_chain(&conn)?;
+    if !broken.is_empty() {
+        say!("⚠️  {} decisions link to a hash that is neither stored nor tombstoned: {}", broken.len(), broken.join(", "));
+    }
+    Ok(())
+}
+
+/// Replay the learning history, as recorded or under what-if settings
+fn run_replay(apply: bool, config: replay::ReplayConfig, mode: OutputMode) -> Result<()> {
+    if apply && !config.is_faithful() {
+        anyhow::bail!("--apply can't be combined with what-if options (--daily-decay, --policy, ...)");
+    }
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let report = replay::replay(&conn, &config)?;
+    let applied = if apply { replay::apply(&conn, &report)? } else { 0 };
+    if !output::machine(mode, &report)? {
+        say!(
+            "🔁 Replayed {} adjustments and {} decay runs over {} principles",
+            report.adjustments, report.decay_runs, report.principles
+        );
+        if report.rolled_up > 0 {
+            say!(
+                "⚠️  {} older adjustments were rolled up by prune and can't be replayed exactly",
+                report.rolled_up
+            );
+        }
+        let label = if config.is_faithful() { "live" } else { "now" };
+        for d in report.differences.iter().take(20) {
+            let delta = d.replayed - d.live;
+            say!("   {:<40} {} {:.4} → replayed {:.4} ({:+.4})", d.name, label, d.live, d.replayed, delta);
+        }
+        if report.differences.len() > 20 {
+            say!("   … and {} more", report.differences.len() - 20);
+        }
+        match (config.is_faithful(), report.differences.is_empty(), apply) {
+            (true, true, _) => say!("✅ Replay reproduces every learned confidence"),
+            (true, false, true) => say!("✏️  Wrote the replayed confidence of {} principles", applied),
+            (true, false, false) => {}
+            (false, _, _) => {
+                say!("🧪 What-if replay: {} principles would differ; nothing written", report.differences.len())
+            }
+        }
+        if let Some(s) = &report.selection {
+            let pct = |rate: Option<f64>| rate.map_or("-".to_string(), |r| format!("{:.0}%", r * 100.0));
+            say!("\n🎯 Decisions replayed under {} (epsilon {:.2}): {}", s.policy, s.epsilon, s.decisions);
+            say!("   Logged success rate:     {}", pct(Some(s.logged_success_rate)));
+            say!("   Same lead principle:     {} of {} decisions", s.matched, s.decisions);
+            say!("   Estimated success rate:  {} (over those {})", pct(s.estimated_success_rate), s.matched);
+            say!(
+                "   With an epsilon pick:    {} decisions, {} succeeded (vs {} without)",
+                s.explored,
+                pct(s.explored_success_rate),
+                pct(s.greedy_success_rate)
+            );
+            if let Some(ope) = &s.off_policy {
+                say!(
+                    "   Off-policy ({} with logged propensities, worth ~{:.0}): IPS {:.0}%, SNIPS {:.0}%, DR {:.0}%",
+                    ope.samples,
+                    ope.effective_sample_size,
+                    ope.ips * 100.0,
+                    ope.snips * 100.0,
+                    ope.doubly_robust * 100.0
+                );
+            }
+            for change in s.lead_changes.iter().take(10) {
+                say!("   {:<40} led {} → {}", change.principle_id, change.logged, change.replayed);
+            }
+            say!("   The estimate is biased unless the logged picks were random; use it for direction");
+        }
+    }
+    if config.is_faithful() && !apply && !report.differences.is_empty() {
+        let drifted = report.differences.len();
+        anyhow::bail!("{} principles drifted from their recorded history (--apply to rebuild them)", drifted);
+    }
+    Ok(())
+}
+
+/// Erase a subject from the stored history
+fn run_forget(pattern: &str, regex: bool, redact: bool, dry_run: bool, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    let provenance = Provenance::init(&data_dir.join("agent.key"))?;
+    let subject = forget::subject(pattern, regex)?;
+    let forget_mode = if redact { forget::Mode::Redact } else { forget::Mode::Remove };
+    let report = forget::forget(&conn, &provenance, &subject, forget_mode, dry_run, chrono::Utc::now())?;
+    if output::machine(mode, &report)? {
+        return Ok(());
+    }
+    let verb = match (dry_run, forget_mode) {
+        (true, _) => "Would forget",
+        (false, forget::Mode::Remove) => "Removed",
+        (false, forget::Mode::Redact) => "Redacted",
+    };
+    say!(
+        "🧹 {} {} decisions, {} counsel jobs, {} quarantined outcomes",
+        verb,
+        report.decisions.len(),
+        report.counsel_jobs,
+        report.quarantined_outcomes
+    );
+    for id in &report.decisions {
+        say!("   {}", id);
+    }
+    if let Some(event) = &report.event {
+        say!("🔏 Deletion event {} signed into the provenance chain", event.id);
+    }
+    Ok(())
+}
+
+/// Redact PII from already stored decisions
+fn run_scrub(dry_run: bool, mode: OutputMode) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let report = redact::scrub_existing(&conn, dry_run)?;
+    if output::machine(mode, &report)? {
+        return Ok(());
+    }
+    let verb = if dry_run { "Would redact" } else { "Redacted" };
+    say!("🧽 {} {} of {} decisions", verb, report.changed, report.decisions);
+    for (kind, count) in &report.kinds {
+        say!("   {:12} {}", kind, count);
+    }
+    Ok(())
+}
+
+/// Convert an existing install to encrypted storage
+#[cfg(feature = "encryption")]
+fn run_encrypt_db(keychain: bool, mode: OutputMode) -> Result<()> {
+    use minds_mcp::encryption;
+
+    let data_dir = get_data_dir()?;
+    let passphrase = match encryption::passphrase() {
+        Some(passphrase) => passphrase,
+        None if keychain => hex::encode(rand::random::<[u8; 32]>()),
+        None => anyhow::bail!(
+            "Set {} or pass --keychain to generate a passphrase kept in the OS keychain",
+            encryption::PASSPHRASE_ENV
+        ),
+    };
+    if keychain {
+        encryption::keychain_store(&passphrase)?;
+    }
+
+    let db_path = data_dir.join("wisdom.db");
+    let key_path = data_dir.join("agent.key");
+    let db_converted = !encryption::is_encrypted_db(&db_path)?;
+    if db_converted {
+        encryption::encrypt_db(&db_path, &passphrase)?;
+    }
+    let key_converted = key_path.exists() && encryption::seal_key_file(&key_path, &passphrase)?;
+
+    let report = serde_json::json!({"database": db_converted, "agent_key": key_converted, "keychain": keychain});
+    if output::machine(mode, &report)? {
+        return Ok(());
+    }
+    say!("🔒 wisdom.db: {}", if db_converted { "encrypted" } else { "already encrypted" });
+    say!("🔒 agent.key: {}", if key_converted { "sealed" } else { "already sealed or missing" });
+    if keychain {
+        say!("🔑 Passphrase stored in the OS keychain");
+    }
+    say!("\nCopies and backups made before now are still plaintext.");
+    Ok(())
+}
+
+#[cfg(not(feature = "encryption"))]
+fn run_encrypt_db(_keychain: bool, _mode: OutputMode) -> Result<()> {
+    anyhow::bail!("This build lacks the `encryption` feature (cargo install --features encryption)")
+}
+
+/// Import outcomes recorded offline (JSONL)
+fn run_outcome_import(path: &str, dry_run: bool, all_or_nothing: bool, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+
+    let jsonl = std::fs::read_to_string(path)?;
+    let options = outcome::ImportOptions { dry_run, all_or_nothing };
+    let report = outcome::import_batch(&conn, &jsonl, options)?;
+    let failed = !report.errors.is_empty();
+
+    if !output::machine(mode, &report)? {
+        say!("\n┌─────────────────────────────────────────────────────────────┐");
+        say!("│ 📥 OUTCOME IMPORT                                           │");
+        say!("└─────────────────────────────────────────────────────────────┘\n");
+
+        say!("File: {}", path);
+        say!("Applied: {}", report.applied.len());
+        say!("Duplicates skipped: {}", report.duplicates.len());
+        say!("Quarantined: {}", report.quarantined.len());
+        say!("Errors: {}", report.errors.len());
+
+        for d in &report.duplicates {
+            say!("   ⏭ line {}: {} ({})", d.line, d.decision_id.as_deref().unwrap_or("?"), d.message);
+        }
+        for e in &report.errors {
+            say!("   ❌ line {}: {} ({})", e.line, e.decision_id.as_deref().unwrap_or("?"), e.message);
+        }
+        if let Some(q) = report.quarantined.first() {
+            say!("   ⏸ {} held for review: {} (see `100minds outcomes quarantined`)", report.quarantined.len(), q.message);
+        }
+        if report.rolled_back {
+            say!("\n↩️  Rolled back: {}", if dry_run { "dry run" } else { "--atomic and errors present" });
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Calibration commands: report | fit <platt|isotonic> | off
+fn run_calibration(action: &str, method: Option<&str>) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let db_path = data_dir.join("wisdom.db");
+    let conn = db::init_db(&db_path)?;
+
+    let samples = calibration::load_samples(&conn)?;
+
+    match action {
+        "report" => {
+            if samples.is_empty() {
+                say!("No positions with recorded outcomes yet.");
+                say!("Record outcomes with: 100minds --outcome <decision-id> --success|--failed --principles \"id1,id2\"");
+                return Ok(());
+            }
+            let report = calibration::analyze_calibration(&samples);
+            calibration::print_calibration_report(&report);
+
+            match calibration::load_active_calibrator(&conn)? {
+                Some(c) => say!("\nActive correction: {} (applied to counsel)", c.method().name()),
+                None => say!("\nNo active correction. Fit one with: 100minds --calibration fit platt|isotonic"),
+            }
+        }
+        "fit" => {
+            let method = method
+                .and_then(calibration::CalibrationMethod::parse)
+                .unwrap_or(calibration::CalibrationMethod::Isotonic);
+
+            let calibrator = match calibration::Calibrator::fit(&samples, method) {
+                Ok(c) => c,
+                Err(e) => {
+                    say!("❌ {}", e);
+                    return Ok(());
+                }
+            };
+            calibration::save_calibrator(&conn, &calibrator, samples.len(), true)?;
+
+            say!("✅ Fitted {} calibration on {} positions", method.name(), samples.len());
+            say!("\nCORRECTION PREVIEW:");
+            for p in [0.3, 0.5, 0.7, 0.8, 0.9] {
+                say!("   stated {:3.0}% → {:5.1}%", p * 100.0, calibrator.apply(p) * 100.0);
+            }
+            say!("\nCounsel confidences are now corrected. Disable with: 100minds --calibration off");
+        }
+        "off" => {
+            calibration::deactivate_calibration(&conn)?;
+            say!("Calibration correction disabled; counsel returns raw confidences.");
+        }
+        other => anyhow::bail!("Unknown calibration command: {}", other),
+    }
+
+    Ok(())
+}
+
+/// Render a decision as an ADR
+/// No output path: write into ./docs/adr/ if it exists, else print to stdout
+fn run_adr(decision_id: &str, output: Option<&str>) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+
+    let target = output.map(PathBuf::from).or_else(|| {
+        let default_dir = PathBuf::from("docs/adr");
+        default_dir.is_dir().then_some(default_dir)
+    });
+
+    match target {
+        Some(dir) if dir.is_dir() || output.is_some_and(|o| o.ends_with('/')) => {
+            let path = adr::write_adr(&conn, decision_id, &dir)?;
+            say!("📄 ADR saved to: {:?}", path);
+        }
+        Some(file) => {
+            let source = adr::load_adr_source(&conn, decision_id)?;
+            std::fs::write(&file, adr::render_adr(&source, None))?;
+            say!("📄 ADR saved to: {:?}", file);
+        }
+        None => {
+            let source = adr::load_adr_source(&conn, decision_id)?;
+            std::print!("{}", adr::render_adr(&source, None));
+        }
+    }
+
+    Ok(())
+}
+
+/// Record a fired falsification condition; with no position, list flagged decisions
+fn run_falsify(decision_id: Option<&str>, position: Option<&str>, evidence: &str, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+
+    let (Some(decision_id), Some(position)) = (decision_id, position) else {
+        let flagged: Vec<_> = falsification::flagged_decisions(&conn)?
+            .into_iter()
+            .filter(|f| decision_id.is_none_or(|id| f.decision_id == id))
+            .collect();
+        if output::machine(mode, &flagged)? {
+            return Ok(());
+        }
+        if flagged.is_empty() {
+            say!("No falsification conditions recorded. Add one with: 100minds falsify <decision-id> <thinker>");
+        }
+        for f in &flagged {
+            say!("{}  {} condition(s), last {}  {}", f.decision_id, f.conditions_fired, f.last_observed_at, f.question);
+        }
+        return Ok(());
+    };
+
+    let result = falsification::record(&conn, decision_id, position, evidence)?;
+    if output::machine(mode, &result)? {
+        return Ok(());
+    }
+    if result.duplicate {
+        say!("Already recorded: {}'s condition for {}", result.thinker, result.decision_id);
+        return Ok(());
+    }
+    say!("🚩 Falsified: {} ({})", result.condition, result.thinker);
+    for (principle, confidence) in &result.principles {
+        say!("   {} → {:.2}", principle, confidence);
+    }
+    Ok(())
+}
+
+/// Generate (or assess) a decision's risk register
+fn run_risk(
+    decision_id: &str,
+    format: &str,
+    assess: Option<&str>,
+    likelihood: Option<&str>,
+    impact: Option<&str>,
+    mitigation: Option<&str>,
+    mode: OutputMode,
+) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+
+    let register = match assess {
+        Some(risk_id) => risk::assess(&conn, decision_id, risk_id, likelihood, impact, mitigation)?,
+        None => risk::generate(&conn, decision_id)?,
+    };
+    if format == "json" {
+        std::println!("{}", serde_json::to_string_pretty(&register)?);
+    } else if !output::machine(mode, &register)? {
+        std::print!("{}", risk::render_markdown(&register));
+    }
+    Ok(())
+}
+
+/// Compile a decision's checklist, ticking or unticking an item first
+fn run_checklist(decision_id: &str, done: Option<&str>, undo: Option<&str>, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+
+    let checklist = match (done, undo) {
+        (Some(item_id), _) => checklist::tick(&conn, decision_id, item_id, true)?,
+        (None, Some(item_id)) => checklist::tick(&conn, decision_id, item_id, false)?,
+        (None, None) => checklist::generate(&conn, decision_id)?,
+    };
+    if !output::machine(mode, &checklist)? {
+        std::print!("{}", checklist::render_markdown(&checklist));
+    }
+    Ok(())
+}
+
+/// Export the corpus as an Obsidian/Markdown vault (incremental)
+fn run_export_vault(dir: &str) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+
+    let stats = vault::export_vault(&conn, std::path::Path::new(dir))?;
+    say!(
+        "📚 Vault refreshed: {} written, {} unchanged, {} removed",
+        stats.written, stats.unchanged, stats.removed
+    );
+    say!("📄 Vault saved to: {:?}", dir);
+    Ok(())
+}
+
+fn run_dashboard(out_dir: &str) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let path = dashboard::write(&conn, Path::new(out_dir))?;
+    say!("📊 Dashboard written to {}", path.display());
+    Ok(())
+}
+
+fn run_export_graph(format: &str, output: Option<&str>, corpus_only: bool) -> Result<()> {
+    let conn = db::init_db(&get_data_dir()?.join("wisdom.db"))?;
+    let graph = knowledge_graph::build(&conn, !corpus_only)?;
+    let text = match format {
+        "json" => serde_json::to_string_pretty(&graph.to_d3_json())?,
+        _ => graph.to_graphml(),
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(path, text)?;
+            say!("🕸  {} nodes, {} edges written to {}", graph.nodes.len(), graph.links.len(), path);
+        }
+        None => std::println!("{}", text),
+    }
+    Ok(())
+}
+
+/// Link a decision to the branch/PR that implements it
+fn run_link_decision(
+    decision_id: &str,
+    branch: Option<&str>,
+    pr: Option<&str>,
+    repo: &str,
+    forge_repo: Option<&str>,
+) -> Result<()> {
+    let pr_number = pr.and_then(|n| n.trim_start_matches('#').parse().ok());
+    let repo = std::fs::canonicalize(repo)?;
+
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    git_outcomes::link_decision(&conn, decision_id, branch, pr_number, &repo.to_string_lossy(), forge_repo)?;
+
+    say!(
+        "🔗 Linked {} to {}{}",
+        decision_id,
+        branch.map(|b| format!("branch '{}' ", b)).unwrap_or_default(),
+        pr_number.map(|n| format!("PR #{}", n)).unwrap_or_default()
+    );
+    say!("   Repository: {}", repo.display());
+    match forge_repo.map(String::from).or_else(|| git_outcomes::origin_repo(&repo)) {
+        Some(forge_repo) => say!("   Webhooks from: {}", forge_repo),
+        None => say!("   ⚠️ No origin remote; pass --forge-repo owner/name for webhooks to find this link"),
+    }
+    Ok(())
+}
+
+/// Suggest outcomes for linked decisions from merge/revert/fix history
+/// Nothing is recorded - each suggestion needs confirmation
+fn run_suggest_outcomes(window_days: i64, mode: OutputMode) -> Result<()> {
+    let data_dir = get_data_dir()?;
+    let conn = db::init_db(&data_dir.join("wisdom.db"))?;
+    let suggestions = git_outcomes::suggest_outcomes(&conn, window_days)?;
+
+    // Shape matches the record_outcomes_batch tool, ready to confirm
+    let outcomes: Vec<_> = suggestions.iter().map(|s| &s.request).collect();
+    if output::machine(mode, &serde_json::json!({ "outcomes": outcomes }))? {
+        return Ok(());
+    }
+
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🔍 SUGGESTED OUTCOMES (FROM GIT HISTORY)                    │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
+
+    if suggestions.is_empty() {
+        say!("No suggestions yet. Decisions need a link and a merge whose");
+        say!("{}-day window has passed (reverts and repeated fixes count sooner).", window_days);
+        say!("\nLink a decision with: 100minds --link-decision <decision-id> --branch=name --pr=N");
+        return Ok(());
+    }
+
+    for suggestion in &suggestions {
+        let request = &suggestion.request;
+        say!(
+            "{} {} ({:.0}% confidence)",
+            if request.success { "✅ SUCCESS" } else { "❌ FAILURE" },
+            request.decision_id,
+            request.confidence_score.unwrap_or(0.0) * 100.0
+        );
+        for line in &suggestion.evidence {
+            say!("   • {}", line);
+        }
+        say!(
+            "   Confirm: 100minds --outcome {} {} --principles \"{}\"",
+            request.decision_id,
+            if request.success { "--success" } else { "--failed" },
+            request.principle_ids.join(",")
+        );
+        say!();
+    }
+
+    say!("Agents: pass `--json` output to the record_outcomes_batch tool after review.");
+    Ok(())
+}
+
 /// Show learning flywheel statistics
-fn run_learning_stats() -> Result<()> {
+fn run_learning_stats(mode: OutputMode) -> Result<()> {
     let data_dir = get_data_dir()?;
     let db_path = data_dir.join("wisdom.db");
     let conn = db::init_db(&db_path)?;
 
     let stats = outcome::get_learning_stats(&conn)?;
+    if output::machine(mode, &stats)? {
+        return Ok(());
+    }
     outcome::print_learning_stats(&stats);
 
     // Additional context
     if stats.total_outcomes > 0 {
-        println!("\nHOW THE FLYWHEEL WORKS:");
-        println!("   1. Agent asks for counsel → 100minds provides principles");
-        println!("   2. Agent executes decision → records outcome with --outcome");
-        println!("   3. Principles that led to success gain confidence (+5%)");
-        println!("   4. Principles that led to failure lose confidence (-10%)");
-        println!("   5. Next counsel uses learned_confidence for better ranking");
-        println!();
-        println!("INTEGRATION:");
-        println!("   Zesty workers can record outcomes automatically:");
-        println!("   zesty outcome record --bead bd-123 --success --principles \"yagni\"");
+        say!("\nHOW THE FLYWHEEL WORKS:");
+        say!("   1. Agent asks for counsel → 100minds provides principles");
+        say!("   2. Agent executes decision → records outcome with --outcome");
+        say!("   3. Principles that led to success gain confidence (+5%)");
+        say!("   4. Principles that led to failure lose confidence (-10%)");
+        say!("   5. Next counsel uses learned_confidence for better ranking");
+        say!();
+        say!("INTEGRATION:");
+        say!("   Zesty workers can record outcomes automatically:");
+        say!("   zesty outcome record --bead bd-123 --success --principles \"yagni\"");
     }
 
     Ok(())
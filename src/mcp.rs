@@ -12,11 +12,131 @@
 
 use crate::db::{self};
 use crate::templates::{self, DecisionTemplate};
-use anyhow::Result;
+use crate::error::Result;
+use crate::types::{
+    ContextSnippet, CounselContext, CounselDepth, CounselRequest, ExplorationSettings, RecordOutcomeRequest,
+    RecordOutcomesBatchRequest,
+};
 use rusqlite::Connection;
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+// ============================================================================
+// TOOL INPUT SCHEMAS - derived from the types the handlers parse into
+// ============================================================================
+
+/// Arguments of the `counsel` tool: a [`CounselRequest`] with its context flattened
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct CounselArgs {
+    /// The decision question to get counsel on
+    pub question: String,
+    /// Optional domain hint (software-architecture, entrepreneurship, ai-ml, management-theory)
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// How many perspectives to include (quick=3, standard=4, deep=6, auto=picked from the question)
+    #[serde(default)]
+    pub depth: Option<CounselDepth>,
+    /// Optional free-text background, e.g. 'team of 3, deadline in 2 weeks, legacy Rails app'
+    #[serde(default)]
+    pub context: Option<String>,
+    /// Optional hard constraints (budget, compliance, must-keep systems)
+    #[serde(default)]
+    pub constraints: Vec<String>,
+    /// Optional number of engineers working on this
+    #[serde(default)]
+    pub team_size: Option<u32>,
+    /// Optional days until the deadline
+    #[serde(default)]
+    pub deadline_days: Option<u32>,
+    /// Optional technologies in play (e.g. ['rust', 'postgres'])
+    #[serde(default)]
+    pub stack: Vec<String>,
+    /// Optional code or doc excerpts relevant to the decision
+    #[serde(default)]
+    pub snippets: Vec<ContextSnippet>,
+    /// Optional explicit decision ID (e.g. a bead ID for swarm tracking)
+    #[serde(default)]
+    pub decision_id: Option<String>,
+    /// Optional client-chosen key; a retry with the same key returns the original decision
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Optional time budget; expensive steps that don't fit are skipped and the response is marked degraded
+    #[serde(default)]
+    pub latency_budget_ms: Option<u64>,
+    /// Only use principles with one of these domain tags (e.g. ["security"])
+    #[serde(default)]
+    pub include_tags: Vec<String>,
+    /// Skip principles with any of these domain tags (e.g. ["entrepreneurship"])
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+    /// Optional agent or user ID; ranking blends what this actor's outcomes taught with global learning
+    #[serde(default)]
+    pub actor_id: Option<String>,
+    /// Optional exploration overrides; the response's exploration log records what was used
+    #[serde(default)]
+    pub exploration: Option<ExplorationSettings>,
+    /// Optional two objectives to weigh (e.g. ['speed', 'quality']); the response's tradeoff tags each principle
+    /// with the one it serves. Read from 'X vs Y' questions when not given
+    #[serde(default)]
+    pub objectives: Vec<String>,
+    /// Optional filled cost-of-delay items (see the worksheet prioritization questions return); sequenced by CD3 in
+    /// the response's cost_of_delay
+    #[serde(default)]
+    pub delay_items: Vec<crate::cost_of_delay::DelayItem>,
+    /// Optional older response shape to render, for clients that parse it: 1 (the debate only) or 2 (plus
+    /// principle_ids, urgency_adjustment and causal_hints). Default: the current schema_version
+    #[serde(default)]
+    pub response_version: Option<u32>,
+}
+
+impl CounselArgs {
+    pub fn into_request(self) -> CounselRequest {
+        CounselRequest {
+            question: self.question,
+            context: CounselContext {
+                domain: self.domain,
+                constraints: self.constraints,
+                depth: self.depth.unwrap_or_default(),
+                notes: self.context,
+                snippets: self.snippets,
+                team_size: self.team_size,
+                deadline_days: self.deadline_days,
+                stack: self.stack,
+                latency_budget_ms: self.latency_budget_ms,
+                include_tags: self.include_tags,
+                exclude_tags: self.exclude_tags,
+                actor_id: self.actor_id,
+                exploration: self.exploration,
+                objectives: self.objectives,
+                delay_items: self.delay_items,
+                ..Default::default()
+            },
+            decision_id: self.decision_id,
+        }
+    }
+}
+
+/// The `inputSchema` of the named tool
+pub fn tool_schema(name: &str) -> Option<Value> {
+    get_tools().into_iter().find(|t| t["name"] == name).map(|t| t["inputSchema"].clone())
+}
+
+/// A tool's `inputSchema`: `T`'s JSON Schema with subschemas inlined, since
+/// MCP clients don't all resolve `$ref`
+pub fn input_schema<T: JsonSchema>() -> Value {
+    let generator = schemars::gen::SchemaSettings::draft07()
+        .with(|s| s.inline_subschemas = true)
+        .into_generator();
+    let mut schema = serde_json::to_value(generator.into_root_schema_for::<T>()).unwrap_or_default();
+    if let Some(root) = schema.as_object_mut() {
+        root.remove("$schema");
+        root.remove("title");
+        root.remove("description");
+    }
+    schema
+}
+
 // ============================================================================
 // MCP TOOL DEFINITIONS - The full API surface
 // ============================================================================
@@ -27,26 +147,18 @@ pub fn get_tools() -> Vec<Value> {
         // CORE: Adversarial Wisdom Council
         json!({
             "name": "counsel",
-            "description": "Get adversarial wisdom council on a decision. Returns FOR, AGAINST, SYNTHESIZE positions from named thinkers (Fred Brooks, Sam Newman, Kent Beck, etc.) with specific principles. Unlike generic 'mental models' tools, this provides: (1) Named authority with citations, (2) Adversarial debate format, (3) Falsification conditions per position, (4) Actionable next steps. 10x better than CognitiveCompass or ThinkingPatterns MCPs.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "question": {
-                        "type": "string",
-                        "description": "The decision question to get counsel on"
-                    },
-                    "domain": {
-                        "type": "string",
-                        "description": "Optional domain hint (software-architecture, entrepreneurship, ai-ml, management-theory)"
-                    },
-                    "depth": {
-                        "type": "string",
-                        "enum": ["quick", "standard", "deep"],
-                        "description": "How many perspectives to include (quick=3, standard=4, deep=6)"
-                    }
-                },
-                "required": ["question"]
-            }
+            "description": "Get adversarial wisdom council on a decision. Returns FOR, AGAINST, SYNTHESIZE positions from named thinkers (Fred Brooks, Sam Newman, Kent Beck, etc.) with specific principles. Unlike generic 'mental models' tools, this provides: (1) Named authority with citations, (2) Adversarial debate format, (3) Falsification conditions per position, (4) Actionable next steps, (5) Similar past decisions with their outcomes. 10x better than CognitiveCompass or ThinkingPatterns MCPs.",
+            "inputSchema": input_schema::<CounselArgs>()
+        }),
+        json!({
+            "name": "counsel_followup",
+            "description": "Refine a previous counsel with a follow-up ('ok, but what if the team is only 2 people?'). Re-counsels the parent decision's question with the refinement added to its context (team size and deadline in the refinement replace the parent's). Returns a full counsel response stored as a new decision, with `thread` naming the parent and root decision; its provenance chains from the parent's hash.",
+            "inputSchema": input_schema::<crate::thread::FollowupRequest>()
+        }),
+        json!({
+            "name": "evaluate_options",
+            "description": "Score 2-6 concrete options against the principles relevant to them, instead of debating an open question. Each principle's alignment with each option runs from -1 (the principle warns against it) to 1 (the option follows it). Returns the options ranked by confidence-weighted score, the principle x option matrix, and the overall tilt: the best option, its margin over the runner-up ('clear', 'slight' or 'even'), and the principles driving it.",
+            "inputSchema": input_schema::<crate::options::EvaluateOptionsRequest>()
         }),
         // NEW: Decision Template matching
         json!({
@@ -66,6 +178,24 @@ pub fn get_tools() -> Vec<Value> {
                 }
             }
         }),
+        json!({
+            "name": "match_template",
+            "description": "Lightweight check of which decision templates fit a question, best first, without running counsel. Returns each template's id, name, domain and match score; fetch the full tree with get_decision_template.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "question": {
+                        "type": "string",
+                        "description": "The decision being made"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum templates to return (default 3)"
+                    }
+                },
+                "required": ["question"]
+            }
+        }),
         // NEW: Blind spot analysis
         json!({
             "name": "check_blind_spots",
@@ -80,25 +210,119 @@ pub fn get_tools() -> Vec<Value> {
                     "template_id": {
                         "type": "string",
                         "description": "Optional: specific template to check blind spots from"
+                    },
+                    "decision_id": {
+                        "type": "string",
+                        "description": "Optional: record these blind spots against a decision, to resolve with ack_blind_spot"
                     }
                 },
                 "required": ["decision_context"]
             }
         }),
+        json!({
+            "name": "ack_blind_spot",
+            "description": "Mark a blind spot raised for a decision as addressed, or as an accepted risk. Unresolved critical blind spots show up in learning stats.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "decision_id": {
+                        "type": "string",
+                        "description": "Decision the blind spot was recorded against (via check_blind_spots)"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Blind spot name, as returned by check_blind_spots"
+                    },
+                    "status": {
+                        "type": "string",
+                        "enum": ["addressed", "accepted_risk"]
+                    },
+                    "note": {
+                        "type": "string",
+                        "description": "Optional: how it was addressed, or why the risk is acceptable"
+                    }
+                },
+                "required": ["decision_id", "name", "status"]
+            }
+        }),
+        json!({
+            "name": "get_checklist",
+            "description": "Turn a decision's counsel into an ordered checklist: ACTION items first, then blind-spot checks (most severe first), then falsification conditions to watch. Item IDs are stable, so ticks made with tick_checklist_item survive recompiling.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "decision_id": {
+                        "type": "string",
+                        "description": "Decision to compile the checklist for"
+                    }
+                },
+                "required": ["decision_id"]
+            }
+        }),
+        json!({
+            "name": "tick_checklist_item",
+            "description": "Mark a checklist item done (or not done). Completion is kept with the decision so it can be compared with its outcome.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "decision_id": {
+                        "type": "string",
+                        "description": "Decision the checklist belongs to"
+                    },
+                    "item_id": {
+                        "type": "string",
+                        "description": "Item ID from get_checklist, e.g. 'A-3f9c1a2b'"
+                    },
+                    "done": {
+                        "type": "boolean",
+                        "description": "Optional: false to untick (default true)"
+                    }
+                },
+                "required": ["decision_id", "item_id"]
+            }
+        }),
+        json!({
+            "name": "evaluate_cost_of_delay",
+            "description": "Evaluate a filled cost-of-delay worksheet (the one counsel returns for prioritization questions). Each item's cost of delay is value + time criticality + risk reduction per week; items are sequenced by CD3 (cost of delay / duration), with the week each ships, its delay cost and the opportunity cost of building it first. Returns the sequence, the total delay cost of the CD3 order against the order given, and a recommendation.",
+            "inputSchema": input_schema::<crate::cost_of_delay::CostOfDelayRequest>()
+        }),
+        json!({
+            "name": "sanity_check_estimate",
+            "description": "Sanity-check a task estimate with reference-class forecasting. Compares it with the actual/estimate ratios of the most similar past tasks (by embedding when the semantic model is available), falls back to a planning-fallacy prior when fewer than 3 are known, and applies principle adjustments (Hofstadter's Law always, Brooks's Law when the plan adds people). Returns a calibrated low/likely/high range, the reference tasks, the cited principles and a verdict. The estimate is recorded: report how long it really took with record_estimate_actual.",
+            "inputSchema": input_schema::<crate::estimates::EstimateCheckRequest>()
+        }),
+        json!({
+            "name": "record_estimate_actual",
+            "description": "Record how long an estimated task really took, so it joins the reference class of later sanity_check_estimate calls.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "estimate_id": {
+                        "type": "integer",
+                        "description": "estimate_id returned by sanity_check_estimate"
+                    },
+                    "actual": {
+                        "type": "number",
+                        "description": "How long the task took, in the estimate's unit"
+                    }
+                },
+                "required": ["estimate_id", "actual"]
+            }
+        }),
         // NEW: Anti-pattern detection
         json!({
             "name": "detect_anti_patterns",
-            "description": "Check for known bad patterns in your approach. Returns anti-patterns with symptoms, source thinker, and cures.",
+            "description": "Check a plan, PRD or design doc for known anti-patterns. Every sentence is matched against the symptoms of every decision template's anti-patterns (keywords, plus embedding similarity when the semantic model is available). Returns each matched anti-pattern with the sentences that showed its symptoms, a score, the cure and the source thinker, best first.",
             "inputSchema": {
                 "type": "object",
                 "properties": {
                     "description": {
                         "type": "string",
-                        "description": "Describe your current approach/plan"
+                        "description": "Free text of your plan, PRD or design doc"
                     },
                     "domain": {
                         "type": "string",
-                        "description": "Optional domain filter"
+                        "description": "Optional: only check templates of this domain (e.g. software-architecture)"
                     }
                 },
                 "required": ["description"]
@@ -150,29 +374,34 @@ pub fn get_tools() -> Vec<Value> {
         json!({
             "name": "record_outcome",
             "description": "Record the outcome of a decision for learning. CRITICAL for 100minds to get smarter over time. Adjusts confidence in principles based on success/failure.",
+            "inputSchema": input_schema::<RecordOutcomeRequest>()
+        }),
+        json!({
+            "name": "record_falsification",
+            "description": "Report that a position's falsifiable_if condition was observed. Flags the decision and lowers confidence in the principles that position cited, separately from success/failure outcomes.",
             "inputSchema": {
                 "type": "object",
                 "properties": {
                     "decision_id": {
                         "type": "string",
-                        "description": "The decision ID from a previous counsel call"
+                        "description": "The decision whose counsel stated the condition"
                     },
-                    "success": {
-                        "type": "boolean",
-                        "description": "Whether the decision led to a successful outcome"
+                    "position": {
+                        "type": "string",
+                        "description": "Thinker name or ID of the position, or text from its falsifiable_if"
                     },
-                    "notes": {
+                    "evidence": {
                         "type": "string",
-                        "description": "Optional notes about what happened"
+                        "description": "What was observed"
                     }
                 },
-                "required": ["decision_id", "success"]
+                "required": ["decision_id", "position"]
             }
         }),
         // Principle search
         json!({
             "name": "search_principles",
-            "description": "Search 66 thinkers and 345+ principles. FTS5 full-text search with confidence scores.",
+            "description": "Find relevant principles without a full debate. Searches 66 thinkers and 345+ principles with BM25 keyword search, fused with semantic similarity when embeddings are computed; each result has id, name, thinker, description, score and (optionally) confidence. Misspelled words are corrected against the corpus first; the response then has searched_query and corrections.",
             "inputSchema": {
                 "type": "object",
                 "properties": {
@@ -182,11 +411,35 @@ pub fn get_tools() -> Vec<Value> {
                     },
                     "domain": {
                         "type": "string",
-                        "description": "Optional domain filter"
+                        "description": "Only principles whose thinker's domain or tags include this (e.g. software, economics)"
                     },
-                    "limit": {
+                    "top_k": {
                         "type": "integer",
                         "description": "Maximum results to return (default 10)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Older name for top_k"
+                    },
+                    "include_confidence": {
+                        "type": "boolean",
+                        "description": "Include each principle's learned confidence (default true)"
+                    },
+                    "semantic_weight": {
+                        "type": "number",
+                        "description": "Weight of embedding similarity in the fused ranking (default from [search] in config.toml)"
+                    },
+                    "bm25_weight": {
+                        "type": "number",
+                        "description": "Weight of BM25 keyword ranking in the fused ranking"
+                    },
+                    "min_score": {
+                        "type": "number",
+                        "description": "Drop results whose fused score (0-1) is below this"
+                    },
+                    "rerank": {
+                        "type": "boolean",
+                        "description": "Rerank the fused results"
                     }
                 },
                 "required": ["query"]
@@ -250,6 +503,126 @@ pub fn get_tools() -> Vec<Value> {
                 }
             }
         }),
+        json!({
+            "name": "get_learning_stats",
+            "description": "Live learning flywheel status: outcomes recorded, success rate, confidence adjustments, principles that improved or declined most, and unresolved critical blind spots. Rates come with 95% Wilson and Beta credible intervals, and the last 30 days, each domain and each principle's recent record are tested for significance, so check `significant` before calling a change real. Same data as --learning-stats.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {}
+            }
+        }),
+        json!({
+            "name": "coverage_report",
+            "description": "Corpus coverage analysis: thinker utilization, domain coverage, redundant and never-selected principles, and recommended thinker additions and removals. Same data as --analyze coverage; use it to decide when to explore, cull or add to the corpus.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "rebuild_coverage": {
+                        "type": "boolean",
+                        "description": "Recount selection counters from stored decisions first (default false)"
+                    }
+                }
+            }
+        }),
+        json!({
+            "name": "decision_themes",
+            "description": "Cluster recorded decision questions into recurring themes, each with its top keywords, outcome counts and success rate. Themes with several outcomes and mostly failures are flagged as struggling. Same data as --analyze themes.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "k": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Number of themes (default: about sqrt(decisions/2), at most 8)"
+                    }
+                }
+            }
+        }),
+        json!({
+            "name": "search_decisions",
+            "description": "Full-text search over past decisions: their questions, the arguments made and outcome notes. Returns decision ids, a snippet with the matched terms in [brackets], and each decision's outcome, best match first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Words to look for, e.g. \"payment retries\" (stemmed, any word matches)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Most results (default: 10, at most 100)"
+                    }
+                },
+                "required": ["query"]
+            }
+        }),
+        json!({
+            "name": "mark_event",
+            "description": "Bookmark a corpus or config change (\"imported SRE pack\", \"raised learning rate\") so cohort_report can compare decisions made before and after it.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "label": {
+                        "type": "string",
+                        "description": "What changed"
+                    }
+                },
+                "required": ["label"]
+            }
+        }),
+        json!({
+            "name": "cohort_report",
+            "description": "For every event bookmark, compare the decisions made before it with those made after: success rate, median hours to outcome, and principle diversity. Same data as --analyze cohorts.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {}
+            }
+        }),
+        json!({
+            "name": "approve_decision",
+            "description": "Approve an escalated decision that is pending review. The approval is signed over the decision's content hash and is final.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "decision_id": {
+                        "type": "string",
+                        "description": "The escalated decision to approve"
+                    },
+                    "reviewer": {
+                        "type": "string",
+                        "description": "Who reviewed it"
+                    },
+                    "note": {
+                        "type": "string",
+                        "description": "Optional reasoning or conditions"
+                    }
+                },
+                "required": ["decision_id", "reviewer"]
+            }
+        }),
+        json!({
+            "name": "reject_decision",
+            "description": "Reject an escalated decision that is pending review. Signed like approve_decision; a note explaining the rejection is required.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "decision_id": {
+                        "type": "string",
+                        "description": "The escalated decision to reject"
+                    },
+                    "reviewer": {
+                        "type": "string",
+                        "description": "Who reviewed it"
+                    },
+                    "note": {
+                        "type": "string",
+                        "description": "Why it was rejected"
+                    }
+                },
+                "required": ["decision_id", "reviewer", "note"]
+            }
+        }),
         // Audit trail
         json!({
             "name": "audit_decision",
@@ -295,31 +668,7 @@ pub fn get_tools() -> Vec<Value> {
         json!({
             "name": "record_outcomes_batch",
             "description": "Record multiple decision outcomes in batch. Used for offline worker catch-up or daemon restart recovery. Each outcome updates Thompson posteriors.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "outcomes": {
-                        "type": "array",
-                        "description": "Array of outcome records",
-                        "items": {
-                            "type": "object",
-                            "properties": {
-                                "decision_id": { "type": "string" },
-                                "success": { "type": "boolean" },
-                                "principle_ids": {
-                                    "type": "array",
-                                    "items": { "type": "string" }
-                                },
-                                "domain": { "type": "string" },
-                                "confidence_score": { "type": "number" },
-                                "failure_stage": { "type": "string" }
-                            },
-                            "required": ["decision_id", "success"]
-                        }
-                    }
-                },
-                "required": ["outcomes"]
-            }
+            "inputSchema": input_schema::<RecordOutcomesBatchRequest>()
         }),
         // Counterfactual simulation (Phase 2)
         json!({
@@ -345,6 +694,26 @@ pub fn get_tools() -> Vec<Value> {
                 "required": ["question", "excluded_principles"]
             }
         }),
+        // ASYNC: Fire-and-forget counsel for slow (deep) questions
+        json!({
+            "name": "submit_counsel_async",
+            "description": "Queue a counsel question for a background worker and return a job_id immediately. Takes the same arguments as counsel. Poll get_counsel_result with the job_id. Requires a running `100minds worker`.",
+            "inputSchema": input_schema::<CounselArgs>()
+        }),
+        json!({
+            "name": "get_counsel_result",
+            "description": "Status of a job from submit_counsel_async: queued, running, done (with the full counsel response) or failed (with the error).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "job_id": {
+                        "type": "string",
+                        "description": "Job ID returned by submit_counsel_async"
+                    }
+                },
+                "required": ["job_id"]
+            }
+        }),
     ]
 }
 
@@ -723,6 +1092,107 @@ pub fn validate_prd(_conn: &Connection, prd_json: &str) -> Result<PrdValidation>
     })
 }
 
+/// Version of the machine-readable PRD report; bump on breaking changes
+pub const PRD_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Severity threshold at which a PRD fails a CI gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailOn {
+    /// Fail on errors or a sub-70 score (same as `valid == false`)
+    Error,
+    /// Also fail on any warning
+    Warning,
+    /// Report only, never fail
+    Never,
+}
+
+impl FailOn {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "error" | "errors" => Some(FailOn::Error),
+            "warning" | "warnings" | "warn" => Some(FailOn::Warning),
+            "never" | "none" => Some(FailOn::Never),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FailOn::Error => "error",
+            FailOn::Warning => "warning",
+            FailOn::Never => "never",
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SeverityCounts {
+    pub error: usize,
+    pub warning: usize,
+    pub info: usize,
+}
+
+/// Stable, machine-readable PRD validation result for pipelines
+#[derive(Debug, Serialize)]
+pub struct PrdValidationReport {
+    pub schema_version: u32,
+    /// Whether the PRD passed the gate at `fail_on`
+    pub passed: bool,
+    pub fail_on: FailOn,
+    pub valid: bool,
+    pub score: f64,
+    pub counts: SeverityCounts,
+    pub findings: Vec<PrdWarning>,
+    pub suggestions: Vec<PrdSuggestion>,
+    pub principles_applied: Vec<String>,
+    pub blind_spots_to_check: Vec<String>,
+}
+
+impl PrdValidationReport {
+    /// Process exit code: 0 passed, 1 failed the gate
+    pub fn exit_code(&self) -> i32 {
+        if self.passed {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+/// Validate a PRD and judge it against a CI gate threshold
+pub fn validate_prd_report(conn: &Connection, prd_json: &str, fail_on: FailOn) -> Result<PrdValidationReport> {
+    let result = validate_prd(conn, prd_json)?;
+
+    let mut counts = SeverityCounts::default();
+    for w in &result.warnings {
+        match w.severity.as_str() {
+            "error" => counts.error += 1,
+            "warning" => counts.warning += 1,
+            _ => counts.info += 1,
+        }
+    }
+
+    let passed = match fail_on {
+        FailOn::Error => result.valid,
+        FailOn::Warning => result.valid && counts.warning == 0,
+        FailOn::Never => true,
+    };
+
+    Ok(PrdValidationReport {
+        schema_version: PRD_REPORT_SCHEMA_VERSION,
+        passed,
+        fail_on,
+        valid: result.valid,
+        score: result.score,
+        counts,
+        findings: result.warnings,
+        suggestions: result.suggestions,
+        principles_applied: result.principles_applied,
+        blind_spots_to_check: result.blind_spots_to_check,
+    })
+}
+
 // ============================================================================
 // PRE-WORK CONTEXT - Inject wisdom before starting a task
 // ============================================================================
@@ -904,6 +1374,35 @@ pub struct TemplateMatch {
     pub match_score: f64,
 }
 
+/// A matched template without its decision tree
+#[derive(Debug, Serialize)]
+pub struct TemplateSummary {
+    pub template_id: String,
+    pub name: String,
+    pub description: String,
+    pub domain: String,
+    pub match_score: f64,
+    pub blind_spots: usize,
+}
+
+/// The best `limit` templates for a question, as summaries
+pub fn match_template(question: &str, limit: usize) -> Vec<TemplateSummary> {
+    let mut matches = get_matching_templates(question);
+    matches.sort_by(|a, b| b.match_score.total_cmp(&a.match_score));
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|m| TemplateSummary {
+            blind_spots: m.template.blind_spots.len(),
+            template_id: m.template.id,
+            name: m.template.name,
+            description: m.template.description,
+            domain: m.template.domain,
+            match_score: m.match_score,
+        })
+        .collect()
+}
+
 /// Check blind spots for a decision context
 pub fn check_blind_spots(context: &str, template_id: Option<&str>) -> BlindSpotAnalysis {
     let mut all_blind_spots = Vec::new();
@@ -919,6 +1418,7 @@ pub fn check_blind_spots(context: &str, template_id: Option<&str>) -> BlindSpotA
                         check_question: bs.check_question.clone(),
                         severity: format!("{:?}", bs.severity),
                         source_template: template.name.clone(),
+                        status: None,
                     });
                 }
             }
@@ -933,6 +1433,7 @@ pub fn check_blind_spots(context: &str, template_id: Option<&str>) -> BlindSpotA
                     check_question: bs.check_question.clone(),
                     severity: format!("{:?}", bs.severity),
                     source_template: template.name.clone(),
+                    status: None,
                 });
             }
         }
@@ -948,6 +1449,7 @@ pub fn check_blind_spots(context: &str, template_id: Option<&str>) -> BlindSpotA
             check_question: "What's the deadline? What happens if you miss it?".to_string(),
             severity: "Medium".to_string(),
             source_template: "Generic".to_string(),
+            status: None,
         });
     }
 
@@ -958,6 +1460,7 @@ pub fn check_blind_spots(context: &str, template_id: Option<&str>) -> BlindSpotA
             check_question: "What if this fails? How do you undo it?".to_string(),
             severity: "High".to_string(),
             source_template: "Generic".to_string(),
+            status: None,
         });
     }
 
@@ -998,6 +1501,9 @@ pub struct BlindSpotResult {
     pub check_question: String,
     pub severity: String,
     pub source_template: String,
+    /// "open", "addressed" or "accepted_risk", once recorded against a decision
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
 }
 
 // ============================================================================
@@ -1062,17 +1568,98 @@ mod tests {
         assert!(result.warnings.iter().any(|w| w.principle == "YAGNI"));
     }
 
+    #[test]
+    fn test_validate_prd_report_gates() {
+        let prd = r#"{
+            "stories": [
+                {"id": "US-001", "title": "Add login", "description": "Add user login"},
+                {"id": "US-002", "title": "Future: Add OAuth", "description": "Maybe add OAuth someday"}
+            ]
+        }"#;
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+
+        let report = validate_prd_report(&conn, prd, FailOn::Error).unwrap();
+        assert_eq!(report.schema_version, PRD_REPORT_SCHEMA_VERSION);
+        assert!(report.counts.warning > 0);
+        assert_eq!(report.exit_code(), 0);
+
+        let strict = validate_prd_report(&conn, prd, FailOn::Warning).unwrap();
+        assert_eq!(strict.exit_code(), 1);
+
+        let json = serde_json::to_value(&strict).unwrap();
+        assert_eq!(json["fail_on"], "warning");
+        assert!(json["findings"].is_array());
+
+        assert_eq!(FailOn::parse("warnings"), Some(FailOn::Warning));
+        assert_eq!(FailOn::parse("bogus"), None);
+    }
+
+    #[test]
+    fn test_derived_tool_schemas_accept_example_payloads() {
+        let tools = get_tools();
+        let schema = |name: &str| {
+            let tool = tools.iter().find(|t| t["name"] == name).unwrap();
+            jsonschema::JSONSchema::compile(&tool["inputSchema"]).unwrap()
+        };
+
+        let counsel = json!({
+            "question": "Should we split the monolith?",
+            "depth": "deep",
+            "constraints": ["no downtime"],
+            "team_size": 4,
+            "snippets": [{"source": "ARCHITECTURE.md", "content": "One deployable"}],
+            "exploration": {"epsilon": 0.1}
+        });
+        assert!(schema("counsel").is_valid(&counsel));
+        assert!(schema("submit_counsel_async").is_valid(&counsel));
+        let request = serde_json::from_value::<CounselArgs>(counsel).unwrap().into_request();
+        assert_eq!((request.context.depth, request.context.team_size), (CounselDepth::Deep, Some(4)));
+        assert!(!schema("counsel").is_valid(&json!({"depth": "quick"})));
+        assert!(!schema("counsel").is_valid(&json!({"question": "q", "depth": "forever"})));
+        assert!(!schema("counsel").is_valid(&json!({"question": "q", "snippets": [{"source": "x"}]})));
+        let followup = json!({"parent_decision_id": "d-1", "refinement": "only 2 of us"});
+        assert!(schema("counsel_followup").is_valid(&followup));
+        assert!(!schema("counsel_followup").is_valid(&json!({"parent_decision_id": "d-1"})));
+        assert!(schema("evaluate_options").is_valid(&json!({"options": ["Rewrite", "Refactor"]})));
+        assert!(!schema("evaluate_options").is_valid(&json!({"question": "How?"})));
+        assert!(schema("sanity_check_estimate").is_valid(&json!({"task": "Add retries", "estimate": 3})));
+        let item = json!({"name": "SSO", "value_per_week": 2000, "duration_weeks": 4});
+        assert!(schema("evaluate_cost_of_delay").is_valid(&json!({"items": [item]})));
+        assert!(!schema("evaluate_cost_of_delay").is_valid(&json!({"items": [{"name": "SSO"}]})));
+        assert!(!schema("sanity_check_estimate").is_valid(&json!({"task": "Add retries", "estimate": "soon"})));
+
+        let outcome = json!({
+            "decision_id": "d-1",
+            "success": false,
+            "principle_ids": ["yagni"],
+            "failure_stage": "test",
+            "template_path": ["Yes", 2]
+        });
+        assert!(schema("record_outcome").is_valid(&outcome));
+        assert!(!schema("record_outcome").is_valid(&json!({"decision_id": "d-1", "success": "yes"})));
+        assert!(schema("record_outcomes_batch").is_valid(&json!({"outcomes": [outcome]})));
+        assert!(!schema("record_outcomes_batch").is_valid(&json!({"outcomes": [{"success": true}]})));
+        let batch: RecordOutcomesBatchRequest =
+            serde_json::from_value(json!({"outcomes": [{"decision_id": "d-1", "success": true}]})).unwrap();
+        assert_eq!(batch.outcomes.len(), 1);
+    }
+
     #[test]
     fn test_template_matching() {
         let matches =
             get_matching_templates("Should we use microservices or stay with our monolith?");
         assert!(!matches.is_empty());
         assert!(matches[0].template.id == "monolith-vs-microservices");
+
+        let summaries = match_template("Should we use microservices or stay with our monolith?", 1);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].template_id, "monolith-vs-microservices");
+        assert!(match_template("What should we name the cat?", 3).is_empty());
     }
 
     #[test]
     fn test_blind_spots() {
         let analysis = check_blind_spots("We want to migrate to microservices", None);
-        assert!(analysis.blind_spots.len() > 0);
+        assert!(!analysis.blind_spots.is_empty());
     }
 }
@@ -12,7 +12,7 @@
 //! - Use success_prob as mean estimate
 //! - Use uncertainty to boost exploration (UCB-style)
 
-use anyhow::{anyhow, Context, Result};
+use crate::error::{MindsError, Result};
 use ndarray::Array2;
 use ort::{
     inputs,
@@ -41,9 +41,9 @@ impl NeuralVocab {
     /// Load vocabulary from JSON file
     pub fn from_file(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
-            .context("Failed to read vocabulary file")?;
+            .map_err(|e| MindsError::Embedding(format!("Failed to read vocabulary file: {}", e)))?;
         let vocab: NeuralVocab = serde_json::from_str(&content)
-            .context("Failed to parse vocabulary JSON")?;
+            .map_err(|e| MindsError::Embedding(format!("Failed to parse vocabulary JSON: {}", e)))?;
         Ok(vocab)
     }
 
@@ -115,10 +115,10 @@ impl NeuralPosterior {
         let vocab_path = model_dir.join("neural_bandit_vocab.json");
 
         if !model_path.exists() {
-            return Err(anyhow!(
+            return Err(MindsError::Embedding(format!(
                 "Neural bandit model not found at {}. Run training first.",
                 model_path.display()
-            ));
+            )));
         }
 
         // Load ONNX model
@@ -126,7 +126,7 @@ impl NeuralPosterior {
             .with_optimization_level(GraphOptimizationLevel::Level3)?
             .with_intra_threads(2)?
             .commit_from_file(&model_path)
-            .context("Failed to load neural bandit ONNX model")?;
+            .map_err(|e| MindsError::Embedding(format!("Failed to load neural bandit ONNX model: {}", e)))?;
 
         // Load vocabulary
         let vocab = NeuralVocab::from_file(&vocab_path)?;
@@ -219,13 +219,13 @@ impl NeuralPosterior {
         // Extract outputs
         let success_output = outputs
             .get("success_prob")
-            .ok_or_else(|| anyhow!("No success_prob output"))?;
+            .ok_or_else(|| MindsError::Embedding("No success_prob output".to_string()))?;
         let (_shape, data) = success_output.try_extract_tensor::<f32>()?;
         let success_prob: f32 = *data.first().unwrap_or(&0.5);
 
         let uncertainty_output = outputs
             .get("uncertainty")
-            .ok_or_else(|| anyhow!("No uncertainty output"))?;
+            .ok_or_else(|| MindsError::Embedding("No uncertainty output".to_string()))?;
         let (_shape, data) = uncertainty_output.try_extract_tensor::<f32>()?;
         let uncertainty: f32 = *data.first().unwrap_or(&0.5);
 
@@ -277,11 +277,10 @@ impl NeuralPosterior {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
 
     #[test]
     fn test_context_vector_length() {
-        let vocab = NeuralVocab {
+        let _vocab = NeuralVocab {
             domain: [("architecture".to_string(), 0)].into_iter().collect(),
             stakeholder: [("Tech Lead".to_string(), 0)].into_iter().collect(),
             stage: [("growth".to_string(), 0)].into_iter().collect(),
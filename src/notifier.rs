@@ -0,0 +1,563 @@
+//! Chat Notifier
+//!
+//! Posts to a Slack or Discord channel through an incoming webhook, from
+//! two daemon jobs:
+//!
+//! - `notify`: escalated decisions awaiting review and critical blind spots
+//!   nobody has resolved, each posted once (`notifications_sent`)
+//! - `digest`: the week's learning: decisions and outcomes, the success
+//!   rate against the week before, and the principles whose confidence
+//!   moved most
+//!
+//! ```toml
+//! [notifier]
+//! webhook_url = "https://hooks.slack.com/services/..."  # or MINDS_NOTIFY_WEBHOOK
+//! format = "slack"      # or "discord"; guessed from the URL when unset
+//! digest_days = 7       # what the digest covers; schedule it with [daemon.jobs] digest
+//! ```
+//!
+//! Nothing is posted until a webhook URL is set. Teams not on chat can
+//! have the digest emailed instead (or as well), with the `email` feature:
+//!
+//! ```toml
+//! [notifier.smtp]
+//! server = "smtp.example.com"
+//! port = 587
+//! tls = "starttls"      # or "tls" (usually port 465), or "none" for a local relay
+//! from = "100minds <minds@example.com>"
+//! to = ["eng-leads@example.com"]
+//! username = "minds"    # password from MINDS_SMTP_PASSWORD
+//! ```
+//!
+//! The same digest, with each decision made or resolved in the window, is
+//! the server's Atom feed (see `feed.rs`).
+
+use crate::error::{MindsError, Result};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Escalations or blind spots per `notify` run; the rest wait for the next
+const NOTICE_LIMIT: usize = 20;
+/// Principles listed each way in the digest
+const TOP_MOVERS: usize = 5;
+/// Discord rejects longer messages
+const DISCORD_MAX_CHARS: usize = 2000;
+/// Decisions listed in the digest's activity
+const ACTIVITY_LIMIT: usize = 50;
+
+/// The `[notifier]` table of config.toml
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct NotifierConfig {
+    pub webhook_url: Option<String>,
+    /// "slack" or "discord"
+    pub format: Option<String>,
+    pub digest_days: i64,
+    pub smtp: Option<SmtpConfig>,
+}
+
+impl Default for NotifierConfig {
+    fn default() -> Self {
+        NotifierConfig { webhook_url: None, format: None, digest_days: 7, smtp: None }
+    }
+}
+
+/// `[notifier.smtp]`: where the digest is emailed
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SmtpConfig {
+    pub server: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    /// "starttls", "tls" or "none"
+    #[serde(default = "default_smtp_tls")]
+    pub tls: String,
+    pub from: String,
+    pub to: Vec<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Read from `MINDS_SMTP_PASSWORD`, never from the file
+    #[serde(skip)]
+    pub password: Option<String>,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_tls() -> String {
+    "starttls".to_string()
+}
+
+impl NotifierConfig {
+    /// Read `[notifier]` from the config.toml beside the database;
+    /// `MINDS_NOTIFY_WEBHOOK` wins over the file
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        let mut config: NotifierConfig = crate::config::section(conn, "notifier")?;
+        if let Ok(url) = std::env::var("MINDS_NOTIFY_WEBHOOK") {
+            config.webhook_url = Some(url);
+        }
+        if let Some(smtp) = &mut config.smtp {
+            smtp.password = std::env::var("MINDS_SMTP_PASSWORD").ok();
+        }
+        Ok(config)
+    }
+
+    /// Where to post and how, once a URL is set
+    pub fn target(&self) -> Result<Option<(&str, Format)>> {
+        let Some(url) = self.webhook_url.as_deref().filter(|u| !u.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let format = match self.format.as_deref() {
+            Some(format) => Format::parse(format)?,
+            None => Format::for_url(url),
+        };
+        Ok(Some((url, format)))
+    }
+}
+
+/// Webhook payload flavour
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Slack,
+    Discord,
+}
+
+impl Format {
+    pub fn parse(format: &str) -> Result<Self> {
+        match format.to_lowercase().as_str() {
+            "slack" => Ok(Format::Slack),
+            "discord" => Ok(Format::Discord),
+            _ => Err(MindsError::Config(format!("Unknown notifier format '{}' (slack or discord)", format))),
+        }
+    }
+
+    fn for_url(url: &str) -> Self {
+        if url.contains("discord.com/") || url.contains("discordapp.com/") {
+            Format::Discord
+        } else {
+            Format::Slack
+        }
+    }
+
+    /// The webhook body for a message
+    pub fn payload(self, message: &Message) -> Value {
+        match self {
+            Format::Slack => {
+                let lines: Vec<String> = message.lines.iter().map(|l| format!("• {}", l)).collect();
+                json!({"text": format!("*{}*\n{}", message.title, lines.join("\n"))})
+            }
+            Format::Discord => {
+                let lines: Vec<String> = message.lines.iter().map(|l| format!("- {}", l)).collect();
+                let mut content = format!("**{}**\n{}", message.title, lines.join("\n"));
+                if content.chars().count() > DISCORD_MAX_CHARS {
+                    content = content.chars().take(DISCORD_MAX_CHARS - 1).collect::<String>() + "…";
+                }
+                json!({"content": content})
+            }
+        }
+    }
+}
+
+/// A title and bullet lines, rendered per [`Format`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Message {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+impl Message {
+    /// Plain text, for email
+    pub fn text(&self) -> String {
+        let lines: Vec<String> = self.lines.iter().map(|l| format!("- {}", l)).collect();
+        format!("{}\n\n{}\n", self.title, lines.join("\n"))
+    }
+}
+
+/// Something to tell the channel about once
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Notice {
+    /// "escalation" or "blind_spot"
+    pub kind: String,
+    /// What makes it the same notice next time
+    pub key: String,
+    pub text: String,
+}
+
+/// Escalations and critical blind spots not posted yet
+pub fn new_notices(conn: &Connection) -> Result<Vec<Notice>> {
+    let escalations: Vec<Notice> = crate::approval::pending(conn, usize::MAX)?
+        .into_iter()
+        .map(|p| Notice {
+            kind: "escalation".to_string(),
+            text: format!("Escalated, awaiting review: \"{}\" (decision {})", p.question, p.decision_id),
+            key: p.decision_id,
+        })
+        .collect();
+    let blind_spots: Vec<Notice> = crate::blind_spots::unresolved_critical(conn, usize::MAX)?
+        .into_iter()
+        .map(|b| Notice {
+            kind: "blind_spot".to_string(),
+            key: format!("{}:{}", b.decision_id, b.name),
+            text: format!("Critical blind spot open: {} ({}) on decision {}", b.name, b.check_question, b.decision_id),
+        })
+        .collect();
+
+    let mut notices = Vec::new();
+    for candidates in [escalations, blind_spots] {
+        let mut added = 0;
+        for notice in candidates {
+            let sent: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM notifications_sent WHERE kind = ?1 AND key = ?2)",
+                params![notice.kind, notice.key],
+                |row| row.get(0),
+            )?;
+            if !sent && added < NOTICE_LIMIT {
+                notices.push(notice);
+                added += 1;
+            }
+        }
+    }
+    Ok(notices)
+}
+
+pub fn mark_sent(conn: &Connection, notices: &[Notice], now: DateTime<Utc>) -> Result<()> {
+    for notice in notices {
+        conn.execute(
+            "INSERT OR IGNORE INTO notifications_sent (kind, key, sent_at) VALUES (?1, ?2, ?3)",
+            params![notice.kind, notice.key, now.to_rfc3339()],
+        )?;
+    }
+    Ok(())
+}
+
+pub fn notices_message(notices: &[Notice]) -> Message {
+    let escalations = notices.iter().filter(|n| n.kind == "escalation").count();
+    Message {
+        title: format!(
+            "100minds: {} escalation(s) awaiting review, {} critical blind spot(s) open",
+            escalations,
+            notices.len() - escalations
+        ),
+        lines: notices.iter().map(|n| n.text.clone()).collect(),
+    }
+}
+
+/// A principle whose confidence moved over the digest window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mover {
+    pub principle_id: String,
+    pub name: String,
+    /// Sum of adjustments in the window
+    pub delta: f64,
+    pub confidence: f64,
+}
+
+/// A decision made or resolved in the digest window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Activity {
+    pub decision_id: String,
+    pub question: String,
+    pub created_at: String,
+    /// `None` while pending
+    pub outcome: Option<bool>,
+    pub outcome_notes: Option<String>,
+    /// When it last changed (outcome recorded, else created), RFC 3339
+    pub updated_at: String,
+}
+
+/// Learning over the last `digest_days`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Digest {
+    /// When the digest was taken, RFC 3339
+    pub generated_at: String,
+    pub since: String,
+    pub until: String,
+    pub decisions: i64,
+    pub outcomes: i64,
+    pub successes: i64,
+    /// `None` without outcomes in the window
+    pub success_rate: Option<f64>,
+    /// Same, for the window before
+    pub previous_success_rate: Option<f64>,
+    pub gainers: Vec<Mover>,
+    pub decliners: Vec<Mover>,
+    pub pending_reviews: usize,
+    pub unresolved_critical_blind_spots: usize,
+    /// Newest first
+    pub activity: Vec<Activity>,
+}
+
+pub fn digest(conn: &Connection, now: DateTime<Utc>, days: i64) -> Result<Digest> {
+    let since = now - Duration::days(days);
+    let before = since - Duration::days(days);
+    let decisions: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM decisions WHERE julianday(created_at) > julianday(?1) AND julianday(created_at) <= julianday(?2)",
+        params![since.to_rfc3339(), now.to_rfc3339()],
+        |row| row.get(0),
+    )?;
+    let outcomes_between = |from: DateTime<Utc>, to: DateTime<Utc>| -> rusqlite::Result<(i64, i64)> {
+        conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(outcome_success), 0) FROM decisions
+             WHERE outcome_success IS NOT NULL
+               AND julianday(outcome_recorded_at) > julianday(?1) AND julianday(outcome_recorded_at) <= julianday(?2)",
+            params![from.to_rfc3339(), to.to_rfc3339()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+    };
+    let rate = |(outcomes, successes): (i64, i64)| (outcomes > 0).then(|| successes as f64 / outcomes as f64);
+    let (outcomes, successes) = outcomes_between(since, now)?;
+    let previous = outcomes_between(before, since)?;
+
+    let movers: Vec<Mover> = conn
+        .prepare(
+            "SELECT fa.principle_id, COALESCE(p.name, fa.principle_id), SUM(fa.adjustment), COALESCE(p.learned_confidence, 0.5)
+             FROM framework_adjustments fa
+             LEFT JOIN principles p ON p.id = fa.principle_id
+             WHERE julianday(fa.created_at) > julianday(?1) AND julianday(fa.created_at) <= julianday(?2)
+             GROUP BY fa.principle_id
+             ORDER BY SUM(fa.adjustment) DESC, fa.principle_id",
+        )?
+        .query_map(params![since.to_rfc3339(), now.to_rfc3339()], |row| {
+            Ok(Mover { principle_id: row.get(0)?, name: row.get(1)?, delta: row.get(2)?, confidence: row.get(3)? })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    let gainers = movers.iter().filter(|m| m.delta > 0.0).take(TOP_MOVERS).cloned().collect();
+    let decliners = movers.iter().rev().filter(|m| m.delta < 0.0).take(TOP_MOVERS).cloned().collect();
+
+    let activity = conn
+        .prepare(
+            "SELECT id, question, created_at, outcome_success, outcome_notes,
+                    strftime('%Y-%m-%dT%H:%M:%SZ', COALESCE(outcome_recorded_at, created_at)) AS updated
+             FROM decisions
+             WHERE julianday(COALESCE(outcome_recorded_at, created_at)) > julianday(?1)
+               AND julianday(COALESCE(outcome_recorded_at, created_at)) <= julianday(?2)
+             ORDER BY julianday(updated) DESC, rowid DESC LIMIT ?3",
+        )?
+        .query_map(params![since.to_rfc3339(), now.to_rfc3339(), ACTIVITY_LIMIT as i64], |row| {
+            Ok(Activity {
+                decision_id: row.get(0)?,
+                question: row.get(1)?,
+                created_at: row.get(2)?,
+                outcome: row.get::<_, Option<i64>>(3)?.map(|s| s != 0),
+                outcome_notes: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+
+    Ok(Digest {
+        generated_at: now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        since: since.format("%Y-%m-%d").to_string(),
+        until: now.format("%Y-%m-%d").to_string(),
+        decisions,
+        outcomes,
+        successes,
+        success_rate: rate((outcomes, successes)),
+        previous_success_rate: rate(previous),
+        gainers,
+        decliners,
+        pending_reviews: crate::approval::pending(conn, usize::MAX)?.len(),
+        unresolved_critical_blind_spots: crate::blind_spots::unresolved_critical(conn, usize::MAX)?.len(),
+        activity,
+    })
+}
+
+impl Digest {
+    pub fn message(&self) -> Message {
+        let pct = |rate: Option<f64>| rate.map_or("n/a".to_string(), |r| format!("{:.0}%", r * 100.0));
+        let trend = match (self.success_rate, self.previous_success_rate) {
+            (Some(now), Some(before)) => format!(" ({:+.0} pts on the period before, {})", (now - before) * 100.0, pct(Some(before))),
+            _ => String::new(),
+        };
+        let movers = |movers: &[Mover]| {
+            movers.iter().map(|m| format!("{} {:+.2} → {:.2}", m.name, m.delta, m.confidence)).collect::<Vec<_>>().join(", ")
+        };
+        let mut lines = vec![
+            format!("{} decisions, {} outcomes ({} succeeded)", self.decisions, self.outcomes, self.successes),
+            format!("Success rate {}{}", pct(self.success_rate), trend),
+        ];
+        if !self.gainers.is_empty() {
+            lines.push(format!("Gaining: {}", movers(&self.gainers)));
+        }
+        if !self.decliners.is_empty() {
+            lines.push(format!("Declining: {}", movers(&self.decliners)));
+        }
+        if self.pending_reviews + self.unresolved_critical_blind_spots > 0 {
+            lines.push(format!(
+                "Still open: {} escalation(s) awaiting review, {} critical blind spot(s)",
+                self.pending_reviews, self.unresolved_critical_blind_spots
+            ));
+        }
+        Message { title: format!("100minds digest, {} to {}", self.since, self.until), lines }
+    }
+}
+
+/// Post a message to the webhook
+pub async fn send(url: &str, format: Format, message: &Message) -> Result<()> {
+    let response = reqwest::Client::new().post(url).json(&format.payload(message)).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        return Err(anyhow::anyhow!("Webhook answered {}: {}", status, response.text().await.unwrap_or_default()).into());
+    }
+    Ok(())
+}
+
+/// Email the digest: its message, then the decisions behind it
+#[cfg(feature = "email")]
+pub fn email(smtp: &SmtpConfig, digest: &Digest) -> Result<()> {
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{Message as Email, SmtpTransport, Transport};
+
+    let message = digest.message();
+    let mut body = message.text();
+    if !digest.activity.is_empty() {
+        body.push_str("\nDecisions:\n");
+        for a in &digest.activity {
+            let outcome = match a.outcome {
+                Some(true) => "succeeded",
+                Some(false) => "failed",
+                None => "pending",
+            };
+            body.push_str(&format!("- [{}] {} ({})\n", outcome, a.question, a.decision_id));
+        }
+    }
+    // lettre's address, message and transport errors all go out as `MindsError::Other`
+    let deliver = || -> anyhow::Result<()> {
+        let mut email = Email::builder().from(smtp.from.parse()?).subject(message.title);
+        for to in &smtp.to {
+            email = email.to(to.parse()?);
+        }
+        let email = email.body(body)?;
+
+        let mut transport = match smtp.tls.as_str() {
+            "starttls" => SmtpTransport::starttls_relay(&smtp.server)?,
+            "tls" => SmtpTransport::relay(&smtp.server)?,
+            "none" => SmtpTransport::builder_dangerous(&smtp.server),
+            other => {
+                let problem = format!("Unknown [notifier.smtp] tls '{}' (starttls, tls or none)", other);
+                return Err(MindsError::Config(problem).into());
+            }
+        }
+        .port(smtp.port);
+        if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+            transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        transport.build().send(&email)?;
+        Ok(())
+    };
+    Ok(deliver()?)
+}
+
+#[cfg(not(feature = "email"))]
+pub fn email(_smtp: &SmtpConfig, _digest: &Digest) -> Result<()> {
+    Err(MindsError::Config(
+        "[notifier.smtp] is set, but this build lacks the email feature (cargo build --features email)".to_string(),
+    ))
+}
+
+/// The `notify` and `digest` daemon jobs; returns what was sent, if anything
+pub async fn run_job(conn: &Connection, job: &str, now: DateTime<Utc>) -> Result<Option<Message>> {
+    let config = NotifierConfig::beside(conn)?;
+    let target = config.target()?;
+    match job {
+        "notify" => {
+            let Some((url, format)) = target else {
+                return Ok(None);
+            };
+            let notices = new_notices(conn)?;
+            if notices.is_empty() {
+                return Ok(None);
+            }
+            let message = notices_message(&notices);
+            send(url, format, &message).await?;
+            mark_sent(conn, &notices, now)?;
+            Ok(Some(message))
+        }
+        "digest" => {
+            if target.is_none() && config.smtp.is_none() {
+                return Ok(None);
+            }
+            let digest = digest(conn, now, config.digest_days)?;
+            let message = digest.message();
+            if let Some((url, format)) = target {
+                send(url, format, &message).await?;
+            }
+            if let Some(smtp) = &config.smtp {
+                email(smtp, &digest)?;
+            }
+            Ok(Some(message))
+        }
+        _ => Err(MindsError::Validation(format!("Unknown notifier job '{}'", job))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notices_are_posted_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        crate::db::insert_decision(&conn, "d1", "Drop the users table?", None, "{}", None, "h", "s", "k").unwrap();
+        crate::approval::request_review(&conn, "d1").unwrap();
+        let mut analysis = crate::mcp::check_blind_spots("Should we rewrite the legacy system from scratch?", None);
+        crate::blind_spots::record_checks(&conn, "d1", &mut analysis.blind_spots).unwrap();
+
+        let notices = new_notices(&conn).unwrap();
+        assert_eq!(notices[0].key, "d1");
+        assert!(notices.len() > 1 && notices[1..].iter().all(|n| n.kind == "blind_spot"));
+        let message = notices_message(&notices);
+        assert!(message.title.starts_with("100minds: 1 escalation(s)"));
+
+        mark_sent(&conn, &notices, Utc::now()).unwrap();
+        assert!(new_notices(&conn).unwrap().is_empty());
+
+        let slack = Format::for_url("https://hooks.slack.com/services/x").payload(&message);
+        assert!(slack["text"].as_str().unwrap().contains("• Escalated, awaiting review: \"Drop the users table?\""));
+        let discord = Format::for_url("https://discord.com/api/webhooks/1/x").payload(&message);
+        assert!(discord["content"].as_str().unwrap().starts_with("**100minds: 1 escalation(s)"));
+    }
+
+    #[test]
+    fn test_digest_compares_windows_and_ranks_movers() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let now = Utc::now();
+        let ago = |days: i64| (now - Duration::days(days)).to_rfc3339();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description, learned_confidence)
+                 VALUES ('yagni', 't', 'YAGNI', 'd', 0.6), ('bdu', 't', 'Big Design Up Front', 'd', 0.4);",
+        )
+        .unwrap();
+        for (id, success, days) in [("a", 1, 1), ("b", 0, 2), ("c", 1, 3), ("d", 1, 9), ("e", 1, 10)] {
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, created_at,
+                                        outcome_success, outcome_recorded_at)
+                 VALUES (?1, 'q', '{}', 'h', 's', 'k', ?3, ?2, ?3)",
+                params![id, success, ago(days)],
+            )
+            .unwrap();
+        }
+        for (principle, adjustment, days) in [("yagni", 0.05, 1), ("yagni", 0.05, 2), ("bdu", -0.05, 2), ("bdu", 0.2, 9)] {
+            conn.execute(
+                "INSERT INTO framework_adjustments (principle_id, adjustment, created_at) VALUES (?1, ?2, ?3)",
+                params![principle, adjustment, ago(days)],
+            )
+            .unwrap();
+        }
+
+        let digest = digest(&conn, now, 7).unwrap();
+        assert_eq!((digest.decisions, digest.outcomes, digest.successes), (3, 3, 2));
+        assert_eq!(digest.previous_success_rate, Some(1.0));
+        assert_eq!((digest.gainers[0].name.as_str(), digest.decliners[0].name.as_str()), ("YAGNI", "Big Design Up Front"));
+        let text = Format::Slack.payload(&digest.message())["text"].as_str().unwrap().to_string();
+        assert!(text.contains("Success rate 67% (-33 pts on the period before, 100%)"));
+        assert!(text.contains("Gaining: YAGNI +0.10 → 0.60"));
+        let activity: Vec<&str> = digest.activity.iter().map(|a| a.decision_id.as_str()).collect();
+        assert_eq!(activity, ["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,269 @@
+//! Option Scoring
+//!
+//! Sometimes the question isn't open: there are three concrete options on
+//! the table and what's wanted is a scored comparison rather than a debate.
+//! `evaluate_options` finds the principles relevant to the question and all
+//! options together, and scores every option against every principle:
+//!
+//! - each content word of the option found in the principle's name or
+//!   description is a hit
+//! - hits in a part that warns ("Never Rewrite From Scratch", "Don't build
+//!   capability until it is needed") count against the option; hits in a
+//!   part that prescribes count for it
+//! - alignment = (for - against) / max(for + against, 2), so -1 to 1
+//!
+//! An option's score is the mean of its alignments, weighted by each
+//! principle's learned confidence and relevance.
+//! The tilt names the best option, its margin over the runner-up, and the
+//! principles that separate the two most.
+
+use crate::db::PrincipleMatch;
+use crate::error::{MindsError, Result};
+use crate::search;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Most options compared at once
+pub const MAX_OPTIONS: usize = 6;
+/// Principles kept in the matrix
+pub const MAX_PRINCIPLES: usize = 10;
+/// Margin over the runner-up for a clear tilt
+pub const CLEAR_MARGIN: f64 = 0.3;
+/// Margin over the runner-up for a slight tilt
+pub const SLIGHT_MARGIN: f64 = 0.1;
+/// Principles named as driving the tilt
+const MAX_DRIVERS: usize = 3;
+
+/// Words that make a principle's name or description a warning
+const WARNING_CUES: [&str; 15] = [
+    "never", "don", "avoid", "beware", "stop", "against", "throw", "trap", "premature", "over", "later",
+    "cascade", "stall", "mistake", "pitfall",
+];
+
+/// Arguments of `evaluate_options`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EvaluateOptionsRequest {
+    /// The decision the options answer, e.g. 'How should we fix the billing system?'
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+    /// Two to six option descriptions
+    pub options: Vec<String>,
+    /// Optional domain to draw principles from as well
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+impl EvaluateOptionsRequest {
+    pub fn validate(&self) -> Result<()> {
+        if self.options.len() < 2 || self.options.len() > MAX_OPTIONS {
+            return Err(MindsError::Validation(format!(
+                "options must list 2 to {} options, got {}",
+                MAX_OPTIONS,
+                self.options.len()
+            )));
+        }
+        if self.options.iter().any(|o| o.trim().is_empty()) {
+            return Err(MindsError::Validation("options must not be blank".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Question and options as one text, for finding relevant principles
+    pub fn search_text(&self) -> String {
+        let mut parts: Vec<&str> = self.question.iter().map(String::as_str).collect();
+        parts.extend(self.options.iter().map(String::as_str));
+        parts.join(". ")
+    }
+}
+
+/// One principle's row of the matrix
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PrincipleRow {
+    pub principle_id: String,
+    pub name: String,
+    pub thinker_id: String,
+    /// Weight in the option scores: learned confidence times relevance
+    /// (relative to the most relevant principle)
+    pub weight: f64,
+    /// Alignment with each option, in option order, -1 to 1
+    pub alignments: Vec<f64>,
+}
+
+/// One option's overall score
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OptionScore {
+    pub option: String,
+    /// Weighted mean alignment, -1 to 1
+    pub score: f64,
+    /// Principles for and against it
+    pub supported_by: Vec<String>,
+    pub opposed_by: Vec<String>,
+}
+
+/// A principle separating the best option from the runner-up
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TiltDriver {
+    pub principle_id: String,
+    pub name: String,
+    /// Weighted alignment gap between best and runner-up
+    pub contribution: f64,
+}
+
+/// Which way the matrix leans
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Tilt {
+    /// Index into `options`
+    pub best: usize,
+    pub option: String,
+    /// Score over the runner-up
+    pub margin: f64,
+    /// "clear", "slight" or "even"
+    pub strength: String,
+    pub drivers: Vec<TiltDriver>,
+}
+
+/// Scored comparison of options
+#[derive(Debug, Clone, Serialize)]
+pub struct OptionsEvaluation {
+    pub question: Option<String>,
+    /// Best score first
+    pub options: Vec<OptionScore>,
+    /// Columns in the order the options were given
+    pub matrix: Vec<PrincipleRow>,
+    pub tilt: Tilt,
+}
+
+/// Content-word stems of a text
+fn stems(text: &str) -> HashSet<String> {
+    search::tokens(text)
+        .filter(|t| t.chars().count() >= 3 && !search::is_stopword(t))
+        .map(|t| search::stem(&t))
+        .collect()
+}
+
+fn is_warning(text: &str) -> bool {
+    search::tokens(text).any(|t| WARNING_CUES.contains(&search::stem(&t).as_str()))
+}
+
+/// How far an option follows (1) or goes against (-1) a principle
+pub fn alignment(option: &str, principle: &PrincipleMatch) -> f64 {
+    let option = stems(option);
+    let (mut pro, mut anti) = (0, 0);
+    for part in [&principle.name, &principle.description] {
+        let hits = stems(part).intersection(&option).count();
+        if is_warning(part) {
+            anti += hits;
+        } else {
+            pro += hits;
+        }
+    }
+    (pro as f64 - anti as f64) / (pro + anti).max(2) as f64
+}
+
+/// Score the options against candidate principles (most relevant first)
+pub fn evaluate(request: &EvaluateOptionsRequest, principles: &[PrincipleMatch]) -> Result<OptionsEvaluation> {
+    request.validate()?;
+    let top_relevance = principles.iter().map(|p| p.relevance_score).fold(0.0, f64::max);
+    let mut matrix: Vec<PrincipleRow> = principles
+        .iter()
+        .map(|p| PrincipleRow {
+            principle_id: p.id.clone(),
+            name: p.name.clone(),
+            thinker_id: p.thinker_id.clone(),
+            weight: p.confidence.clamp(0.05, 1.0)
+                * if top_relevance > 0.0 { (p.relevance_score / top_relevance).max(0.05) } else { 1.0 },
+            alignments: request.options.iter().map(|o| alignment(o, p)).collect(),
+        })
+        .filter(|row| row.alignments.iter().any(|a| *a != 0.0))
+        .collect();
+    let reach = |row: &PrincipleRow| row.weight * row.alignments.iter().map(|a| a.abs()).sum::<f64>();
+    matrix.sort_by(|a, b| reach(b).total_cmp(&reach(a)));
+    matrix.truncate(MAX_PRINCIPLES);
+
+    let total_weight: f64 = matrix.iter().map(|r| r.weight).sum();
+    let mut scores: Vec<(usize, OptionScore)> = request
+        .options
+        .iter()
+        .enumerate()
+        .map(|(i, option)| {
+            let weighted: f64 = matrix.iter().map(|r| r.weight * r.alignments[i]).sum();
+            let names = |keep: fn(f64) -> bool| {
+                matrix.iter().filter(|r| keep(r.alignments[i])).map(|r| r.name.clone()).collect()
+            };
+            let score = OptionScore {
+                option: option.clone(),
+                score: if total_weight > 0.0 { weighted / total_weight } else { 0.0 },
+                supported_by: names(|a| a > 0.0),
+                opposed_by: names(|a| a < 0.0),
+            };
+            (i, score)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.score.total_cmp(&a.1.score).then(a.0.cmp(&b.0)));
+
+    let (best, runner_up) = (scores[0].0, scores[1].0);
+    let margin = scores[0].1.score - scores[1].1.score;
+    let mut drivers: Vec<TiltDriver> = matrix
+        .iter()
+        .map(|r| TiltDriver {
+            principle_id: r.principle_id.clone(),
+            name: r.name.clone(),
+            contribution: r.weight * (r.alignments[best] - r.alignments[runner_up]),
+        })
+        .filter(|d| d.contribution > 0.0)
+        .collect();
+    drivers.sort_by(|a, b| b.contribution.total_cmp(&a.contribution));
+    drivers.truncate(MAX_DRIVERS);
+    let strength = match margin {
+        m if m >= CLEAR_MARGIN => "clear",
+        m if m >= SLIGHT_MARGIN => "slight",
+        _ => "even",
+    };
+
+    Ok(OptionsEvaluation {
+        question: request.question.clone(),
+        tilt: Tilt {
+            best,
+            option: request.options[best].clone(),
+            margin,
+            strength: strength.to_string(),
+            drivers,
+        },
+        options: scores.into_iter().map(|(_, s)| s).collect(),
+        matrix,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_are_scored_against_warnings_and_prescriptions() {
+        let fixture = crate::testing::fixture().unwrap();
+        let request = EvaluateOptionsRequest {
+            question: Some("How should we deal with the legacy billing system?".to_string()),
+            options: vec![
+                "Rewrite it from scratch in a new stack".to_string(),
+                "Strangle it incrementally, with characterization tests first".to_string(),
+                "Buy a billing product instead of building one".to_string(),
+            ],
+            domain: None,
+        };
+        let evaluation = fixture.counsel_engine().evaluate_options(&request).unwrap();
+
+        let row = |name: &str| evaluation.matrix.iter().find(|r| r.name == name).unwrap();
+        assert!(row("Never Rewrite From Scratch").alignments[0] < 0.0);
+        assert!(row("Characterization Tests").alignments[1] > 0.0);
+        assert!(row("Build vs Buy").alignments[2] > 0.0);
+        assert_eq!(evaluation.tilt.best, 1, "{:#?}", evaluation.options);
+        assert_eq!(evaluation.options[0].option, request.options[1]);
+        assert!(evaluation.tilt.margin > 0.0 && !evaluation.tilt.drivers.is_empty());
+        let rewrite = evaluation.options.iter().find(|o| o.option == request.options[0]).unwrap();
+        assert!(rewrite.opposed_by.contains(&"Never Rewrite From Scratch".to_string()));
+
+        let one = EvaluateOptionsRequest { options: vec!["Rewrite".to_string()], ..Default::default() };
+        assert!(matches!(fixture.counsel_engine().evaluate_options(&one), Err(MindsError::Validation(_))));
+    }
+}
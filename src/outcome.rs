@@ -9,17 +9,51 @@
 //! Without this, learned_confidence never changes.
 
 use crate::eval::thompson::init_thompson_schema;
-use anyhow::Result;
+use crate::types::RecordOutcomeRequest;
+use crate::error::{MindsError, Result};
+use crate::outcome_guard::{self, OutcomeGuardConfig, Verdict};
+use crate::stats::failures::{self, FailureStage};
+use crate::stats::intervals::{self, RateComparison, RateInterval};
 use chrono::Utc;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
+/// Confidence change for each principle behind a successful decision
+pub const SUCCESS_DELTA: f64 = 0.05;
+/// ... and a failed one. Asymmetric: failures hurt more than successes help
+pub const FAILURE_DELTA: f64 = -0.10;
+/// Outcomes keep a live principle's confidence within this range
+pub const CONFIDENCE_RANGE: (f64, f64) = (0.1, 0.95);
+
 /// Outcome recording result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutcomeResult {
     pub decision_id: String,
     pub principles_adjusted: Vec<PrincipleAdjustment>,
     pub new_confidences: Vec<(String, f64)>,
+    /// True when an idempotency key matched and nothing was re-applied
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub replayed: bool,
+    /// True when the decision already had this outcome; nothing was applied
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub duplicate: bool,
+    /// Why the outcome guard held the request for review instead of applying it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quarantined: Option<String>,
+}
+
+impl OutcomeResult {
+    /// A result for a request that changed nothing
+    fn unapplied(decision_id: &str) -> Self {
+        OutcomeResult {
+            decision_id: decision_id.to_string(),
+            principles_adjusted: Vec::new(),
+            new_confidences: Vec::new(),
+            replayed: false,
+            duplicate: false,
+            quarantined: None,
+        }
+    }
 }
 
 /// Individual principle adjustment
@@ -35,6 +69,8 @@ pub struct PrincipleAdjustment {
 /// Record an outcome for a decision
 ///
 /// This is THE critical function that activates the learning flywheel.
+/// The decision, confidence, adjustment, Thompson and reliability updates
+/// commit together or not at all.
 pub fn record_outcome(
     conn: &Connection,
     decision_id: &str,
@@ -46,6 +82,22 @@ pub fn record_outcome(
     // Initialize Thompson schema if needed
     init_thompson_schema(conn)?;
 
+    crate::db::with_savepoint(conn, "record_outcome", || {
+        apply_outcome(conn, decision_id, success, applied_principles, notes, context_pattern)
+    })
+}
+
+/// The writes behind [`record_outcome`], without the savepoint
+fn apply_outcome(
+    conn: &Connection,
+    decision_id: &str,
+    success: bool,
+    applied_principles: &[String],
+    notes: &str,
+    context_pattern: Option<&str>,
+) -> Result<OutcomeResult> {
+    let notes = &crate::redact::scrub_notes(conn, decision_id, notes)?;
+
     // 1. Update the decision with outcome
     let rows_updated = conn.execute(
         "UPDATE decisions
@@ -69,14 +121,18 @@ pub fn record_outcome(
                 Utc::now().to_rfc3339(),
             ],
         )?;
+        crate::db::count_decision_coverage(conn, None, "{}")?;
     }
 
     // 2. Adjust principle confidences (THE KEY PART)
+    // Old decisions may cite principles that have since been merged
+    let applied_principles = &crate::corpus::resolve_principle_ids(conn, applied_principles)?;
+    let (actor_id, actor_domain) = outcome_actor(conn, decision_id, context_pattern);
     let mut adjustments = Vec::new();
 
     // Asymmetric learning: failures hurt more than successes help
     // This implements Taleb's "skin in the game" - bad advice is penalized heavily
-    let delta = if success { 0.05 } else { -0.10 };
+    let delta = if success { SUCCESS_DELTA } else { FAILURE_DELTA };
 
     for principle_id in applied_principles {
         // Get current confidence
@@ -88,8 +144,10 @@ pub fn record_outcome(
             )
             .unwrap_or(0.5);
 
-        // Calculate new confidence (clamped to 0.1-0.95)
-        let new_confidence = (current + delta).clamp(0.1, 0.95);
+        // Calculate new confidence (clamped to 0.1-0.95); a culled
+        // principle (-1) stays archived, though its arms still learn
+        let (floor, ceiling) = CONFIDENCE_RANGE;
+        let new_confidence = if current < 0.0 { current } else { (current + delta).clamp(floor, ceiling) };
 
         // Update principle confidence
         conn.execute(
@@ -102,18 +160,22 @@ pub fn record_outcome(
         // Record the adjustment for tracking
         conn.execute(
             "INSERT INTO framework_adjustments
-             (principle_id, context_pattern, adjustment, decision_id)
-             VALUES (?1, ?2, ?3, ?4)",
+             (principle_id, context_pattern, adjustment, decision_id, actor_id)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
             params![
                 principle_id,
                 context_pattern.unwrap_or("{}"),
                 delta,
                 decision_id,
+                actor_id,
             ],
         )?;
 
         // Update Thompson Sampling parameters
         update_thompson_params(conn, principle_id, success, context_pattern)?;
+        if let (Some(actor), Some(domain)) = (&actor_id, &actor_domain) {
+            crate::db::update_actor_arm(conn, actor, principle_id, domain, success)?;
+        }
 
         // Get principle name for reporting
         let name: String = conn
@@ -133,7 +195,15 @@ pub fn record_outcome(
         });
     }
 
-    // 3. Build result
+    // 3. Update the track record of the thinkers behind these principles
+    crate::db::update_thinker_reliability(conn, applied_principles, success)?;
+    let context = context_pattern.and_then(|c| serde_json::from_str::<serde_json::Value>(c).ok());
+    if let Some(context) = &context {
+        crate::template_feedback::record_from_context(conn, decision_id, context, success)?;
+    }
+    failures::record_from_context(conn, decision_id, context.as_ref(), success)?;
+
+    // 4. Build result
     let new_confidences: Vec<(String, f64)> = adjustments
         .iter()
         .map(|a| (a.principle_id.clone(), a.new_confidence))
@@ -143,9 +213,40 @@ pub fn record_outcome(
         decision_id: decision_id.to_string(),
         principles_adjusted: adjustments,
         new_confidences,
+        replayed: false,
+        duplicate: false,
+        quarantined: None,
     })
 }
 
+/// Who an outcome belongs to (the outcome context wins, then the context
+/// the decision was counseled with), and the domain counsel will look the
+/// actor's arms up under: detected from the question, as in scoring, or
+/// the outcome's domain when there is no counseled question
+fn outcome_actor(conn: &Connection, decision_id: &str, context_pattern: Option<&str>) -> (Option<String>, Option<String>) {
+    let field = |json: &serde_json::Value, key: &str| {
+        json.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty()).map(String::from)
+    };
+    let outcome: serde_json::Value =
+        context_pattern.and_then(|c| serde_json::from_str(c).ok()).unwrap_or_default();
+    let counseled: Option<(String, serde_json::Value)> = conn
+        .query_row(
+            "SELECT question, context_json FROM decisions WHERE id = ?1 AND context_json IS NOT NULL",
+            [decision_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .ok()
+        .map(|(question, context)| (question, serde_json::from_str(&context).unwrap_or_default()));
+
+    let actor = field(&outcome, "actor_id")
+        .or_else(|| counseled.as_ref().and_then(|(_, context)| field(context, "actor_id")));
+    let domain = match &counseled {
+        Some((question, _)) => Some(crate::relevance::QueryFeatures::new(question).domain().to_string()),
+        None => field(&outcome, "domain"),
+    };
+    (actor, domain)
+}
+
 /// Update Thompson Sampling parameters for a principle
 fn update_thompson_params(
     conn: &Connection,
@@ -155,7 +256,7 @@ fn update_thompson_params(
 ) -> Result<()> {
     // Update global Thompson parameters
     // Beta distribution: alpha = successes + 1, beta = failures + 1
-    let (alpha_delta, beta_delta) = if success { (1.0, 0.0) } else { (0.0, 1.0) };
+    let (alpha_delta, beta_delta) = crate::eval::bandit::beta_deltas(success);
 
     conn.execute(
         "INSERT INTO thompson_arms (principle_id, alpha, beta, pulls)
@@ -173,13 +274,12 @@ fn update_thompson_params(
         if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(ctx) {
             if let Some(domain) = parsed.get("domain").and_then(|d| d.as_str()) {
                 conn.execute(
-                    "INSERT INTO thompson_domain_arms (principle_id, domain, alpha, beta, sample_count)
-                     VALUES (?1, ?2, 1.0 + ?3, 1.0 + ?4, 1)
+                    "INSERT INTO thompson_domain_arms (principle_id, domain, alpha, beta)
+                     VALUES (?1, ?2, 1.0 + ?3, 1.0 + ?4)
                      ON CONFLICT(principle_id, domain) DO UPDATE SET
                         alpha = alpha + ?3,
                         beta = beta + ?4,
-                        sample_count = sample_count + 1,
-                        last_updated = CURRENT_TIMESTAMP",
+                        updated_at = CURRENT_TIMESTAMP",
                     params![principle_id, domain, alpha_delta, beta_delta],
                 )?;
             }
@@ -294,7 +394,24 @@ pub fn get_learning_stats(conn: &Connection) -> Result<LearningStats> {
         .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
         .collect::<Result<Vec<_>, _>>()?;
 
+    let window = |from_days: i64, to_days: i64| -> Result<RateInterval> {
+        let (successes, trials): (i64, i64) = conn.query_row(
+            "SELECT COALESCE(SUM(outcome_success = 1), 0), COUNT(*) FROM decisions
+             WHERE outcome_success IS NOT NULL
+               AND julianday(outcome_recorded_at) >= julianday('now', ?1)
+               AND julianday(outcome_recorded_at) < julianday('now', ?2)",
+            params![format!("-{} days", from_days), format!("-{} days", to_days)],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(RateInterval::new(successes as u64, trials as u64))
+    };
+    let recent_change = intervals::compare(window(2 * WINDOW_DAYS, WINDOW_DAYS)?, window(WINDOW_DAYS, 0)?);
+
     Ok(LearningStats {
+        success_rate_interval: RateInterval::new(successful_outcomes as u64, total_outcomes as u64),
+        recent_change,
+        principle_evidence: principle_evidence(conn)?,
+        domains: domain_evidence(conn)?,
         total_outcomes,
         successful_outcomes,
         success_rate: if total_outcomes > 0 {
@@ -306,63 +423,239 @@ pub fn get_learning_stats(conn: &Connection) -> Result<LearningStats> {
         principles_with_learning,
         top_improved,
         top_declined,
+        unresolved_critical_blind_spots: crate::blind_spots::unresolved_critical(conn, 20)?,
+        alerts: crate::watchdog::scan(conn, &crate::watchdog::WatchdogConfig::beside(conn)?, Utc::now())?,
+        pending_reviews: crate::approval::pending(conn, 20)?,
     })
 }
 
+/// Days in each window the stats compare (the latest against the one before)
+pub const WINDOW_DAYS: i64 = 30;
+
+/// Principles [`get_learning_stats`] reports evidence for, most-adjusted first
+const EVIDENCE_LIMIT: usize = 20;
+
+/// A principle's outcome record, and whether its last [`WINDOW_DAYS`] differ
+#[derive(Debug, Clone, Serialize)]
+pub struct PrincipleEvidence {
+    pub principle_id: String,
+    pub name: String,
+    /// Confidence gains (successes) out of all adjustments
+    pub interval: RateInterval,
+    /// Earlier adjustments against the last [`WINDOW_DAYS`]
+    pub recent_change: Option<RateComparison>,
+}
+
+/// Success rate of decisions in one detected domain, against all the others
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainEvidence {
+    pub domain: String,
+    pub interval: RateInterval,
+    /// The other domains (before) against this one (after)
+    pub versus_rest: Option<RateComparison>,
+}
+
+fn principle_evidence(conn: &Connection) -> Result<Vec<PrincipleEvidence>> {
+    let rows: Vec<(String, String, i64, i64, i64, i64)> = conn
+        .prepare(
+            "SELECT p.id, p.name, SUM(fa.adjustment > 0), COUNT(*),
+                    SUM(fa.adjustment > 0 AND julianday(fa.created_at) >= julianday('now', ?1)),
+                    SUM(julianday(fa.created_at) >= julianday('now', ?1))
+             FROM framework_adjustments fa JOIN principles p ON p.id = fa.principle_id
+             GROUP BY p.id
+             ORDER BY COUNT(*) DESC, p.id
+             LIMIT ?2",
+        )?
+        .query_map(params![format!("-{} days", WINDOW_DAYS), EVIDENCE_LIMIT as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(rows
+        .into_iter()
+        .map(|(principle_id, name, successes, trials, recent_successes, recent_trials)| {
+            let earlier = RateInterval::new((successes - recent_successes) as u64, (trials - recent_trials) as u64);
+            let recent = RateInterval::new(recent_successes as u64, recent_trials as u64);
+            PrincipleEvidence {
+                principle_id,
+                name,
+                interval: RateInterval::new(successes as u64, trials as u64),
+                recent_change: intervals::compare(earlier, recent),
+            }
+        })
+        .collect())
+}
+
+fn domain_evidence(conn: &Connection) -> Result<Vec<DomainEvidence>> {
+    let mut counts: std::collections::BTreeMap<String, (u64, u64)> = Default::default();
+    let mut stmt = conn.prepare("SELECT question, outcome_success FROM decisions WHERE outcome_success IS NOT NULL")?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let question: String = row.get(0)?;
+        let domain = crate::relevance::QueryFeatures::new(&question).domain().to_string();
+        let entry = counts.entry(domain).or_default();
+        entry.0 += (row.get::<_, i64>(1)? == 1) as u64;
+        entry.1 += 1;
+    }
+    let (all_successes, all_trials) = counts.values().fold((0, 0), |(s, n), (ds, dn)| (s + ds, n + dn));
+    let mut domains: Vec<DomainEvidence> = counts
+        .into_iter()
+        .map(|(domain, (successes, trials))| DomainEvidence {
+            domain,
+            interval: RateInterval::new(successes, trials),
+            versus_rest: intervals::compare(
+                RateInterval::new(all_successes - successes, all_trials - trials),
+                RateInterval::new(successes, trials),
+            ),
+        })
+        .collect();
+    domains.sort_by(|a, b| b.interval.trials.cmp(&a.interval.trials).then_with(|| a.domain.cmp(&b.domain)));
+    Ok(domains)
+}
+
 /// Learning statistics summary
 #[derive(Debug, Clone, Serialize)]
 pub struct LearningStats {
     pub total_outcomes: i64,
     pub successful_outcomes: i64,
     pub success_rate: f64,
+    /// `success_rate` with its 95% Wilson and credible intervals
+    pub success_rate_interval: RateInterval,
+    /// Outcomes of the [`WINDOW_DAYS`] before the last against the last
+    pub recent_change: Option<RateComparison>,
+    /// The most-adjusted principles' gains out of adjustments, with intervals
+    pub principle_evidence: Vec<PrincipleEvidence>,
+    /// Success rate per detected domain, each tested against the rest
+    pub domains: Vec<DomainEvidence>,
     pub total_adjustments: i64,
     pub principles_with_learning: i64,
     pub top_improved: Vec<(String, f64, i64)>,
     pub top_declined: Vec<(String, f64, i64)>,
+    /// Critical blind spots raised for decisions and never acknowledged
+    pub unresolved_critical_blind_spots: Vec<crate::blind_spots::UnresolvedBlindSpot>,
+    /// Suspicious learning patterns the watchdog sees right now
+    pub alerts: Vec<crate::watchdog::Alert>,
+    /// Escalated decisions nobody has approved or rejected
+    pub pending_reviews: Vec<crate::approval::PendingReview>,
 }
 
 /// Print learning statistics in a human-readable format
 pub fn print_learning_stats(stats: &LearningStats) {
-    println!("\n┌─────────────────────────────────────────────────────────────┐");
-    println!("│ 🔄 LEARNING FLYWHEEL STATUS                                 │");
-    println!("└─────────────────────────────────────────────────────────────┘\n");
+    say!("\n┌─────────────────────────────────────────────────────────────┐");
+    say!("│ 🔄 LEARNING FLYWHEEL STATUS                                 │");
+    say!("└─────────────────────────────────────────────────────────────┘\n");
 
     if stats.total_outcomes == 0 {
-        println!("⚠️  FLYWHEEL NOT ACTIVATED");
-        println!("   No outcomes recorded yet. Record outcomes to start learning:");
-        println!("   cargo run --bin 100minds -- --outcome <decision-id> --success --principles \"id1,id2\"");
+        say!("⚠️  FLYWHEEL NOT ACTIVATED");
+        say!("   No outcomes recorded yet. Record outcomes to start learning:");
+        say!("   cargo run --bin 100minds -- --outcome <decision-id> --success --principles \"id1,id2\"");
         return;
     }
 
-    println!("OUTCOMES:");
-    println!("   Total: {}", stats.total_outcomes);
-    println!(
-        "   Successful: {} ({:.1}%)",
+    say!("OUTCOMES:");
+    say!("   Total: {}", stats.total_outcomes);
+    let (low, high) = stats.success_rate_interval.wilson;
+    say!(
+        "   Successful: {} ({:.1}%, 95% CI {:.1}-{:.1}%)",
         stats.successful_outcomes,
-        stats.success_rate * 100.0
+        stats.success_rate * 100.0,
+        low * 100.0,
+        high * 100.0
     );
-    println!();
+    if let Some(change) = &stats.recent_change {
+        say!(
+            "   Last {} days: {:.1}% vs {:.1}% before ({:+.1} points, {})",
+            WINDOW_DAYS,
+            change.after.rate * 100.0,
+            change.before.rate * 100.0,
+            change.difference * 100.0,
+            change.verdict()
+        );
+    }
+    say!();
 
-    println!("LEARNING:");
-    println!("   Adjustments made: {}", stats.total_adjustments);
-    println!("   Principles learning: {}", stats.principles_with_learning);
-    println!();
+    say!("LEARNING:");
+    say!("   Adjustments made: {}", stats.total_adjustments);
+    say!("   Principles learning: {}", stats.principles_with_learning);
+    say!();
 
     if !stats.top_improved.is_empty() {
-        println!("📈 TOP IMPROVED PRINCIPLES:");
+        say!("📈 TOP IMPROVED PRINCIPLES:");
         for (name, delta, count) in &stats.top_improved {
             let bar = if *delta > 0.0 { "+" } else { "" };
-            println!("   {}{:.2} ({} adjustments) - {}", bar, delta, count, name);
+            say!("   {}{:.2} ({} adjustments) - {}", bar, delta, count, name);
         }
-        println!();
+        say!();
     }
 
     if !stats.top_declined.is_empty() {
-        println!("📉 PRINCIPLES NEEDING REVIEW:");
+        say!("📉 PRINCIPLES NEEDING REVIEW:");
         for (name, delta, count) in &stats.top_declined {
-            println!("   {:.2} ({} adjustments) - {}", delta, count, name);
+            say!("   {:.2} ({} adjustments) - {}", delta, count, name);
+        }
+        say!();
+    }
+
+    if !stats.principle_evidence.is_empty() {
+        say!("🔬 EVIDENCE (gains / adjustments, 95% CI; change over the last {} days):", WINDOW_DAYS);
+        for evidence in stats.principle_evidence.iter().take(10) {
+            let interval = &evidence.interval;
+            let change = evidence.recent_change.as_ref().map_or(String::new(), |c| {
+                format!(" - {:+.0} points, {}", c.difference * 100.0, c.verdict())
+            });
+            say!(
+                "   {}/{} ({:.0}-{:.0}%){} - {}",
+                interval.successes,
+                interval.trials,
+                interval.wilson.0 * 100.0,
+                interval.wilson.1 * 100.0,
+                change,
+                evidence.name
+            );
+        }
+        say!();
+    }
+
+    if stats.domains.len() > 1 {
+        say!("🧭 SUCCESS BY DOMAIN (95% CI; against the other domains):");
+        for domain in &stats.domains {
+            let interval = &domain.interval;
+            let versus = domain.versus_rest.as_ref().map_or("-".to_string(), RateComparison::verdict);
+            say!(
+                "   {:<24} {:.0}% of {} ({:.0}-{:.0}%), {}",
+                domain.domain,
+                interval.rate * 100.0,
+                interval.trials,
+                interval.wilson.0 * 100.0,
+                interval.wilson.1 * 100.0,
+                versus
+            );
+        }
+        say!();
+    }
+
+    if !stats.unresolved_critical_blind_spots.is_empty() {
+        say!("🚨 UNRESOLVED CRITICAL BLIND SPOTS:");
+        for spot in &stats.unresolved_critical_blind_spots {
+            say!("   {} - {} ({})", spot.decision_id, spot.name, spot.check_question);
         }
-        println!();
+        say!();
+    }
+
+    if !stats.pending_reviews.is_empty() {
+        say!("⏳ ESCALATIONS AWAITING REVIEW:");
+        for review in &stats.pending_reviews {
+            say!("   {} - {}", review.decision_id, review.question);
+        }
+        say!();
+    }
+
+    if !stats.alerts.is_empty() {
+        say!("🚨 WATCHDOG ALERTS:");
+        for alert in &stats.alerts {
+            let marker = if alert.severity == "critical" { "🔴" } else { "🟡" };
+            say!("   {} [{}] {}", marker, alert.kind, alert.message);
+        }
+        say!();
     }
 }
 
@@ -370,7 +663,7 @@ pub fn print_learning_stats(stats: &LearningStats) {
 // SWARM INTEGRATION - Distributed learning synchronization
 // ============================================================================
 
-use crate::types::{PrinciplePosterior, RecordOutcomeRequest, SyncPosteriorsResponse};
+use crate::types::{PrinciplePosterior, SyncPosteriorsResponse};
 use std::collections::HashMap;
 
 /// Sync Thompson posteriors for distributed swarm learning
@@ -459,48 +752,70 @@ pub fn sync_posteriors(
 }
 
 /// Record outcomes in batch (for worker catch-up sync)
+///
+/// Each request goes through [`record_outcome_v2`]; a batch the outcome
+/// guard considers an outlier is quarantined whole instead.
 pub fn record_outcomes_batch(
     conn: &Connection,
     outcomes: &[RecordOutcomeRequest],
 ) -> Result<Vec<OutcomeResult>> {
-    let mut results = Vec::new();
-
-    for outcome in outcomes {
-        let context = outcome.domain.as_ref().map(|d| {
-            serde_json::json!({
-                "domain": d,
-                "confidence_score": outcome.confidence_score,
-                "failure_stage": outcome.failure_stage,
-            })
-            .to_string()
-        });
-
-        let result = record_outcome(
-            conn,
-            &outcome.decision_id,
-            outcome.success,
-            &outcome.principle_ids,
-            outcome.notes.as_deref().unwrap_or(""),
-            context.as_deref(),
-        )?;
-
-        results.push(result);
+    if let Some(held) = hold_outlier_batch(conn, outcomes)? {
+        return Ok(held);
     }
+    outcomes.iter().map(|outcome| record_outcome_v2(conn, outcome)).collect()
+}
 
-    Ok(results)
+/// Quarantine `outcomes` together if they look like an automation bug;
+/// `None` when they should be recorded one by one
+pub fn hold_outlier_batch(conn: &Connection, outcomes: &[RecordOutcomeRequest]) -> Result<Option<Vec<OutcomeResult>>> {
+    let config = OutcomeGuardConfig::beside(conn)?;
+    let Some(reason) = outcome_guard::outlier_batch(&config, outcomes) else {
+        return Ok(None);
+    };
+    let batch_id = outcome_guard::new_batch_id();
+    crate::db::with_savepoint(conn, "quarantine_batch", || {
+        outcomes
+            .iter()
+            .map(|outcome| {
+                outcome_guard::quarantine(conn, Some(&batch_id), outcome, &reason)?;
+                Ok(OutcomeResult { quarantined: Some(reason.clone()), ..OutcomeResult::unapplied(&outcome.decision_id) })
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    })
 }
 
 /// Enhanced record outcome with swarm fields
+///
+/// With an `idempotency_key`, a retry of an already-applied request returns
+/// the original result (marked `replayed`) instead of adjusting twice.
+/// Requests the outcome guard stops come back `duplicate` or `quarantined`.
 pub fn record_outcome_v2(
     conn: &Connection,
     request: &RecordOutcomeRequest,
 ) -> Result<OutcomeResult> {
-    // Build context with swarm-specific fields
-    let context = serde_json::json!({
-        "domain": request.domain,
-        "confidence_score": request.confidence_score,
-        "failure_stage": request.failure_stage,
-    });
+    if let Some(key) = &request.idempotency_key {
+        if let Some(mut previous) = lookup_outcome_key(conn, &request.decision_id, key)? {
+            previous.replayed = true;
+            return Ok(previous);
+        }
+    }
+
+    let config = OutcomeGuardConfig::beside(conn)?;
+    match outcome_guard::check(conn, &config, request, Utc::now())? {
+        Verdict::Apply => apply_request(conn, request),
+        Verdict::Duplicate(_) => Ok(OutcomeResult { duplicate: true, ..OutcomeResult::unapplied(&request.decision_id) }),
+        Verdict::Quarantine(reason) => {
+            outcome_guard::quarantine(conn, None, request, &reason)?;
+            Ok(OutcomeResult { quarantined: Some(reason), ..OutcomeResult::unapplied(&request.decision_id) })
+        }
+    }
+}
+
+/// Apply a request without consulting the outcome guard (it passed, or
+/// someone reviewed it in quarantine)
+pub fn apply_request(conn: &Connection, request: &RecordOutcomeRequest) -> Result<OutcomeResult> {
+    let context = request_context(request);
 
     // If principle_ids not provided, look them up from stored counsel_json
     let principle_ids = if request.principle_ids.is_empty() {
@@ -509,18 +824,199 @@ pub fn record_outcome_v2(
         request.principle_ids.clone()
     };
 
-    record_outcome(
-        conn,
-        &request.decision_id,
-        request.success,
-        &principle_ids,
-        request.notes.as_deref().unwrap_or(""),
-        Some(&context.to_string()),
-    )
+    // The key is stored in the same savepoint, so a failed store can't leave
+    // an applied outcome that a retry would apply again
+    crate::db::with_savepoint(conn, "keyed_outcome", || {
+        let result = record_outcome(
+            conn,
+            &request.decision_id,
+            request.success,
+            &principle_ids,
+            request.notes.as_deref().unwrap_or(""),
+            Some(&context.to_string()),
+        )?;
+        if let Some(key) = &request.idempotency_key {
+            store_outcome_key(conn, &request.decision_id, key, &result)?;
+        }
+        outcome_guard::log_applied(conn, request, Utc::now())?;
+        Ok(result)
+    })
+}
+
+/// Context pattern for a request, with the swarm-specific fields
+fn request_context(request: &RecordOutcomeRequest) -> serde_json::Value {
+    let mut context = serde_json::json!({
+        "domain": request.domain,
+        "confidence_score": request.confidence_score,
+        "failure_stage": request.failure_stage.as_deref().and_then(FailureStage::parse),
+        "actor_id": request.actor_id,
+    });
+    if !request.failure_reasons.is_empty() {
+        context["failure_reasons"] = serde_json::json!(request.failure_reasons);
+    }
+    if let Some(template_id) = &request.template_id {
+        context["template_id"] = serde_json::json!(template_id);
+        context["template_path"] = serde_json::json!(request.template_path);
+    }
+    context
+}
+
+/// Result stored for a (decision, idempotency key) pair, if already applied
+pub fn lookup_outcome_key(conn: &Connection, decision_id: &str, key: &str) -> Result<Option<OutcomeResult>> {
+    let json: Option<String> = conn
+        .query_row(
+            "SELECT result_json FROM outcome_keys WHERE decision_id = ?1 AND idempotency_key = ?2",
+            params![decision_id, key],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(json.map(|j| serde_json::from_str(&j)).transpose()?)
+}
+
+/// Remember the result of an applied keyed outcome
+pub fn store_outcome_key(conn: &Connection, decision_id: &str, key: &str, result: &OutcomeResult) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO outcome_keys (decision_id, idempotency_key, result_json) VALUES (?1, ?2, ?3)",
+        params![decision_id, key, serde_json::to_string(result)?],
+    )?;
+    Ok(())
+}
+
+/// Reject requests that can't be applied meaningfully
+pub fn validate_outcome_request(request: &RecordOutcomeRequest) -> Result<()> {
+    if request.decision_id.trim().is_empty() {
+        return Err(MindsError::Validation("decision_id is empty".to_string()));
+    }
+    if let Some(c) = request.confidence_score {
+        if !(0.0..=1.0).contains(&c) {
+            return Err(MindsError::Validation(format!("confidence_score {} is outside 0.0-1.0", c)));
+        }
+    }
+    failures::check(request.success, request.failure_stage.as_deref(), &request.failure_reasons)
+        .map_err(MindsError::Validation)?;
+    if request.template_id.is_none() && !request.template_path.is_empty() {
+        return Err(MindsError::Validation("template_path given without template_id".to_string()));
+    }
+    Ok(())
+}
+
+/// How `import_batch` applies a file
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportOptions {
+    /// Validate and report, then roll everything back
+    pub dry_run: bool,
+    /// Any bad record aborts the whole import
+    pub all_or_nothing: bool,
+}
+
+/// A record that wasn't applied, by 1-based line number
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportIssue {
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    pub applied: Vec<OutcomeResult>,
+    pub duplicates: Vec<ImportIssue>,
+    /// Held by the outcome guard for review
+    pub quarantined: Vec<ImportIssue>,
+    pub errors: Vec<ImportIssue>,
+    /// True when nothing was kept (dry run, or all-or-nothing with errors)
+    pub rolled_back: bool,
+}
+
+/// Apply a JSONL file of `RecordOutcomeRequest`s in one transaction
+///
+/// Each record gets its own savepoint, so a bad record is rolled back and
+/// reported without disturbing the others. Duplicates - the same decision
+/// and idempotency key earlier in the file or in a previous import, or an
+/// outcome the decision already has - are skipped. A file the outcome guard
+/// considers an outlier batch is quarantined whole.
+pub fn import_batch(conn: &Connection, jsonl: &str, options: ImportOptions) -> Result<ImportReport> {
+    let mut tx = conn.unchecked_transaction()?;
+    let mut report = ImportReport::default();
+    let mut seen = std::collections::HashSet::new();
+    let parsed: Vec<RecordOutcomeRequest> = jsonl.lines().filter_map(|line| serde_json::from_str(line).ok()).collect();
+    let outlier = outcome_guard::outlier_batch(&OutcomeGuardConfig::beside(conn)?, &parsed)
+        .map(|reason| (outcome_guard::new_batch_id(), reason));
+
+    for (index, line) in jsonl.lines().enumerate() {
+        let line_no = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let issue = |decision_id: Option<&str>, message: String| ImportIssue {
+            line: line_no,
+            decision_id: decision_id.map(String::from),
+            message,
+        };
+
+        let request: RecordOutcomeRequest = match serde_json::from_str(line) {
+            Ok(r) => r,
+            Err(e) => {
+                report.errors.push(issue(None, format!("invalid JSON: {}", e)));
+                continue;
+            }
+        };
+        let decision_id = request.decision_id.as_str();
+        if let Err(e) = validate_outcome_request(&request) {
+            report.errors.push(issue(Some(decision_id), e.to_string()));
+            continue;
+        }
+
+        let key = request.idempotency_key.clone();
+        if !seen.insert((request.decision_id.clone(), key.clone())) {
+            report.duplicates.push(issue(Some(decision_id), "repeated earlier in this file".to_string()));
+            continue;
+        }
+        if let Some(key) = &key {
+            if lookup_outcome_key(&tx, decision_id, key)?.is_some() {
+                report.duplicates.push(issue(Some(decision_id), format!("key '{}' already applied", key)));
+                continue;
+            }
+        }
+
+        if let Some((batch_id, reason)) = &outlier {
+            outcome_guard::quarantine(&tx, Some(batch_id), &request, reason)?;
+            report.quarantined.push(issue(Some(decision_id), reason.clone()));
+            continue;
+        }
+
+        let sp = tx.savepoint()?;
+        match record_outcome_v2(&sp, &request) {
+            Ok(result) => {
+                sp.commit()?;
+                if result.duplicate {
+                    report.duplicates.push(issue(Some(decision_id), "outcome already recorded".to_string()));
+                } else if let Some(reason) = &result.quarantined {
+                    report.quarantined.push(issue(Some(decision_id), reason.clone()));
+                } else {
+                    report.applied.push(result);
+                }
+            }
+            Err(e) => {
+                // Dropping the savepoint rolls this record back
+                drop(sp);
+                report.errors.push(issue(Some(decision_id), e.to_string()));
+            }
+        }
+    }
+
+    report.rolled_back = options.dry_run || (options.all_or_nothing && !report.errors.is_empty());
+    if report.rolled_back {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+    Ok(report)
 }
 
 /// Extract principle IDs from a stored decision's counsel_json
-fn extract_principles_from_decision(conn: &Connection, decision_id: &str) -> Vec<String> {
+pub fn extract_principles_from_decision(conn: &Connection, decision_id: &str) -> Vec<String> {
     let counsel_json: Option<String> = conn
         .query_row(
             "SELECT counsel_json FROM decisions WHERE id = ?1",
@@ -691,9 +1187,9 @@ mod tests {
             )
             .unwrap();
 
-        // Fresh insert uses delta values directly: alpha=1.0, beta=0.0 for success
-        assert!((alpha - 1.0).abs() < 0.001);
-        assert!((beta - 0.0).abs() < 0.001);
+        // Fresh insert starts from the Beta(1,1) prior: alpha=2.0, beta=1.0 after a success
+        assert!((alpha - 2.0).abs() < 0.001);
+        assert!((beta - 1.0).abs() < 0.001);
     }
 
     #[test]
@@ -743,6 +1239,21 @@ mod tests {
         assert_eq!(stats.successful_outcomes, 2);
         assert!((stats.success_rate - 0.666).abs() < 0.01);
         assert_eq!(stats.principles_with_learning, 1);
+
+        // Three outcomes leave a wide interval, and 2/2 then 0/1 is no evidence of a decline
+        let (low, high) = stats.success_rate_interval.wilson;
+        assert!(low < 0.25 && high > 0.9);
+        let evidence = &stats.principle_evidence[0];
+        assert_eq!((evidence.interval.successes, evidence.interval.trials), (2, 3));
+        assert!(evidence.recent_change.is_none(), "every adjustment is recent");
+        conn.execute(
+            "UPDATE decisions SET outcome_recorded_at = datetime('now', '-40 days') WHERE id IN ('d1', 'd2')",
+            [],
+        )
+        .unwrap();
+        let change = get_learning_stats(&conn).unwrap().recent_change.unwrap();
+        assert_eq!((change.before.trials, change.after.trials, change.difference), (2, 1, -1.0));
+        assert!(!change.significant);
     }
 
     #[test]
@@ -781,6 +1292,11 @@ mod tests {
                 domain: Some("testing".to_string()),
                 confidence_score: None,
                 failure_stage: None,
+                failure_reasons: Vec::new(),
+                idempotency_key: None,
+                actor_id: None,
+                template_id: None,
+                template_path: vec![],
             },
             RecordOutcomeRequest {
                 decision_id: "batch-2".to_string(),
@@ -790,6 +1306,11 @@ mod tests {
                 domain: Some("testing".to_string()),
                 confidence_score: None,
                 failure_stage: None,
+                failure_reasons: Vec::new(),
+                idempotency_key: None,
+                actor_id: None,
+                template_id: None,
+                template_path: vec![],
             },
         ];
 
@@ -859,4 +1380,304 @@ mod tests {
 
         assert_eq!(count, 1, "Domain-specific Thompson arm should exist");
     }
+
+    /// Make inserts/updates on `table` fail when `condition` holds
+    fn inject_failure(conn: &Connection, table: &str, event: &str, condition: &str) {
+        conn.execute_batch(&format!(
+            "CREATE TRIGGER inject_{table} BEFORE {event} ON {table} WHEN {condition}
+             BEGIN SELECT RAISE(ABORT, 'injected failure'); END;"
+        ))
+        .unwrap();
+    }
+
+    fn outcome_state(conn: &Connection, decision_id: &str) -> (Option<i32>, f64, f64, i64, i64, i64) {
+        let outcome = conn
+            .query_row("SELECT outcome_success FROM decisions WHERE id = ?1", [decision_id], |row| row.get(0))
+            .optional()
+            .unwrap()
+            .flatten();
+        let confidence = |id: &str| {
+            conn.query_row("SELECT learned_confidence FROM principles WHERE id = ?1", [id], |row| row.get(0))
+                .unwrap()
+        };
+        let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, i64>(0)).unwrap();
+        (
+            outcome,
+            confidence("pa"),
+            confidence("pb"),
+            count("SELECT COUNT(*) FROM framework_adjustments"),
+            count("SELECT COUNT(*) FROM thompson_arms"),
+            count("SELECT COUNT(*) FROM thinker_reliability"),
+        )
+    }
+
+    #[test]
+    fn test_record_outcome_is_atomic_under_injected_failures() {
+        let failures = [
+            ("framework_adjustments", "INSERT", "NEW.principle_id = 'pb'"),
+            ("thompson_arms", "INSERT", "NEW.principle_id = 'pb'"),
+            ("thinker_reliability", "INSERT", "1"),
+            ("principles", "UPDATE", "OLD.id = 'pb'"),
+        ];
+        for (table, event, condition) in failures {
+            let (conn, _dir) = setup_test_db();
+            insert_test_thinker(&conn, "ta", "Thinker", "domain");
+            insert_test_principle(&conn, "pa", "ta", "First");
+            insert_test_principle(&conn, "pb", "ta", "Second");
+            init_thompson_schema(&conn).unwrap();
+            let before = outcome_state(&conn, "atomic-1");
+
+            inject_failure(&conn, table, event, condition);
+            let result = record_outcome(&conn, "atomic-1", true, &["pa".to_string(), "pb".to_string()], "", None);
+            assert!(result.is_err(), "{} {} should fail", event, table);
+            assert_eq!(outcome_state(&conn, "atomic-1"), before, "partial write after {} {} failed", event, table);
+
+            // The connection is usable again once the fault is gone
+            conn.execute_batch(&format!("DROP TRIGGER inject_{}", table)).unwrap();
+            record_outcome(&conn, "atomic-1", true, &["pa".to_string(), "pb".to_string()], "", None).unwrap();
+            assert_eq!(outcome_state(&conn, "atomic-1").3, 2);
+        }
+    }
+
+    #[test]
+    fn test_failed_key_store_rolls_back_outcome() {
+        let (conn, _dir) = setup_test_db();
+        insert_test_thinker(&conn, "ta", "Thinker", "domain");
+        insert_test_principle(&conn, "pa", "ta", "First");
+        insert_test_principle(&conn, "pb", "ta", "Second");
+        init_thompson_schema(&conn).unwrap();
+        let before = outcome_state(&conn, "atomic-2");
+
+        inject_failure(&conn, "outcome_keys", "INSERT", "1");
+        let request: RecordOutcomeRequest = serde_json::from_str(
+            r#"{"decision_id": "atomic-2", "success": false, "principle_ids": ["pa"], "idempotency_key": "k"}"#,
+        )
+        .unwrap();
+        assert!(record_outcome_v2(&conn, &request).is_err());
+        assert_eq!(outcome_state(&conn, "atomic-2"), before);
+    }
+
+    #[test]
+    fn test_domain_arm_updates_from_its_prior() {
+        let (conn, _dir) = setup_test_db();
+        insert_test_thinker(&conn, "t10", "Thinker", "domain");
+        insert_test_principle(&conn, "p10", "t10", "Test Principle");
+        let context = serde_json::json!({"domain": "software-design"}).to_string();
+
+        // First outcome inserts the arm, the second updates it in place
+        record_outcome(&conn, "d-1", true, &["p10".to_string()], "Success!", Some(&context)).unwrap();
+        record_outcome(&conn, "d-2", false, &["p10".to_string()], "Failed!", Some(&context)).unwrap();
+
+        let (alpha, beta): (f64, f64) = conn
+            .query_row(
+                "SELECT alpha, beta FROM thompson_domain_arms WHERE principle_id = ?1 AND domain = ?2",
+                params!["p10", "software-design"],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        // Beta(1,1) prior plus one success and one failure
+        assert_eq!((alpha, beta), (2.0, 2.0));
+    }
+
+    #[test]
+    fn test_keyed_outcome_is_applied_once() {
+        let (conn, _dir) = setup_test_db();
+        insert_test_thinker(&conn, "t11", "Thinker", "domain");
+        insert_test_principle(&conn, "p11", "t11", "Test Principle");
+
+        let request: RecordOutcomeRequest = serde_json::from_str(
+            r#"{"decision_id": "retry-1", "success": true, "principle_ids": ["p11"], "idempotency_key": "req-42"}"#,
+        )
+        .unwrap();
+        let first = record_outcome_v2(&conn, &request).unwrap();
+        assert!(!first.replayed);
+
+        let retry = record_outcome_v2(&conn, &request).unwrap();
+        assert!(retry.replayed);
+        assert_eq!(retry.new_confidences, first.new_confidences);
+
+        let adjustments: i64 = conn
+            .query_row("SELECT COUNT(*) FROM framework_adjustments WHERE decision_id = 'retry-1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(adjustments, 1);
+    }
+
+    #[test]
+    fn test_import_batch_reports_per_record() {
+        let (conn, _dir) = setup_test_db();
+        insert_test_thinker(&conn, "t10", "Thinker", "domain");
+        insert_test_principle(&conn, "p10", "t10", "Test Principle");
+
+        let jsonl = [
+            r#"{"decision_id": "imp-1", "success": true, "principle_ids": ["p10"], "idempotency_key": "k1"}"#,
+            r#"{"decision_id": "imp-1", "success": true, "principle_ids": ["p10"], "idempotency_key": "k1"}"#,
+            "",
+            r#"{"decision_id": "imp-2", "success": false"#,
+            r#"{"decision_id": "imp-3", "success": true, "confidence_score": 1.5}"#,
+            r#"{"decision_id": "imp-4", "success": false, "principle_ids": ["p10"]}"#,
+        ]
+        .join("\n");
+
+        let report = import_batch(&conn, &jsonl, ImportOptions::default()).unwrap();
+        assert_eq!(report.applied.len(), 2);
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(report.duplicates[0].line, 2);
+        assert_eq!(report.errors.iter().map(|e| e.line).collect::<Vec<_>>(), vec![4, 5]);
+        assert!(!report.rolled_back);
+
+        // Re-importing: the keyed record matches its key, the unkeyed one
+        // matches the outcome the decision already has
+        let report = import_batch(&conn, &jsonl, ImportOptions::default()).unwrap();
+        assert!(report.applied.is_empty());
+        assert_eq!(report.duplicates.iter().map(|d| d.line).collect::<Vec<_>>(), vec![1, 2, 6]);
+        let adjustments: i64 = conn
+            .query_row("SELECT COUNT(*) FROM framework_adjustments WHERE decision_id = 'imp-1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(adjustments, 1);
+    }
+
+    #[test]
+    fn test_import_batch_all_or_nothing_rolls_back() {
+        let (conn, _dir) = setup_test_db();
+        let jsonl = "{\"decision_id\": \"ok\", \"success\": true}\nnot json\n";
+
+        let options = ImportOptions { all_or_nothing: true, ..Default::default() };
+        let report = import_batch(&conn, jsonl, options).unwrap();
+        assert!(report.rolled_back);
+        assert_eq!(report.applied.len(), 1);
+
+        let decisions: i64 = conn.query_row("SELECT COUNT(*) FROM decisions", [], |row| row.get(0)).unwrap();
+        assert_eq!(decisions, 0);
+    }
+
+    /// One step of a random interleaving
+    #[derive(Debug, Clone)]
+    enum Step {
+        Counsel(usize),
+        Outcome(usize, bool),
+        Decay(u32),
+        Archive(usize),
+    }
+
+    fn step() -> impl proptest::strategy::Strategy<Value = Step> {
+        use proptest::prelude::*;
+        prop_oneof![
+            3 => (0..3usize).prop_map(Step::Counsel),
+            4 => (any::<usize>(), any::<bool>()).prop_map(|(i, s)| Step::Outcome(i, s)),
+            2 => (0..60u32).prop_map(Step::Decay),
+            1 => any::<usize>().prop_map(Step::Archive),
+        ]
+    }
+
+    /// Every stored decision links to the one before and carries a valid
+    /// signature over the content its hash was taken of
+    fn assert_chain_verifies(conn: &Connection) {
+        let mut stmt = conn
+            .prepare("SELECT context_json, counsel_json, previous_hash, content_hash FROM decisions ORDER BY rowid")
+            .unwrap();
+        let rows: Vec<(String, String, Option<String>, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        let mut links = Vec::new();
+        for (context, counsel, previous_hash, content_hash) in rows {
+            let response: crate::types::CounselResponse = serde_json::from_str(&counsel).unwrap();
+            let context: crate::types::CounselContext = serde_json::from_str(&context).unwrap();
+            let content = serde_json::json!({
+                "question": response.question,
+                "context": context,
+                "positions": response.positions,
+                "challenge": response.challenge,
+            });
+            links.push(crate::provenance::ChainLink {
+                content: serde_json::to_vec(&content).unwrap(),
+                content_hash,
+                previous_hash,
+                signature: response.provenance.signature,
+                agent_pubkey: response.provenance.agent_pubkey,
+            });
+        }
+        let verification = crate::provenance::Provenance::ephemeral().verify_chain(&links);
+        assert!(verification.valid, "{:?}", verification.errors);
+        assert!(links.first().is_none_or(|first| first.previous_hash.is_none()));
+        assert!(crate::retention::check_chain(conn).unwrap().is_empty());
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(24))]
+
+        /// Whatever order counsel, outcomes, decay and culling happen in,
+        /// confidences stay in [0, 1] (or -1 once archived, for good), the
+        /// provenance chain verifies, and no Beta arm loses its positive
+        /// parameters
+        #[test]
+        fn prop_learning_invariants_hold_under_interleaving(steps in proptest::collection::vec(step(), 1..14)) {
+            let fixture = crate::testing::fixture().unwrap();
+            let conn = &fixture.conn;
+            init_thompson_schema(conn).unwrap();
+            let engine = fixture.counsel_engine();
+            let policy = crate::eval::bandit::FeelGoodIndex { config: Default::default() };
+            let questions = [
+                crate::testing::SAMPLE_QUESTION,
+                "Should we add engineers to the late project?",
+                "The API is slow under load, should we add caching and timeouts?",
+            ];
+            let principles: Vec<String> = conn
+                .prepare("SELECT id FROM principles ORDER BY id").unwrap()
+                .query_map([], |row| row.get(0)).unwrap()
+                .collect::<rusqlite::Result<_>>().unwrap();
+            let mut decisions: Vec<(String, Vec<String>)> = Vec::new();
+            let mut archived = std::collections::HashSet::new();
+            let mut now = chrono::Utc::now();
+
+            for step in steps {
+                match step {
+                    Step::Counsel(q) => {
+                        let request = crate::types::CounselRequest {
+                            question: questions[q].to_string(),
+                            context: Default::default(),
+                            decision_id: None,
+                        };
+                        let response = engine.counsel(&request).unwrap();
+                        let cited = crate::ZestyEngine::extract_principle_ids(&response);
+                        decisions.push((response.decision_id, cited));
+                    }
+                    Step::Outcome(i, success) if !decisions.is_empty() => {
+                        let (id, cited) = &decisions[i % decisions.len()];
+                        record_outcome(conn, id, success, cited, "", None).unwrap();
+                    }
+                    Step::Outcome(..) => {}
+                    Step::Decay(days) => {
+                        now += chrono::Duration::days(days as i64);
+                        crate::decay::apply_decay(conn, &policy, now).unwrap();
+                    }
+                    Step::Archive(i) => {
+                        // What `--cull` does to a principle that keeps failing
+                        let id = &principles[i % principles.len()];
+                        conn.execute("UPDATE principles SET learned_confidence = -1.0 WHERE id = ?1", [id]).unwrap();
+                        archived.insert(id.clone());
+                    }
+                }
+            }
+
+            let confidences: Vec<(String, f64)> = conn
+                .prepare("SELECT id, learned_confidence FROM principles").unwrap()
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+                .collect::<rusqlite::Result<_>>().unwrap();
+            for (id, confidence) in confidences {
+                if archived.contains(&id) {
+                    proptest::prop_assert_eq!(confidence, -1.0, "{} was archived", id);
+                } else {
+                    proptest::prop_assert!((0.0..=1.0).contains(&confidence), "{} at {}", id, confidence);
+                }
+            }
+            for table in ["thompson_arms", "thompson_domain_arms", "contextual_arms", "actor_arms"] {
+                let sql = format!("SELECT COUNT(*) FROM {} WHERE NOT (alpha > 0 AND beta > 0)", table);
+                let bad: i64 = conn.query_row(&sql, [], |row| row.get(0)).unwrap();
+                proptest::prop_assert_eq!(bad, 0, "{} has non-positive parameters", table);
+            }
+            assert_chain_verifies(conn);
+        }
+    }
 }
@@ -0,0 +1,230 @@
+//! Outcome Guard
+//!
+//! A buggy worker can record hundreds of bogus failures in a minute and
+//! undo months of learning. Before an outcome request touches any
+//! confidence or posterior it is checked here:
+//!
+//! - **duplicate**: the decision already has this exact outcome; applying
+//!   it again would count it twice. Reported, not applied.
+//! - **rate limit**: its source (the request's `actor_id`, or
+//!   "unattributed") already recorded `max_per_source_per_hour` outcomes
+//!   in the last hour. Quarantined.
+//! - **outlier batch**: a batch of at least `outlier_batch_min` outcomes
+//!   that are nearly all failures. The whole batch is quarantined.
+//!
+//! Quarantined requests wait in `quarantined_outcomes` until someone
+//! applies or drops them (`100minds outcomes quarantined|apply|drop`).
+//! Limits come from `[outcome_guard]` in config.toml.
+
+use crate::types::RecordOutcomeRequest;
+use crate::error::{MindsError, Result};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// Source of requests without an `actor_id`
+pub const UNATTRIBUTED: &str = "unattributed";
+
+/// The `[outcome_guard]` table of config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutcomeGuardConfig {
+    pub max_per_source_per_hour: usize,
+    /// Smallest batch checked for being an outlier...
+    pub outlier_batch_min: usize,
+    /// ...and the failure share that makes it one
+    pub outlier_failure_rate: f64,
+}
+
+impl Default for OutcomeGuardConfig {
+    fn default() -> Self {
+        OutcomeGuardConfig { max_per_source_per_hour: 120, outlier_batch_min: 20, outlier_failure_rate: 0.9 }
+    }
+}
+
+impl OutcomeGuardConfig {
+    /// Read `[outcome_guard]` from the config.toml beside the database; a missing file or table gives the defaults
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        crate::config::section(conn, "outcome_guard")
+    }
+}
+
+/// What to do with one outcome request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Apply,
+    Duplicate(String),
+    Quarantine(String),
+}
+
+/// A request held for review
+#[derive(Debug, Clone, Serialize)]
+pub struct QuarantinedOutcome {
+    pub id: i64,
+    /// Shared by every request of a quarantined batch
+    pub batch_id: Option<String>,
+    pub source: String,
+    pub request: RecordOutcomeRequest,
+    pub reason: String,
+    pub created_at: String,
+}
+
+pub fn source(request: &RecordOutcomeRequest) -> &str {
+    request.actor_id.as_deref().filter(|a| !a.is_empty()).unwrap_or(UNATTRIBUTED)
+}
+
+/// Check one request against the recorded outcomes and its source's rate
+pub fn check(conn: &Connection, config: &OutcomeGuardConfig, request: &RecordOutcomeRequest, now: DateTime<Utc>) -> Result<Verdict> {
+    let recorded: Option<i64> = conn
+        .query_row("SELECT outcome_success FROM decisions WHERE id = ?1", [&request.decision_id], |row| row.get(0))
+        .unwrap_or(None);
+    if recorded == Some(request.success as i64) {
+        let outcome = if request.success { "success" } else { "failure" };
+        return Ok(Verdict::Duplicate(format!("{} already recorded for {}", outcome, request.decision_id)));
+    }
+
+    let source = source(request);
+    let recent: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM outcome_log WHERE source = ?1 AND julianday(recorded_at) > julianday(?2)",
+        params![source, (now - Duration::hours(1)).to_rfc3339()],
+        |row| row.get(0),
+    )?;
+    if recent as usize >= config.max_per_source_per_hour {
+        return Ok(Verdict::Quarantine(format!(
+            "rate limit: {} already recorded {} outcomes in the last hour",
+            source, recent
+        )));
+    }
+    Ok(Verdict::Apply)
+}
+
+/// Why `requests` look like an automation bug rather than real outcomes, if they do
+pub fn outlier_batch(config: &OutcomeGuardConfig, requests: &[RecordOutcomeRequest]) -> Option<String> {
+    let failures = requests.iter().filter(|r| !r.success).count();
+    let share = failures as f64 / requests.len().max(1) as f64;
+    (requests.len() >= config.outlier_batch_min && share >= config.outlier_failure_rate).then(|| {
+        format!("outlier batch: {} of {} outcomes are failures", failures, requests.len())
+    })
+}
+
+/// Count an applied request against its source's rate
+pub fn log_applied(conn: &Connection, request: &RecordOutcomeRequest, now: DateTime<Utc>) -> Result<()> {
+    conn.execute(
+        "INSERT INTO outcome_log (source, decision_id, recorded_at) VALUES (?1, ?2, ?3)",
+        params![source(request), request.decision_id, now.to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+/// Hold `request` for review
+pub fn quarantine(conn: &Connection, batch_id: Option<&str>, request: &RecordOutcomeRequest, reason: &str) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO quarantined_outcomes (batch_id, source, decision_id, request_json, reason) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![batch_id, source(request), request.decision_id, serde_json::to_string(request)?, reason],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Id for a new quarantined batch
+pub fn new_batch_id() -> String {
+    format!("batch-{}", uuid::Uuid::new_v4().simple())
+}
+
+/// Held requests, oldest first
+pub fn list(conn: &Connection) -> Result<Vec<QuarantinedOutcome>> {
+    let rows: Vec<(i64, Option<String>, String, String, String, String)> = conn
+        .prepare(
+            "SELECT id, batch_id, source, request_json, reason, created_at FROM quarantined_outcomes ORDER BY id",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    rows.into_iter()
+        .map(|(id, batch_id, source, request, reason, created_at)| {
+            Ok(QuarantinedOutcome { id, batch_id, source, request: serde_json::from_str(&request)?, reason, created_at })
+        })
+        .collect()
+}
+
+/// Held requests a target names: a quarantine id, a batch id, or "all"
+pub fn select(conn: &Connection, target: &str) -> Result<Vec<QuarantinedOutcome>> {
+    let held = list(conn)?;
+    let selected: Vec<QuarantinedOutcome> = held
+        .into_iter()
+        .filter(|q| target == "all" || q.id.to_string() == target || q.batch_id.as_deref() == Some(target))
+        .collect();
+    if selected.is_empty() {
+        return Err(MindsError::NotFound(format!("Nothing quarantined matches '{}'", target)));
+    }
+    Ok(selected)
+}
+
+/// Release held requests (after they were applied, or to drop them)
+pub fn release(conn: &Connection, held: &[QuarantinedOutcome]) -> Result<()> {
+    for q in held {
+        conn.execute("DELETE FROM quarantined_outcomes WHERE id = ?1", [q.id])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(decision_id: &str, success: bool, actor: &str) -> RecordOutcomeRequest {
+        serde_json::from_value(serde_json::json!({"decision_id": decision_id, "success": success, "actor_id": actor})).unwrap()
+    }
+
+    #[test]
+    fn test_duplicates_and_rate_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let config = OutcomeGuardConfig { max_per_source_per_hour: 3, ..Default::default() };
+        let now = Utc::now();
+        conn.execute(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success)
+             VALUES ('d1', 'q', '{}', 'h', 's', 'k', 0)",
+            [],
+        )
+        .unwrap();
+
+        assert!(matches!(check(&conn, &config, &request("d1", false, "w"), now).unwrap(), Verdict::Duplicate(_)));
+        // Correcting a recorded outcome is not a duplicate
+        assert_eq!(check(&conn, &config, &request("d1", true, "w"), now).unwrap(), Verdict::Apply);
+
+        for i in 0..3 {
+            log_applied(&conn, &request(&format!("n{}", i), false, "w"), now - Duration::minutes(i)).unwrap();
+        }
+        log_applied(&conn, &request("old", false, "w"), now - Duration::hours(2)).unwrap();
+        match check(&conn, &config, &request("n9", false, "w"), now).unwrap() {
+            Verdict::Quarantine(reason) => assert!(reason.contains("w already recorded 3")),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(check(&conn, &config, &request("n9", false, "other"), now).unwrap(), Verdict::Apply);
+    }
+
+    #[test]
+    fn test_outlier_batches_are_held_together() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let config = OutcomeGuardConfig::default();
+        let mut batch: Vec<RecordOutcomeRequest> = (0..20).map(|i| request(&format!("b{}", i), false, "w")).collect();
+        assert!(outlier_batch(&config, &batch[..19]).is_none());
+        let reason = outlier_batch(&config, &batch).unwrap();
+        assert_eq!(reason, "outlier batch: 20 of 20 outcomes are failures");
+        batch[0].success = true;
+        batch[1].success = true;
+        batch[2].success = true;
+        assert!(outlier_batch(&config, &batch).is_none());
+
+        let batch_id = new_batch_id();
+        for r in &batch[..2] {
+            quarantine(&conn, Some(&batch_id), r, &reason).unwrap();
+        }
+        let lone = quarantine(&conn, None, &batch[5], "rate limit").unwrap();
+        assert_eq!(select(&conn, &batch_id).unwrap().len(), 2);
+        assert_eq!(select(&conn, &lone.to_string()).unwrap()[0].request.decision_id, "b5");
+        release(&conn, &select(&conn, "all").unwrap()).unwrap();
+        assert!(list(&conn).unwrap().is_empty());
+        assert!(select(&conn, "all").is_err());
+    }
+}
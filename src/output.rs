@@ -0,0 +1,175 @@
+//! CLI Output Modes
+//!
+//! Every command renders through [`OutputMode`]: the usual boxed report,
+//! pretty JSON for scripts (`--json`), or nothing at all (`--quiet`) when
+//! only the exit code matters.
+//!
+//! Independently, plain rendering (`--plain`, `NO_COLOR`, or a non-TTY
+//! stdout) swaps box drawing and emoji for ASCII and text labels. The
+//! crate's `say!`/`say_err!` console macros go through [`render`] for this.
+
+use crate::error::Result;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    #[default]
+    Human,
+    Json,
+    Quiet,
+}
+
+impl OutputMode {
+    /// `--json` wins over `--quiet` so scripts always get a parseable result
+    pub fn from_flags(json: bool, quiet: bool) -> Self {
+        match (json, quiet) {
+            (true, _) => OutputMode::Json,
+            (false, true) => OutputMode::Quiet,
+            (false, false) => OutputMode::Human,
+        }
+    }
+
+    pub fn is_human(self) -> bool {
+        self == OutputMode::Human
+    }
+
+    pub fn is_json(self) -> bool {
+        self == OutputMode::Json
+    }
+}
+
+/// Handle the machine-facing modes: print `value` as JSON, or nothing
+/// when quiet. Returns true when the caller should skip its human report.
+pub fn machine<T: Serialize>(mode: OutputMode, value: &T) -> Result<bool> {
+    match mode {
+        OutputMode::Human => Ok(false),
+        OutputMode::Json => {
+            // JSON is data, never re-rendered
+            std::println!("{}", serde_json::to_string_pretty(value)?);
+            Ok(true)
+        }
+        OutputMode::Quiet => Ok(true),
+    }
+}
+
+/// Plain rendering if asked for, if `NO_COLOR`/`TERM=dumb` is set, or if
+/// stdout is a pipe or file (logs, CI)
+pub fn detect_plain(flag: bool) -> bool {
+    flag
+        || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+        || std::env::var("TERM").is_ok_and(|t| t == "dumb")
+        || !std::io::stdout().is_terminal()
+}
+
+pub fn set_plain(on: bool) {
+    PLAIN.store(on, Ordering::Relaxed);
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// A line of console output in the active rendering
+pub fn render(s: &str) -> Cow<'_, str> {
+    if is_plain() && !s.is_ascii() {
+        Cow::Owned(to_ascii(s))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// ASCII stand-in for a glyph; `None` drops it
+fn ascii_glyph(c: char) -> Option<&'static str> {
+    Some(match c {
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╔' | '╗' | '╚' | '╝' => "+",
+        '─' | '━' | '–' | '—' => "-",
+        '═' => "=",
+        '│' | '┃' | '║' => "|",
+        '█' | '▇' | '▓' => "#",
+        '▁' | '▂' => "_",
+        '▃' | '▄' => "-",
+        '▅' | '▆' => "=",
+        '░' | '▒' => ".",
+        '•' | '·' => "*",
+        '→' | '▶' | '▼' => "->",
+        '←' => "<-",
+        '↔' => "<->",
+        '↑' => "^",
+        '↓' => "v",
+        '×' => "x",
+        '…' => "...",
+        '“' | '”' => "\"",
+        '‘' | '’' => "'",
+        '✅' | '✓' | '✔' => "[OK]",
+        '❌' | '✗' | '✘' => "[FAIL]",
+        '⚠' | '🟡' => "[WARN]",
+        '🔴' => "[ERROR]",
+        '🟠' => "[HIGH]",
+        '🔵' => "[INFO]",
+        '🚫' => "[BLOCKED]",
+        '💡' => "[TIP]",
+        '❓' => "[?]",
+        '📈' => "[UP]",
+        '📉' => "[DOWN]",
+        '⏸' => "[LATER]",
+        '➕' => "[+]",
+        _ => return None,
+    })
+}
+
+/// Replace everything outside ASCII: glyphs with their stand-ins, letters
+/// kept as-is (thinker names), remaining symbols and emoji dropped
+pub fn to_ascii(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut dropped = false;
+    for c in s.chars() {
+        // Emoji presentation selectors and joiners modify the previous glyph
+        if matches!(c, '\u{FE0E}' | '\u{FE0F}' | '\u{200D}') {
+            continue;
+        }
+        if c.is_ascii() {
+            // Don't leave a double space where a decorative emoji was
+            if !(dropped && c == ' ') {
+                out.push(c);
+            }
+            dropped = false;
+        } else if let Some(glyph) = ascii_glyph(c) {
+            out.push_str(glyph);
+            dropped = false;
+        } else if c.is_alphanumeric() {
+            out.push(c);
+            dropped = false;
+        } else {
+            dropped = true;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_from_flags() {
+        assert_eq!(OutputMode::from_flags(false, false), OutputMode::Human);
+        assert_eq!(OutputMode::from_flags(false, true), OutputMode::Quiet);
+        assert_eq!(OutputMode::from_flags(true, true), OutputMode::Json);
+
+        assert!(machine(OutputMode::Quiet, &1).unwrap());
+        assert!(!machine(OutputMode::Human, &1).unwrap());
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(to_ascii("│ 🧠 100MINDS STATS │"), "| 100MINDS STATS |");
+        assert_eq!(to_ascii("✅ SUCCESS → [██░░]"), "[OK] SUCCESS -> [##..]");
+        assert_eq!(to_ascii("▁▃▅█"), "_-=#");
+        assert_eq!(to_ascii("⚠️ WARNINGS: • Gödel"), "[WARN] WARNINGS: * Gödel");
+    }
+}
@@ -0,0 +1,707 @@
+//! Thinker Packs
+//!
+//! A pack is a shareable JSON bundle of thinkers and their principles
+//! ("SRE minds", "Security minds") that installs into the same tables as
+//! the core corpus, so its principles are searched, counseled and learned
+//! from like any other:
+//!
+//! ```json
+//! {
+//!   "pack": { "id": "sre-minds", "name": "SRE Minds", "version": "1.0.0" },
+//!   "thinkers": [{
+//!     "id": "charity-majors", "name": "Charity Majors", "domain": "systems",
+//!     "principles": [{
+//!       "id": "observability",
+//!       "name": "Observability Over Monitoring",
+//!       "description": "...",
+//!       "domain_tags": ["operations"],
+//!       "embedding": [0.01, ...]
+//!     }]
+//!   }],
+//!   "signature": { "pubkey": "<hex>", "signature": "<hex>" }
+//! }
+//! ```
+//!
+//! The optional Ed25519 signature covers the compact JSON of `pack` and
+//! `thinkers` ([`ThinkerPack::signed_bytes`]). Installing a pack whose
+//! signature doesn't verify fails; unsigned packs install unless the
+//! caller requires a signature. A signature only proves who signed, so
+//! `[packs] trusted_keys` in config.toml lists the signers to accept, and
+//! a signed pack can only be upgraded by the key it was installed with.
+//!
+//! Principle ids are `<thinker id>-<principle id>`, the principle id
+//! defaulting to a slug of its name. Reinstalling a pack updates its text
+//! but keeps learned confidence, even when principles are reordered, and a
+//! pack may not overwrite thinkers it doesn't own.
+
+use crate::embeddings::{init_embedding_schema, EMBEDDING_DIM};
+use crate::provenance::Provenance;
+use crate::error::{MindsError, Result};
+use anyhow::Context;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A thinker pack file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThinkerPack {
+    pub pack: PackMetadata,
+    pub thinkers: Vec<PackThinker>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<PackSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackMetadata {
+    /// Stable identifier, used by `pack remove`
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub license: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackThinker {
+    pub id: String,
+    pub name: String,
+    pub domain: String,
+    #[serde(default)]
+    pub background: String,
+    pub principles: Vec<PackPrinciple>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackPrinciple {
+    /// Stable id within the thinker, kept across renames; defaults to a slug of the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub domain_tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub application_rule: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anti_pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub falsification: Option<String>,
+    /// ACTION line shown under counsel positions citing this principle
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_prompt: Option<String>,
+    /// Precomputed embedding; used only if it matches [`EMBEDDING_DIM`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackSignature {
+    pub pubkey: String,
+    pub signature: String,
+}
+
+/// The `[packs]` table of config.toml
+///
+/// ```toml
+/// [packs]
+/// trusted_keys = ["<hex pubkey>"]   # when set, packs must be signed by one of these
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PackConfig {
+    pub trusted_keys: Vec<String>,
+}
+
+impl PackConfig {
+    /// Read `[packs]` from the config.toml beside the database; a missing file or table trusts any valid signature
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        crate::config::section(conn, "packs")
+    }
+}
+
+/// An installed pack (`pack list`)
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledPack {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    /// Public key the pack was signed with, if it was
+    pub signed_by: Option<String>,
+    pub thinkers: usize,
+    pub principles: usize,
+    pub installed_at: String,
+}
+
+/// What an install did
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallReport {
+    pub id: String,
+    pub version: String,
+    pub thinkers: usize,
+    pub principles: usize,
+    pub embeddings: usize,
+    pub signed_by: Option<String>,
+    /// True when an earlier version of the pack was replaced
+    pub upgraded: bool,
+}
+
+impl ThinkerPack {
+    /// Parse and sanity-check a pack
+    pub fn from_json(json: &str) -> Result<Self> {
+        let invalid = |problem: String| Err(MindsError::Validation(problem));
+        let pack: ThinkerPack =
+            serde_json::from_str(json).map_err(|e| MindsError::Validation(format!("Not a thinker pack: {}", e)))?;
+        if pack.pack.id.trim().is_empty() {
+            return invalid("Pack id is empty".to_string());
+        }
+        if pack.thinkers.is_empty() {
+            return invalid(format!("Pack '{}' has no thinkers", pack.pack.id));
+        }
+        for thinker in &pack.thinkers {
+            if thinker.id.trim().is_empty() || thinker.principles.is_empty() {
+                let problem = format!("Pack '{}': thinker '{}' needs an id and principles", pack.pack.id, thinker.name);
+                return invalid(problem);
+            }
+            let mut seen = std::collections::HashSet::new();
+            for principle in &thinker.principles {
+                let id = principle.local_id();
+                if id.is_empty() {
+                    return invalid(format!("Pack '{}': principle '{}' needs an id", pack.pack.id, principle.name));
+                }
+                if !seen.insert(id.clone()) {
+                    return invalid(format!(
+                        "Pack '{}': thinker '{}' has two principles with id '{}'",
+                        pack.pack.id, thinker.id, id
+                    ));
+                }
+            }
+        }
+        Ok(pack)
+    }
+
+    /// Read a pack from a file path or an http(s) URL
+    pub async fn fetch(source: &str) -> Result<Self> {
+        let json = if source.starts_with("http://") || source.starts_with("https://") {
+            let fetch = async { reqwest::get(source).await?.error_for_status()?.text().await };
+            fetch.await.with_context(|| format!("Failed to fetch {}", source))?
+        } else {
+            std::fs::read_to_string(source).with_context(|| format!("Failed to read {}", source))?
+        };
+        Self::from_json(&json)
+    }
+
+    /// The bytes a signature covers
+    pub fn signed_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&serde_json::json!({
+            "pack": self.pack,
+            "thinkers": self.thinkers,
+        }))?)
+    }
+
+    /// Sign with the local agent key, replacing any existing signature
+    pub fn sign(&mut self, provenance: &Provenance) -> Result<()> {
+        let signature = provenance.sign(&self.signed_bytes()?)?;
+        self.signature = Some(PackSignature { pubkey: provenance.public_key_hex(), signature });
+        Ok(())
+    }
+
+    /// The signer's public key if the pack is signed and the signature verifies
+    pub fn verify(&self) -> Result<Option<String>> {
+        let Some(sig) = &self.signature else {
+            return Ok(None);
+        };
+        if !Provenance::verify_detached(&self.signed_bytes()?, &sig.signature, &sig.pubkey)? {
+            return Err(MindsError::Provenance(format!("Pack '{}' signature does not verify", self.pack.id)));
+        }
+        Ok(Some(sig.pubkey.clone()))
+    }
+
+    fn principle_count(&self) -> usize {
+        self.thinkers.iter().map(|t| t.principles.len()).sum()
+    }
+}
+
+impl PackPrinciple {
+    /// The manifest id, or `Observability Over Monitoring` -> `observability-over-monitoring`
+    fn local_id(&self) -> String {
+        let source = self.id.as_deref().unwrap_or(&self.name);
+        source
+            .to_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
+
+/// The stored id of each of a thinker's principles. An installed principle
+/// with the same name keeps its id, so installs from before principle ids
+/// (positional `<thinker>-1`, `-2`, ...) keep what was learned about them.
+fn principle_ids(conn: &Connection, thinker: &PackThinker) -> Result<Vec<String>> {
+    thinker
+        .principles
+        .iter()
+        .map(|principle| {
+            let id = format!("{}-{}", thinker.id, principle.local_id());
+            let exists: bool = conn.query_row(
+                "SELECT COUNT(*) > 0 FROM principles WHERE id = ?1 AND thinker_id = ?2",
+                params![id, thinker.id],
+                |row| row.get(0),
+            )?;
+            if exists {
+                return Ok(id);
+            }
+            let by_name: Option<String> = conn
+                .query_row(
+                    "SELECT id FROM principles WHERE thinker_id = ?1 AND name = ?2",
+                    params![thinker.id, principle.name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            Ok(by_name.unwrap_or(id))
+        })
+        .collect()
+}
+
+/// Refuse signers the config doesn't trust, and upgrades signed by a different key
+fn check_signer(conn: &Connection, pack: &ThinkerPack, signed_by: Option<&str>) -> Result<()> {
+    let trusted = PackConfig::beside(conn)?.trusted_keys;
+    let refuse = |problem: String| Err(MindsError::Provenance(problem));
+    if !trusted.is_empty() {
+        match signed_by {
+            None => return refuse(format!("Pack '{}' is unsigned and config.toml lists trusted keys", pack.pack.id)),
+            Some(key) if !trusted.iter().any(|t| t == key) => {
+                return refuse(format!("Pack '{}' is signed by {}, which is not a trusted key", pack.pack.id, key))
+            }
+            Some(_) => {}
+        }
+    }
+    let installed: Option<Option<String>> = conn
+        .query_row("SELECT signed_by FROM thinker_packs WHERE id = ?1", [&pack.pack.id], |row| row.get(0))
+        .optional()?;
+    if let Some(Some(previous)) = installed {
+        if signed_by != Some(previous.as_str()) {
+            return refuse(format!(
+                "Pack '{}' was installed signed by {}; refusing an upgrade signed by {}",
+                pack.pack.id,
+                previous,
+                signed_by.unwrap_or("nobody")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Install (or upgrade) a pack
+pub fn install(conn: &Connection, pack: &ThinkerPack, require_signature: bool) -> Result<InstallReport> {
+    let signed_by = pack.verify()?;
+    if require_signature && signed_by.is_none() {
+        return Err(MindsError::Provenance(format!("Pack '{}' is unsigned", pack.pack.id)));
+    }
+    check_signer(conn, pack, signed_by.as_deref())?;
+    let has_embeddings = pack
+        .thinkers
+        .iter()
+        .flat_map(|t| &t.principles)
+        .any(|p| p.embedding.as_ref().is_some_and(|e| e.len() == EMBEDDING_DIM));
+    if has_embeddings {
+        init_embedding_schema(conn)?;
+    }
+    crate::eval::thompson::init_thompson_schema(conn)?;
+
+    crate::db::with_savepoint(conn, "install_pack", || {
+        for thinker in &pack.thinkers {
+            let owner: Option<String> = conn
+                .query_row("SELECT pack_id FROM pack_thinkers WHERE thinker_id = ?1", [&thinker.id], |row| row.get(0))
+                .optional()?;
+            let exists: bool = conn.query_row("SELECT COUNT(*) > 0 FROM thinkers WHERE id = ?1", [&thinker.id], |row| row.get(0))?;
+            match owner {
+                Some(owner) if owner != pack.pack.id => {
+                    let problem = format!("Thinker '{}' already belongs to pack '{}'", thinker.id, owner);
+                    return Err(MindsError::Validation(problem));
+                }
+                None if exists => {
+                    return Err(MindsError::Validation(format!("Thinker '{}' is already in the corpus", thinker.id)))
+                }
+                _ => {}
+            }
+        }
+
+        let ids: Vec<Vec<String>> = pack.thinkers.iter().map(|t| principle_ids(conn, t)).collect::<Result<_>>()?;
+        let upgraded = remove_stale(conn, pack, &ids)?;
+        let mut embeddings = 0;
+        for (thinker, ids) in pack.thinkers.iter().zip(&ids) {
+            conn.execute(
+                "INSERT INTO thinkers (id, name, domain, background, profile_json)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(id) DO UPDATE SET
+                     name = excluded.name, domain = excluded.domain,
+                     background = excluded.background, profile_json = excluded.profile_json",
+                params![thinker.id, thinker.name, thinker.domain, thinker.background, serde_json::to_string(thinker)?],
+            )?;
+            conn.execute(
+                "INSERT OR IGNORE INTO pack_thinkers (pack_id, thinker_id) VALUES (?1, ?2)",
+                params![pack.pack.id, thinker.id],
+            )?;
+
+            for (principle, principle_id) in thinker.principles.iter().zip(ids) {
+                conn.execute(
+                    "INSERT INTO principles (id, thinker_id, name, description, domain_tags,
+                                             application_rule, anti_pattern, falsification, action_prompt)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                     ON CONFLICT(id) DO UPDATE SET
+                         name = excluded.name, description = excluded.description,
+                         domain_tags = excluded.domain_tags, application_rule = excluded.application_rule,
+                         anti_pattern = excluded.anti_pattern, falsification = excluded.falsification,
+                         action_prompt = excluded.action_prompt",
+                    params![
+                        principle_id,
+                        thinker.id,
+                        principle.name,
+                        principle.description,
+                        serde_json::to_string(&principle.domain_tags)?,
+                        principle.application_rule,
+                        principle.anti_pattern,
+                        principle.falsification,
+                        principle.action_prompt,
+                    ],
+                )?;
+                if let Some(embedding) = principle.embedding.as_ref().filter(|e| e.len() == EMBEDDING_DIM) {
+                    let bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
+                    conn.execute("UPDATE principles SET embedding = ?2 WHERE id = ?1", params![principle_id, bytes])?;
+                    embeddings += 1;
+                }
+            }
+        }
+
+        conn.execute(
+            "INSERT INTO thinker_packs (id, name, version, author, signed_by, manifest_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                 name = excluded.name, version = excluded.version, author = excluded.author,
+                 signed_by = excluded.signed_by, manifest_json = excluded.manifest_json,
+                 installed_at = CURRENT_TIMESTAMP",
+            params![
+                pack.pack.id,
+                pack.pack.name,
+                pack.pack.version,
+                pack.pack.author,
+                signed_by,
+                serde_json::to_string(&pack.pack)?
+            ],
+        )?;
+
+        Ok(InstallReport {
+            id: pack.pack.id.clone(),
+            version: pack.pack.version.clone(),
+            thinkers: pack.thinkers.len(),
+            principles: pack.principle_count(),
+            embeddings,
+            signed_by: signed_by.clone(),
+            upgraded,
+        })
+    })
+}
+
+/// On upgrade, drop thinkers and principles the new version no longer has.
+/// `ids` are the principle ids of each of the pack's thinkers.
+/// Returns whether an earlier version was installed.
+fn remove_stale(conn: &Connection, pack: &ThinkerPack, ids: &[Vec<String>]) -> Result<bool> {
+    let installed: Vec<String> = conn
+        .prepare("SELECT thinker_id FROM pack_thinkers WHERE pack_id = ?1")?
+        .query_map([&pack.pack.id], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    for thinker_id in &installed {
+        match pack.thinkers.iter().position(|t| &t.id == thinker_id) {
+            Some(index) => {
+                let keep = &ids[index];
+                let existing: Vec<String> = conn
+                    .prepare("SELECT id FROM principles WHERE thinker_id = ?1")?
+                    .query_map([thinker_id], |row| row.get(0))?
+                    .collect::<Result<_, _>>()?;
+                for id in existing.iter().filter(|id| !keep.contains(id)) {
+                    delete_principle(conn, id)?;
+                }
+            }
+            None => delete_thinker(conn, thinker_id)?,
+        }
+    }
+    Ok(!installed.is_empty())
+}
+
+/// Installed packs, by id
+pub fn list(conn: &Connection) -> Result<Vec<InstalledPack>> {
+    let mut stmt = conn.prepare(
+        "SELECT p.id, p.name, p.version, p.author, p.signed_by, p.installed_at,
+                (SELECT COUNT(*) FROM pack_thinkers pt WHERE pt.pack_id = p.id),
+                (SELECT COUNT(*) FROM principles pr
+                 JOIN pack_thinkers pt ON pt.thinker_id = pr.thinker_id
+                 WHERE pt.pack_id = p.id)
+         FROM thinker_packs p ORDER BY p.id",
+    )?;
+    let packs = stmt
+        .query_map([], |row| {
+            Ok(InstalledPack {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                version: row.get(2)?,
+                author: row.get(3)?,
+                signed_by: row.get(4)?,
+                installed_at: row.get(5)?,
+                thinkers: row.get::<_, i64>(6)? as usize,
+                principles: row.get::<_, i64>(7)? as usize,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(packs)
+}
+
+/// Uninstall a pack: its thinkers, their principles and what was learned about them.
+/// Returns the number of thinkers removed.
+pub fn remove(conn: &Connection, pack_id: &str) -> Result<usize> {
+    crate::eval::thompson::init_thompson_schema(conn)?;
+    crate::db::with_savepoint(conn, "remove_pack", || {
+        let thinkers: Vec<String> = conn
+            .prepare("SELECT thinker_id FROM pack_thinkers WHERE pack_id = ?1")?
+            .query_map([pack_id], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        if conn.execute("DELETE FROM thinker_packs WHERE id = ?1", [pack_id])? == 0 {
+            return Err(MindsError::NotFound(format!("No pack '{}' is installed", pack_id)));
+        }
+        for thinker_id in &thinkers {
+            delete_thinker(conn, thinker_id)?;
+        }
+        Ok(thinkers.len())
+    })
+}
+
+fn delete_thinker(conn: &Connection, thinker_id: &str) -> Result<()> {
+    let principles: Vec<String> = conn
+        .prepare("SELECT id FROM principles WHERE thinker_id = ?1")?
+        .query_map([thinker_id], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    for id in &principles {
+        delete_principle(conn, id)?;
+    }
+    conn.execute("DELETE FROM thinker_reliability WHERE thinker_id = ?1", [thinker_id])?;
+    conn.execute("DELETE FROM pack_thinkers WHERE thinker_id = ?1", [thinker_id])?;
+    conn.execute("DELETE FROM thinkers WHERE id = ?1", [thinker_id])?;
+    Ok(())
+}
+
+/// Delete a principle and everything learned or curated about it. Its FTS
+/// row goes with it (the `principles_ad` trigger); templates cite principles
+/// by name, so they need nothing.
+fn delete_principle(conn: &Connection, principle_id: &str) -> Result<()> {
+    for table in [
+        "framework_adjustments",
+        "adjustment_rollups",
+        "thompson_arms",
+        "thompson_domain_arms",
+        "contextual_arms",
+        "actor_arms",
+        "hard_negatives",
+        "principle_policies",
+        "principle_decay",
+    ] {
+        conn.execute(&format!("DELETE FROM {} WHERE principle_id = ?1", table), [principle_id])?;
+    }
+    conn.execute("DELETE FROM principle_edges WHERE from_id = ?1 OR to_id = ?1", [principle_id])?;
+    conn.execute("DELETE FROM principles WHERE id = ?1", [principle_id])?;
+    Ok(())
+}
+
+/// Write a pack as pretty JSON
+pub fn save(pack: &ThinkerPack, path: &Path) -> Result<()> {
+    std::fs::write(path, serde_json::to_string_pretty(pack)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_pack(principles: usize) -> ThinkerPack {
+        ThinkerPack {
+            pack: PackMetadata {
+                id: "sre-minds".to_string(),
+                name: "SRE Minds".to_string(),
+                version: "1.0.0".to_string(),
+                description: String::new(),
+                author: "sre-guild".to_string(),
+                license: String::new(),
+            },
+            thinkers: vec![PackThinker {
+                id: "charity-majors".to_string(),
+                name: "Charity Majors".to_string(),
+                domain: "systems".to_string(),
+                background: String::new(),
+                principles: (1..=principles)
+                    .map(|i| PackPrinciple {
+                        id: None,
+                        name: format!("Observability {}", i),
+                        description: "Debug production with high-cardinality events".to_string(),
+                        domain_tags: vec!["operations".to_string()],
+                        application_rule: None,
+                        anti_pattern: None,
+                        falsification: None,
+                        action_prompt: None,
+                        embedding: None,
+                    })
+                    .collect(),
+            }],
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_install_upgrade_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+
+        let report = install(&conn, &sample_pack(3), false).unwrap();
+        assert_eq!((report.thinkers, report.principles, report.upgraded), (1, 3, false));
+        conn.execute("UPDATE principles SET learned_confidence = 0.9 WHERE id = 'charity-majors-observability-1'", [])
+            .unwrap();
+
+        // Upgrade keeps learning and drops principles the new version removed
+        let mut v2 = sample_pack(2);
+        v2.pack.version = "1.1.0".to_string();
+        assert!(install(&conn, &v2, false).unwrap().upgraded);
+        let learned: f64 = conn
+            .query_row("SELECT learned_confidence FROM principles WHERE id = 'charity-majors-observability-1'", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(learned, 0.9);
+        let packs = list(&conn).unwrap();
+        assert_eq!((packs[0].version.as_str(), packs[0].principles), ("1.1.0", 2));
+
+        assert_eq!(remove(&conn, "sre-minds").unwrap(), 1);
+        let left: i64 = conn.query_row("SELECT COUNT(*) FROM principles", [], |r| r.get(0)).unwrap();
+        assert_eq!(left, 0);
+        assert!(list(&conn).unwrap().is_empty());
+        assert!(remove(&conn, "sre-minds").is_err());
+    }
+
+    #[test]
+    fn test_reordered_upgrade_keeps_learning_and_cleans_up_dropped_principles() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        install(&conn, &sample_pack(3), false).unwrap();
+        let confidence = |id: &str| -> f64 {
+            conn.query_row("SELECT learned_confidence FROM principles WHERE id = ?1", [id], |r| r.get(0)).unwrap()
+        };
+        conn.execute("UPDATE principles SET learned_confidence = 0.9 WHERE id = 'charity-majors-observability-1'", [])
+            .unwrap();
+        conn.execute("UPDATE principles SET learned_confidence = 0.2 WHERE id = 'charity-majors-observability-3'", [])
+            .unwrap();
+        crate::db::insert_decision(&conn, "d1", "Page on symptoms?", None, "{}", None, "h", "s", "k").unwrap();
+        crate::db::apply_adjustment(&conn, "charity-majors-observability-2", Some("ops"), 0.1, "d1").unwrap();
+        crate::db::update_contextual_arm(&conn, "charity-majors-observability-2", "ops", true).unwrap();
+        conn.execute("INSERT INTO thompson_arms (principle_id) VALUES ('charity-majors-observability-2')", []).unwrap();
+
+        // v2 drops the second principle and swaps the other two
+        let mut v2 = sample_pack(3);
+        v2.pack.version = "2.0.0".to_string();
+        v2.thinkers[0].principles.remove(1);
+        v2.thinkers[0].principles.reverse();
+        install(&conn, &v2, false).unwrap();
+        assert_eq!(confidence("charity-majors-observability-1"), 0.9);
+        assert_eq!(confidence("charity-majors-observability-3"), 0.2);
+        for table in ["principles", "framework_adjustments", "contextual_arms", "thompson_arms"] {
+            let column = if table == "principles" { "id" } else { "principle_id" };
+            let left: i64 = conn
+                .query_row(
+                    &format!("SELECT COUNT(*) FROM {} WHERE {} = 'charity-majors-observability-2'", table, column),
+                    [],
+                    |r| r.get(0),
+                )
+                .unwrap();
+            assert_eq!(left, 0, "{} still has the dropped principle", table);
+        }
+        let fts: i64 = conn
+            .query_row("SELECT COUNT(*) FROM principles_fts WHERE principles_fts MATCH '\"Observability 2\"'", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(fts, 0);
+
+        // A manifest id survives a rename; positional ids from older installs are kept by name
+        let mut v3 = v2.clone();
+        v3.thinkers[0].principles[0].id = Some("observability-3".to_string());
+        v3.thinkers[0].principles[0].name = "Observability, Renamed".to_string();
+        install(&conn, &v3, false).unwrap();
+        assert_eq!(confidence("charity-majors-observability-3"), 0.2);
+        conn.execute("UPDATE principles SET id = 'charity-majors-1' WHERE id = 'charity-majors-observability-1'", [])
+            .unwrap();
+        install(&conn, &v3, false).unwrap();
+        assert_eq!(confidence("charity-majors-1"), 0.9);
+    }
+
+    #[test]
+    fn test_trusted_keys_and_upgrade_key_pinning() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let provenance = Provenance::init(&dir.path().join("agent.key")).unwrap();
+        let stranger = Provenance::init(&dir.path().join("stranger.key")).unwrap();
+
+        let mut pack = sample_pack(1);
+        pack.sign(&provenance).unwrap();
+        install(&conn, &pack, true).unwrap();
+
+        // A self-signed upgrade from another key verifies but may not replace the pack
+        let mut hijack = sample_pack(2);
+        hijack.sign(&stranger).unwrap();
+        assert_eq!(hijack.verify().unwrap(), Some(stranger.public_key_hex()));
+        let err = install(&conn, &hijack, true).unwrap_err().to_string();
+        assert!(err.contains("refusing an upgrade"), "{}", err);
+        assert!(install(&conn, &sample_pack(2), false).is_err());
+        install(&conn, &pack, true).unwrap();
+
+        // With trusted keys configured, only their signatures install
+        std::fs::write(
+            dir.path().join("config.toml"),
+            format!("[packs]\ntrusted_keys = [\"{}\"]\n", provenance.public_key_hex()),
+        )
+        .unwrap();
+        let mut other = sample_pack(1);
+        other.pack.id = "other".to_string();
+        other.thinkers[0].id = "liz-fong-jones".to_string();
+        assert!(install(&conn, &other, false).unwrap_err().to_string().contains("unsigned"));
+        other.sign(&stranger).unwrap();
+        assert!(install(&conn, &other, false).unwrap_err().to_string().contains("not a trusted key"));
+        other.sign(&provenance).unwrap();
+        install(&conn, &other, false).unwrap();
+    }
+
+    #[test]
+    fn test_signatures_and_ownership() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let provenance = Provenance::init(&dir.path().join("agent.key")).unwrap();
+
+        assert!(install(&conn, &sample_pack(1), true).is_err());
+
+        let mut pack = sample_pack(1);
+        pack.sign(&provenance).unwrap();
+        let json = serde_json::to_string(&pack).unwrap();
+        assert_eq!(ThinkerPack::from_json(&json).unwrap().verify().unwrap(), Some(provenance.public_key_hex()));
+
+        let mut tampered = pack.clone();
+        tampered.thinkers[0].principles[0].description = "Trust the dashboards".to_string();
+        assert!(install(&conn, &tampered, false).is_err());
+
+        assert_eq!(install(&conn, &pack, true).unwrap().signed_by, Some(provenance.public_key_hex()));
+
+        // Another pack can't take over the thinker
+        let mut other = sample_pack(1);
+        other.pack.id = "other".to_string();
+        assert!(install(&conn, &other, false).is_err());
+    }
+}
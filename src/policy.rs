@@ -0,0 +1,210 @@
+//! Organization Policy
+//!
+//! House rules on top of the learned rankings: a *pinned* principle is
+//! always considered (and boosted) in counsel, a *banned* one is never
+//! returned. Rules live in `principle_policies`, set with
+//! `100minds policy pin|ban|clear`, or declared in config.toml:
+//!
+//! ```toml
+//! [policy]
+//! pinned = ["Data Privacy"]                # principle names or IDs
+//! banned = ["Move Fast and Break Things"]
+//! ```
+//!
+//! Config rules are synced into the table (replacing earlier config rules)
+//! when a counsel-serving command starts; CLI rules are left alone.
+
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Policies in force, by principle ID
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+    pinned: HashMap<String, Option<String>>,
+    banned: HashMap<String, Option<String>>,
+}
+
+/// One row of `principle_policies`
+#[derive(Debug, Clone, Serialize)]
+pub struct PrinciplePolicy {
+    pub principle_id: String,
+    pub principle_name: String,
+    /// "pinned" or "banned"
+    pub rule: String,
+    pub reason: Option<String>,
+    /// "cli" or "config"
+    pub source: String,
+}
+
+/// How policy shaped one counsel response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolicyReport {
+    /// Pinned principles that were considered
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned: Vec<String>,
+    /// Banned principles that matched the question and were dropped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub banned: Vec<String>,
+}
+
+impl PolicyReport {
+    pub fn is_empty(&self) -> bool {
+        self.pinned.is_empty() && self.banned.is_empty()
+    }
+
+    pub(crate) fn note(list: &mut Vec<String>, id: &str) {
+        if !list.iter().any(|p| p == id) {
+            list.push(id.to_string());
+        }
+    }
+}
+
+impl Policy {
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let mut policy = Policy::default();
+        for rule in list(conn)? {
+            let target = if rule.rule == "pinned" { &mut policy.pinned } else { &mut policy.banned };
+            target.insert(rule.principle_id, rule.reason);
+        }
+        Ok(policy)
+    }
+
+    pub fn is_pinned(&self, principle_id: &str) -> bool {
+        self.pinned.contains_key(principle_id)
+    }
+
+    pub fn is_banned(&self, principle_id: &str) -> bool {
+        self.banned.contains_key(principle_id)
+    }
+
+    /// Pinned principle IDs, sorted
+    pub fn pinned_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.pinned.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pinned.is_empty() && self.banned.is_empty()
+    }
+}
+
+/// Resolve a principle by ID, or by name (case-insensitive)
+pub fn resolve_principle(conn: &Connection, principle: &str) -> Result<String> {
+    conn.query_row(
+        "SELECT id FROM principles WHERE id = ?1 OR name = ?1 COLLATE NOCASE ORDER BY id = ?1 DESC LIMIT 1",
+        [principle],
+        |row| row.get(0),
+    )
+    .optional()?
+    .ok_or_else(|| MindsError::NotFound(format!("principle {}", principle)))
+}
+
+/// Pin or ban a principle (replacing any earlier rule for it)
+pub fn set(conn: &Connection, principle: &str, rule: &str, reason: Option<&str>, source: &str) -> Result<String> {
+    if rule != "pinned" && rule != "banned" {
+        return Err(MindsError::Validation(format!("Unknown policy rule '{}' (pinned or banned)", rule)));
+    }
+    let id = resolve_principle(conn, principle)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO principle_policies (principle_id, rule, reason, source) VALUES (?1, ?2, ?3, ?4)",
+        params![id, rule, reason, source],
+    )?;
+    Ok(id)
+}
+
+/// Drop the rule for a principle; returns whether there was one
+pub fn clear(conn: &Connection, principle: &str) -> Result<bool> {
+    let id = resolve_principle(conn, principle)?;
+    Ok(conn.execute("DELETE FROM principle_policies WHERE principle_id = ?1", [id])? > 0)
+}
+
+/// Every rule, pinned first
+pub fn list(conn: &Connection) -> Result<Vec<PrinciplePolicy>> {
+    let mut stmt = conn.prepare(
+        "SELECT pp.principle_id, COALESCE(p.name, pp.principle_id), pp.rule, pp.reason, pp.source
+         FROM principle_policies pp LEFT JOIN principles p ON p.id = pp.principle_id
+         ORDER BY pp.rule DESC, pp.principle_id",
+    )?;
+    let rules = stmt
+        .query_map([], |row| {
+            Ok(PrinciplePolicy {
+                principle_id: row.get(0)?,
+                principle_name: row.get(1)?,
+                rule: row.get(2)?,
+                reason: row.get(3)?,
+                source: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rules)
+}
+
+/// The `[policy]` table of config.toml
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    pub pinned: Vec<String>,
+    pub banned: Vec<String>,
+}
+
+impl PolicyConfig {
+    /// Read `[policy]` from config.toml; a missing file or table means no rules
+    pub fn load(path: &Path) -> Result<Self> {
+        crate::config::section_at(path, "policy")
+    }
+
+    /// Replace config-sourced rules with these; unknown principles are skipped with a warning
+    pub fn sync(&self, conn: &Connection) -> Result<()> {
+        crate::db::with_savepoint(conn, "sync_policy", || {
+            conn.execute("DELETE FROM principle_policies WHERE source = 'config'", [])?;
+            for (rule, principles) in [("pinned", &self.pinned), ("banned", &self.banned)] {
+                for principle in principles {
+                    if let Err(e) = set(conn, principle, rule, Some("config.toml"), "config") {
+                        tracing::warn!("Policy: {}", e);
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_rules_sync_without_touching_cli_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('privacy', 't', 'Data Privacy', 'Protect user data'),
+                        ('move-fast', 't', 'Move Fast and Break Things', 'Ship it'),
+                        ('yagni', 't', 'YAGNI', 'Build only what you need');",
+        )
+        .unwrap();
+        set(&conn, "yagni", "pinned", Some("team rule"), "cli").unwrap();
+
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[policy]\npinned = [\"data privacy\"]\nbanned = [\"Move Fast and Break Things\", \"Nope\"]\n").unwrap();
+        PolicyConfig::load(&path).unwrap().sync(&conn).unwrap();
+        let policy = Policy::load(&conn).unwrap();
+        assert!(policy.is_pinned("privacy") && policy.is_pinned("yagni"));
+        assert!(policy.is_banned("move-fast"));
+
+        std::fs::write(&path, "[policy]\nbanned = []\n").unwrap();
+        PolicyConfig::load(&path).unwrap().sync(&conn).unwrap();
+        let policy = Policy::load(&conn).unwrap();
+        assert_eq!(policy.pinned_ids(), vec!["yagni"]);
+        assert!(!policy.is_banned("move-fast"));
+
+        assert!(clear(&conn, "YAGNI").unwrap());
+        assert!(Policy::load(&conn).unwrap().is_empty());
+    }
+}
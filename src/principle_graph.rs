@@ -0,0 +1,300 @@
+//! Principle Graph
+//!
+//! Curated relations between principles, kept in `principle_edges`:
+//!
+//! - `A requires B`: A presumes B has been done ("Extract Hotspots" requires
+//!   "Profile First")
+//! - `A conflicts B`: following one works against the other (symmetric)
+//! - `A refines B`: A is a narrower, more specific version of B
+//!
+//! Set with `100minds edges add <A> requires|conflicts|refines <B>`. Counsel
+//! orders positions so a prerequisite comes before the positions that need
+//! it, and notes each relation among the cited principles: "do B before A",
+//! a prerequisite none of the positions covers, a conflict to pick a side
+//! in, a refinement to prefer.
+
+use crate::policy::resolve_principle;
+use crate::types::{CounselPosition, Stance};
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub const RELATIONS: [&str; 3] = ["requires", "conflicts", "refines"];
+
+/// One row of `principle_edges`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PrincipleEdge {
+    pub from_id: String,
+    pub from_name: String,
+    /// "requires", "conflicts" or "refines"
+    pub relation: String,
+    pub to_id: String,
+    pub to_name: String,
+    pub note: Option<String>,
+}
+
+/// A relation among the principles of one counsel response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelationNote {
+    pub relation: String,
+    pub principle: String,
+    pub other: String,
+    /// Whether a position cites `other` too
+    pub other_cited: bool,
+    /// What to do about it, in a sentence
+    pub advice: String,
+    /// Curator's note on the edge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Add a relation (replacing the note of an existing one); conflicts are
+/// stored once per pair, and a `requires` that would close a cycle is refused
+pub fn add(conn: &Connection, from: &str, relation: &str, to: &str, note: Option<&str>) -> Result<PrincipleEdge> {
+    if !RELATIONS.contains(&relation) {
+        return Err(MindsError::Validation(format!("Unknown relation '{}' ({})", relation, RELATIONS.join(", "))));
+    }
+    let (mut from_id, mut to_id) = (resolve_principle(conn, from)?, resolve_principle(conn, to)?);
+    if from_id == to_id {
+        return Err(MindsError::Validation(format!("A principle can't {} itself", relation.trim_end_matches('s'))));
+    }
+    if relation == "conflicts" && from_id > to_id {
+        std::mem::swap(&mut from_id, &mut to_id);
+    }
+    if relation == "requires" && requires_path(conn, &to_id, &from_id)? {
+        return Err(MindsError::Validation(format!(
+            "{} already requires {} (directly or through others)",
+            to_id, from_id
+        )));
+    }
+    conn.execute(
+        "INSERT INTO principle_edges (from_id, relation, to_id, note) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(from_id, relation, to_id) DO UPDATE SET note = excluded.note",
+        params![from_id, relation, to_id, note],
+    )?;
+    Ok(list(conn)?
+        .into_iter()
+        .find(|e| e.from_id == from_id && e.relation == relation && e.to_id == to_id)
+        .expect("edge was just written"))
+}
+
+/// Drop a relation; returns whether there was one
+pub fn remove(conn: &Connection, from: &str, relation: &str, to: &str) -> Result<bool> {
+    let (from_id, to_id) = (resolve_principle(conn, from)?, resolve_principle(conn, to)?);
+    Ok(conn.execute(
+        "DELETE FROM principle_edges WHERE relation = ?2
+           AND ((from_id = ?1 AND to_id = ?3) OR (relation = 'conflicts' AND from_id = ?3 AND to_id = ?1))",
+        params![from_id, relation, to_id],
+    )? > 0)
+}
+
+/// Every relation between principles still in the corpus
+pub fn list(conn: &Connection) -> Result<Vec<PrincipleEdge>> {
+    let edges = conn
+        .prepare(
+            "SELECT e.from_id, a.name, e.relation, e.to_id, b.name, e.note FROM principle_edges e
+             JOIN principles a ON a.id = e.from_id
+             JOIN principles b ON b.id = e.to_id
+             ORDER BY e.relation, a.name, b.name",
+        )?
+        .query_map([], |row| {
+            Ok(PrincipleEdge {
+                from_id: row.get(0)?,
+                from_name: row.get(1)?,
+                relation: row.get(2)?,
+                to_id: row.get(3)?,
+                to_name: row.get(4)?,
+                note: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(edges)
+}
+
+/// Whether `from` requires `to`, directly or through other principles
+fn requires_path(conn: &Connection, from: &str, to: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "WITH RECURSIVE reachable(id) AS (
+             SELECT ?1
+             UNION SELECT e.to_id FROM principle_edges e JOIN reachable r ON e.from_id = r.id
+             WHERE e.relation = 'requires'
+         )
+         SELECT EXISTS (SELECT 1 FROM reachable WHERE id = ?2)",
+        params![from, to],
+        |row| row.get(0),
+    )?)
+}
+
+/// The curated relations, loaded once per counsel engine
+#[derive(Debug, Clone, Default)]
+pub struct PrincipleGraph {
+    edges: Vec<PrincipleEdge>,
+}
+
+impl PrincipleGraph {
+    pub fn load(conn: &Connection) -> Result<Self> {
+        Ok(PrincipleGraph { edges: list(conn)? })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    fn requires(&self, from: &str, to: &str) -> bool {
+        self.edges.iter().any(|e| e.relation == "requires" && e.from_id == from && e.to_id == to)
+    }
+
+    /// Move positions citing a prerequisite ahead of the positions that need
+    /// it, otherwise keeping their order. Only FOR and AGAINST positions
+    /// move; on a cycle the earliest remaining position goes next.
+    pub fn order(&self, positions: &mut Vec<CounselPosition>) {
+        if !self.edges.iter().any(|e| e.relation == "requires") {
+            return;
+        }
+        let movable = |p: &CounselPosition| matches!(p.stance, Stance::For | Stance::Against);
+        // after[i][j]: position i has to follow position j
+        let after: Vec<Vec<bool>> = positions
+            .iter()
+            .map(|a| {
+                positions
+                    .iter()
+                    .map(|b| {
+                        movable(a)
+                            && movable(b)
+                            && !std::ptr::eq(a, b)
+                            && a.principles_cited.iter().any(|needs| {
+                                b.principles_cited.iter().any(|pre| {
+                                    !a.principles_cited.contains(pre) && self.requires(needs, pre)
+                                })
+                            })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut placed = vec![false; positions.len()];
+        let mut order = Vec::with_capacity(positions.len());
+        while order.len() < positions.len() {
+            let ready = |i: &usize| !placed[*i] && (0..positions.len()).all(|j| placed[j] || !after[*i][j]);
+            let next = (0..positions.len())
+                .find(ready)
+                .or_else(|| (0..positions.len()).find(|&i| !placed[i]))
+                .expect("an unplaced position remains");
+            placed[next] = true;
+            order.push(next);
+        }
+        let mut taken: Vec<Option<CounselPosition>> = positions.drain(..).map(Some).collect();
+        positions.extend(order.into_iter().filter_map(|i| taken[i].take()));
+    }
+
+    /// Relations that touch the cited principles, prerequisites first
+    pub fn notes(&self, positions: &[CounselPosition]) -> Vec<RelationNote> {
+        let cited: HashSet<&str> =
+            positions.iter().flat_map(|p| p.principles_cited.iter().map(String::as_str)).collect();
+        let mut notes: Vec<RelationNote> = Vec::new();
+        for relation in RELATIONS {
+            for edge in self.edges.iter().filter(|e| e.relation == relation && cited.contains(e.from_id.as_str())) {
+                let other_cited = cited.contains(edge.to_id.as_str());
+                let (a, b) = (&edge.from_name, &edge.to_name);
+                let advice = match (relation, other_cited) {
+                    ("requires", true) => format!("Do {} before {}", b, a),
+                    ("requires", false) => format!("{} presumes {}, which no position covers: do that first", a, b),
+                    ("conflicts", true) => format!("{} and {} work against each other: pick one", a, b),
+                    ("refines", true) => format!("{} is a narrower {}: where both apply, follow {}", a, b, a),
+                    _ => continue,
+                };
+                notes.push(RelationNote {
+                    relation: relation.to_string(),
+                    principle: a.clone(),
+                    other: b.clone(),
+                    other_cited,
+                    advice,
+                    note: edge.note.clone(),
+                });
+            }
+        }
+        notes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(stance: Stance, principle: &str) -> CounselPosition {
+        CounselPosition {
+            thinker: format!("Thinker {}", principle),
+            thinker_id: "t".to_string(),
+            stance,
+            argument: String::new(),
+            principles_cited: vec![principle.to_string()],
+            confidence: 0.7,
+            raw_confidence: None,
+            falsifiable_if: None,
+        }
+    }
+
+    fn setup() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('profile', 't', 'Profile First', 'd'), ('hotspots', 't', 'Extract Hotspots', 'd'),
+                        ('yagni', 't', 'YAGNI', 'd'), ('plan', 't', 'Plan for Scale', 'd'),
+                        ('budget', 't', 'Performance Budget', 'd');",
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn test_curation_refuses_cycles_and_self_edges() {
+        let (_dir, conn) = setup();
+        add(&conn, "Extract Hotspots", "requires", "profile first", None).unwrap();
+        add(&conn, "budget", "requires", "hotspots", Some("budgets need a baseline")).unwrap();
+        assert!(add(&conn, "profile", "requires", "budget", None).unwrap_err().to_string().contains("already requires"));
+        assert!(add(&conn, "yagni", "conflicts", "yagni", None).is_err());
+        assert!(add(&conn, "yagni", "blocks", "plan", None).is_err());
+
+        let edge = add(&conn, "YAGNI", "conflicts", "Plan for Scale", None).unwrap();
+        assert_eq!((edge.from_id.as_str(), edge.to_id.as_str()), ("plan", "yagni"));
+        assert_eq!(list(&conn).unwrap().len(), 3);
+        assert!(remove(&conn, "yagni", "conflicts", "plan").unwrap());
+        assert!(!remove(&conn, "yagni", "conflicts", "plan").unwrap());
+    }
+
+    #[test]
+    fn test_graph_orders_prerequisites_first_and_notes_relations() {
+        let (_dir, conn) = setup();
+        add(&conn, "hotspots", "requires", "profile", None).unwrap();
+        add(&conn, "budget", "requires", "profile", None).unwrap();
+        add(&conn, "yagni", "conflicts", "plan", Some("team debate")).unwrap();
+        add(&conn, "budget", "refines", "plan", None).unwrap();
+        let graph = PrincipleGraph::load(&conn).unwrap();
+
+        let mut positions = vec![
+            position(Stance::For, "hotspots"),
+            position(Stance::Against, "yagni"),
+            position(Stance::For, "profile"),
+            position(Stance::For, "plan"),
+            position(Stance::Synthesize, "hotspots"),
+        ];
+        graph.order(&mut positions);
+        let order: Vec<&str> = positions.iter().map(|p| p.principles_cited[0].as_str()).collect();
+        assert_eq!(order, ["yagni", "profile", "hotspots", "plan", "hotspots"]);
+
+        let notes = graph.notes(&positions);
+        let advice: Vec<&str> = notes.iter().map(|n| n.advice.as_str()).collect();
+        assert_eq!(
+            advice,
+            ["Do Profile First before Extract Hotspots", "Plan for Scale and YAGNI work against each other: pick one"]
+        );
+        assert_eq!(notes[1].note.as_deref(), Some("team debate"));
+
+        let notes = graph.notes(&[position(Stance::For, "budget")]);
+        assert_eq!(notes.len(), 1);
+        assert!(!notes[0].other_cited && notes[0].advice.starts_with("Performance Budget presumes Profile First"));
+    }
+}
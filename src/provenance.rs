@@ -10,7 +10,7 @@
 //! - Decision auditing
 //! - Accountability trails
 
-use anyhow::{Context, Result};
+use crate::error::{MindsError, Result};
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use rand::rngs::OsRng;
 use sha2::{Digest, Sha256};
@@ -36,6 +36,11 @@ impl Provenance {
         Ok(Self { signing_key })
     }
 
+    /// A fresh key that is never written to disk (tests and fixtures)
+    pub fn ephemeral() -> Self {
+        Self { signing_key: Self::generate_key() }
+    }
+
     /// Generate a new Ed25519 signing key
     fn generate_key() -> SigningKey {
         SigningKey::generate(&mut OsRng)
@@ -43,11 +48,12 @@ impl Provenance {
 
     /// Load key from file
     fn load_key(path: &Path) -> Result<SigningKey> {
-        let bytes =
-            fs::read(path).with_context(|| format!("Failed to read key from {:?}", path))?;
+        let bytes = fs::read(path)
+            .map_err(|e| MindsError::Provenance(format!("Failed to read key from {:?}: {}", path, e)))?;
+        let bytes = crate::encryption::unseal_key(&bytes)?;
 
         if bytes.len() != 32 {
-            anyhow::bail!("Invalid key length: expected 32 bytes, got {}", bytes.len());
+            return Err(MindsError::Provenance(format!("Invalid key length: expected 32 bytes, got {}", bytes.len())));
         }
 
         let mut key_bytes = [0u8; 32];
@@ -64,7 +70,7 @@ impl Provenance {
         }
 
         fs::write(path, key.to_bytes())
-            .with_context(|| format!("Failed to write key to {:?}", path))?;
+            .map_err(|e| MindsError::Provenance(format!("Failed to write key to {:?}: {}", path, e)))?;
 
         // Set restrictive permissions on Unix
         #[cfg(unix)]
@@ -98,16 +104,22 @@ impl Provenance {
 
     /// Verify a signature
     pub fn verify(&self, content: &[u8], signature_hex: &str, pubkey_hex: &str) -> Result<bool> {
-        let sig_bytes = hex::decode(signature_hex).context("Invalid signature hex")?;
+        Self::verify_detached(content, signature_hex, pubkey_hex)
+    }
+
+    /// Verify a signature without a local key (e.g. a signed thinker pack)
+    pub fn verify_detached(content: &[u8], signature_hex: &str, pubkey_hex: &str) -> Result<bool> {
+        let invalid = |what: &str| MindsError::Provenance(format!("Invalid {}", what));
+        let sig_bytes = hex::decode(signature_hex).map_err(|_| invalid("signature hex"))?;
 
-        let pubkey_bytes = hex::decode(pubkey_hex).context("Invalid public key hex")?;
+        let pubkey_bytes = hex::decode(pubkey_hex).map_err(|_| invalid("public key hex"))?;
 
         if sig_bytes.len() != 64 {
-            anyhow::bail!("Invalid signature length");
+            return Err(invalid("signature length"));
         }
 
         if pubkey_bytes.len() != 32 {
-            anyhow::bail!("Invalid public key length");
+            return Err(invalid("public key length"));
         }
 
         let mut sig_arr = [0u8; 64];
@@ -116,7 +128,7 @@ impl Provenance {
 
         let mut pubkey_arr = [0u8; 32];
         pubkey_arr.copy_from_slice(&pubkey_bytes);
-        let verifying_key = VerifyingKey::from_bytes(&pubkey_arr).context("Invalid public key")?;
+        let verifying_key = VerifyingKey::from_bytes(&pubkey_arr).map_err(|_| invalid("public key"))?;
 
         Ok(verifying_key.verify(content, &signature).is_ok())
     }
@@ -0,0 +1,457 @@
+//! PII Redaction
+//!
+//! Questions, decision context and outcome notes often name customers or
+//! paste an email thread. With `[privacy] redact = true` in config.toml they
+//! are scrubbed before they are stored: built-in rules (emails, phone and
+//! card numbers, IP addresses, API tokens), any extra `patterns`, and, with
+//! the `ner` feature and a token-classification model in `ner_model_dir`,
+//! names of people, organisations and places. Each match becomes a
+//! placeholder such as `[EMAIL]` or `[PER]`.
+//!
+//! Only a SHA-256 of each original is kept (in `redactions`), so whoever
+//! still has the original can show it matches. Exports and syncs read the
+//! stored rows and so carry the redacted text; `100minds scrub` applies the
+//! current rules to decisions stored before redaction was turned on.
+
+use crate::error::{MindsError, Result};
+use chrono::Utc;
+use regex::Regex;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Built-in rules: name, placeholder, pattern. Order matters: tokens and
+/// emails go before the digit rules that would match inside them.
+const BUILTIN: [(&str, &str, &str); 5] = [
+    ("secret", "[SECRET]", r"\b(?:sk-|pk-|ghp_|gho_|glpat-|xox[abp]-|AKIA)[A-Za-z0-9_-]{12,}"),
+    ("email", "[EMAIL]", r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}"),
+    ("card", "[CARD]", r"\b\d{4}[ -]?\d{4}[ -]?\d{4}[ -]?\d{1,4}\b"),
+    ("phone", "[PHONE]", r"(?:\+\d{1,3}[ .-]?)?\(?\b\d{3}\)?[ .-]\d{3}[ .-]\d{4}\b"),
+    ("ip", "[IP]", r"\b(?:\d{1,3}\.){3}\d{1,3}\b"),
+];
+
+/// The `[privacy]` table of config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacyConfig {
+    /// Scrub questions, context and notes before storing them
+    pub redact: bool,
+    /// Built-in rules to apply: email, phone, card, ip, secret
+    pub builtin: Vec<String>,
+    /// Extra regexes; matches become `[REDACTED]`
+    pub patterns: Vec<String>,
+    /// Token-classification export (model.onnx, tokenizer.json and a
+    /// config.json with `id2label`); needs the `ner` feature
+    pub ner_model_dir: Option<PathBuf>,
+    /// Entity labels the model's matches are redacted for
+    pub ner_labels: Vec<String>,
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        PrivacyConfig {
+            redact: false,
+            builtin: BUILTIN.iter().map(|(name, _, _)| name.to_string()).collect(),
+            patterns: Vec::new(),
+            ner_model_dir: None,
+            ner_labels: vec!["PER".to_string(), "ORG".to_string(), "LOC".to_string()],
+        }
+    }
+}
+
+impl PrivacyConfig {
+    /// Read `[privacy]` from the config.toml beside the database; a missing file or table gives the defaults
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        crate::config::section(conn, "privacy")
+    }
+}
+
+/// A named entity: byte range in the text and its label ("PER", "ORG", ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub start: usize,
+    pub end: usize,
+    pub label: String,
+}
+
+/// Finds named entities in text
+pub trait EntityRecognizer: Send + Sync {
+    fn entities(&self, text: &str) -> Result<Vec<Entity>>;
+}
+
+/// Redacted text and what was taken out of it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Redacted {
+    pub text: String,
+    /// Placeholders used, e.g. `[EMAIL]`
+    pub kinds: BTreeSet<String>,
+    /// Entity text the recognizer found, with its placeholder, so the same
+    /// name can be taken out of other copies of the text
+    pub entities: Vec<(String, String)>,
+}
+
+impl Redacted {
+    pub fn changed(&self) -> bool {
+        !self.kinds.is_empty()
+    }
+}
+
+pub struct Redactor {
+    rules: Vec<(String, Regex)>,
+    recognizer: Option<Arc<dyn EntityRecognizer>>,
+    ner_labels: Vec<String>,
+}
+
+impl Redactor {
+    pub fn new(config: &PrivacyConfig) -> Result<Self> {
+        let mut rules = Vec::new();
+        for name in &config.builtin {
+            let Some((_, placeholder, pattern)) = BUILTIN.iter().find(|(n, _, _)| n == name) else {
+                return Err(MindsError::Config(format!(
+                    "Unknown built-in redaction rule {:?} (expected email, phone, card, ip or secret)",
+                    name
+                )));
+            };
+            rules.push((placeholder.to_string(), Regex::new(pattern).map_err(anyhow::Error::from)?));
+        }
+        for pattern in &config.patterns {
+            let regex = Regex::new(pattern)
+                .map_err(|e| MindsError::Config(format!("Bad [privacy] pattern {:?}: {}", pattern, e)))?;
+            rules.push(("[REDACTED]".to_string(), regex));
+        }
+        // Built-ins go in table order whatever order the config lists them in
+        rules.sort_by_key(|(placeholder, _)| BUILTIN.iter().position(|(_, p, _)| p == placeholder).unwrap_or(BUILTIN.len()));
+        Ok(Redactor {
+            rules,
+            recognizer: config.ner_model_dir.as_deref().and_then(shared_recognizer),
+            ner_labels: config.ner_labels.clone(),
+        })
+    }
+
+    /// The redactor for the connection's config.toml, if redaction is on
+    pub fn beside(conn: &Connection) -> Result<Option<Self>> {
+        let config = PrivacyConfig::beside(conn)?;
+        if !config.redact {
+            return Ok(None);
+        }
+        Ok(Some(Self::new(&config)?))
+    }
+
+    /// A redactor with one rule: `subject` becomes `placeholder`
+    pub fn for_subject(subject: Regex, placeholder: &str) -> Self {
+        Redactor { rules: vec![(placeholder.to_string(), subject)], recognizer: None, ner_labels: Vec::new() }
+    }
+
+    pub fn with_recognizer(mut self, recognizer: Arc<dyn EntityRecognizer>) -> Self {
+        self.recognizer = Some(recognizer);
+        self
+    }
+
+    /// Redact free text: named entities first, then the rules
+    pub fn redact(&self, text: &str) -> Redacted {
+        let mut redacted = Redacted { text: text.to_string(), ..Default::default() };
+        if let Some(recognizer) = &self.recognizer {
+            match recognizer.entities(text) {
+                Ok(mut entities) => {
+                    entities.retain(|e| self.ner_labels.contains(&e.label) && text.get(e.start..e.end).is_some());
+                    entities.sort_by_key(|e| std::cmp::Reverse(e.start));
+                    for entity in entities {
+                        let placeholder = format!("[{}]", entity.label);
+                        redacted.entities.push((text[entity.start..entity.end].to_string(), placeholder.clone()));
+                        redacted.text.replace_range(entity.start..entity.end, &placeholder);
+                        redacted.kinds.insert(placeholder);
+                    }
+                }
+                Err(e) => tracing::warn!("Entity recognition failed, redacting by pattern only: {}", e),
+            }
+        }
+        self.apply_rules(&mut redacted);
+        redacted
+    }
+
+    fn apply_rules(&self, redacted: &mut Redacted) {
+        for (placeholder, regex) in &self.rules {
+            if regex.is_match(&redacted.text) {
+                redacted.text = regex.replace_all(&redacted.text, placeholder.as_str()).into_owned();
+                redacted.kinds.insert(placeholder.clone());
+            }
+        }
+    }
+
+    /// Redact every string in a JSON document. Entity recognition only runs
+    /// when `names` is set (a stored counsel response is full of thinkers'
+    /// names); `known` entities are taken out either way.
+    pub fn redact_json(&self, value: &mut Value, names: bool, known: &[(String, String)]) -> Redacted {
+        let mut all = Redacted::default();
+        self.walk(value, names, known, &mut all);
+        all
+    }
+
+    fn walk(&self, value: &mut Value, names: bool, known: &[(String, String)], all: &mut Redacted) {
+        match value {
+            Value::String(s) => {
+                let mut redacted = if names {
+                    self.redact(s)
+                } else {
+                    let mut redacted = Redacted { text: s.clone(), ..Default::default() };
+                    self.apply_rules(&mut redacted);
+                    redacted
+                };
+                for (entity, placeholder) in known {
+                    if redacted.text.contains(entity.as_str()) {
+                        redacted.text = redacted.text.replace(entity.as_str(), placeholder);
+                        redacted.kinds.insert(placeholder.clone());
+                    }
+                }
+                *s = redacted.text;
+                all.kinds.extend(redacted.kinds);
+                all.entities.extend(redacted.entities);
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.walk(item, names, known, all)),
+            Value::Object(fields) => fields.values_mut().for_each(|field| self.walk(field, names, known, all)),
+            _ => {}
+        }
+    }
+}
+
+/// Entity recognizers are expensive to load, so one per model dir is shared
+fn shared_recognizer(model_dir: &Path) -> Option<Arc<dyn EntityRecognizer>> {
+    type Cache = Mutex<HashMap<PathBuf, Option<Arc<dyn EntityRecognizer>>>>;
+    static RECOGNIZERS: OnceLock<Cache> = OnceLock::new();
+    let mut cache = RECOGNIZERS.get_or_init(Cache::default).lock().unwrap_or_else(|e| e.into_inner());
+    cache.entry(model_dir.to_path_buf()).or_insert_with(|| load_recognizer(model_dir)).clone()
+}
+
+#[cfg(feature = "ner")]
+fn load_recognizer(model_dir: &Path) -> Option<Arc<dyn EntityRecognizer>> {
+    match crate::embeddings::NerModel::new(model_dir) {
+        Ok(model) => Some(Arc::new(model)),
+        Err(e) => {
+            tracing::warn!("NER model unavailable, redacting by pattern only: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "ner"))]
+fn load_recognizer(_model_dir: &Path) -> Option<Arc<dyn EntityRecognizer>> {
+    tracing::warn!("ner_model_dir needs a build with the `ner` feature; redacting by pattern only");
+    None
+}
+
+fn sha256(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Keep the hash of a field's original text when redaction changed it
+pub fn record(conn: &Connection, decision_id: &str, field: &str, original: &str, redacted: &Redacted) -> Result<()> {
+    if !redacted.changed() {
+        return Ok(());
+    }
+    let kinds: Vec<&str> = redacted.kinds.iter().map(String::as_str).collect();
+    conn.execute(
+        "INSERT OR REPLACE INTO redactions (decision_id, field, original_sha256, kinds, redacted_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![decision_id, field, sha256(original), kinds.join(","), Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+/// One stored field before and after redaction
+pub(crate) struct Field<'a> {
+    pub name: &'static str,
+    pub original: &'a str,
+    /// `redacted.text` is what gets stored (re-serialized for JSON fields)
+    pub redacted: Redacted,
+}
+
+pub(crate) fn scrub_fields<'a>(
+    redactor: &Redactor,
+    question: &'a str,
+    context_json: &'a str,
+    counsel_json: &'a str,
+) -> Result<[Field<'a>; 3]> {
+    let scrubbed_question = redactor.redact(question);
+    let mut context: Value = serde_json::from_str(context_json).unwrap_or(Value::Null);
+    let mut scrubbed_context = redactor.redact_json(&mut context, true, &scrubbed_question.entities);
+    let known: Vec<(String, String)> =
+        scrubbed_question.entities.iter().chain(&scrubbed_context.entities).cloned().collect();
+    let mut counsel: Value = serde_json::from_str(counsel_json)?;
+    let mut scrubbed_counsel = redactor.redact_json(&mut counsel, false, &known);
+
+    scrubbed_context.text = if scrubbed_context.changed() { serde_json::to_string(&context)? } else { context_json.to_string() };
+    scrubbed_counsel.text = if scrubbed_counsel.changed() { serde_json::to_string(&counsel)? } else { counsel_json.to_string() };
+    Ok([
+        Field { name: "question", original: question, redacted: scrubbed_question },
+        Field { name: "context", original: context_json, redacted: scrubbed_context },
+        Field { name: "counsel", original: counsel_json, redacted: scrubbed_counsel },
+    ])
+}
+
+/// A decision's question, context JSON and counsel JSON as they should be
+/// stored (unchanged when redaction is off)
+pub fn scrub_decision(
+    conn: &Connection,
+    decision_id: &str,
+    question: &str,
+    context_json: &str,
+    counsel_json: &str,
+) -> Result<(String, String, String)> {
+    let Some(redactor) = Redactor::beside(conn)? else {
+        return Ok((question.to_string(), context_json.to_string(), counsel_json.to_string()));
+    };
+    let fields = scrub_fields(&redactor, question, context_json, counsel_json)?;
+    for field in &fields {
+        record(conn, decision_id, field.name, field.original, &field.redacted)?;
+    }
+    let [question, context, counsel] = fields;
+    Ok((question.redacted.text, context.redacted.text, counsel.redacted.text))
+}
+
+/// Outcome notes as they should be stored
+pub fn scrub_notes(conn: &Connection, decision_id: &str, notes: &str) -> Result<String> {
+    let Some(redactor) = Redactor::beside(conn)? else {
+        return Ok(notes.to_string());
+    };
+    let redacted = redactor.redact(notes);
+    record(conn, decision_id, "outcome_notes", notes, &redacted)?;
+    Ok(redacted.text)
+}
+
+/// What `100minds scrub` changed (or would change, on a dry run)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScrubReport {
+    pub decisions: usize,
+    pub changed: usize,
+    /// Decisions each placeholder was used in
+    pub kinds: BTreeMap<String, usize>,
+    pub dry_run: bool,
+}
+
+/// Apply the configured rules (whether or not `redact` is on) to every
+/// stored decision
+pub fn scrub_existing(conn: &Connection, dry_run: bool) -> Result<ScrubReport> {
+    let redactor = Redactor::new(&PrivacyConfig::beside(conn)?)?;
+    type Row = (String, String, Option<String>, String, Option<String>);
+    let rows: Vec<Row> = conn
+        .prepare("SELECT id, question, context_json, counsel_json, outcome_notes FROM decisions")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut report = ScrubReport { decisions: rows.len(), dry_run, ..Default::default() };
+    crate::db::with_savepoint(conn, "scrub", || -> Result<()> {
+        for (id, question, context_json, counsel_json, notes) in &rows {
+            let [question, context, counsel] =
+                scrub_fields(&redactor, question, context_json.as_deref().unwrap_or("null"), counsel_json)?;
+            let notes = notes.as_deref().map(|original| Field {
+                name: "outcome_notes",
+                original,
+                redacted: redactor.redact(original),
+            });
+
+            let fields: Vec<&Field> = [&question, &context, &counsel].into_iter().chain(&notes).collect();
+            let kinds: BTreeSet<&String> = fields.iter().flat_map(|f| &f.redacted.kinds).collect();
+            if kinds.is_empty() {
+                continue;
+            }
+            report.changed += 1;
+            for kind in kinds {
+                *report.kinds.entry(kind.clone()).or_default() += 1;
+            }
+            if dry_run {
+                continue;
+            }
+            for field in &fields {
+                record(conn, id, field.name, field.original, &field.redacted)?;
+            }
+            conn.execute(
+                "UPDATE decisions SET question = ?2, context_json = ?3, counsel_json = ?4, outcome_notes = ?5 WHERE id = ?1",
+                params![
+                    id,
+                    question.redacted.text,
+                    context_json.as_ref().map(|_| &context.redacted.text),
+                    counsel.redacted.text,
+                    notes.as_ref().map(|n| &n.redacted.text)
+                ],
+            )?;
+        }
+        Ok(())
+    })?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tags every occurrence of "Dana Reyes"
+    struct Names;
+
+    impl EntityRecognizer for Names {
+        fn entities(&self, text: &str) -> Result<Vec<Entity>> {
+            let entity = |(start, name): (usize, &str)| Entity { start, end: start + name.len(), label: "PER".to_string() };
+            Ok(text.match_indices("Dana Reyes").map(entity).collect())
+        }
+    }
+
+    #[test]
+    fn test_rules_and_entities_become_placeholders() {
+        let config = PrivacyConfig { patterns: vec![r"ACME-\d+".to_string()], ..Default::default() };
+        let redactor = Redactor::new(&config).unwrap().with_recognizer(Arc::new(Names));
+        let redacted = redactor.redact(
+            "Dana Reyes (dana@acme.io, +1 415-555-0100) says ACME-4411 hit 10.0.0.12 with key sk-abcdef1234567890XYZ",
+        );
+        assert_eq!(redacted.text, "[PER] ([EMAIL], [PHONE]) says [REDACTED] hit [IP] with key [SECRET]");
+        assert_eq!(redacted.kinds.len(), 6);
+        assert_eq!(redactor.redact("Pay with 4111 1111 1111 1111").text, "Pay with [CARD]");
+        assert!(!redactor.redact("Should we split the monolith?").changed());
+
+        // A stored counsel response keeps thinkers' names but loses the ones found in the question
+        let mut counsel = serde_json::json!({"question": "Hire Dana Reyes?", "positions": [{"thinker": "Fred Brooks"}]});
+        let found = redactor.redact_json(&mut counsel, false, &redacted.entities);
+        assert_eq!(counsel["question"], "Hire [PER]?");
+        assert_eq!(counsel["positions"][0]["thinker"], "Fred Brooks");
+        assert!(found.changed());
+        assert!(Redactor::new(&PrivacyConfig { builtin: vec!["ssn".to_string()], ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn test_stored_text_is_redacted_and_originals_hashed() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let counsel = r#"{"question":"Email bo@corp.com first?"}"#;
+        crate::db::insert_decision(&conn, "old", "Email bo@corp.com first?", None, counsel, None, "h", "s", "k").unwrap();
+
+        // Off by default: nothing changes
+        let (question, _, _) = scrub_decision(&conn, "d1", "Ask bo@corp.com?", "{}", "{}").unwrap();
+        assert_eq!(question, "Ask bo@corp.com?");
+
+        std::fs::write(dir.path().join("config.toml"), "[privacy]\nredact = true\n").unwrap();
+        let context = r#"{"team":"call 415-555-0100"}"#;
+        let (question, context, counsel) =
+            scrub_decision(&conn, "d1", "Ask bo@corp.com?", context, r#"{"question":"Ask bo@corp.com?"}"#).unwrap();
+        assert_eq!((question.as_str(), context.as_str()), ("Ask [EMAIL]?", r#"{"team":"call [PHONE]"}"#));
+        assert_eq!(counsel, r#"{"question":"Ask [EMAIL]?"}"#);
+        assert_eq!(scrub_notes(&conn, "d1", "no PII here").unwrap(), "no PII here");
+        let (hash, kinds): (String, String) = conn
+            .query_row("SELECT original_sha256, kinds FROM redactions WHERE decision_id = 'd1' AND field = 'question'", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((hash, kinds), (sha256("Ask bo@corp.com?"), "[EMAIL]".to_string()));
+
+        let preview = scrub_existing(&conn, true).unwrap();
+        assert_eq!((preview.decisions, preview.changed, preview.kinds["[EMAIL]"]), (1, 1, 1));
+        let stored = |conn: &Connection| -> String {
+            conn.query_row("SELECT question FROM decisions WHERE id = 'old'", [], |row| row.get(0)).unwrap()
+        };
+        assert_eq!(stored(&conn), "Email bo@corp.com first?");
+        scrub_existing(&conn, false).unwrap();
+        assert_eq!(stored(&conn), "Email [EMAIL] first?");
+        assert_eq!(scrub_existing(&conn, false).unwrap().changed, 0);
+    }
+}
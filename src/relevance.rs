@@ -0,0 +1,421 @@
+//! Precomputed Relevance Features
+//!
+//! Keyword relevance scoring checks ~100 fixed keywords against the name
+//! and description of every candidate principle. None of that depends on
+//! the question, so each principle's lowercased text and keyword hits are
+//! computed once ([`PrincipleFeatures`]) and cached process-wide by
+//! principle id. A cached entry is rebuilt when the principle's name or
+//! description no longer matches, so corpus edits invalidate it.
+//!
+//! The question side is likewise analyzed once per query
+//! ([`QueryFeatures`]) instead of once per candidate.
+
+use crate::db::PrincipleMatch;
+use crate::search;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+// Keyword groups (each at most 64 entries: hits are stored as bitmasks)
+
+/// High-value principle types (these are gold)
+const HIGH_VALUE: &[&str] = &[
+    "80/20", "focus", "lean", "fear", "compound", "eliminate", "pareto", "yagni", "simplest",
+    "overengineer", "speculative",
+];
+
+/// Architecture terms (Sam Newman, Fowler, etc.)
+const ARCHITECTURE: &[&str] = &[
+    "microservices", "monolith", "database", "service", "distributed", "migration", "bounded",
+    "aggregate", "cqrs", "event sourcing", "strangler", "circuit breaker", "failure", "legacy",
+    "rewrite", "incremental", "deploy", "resilience", "cache", "caching", "premature",
+    "optimization", "latency", "throughput", "scale", "simple", "simplicity", "complexity", "yagni",
+    "needless",
+];
+
+/// Performance/optimization (Knuth, Gregg, etc.)
+const PERFORMANCE: &[&str] = &[
+    "premature", "optimization", "fast", "slow", "performance", "measure", "profile", "bottleneck",
+    "efficient", "speed", "flame", "latency", "throughput",
+];
+
+/// Testing/TDD (Kent Beck, Feathers, etc.)
+const TESTING: &[&str] = &[
+    "test", "tdd", "red-green", "test-first", "mock", "stub", "coverage", "unit", "integration",
+    "pyramid", "isolation",
+];
+
+/// Legacy / tangled code (Feathers, seams, etc.)
+const LEGACY: &[&str] = &[
+    "legacy", "seam", "tangled", "breaks", "brittle", "fragile", "coupling", "dependency",
+    "working effectively", "characterization",
+];
+
+/// Refactoring/code cleanup
+const REFACTORING: &[&str] = &[
+    "refactor", "messy", "cleanup", "clean", "spaghetti", "improve", "incremental design",
+    "technical debt",
+];
+
+/// Project management / team scaling
+const MANAGEMENT: &[&str] = &[
+    "late", "deadline", "team", "people", "adding", "hire", "staff", "communication", "overhead",
+    "brooks", "mythical",
+];
+
+/// Database/migration
+const DATABASE: &[&str] = &[
+    "database", "migrate", "migration", "oracle", "postgres", "mysql", "nosql", "sql", "schema",
+    "query", "data model",
+];
+
+/// Build vs buy
+const BUILD_BUY: &[&str] = &[
+    "build", "buy", "vendor", "custom", "off-the-shelf", "integrate", "tco", "total cost",
+    "maintenance", "saas", "third-party", "hosted", "managed",
+];
+
+/// Technical debt / rewrite
+const DEBT: &[&str] = &[
+    "technical debt", "debt", "rewrite", "refactor", "legacy", "strangler", "incremental",
+    "migration", "modernize",
+];
+
+const GROUPS: [&[&str]; 10] = [
+    HIGH_VALUE, ARCHITECTURE, PERFORMANCE, TESTING, LEGACY, REFACTORING, MANAGEMENT, DATABASE,
+    BUILD_BUY, DEBT,
+];
+
+#[derive(Clone, Copy)]
+enum Group {
+    HighValue,
+    Architecture,
+    Performance,
+    Testing,
+    Legacy,
+    Refactoring,
+    Management,
+    Database,
+    BuildBuy,
+    Debt,
+}
+
+/// Which keywords of each group appear in a text, one bit per keyword
+fn keyword_hits(text: &str) -> [u64; 10] {
+    let mut hits = [0u64; 10];
+    for (group, keywords) in GROUPS.iter().enumerate() {
+        for (bit, kw) in keywords.iter().enumerate() {
+            if text.contains(kw) {
+                hits[group] |= 1 << bit;
+            }
+        }
+    }
+    hits
+}
+
+/// Question-independent scoring inputs for one principle
+#[derive(Debug)]
+pub struct PrincipleFeatures {
+    // Source text, to notice corpus edits
+    name: String,
+    description: String,
+    name_lower: String,
+    desc_lower: String,
+    name_hits: [u64; 10],
+    desc_hits: [u64; 10],
+    /// "Profile Before Optimizing" and friends
+    profiling: bool,
+    /// TDD / test-first / red-green by name
+    test_first: bool,
+    /// Feathers-style legacy code guidance
+    legacy_authority: bool,
+    /// Incremental design / tech debt guidance
+    refactor_authority: bool,
+    /// Literally "Build vs Buy"
+    build_vs_buy: bool,
+}
+
+impl PrincipleFeatures {
+    pub fn new(name: &str, description: &str) -> Self {
+        let name_lower = name.to_lowercase();
+        let desc_lower = description.to_lowercase();
+        PrincipleFeatures {
+            name: name.to_string(),
+            description: description.to_string(),
+            name_hits: keyword_hits(&name_lower),
+            desc_hits: keyword_hits(&desc_lower),
+            profiling: name_lower.contains("profile") || name_lower.contains("premature"),
+            test_first: ["tdd", "test-first", "test first", "red-green"]
+                .iter()
+                .any(|kw| name_lower.contains(kw)),
+            legacy_authority: name_lower.contains("feathers")
+                || desc_lower.contains("seam")
+                || name_lower.contains("legacy")
+                || desc_lower.contains("working effectively"),
+            refactor_authority: name_lower.contains("incremental")
+                || name_lower.contains("debt")
+                || desc_lower.contains("incremental")
+                || desc_lower.contains("tech debt")
+                || desc_lower.contains("technical debt"),
+            build_vs_buy: name_lower.contains("build") && name_lower.contains("buy"),
+            name_lower,
+            desc_lower,
+        }
+    }
+
+    fn matches(&self, principle: &PrincipleMatch) -> bool {
+        self.name == principle.name && self.description == principle.description
+    }
+
+    /// Keywords of `group` found in the name
+    fn in_name(&self, group: Group) -> f64 {
+        self.name_hits[group as usize].count_ones() as f64
+    }
+
+    /// Keywords of `group` found in the description
+    fn in_desc(&self, group: Group) -> f64 {
+        self.desc_hits[group as usize].count_ones() as f64
+    }
+
+    /// Keywords of `group` found in either
+    fn in_either(&self, group: Group) -> f64 {
+        (self.name_hits[group as usize] | self.desc_hits[group as usize]).count_ones() as f64
+    }
+}
+
+fn cache() -> &'static RwLock<HashMap<String, Arc<PrincipleFeatures>>> {
+    static CACHE: OnceLock<RwLock<HashMap<String, Arc<PrincipleFeatures>>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// Cached features for a principle, (re)built if missing or stale
+pub fn features(principle: &PrincipleMatch) -> Arc<PrincipleFeatures> {
+    if let Some(cached) = cache().read().unwrap().get(&principle.id) {
+        if cached.matches(principle) {
+            return Arc::clone(cached);
+        }
+    }
+    let fresh = Arc::new(PrincipleFeatures::new(&principle.name, &principle.description));
+    cache().write().unwrap().insert(principle.id.clone(), Arc::clone(&fresh));
+    fresh
+}
+
+/// Drop every cached entry (after a bulk corpus reload)
+pub fn clear_cache() {
+    cache().write().unwrap().clear();
+}
+
+/// Per-question scoring inputs
+#[derive(Debug)]
+pub struct QueryFeatures {
+    /// Meaningful (4+ character) words
+    words: Vec<String>,
+    /// Stems of the non-stopword words (focus/focused, build/building)
+    stems: Vec<String>,
+    is_perf: bool,
+    mentions_test: bool,
+    is_tdd: bool,
+    is_legacy: bool,
+    is_refactor: bool,
+    mentions_db: bool,
+    is_build_buy: bool,
+    is_debt: bool,
+}
+
+impl QueryFeatures {
+    pub fn new(question: &str) -> Self {
+        let q = question.to_lowercase();
+        let words: Vec<String> = q
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| w.len() > 3)
+            .map(String::from)
+            .collect();
+        let stems = words
+            .iter()
+            .filter(|w| !search::is_stopword(w))
+            .map(|w| search::stem(w))
+            .collect();
+        let any = |kws: &[&str]| kws.iter().any(|kw| q.contains(kw));
+
+        QueryFeatures {
+            is_perf: any(&["slow", "fast", "performance", "optimize"]),
+            mentions_test: q.contains("test"),
+            // TDD-specific questions, not just any test mention
+            is_tdd: q.contains("before") && q.contains("after") && q.contains("test"),
+            is_legacy: any(&["tangled", "breaks", "legacy", "old code", "every change"]),
+            is_refactor: any(&["refactor", "messy", "cleanup", "clean up", "before adding"]),
+            mentions_db: any(&["database", "oracle", "postgres", "migrate"]),
+            // "build...or use X" is the same as "build vs buy"
+            is_build_buy: (q.contains("build") && (q.contains("buy") || q.contains("use ")))
+                || any(&["vendor", "custom", "hosted", "managed"])
+                || (q.contains("our own") && q.contains("or ")),
+            is_debt: any(&["debt", "rewrite", "refactor", "legacy"]),
+            words,
+            stems,
+        }
+    }
+
+    /// Domain used for the contextual Thompson lookup
+    pub fn domain(&self) -> &'static str {
+        if self.mentions_test {
+            "testing"
+        } else if self.is_build_buy {
+            "architecture"
+        } else if self.is_debt {
+            "practices"
+        } else if self.mentions_db {
+            "architecture"
+        } else {
+            "entrepreneurship"
+        }
+    }
+}
+
+/// Keyword relevance of a principle to a question (before learned boosts)
+pub fn keyword_score(q: &QueryFeatures, p: &PrincipleFeatures) -> f64 {
+    let pick = |cond: bool, yes: f64, no: f64| if cond { yes } else { no };
+    let mut score = 0.0;
+
+    // Question stems in description and (most valuable) name
+    for stem in &q.stems {
+        if p.desc_lower.contains(stem.as_str()) {
+            score += 3.0;
+        }
+        if p.name_lower.contains(stem.as_str()) {
+            score += 5.0;
+        }
+    }
+
+    // Known-good frameworks
+    score += 4.0 * p.in_either(Group::HighValue);
+
+    score += 6.0 * p.in_name(Group::Architecture) + 3.0 * p.in_desc(Group::Architecture);
+
+    score += pick(q.is_perf, 12.0, 3.0) * p.in_name(Group::Performance)
+        + pick(q.is_perf, 6.0, 2.0) * p.in_desc(Group::Performance);
+    if q.is_perf && p.profiling {
+        score += 15.0;
+    }
+
+    score += pick(q.mentions_test, 10.0, 2.0) * p.in_name(Group::Testing)
+        + pick(q.mentions_test, 5.0, 1.0) * p.in_desc(Group::Testing);
+    if q.is_tdd && p.test_first {
+        score += 30.0; // Override other signals for explicit TDD questions
+    }
+
+    score += pick(q.is_legacy, 15.0, 2.0) * p.in_name(Group::Legacy)
+        + pick(q.is_legacy, 8.0, 1.0) * p.in_desc(Group::Legacy);
+    if q.is_legacy && p.legacy_authority {
+        score += 20.0;
+    }
+
+    score += pick(q.is_refactor, 15.0, 2.0) * p.in_name(Group::Refactoring)
+        + pick(q.is_refactor, 8.0, 1.0) * p.in_desc(Group::Refactoring);
+    if q.is_refactor && p.refactor_authority {
+        score += 25.0;
+    }
+
+    // Exact question terms anywhere, and again in the name
+    for word in &q.words {
+        let in_name = p.name_lower.contains(word.as_str());
+        if in_name || p.desc_lower.contains(word.as_str()) {
+            score += 4.0;
+        }
+        if in_name {
+            score += 3.0;
+        }
+    }
+
+    score += 5.0 * p.in_either(Group::Management);
+    score += pick(q.mentions_db, 8.0, 2.0) * p.in_either(Group::Database);
+
+    if q.is_build_buy && p.build_vs_buy {
+        score += 50.0; // This is THE principle for this question
+    }
+    score += pick(q.is_build_buy, 8.0, 2.0) * p.in_either(Group::BuildBuy);
+    score += pick(q.is_debt, 8.0, 2.0) * p.in_either(Group::Debt);
+
+    score
+}
+
+/// Timings from [`micro_benchmark`], in microseconds per principle scored
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ScoringBench {
+    pub principles: usize,
+    pub questions: usize,
+    /// Features rebuilt for every score (the pre-index cost)
+    pub uncached_us: f64,
+    /// Features from the cache
+    pub cached_us: f64,
+}
+
+/// Score every principle against every question, with and without the cache
+pub fn micro_benchmark(principles: &[PrincipleMatch], questions: &[&str]) -> ScoringBench {
+    let queries: Vec<QueryFeatures> = questions.iter().map(|q| QueryFeatures::new(q)).collect();
+    let scored = (principles.len() * queries.len()).max(1) as f64;
+    let mut checksum = 0.0;
+
+    let started = std::time::Instant::now();
+    for q in &queries {
+        for p in principles {
+            checksum += keyword_score(q, &PrincipleFeatures::new(&p.name, &p.description));
+        }
+    }
+    let uncached_us = started.elapsed().as_secs_f64() * 1e6 / scored;
+
+    // Warm the cache
+    for p in principles {
+        features(p);
+    }
+    let started = std::time::Instant::now();
+    for q in &queries {
+        for p in principles {
+            checksum -= keyword_score(q, &features(p));
+        }
+    }
+    let cached_us = started.elapsed().as_secs_f64() * 1e6 / scored;
+    debug_assert!(checksum.abs() < 1e-6);
+
+    ScoringBench { principles: principles.len(), questions: queries.len(), uncached_us, cached_us }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn principle(id: &str, name: &str, description: &str) -> PrincipleMatch {
+        PrincipleMatch {
+            id: id.to_string(),
+            thinker_id: "t".to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            confidence: 0.5,
+            relevance_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_keyword_score_boosts() {
+        let profile = PrincipleFeatures::new("Profile Before Optimizing", "Measure the bottleneck first");
+        let perf = QueryFeatures::new("Our API is slow, should we optimize?");
+        let other = QueryFeatures::new("Should we hire a designer?");
+        // Perf question: name hits (profile) x12, desc hits (measure, bottleneck) x6, +15
+        assert!(keyword_score(&perf, &profile) >= 12.0 + 2.0 * 6.0 + 15.0);
+        assert!(keyword_score(&perf, &profile) > keyword_score(&other, &profile));
+
+        let build_buy = PrincipleFeatures::new("Build vs Buy", "Total cost of ownership");
+        let q = QueryFeatures::new("Should we build our own auth or use Auth0?");
+        assert_eq!(q.domain(), "architecture");
+        assert!(keyword_score(&q, &build_buy) >= 50.0);
+    }
+
+    #[test]
+    fn test_cache_rebuilds_on_edit() {
+        let mut p = principle("relevance-test-1", "Strangler Fig", "Replace legacy systems gradually");
+        let q = QueryFeatures::new("How do we replace a legacy system?");
+        let before = keyword_score(&q, &features(&p));
+        assert!(Arc::ptr_eq(&features(&p), &features(&p)));
+
+        p.description = "Grow the new system around the old".to_string();
+        assert!(keyword_score(&q, &features(&p)) < before);
+    }
+}
@@ -0,0 +1,548 @@
+//! Learning Replay
+//!
+//! `100minds replay` rebuilds every principle's confidence from its base by
+//! re-applying the recorded history in order: each row of
+//! `framework_adjustments` (outcomes, falsifications, and the older
+//! engine-level outcome path) and each decay run, with the same clamps and
+//! decay clocks the live code uses. A faithful replay should land exactly on
+//! the live confidences, so any difference points at learning that bypassed
+//! the log (manual edits, merges, syncs) or at history pruned into monthly
+//! rollups. `--apply` writes the replayed values back.
+//!
+//! With a [`ReplayConfig`] override (another daily decay factor or outcome
+//! deltas) the same history answers "what would the confidences be now if
+//! we had learned this way?"; such replays are reports only.
+//!
+//! Naming another selection policy or epsilon replays the decisions too (see
+//! [`replay_selection`]): each decision's lead principle is re-picked by the
+//! alternative from the arms as they stood then, and where it agrees with
+//! the logged pick the logged outcome counts toward its success rate. That
+//! is the replay estimator (Li et al., 2011); it is only unbiased when the
+//! logged picks were uniformly random, so treat it as a direction, not a
+//! number to tune to the second decimal. Decisions that logged their
+//! selection propensities get IPS and doubly robust estimates as well.
+//!
+//! Culled principles (confidence -1) stay archived throughout.
+
+use crate::eval::bandit::{off_policy_estimate, policy_engine, BetaPosterior, LoggedSample, OffPolicyEstimate};
+use crate::eval::thompson::{FGTSConfig, LearningConfig};
+use crate::outcome::CONFIDENCE_RANGE;
+use crate::error::{MindsError, Result};
+use chrono::{DateTime, NaiveDateTime};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Differences below this are rounding, not drift
+pub const TOLERANCE: f64 = 1e-9;
+
+const TIMESTAMP: &str = "%Y-%m-%d %H:%M:%S";
+
+/// What to change about the recorded history; the default replays it as it happened
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReplayConfig {
+    /// Daily decay factor instead of each run's recorded one
+    pub daily_decay: Option<f64>,
+    /// Outcome delta for a success instead of the recorded one
+    pub success_delta: Option<f64>,
+    /// Outcome delta for a failure instead of the recorded one
+    pub failure_delta: Option<f64>,
+    /// Selection policy to replay the decisions under (see `bandit::POLICIES`)
+    pub policy: Option<String>,
+    /// Exploration rate to replay the decisions under
+    pub epsilon: Option<f64>,
+    /// Seed for the replayed policy's random draws
+    pub seed: u64,
+}
+
+impl ReplayConfig {
+    /// No overrides: the replay should reproduce the live confidences
+    pub fn is_faithful(&self) -> bool {
+        ReplayConfig { seed: 0, ..self.clone() } == ReplayConfig::default()
+    }
+
+    /// Whether the decisions' selections are replayed too
+    pub fn replays_selection(&self) -> bool {
+        self.policy.is_some() || self.epsilon.is_some()
+    }
+}
+
+/// A principle whose replayed confidence isn't its live one
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Difference {
+    pub principle_id: String,
+    pub name: String,
+    pub live: f64,
+    pub replayed: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub config: ReplayConfig,
+    pub adjustments: usize,
+    pub decay_runs: usize,
+    /// Raw adjustments pruned into `adjustment_rollups`, which can't be replayed
+    pub rolled_up: i64,
+    pub principles: usize,
+    /// Largest gap, by how far apart they are
+    pub differences: Vec<Difference>,
+    /// Every principle's replayed confidence
+    #[serde(skip)]
+    pub confidences: BTreeMap<String, f64>,
+    /// The decisions replayed under another policy or epsilon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection: Option<SelectionReplay>,
+}
+
+/// How the decisions with outcomes would have gone under another selection policy
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectionReplay {
+    pub policy: String,
+    pub epsilon: f64,
+    /// Decisions with an outcome and at least one logged principle
+    pub decisions: usize,
+    pub logged_success_rate: f64,
+    /// Decisions where the alternative picked the logged lead principle
+    pub matched: usize,
+    /// Success rate over the matched decisions (None if none matched)
+    pub estimated_success_rate: Option<f64>,
+    /// Decisions that had an epsilon (exploration zone) pick, and how they went
+    pub explored: usize,
+    pub explored_success_rate: Option<f64>,
+    pub greedy_success_rate: Option<f64>,
+    /// Principles the alternative would have led with more or less often, largest change first
+    pub lead_changes: Vec<LeadChange>,
+    /// IPS / doubly robust success rate over decisions logged with propensities
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub off_policy: Option<OffPolicyEstimate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeadChange {
+    pub principle_id: String,
+    pub logged: usize,
+    pub replayed: usize,
+}
+
+/// Which code path wrote an adjustment, which decides its clamp
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Source {
+    /// `outcome::record_outcome`: ±delta within [`CONFIDENCE_RANGE`]
+    Outcome,
+    /// A fired falsification condition: within [`CONFIDENCE_RANGE`], delta as recorded
+    Falsification,
+    /// `CounselEngine::record_outcome`, which leaves no context: within [0, 1]
+    Engine,
+}
+
+enum Event {
+    Adjustment { principle_id: String, delta: f64, source: Source, at: Option<NaiveDateTime> },
+    Decay { daily_factor: f64, at: NaiveDateTime },
+}
+
+impl Event {
+    fn at(&self) -> Option<NaiveDateTime> {
+        match self {
+            Event::Adjustment { at, .. } => *at,
+            Event::Decay { at, .. } => Some(*at),
+        }
+    }
+}
+
+/// `decisions` mixes SQLite's `CURRENT_TIMESTAMP` with RFC 3339
+fn parse_time(at: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(at)
+        .map(|t| t.naive_utc())
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(at, TIMESTAMP).ok())
+}
+
+fn source(context_pattern: Option<&str>) -> Source {
+    let Some(context) = context_pattern else {
+        return Source::Engine;
+    };
+    let signal = serde_json::from_str::<serde_json::Value>(context).ok().and_then(|c| c.get("signal").cloned());
+    if signal.as_ref().and_then(|s| s.as_str()) == Some("falsification") {
+        Source::Falsification
+    } else {
+        Source::Outcome
+    }
+}
+
+/// Adjustments and decay runs, oldest first (adjustments first within a second)
+fn history(conn: &Connection) -> Result<Vec<Event>> {
+    let parse = |at: &str| NaiveDateTime::parse_from_str(at, TIMESTAMP).ok();
+    let mut events: Vec<Event> = conn
+        .prepare("SELECT principle_id, context_pattern, adjustment, created_at FROM framework_adjustments ORDER BY id")?
+        .query_map([], |row| {
+            let context: Option<String> = row.get(1)?;
+            Ok((row.get::<_, String>(0)?, context, row.get(2)?, row.get::<_, Option<String>>(3)?))
+        })?
+        .map(|row| {
+            let (principle_id, context, delta, at) = row?;
+            let source = source(context.as_deref());
+            Ok(Event::Adjustment { principle_id, delta, source, at: at.as_deref().and_then(parse) })
+        })
+        .collect::<Result<_>>()?;
+    let runs: Vec<(f64, String)> = conn
+        .prepare("SELECT daily_factor, run_at FROM decay_runs ORDER BY id")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for (daily_factor, run_at) in runs {
+        let Some(at) = parse(&run_at) else {
+            return Err(anyhow::anyhow!("Decay run at {:?} has an unreadable timestamp", run_at).into());
+        };
+        events.push(Event::Decay { daily_factor, at });
+    }
+    // Stable, so each table keeps its own order; adjustments without a
+    // readable time go first
+    events.sort_by_key(|event| (event.at(), matches!(event, Event::Decay { .. })));
+    Ok(events)
+}
+
+/// Replay the recorded history under `config`
+pub fn replay(conn: &Connection, config: &ReplayConfig) -> Result<ReplayReport> {
+    struct State {
+        name: String,
+        base: f64,
+        live: f64,
+        confidence: f64,
+        /// When decay last ran for it
+        decayed_at: Option<NaiveDateTime>,
+        /// Its latest adjustment so far, where a first decay clock starts
+        adjusted_at: Option<NaiveDateTime>,
+    }
+
+    let mut principles: HashMap<String, State> = conn
+        .prepare("SELECT id, name, COALESCE(base_confidence, 0.5), COALESCE(learned_confidence, 0.5) FROM principles")?
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?, row.get::<_, f64>(3)?)))?
+        .map(|row| {
+            let (id, name, base, live) = row?;
+            // Archived principles are pinned at -1 from the start
+            let confidence = if live < 0.0 { live } else { base };
+            Ok((id, State { name, base, live, confidence, decayed_at: None, adjusted_at: None }))
+        })
+        .collect::<Result<_>>()?;
+
+    let (floor, ceiling) = CONFIDENCE_RANGE;
+    let (mut adjustments, mut decay_runs) = (0, 0);
+    for event in history(conn)? {
+        match event {
+            Event::Adjustment { principle_id, delta, source, at } => {
+                adjustments += 1;
+                let Some(state) = principles.get_mut(&principle_id) else {
+                    continue;
+                };
+                state.adjusted_at = at.or(state.adjusted_at);
+                if state.confidence < 0.0 {
+                    continue;
+                }
+                state.confidence = match source {
+                    Source::Outcome => {
+                        let delta = match delta > 0.0 {
+                            true => config.success_delta.unwrap_or(delta),
+                            false => config.failure_delta.unwrap_or(delta),
+                        };
+                        (state.confidence + delta).clamp(floor, ceiling)
+                    }
+                    Source::Falsification => (state.confidence + delta).clamp(floor, ceiling),
+                    Source::Engine => (state.confidence + delta).clamp(0.0, 1.0),
+                };
+            }
+            Event::Decay { daily_factor, at } => {
+                decay_runs += 1;
+                let daily_factor = config.daily_decay.unwrap_or(daily_factor);
+                for state in principles.values_mut().filter(|s| s.confidence >= 0.0) {
+                    // Same clock as `decay::apply_decay`: from the last run, else
+                    // the latest adjustment, else it starts now
+                    if let Some(since) = state.decayed_at.or(state.adjusted_at) {
+                        let days = ((at - since).num_seconds().max(0) as f64) / 86_400.0;
+                        let factor = daily_factor.powf(days);
+                        state.confidence = (state.base + (state.confidence - state.base) * factor).clamp(0.0, 1.0);
+                    }
+                    state.decayed_at = Some(at);
+                }
+            }
+        }
+    }
+
+    let rolled_up = conn.query_row("SELECT COALESCE(SUM(adjustments), 0) FROM adjustment_rollups", [], |row| row.get(0))?;
+    let mut differences: Vec<Difference> = principles
+        .iter()
+        .filter(|(_, s)| (s.live - s.confidence).abs() > TOLERANCE)
+        .map(|(id, s)| Difference {
+            principle_id: id.clone(),
+            name: s.name.clone(),
+            live: s.live,
+            replayed: s.confidence,
+        })
+        .collect();
+    let gap = |d: &Difference| (d.live - d.replayed).abs();
+    differences.sort_by(|a, b| gap(b).total_cmp(&gap(a)).then_with(|| a.principle_id.cmp(&b.principle_id)));
+
+    Ok(ReplayReport {
+        config: config.clone(),
+        adjustments,
+        decay_runs,
+        rolled_up,
+        principles: principles.len(),
+        differences,
+        confidences: principles.into_iter().map(|(id, s)| (id, s.confidence)).collect(),
+        selection: config.replays_selection().then(|| replay_selection(conn, config)).transpose()?,
+    })
+}
+
+/// A decision with an outcome: when it was made and resolved, what it cited
+struct Resolved {
+    created_at: NaiveDateTime,
+    resolved_at: NaiveDateTime,
+    success: bool,
+    /// (principle id, strategy) per position, in order
+    selections: Vec<(String, String)>,
+    /// The logging policy's chance of the lead pick, when it was recorded
+    propensity: Option<f64>,
+}
+
+fn resolved_decisions(conn: &Connection) -> Result<Vec<Resolved>> {
+    let rows: Vec<(String, Option<String>, Option<String>, bool)> = conn
+        .prepare(
+            "SELECT counsel_json, created_at, outcome_recorded_at, outcome_success FROM decisions
+             WHERE outcome_success IS NOT NULL ORDER BY created_at, rowid",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    let mut decisions = Vec::new();
+    for (counsel_json, created_at, resolved_at, success) in rows {
+        let Some(created_at) = created_at.as_deref().and_then(parse_time) else {
+            continue;
+        };
+        let counsel: serde_json::Value = serde_json::from_str(&counsel_json).unwrap_or_default();
+        let logged = counsel.pointer("/exploration/selections").and_then(|s| s.as_array());
+        // Responses from before exploration logging: every position counts as greedy
+        let selections: Vec<(String, String)> = match logged {
+            Some(logged) => logged
+                .iter()
+                .filter_map(|s| {
+                    let strategy = s.get("strategy").and_then(|v| v.as_str()).unwrap_or("greedy");
+                    Some((s.get("principle_id")?.as_str()?.to_string(), strategy.to_string()))
+                })
+                .collect(),
+            None => counsel
+                .get("positions")
+                .and_then(|p| p.as_array())
+                .into_iter()
+                .flatten()
+                .filter(|p| !p.get("thinker_id").and_then(|t| t.as_str()).unwrap_or("_").starts_with('_'))
+                .filter_map(|p| p.pointer("/principles_cited/0")?.as_str())
+                .map(|id| (id.to_string(), "greedy".to_string()))
+                .collect(),
+        };
+        if selections.is_empty() {
+            continue;
+        }
+        let propensity = logged.and_then(|l| l.first()?.get("propensity")?.as_f64());
+        let resolved_at = resolved_at.as_deref().and_then(parse_time).unwrap_or(created_at);
+        decisions.push(Resolved { created_at, resolved_at, success, selections, propensity });
+    }
+    Ok(decisions)
+}
+
+/// Re-pick each resolved decision's lead principle under `config.policy` and
+/// `config.epsilon` (unset ones as in `[learning]`)
+///
+/// The arms are global per principle, rebuilt from the outcomes resolved
+/// before each decision was made. The alternative explores with probability
+/// epsilon by picking uniformly among the decision's principles, and
+/// otherwise takes the one its policy values highest. Decisions logged with
+/// the lead pick's propensity also feed IPS and doubly robust estimates,
+/// which use every such decision rather than only the agreeing ones.
+pub fn replay_selection(conn: &Connection, config: &ReplayConfig) -> Result<SelectionReplay> {
+    let learning = LearningConfig::beside(conn)?;
+    let learning = LearningConfig {
+        policy: config.policy.clone().unwrap_or(learning.policy),
+        epsilon: config.epsilon.unwrap_or(learning.epsilon).clamp(0.0, 1.0),
+        ..learning
+    };
+    let fgts = FGTSConfig { optimism_constant: learning.optimism, ..FGTSConfig::default() };
+    let policy = policy_engine(&learning, fgts)?;
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let decisions = resolved_decisions(conn)?;
+    let mut arms: HashMap<String, BetaPosterior> = HashMap::new();
+    let mut pending: Vec<&Resolved> = Vec::new();
+    let mut leads: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut logged_samples = Vec::new();
+    let (mut matched, mut matched_successes) = (0, 0);
+    let (mut explored, mut explored_successes, mut greedy_successes) = (0, 0, 0);
+    for decision in &decisions {
+        // Outcomes known by the time this decision was made
+        pending.sort_by_key(|d| std::cmp::Reverse(d.resolved_at));
+        while pending.last().is_some_and(|d| d.resolved_at <= decision.created_at) {
+            let done = pending.pop().expect("checked above");
+            for (principle_id, _) in &done.selections {
+                policy.update(arms.entry(principle_id.clone()).or_default(), done.success);
+            }
+        }
+
+        let candidates: Vec<&str> = decision.selections.iter().map(|(id, _)| id.as_str()).collect();
+        let candidate_arms: Vec<BetaPosterior> =
+            candidates.iter().map(|id| arms.get(*id).cloned().unwrap_or_default()).collect();
+        let values = policy.sample(&candidate_arms, &mut rng);
+        let best = (0..candidates.len()).max_by(|&a, &b| values[a].total_cmp(&values[b]).then(b.cmp(&a))).unwrap_or(0);
+        let pick = if rng.gen::<f64>() < learning.epsilon {
+            candidates[rng.gen_range(0..candidates.len())]
+        } else {
+            candidates[best]
+        };
+        if let Some(propensity) = decision.propensity {
+            // The alternative's chance of each candidate, with arm means as the reward model
+            let chance = |i: usize| {
+                learning.epsilon / candidates.len() as f64 + if i == best { 1.0 - learning.epsilon } else { 0.0 }
+            };
+            logged_samples.push(LoggedSample {
+                propensity,
+                reward: if decision.success { 1.0 } else { 0.0 },
+                target_probability: chance(0),
+                predicted_reward: candidate_arms[0].mean(),
+                target_predicted_reward: (0..candidates.len()).map(|i| chance(i) * candidate_arms[i].mean()).sum(),
+            });
+        }
+        leads.entry(candidates[0].to_string()).or_default().0 += 1;
+        leads.entry(pick.to_string()).or_default().1 += 1;
+        if pick == candidates[0] {
+            matched += 1;
+            matched_successes += decision.success as usize;
+        }
+        if decision.selections.iter().any(|(_, strategy)| strategy == "explore") {
+            explored += 1;
+            explored_successes += decision.success as usize;
+        } else {
+            greedy_successes += decision.success as usize;
+        }
+        pending.push(decision);
+    }
+
+    let rate = |successes: usize, total: usize| (total > 0).then(|| successes as f64 / total as f64);
+    let successes = decisions.iter().filter(|d| d.success).count();
+    let mut lead_changes: Vec<LeadChange> = leads
+        .into_iter()
+        .filter(|(_, (logged, replayed))| logged != replayed)
+        .map(|(principle_id, (logged, replayed))| LeadChange { principle_id, logged, replayed })
+        .collect();
+    lead_changes.sort_by_key(|c| std::cmp::Reverse(c.logged.abs_diff(c.replayed)));
+    Ok(SelectionReplay {
+        policy: learning.policy,
+        epsilon: learning.epsilon,
+        decisions: decisions.len(),
+        logged_success_rate: rate(successes, decisions.len()).unwrap_or(0.0),
+        matched,
+        estimated_success_rate: rate(matched_successes, matched),
+        explored,
+        explored_success_rate: rate(explored_successes, explored),
+        greedy_success_rate: rate(greedy_successes, decisions.len() - explored),
+        lead_changes,
+        off_policy: off_policy_estimate(&logged_samples),
+    })
+}
+
+/// Write a faithful replay's confidences over the live ones; returns how many changed
+pub fn apply(conn: &Connection, report: &ReplayReport) -> Result<usize> {
+    if !report.config.is_faithful() {
+        return Err(MindsError::Validation(
+            "Only a replay of the history as recorded can be applied (drop the what-if options)".to_string(),
+        ));
+    }
+    crate::db::with_savepoint(conn, "replay", || {
+        for difference in &report.differences {
+            conn.execute(
+                "UPDATE principles SET learned_confidence = ?2 WHERE id = ?1",
+                params![difference.principle_id, difference.replayed],
+            )?;
+        }
+        Ok(report.differences.len())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_replay_reproduces_outcomes_and_decay_and_finds_drift() {
+        let fixture = crate::testing::fixture().unwrap();
+        let conn = &fixture.conn;
+        let strangler = crate::testing::principle_id("fowler", "Strangler Fig");
+        let yagni = crate::testing::principle_id("beck", "YAGNI");
+        let policy = crate::eval::bandit::FeelGoodIndex { config: Default::default() };
+
+        let now = Utc::now();
+        crate::decay::apply_decay(conn, &policy, now).unwrap();
+        for (i, success) in [true, true, false, true].into_iter().enumerate() {
+            let cited = vec![strangler.clone(), yagni.clone()];
+            crate::outcome::record_outcome(conn, &format!("d{}", i), success, &cited, "", None).unwrap();
+        }
+        crate::decay::apply_decay(conn, &policy, now + Duration::days(9)).unwrap();
+        crate::decay::apply_decay(conn, &policy, now + Duration::days(30)).unwrap();
+
+        let report = replay(conn, &ReplayConfig::default()).unwrap();
+        assert_eq!((report.adjustments, report.decay_runs), (8, 3));
+        assert!(report.differences.is_empty(), "{:?}", report.differences);
+
+        // An edit that bypassed the log shows up, and applying puts it back
+        conn.execute("UPDATE principles SET learned_confidence = 0.9 WHERE id = ?1", [&yagni]).unwrap();
+        let report = replay(conn, &ReplayConfig::default()).unwrap();
+        assert_eq!(report.differences.len(), 1);
+        assert_eq!((report.differences[0].principle_id.as_str(), report.differences[0].live), (yagni.as_str(), 0.9));
+        assert_eq!(apply(conn, &report).unwrap(), 1);
+        assert!(replay(conn, &ReplayConfig::default()).unwrap().differences.is_empty());
+
+        // What-if: harsher failures leave the principles lower, and can't be applied
+        let harsher = ReplayConfig { failure_delta: Some(-0.3), ..Default::default() };
+        let what_if = replay(conn, &harsher).unwrap();
+        assert!(what_if.differences.iter().all(|d| d.replayed < d.live) && what_if.differences.len() == 2);
+        assert!(apply(conn, &what_if).is_err());
+    }
+
+    #[test]
+    fn test_selection_replay_counts_agreement_with_logged_leads() {
+        let conn = crate::db::init_db(std::path::Path::new(":memory:")).unwrap();
+        // Always led with "a" (failing three times) next to a fresh principle each time
+        let history = [("greedy", 0), ("greedy", 0), ("greedy", 0), ("explore", 1)];
+        for (i, (lead_strategy, success)) in history.iter().enumerate() {
+            let selections = serde_json::json!([
+                {"principle_id": "a", "strategy": lead_strategy, "propensity": 0.5},
+                {"principle_id": format!("b{}", i), "strategy": "greedy"},
+            ]);
+            let counsel = serde_json::json!({"exploration": {"selections": selections}});
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey,
+                                        outcome_success, outcome_recorded_at, created_at)
+                 VALUES (?1, 'q', ?2, 'h', 's', 'k', ?3, ?4, ?5)",
+                params![
+                    format!("d{}", i),
+                    counsel.to_string(),
+                    success,
+                    format!("2026-01-0{}T12:00:00+00:00", i + 1),
+                    format!("2026-01-0{} 00:00:00", i + 1),
+                ],
+            )
+            .unwrap();
+        }
+
+        let config = ReplayConfig { policy: Some("ucb1".into()), epsilon: Some(0.0), ..Default::default() };
+        assert!(!config.is_faithful());
+        let selection = replay(&conn, &config).unwrap().selection.unwrap();
+        // Cold arms tie on the first decision; after that "a" has failed and a fresh arm wins
+        assert_eq!((selection.decisions, selection.matched, selection.estimated_success_rate), (4, 1, Some(0.0)));
+        assert_eq!(selection.logged_success_rate, 0.25);
+        assert_eq!(selection.explored, 1);
+        assert_eq!((selection.explored_success_rate, selection.greedy_success_rate), (Some(1.0), Some(0.0)));
+        assert_eq!(selection.lead_changes[0], LeadChange { principle_id: "a".into(), logged: 4, replayed: 1 });
+        // Only the first (failed) decision has the alternative choosing "a": 1/0.5 * 0 over four samples
+        let off_policy = selection.off_policy.unwrap();
+        assert_eq!((off_policy.samples, off_policy.ips, off_policy.effective_sample_size), (4, 0.0, 1.0));
+    }
+}
@@ -0,0 +1,260 @@
+//! Retention
+//!
+//! `[retention]` in config.toml bounds how long raw history is kept;
+//! `100minds prune` (and the daemon's `prune` job) applies it:
+//!
+//! ```toml
+//! [retention]
+//! decision_days = 365     # decisions, with the rows that only describe them
+//! adjustment_days = 90    # raw confidence adjustments, rolled up per principle and month
+//! log_days = 30           # outcome log, watchdog alerts, finished counsel jobs
+//! ```
+//!
+//! Unset limits keep everything. Learned state (confidences, Thompson arms,
+//! template outcomes, adjustment rollups) is never pruned; adjustments that
+//! outlive their decision lose the link to it.
+//!
+//! A pruned decision leaves a tombstone with its chain fields (content
+//! hash, previous hash, signature, key), so the decisions after it still
+//! link to something and [`check_chain`] can tell a pruned gap from
+//! tampering.
+
+use crate::error::Result;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// Rows that only describe a decision, removed with it
+const DECISION_TABLES: [&str; 11] = [
+    "decision_links",
+    "outcome_keys",
+    "counsel_keys",
+    "blind_spot_checks",
+    "checklist_items",
+    "risk_registers",
+    "falsifications",
+    "redactions",
+    "decision_approvals",
+    "outcome_log",
+    "quarantined_outcomes",
+];
+
+/// The `[retention]` table of config.toml
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Days decisions are kept
+    pub decision_days: Option<i64>,
+    /// Days raw framework adjustments are kept before being rolled up
+    pub adjustment_days: Option<i64>,
+    /// Days operational logs are kept
+    pub log_days: Option<i64>,
+}
+
+impl RetentionConfig {
+    /// Read `[retention]` from the config.toml beside the database; a missing file or table keeps everything
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        crate::config::section(conn, "retention")
+    }
+}
+
+/// What a prune removed (or would remove, on a dry run)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PruneReport {
+    pub decisions: usize,
+    /// Raw adjustments folded into `adjustment_rollups`
+    pub adjustments: usize,
+    pub log_rows: usize,
+    pub dry_run: bool,
+}
+
+/// Remove what the retention config no longer keeps, in one transaction
+pub fn prune(conn: &Connection, config: &RetentionConfig, now: DateTime<Utc>, dry_run: bool) -> Result<PruneReport> {
+    let cutoff = |days: i64| (now - Duration::days(days)).to_rfc3339();
+    let count = |sql: &str, cutoff: &str| -> rusqlite::Result<usize> {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", sql), [cutoff], |row| row.get::<_, i64>(0).map(|n| n as usize))
+    };
+    let mut report = PruneReport { dry_run, ..Default::default() };
+
+    crate::db::with_savepoint(conn, "prune", || -> Result<()> {
+        if let Some(days) = config.decision_days {
+            let old = "julianday(created_at) < julianday(?1)";
+            report.decisions = count(&format!("decisions WHERE {}", old), &cutoff(days))?;
+            if !dry_run && report.decisions > 0 {
+                tombstone(conn, &format!("SELECT id FROM decisions WHERE {}", old), &cutoff(days), "retention", now)?;
+            }
+        }
+        if let Some(days) = config.adjustment_days {
+            let old = "framework_adjustments WHERE julianday(created_at) < julianday(?1)";
+            report.adjustments = count(old, &cutoff(days))?;
+            if !dry_run && report.adjustments > 0 {
+                conn.execute(
+                    &format!(
+                        "INSERT INTO adjustment_rollups (principle_id, month, adjustments, total, gains, losses)
+                         SELECT principle_id, strftime('%Y-%m', created_at), COUNT(*), SUM(adjustment),
+                                SUM(adjustment > 0), SUM(adjustment < 0)
+                         FROM {} GROUP BY 1, 2
+                         ON CONFLICT(principle_id, month) DO UPDATE SET
+                             adjustments = adjustments + excluded.adjustments,
+                             total = total + excluded.total,
+                             gains = gains + excluded.gains,
+                             losses = losses + excluded.losses",
+                        old
+                    ),
+                    [cutoff(days)],
+                )?;
+                conn.execute(&format!("DELETE FROM {}", old), [cutoff(days)])?;
+            }
+        }
+        if let Some(days) = config.log_days {
+            let logs = [
+                "outcome_log WHERE julianday(recorded_at) < julianday(?1)",
+                "watchdog_alerts WHERE julianday(last_seen) < julianday(?1)",
+                "counsel_jobs WHERE status IN ('done', 'failed') AND julianday(finished_at) < julianday(?1)",
+            ];
+            for log in logs {
+                report.log_rows += count(log, &cutoff(days))?;
+                if !dry_run {
+                    conn.execute(&format!("DELETE FROM {}", log), [cutoff(days)])?;
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(report)
+}
+
+/// Replace the decisions `ids_sql` selects (with `?1` bound to `arg`) by
+/// tombstones, deleting the rows that only describe them
+pub fn tombstone(conn: &Connection, ids_sql: &str, arg: &str, reason: &str, now: DateTime<Utc>) -> Result<usize> {
+    conn.execute(
+        &format!(
+            "INSERT OR IGNORE INTO decision_tombstones
+                 (decision_id, previous_hash, content_hash, signature, agent_pubkey, created_at, removed_at, reason)
+             SELECT id, previous_hash, content_hash, signature, agent_pubkey, created_at, ?2, ?3
+             FROM decisions WHERE id IN ({})",
+            ids_sql
+        ),
+        params![arg, now.to_rfc3339(), reason],
+    )?;
+    for table in DECISION_TABLES {
+        conn.execute(&format!("DELETE FROM {} WHERE decision_id IN ({})", table, ids_sql), [arg])?;
+    }
+    // What was learned stays; it just can't point at the decision any more
+    conn.execute(
+        &format!("UPDATE framework_adjustments SET decision_id = NULL WHERE decision_id IN ({})", ids_sql),
+        [arg],
+    )?;
+    conn.execute(&format!("UPDATE estimates SET decision_id = NULL WHERE decision_id IN ({})", ids_sql), [arg])?;
+    Ok(conn.execute(&format!("DELETE FROM decisions WHERE id IN ({})", ids_sql), [arg])?)
+}
+
+/// A removed decision, as its tombstone remembers it
+#[derive(Debug, Clone, Serialize)]
+pub struct Tombstone {
+    pub decision_id: String,
+    pub content_hash: String,
+    pub created_at: Option<String>,
+    pub removed_at: String,
+    pub reason: String,
+}
+
+pub fn get_tombstone(conn: &Connection, decision_id: &str) -> Result<Option<Tombstone>> {
+    use rusqlite::OptionalExtension;
+
+    Ok(conn
+        .query_row(
+            "SELECT decision_id, content_hash, created_at, removed_at, reason FROM decision_tombstones WHERE decision_id = ?1",
+            [decision_id],
+            |row| {
+                Ok(Tombstone {
+                    decision_id: row.get(0)?,
+                    content_hash: row.get(1)?,
+                    created_at: row.get(2)?,
+                    removed_at: row.get(3)?,
+                    reason: row.get(4)?,
+                })
+            },
+        )
+        .optional()?)
+}
+
+/// Chain links whose previous hash matches no stored decision, tombstone
+/// or deletion event
+pub fn check_chain(conn: &Connection) -> Result<Vec<String>> {
+    let broken = conn
+        .prepare(
+            "WITH links AS (
+                 SELECT id, previous_hash FROM decisions
+                 UNION ALL SELECT decision_id, previous_hash FROM decision_tombstones
+                 UNION ALL SELECT id, previous_hash FROM deletion_events
+             ), hashes AS (
+                 SELECT content_hash FROM decisions
+                 UNION SELECT content_hash FROM decision_tombstones
+                 UNION SELECT content_hash FROM deletion_events
+             )
+             SELECT id FROM links
+             WHERE previous_hash IS NOT NULL AND previous_hash NOT IN (SELECT content_hash FROM hashes)
+             ORDER BY id",
+        )?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(broken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_tombstones_decisions_and_rolls_up_adjustments() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description) VALUES ('p', 't', 'P', 'd');",
+        )
+        .unwrap();
+        let now = Utc::now();
+        for (i, days) in [400, 300, 10].iter().enumerate() {
+            let previous = (i > 0).then(|| format!("h{}", i - 1));
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, previous_hash, content_hash, signature, agent_pubkey, created_at)
+                 VALUES (?1, 'q', '{}', ?2, ?3, 's', 'k', ?4)",
+                params![format!("d{}", i), previous, format!("h{}", i), (now - Duration::days(*days)).to_rfc3339()],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO framework_adjustments (principle_id, adjustment, decision_id, created_at) VALUES ('p', ?1, ?2, ?3)",
+                params![if i == 1 { -0.1 } else { 0.05 }, format!("d{}", i), (now - Duration::days(*days)).to_rfc3339()],
+            )
+            .unwrap();
+        }
+        crate::approval::request_review(&conn, "d0").unwrap();
+
+        let config = RetentionConfig { decision_days: Some(365), adjustment_days: Some(90), log_days: None };
+        let preview = prune(&conn, &config, now, true).unwrap();
+        assert_eq!((preview.decisions, preview.adjustments), (1, 2));
+        assert_eq!(conn.query_row("SELECT COUNT(*) FROM decisions", [], |r| r.get::<_, i64>(0)).unwrap(), 3);
+
+        prune(&conn, &config, now, false).unwrap();
+        assert_eq!(conn.query_row("SELECT COUNT(*) FROM decisions", [], |r| r.get::<_, i64>(0)).unwrap(), 2);
+        assert!(crate::approval::get(&conn, "d0").unwrap().is_none());
+        assert_eq!(get_tombstone(&conn, "d0").unwrap().unwrap().reason, "retention");
+        assert!(check_chain(&conn).unwrap().is_empty());
+        assert_eq!(crate::db::get_latest_decision_hash(&conn).unwrap().as_deref(), Some("h2"));
+
+        let (adjustments, total, losses): (i64, f64, i64) = conn
+            .query_row("SELECT SUM(adjustments), SUM(total), SUM(losses) FROM adjustment_rollups", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+            })
+            .unwrap();
+        assert_eq!((adjustments, losses), (2, 1));
+        assert!((total + 0.05).abs() < 1e-9);
+        assert_eq!(prune(&conn, &config, now, false).unwrap(), PruneReport::default());
+
+        // Without the tombstone, the next decision's link would dangle
+        conn.execute("DELETE FROM decision_tombstones", []).unwrap();
+        assert_eq!(check_chain(&conn).unwrap(), vec!["d1".to_string()]);
+    }
+}
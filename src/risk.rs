@@ -0,0 +1,298 @@
+//! Risk Register
+//!
+//! Collects what could go wrong with a decision into one reviewable list:
+//! the Against and Challenge arguments from its counsel, every stated
+//! falsification condition, and its critical blind spots (the ones recorded
+//! with `check_blind_spots`, or the template's own if none were). Likelihood,
+//! impact and mitigation start empty for a person to fill in with
+//! [`assess`]; regenerating a register keeps those assessments.
+//!
+//! Registers are stored in `risk_registers` keyed by decision ID, alongside
+//! the decision's provenance hash, and render as JSON or Markdown.
+
+use crate::adr::load_adr_source;
+use crate::types::Stance;
+use crate::error::{MindsError, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Allowed likelihood and impact ratings
+pub const LEVELS: [&str; 3] = ["low", "medium", "high"];
+
+/// One risk to a decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskEntry {
+    /// "R1", "R2", ... in register order
+    pub id: String,
+    /// "against", "challenge", "falsification" or "blind_spot"
+    pub source: String,
+    pub title: String,
+    pub description: String,
+    /// Thinker or template the risk came from
+    pub raised_by: String,
+    #[serde(default)]
+    pub principles: Vec<String>,
+    pub likelihood: Option<String>,
+    pub impact: Option<String>,
+    pub mitigation: Option<String>,
+}
+
+impl RiskEntry {
+    fn same_risk(&self, other: &RiskEntry) -> bool {
+        self.source == other.source && self.description == other.description
+    }
+}
+
+/// Every risk for one decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskRegister {
+    pub decision_id: String,
+    pub question: String,
+    /// Provenance hash of the decision the register was built from
+    pub content_hash: String,
+    pub generated_at: String,
+    pub risks: Vec<RiskEntry>,
+}
+
+/// Build a decision's register from its stored counsel, keeping assessments
+/// from any earlier register, and store it
+pub fn generate(conn: &Connection, decision_id: &str) -> Result<RiskRegister> {
+    let source = load_adr_source(conn, decision_id)?;
+    let response = &source.response;
+    let mut risks: Vec<RiskEntry> = Vec::new();
+    let mut push = |source: &str, title: String, description: String, raised_by: &str, principles: &[String]| {
+        risks.push(RiskEntry {
+            id: String::new(),
+            source: source.to_string(),
+            title,
+            description,
+            raised_by: raised_by.to_string(),
+            principles: principles.to_vec(),
+            likelihood: None,
+            impact: None,
+            mitigation: None,
+        });
+    };
+
+    let positions = || response.positions.iter().chain(std::iter::once(&response.challenge));
+    for p in positions() {
+        let kind = match p.stance {
+            Stance::Against => "against",
+            Stance::Challenge => "challenge",
+            _ => continue,
+        };
+        push(kind, format!("{} objects", p.thinker), p.argument.clone(), &p.thinker, &p.principles_cited);
+    }
+    for p in positions() {
+        if let Some(condition) = p.falsifiable_if.as_ref().filter(|c| !c.trim().is_empty()) {
+            push(
+                "falsification",
+                "Decision proves wrong".to_string(),
+                condition.clone(),
+                &p.thinker,
+                &p.principles_cited,
+            );
+        }
+    }
+    for spot in critical_blind_spots(conn, decision_id, &response.question)? {
+        push("blind_spot", spot.0, spot.1, &spot.2, &[]);
+    }
+
+    let previous = load(conn, decision_id)?;
+    for (i, risk) in risks.iter_mut().enumerate() {
+        risk.id = format!("R{}", i + 1);
+        if let Some(old) = previous.iter().flat_map(|r| &r.risks).find(|old| old.same_risk(risk)) {
+            risk.likelihood = old.likelihood.clone();
+            risk.impact = old.impact.clone();
+            risk.mitigation = old.mitigation.clone();
+        }
+    }
+
+    let register = RiskRegister {
+        decision_id: decision_id.to_string(),
+        question: response.question.clone(),
+        content_hash: response.provenance.content_hash.clone(),
+        generated_at: Utc::now().to_rfc3339(),
+        risks,
+    };
+    save(conn, &register)?;
+    Ok(register)
+}
+
+/// (name, check question, source) for the decision's unaddressed critical blind spots
+fn critical_blind_spots(conn: &Connection, decision_id: &str, question: &str) -> Result<Vec<(String, String, String)>> {
+    let recorded: Vec<(String, String, String)> = conn
+        .prepare(
+            "SELECT name, COALESCE(check_question, ''), COALESCE(source_template, '')
+             FROM blind_spot_checks
+             WHERE decision_id = ?1 AND severity = 'Critical' AND status != 'addressed'
+             ORDER BY name",
+        )?
+        .query_map([decision_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    let any_recorded: bool =
+        conn.query_row("SELECT COUNT(*) > 0 FROM blind_spot_checks WHERE decision_id = ?1", [decision_id], |row| row.get(0))?;
+    if any_recorded {
+        return Ok(recorded);
+    }
+    Ok(crate::mcp::check_blind_spots(question, None)
+        .blind_spots
+        .into_iter()
+        .filter(|b| b.severity == "Critical")
+        .map(|b| (b.name, b.check_question, b.source_template))
+        .collect())
+}
+
+/// The stored register for a decision, if one was generated
+pub fn load(conn: &Connection, decision_id: &str) -> Result<Option<RiskRegister>> {
+    let json: Option<String> = conn
+        .query_row("SELECT register_json FROM risk_registers WHERE decision_id = ?1", [decision_id], |row| row.get(0))
+        .optional()?;
+    json.map(|j| serde_json::from_str(&j).map_err(Into::into)).transpose()
+}
+
+fn save(conn: &Connection, register: &RiskRegister) -> Result<()> {
+    conn.execute(
+        "INSERT INTO risk_registers (decision_id, content_hash, register_json) VALUES (?1, ?2, ?3)
+         ON CONFLICT(decision_id) DO UPDATE SET
+             content_hash = excluded.content_hash,
+             register_json = excluded.register_json,
+             updated_at = CURRENT_TIMESTAMP",
+        params![register.decision_id, register.content_hash, serde_json::to_string(register)?],
+    )?;
+    Ok(())
+}
+
+/// Rate a risk and record its mitigation; unset arguments are left alone
+pub fn assess(
+    conn: &Connection,
+    decision_id: &str,
+    risk_id: &str,
+    likelihood: Option<&str>,
+    impact: Option<&str>,
+    mitigation: Option<&str>,
+) -> Result<RiskRegister> {
+    for level in [likelihood, impact].into_iter().flatten() {
+        if !LEVELS.contains(&level) {
+            return Err(MindsError::Validation(format!("Unknown rating '{}' (low, medium or high)", level)));
+        }
+    }
+    let mut register = match load(conn, decision_id)? {
+        Some(register) => register,
+        None => generate(conn, decision_id)?,
+    };
+    let risk = register
+        .risks
+        .iter_mut()
+        .find(|r| r.id.eq_ignore_ascii_case(risk_id))
+        .ok_or_else(|| MindsError::NotFound(format!("No risk {} in the register for {}", risk_id, decision_id)))?;
+    if let Some(likelihood) = likelihood {
+        risk.likelihood = Some(likelihood.to_string());
+    }
+    if let Some(impact) = impact {
+        risk.impact = Some(impact.to_string());
+    }
+    if let Some(mitigation) = mitigation {
+        risk.mitigation = Some(mitigation.to_string());
+    }
+    save(conn, &register)?;
+    Ok(register)
+}
+
+/// Render a register as a Markdown table
+pub fn render_markdown(register: &RiskRegister) -> String {
+    let cell = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+    let mut md = String::new();
+    writeln!(md, "# Risk register: {}", register.question).unwrap();
+    writeln!(md).unwrap();
+    writeln!(md, "- **Decision ID:** `{}`", register.decision_id).unwrap();
+    writeln!(md, "- **Provenance hash:** `{}`", register.content_hash).unwrap();
+    writeln!(md, "- **Generated:** {}", register.generated_at).unwrap();
+    writeln!(md).unwrap();
+    if register.risks.is_empty() {
+        writeln!(md, "_No risks were raised._").unwrap();
+        return md;
+    }
+    writeln!(md, "| ID | Source | Risk | Raised by | Likelihood | Impact | Mitigation |").unwrap();
+    writeln!(md, "|----|--------|------|-----------|------------|--------|------------|").unwrap();
+    for r in &register.risks {
+        writeln!(
+            md,
+            "| {} | {} | **{}**: {} | {} | {} | {} | {} |",
+            r.id,
+            r.source,
+            cell(&r.title),
+            cell(&r.description),
+            cell(&r.raised_by),
+            r.likelihood.as_deref().unwrap_or("?"),
+            r.impact.as_deref().unwrap_or("?"),
+            cell(r.mitigation.as_deref().unwrap_or("")),
+        )
+        .unwrap();
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CounselPosition, CounselResponse, ProvenanceInfo};
+
+    fn position(stance: Stance, thinker: &str, argument: &str, falsifiable_if: Option<&str>) -> CounselPosition {
+        CounselPosition {
+            thinker: thinker.to_string(),
+            thinker_id: thinker.to_lowercase(),
+            stance,
+            argument: argument.to_string(),
+            principles_cited: vec!["p1".to_string()],
+            confidence: 0.7,
+            raw_confidence: None,
+            falsifiable_if: falsifiable_if.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_register_collects_risks_and_keeps_assessments() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let response = CounselResponse::new(
+            "Should we rewrite the billing system from scratch?".to_string(),
+            vec![
+                position(Stance::For, "Fowler", "Strangle it", Some("Deploys stay coupled")),
+                position(Stance::Against, "Brooks", "Second-system effect", None),
+            ],
+            position(Stance::Challenge, "Taleb", "What if the rewrite never ships?", None),
+            ProvenanceInfo {
+                agent_pubkey: "pk".to_string(),
+                signature: "sig".to_string(),
+                previous_hash: None,
+                content_hash: "hash".to_string(),
+            },
+            Some("d-risk".to_string()),
+        );
+        conn.execute(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey)
+             VALUES (?1, ?2, ?3, 'hash', 'sig', 'pk')",
+            params![response.decision_id, response.question, serde_json::to_string(&response).unwrap()],
+        )
+        .unwrap();
+
+        let register = generate(&conn, &response.decision_id).unwrap();
+        let sources: Vec<&str> = register.risks.iter().map(|r| r.source.as_str()).collect();
+        assert_eq!(&sources[..3], ["against", "challenge", "falsification"]);
+        assert!(sources[3..].iter().all(|s| *s == "blind_spot") && sources.len() > 3);
+        assert!(register.risks.iter().all(|r| r.likelihood.is_none() && r.mitigation.is_none()));
+
+        assess(&conn, &response.decision_id, "r1", Some("high"), Some("medium"), Some("Ship behind a flag")).unwrap();
+        assert!(assess(&conn, &response.decision_id, "R1", Some("certain"), None, None).is_err());
+        let register = generate(&conn, &response.decision_id).unwrap();
+        assert_eq!(register.risks[0].likelihood.as_deref(), Some("high"));
+        assert_eq!(register.risks[0].mitigation.as_deref(), Some("Ship behind a flag"));
+
+        let md = render_markdown(&register);
+        assert!(md.contains("| R1 | against | **Brooks objects**: Second-system effect | Brooks | high | medium | Ship behind a flag |"));
+        assert!(md.contains("`hash`"));
+    }
+}
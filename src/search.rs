@@ -0,0 +1,745 @@
+//! Principle Search Queries
+//!
+//! `principles_fts` is tokenized with `porter unicode61 remove_diacritics 2`,
+//! so "testing", "tested" and "tests" all index as "test" and "café" matches
+//! "cafe". Questions reach it through [`fts_query`], which keeps only content
+//! words and quotes each one: user text can't inject FTS5 syntax (`NOT`,
+//! `NEAR`, `col:`, `*`, unbalanced quotes), and "what is the" no longer
+//! matches every principle.
+//!
+//! Code that scores text itself (the in-memory snapshot, keyword relevance)
+//! uses [`terms`] and [`stem`], a light suffix stripper that approximates the
+//! porter stemmer. Its stems are always prefixes of the word, so they can
+//! also be used for substring matching.
+//!
+//! Misspelled words ("microservces", "stranger fig") match nothing, so
+//! searches first run the query through a [`Vocabulary`] of the corpus's
+//! own words, which swaps each unknown word for the closest known one
+//! within a small edit distance and reports what it changed.
+//!
+//! [`hybrid`] is the one search path for the CLI and MCP: BM25 over the FTS
+//! index and, when embeddings are loaded, cosine similarity, fused by
+//! weighted reciprocal rank. Weights and a score floor come from `[search]`
+//! in config.toml and can be overridden per query:
+//!
+//! ```toml
+//! [search]
+//! semantic_weight = 0.8
+//! bm25_weight = 0.2
+//! min_score = 0.3
+//! rerank = true
+//! rerank_model_dir = "/opt/models/ms-marco-MiniLM-L-6-v2"
+//! exact_similarity = false
+//! ```
+//!
+//! Embedding similarity first scores int8-quantized vectors, then rescores
+//! the best few candidates in f32; `exact_similarity = true` scores every
+//! principle in f32 instead.
+//!
+//! Reranking re-scores the top [`RERANK_CANDIDATES`] results, in search and
+//! before counsel picks positions. Builds with the `rerank` feature use a
+//! cross-encoder from `rerank_model_dir`; otherwise (or without a model)
+//! the stage falls back to [`TermOverlapReranker`]. Its time is reported
+//! apart from the rest of the search ([`RerankReport`]).
+
+use crate::embeddings::{HybridMatch, SemanticEngine};
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+/// Reciprocal rank fusion constant: a list's rank r contributes 1 / (RRF_K + r)
+const RRF_K: f32 = 60.0;
+
+/// Results the reranking stage re-scores
+pub const RERANK_CANDIDATES: usize = 20;
+
+/// Most keywords taken from one query
+pub const MAX_KEYWORDS: usize = 15;
+
+/// Words too common to say anything about a principle
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "been", "before",
+    "but", "by", "can", "could", "did", "do", "does", "for", "from", "get", "has", "have", "how", "i",
+    "if", "in", "into", "is", "it", "its", "just", "me", "my", "not", "now", "of", "on", "or", "our",
+    "out", "over", "should", "so", "than", "that", "the", "their", "them", "then", "there", "these",
+    "they", "this", "to", "too", "up", "us", "use", "was", "we", "were", "what", "when", "where",
+    "which", "while", "who", "why", "will", "with", "would", "you", "your",
+];
+
+/// Lowercase `c` and strip a Latin diacritic, as unicode61 does
+fn fold(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ñ' => 'n',
+        'ç' => 'c',
+        _ => c,
+    }
+}
+
+/// Lowercased, diacritic-free tokens of `text`, in order
+pub fn tokens(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase().chars().map(fold).collect())
+}
+
+pub fn is_stopword(word: &str) -> bool {
+    STOPWORDS.contains(&word)
+}
+
+/// Content words of a query: tokens of 3+ characters that aren't
+/// stopwords, first occurrence only
+pub fn keywords(query: &str) -> Vec<String> {
+    let mut keywords: Vec<String> = Vec::new();
+    for token in tokens(query) {
+        if token.chars().count() > 2 && !is_stopword(&token) && !keywords.contains(&token) {
+            keywords.push(token);
+        }
+        if keywords.len() == MAX_KEYWORDS {
+            break;
+        }
+    }
+    keywords
+}
+
+/// FTS5 MATCH expression for a user query: each keyword as a quoted string,
+/// OR-ed together. `None` when the query has no keywords.
+pub fn fts_query(query: &str) -> Option<String> {
+    let keywords = keywords(query);
+    if keywords.is_empty() {
+        return None;
+    }
+    let quoted: Vec<String> = keywords.iter().map(|k| format!("\"{}\"", k.replace('"', "\"\""))).collect();
+    Some(quoted.join(" OR "))
+}
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u')
+}
+
+/// Strip common inflectional suffixes (plurals, -ed, -ing, -ly, -ment,
+/// -ation, final e). The result is a prefix of `word`; words of four
+/// characters or fewer, and non-ASCII words, are left alone.
+pub fn stem(word: &str) -> String {
+    if word.len() <= 4 || !word.is_ascii() {
+        return word.to_string();
+    }
+    let mut w = word;
+    // Plurals: caresses → caress, ponies → poni, systems → system
+    if let Some(base) = w.strip_suffix("sses") {
+        w = &word[..base.len() + 2];
+    } else if let Some(base) = w.strip_suffix("ies") {
+        w = &word[..base.len() + 1];
+    } else if w.ends_with('s') && !w.ends_with("ss") && !w.ends_with("us") && !w.ends_with("is") {
+        w = &w[..w.len() - 1];
+    }
+    // -ed / -ing, when what's left still has a vowel
+    for suffix in ["ing", "ed"] {
+        if let Some(base) = w.strip_suffix(suffix) {
+            if base.len() >= 3 && base.bytes().any(is_vowel) {
+                w = base;
+                // running → run, but not fall → fal or miss → mis
+                let b = w.as_bytes();
+                let n = b.len();
+                if n >= 4 && b[n - 1] == b[n - 2] && !is_vowel(b[n - 1]) && !matches!(b[n - 1], b'l' | b's' | b'z') {
+                    w = &w[..n - 1];
+                }
+            }
+            break;
+        }
+    }
+    for suffix in ["ations", "ation", "ments", "ment", "ly"] {
+        if let Some(base) = w.strip_suffix(suffix) {
+            if base.len() >= 4 {
+                w = base;
+            }
+            break;
+        }
+    }
+    // optimize / optimization → optimiz
+    if w.len() > 4 && w.ends_with('e') {
+        w = &w[..w.len() - 1];
+    }
+    w.to_string()
+}
+
+/// Stems of a text's tokens, in order (stopwords included)
+pub fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    tokens(text).map(|t| stem(&t))
+}
+
+/// A query word replaced by spelling correction
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Correction {
+    pub from: String,
+    pub to: String,
+}
+
+/// A query after spelling correction
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CorrectedQuery {
+    pub original: String,
+    /// What is actually searched (the original if nothing was corrected)
+    pub query: String,
+    pub corrections: Vec<Correction>,
+}
+
+impl CorrectedQuery {
+    pub fn is_corrected(&self) -> bool {
+        !self.corrections.is_empty()
+    }
+}
+
+/// Words of the corpus with their frequencies, for spelling correction
+#[derive(Debug, Default)]
+pub struct Vocabulary {
+    words: HashMap<String, u32>,
+    stems: HashMap<String, u32>,
+}
+
+impl Vocabulary {
+    pub fn from_texts<'a>(texts: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut vocabulary = Vocabulary::default();
+        for text in texts {
+            for token in tokens(text).filter(|t| t.len() > 2 && !is_stopword(t)) {
+                *vocabulary.stems.entry(stem(&token)).or_insert(0) += 1;
+                *vocabulary.words.entry(token).or_insert(0) += 1;
+            }
+        }
+        vocabulary
+    }
+
+    /// Words of every principle's name, description and application rule
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let texts: Vec<String> = conn
+            .prepare("SELECT name || ' ' || description || ' ' || COALESCE(application_rule, '') FROM principles")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(Self::from_texts(texts.iter().map(String::as_str)))
+    }
+
+    fn knows(&self, word: &str) -> bool {
+        self.words.contains_key(word) || self.stems.contains_key(&stem(word))
+    }
+
+    /// The closest known word to an unknown one: at most one edit away for
+    /// words under eight characters, two for longer ones; ties go to the
+    /// more frequent word
+    pub fn suggest(&self, word: &str) -> Option<&str> {
+        let word = word.to_lowercase();
+        if word.chars().count() < 4 || is_stopword(&word) || word.chars().any(|c| c.is_ascii_digit()) || self.knows(&word) {
+            return None;
+        }
+        let max_distance = if word.chars().count() < 8 { 1 } else { 2 };
+        self.words
+            .iter()
+            .filter(|(known, _)| known.len().abs_diff(word.len()) <= max_distance)
+            .map(|(known, count)| (strsim::damerau_levenshtein(&word, known), *count, known.as_str()))
+            .filter(|(distance, _, _)| *distance <= max_distance)
+            .min_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)))
+            .map(|(_, _, known)| known)
+    }
+
+    /// `query` with each unknown word swapped for its suggestion
+    pub fn correct(&self, query: &str) -> CorrectedQuery {
+        let mut corrected = String::with_capacity(query.len());
+        let mut corrections = Vec::new();
+        let mut word = String::new();
+        let mut flush = |word: &mut String, out: &mut String| {
+            match self.suggest(word) {
+                Some(to) => {
+                    corrections.push(Correction { from: word.clone(), to: to.to_string() });
+                    out.push_str(to);
+                }
+                None => out.push_str(word),
+            }
+            word.clear();
+        };
+        for c in query.chars() {
+            if c.is_alphanumeric() {
+                word.push(c);
+            } else {
+                flush(&mut word, &mut corrected);
+                corrected.push(c);
+            }
+        }
+        flush(&mut word, &mut corrected);
+        CorrectedQuery { original: query.to_string(), query: corrected, corrections }
+    }
+}
+
+/// The `[search]` table of config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Weight of the embedding-similarity ranking
+    pub semantic_weight: f32,
+    /// Weight of the BM25 keyword ranking
+    pub bm25_weight: f32,
+    /// Fused scores below this are dropped; 1.0 means ranked first by
+    /// every available signal
+    pub min_score: f32,
+    /// Reorder the top results with a [`Reranker`]
+    pub rerank: bool,
+    /// Cross-encoder export (model.onnx + tokenizer.json) to rerank with;
+    /// needs the `rerank` feature, otherwise term overlap is used
+    pub rerank_model_dir: Option<PathBuf>,
+    /// Score every embedding in f32, skipping the int8 first pass
+    pub exact_similarity: bool,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            semantic_weight: 0.6,
+            bm25_weight: 0.4,
+            min_score: 0.0,
+            rerank: false,
+            rerank_model_dir: None,
+            exact_similarity: false,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// Read `[search]` from the config.toml beside the database; a missing file or table gives the defaults
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        let config: SearchConfig = crate::config::section(conn, "search")?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.semantic_weight < 0.0 || self.bm25_weight < 0.0 || self.semantic_weight + self.bm25_weight <= 0.0 {
+            return Err(MindsError::Validation("search weights must be non-negative and not both zero".to_string()));
+        }
+        if !(0.0..=1.0).contains(&self.min_score) {
+            return Err(MindsError::Validation("min_score must be between 0 and 1".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Override the weights with a "semantic,bm25" pair such as "0.8,0.2"
+    pub fn with_weights(mut self, weights: &str) -> Result<Self> {
+        let invalid = || MindsError::Validation("weights must be two numbers, e.g. 0.8,0.2".to_string());
+        let parsed: Vec<f32> =
+            weights.split(',').map(|w| w.trim().parse::<f32>()).collect::<Result<_, _>>().map_err(|_| invalid())?;
+        let [semantic, bm25] = parsed[..] else {
+            return Err(invalid());
+        };
+        self.semantic_weight = semantic;
+        self.bm25_weight = bm25;
+        self.validate()?;
+        Ok(self)
+    }
+}
+
+/// Scores candidate texts against a query, higher is more relevant
+pub trait Reranker: Send + Sync {
+    fn name(&self) -> &str;
+    fn score(&self, query: &str, candidates: &[&str]) -> Result<Vec<f32>>;
+}
+
+/// What the reranking stage did
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RerankReport {
+    pub reranker: String,
+    pub candidates: usize,
+    pub latency_ms: f64,
+}
+
+/// Indices of `texts` from best to worst reranker score (ties keep their
+/// order), and the stage's report
+pub fn rerank_order(reranker: &dyn Reranker, query: &str, texts: &[&str]) -> Result<(Vec<usize>, RerankReport)> {
+    let started = Instant::now();
+    let scores = reranker.score(query, texts)?;
+    if scores.len() != texts.len() {
+        return Err(MindsError::Embedding(format!(
+            "reranker {} returned {} scores for {} candidates",
+            reranker.name(),
+            scores.len(),
+            texts.len()
+        )));
+    }
+    let mut order: Vec<usize> = (0..texts.len()).collect();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+    let report = RerankReport {
+        reranker: reranker.name().to_string(),
+        candidates: texts.len(),
+        latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+    };
+    Ok((order, report))
+}
+
+/// The reranker for a config, or `None` if it doesn't ask to rerank.
+/// Loaded once per model directory and shared by every caller.
+pub fn shared_reranker(config: &SearchConfig) -> Option<Arc<dyn Reranker>> {
+    type Cache = Mutex<HashMap<Option<PathBuf>, Arc<dyn Reranker>>>;
+    static RERANKERS: OnceLock<Cache> = OnceLock::new();
+    if !config.rerank {
+        return None;
+    }
+    let mut cache = RERANKERS.get_or_init(Cache::default).lock().unwrap_or_else(|e| e.into_inner());
+    let reranker = cache
+        .entry(config.rerank_model_dir.clone())
+        .or_insert_with(|| load_reranker(config.rerank_model_dir.as_deref()));
+    Some(reranker.clone())
+}
+
+#[cfg(feature = "rerank")]
+fn load_reranker(model_dir: Option<&Path>) -> Arc<dyn Reranker> {
+    if let Some(dir) = model_dir {
+        match crate::embeddings::CrossEncoderReranker::new(dir) {
+            Ok(reranker) => return Arc::new(reranker),
+            Err(e) => tracing::warn!("Cross-encoder unavailable, reranking by term overlap: {}", e),
+        }
+    }
+    Arc::new(TermOverlapReranker)
+}
+
+#[cfg(not(feature = "rerank"))]
+fn load_reranker(model_dir: Option<&Path>) -> Arc<dyn Reranker> {
+    if model_dir.is_some() {
+        tracing::warn!("rerank_model_dir needs a build with the `rerank` feature; reranking by term overlap");
+    }
+    Arc::new(TermOverlapReranker)
+}
+
+/// Share of the query's keyword stems each candidate contains. No model,
+/// so it is always available; it favors principles that address the whole
+/// question over ones that share a single rare word with it.
+pub struct TermOverlapReranker;
+
+impl Reranker for TermOverlapReranker {
+    fn name(&self) -> &str {
+        "term-overlap"
+    }
+
+    fn score(&self, query: &str, candidates: &[&str]) -> Result<Vec<f32>> {
+        let wanted: Vec<String> = keywords(query).iter().map(|k| stem(k)).collect();
+        Ok(candidates
+            .iter()
+            .map(|text| {
+                let have: std::collections::HashSet<String> = terms(text).collect();
+                let hits = wanted.iter().filter(|w| have.contains(*w)).count();
+                hits as f32 / wanted.len().max(1) as f32
+            })
+            .collect())
+    }
+}
+
+/// BM25 matches for a query, best first, as (principle_id, bm25) where
+/// bm25 is FTS5's score (more negative is better)
+pub fn bm25(conn: &Connection, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
+    let Some(fts_query) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    let mut stmt = conn.prepare(
+        "SELECT p.id, bm25(principles_fts) AS score
+         FROM principles_fts JOIN principles p ON principles_fts.rowid = p.rowid
+         WHERE principles_fts MATCH ?1
+         ORDER BY score
+         LIMIT ?2",
+    )?;
+    let matches = stmt
+        .query_map(params![fts_query, limit as i64], |row| Ok((row.get(0)?, row.get::<_, f64>(1)? as f32)))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(matches)
+}
+
+/// Weighted reciprocal rank fusion of two best-first lists, normalized so
+/// that ranking first in every non-empty list scores 1.0
+pub fn fuse(semantic: &[(String, f32)], bm25: &[(String, f32)], config: &SearchConfig) -> Vec<HybridMatch> {
+    let top = 1.0 / (RRF_K + 1.0);
+    let weight_sum = if semantic.is_empty() { 0.0 } else { config.semantic_weight }
+        + if bm25.is_empty() { 0.0 } else { config.bm25_weight };
+    if weight_sum <= 0.0 {
+        return Vec::new();
+    }
+    let mut fused: Vec<HybridMatch> = Vec::new();
+    let lists = [(semantic, config.semantic_weight, true), (bm25, config.bm25_weight, false)];
+    for (list, weight, is_semantic) in lists {
+        for (rank, (id, raw)) in list.iter().enumerate() {
+            let index = match fused.iter().position(|m| &m.principle_id == id) {
+                Some(index) => index,
+                None => {
+                    fused.push(HybridMatch {
+                        principle_id: id.clone(),
+                        semantic_score: 0.0,
+                        bm25_score: 0.0,
+                        combined_score: 0.0,
+                    });
+                    fused.len() - 1
+                }
+            };
+            let m = &mut fused[index];
+            if is_semantic {
+                m.semantic_score = *raw;
+            } else {
+                m.bm25_score = *raw;
+            }
+            m.combined_score += weight / (RRF_K + rank as f32 + 1.0) / (top * weight_sum);
+        }
+    }
+    for m in &mut fused {
+        m.combined_score = m.combined_score.min(1.0);
+    }
+    fused.retain(|m| m.combined_score >= config.min_score);
+    fused.sort_by(|a, b| b.combined_score.total_cmp(&a.combined_score).then(a.principle_id.cmp(&b.principle_id)));
+    fused
+}
+
+/// Hybrid search results, with the reranking stage's report if it ran
+#[derive(Debug, Clone, Serialize)]
+pub struct HybridResults {
+    pub matches: Vec<HybridMatch>,
+    pub rerank: Option<RerankReport>,
+}
+
+/// Hybrid search: BM25 plus, with an engine that has embeddings loaded,
+/// semantic similarity; the top results reranked when the config asks
+pub fn hybrid(
+    conn: &Connection,
+    semantic: Option<&mut SemanticEngine>,
+    query: &str,
+    top_k: usize,
+    config: &SearchConfig,
+) -> Result<HybridResults> {
+    let candidates = (top_k * 2).max(if config.rerank { RERANK_CANDIDATES } else { 0 });
+    let semantic: Vec<(String, f32)> = match semantic {
+        Some(engine) if engine.embedding_count() > 0 => {
+            engine.set_exact(config.exact_similarity);
+            engine.knn(conn, query, candidates)?.into_iter().map(|m| (m.principle_id, m.similarity)).collect()
+        }
+        _ => Vec::new(),
+    };
+    let mut matches = fuse(&semantic, &bm25(conn, query, candidates)?, config);
+    let mut report = None;
+    if let Some(reranker) = shared_reranker(config) {
+        matches.truncate(RERANK_CANDIDATES);
+        let mut texts = Vec::with_capacity(matches.len());
+        for m in &matches {
+            let text: String = conn.query_row(
+                "SELECT name || '. ' || description FROM principles WHERE id = ?1",
+                [&m.principle_id],
+                |row| row.get(0),
+            )?;
+            texts.push(text);
+        }
+        let (order, stage) = rerank_order(reranker.as_ref(), query, &texts.iter().map(String::as_str).collect::<Vec<_>>())?;
+        matches = order.into_iter().map(|i| matches[i].clone()).collect();
+        report = Some(stage);
+    }
+    matches.truncate(top_k);
+    Ok(HybridResults { matches, rerank: report })
+}
+
+/// One principle found by [`principles`]
+#[derive(Debug, Clone, Serialize)]
+pub struct PrincipleHit {
+    pub id: String,
+    pub name: String,
+    pub thinker_id: String,
+    pub thinker: String,
+    pub description: String,
+    /// Learned confidence; left out when the caller didn't ask for it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Fused score (0-1), or the keyword fallback's BM25 score
+    pub score: f64,
+}
+
+/// Principles for a query, best first, with the rerank stage's report
+pub struct PrincipleResults {
+    pub hits: Vec<PrincipleHit>,
+    pub rerank: Option<RerankReport>,
+}
+
+/// Standalone principle search: [`hybrid`] ranking, optionally narrowed to a
+/// domain (the thinker's domain or one of the principle's tags)
+pub fn principles(
+    conn: &Connection,
+    semantic: Option<&mut SemanticEngine>,
+    query: &str,
+    domain: Option<&str>,
+    top_k: usize,
+    config: &SearchConfig,
+) -> Result<PrincipleResults> {
+    let domain = domain.map(str::trim).filter(|d| !d.is_empty()).map(str::to_lowercase);
+    // A domain filter drops candidates, so rank more of them first
+    let candidates = if domain.is_some() { top_k * 10 } else { top_k };
+    let hybrid = hybrid(conn, semantic, query, candidates, config)?;
+    let mut ranked: Vec<(String, f64)> =
+        hybrid.matches.iter().map(|m| (m.principle_id.clone(), m.combined_score as f64)).collect();
+    if ranked.is_empty() && config.min_score == 0.0 {
+        // Substring fallback for text FTS can't tokenize
+        ranked = crate::db::search_principles(conn, query, candidates)?
+            .into_iter()
+            .map(|p| (p.id, p.relevance_score))
+            .collect();
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT p.name, p.thinker_id, COALESCE(t.name, p.thinker_id), p.description, p.learned_confidence,
+                COALESCE(t.domain, ''), COALESCE(p.domain_tags, '[]')
+         FROM principles p LEFT JOIN thinkers t ON t.id = p.thinker_id
+         WHERE p.id = ?1",
+    )?;
+    let mut hits = Vec::new();
+    for (id, score) in ranked {
+        let row = stmt
+            .query_row([&id], |row| {
+                let hit = PrincipleHit {
+                    id: id.clone(),
+                    name: row.get(0)?,
+                    thinker_id: row.get(1)?,
+                    thinker: row.get(2)?,
+                    description: row.get(3)?,
+                    confidence: row.get(4)?,
+                    score,
+                };
+                Ok((hit, row.get::<_, String>(5)?, row.get::<_, String>(6)?))
+            })
+            .optional()?;
+        let Some((hit, thinker_domain, tags)) = row else { continue };
+        if let Some(domain) = &domain {
+            let tags: Vec<String> = serde_json::from_str(&tags).unwrap_or_default();
+            if thinker_domain.to_lowercase() != *domain && !tags.iter().any(|t| t.to_lowercase() == *domain) {
+                continue;
+            }
+        }
+        hits.push(hit);
+        if hits.len() == top_k {
+            break;
+        }
+    }
+    Ok(PrincipleResults { hits, rerank: hybrid.rerank })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    #[test]
+    fn test_stems_are_prefixes_and_group_inflections() {
+        let stems = |words: &[&str]| words.iter().map(|w| stem(w)).collect::<Vec<_>>();
+        assert_eq!(stems(&["tests", "testing", "tested"]), ["test", "test", "test"]);
+        assert_eq!(stems(&["optimize", "optimization", "optimizing"]), ["optimiz", "optimiz", "optimiz"]);
+        assert_eq!(stems(&["running", "focused", "deployment", "microservices"]), ["run", "focus", "deploy", "microservic"]);
+        // Not over-stripped
+        assert_eq!(stems(&["process", "status", "analysis", "falling", "need"]), ["process", "status", "analysis", "fall", "need"]);
+        for word in ["caresses", "ponies", "quickly", "database", "strangler"] {
+            assert!(word.starts_with(&stem(word)), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_tricky_queries_are_escaped_and_stemmed_by_fts() {
+        assert_eq!(fts_query("What is the best way?"), Some("\"best\" OR \"way\"".to_string()));
+        assert_eq!(fts_query("what is the"), None);
+        assert_eq!(fts_query("\"quoted\" NOT col:name* NEAR(a b) -x"), Some("\"quoted\" OR \"col\" OR \"name\" OR \"near\"".to_string()));
+        assert_eq!(keywords("Café CAFE cafe"), vec!["cafe"]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let conn = db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('beck', 'Kent Beck', 'software');
+             INSERT INTO principles (id, thinker_id, name, description)
+                 VALUES ('tdd', 'beck', 'Test-Driven Development', 'Write a failing test before the code'),
+                        ('cafe', 'beck', 'Café Rule', 'Talk it over where the team gathers');",
+        )
+        .unwrap();
+        let ids = |query: &str| -> Vec<String> {
+            db::search_principles(&conn, query, 10).unwrap().into_iter().map(|p| p.id).collect()
+        };
+        // Porter: "testing" finds "test"; unicode61 folds the accent
+        assert_eq!(ids("Should we be testing first?"), ["tdd"]);
+        assert_eq!(ids("cafe"), ["cafe"]);
+        // FTS syntax in user input is just text; stopwords don't match everything
+        assert_eq!(ids("tests\" OR \"*"), ["tdd"]);
+        assert!(ids("NOT the a").is_empty());
+        assert!(ids("where is the").is_empty());
+    }
+
+    #[test]
+    fn test_misspelled_words_are_corrected_against_the_corpus() {
+        let vocabulary = Vocabulary::from_texts([
+            "Strangler Fig: replace a legacy system piece by piece",
+            "Microservices: split services along team boundaries",
+            "Monolith First: start with a monolith before microservices",
+        ]);
+        let fixed = vocabulary.correct("Microservces or a stranger fig?");
+        assert_eq!(fixed.query, "microservices or a strangler fig?");
+        assert_eq!(
+            fixed.corrections,
+            vec![
+                Correction { from: "Microservces".to_string(), to: "microservices".to_string() },
+                Correction { from: "stranger".to_string(), to: "strangler".to_string() },
+            ]
+        );
+        // Known words, inflections of known words, stopwords and short words stay
+        let untouched = vocabulary.correct("Should we split the monoliths? k8s");
+        assert!(!untouched.is_corrected());
+        assert_eq!(untouched.query, "Should we split the monoliths? k8s");
+        assert_eq!(vocabulary.suggest("pizza"), None);
+    }
+
+    #[test]
+    fn test_fusion_weights_floor_and_rerank() {
+        let list = |ids: &[&str]| ids.iter().map(|id| (id.to_string(), 0.5)).collect::<Vec<_>>();
+        let semantic = list(&["a", "b"]);
+        let bm25 = list(&["b", "a", "c"]);
+
+        let ranked = |config: &SearchConfig| fuse(&semantic, &bm25, config).into_iter().map(|m| m.principle_id).collect::<Vec<_>>();
+        let semantic_heavy = SearchConfig::default().with_weights("0.9,0.1").unwrap();
+        assert_eq!(ranked(&semantic_heavy), ["a", "b", "c"]);
+        let keyword_heavy = SearchConfig::default().with_weights("0.1, 0.9").unwrap();
+        assert_eq!(ranked(&keyword_heavy), ["b", "a", "c"]);
+        // Only BM25 available: its first hit scores 1.0
+        assert!((fuse(&[], &bm25, &semantic_heavy)[0].combined_score - 1.0).abs() < 1e-6);
+        let floor = SearchConfig { min_score: 0.9, ..keyword_heavy };
+        assert_eq!(ranked(&floor), ["b", "a"]);
+
+        assert!(SearchConfig::default().with_weights("0.8").is_err());
+        assert!(SearchConfig::default().with_weights("0,0").is_err());
+        let config: SearchConfig = toml::from_str("bm25_weight = 0.5\nrerank = true\n").unwrap();
+        assert_eq!((config.semantic_weight, config.bm25_weight, config.rerank), (0.6, 0.5, true));
+
+        let scores = TermOverlapReranker
+            .score("split the monolith into services", &["Monolith First: services later", "Conway: split teams"])
+            .unwrap();
+        assert_eq!(scores, [2.0 / 3.0, 1.0 / 3.0]);
+    }
+
+    #[test]
+    fn test_principle_search_filters_by_domain() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('fowler', 'Martin Fowler', 'software'),
+                                                        ('taleb', 'Nassim Taleb', 'risk');
+             INSERT INTO principles (id, thinker_id, name, description, domain_tags)
+                 VALUES ('strangler', 'fowler', 'Strangler Fig', 'Replace a legacy system incrementally', '[]'),
+                        ('barbell', 'taleb', 'Barbell Strategy', 'Pair a safe legacy system with small risky bets', '[\"Finance\"]');",
+        )
+        .unwrap();
+        let search = |domain| principles(&conn, None, "legacy system", domain, 5, &SearchConfig::default()).unwrap().hits;
+
+        let all = search(None);
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|h| h.thinker == "Martin Fowler" && h.id == "strangler"));
+        let ids = |hits: Vec<PrincipleHit>| hits.into_iter().map(|h| h.id).collect::<Vec<_>>();
+        assert_eq!(ids(search(Some("Software"))), ["strangler"]);
+        assert_eq!(ids(search(Some("finance"))), ["barbell"]);
+        assert!(search(Some("biology")).is_empty());
+    }
+}
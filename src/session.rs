@@ -0,0 +1,151 @@
+//! REPL Sessions
+//!
+//! Successive questions in one REPL or TUI sitting usually share a
+//! background: the same domain, team and constraints. A session carries
+//! that context from question to question, remembers the decisions made
+//! so far, and stamps its id on each one (`decisions.session_id`) so a
+//! sitting can be reviewed later.
+//!
+//! In the REPL:
+//!
+//! ```text
+//! /context show
+//! /context set team_size 3
+//! /context set constraint no downtime
+//! /context clear
+//! ```
+
+use crate::types::{CounselContext, CounselRequest, CounselResponse};
+use crate::error::{MindsError, Result};
+
+/// Context fields `/context set` understands
+pub const KEYS: [&str; 6] = ["domain", "constraint", "notes", "team_size", "deadline_days", "stack"];
+
+/// Context shared by the questions of one sitting
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: String,
+    pub context: CounselContext,
+    /// (decision id, question) for each answer so far, oldest first
+    pub decisions: Vec<(String, String)>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session {
+            id: format!("session-{}", uuid::Uuid::new_v4().simple()),
+            context: CounselContext::default(),
+            decisions: Vec::new(),
+        }
+    }
+
+    /// A counsel request for `question` that inherits the session context
+    pub fn request(&self, question: &str) -> CounselRequest {
+        CounselRequest {
+            question: question.to_string(),
+            context: CounselContext { session_id: Some(self.id.clone()), ..self.context.clone() },
+            decision_id: None,
+        }
+    }
+
+    /// Remember a decision made in this session
+    pub fn record(&mut self, response: &CounselResponse) {
+        self.decisions.push((response.decision_id.clone(), response.question.clone()));
+    }
+
+    /// The most recent decision, for `/followup`
+    pub fn last_decision(&self) -> Option<&str> {
+        self.decisions.last().map(|(id, _)| id.as_str())
+    }
+
+    /// Set one context field; `constraint` adds to the list, `stack` takes a comma-separated list
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(MindsError::Validation(format!("/context set {} needs a value", key)));
+        }
+        let number = || {
+            value.parse::<u32>().map_err(|_| MindsError::Validation(format!("{} expects a number, got {}", key, value)))
+        };
+        match key {
+            "domain" => self.context.domain = Some(value.to_string()),
+            "constraint" => self.context.constraints.push(value.to_string()),
+            "notes" => self.context.notes = Some(value.to_string()),
+            "team_size" => self.context.team_size = Some(number()?),
+            "deadline_days" => self.context.deadline_days = Some(number()?),
+            "stack" => self.context.stack = value.split(',').map(|t| t.trim().to_string()).collect(),
+            _ => {
+                return Err(MindsError::Validation(format!(
+                    "Unknown context field {:?} (expected one of: {})",
+                    key,
+                    KEYS.join(", ")
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget the context and prior decisions; the session id stays
+    pub fn clear(&mut self) {
+        self.context = CounselContext::default();
+        self.decisions.clear();
+    }
+
+    /// Lines for `/context show`
+    pub fn describe(&self) -> Vec<String> {
+        let context = &self.context;
+        let mut lines = vec![format!("Session: {}", self.id)];
+        let mut field = |name: &str, value: Option<String>| {
+            lines.push(format!("  {:14} {}", name, value.unwrap_or_else(|| "-".to_string())));
+        };
+        field("domain", context.domain.clone());
+        field("constraints", (!context.constraints.is_empty()).then(|| context.constraints.join("; ")));
+        field("notes", context.notes.clone());
+        field("team_size", context.team_size.map(|n| n.to_string()));
+        field("deadline_days", context.deadline_days.map(|n| n.to_string()));
+        field("stack", (!context.stack.is_empty()).then(|| context.stack.join(", ")));
+        lines.push(format!("Prior decisions: {}", self.decisions.len()));
+        for (id, question) in &self.decisions {
+            lines.push(format!("  {}  {}", id, question));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_context_is_inherited_and_stored_on_decisions() {
+        let fixture = crate::testing::fixture().unwrap();
+        let engine = fixture.counsel_engine();
+        let mut session = Session::new();
+        session.set("team_size", "3").unwrap();
+        session.set("constraint", "no downtime").unwrap();
+        session.set("stack", "rust, postgres").unwrap();
+        assert!(session.set("team_size", "three").is_err());
+        assert!(session.set("budget", "10k").is_err());
+
+        let request = session.request(crate::testing::SAMPLE_QUESTION);
+        assert_eq!((request.context.team_size, request.context.stack.len()), (Some(3), 2));
+        let response = engine.counsel(&request).unwrap();
+        session.record(&response);
+        assert_eq!(session.last_decision(), Some(response.decision_id.as_str()));
+        let stored: String = fixture
+            .conn
+            .query_row("SELECT session_id FROM decisions WHERE id = ?1", [&response.decision_id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, session.id);
+
+        let id = session.id.clone();
+        session.clear();
+        assert_eq!((session.id.as_str(), session.context.team_size, session.last_decision()), (id.as_str(), None, None));
+    }
+}
@@ -0,0 +1,336 @@
+//! In-Memory Corpus Snapshot
+//!
+//! Counsel reads the corpus dozens of times per question: keyword and
+//! domain searches, thinker names, contextual arms, reliability, the active
+//! calibration. For read-heavy deployments (`100minds serve --snapshot`)
+//! all of it is loaded once into a [`CorpusSnapshot`], and an engine built
+//! with [`CounselEngine::with_snapshot`](crate::counsel::CounselEngine::with_snapshot)
+//! only touches SQLite to write the decision record.
+//!
+//! The snapshot is immutable; [`SharedSnapshot::refresh`] swaps in a new one
+//! when the server is told the corpus or the learned state changed.
+
+use crate::calibration::{self, Calibrator};
+use crate::db::{self, PrincipleMatch};
+use crate::search;
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// BM25 parameters (SQLite FTS5 uses the same defaults)
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+/// A principle with what search and synthesis need
+#[derive(Debug, Clone)]
+pub struct SnapshotPrinciple {
+    pub principle: PrincipleMatch,
+    pub domain_tags: Vec<String>,
+    pub application_rule: String,
+    /// Term frequencies over name, description and application rule
+    terms: HashMap<String, u32>,
+    length: u32,
+}
+
+#[derive(Debug)]
+pub struct CorpusSnapshot {
+    principles: Vec<SnapshotPrinciple>,
+    by_id: HashMap<String, usize>,
+    thinker_names: HashMap<String, String>,
+    reliability: HashMap<String, f64>,
+    /// principle → (domain, alpha, beta, sample_count) for each domain
+    arms: HashMap<String, Vec<(String, f64, f64, i64)>>,
+    /// Contextual samples per principle across domains
+    citations: HashMap<String, i64>,
+    calibrator: Option<Calibrator>,
+    doc_freq: HashMap<String, usize>,
+    avg_length: f64,
+    pub loaded_at: DateTime<Utc>,
+}
+
+impl CorpusSnapshot {
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let mut stmt = conn.prepare(
+            "SELECT id, thinker_id, name, description, learned_confidence,
+                    COALESCE(domain_tags, '[]'), COALESCE(application_rule, '')
+             FROM principles ORDER BY id",
+        )?;
+        let principles = stmt
+            .query_map([], |row| {
+                let principle = PrincipleMatch {
+                    id: row.get(0)?,
+                    thinker_id: row.get(1)?,
+                    name: row.get(2)?,
+                    description: row.get(3)?,
+                    confidence: row.get(4)?,
+                    relevance_score: 0.0,
+                };
+                let tags: String = row.get(5)?;
+                Ok((principle, tags, row.get::<_, String>(6)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .map(|(principle, tags, application_rule)| {
+                let mut terms = HashMap::new();
+                let mut length = 0;
+                let text = format!("{} {} {}", principle.name, principle.description, application_rule);
+                for token in search::terms(&text) {
+                    *terms.entry(token).or_insert(0) += 1;
+                    length += 1;
+                }
+                SnapshotPrinciple {
+                    domain_tags: serde_json::from_str(&tags).unwrap_or_default(),
+                    principle,
+                    application_rule,
+                    terms,
+                    length,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut doc_freq: HashMap<String, usize> = HashMap::new();
+        for p in &principles {
+            for term in p.terms.keys() {
+                *doc_freq.entry(term.clone()).or_insert(0) += 1;
+            }
+        }
+        let avg_length =
+            principles.iter().map(|p| p.length as f64).sum::<f64>() / principles.len().max(1) as f64;
+
+        let thinker_names = conn
+            .prepare("SELECT id, name FROM thinkers")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<HashMap<String, String>>>()?;
+        let reliability = conn
+            .prepare("SELECT thinker_id, successes, failures FROM thinker_reliability")?
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, db::reliability_factor(row.get(1)?, row.get(2)?)))
+            })?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+        let mut arms: HashMap<String, Vec<(String, f64, f64, i64)>> = HashMap::new();
+        let mut citations: HashMap<String, i64> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT principle_id, domain, alpha, beta, sample_count FROM contextual_arms")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+        })?;
+        for row in rows {
+            let (principle_id, domain, alpha, beta, samples): (String, String, f64, f64, i64) = row?;
+            *citations.entry(principle_id.clone()).or_insert(0) += samples;
+            arms.entry(principle_id).or_default().push((domain, alpha, beta, samples));
+        }
+
+        Ok(CorpusSnapshot {
+            by_id: principles.iter().enumerate().map(|(i, p)| (p.principle.id.clone(), i)).collect(),
+            principles,
+            thinker_names,
+            reliability,
+            arms,
+            citations,
+            calibrator: calibration::load_active_calibrator(conn)?,
+            doc_freq,
+            avg_length,
+            loaded_at: Utc::now(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.principles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.principles.is_empty()
+    }
+
+    /// In-memory stand-in for [`db::search_principles`]: BM25 over the
+    /// same keywords, stemmed, falling back to an in-order substring match
+    pub fn search(&self, query: &str, limit: usize) -> Vec<PrincipleMatch> {
+        let keywords = search::keywords(query);
+        if keywords.is_empty() {
+            return Vec::new();
+        }
+        let terms: Vec<String> = keywords.iter().map(|k| search::stem(k)).collect();
+
+        let n = self.principles.len() as f64;
+        let mut scored: Vec<(f64, &SnapshotPrinciple)> = self
+            .principles
+            .iter()
+            .filter_map(|p| {
+                let mut score = 0.0;
+                for term in &terms {
+                    let Some(&tf) = p.terms.get(term) else { continue };
+                    let df = self.doc_freq.get(term).copied().unwrap_or(0) as f64;
+                    let idf = ((n - df + 0.5) / (df + 0.5)).ln_1p();
+                    let tf = tf as f64;
+                    let norm = 1.0 - BM25_B + BM25_B * p.length as f64 / self.avg_length.max(1.0);
+                    score += idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * norm);
+                }
+                (score > 0.0).then_some((score, p))
+            })
+            .collect();
+
+        if scored.is_empty() {
+            let needles: Vec<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
+            let in_order = |text: &str| {
+                let text = text.to_lowercase();
+                let mut from = 0;
+                needles.iter().all(|n| match text[from..].find(n.as_str()) {
+                    Some(at) => {
+                        from += at + n.len();
+                        true
+                    }
+                    None => false,
+                })
+            };
+            let mut matches: Vec<&SnapshotPrinciple> = self
+                .principles
+                .iter()
+                .filter(|p| in_order(&p.principle.name) || in_order(&p.principle.description))
+                .collect();
+            matches.sort_by(|a, b| b.principle.confidence.total_cmp(&a.principle.confidence));
+            return matches
+                .into_iter()
+                .take(limit)
+                .map(|p| PrincipleMatch { relevance_score: 0.5, ..p.principle.clone() })
+                .collect();
+        }
+
+        // FTS5 reports bm25 as negative (lower is better); keep that convention
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(score, p)| PrincipleMatch { relevance_score: -score, ..p.principle.clone() })
+            .collect()
+    }
+
+    /// In-memory stand-in for [`db::get_principles_by_domain`]
+    pub fn by_domain(&self, domain: &str) -> Vec<PrincipleMatch> {
+        let mut matches: Vec<PrincipleMatch> = self
+            .principles
+            .iter()
+            .filter(|p| p.domain_tags.iter().any(|t| t == domain))
+            .map(|p| p.principle.clone())
+            .collect();
+        matches.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        matches
+    }
+
+    pub fn principle(&self, id: &str) -> Option<&SnapshotPrinciple> {
+        self.by_id.get(id).map(|&i| &self.principles[i])
+    }
+
+    pub fn thinker_name(&self, thinker_id: &str) -> Option<&str> {
+        self.thinker_names.get(thinker_id).map(String::as_str)
+    }
+
+    /// Same factor as [`db::get_thinker_reliability`]
+    pub fn reliability(&self, thinker_id: &str) -> f64 {
+        self.reliability.get(thinker_id).copied().unwrap_or(1.0)
+    }
+
+    /// (alpha, beta, sample_count) of a contextual arm
+    pub fn arm(&self, principle_id: &str, domain: &str) -> Option<(f64, f64, i64)> {
+        self.arms(principle_id)
+            .iter()
+            .find(|(d, ..)| d == domain)
+            .map(|&(_, alpha, beta, samples)| (alpha, beta, samples))
+    }
+
+    /// (domain, alpha, beta, sample_count) of a principle's contextual arms
+    pub fn arms(&self, principle_id: &str) -> &[(String, f64, f64, i64)] {
+        self.arms.get(principle_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn citations(&self, principle_id: &str) -> i64 {
+        self.citations.get(principle_id).copied().unwrap_or(0)
+    }
+
+    pub fn calibrator(&self) -> Option<&Calibrator> {
+        self.calibrator.as_ref()
+    }
+}
+
+/// A snapshot that can be swapped out while requests are using the old one
+pub struct SharedSnapshot(RwLock<Arc<CorpusSnapshot>>);
+
+impl SharedSnapshot {
+    pub fn load(conn: &Connection) -> Result<Self> {
+        Ok(SharedSnapshot(RwLock::new(Arc::new(CorpusSnapshot::load(conn)?))))
+    }
+
+    pub fn current(&self) -> Arc<CorpusSnapshot> {
+        Arc::clone(&self.0.read().unwrap())
+    }
+
+    /// Reload from the database; in-flight requests keep the snapshot they started with
+    pub fn refresh(&self, conn: &Connection) -> Result<Arc<CorpusSnapshot>> {
+        let fresh = Arc::new(CorpusSnapshot::load(conn)?);
+        *self.0.write().unwrap() = Arc::clone(&fresh);
+        Ok(fresh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (Connection, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            r#"
+            INSERT INTO thinkers (id, name, domain) VALUES ('knuth', 'Donald Knuth', 'software');
+            INSERT INTO thinkers (id, name, domain) VALUES ('newman', 'Sam Newman', 'software');
+            INSERT INTO principles (id, thinker_id, name, description, domain_tags, learned_confidence)
+                VALUES ('premature', 'knuth', 'Premature Optimization', 'Measure before you optimize performance', '["performance"]', 0.7);
+            INSERT INTO principles (id, thinker_id, name, description, domain_tags, learned_confidence)
+                VALUES ('monolith', 'newman', 'Monolith First', 'Start with a monolith before microservices', '["architecture"]', 0.6);
+            INSERT INTO thinker_reliability (thinker_id, successes, failures) VALUES ('newman', 0, 4);
+            "#,
+        )
+        .unwrap();
+        (conn, dir)
+    }
+
+    #[test]
+    fn test_snapshot_search_matches_fts_top_hit() {
+        let (conn, _dir) = setup();
+        let snapshot = CorpusSnapshot::load(&conn).unwrap();
+        assert_eq!(snapshot.len(), 2);
+
+        for query in ["should we optimize performance", "split the monolith into microservices"] {
+            let fts = db::search_principles(&conn, query, 5).unwrap();
+            let mem = snapshot.search(query, 5);
+            assert_eq!(mem.first().map(|p| &p.id), fts.first().map(|p| &p.id), "{}", query);
+        }
+        assert!(snapshot.search("xy", 5).is_empty());
+
+        assert_eq!(snapshot.by_domain("architecture")[0].id, "monolith");
+        assert_eq!(snapshot.thinker_name("knuth"), Some("Donald Knuth"));
+        assert_eq!(snapshot.reliability("newman"), db::get_thinker_reliability(&conn, "newman"));
+        assert_eq!(snapshot.reliability("knuth"), 1.0);
+    }
+
+    #[test]
+    fn test_refresh_swaps_in_new_state() {
+        let (conn, _dir) = setup();
+        let shared = SharedSnapshot::load(&conn).unwrap();
+        let before = shared.current();
+
+        conn.execute(
+            "INSERT INTO contextual_arms (principle_id, domain, alpha, beta, sample_count)
+             VALUES ('premature', 'performance', 5.0, 2.0, 5)",
+            [],
+        )
+        .unwrap();
+        assert!(before.arm("premature", "performance").is_none());
+
+        shared.refresh(&conn).unwrap();
+        assert_eq!(shared.current().arm("premature", "performance"), Some((5.0, 2.0, 5)));
+        assert_eq!(shared.current().citations("premature"), 5);
+        assert!(before.arm("premature", "performance").is_none());
+    }
+}
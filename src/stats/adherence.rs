@@ -0,0 +1,264 @@
+//! Advice Adherence
+//!
+//! Counsel leaves artifacts to act on: checklist items to tick
+//! (`tick_checklist_item`) and blind spots to resolve (`ack_blind_spot`).
+//! `100minds analyze adherence` asks whether acting on them pays off. For
+//! each decision with an outcome and at least one artifact, adherence is
+//! the share of its items ticked and blind spots resolved; a decision with
+//! adherence of at least [`FOLLOWED_THRESHOLD`] counts as followed.
+//!
+//! Success rates of followed and skipped decisions are compared overall,
+//! for checklists and blind spots separately, and per cited principle and
+//! matched template, with the two-proportion z-test from
+//! [`intervals`](super::intervals). A principle or template whose followed
+//! decisions do no better than its skipped ones is advice nobody misses.
+
+use super::intervals::{self, RateComparison, RateInterval};
+use crate::templates;
+use crate::error::Result;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Adherence at which a decision counts as followed
+pub const FOLLOWED_THRESHOLD: f64 = 0.5;
+/// Decisions a principle or template needs to be listed
+const MIN_DECISIONS: usize = 2;
+
+/// Success of followed against skipped decisions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdherenceSplit {
+    pub followed: RateInterval,
+    pub skipped: RateInterval,
+    /// Followed minus skipped; None unless both have outcomes
+    pub significance: Option<RateComparison>,
+}
+
+impl AdherenceSplit {
+    fn new(outcomes: &[(bool, bool)]) -> Self {
+        let rate = |followed: bool| {
+            let group: Vec<bool> = outcomes.iter().filter(|o| o.0 == followed).map(|o| o.1).collect();
+            RateInterval::new(group.iter().filter(|&&s| s).count() as u64, group.len() as u64)
+        };
+        let (followed, skipped) = (rate(true), rate(false));
+        AdherenceSplit { followed, skipped, significance: intervals::compare(skipped, followed) }
+    }
+
+    /// Followed minus skipped success rate, when both have outcomes
+    pub fn difference(&self) -> Option<f64> {
+        self.significance.as_ref().map(|s| s.difference)
+    }
+}
+
+/// Adherence and success for the decisions citing a principle or matching a template
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupAdherence {
+    pub id: String,
+    pub name: String,
+    pub decisions: usize,
+    pub mean_adherence: f64,
+    #[serde(flatten)]
+    pub split: AdherenceSplit,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdherenceReport {
+    /// Decisions with an outcome and a checklist or recorded blind spots
+    pub decisions: usize,
+    pub mean_adherence: Option<f64>,
+    pub overall: AdherenceSplit,
+    /// Decisions with a checklist, split by its completion alone
+    pub checklist: AdherenceSplit,
+    /// Decisions with recorded blind spots, split by how many were resolved
+    pub blind_spots: AdherenceSplit,
+    /// Largest followed-minus-skipped difference first
+    pub principles: Vec<GroupAdherence>,
+    pub templates: Vec<GroupAdherence>,
+}
+
+/// One decision's artifacts and outcome
+struct Decided {
+    success: bool,
+    /// (ticked, total) checklist items
+    checklist: (usize, usize),
+    /// (resolved, total) blind spots
+    blind_spots: (usize, usize),
+    principles: Vec<String>,
+    templates: Vec<(String, String)>,
+}
+
+impl Decided {
+    fn adherence(&self) -> f64 {
+        let total = self.checklist.1 + self.blind_spots.1;
+        (self.checklist.0 + self.blind_spots.0) as f64 / total.max(1) as f64
+    }
+}
+
+/// (ticked or resolved, total) per decision
+fn counts(conn: &Connection, sql: &str) -> Result<HashMap<String, (usize, usize)>> {
+    Ok(conn
+        .prepare(sql)?
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, (row.get::<_, i64>(1)? as usize, row.get::<_, i64>(2)? as usize)))
+        })?
+        .collect::<rusqlite::Result<_>>()?)
+}
+
+/// Correlate checklist completion and blind-spot resolution with outcomes
+pub fn analyze(conn: &Connection) -> Result<AdherenceReport> {
+    let checklists =
+        counts(conn, "SELECT decision_id, SUM(done), COUNT(*) FROM checklist_items GROUP BY decision_id")?;
+    let blind_spots = counts(
+        conn,
+        "SELECT decision_id, SUM(status != 'open'), COUNT(*) FROM blind_spot_checks GROUP BY decision_id",
+    )?;
+    let names: HashMap<String, String> = conn
+        .prepare("SELECT id, name FROM principles")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    let rows: Vec<(String, String, String, bool)> = conn
+        .prepare("SELECT id, question, counsel_json, outcome_success FROM decisions WHERE outcome_success IS NOT NULL")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut decided = Vec::new();
+    for (id, question, counsel_json, success) in rows {
+        let checklist = checklists.get(&id).copied().unwrap_or_default();
+        let blind_spots = blind_spots.get(&id).copied().unwrap_or_default();
+        if checklist.1 == 0 && blind_spots.1 == 0 {
+            continue;
+        }
+        let counsel: serde_json::Value = serde_json::from_str(&counsel_json).unwrap_or_default();
+        let principles = counsel["principle_ids"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.as_str().map(String::from))
+            .collect();
+        let templates = templates::match_templates(&question).into_iter().map(|(t, _)| (t.id, t.name)).collect();
+        decided.push(Decided { success, checklist, blind_spots, principles, templates });
+    }
+
+    let outcomes = |decisions: &[&Decided], adherence: &dyn Fn(&Decided) -> Option<f64>| -> Vec<(bool, bool)> {
+        decisions
+            .iter()
+            .filter_map(|d| adherence(d).map(|a| (a >= FOLLOWED_THRESHOLD, d.success)))
+            .collect()
+    };
+    let share = |(done, total): (usize, usize)| (total > 0).then(|| done as f64 / total as f64);
+    let all: Vec<&Decided> = decided.iter().collect();
+
+    let mut by_principle: BTreeMap<String, Vec<&Decided>> = BTreeMap::new();
+    let mut by_template: BTreeMap<(String, String), Vec<&Decided>> = BTreeMap::new();
+    for d in &decided {
+        for p in &d.principles {
+            by_principle.entry(p.clone()).or_default().push(d);
+        }
+        for t in &d.templates {
+            by_template.entry(t.clone()).or_default().push(d);
+        }
+    }
+    let group = |id: String, name: String, decisions: &[&Decided]| GroupAdherence {
+        id,
+        name,
+        decisions: decisions.len(),
+        mean_adherence: decisions.iter().map(|d| d.adherence()).sum::<f64>() / decisions.len() as f64,
+        split: AdherenceSplit::new(&outcomes(decisions, &|d| Some(d.adherence()))),
+    };
+    let ranked = |mut groups: Vec<GroupAdherence>| {
+        groups.retain(|g| g.decisions >= MIN_DECISIONS);
+        groups.sort_by(|a, b| {
+            let difference = |g: &GroupAdherence| g.split.difference().unwrap_or(f64::NEG_INFINITY);
+            difference(b).total_cmp(&difference(a)).then(b.decisions.cmp(&a.decisions))
+        });
+        groups
+    };
+    let principles = by_principle
+        .into_iter()
+        .map(|(id, decisions)| {
+            let name = names.get(&id).cloned().unwrap_or_else(|| id.clone());
+            group(id, name, &decisions)
+        })
+        .collect();
+    let templates = by_template.into_iter().map(|((id, name), decisions)| group(id, name, &decisions)).collect();
+
+    Ok(AdherenceReport {
+        decisions: decided.len(),
+        mean_adherence: (!decided.is_empty())
+            .then(|| decided.iter().map(|d| d.adherence()).sum::<f64>() / decided.len() as f64),
+        overall: AdherenceSplit::new(&outcomes(&all, &|d| Some(d.adherence()))),
+        checklist: AdherenceSplit::new(&outcomes(&all, &|d| share(d.checklist))),
+        blind_spots: AdherenceSplit::new(&outcomes(&all, &|d| share(d.blind_spots))),
+        principles: ranked(principles),
+        templates: ranked(templates),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+
+    #[test]
+    fn test_followed_decisions_are_compared_with_skipped_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description) VALUES ('kiss', 't', 'Keep It Simple', 'd');",
+        )
+        .unwrap();
+        // (id, success, ticked of 2 checklist items, blind spot resolved)
+        let decisions = [
+            ("a", true, 2, true),
+            ("b", true, 2, false),
+            ("c", false, 0, false),
+            ("d", false, 1, false),
+            ("e", true, 0, true),
+        ];
+        for (id, success, ticked, resolved) in decisions {
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success)
+                 VALUES (?1, 'Should we rewrite the legacy system from scratch?', '{\"principle_ids\": [\"kiss\"]}',
+                         'h', 's', 'k', ?2)",
+                params![id, success],
+            )
+            .unwrap();
+            for item in 0..2 {
+                conn.execute(
+                    "INSERT INTO checklist_items (decision_id, item_id, position, kind, text, source, done)
+                     VALUES (?1, ?2, ?2, 'action', 'Do it', 'T', ?3)",
+                    params![id, item, item < ticked],
+                )
+                .unwrap();
+            }
+            conn.execute(
+                "INSERT INTO blind_spot_checks (decision_id, name, severity, status) VALUES (?1, 'Rollback', 'High', ?2)",
+                params![id, if resolved { "addressed" } else { "open" }],
+            )
+            .unwrap();
+        }
+        // No artifacts: left out
+        conn.execute(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success)
+             VALUES ('f', 'q', '{}', 'h', 's', 'k', 0)",
+            [],
+        )
+        .unwrap();
+
+        let report = analyze(&conn).unwrap();
+        assert_eq!(report.decisions, 5);
+        // Adherence a=1, b=2/3, c=0, d=1/3, e=1/3: followed a, b (2/2); skipped c, d, e (1/3)
+        assert_eq!((report.overall.followed.successes, report.overall.followed.trials), (2, 2));
+        assert_eq!((report.overall.skipped.successes, report.overall.skipped.trials), (1, 3));
+        assert!((report.overall.significance.as_ref().unwrap().difference - 2.0 / 3.0).abs() < 1e-12);
+        // Checklist alone: a, b, d followed (2/3); blind spots alone: a, e resolved (2/2)
+        assert_eq!((report.checklist.followed.successes, report.checklist.followed.trials), (2, 3));
+        assert_eq!((report.blind_spots.followed.successes, report.blind_spots.followed.trials), (2, 2));
+
+        let kiss = &report.principles[0];
+        assert_eq!((kiss.id.as_str(), kiss.name.as_str(), kiss.decisions), ("kiss", "Keep It Simple", 5));
+        assert_eq!(kiss.split, report.overall);
+        assert!(report.templates.iter().any(|t| t.id == "rewrite-vs-refactor" && t.decisions == 5));
+    }
+}
@@ -0,0 +1,196 @@
+//! Before/After Cohorts
+//!
+//! Did importing that pack, or changing the learning rate, help? A bookmark
+//! (`100minds mark-event "imported SRE pack"`, or the `mark_event` tool)
+//! splits the decision history; the report compares the decisions made
+//! between the previous bookmark and this one with those made between this
+//! one and the next. Installing a thinker pack bookmarks itself.
+//!
+//! Each cohort reports its success rate, the median time from decision to
+//! recorded outcome, and how varied the cited principles were. The change in
+//! success rate comes with a two-proportion test, since a handful of
+//! outcomes on either side rarely shows anything.
+
+use super::intervals::{self, RateComparison, RateInterval};
+use crate::error::{MindsError, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// A named point in time that splits the decision history
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventMark {
+    pub id: i64,
+    pub label: String,
+    pub marked_at: String,
+}
+
+/// Decisions made in one stretch of time
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Cohort {
+    /// Inclusive start; `None` from the beginning of history
+    pub from: Option<String>,
+    /// Exclusive end; `None` up to now
+    pub to: Option<String>,
+    pub decisions: usize,
+    /// Decisions with a recorded outcome
+    pub outcomes: usize,
+    pub successes: usize,
+    pub success_rate: Option<f64>,
+    /// Median hours from decision to recorded outcome
+    pub median_hours_to_outcome: Option<f64>,
+    /// Distinct principles cited / principle citations (1.0: no principle
+    /// cited twice)
+    pub principle_diversity: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CohortComparison {
+    pub mark: EventMark,
+    pub before: Cohort,
+    pub after: Cohort,
+    /// After minus before, when both have outcomes
+    pub success_rate_change: Option<f64>,
+    /// Whether that change is more than noise
+    pub significance: Option<RateComparison>,
+}
+
+/// Bookmark `at` with `label`
+pub fn mark_event(conn: &Connection, label: &str, at: DateTime<Utc>) -> Result<EventMark> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(MindsError::Validation("An event mark needs a label".to_string()));
+    }
+    let marked_at = at.to_rfc3339();
+    conn.execute("INSERT INTO event_marks (label, marked_at) VALUES (?1, ?2)", params![label, marked_at])?;
+    Ok(EventMark { id: conn.last_insert_rowid(), label: label.to_string(), marked_at })
+}
+
+/// Every bookmark, oldest first
+pub fn marks(conn: &Connection) -> Result<Vec<EventMark>> {
+    let marks = conn
+        .prepare("SELECT id, label, marked_at FROM event_marks ORDER BY julianday(marked_at), id")?
+        .query_map([], |row| Ok(EventMark { id: row.get(0)?, label: row.get(1)?, marked_at: row.get(2)? }))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(marks)
+}
+
+/// Before/after cohorts around every bookmark, oldest first
+pub fn compare(conn: &Connection) -> Result<Vec<CohortComparison>> {
+    let marks = marks(conn)?;
+    let mut comparisons = Vec::new();
+    for (i, mark) in marks.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|p| marks[p].marked_at.as_str());
+        let next = marks.get(i + 1).map(|n| n.marked_at.as_str());
+        let before = cohort(conn, previous, Some(&mark.marked_at))?;
+        let after = cohort(conn, Some(&mark.marked_at), next)?;
+        let success_rate_change = match (before.success_rate, after.success_rate) {
+            (Some(b), Some(a)) => Some(a - b),
+            _ => None,
+        };
+        let rate = |c: &Cohort| RateInterval::new(c.successes as u64, c.outcomes as u64);
+        let significance = intervals::compare(rate(&before), rate(&after));
+        comparisons.push(CohortComparison { mark: mark.clone(), before, after, success_rate_change, significance });
+    }
+    Ok(comparisons)
+}
+
+fn cohort(conn: &Connection, from: Option<&str>, to: Option<&str>) -> Result<Cohort> {
+    let rows: Vec<(Option<bool>, Option<f64>, String)> = conn
+        .prepare(
+            "SELECT outcome_success, (julianday(outcome_recorded_at) - julianday(created_at)) * 24, counsel_json
+             FROM decisions
+             WHERE (?1 IS NULL OR julianday(created_at) >= julianday(?1))
+               AND (?2 IS NULL OR julianday(created_at) < julianday(?2))",
+        )?
+        .query_map(params![from, to], |row| {
+            Ok((row.get::<_, Option<i64>>(0)?.map(|s| s != 0), row.get(1)?, row.get(2)?))
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let outcomes: Vec<bool> = rows.iter().filter_map(|r| r.0).collect();
+    let successes = outcomes.iter().filter(|&&s| s).count();
+    let mut hours: Vec<f64> = rows.iter().filter(|r| r.0.is_some()).filter_map(|r| r.1).collect();
+    hours.sort_by(f64::total_cmp);
+    let median = match hours.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(hours[n / 2]),
+        n => Some((hours[n / 2 - 1] + hours[n / 2]) / 2.0),
+    };
+
+    let mut citations = 0;
+    let mut distinct = HashSet::new();
+    for (_, _, counsel_json) in &rows {
+        let counsel: serde_json::Value = serde_json::from_str(counsel_json).unwrap_or_default();
+        for id in counsel["principle_ids"].as_array().into_iter().flatten().filter_map(|id| id.as_str()) {
+            citations += 1;
+            distinct.insert(id.to_string());
+        }
+    }
+
+    Ok(Cohort {
+        from: from.map(String::from),
+        to: to.map(String::from),
+        decisions: rows.len(),
+        outcomes: outcomes.len(),
+        successes,
+        success_rate: (!outcomes.is_empty()).then(|| successes as f64 / outcomes.len() as f64),
+        median_hours_to_outcome: median,
+        principle_diversity: (citations > 0).then(|| distinct.len() as f64 / citations as f64),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_cohorts_split_at_each_mark() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let now = Utc::now();
+        let decision = |id: &str, days_ago: i64, success: bool, principles: &str| {
+            let created = now - Duration::days(days_ago);
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success, outcome_recorded_at, created_at)
+                 VALUES (?1, 'q', ?2, 'h', 's', 'k', ?3, ?4, ?5)",
+                params![
+                    id,
+                    format!(r#"{{"principle_ids": {}}}"#, principles),
+                    success,
+                    (created + Duration::hours(10)).to_rfc3339(),
+                    created.format("%Y-%m-%d %H:%M:%S").to_string()
+                ],
+            )
+            .unwrap();
+        };
+        decision("a", 20, false, r#"["yagni", "kiss"]"#);
+        decision("b", 18, false, r#"["yagni", "kiss"]"#);
+        decision("c", 8, true, r#"["sre-slo", "kiss"]"#);
+        decision("d", 6, true, r#"["sre-toil"]"#);
+        decision("e", 1, false, r#"["yagni"]"#);
+
+        mark_event(&conn, "imported SRE pack", now - Duration::days(10)).unwrap();
+        mark_event(&conn, "raised learning rate", now - Duration::days(3)).unwrap();
+        assert!(mark_event(&conn, "  ", now).is_err());
+
+        let report = compare(&conn).unwrap();
+        assert_eq!(report.len(), 2);
+        let sre = &report[0];
+        assert_eq!(sre.mark.label, "imported SRE pack");
+        assert_eq!((sre.before.decisions, sre.before.success_rate), (2, Some(0.0)));
+        assert_eq!((sre.after.decisions, sre.after.success_rate), (2, Some(1.0)));
+        assert_eq!(sre.success_rate_change, Some(1.0));
+        // 0/2 then 2/2 is as far apart as rates get, yet only borderline evidence
+        assert!((sre.significance.as_ref().unwrap().p_value - 0.0455).abs() < 1e-3);
+        assert_eq!(sre.before.principle_diversity, Some(0.5));
+        assert_eq!(sre.after.principle_diversity, Some(1.0));
+        assert!((sre.after.median_hours_to_outcome.unwrap() - 10.0).abs() < 0.01);
+
+        // The second mark's "before" starts at the first mark
+        assert_eq!(report[1].before, sre.after);
+        assert_eq!((report[1].after.decisions, report[1].after.to.as_deref()), (1, None));
+    }
+}
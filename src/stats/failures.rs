@@ -0,0 +1,362 @@
+//! Failure Stages
+//!
+//! A failed outcome can say where the decision went wrong. `failure_stage`
+//! takes one of five stages, and the spellings clients sent before there
+//! was a taxonomy ("lint", "test", "reverted", ...) map onto them:
+//!
+//! | Stage            | Older spellings                                        |
+//! |------------------|--------------------------------------------------------|
+//! | `planning`       | design, requirements, scope, estimate                  |
+//! | `implementation` | lint, types, build, test, compile, bug                 |
+//! | `integration`    | merge, ci, review, merged, reverted, reopened, post-merge-fix |
+//! | `operations`     | deploy, runtime, incident, production, rollback        |
+//! | `external`       | dependency, vendor, third-party, upstream              |
+//!
+//! `failure_reasons` optionally narrows the stage down with codes from its
+//! list ([`FailureStage::reasons`]).
+//!
+//! `100minds analyze failures` counts failures per stage and lists the
+//! principles whose failures land in a stage more often than failures do
+//! overall: lift = (share of the principle's failures in the stage) /
+//! (share of all failures in the stage).
+
+use crate::error::Result;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureStage {
+    /// Requirements, assumptions, estimates, scope
+    Planning,
+    /// The work itself
+    Implementation,
+    /// Fitting it in with everything else: merging, CI, other teams' interfaces
+    Integration,
+    /// Running it: deploys, load, incidents
+    Operations,
+    /// Outside the team's control: dependencies, vendors, policy
+    External,
+}
+
+pub const STAGES: [FailureStage; 5] = [
+    FailureStage::Planning,
+    FailureStage::Implementation,
+    FailureStage::Integration,
+    FailureStage::Operations,
+    FailureStage::External,
+];
+
+/// Pre-taxonomy stage spellings
+const ALIASES: [(&str, FailureStage); 26] = [
+    ("design", FailureStage::Planning),
+    ("requirements", FailureStage::Planning),
+    ("scope", FailureStage::Planning),
+    ("estimate", FailureStage::Planning),
+    ("lint", FailureStage::Implementation),
+    ("types", FailureStage::Implementation),
+    ("build", FailureStage::Implementation),
+    ("test", FailureStage::Implementation),
+    ("compile", FailureStage::Implementation),
+    ("bug", FailureStage::Implementation),
+    ("merge", FailureStage::Integration),
+    ("ci", FailureStage::Integration),
+    ("review", FailureStage::Integration),
+    ("merged", FailureStage::Integration),
+    ("reverted", FailureStage::Integration),
+    ("reopened", FailureStage::Integration),
+    ("post-merge-fix", FailureStage::Integration),
+    ("deploy", FailureStage::Operations),
+    ("runtime", FailureStage::Operations),
+    ("incident", FailureStage::Operations),
+    ("production", FailureStage::Operations),
+    ("rollback", FailureStage::Operations),
+    ("dependency", FailureStage::External),
+    ("vendor", FailureStage::External),
+    ("third-party", FailureStage::External),
+    ("upstream", FailureStage::External),
+];
+
+/// Principles need this many failures in a stage to be listed under it
+const MIN_PRINCIPLE_FAILURES: usize = 2;
+/// Principles listed per stage
+const TOP_PRINCIPLES: usize = 5;
+
+impl FailureStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FailureStage::Planning => "planning",
+            FailureStage::Implementation => "implementation",
+            FailureStage::Integration => "integration",
+            FailureStage::Operations => "operations",
+            FailureStage::External => "external",
+        }
+    }
+
+    /// A stage name or an older spelling, in any case
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        STAGES
+            .into_iter()
+            .find(|s| s.as_str() == text)
+            .or_else(|| ALIASES.iter().find(|(alias, _)| *alias == text).map(|(_, s)| *s))
+    }
+
+    /// Reason codes that narrow this stage down
+    pub fn reasons(self) -> &'static [&'static str] {
+        match self {
+            FailureStage::Planning => {
+                &["unclear-requirements", "wrong-assumption", "underestimated", "scope-change", "other"]
+            }
+            FailureStage::Implementation => &["bug", "lint", "types", "build", "test", "tech-debt", "other"],
+            FailureStage::Integration => &["merge-conflict", "ci", "interface-mismatch", "reverted", "other"],
+            FailureStage::Operations => &["deploy", "performance", "outage", "rollback", "security", "other"],
+            FailureStage::External => &["dependency", "vendor", "policy", "people", "other"],
+        }
+    }
+}
+
+/// Check an outcome's stage and reasons; the stage, if one was given
+pub fn check(
+    success: bool,
+    stage: Option<&str>,
+    reasons: &[String],
+) -> std::result::Result<Option<FailureStage>, String> {
+    let Some(text) = stage else {
+        return match reasons.is_empty() {
+            true => Ok(None),
+            false => Err("failure_reasons given without failure_stage".to_string()),
+        };
+    };
+    if success {
+        return Err("failure_stage given for a successful outcome".to_string());
+    }
+    let names: Vec<&str> = STAGES.iter().map(|s| s.as_str()).collect();
+    let stage = FailureStage::parse(text)
+        .ok_or_else(|| format!("Unknown failure_stage {:?} (expected one of: {})", text, names.join(", ")))?;
+    if let Some(reason) = reasons.iter().find(|r| !stage.reasons().contains(&r.as_str())) {
+        return Err(format!(
+            "Unknown {} failure reason {:?} (expected one of: {})",
+            stage.as_str(),
+            reason,
+            stage.reasons().join(", ")
+        ));
+    }
+    Ok(Some(stage))
+}
+
+/// Store the stage and reasons an outcome's context gives (cleared for
+/// successes and for stages that don't parse)
+pub fn record_from_context(
+    conn: &Connection,
+    decision_id: &str,
+    context: Option<&Value>,
+    success: bool,
+) -> rusqlite::Result<()> {
+    let stage = context
+        .and_then(|c| c.get("failure_stage"))
+        .and_then(|s| s.as_str())
+        .and_then(FailureStage::parse)
+        .filter(|_| !success);
+    let reasons: Vec<&str> = match (stage, context.and_then(|c| c.get("failure_reasons")).and_then(|r| r.as_array())) {
+        (Some(stage), Some(reasons)) => reasons.iter().filter_map(|r| r.as_str()).filter(|r| stage.reasons().contains(r)).collect(),
+        _ => Vec::new(),
+    };
+    conn.execute(
+        "UPDATE decisions SET failure_stage = ?2, failure_reasons = ?3 WHERE id = ?1",
+        params![
+            decision_id,
+            stage.map(|s| s.as_str()),
+            (!reasons.is_empty()).then(|| serde_json::to_string(&reasons).unwrap_or_default())
+        ],
+    )?;
+    Ok(())
+}
+
+/// Fill in the stage of failures recorded before decisions had one, from
+/// the context their adjustments were stored with
+pub fn backfill(conn: &Connection) -> Result<usize> {
+    let failures: Vec<(String, String)> = conn
+        .prepare(
+            "SELECT d.id, fa.context_pattern FROM decisions d
+             JOIN framework_adjustments fa ON fa.decision_id = d.id
+             WHERE d.outcome_success = 0 AND d.failure_stage IS NULL AND fa.context_pattern IS NOT NULL
+             GROUP BY d.id",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    let mut filled = 0;
+    for (id, context) in failures {
+        let context: Option<Value> = serde_json::from_str(&context).ok();
+        if context.as_ref().and_then(|c| c.get("failure_stage")).is_some() {
+            record_from_context(conn, &id, context.as_ref(), false)?;
+            filled += 1;
+        }
+    }
+    Ok(filled)
+}
+
+/// A principle whose failures cluster in one stage
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PrincipleLift {
+    pub principle_id: String,
+    pub name: String,
+    /// Failed decisions citing the principle that failed in this stage
+    pub failures: usize,
+    /// Above 1.0: the principle's failures land here more than failures overall do
+    pub lift: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StageReport {
+    pub stage: FailureStage,
+    pub failures: usize,
+    /// Of the failures with a stage
+    pub share: f64,
+    /// Reason code -> failures giving it
+    pub reasons: BTreeMap<String, usize>,
+    /// Highest lift first
+    pub principles: Vec<PrincipleLift>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailureReport {
+    pub failures: usize,
+    /// Failures with a stage; the rest didn't say
+    pub classified: usize,
+    /// Most failures first
+    pub stages: Vec<StageReport>,
+}
+
+/// Failures by stage, and the principles that correlate with each
+pub fn analyze(conn: &Connection) -> Result<FailureReport> {
+    let failures: Vec<(String, Option<String>, Option<String>)> = conn
+        .prepare("SELECT id, failure_stage, failure_reasons FROM decisions WHERE outcome_success = 0")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    let mut cited: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    let rows = conn
+        .prepare(
+            "SELECT DISTINCT fa.decision_id, p.id, p.name FROM framework_adjustments fa
+             JOIN decisions d ON d.id = fa.decision_id
+             JOIN principles p ON p.id = fa.principle_id
+             WHERE d.outcome_success = 0",
+        )?
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (decision_id, principle_id, name) in rows {
+        cited.entry(decision_id).or_default().push((principle_id, name));
+    }
+
+    let mut stages: BTreeMap<FailureStage, (usize, BTreeMap<String, usize>)> = BTreeMap::new();
+    // principle -> (name, classified failures, failures per stage)
+    let mut principles: HashMap<String, (String, usize, HashMap<FailureStage, usize>)> = HashMap::new();
+    let mut classified = 0;
+    for (id, stage, reasons) in &failures {
+        let Some(stage) = stage.as_deref().and_then(FailureStage::parse) else {
+            continue;
+        };
+        classified += 1;
+        let (count, reason_counts) = stages.entry(stage).or_default();
+        *count += 1;
+        for reason in reasons.as_deref().and_then(|r| serde_json::from_str::<Vec<String>>(r).ok()).unwrap_or_default() {
+            *reason_counts.entry(reason).or_default() += 1;
+        }
+        for (principle_id, name) in cited.get(id).into_iter().flatten() {
+            let entry = principles.entry(principle_id.clone()).or_insert_with(|| (name.clone(), 0, HashMap::new()));
+            entry.1 += 1;
+            *entry.2.entry(stage).or_default() += 1;
+        }
+    }
+
+    let mut report: Vec<StageReport> = stages
+        .into_iter()
+        .map(|(stage, (count, reasons))| {
+            let share = count as f64 / classified as f64;
+            let mut lifts: Vec<PrincipleLift> = principles
+                .iter()
+                .filter_map(|(id, (name, total, by_stage))| {
+                    let in_stage = by_stage.get(&stage).copied().unwrap_or(0);
+                    (in_stage >= MIN_PRINCIPLE_FAILURES).then(|| PrincipleLift {
+                        principle_id: id.clone(),
+                        name: name.clone(),
+                        failures: in_stage,
+                        lift: (in_stage as f64 / *total as f64) / share,
+                    })
+                })
+                .collect();
+            lifts.sort_by(|a, b| b.lift.total_cmp(&a.lift).then(b.failures.cmp(&a.failures)).then_with(|| a.name.cmp(&b.name)));
+            lifts.truncate(TOP_PRINCIPLES);
+            StageReport { stage, failures: count, share, reasons, principles: lifts }
+        })
+        .collect();
+    report.sort_by(|a, b| b.failures.cmp(&a.failures).then(a.stage.cmp(&b.stage)));
+    Ok(FailureReport { failures: failures.len(), classified, stages: report })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stages_parse_aliases_and_check_reasons() {
+        assert_eq!(FailureStage::parse(" Operations "), Some(FailureStage::Operations));
+        assert_eq!(FailureStage::parse("test"), Some(FailureStage::Implementation));
+        assert_eq!(FailureStage::parse("reverted"), Some(FailureStage::Integration));
+        assert_eq!(FailureStage::parse("vibes"), None);
+
+        let reasons = |r: &[&str]| r.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(check(false, Some("deploy"), &reasons(&["outage"])), Ok(Some(FailureStage::Operations)));
+        assert!(check(false, Some("planning"), &reasons(&["outage"])).unwrap_err().contains("unclear-requirements"));
+        assert!(check(true, Some("planning"), &[]).is_err());
+        assert!(check(false, None, &reasons(&["bug"])).is_err());
+        assert!(check(false, Some("vibes"), &[]).unwrap_err().contains("planning, implementation"));
+    }
+
+    #[test]
+    fn test_analyze_finds_principles_that_fail_in_a_stage() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description) VALUES ('yagni', 't', 'YAGNI', 'd'), ('ship', 't', 'Ship it', 'd');",
+        )
+        .unwrap();
+        // YAGNI fails in planning; "Ship it" fails everywhere
+        let failures = [
+            ("d0", "design", vec!["yagni", "ship"], r#"["scope-change"]"#),
+            ("d1", "planning", vec!["yagni"], r#"["scope-change", "underestimated"]"#),
+            ("d2", "planning", vec!["yagni", "ship"], "[]"),
+            ("d3", "deploy", vec!["ship"], r#"["outage"]"#),
+            ("d4", "ci", vec!["ship"], "[]"),
+        ];
+        for (id, stage, cited, reasons) in failures {
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey) VALUES (?1, 'q', '{}', 'h', 's', 'k')",
+                [id],
+            )
+            .unwrap();
+            let context = format!(r#"{{"failure_stage": "{}", "failure_reasons": {}}}"#, stage, reasons);
+            let cited: Vec<String> = cited.into_iter().map(String::from).collect();
+            crate::outcome::record_outcome(&conn, id, false, &cited, "", Some(&context)).unwrap();
+        }
+        conn.execute(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success) VALUES ('d5', 'q', '{}', 'h', 's', 'k', 0)",
+            [],
+        )
+        .unwrap();
+
+        let report = analyze(&conn).unwrap();
+        assert_eq!((report.failures, report.classified), (6, 5));
+        let planning = &report.stages[0];
+        assert_eq!((planning.stage, planning.failures), (FailureStage::Planning, 3));
+        assert_eq!(planning.reasons.get("scope-change"), Some(&2));
+        let names: Vec<&str> = planning.principles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["YAGNI", "Ship it"]);
+        // All of YAGNI's failures are planning ones, against 3 in 5 overall
+        assert!((planning.principles[0].lift - 5.0 / 3.0).abs() < 1e-9);
+        assert!(planning.principles[1].lift < 1.0);
+    }
+}
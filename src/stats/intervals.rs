@@ -0,0 +1,129 @@
+//! Interval Estimates and Significance
+//!
+//! A success rate from a handful of outcomes says little on its own. Each
+//! rate in the learning stats comes with a 95% Wilson score interval and a
+//! Beta(1 + successes, 1 + failures) credible interval, and two rates (two
+//! domains, the last 30 days against the 30 before) are compared with a
+//! two-proportion z-test, so "this improved" is only claimed when p < 0.05.
+
+use serde::Serialize;
+use statrs::distribution::{Beta, ContinuousCDF, Normal};
+
+/// Two-sided 95% normal quantile
+pub const Z_95: f64 = 1.959_964;
+
+/// Significance level for [`compare`]
+pub const ALPHA: f64 = 0.05;
+
+/// A success rate with its uncertainty
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RateInterval {
+    pub successes: u64,
+    pub trials: u64,
+    pub rate: f64,
+    /// 95% Wilson score interval
+    pub wilson: (f64, f64),
+    /// 95% credible interval of Beta(1 + successes, 1 + failures)
+    pub credible: (f64, f64),
+}
+
+impl RateInterval {
+    pub fn new(successes: u64, trials: u64) -> Self {
+        let successes = successes.min(trials);
+        RateInterval {
+            successes,
+            trials,
+            rate: if trials > 0 { successes as f64 / trials as f64 } else { 0.0 },
+            wilson: wilson(successes, trials, Z_95),
+            credible: credible(successes, trials - successes),
+        }
+    }
+}
+
+/// How two success rates differ
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RateComparison {
+    pub before: RateInterval,
+    pub after: RateInterval,
+    /// After minus before
+    pub difference: f64,
+    pub z: f64,
+    /// Two-sided p-value of the pooled two-proportion z-test
+    pub p_value: f64,
+    /// p < [`ALPHA`]
+    pub significant: bool,
+}
+
+impl RateComparison {
+    /// "significant, p=0.004" or "could be noise, p=0.31"
+    pub fn verdict(&self) -> String {
+        if self.significant {
+            format!("significant, p={:.3}", self.p_value)
+        } else {
+            format!("could be noise, p={:.2}", self.p_value)
+        }
+    }
+}
+
+/// Wilson score interval for `successes` of `trials` at normal quantile `z`
+pub fn wilson(successes: u64, trials: u64, z: f64) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = z * z;
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+    ((center - half).max(0.0), (center + half).min(1.0))
+}
+
+/// Central 95% of Beta(1 + successes, 1 + failures)
+pub fn credible(successes: u64, failures: u64) -> (f64, f64) {
+    match Beta::new(1.0 + successes as f64, 1.0 + failures as f64) {
+        Ok(dist) => (dist.inverse_cdf(0.025), dist.inverse_cdf(0.975)),
+        Err(_) => (0.0, 1.0),
+    }
+}
+
+/// Two-proportion z-test of `after` against `before`; None when either is empty
+pub fn compare(before: RateInterval, after: RateInterval) -> Option<RateComparison> {
+    if before.trials == 0 || after.trials == 0 {
+        return None;
+    }
+    let (n1, n2) = (before.trials as f64, after.trials as f64);
+    let pooled = (before.successes + after.successes) as f64 / (n1 + n2);
+    let se = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    let difference = after.rate - before.rate;
+    // All successes or all failures on both sides: nothing differs
+    let z = if se > 0.0 { difference / se } else { 0.0 };
+    let normal = Normal::new(0.0, 1.0).expect("standard normal");
+    let p_value = (2.0 * (1.0 - normal.cdf(z.abs()))).clamp(0.0, 1.0);
+    Some(RateComparison { before, after, difference, z, p_value, significant: p_value < ALPHA })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intervals_narrow_with_evidence_and_small_samples_are_not_significant() {
+        let few = RateInterval::new(2, 3);
+        let many = RateInterval::new(200, 300);
+        assert_eq!(few.rate, many.rate);
+        assert!(few.wilson.1 - few.wilson.0 > 3.0 * (many.wilson.1 - many.wilson.0));
+        assert!(few.wilson.0 < few.rate && few.rate < few.wilson.1);
+        // Known value: 8 of 10 → Wilson (0.490, 0.943)
+        let (low, high) = wilson(8, 10, Z_95);
+        assert!((low - 0.4902).abs() < 1e-3 && (high - 0.9433).abs() < 1e-3, "{} {}", low, high);
+        assert!(many.credible.0 < many.rate && many.rate < many.credible.1);
+
+        // 1/3 → 2/3 is noise; 100/300 → 200/300 is not
+        let small = compare(RateInterval::new(1, 3), few).unwrap();
+        assert!(!small.significant && small.p_value > 0.3);
+        let large = compare(RateInterval::new(100, 300), many).unwrap();
+        assert!(large.significant && large.p_value < 1e-6 && (large.difference - 1.0 / 3.0).abs() < 1e-12);
+        assert!(compare(RateInterval::new(0, 0), many).is_none());
+        assert_eq!(compare(RateInterval::new(5, 5), RateInterval::new(3, 3)).unwrap().p_value, 1.0);
+    }
+}
@@ -0,0 +1,13 @@
+//! Statistics over the learning history
+//!
+//! Reports that need more than the current state of the corpus: how
+//! confidences and outcomes have moved over time, and how decisions
+//! compare before and after a change, where failures happen, and whether
+//! following the advice pays off; and the interval estimates and
+//! significance tests the other reports lean on.
+
+pub mod adherence;
+pub mod cohorts;
+pub mod failures;
+pub mod intervals;
+pub mod timeseries;
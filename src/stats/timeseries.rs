@@ -0,0 +1,305 @@
+//! Trend Time Series
+//!
+//! Is the flywheel actually improving outcomes? Each snapshot records, for
+//! every principle, every domain tag and the corpus as a whole, the learned
+//! confidence and the success rate of outcomes over the preceding
+//! [`WINDOW_DAYS`]. The daemon's `trends` job takes one a day (a second
+//! snapshot on the same day replaces the first), and `100minds trends`
+//! takes one before reporting, so the latest point is always current.
+//!
+//! Snapshots are the only record of past confidences (they drift with
+//! every outcome and decay run), so trends start when snapshots do.
+
+use crate::db::with_savepoint;
+use crate::error::{MindsError, Result};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Days of outcomes behind each snapshot's success rate
+pub const WINDOW_DAYS: i64 = 30;
+
+const SPARK_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One snapshot (or, after [`monthly`], the last snapshot of a month)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendPoint {
+    /// "2026-10-16", or "2026-10" for a monthly point
+    pub date: String,
+    /// Mean learned confidence of the principles in scope
+    pub confidence: Option<f64>,
+    /// Outcomes in the window
+    pub outcomes: usize,
+    /// `None` when the window had no outcomes
+    pub success_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendSeries {
+    /// "all", "principle" or "domain"
+    pub scope: String,
+    pub key: String,
+    /// Principle name, domain tag, or "all principles"
+    pub label: String,
+    pub points: Vec<TrendPoint>,
+}
+
+#[derive(Default)]
+struct Tally {
+    confidences: Vec<f64>,
+    /// Decision id → success, so a decision citing several principles in a
+    /// domain counts once there
+    outcomes: HashMap<String, bool>,
+}
+
+/// Record today's snapshot as of `at`; returns the number of series written
+pub fn record_snapshot(conn: &Connection, at: DateTime<Utc>) -> Result<usize> {
+    let from = (at - Duration::days(WINDOW_DAYS)).to_rfc3339();
+    let to = at.to_rfc3339();
+
+    let principles: Vec<(String, f64, Vec<String>)> = conn
+        .prepare("SELECT id, learned_confidence, domain_tags FROM principles")?
+        .query_map([], |row| {
+            let tags: Option<String> = row.get(2)?;
+            Ok((row.get(0)?, row.get::<_, Option<f64>>(1)?.unwrap_or(0.5), tags))
+        })?
+        .map(|row| {
+            row.map(|(id, confidence, tags)| {
+                let tags: Vec<String> = tags.and_then(|t| serde_json::from_str(&t).ok()).unwrap_or_default();
+                (id, confidence, tags.into_iter().map(|t| t.to_lowercase()).collect())
+            })
+        })
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut tallies: BTreeMap<(&str, String), Tally> = BTreeMap::new();
+    let mut tags_of: HashMap<&str, &[String]> = HashMap::new();
+    for (id, confidence, tags) in &principles {
+        tags_of.insert(id, tags);
+        tallies.entry(("all", String::new())).or_default().confidences.push(*confidence);
+        tallies.entry(("principle", id.clone())).or_default().confidences.push(*confidence);
+        for tag in tags {
+            tallies.entry(("domain", tag.clone())).or_default().confidences.push(*confidence);
+        }
+    }
+
+    let cited: Vec<(String, String, bool)> = conn
+        .prepare(
+            "SELECT fa.principle_id, d.id, d.outcome_success
+             FROM framework_adjustments fa
+             JOIN decisions d ON d.id = fa.decision_id
+             WHERE d.outcome_success IS NOT NULL
+               AND julianday(fa.created_at) > julianday(?1) AND julianday(fa.created_at) <= julianday(?2)",
+        )?
+        .query_map(params![from, to], |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, i64>(2)? != 0)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for (principle_id, decision_id, success) in cited {
+        let Some(tags) = tags_of.get(principle_id.as_str()) else {
+            continue;
+        };
+        for tag in tags.iter() {
+            tallies.entry(("domain", tag.clone())).or_default().outcomes.insert(decision_id.clone(), success);
+        }
+        tallies.entry(("principle", principle_id)).or_default().outcomes.insert(decision_id, success);
+    }
+
+    // Corpus-wide, every recorded outcome counts, cited principles or not
+    let decided: Vec<(String, bool)> = conn
+        .prepare(
+            "SELECT id, outcome_success FROM decisions
+             WHERE outcome_success IS NOT NULL
+               AND julianday(outcome_recorded_at) > julianday(?1) AND julianday(outcome_recorded_at) <= julianday(?2)",
+        )?
+        .query_map(params![from, to], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? != 0)))?
+        .collect::<rusqlite::Result<_>>()?;
+    tallies.entry(("all", String::new())).or_default().outcomes.extend(decided);
+
+    let taken_on = at.date_naive().to_string();
+    with_savepoint(conn, "trend_snapshot", || {
+        let mut insert = conn.prepare(
+            "INSERT OR REPLACE INTO trend_snapshots (taken_on, scope, key, confidence, outcomes, success_rate)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for ((scope, key), tally) in &tallies {
+            let confidence = mean(&tally.confidences);
+            let successes = tally.outcomes.values().filter(|&&s| s).count();
+            let rate = (!tally.outcomes.is_empty()).then(|| successes as f64 / tally.outcomes.len() as f64);
+            insert.execute(params![taken_on, scope, key, confidence, tally.outcomes.len() as i64, rate])?;
+        }
+        Ok(tallies.len())
+    })
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Which series `target` names: nothing for the whole corpus, else a
+/// principle (id or name) or, failing that, a domain tag
+fn resolve(conn: &Connection, target: Option<&str>) -> Result<(&'static str, String, String)> {
+    let Some(target) = target.map(str::trim).filter(|t| !t.is_empty()) else {
+        return Ok(("all", String::new(), "all principles".to_string()));
+    };
+    let principle: Option<(String, String)> = conn
+        .query_row(
+            "SELECT id, name FROM principles WHERE id = ?1 OR lower(name) = lower(?1) ORDER BY id = ?1 DESC LIMIT 1",
+            [target],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    if let Some((id, name)) = principle {
+        return Ok(("principle", id, name));
+    }
+    let domain = target.to_lowercase();
+    let known: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM principles, json_each(principles.domain_tags) WHERE lower(json_each.value) = ?1)",
+        [&domain],
+        |row| row.get(0),
+    )?;
+    if !known {
+        return Err(MindsError::NotFound(format!("No principle or domain named '{}'", target)));
+    }
+    Ok(("domain", domain.clone(), domain))
+}
+
+/// Every recorded snapshot of the series `target` names, oldest first
+pub fn series(conn: &Connection, target: Option<&str>) -> Result<TrendSeries> {
+    let (scope, key, label) = resolve(conn, target)?;
+    let points = conn
+        .prepare(
+            "SELECT taken_on, confidence, outcomes, success_rate FROM trend_snapshots
+             WHERE scope = ?1 AND key = ?2 ORDER BY taken_on",
+        )?
+        .query_map(params![scope, key], |row| {
+            Ok(TrendPoint {
+                date: row.get(0)?,
+                confidence: row.get(1)?,
+                outcomes: row.get::<_, i64>(2)? as usize,
+                success_rate: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(TrendSeries { scope: scope.to_string(), key, label, points })
+}
+
+/// One point per month: its last snapshot, dated "YYYY-MM"
+pub fn monthly(points: &[TrendPoint]) -> Vec<TrendPoint> {
+    let mut months: Vec<TrendPoint> = Vec::new();
+    for point in points {
+        let month = point.date.get(..7).unwrap_or(&point.date).to_string();
+        let point = TrendPoint { date: month, ..point.clone() };
+        match months.last_mut() {
+            Some(last) if last.date == point.date => *last = point,
+            _ => months.push(point),
+        }
+    }
+    months
+}
+
+/// One bar per value, scaled between the series' own min and max; a gap
+/// where there is no value
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    let present = values.iter().flatten();
+    let min = present.clone().copied().fold(f64::INFINITY, f64::min);
+    let max = present.copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| match value {
+            None => ' ',
+            Some(_) if max - min < 1e-9 => SPARK_BARS[3],
+            Some(v) => SPARK_BARS[(((v - min) / (max - min)) * 7.0).round() as usize],
+        })
+        .collect()
+}
+
+/// `date,confidence,outcomes,success_rate`, blank where there is no value
+pub fn to_csv(points: &[TrendPoint]) -> String {
+    let cell = |v: Option<f64>| v.map(|v| format!("{:.4}", v)).unwrap_or_default();
+    let mut csv = "date,confidence,outcomes,success_rate\n".to_string();
+    for p in points {
+        csv.push_str(&format!("{},{},{},{}\n", p.date, cell(p.confidence), p.outcomes, cell(p.success_rate)));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days_ago(days: i64) -> DateTime<Utc> {
+        Utc::now() - Duration::days(days)
+    }
+
+    #[test]
+    fn test_snapshots_track_confidence_and_windowed_success() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description, domain_tags, learned_confidence) VALUES
+                ('yagni', 't', 'YAGNI', 'd', '[\"Architecture\"]', 0.5),
+                ('kiss', 't', 'KISS', 'd', '[\"architecture\", \"testing\"]', 0.7);",
+        )
+        .unwrap();
+        let outcome = |id: &str, principle: &str, success: bool, at: DateTime<Utc>| {
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success, outcome_recorded_at)
+                 VALUES (?1, 'q', '{}', 'h', 's', 'k', ?2, ?3)",
+                params![id, success, at.to_rfc3339()],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO framework_adjustments (principle_id, adjustment, decision_id, created_at) VALUES (?1, 0.05, ?2, ?3)",
+                params![principle, id, at.format("%Y-%m-%d %H:%M:%S").to_string()],
+            )
+            .unwrap();
+        };
+        outcome("old", "yagni", false, days_ago(50));
+        outcome("d1", "yagni", true, days_ago(3));
+        outcome("d2", "kiss", false, days_ago(2));
+        record_snapshot(&conn, days_ago(45)).unwrap();
+        conn.execute("UPDATE principles SET learned_confidence = 0.8 WHERE id = 'yagni'", []).unwrap();
+        assert_eq!(record_snapshot(&conn, Utc::now()).unwrap(), 5);
+        // Same day again replaces, not appends
+        record_snapshot(&conn, Utc::now()).unwrap();
+
+        let yagni = series(&conn, Some("yagni")).unwrap();
+        assert_eq!((yagni.scope.as_str(), yagni.label.as_str(), yagni.points.len()), ("principle", "YAGNI", 2));
+        assert_eq!((yagni.points[0].outcomes, yagni.points[0].success_rate), (1, Some(0.0)));
+        assert_eq!((yagni.points[1].confidence, yagni.points[1].success_rate), (Some(0.8), Some(1.0)));
+
+        let architecture = series(&conn, Some("Architecture")).unwrap();
+        assert_eq!(architecture.scope, "domain");
+        let now = architecture.points.last().unwrap();
+        assert_eq!((now.outcomes, now.success_rate), (2, Some(0.5)));
+        assert!((now.confidence.unwrap() - 0.75).abs() < 1e-9);
+
+        let all = series(&conn, None).unwrap();
+        assert_eq!(all.points.last().unwrap().outcomes, 2);
+        assert!(series(&conn, Some("nonsense")).is_err());
+    }
+
+    #[test]
+    fn test_monthly_points_sparkline_and_csv() {
+        let point = |date: &str, confidence: f64, rate: Option<f64>| TrendPoint {
+            date: date.to_string(),
+            confidence: Some(confidence),
+            outcomes: rate.map_or(0, |_| 4),
+            success_rate: rate,
+        };
+        let points = [
+            point("2026-08-01", 0.5, None),
+            point("2026-08-31", 0.55, Some(0.5)),
+            point("2026-09-30", 0.6, Some(0.75)),
+            point("2026-10-16", 0.7, Some(1.0)),
+        ];
+        let months = monthly(&points);
+        assert_eq!(months.iter().map(|p| p.date.as_str()).collect::<Vec<_>>(), ["2026-08", "2026-09", "2026-10"]);
+        assert_eq!(months[0].confidence, Some(0.55));
+
+        let confidence: Vec<Option<f64>> = months.iter().map(|p| p.confidence).collect();
+        assert_eq!(sparkline(&confidence), "▁▃█");
+        assert_eq!(sparkline(&[None, Some(0.5), Some(0.5)]), " ▄▄");
+        assert_eq!(to_csv(&points[..1]), "date,confidence,outcomes,success_rate\n2026-08-01,0.5000,0,\n");
+    }
+}
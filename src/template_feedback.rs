@@ -0,0 +1,270 @@
+//! Template Feedback
+//!
+//! Outcomes can say which branch of a decision template was followed
+//! (`template_id` plus `template_path`, the option chosen at each question,
+//! by label or 0-based index). [`analyze`] tallies them per leaf and turns
+//! leaves that keep failing into suggested edits: a JSON Patch (RFC 6902)
+//! against the template's JSON, for a human to review before changing the
+//! tree. Each edit is guarded by a `test` op, so a patch written against an
+//! older tree won't apply.
+
+use crate::templates::{get_templates, DecisionTemplate, DecisionTree};
+use crate::error::{MindsError, Result};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Leaves with fewer outcomes than this aren't judged
+pub const MIN_SAMPLES: u32 = 5;
+
+/// Failure rate at which a leaf gets a suggestion
+pub const FAILURE_THRESHOLD: f64 = 0.6;
+
+/// Outcomes of one leaf of a template
+#[derive(Debug, Clone, Serialize)]
+pub struct LeafStats {
+    /// JSON Pointer to the leaf option within the template
+    pub pointer: String,
+    pub label: String,
+    pub recommendation: Option<String>,
+    pub successes: u32,
+    pub failures: u32,
+}
+
+impl LeafStats {
+    pub fn failure_rate(&self) -> f64 {
+        let total = self.successes + self.failures;
+        if total == 0 { 0.0 } else { self.failures as f64 / total as f64 }
+    }
+}
+
+/// A suggested tree edit, with the evidence behind it
+#[derive(Debug, Clone, Serialize)]
+pub struct MutationSuggestion {
+    pub pointer: String,
+    pub message: String,
+    pub failure_rate: f64,
+    pub samples: u32,
+}
+
+/// Outcome-driven review of one template
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateReport {
+    pub template_id: String,
+    pub name: String,
+    pub outcomes: u32,
+    pub success_rate: f64,
+    pub leaves: Vec<LeafStats>,
+    pub suggestions: Vec<MutationSuggestion>,
+    /// JSON Patch against the template, applying every suggestion
+    pub patch: Vec<Value>,
+}
+
+/// Option indices for a path of labels or indices, which must end on a leaf
+pub fn resolve_path(template: &DecisionTemplate, steps: &[String]) -> Result<Vec<usize>> {
+    let mut node: &DecisionTree = &template.tree;
+    let mut path = Vec::with_capacity(steps.len());
+    for (depth, step) in steps.iter().enumerate() {
+        let step = step.trim();
+        let index = step
+            .parse::<usize>()
+            .ok()
+            .filter(|i| *i < node.options.len())
+            .or_else(|| node.options.iter().position(|o| o.label.eq_ignore_ascii_case(step)))
+            .ok_or_else(|| {
+                MindsError::Validation(format!(
+                    "'{}' is not an option of \"{}\" in template {}",
+                    step, node.question, template.id
+                ))
+            })?;
+        path.push(index);
+        match &node.options[index].next {
+            Some(next) if depth + 1 < steps.len() => node = next,
+            Some(_) => {
+                return Err(MindsError::Validation(format!(
+                    "template_path for {} stops before a recommendation",
+                    template.id
+                )))
+            }
+            None if depth + 1 < steps.len() => {
+                return Err(MindsError::Validation(format!(
+                    "template_path for {} continues past a recommendation",
+                    template.id
+                )))
+            }
+            None => {}
+        }
+    }
+    if path.is_empty() {
+        return Err(MindsError::Validation("template_path is empty".to_string()));
+    }
+    Ok(path)
+}
+
+/// A `template_path` JSON array as steps; entries may be labels or indices
+pub fn path_steps(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|p| p.as_array())
+        .map(|steps| {
+            steps
+                .iter()
+                .map(|s| s.as_str().map(String::from).unwrap_or_else(|| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Store which leaf a decision followed, if its outcome context names one
+pub fn record_from_context(conn: &Connection, decision_id: &str, context: &Value, success: bool) -> Result<()> {
+    let Some(template_id) = context.get("template_id").and_then(|t| t.as_str()).filter(|t| !t.is_empty()) else {
+        return Ok(());
+    };
+    let steps = path_steps(context.get("template_path"));
+    let template = get_templates()
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| MindsError::NotFound(format!("template {}", template_id)))?;
+    let path = resolve_path(&template, &steps)?;
+    let leaf = path.iter().map(|i| i.to_string()).collect::<Vec<_>>().join("/");
+    conn.execute(
+        "INSERT OR REPLACE INTO template_outcomes (decision_id, template_id, leaf_path, success)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![decision_id, template_id, leaf, success as i32],
+    )?;
+    Ok(())
+}
+
+/// JSON Pointer to the option at `path`
+fn pointer(path: &[usize]) -> String {
+    path.iter()
+        .enumerate()
+        .map(|(depth, i)| format!("{}/options/{}", if depth == 0 { "/tree" } else { "/next" }, i))
+        .collect()
+}
+
+/// The leaf's headline: its recommendation up to the first sentence break
+fn headline(leaf: &LeafStats) -> String {
+    let text = leaf.recommendation.as_deref().unwrap_or(&leaf.label);
+    let end = text.find(['.', ':', '-']).unwrap_or(text.len());
+    text[..end].trim().to_string()
+}
+
+/// Review every template that has recorded outcomes
+pub fn analyze(conn: &Connection) -> Result<Vec<TemplateReport>> {
+    let mut tallies: BTreeMap<String, BTreeMap<String, (u32, u32)>> = BTreeMap::new();
+    let mut stmt = conn.prepare("SELECT template_id, leaf_path, success FROM template_outcomes")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, bool>(2)?)))?;
+    for row in rows {
+        let (template_id, leaf, success) = row?;
+        let tally = tallies.entry(template_id).or_default().entry(leaf).or_default();
+        if success { tally.0 += 1 } else { tally.1 += 1 }
+    }
+
+    let mut reports = Vec::new();
+    for template in get_templates() {
+        let Some(leaves) = tallies.get(&template.id) else { continue };
+        let tree = serde_json::to_value(&template)?;
+        let mut report = TemplateReport {
+            template_id: template.id.clone(),
+            name: template.name.clone(),
+            outcomes: 0,
+            success_rate: 0.0,
+            leaves: Vec::new(),
+            suggestions: Vec::new(),
+            patch: Vec::new(),
+        };
+        let mut successes = 0;
+        for (leaf, (ok, failed)) in leaves {
+            // Paths that no longer exist in the tree are stale; skip them
+            let path: Vec<usize> = match leaf.split('/').map(str::parse).collect() {
+                Ok(path) => path,
+                Err(_) => continue,
+            };
+            let pointer = pointer(&path);
+            let Some(option) = tree.pointer(&pointer) else { continue };
+            let stats = LeafStats {
+                pointer,
+                label: option["label"].as_str().unwrap_or_default().to_string(),
+                recommendation: option["recommendation"].as_str().map(String::from),
+                successes: *ok,
+                failures: *failed,
+            };
+            report.outcomes += ok + failed;
+            successes += ok;
+
+            let samples = ok + failed;
+            if samples >= MIN_SAMPLES && stats.failure_rate() >= FAILURE_THRESHOLD {
+                let rate = stats.failure_rate();
+                let message = format!(
+                    "the '{}' leaf fails {:.0}% of the time ({}/{}); consider tightening its conditions",
+                    headline(&stats),
+                    rate * 100.0,
+                    failed,
+                    samples
+                );
+                if let Some(recommendation) = &stats.recommendation {
+                    let target = format!("{}/recommendation", stats.pointer);
+                    report.patch.push(json!({"op": "test", "path": target, "value": recommendation}));
+                    report.patch.push(json!({
+                        "op": "replace",
+                        "path": target,
+                        "value": format!(
+                            "CAUTION: failed in {} of {} recorded outcomes. {}",
+                            failed, samples, recommendation
+                        ),
+                    }));
+                }
+                report.suggestions.push(MutationSuggestion {
+                    pointer: stats.pointer.clone(),
+                    message,
+                    failure_rate: rate,
+                    samples,
+                });
+            }
+            report.leaves.push(stats);
+        }
+        if report.outcomes == 0 {
+            continue;
+        }
+        report.success_rate = successes as f64 / report.outcomes as f64;
+        report.patch.push(json!({"op": "replace", "path": "/success_rate", "value": report.success_rate}));
+        report.patch.push(json!({"op": "replace", "path": "/times_used", "value": report.outcomes}));
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failing_leaf_gets_guarded_patch() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let template = get_templates().into_iter().find(|t| t.id == "rewrite-vs-refactor").unwrap();
+        let first = &template.tree.options[0];
+        let steps = vec![first.label.to_lowercase(), "0".to_string()];
+        assert_eq!(resolve_path(&template, &steps).unwrap(), vec![0, 0]);
+        assert!(resolve_path(&template, &steps[..1]).is_err());
+        assert!(resolve_path(&template, &["nope".to_string()]).is_err());
+
+        let context = json!({"template_id": "rewrite-vs-refactor", "template_path": [0, 0]});
+        for i in 0..10 {
+            record_from_context(&conn, &format!("d{}", i), &context, i < 3).unwrap();
+        }
+        let reports = analyze(&conn).unwrap();
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.outcomes, 10);
+        assert_eq!(report.suggestions.len(), 1);
+        assert!(report.suggestions[0].message.contains("'REFACTOR' leaf fails 70%"));
+
+        let leaf = "/tree/options/0/next/options/0/recommendation";
+        assert_eq!(report.patch[0]["op"], "test");
+        assert_eq!(report.patch[0]["path"], leaf);
+        assert_eq!(&serde_json::to_value(&template).unwrap().pointer(leaf).unwrap(), &&report.patch[0]["value"]);
+        assert!(report.patch[1]["value"].as_str().unwrap().starts_with("CAUTION: failed in 7 of 10"));
+    }
+}
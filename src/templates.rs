@@ -170,6 +170,39 @@ pub fn match_templates(question: &str) -> Vec<(DecisionTemplate, f64)> {
     matches
 }
 
+/// Every tension encoded across the templates, with the owning template ID
+pub fn all_tensions() -> Vec<(String, PrincipleTension)> {
+    get_templates()
+        .into_iter()
+        .flat_map(|t| {
+            let id = t.id;
+            t.tensions.into_iter().map(move |tension| (id.clone(), tension))
+        })
+        .collect()
+}
+
+/// Loose match between a template's principle label and a principle name
+///
+/// Case-insensitive, ignores parenthetical qualifiers ("80/20 Analysis (build
+/// high-value)"), and accepts containment either way so "YAGNI" matches
+/// "YAGNI - You Aren't Gonna Need It".
+pub fn principle_name_matches(template_name: &str, name: &str) -> bool {
+    let strip = |s: &str| {
+        s.split('(')
+            .next()
+            .unwrap_or(s)
+            .trim()
+            .to_lowercase()
+    };
+    let a = strip(template_name);
+    let b = strip(name);
+
+    if a.len() < 4 || b.len() < 4 {
+        return a == b;
+    }
+    a == b || a.contains(&b) || b.contains(&a)
+}
+
 // ============================================================================
 // TEMPLATE DEFINITIONS - These are the 10x differentiators
 // ============================================================================
@@ -1640,6 +1673,15 @@ fn performance_optimization() -> DecisionTemplate {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_principle_name_matches() {
+        assert!(principle_name_matches("YAGNI", "yagni"));
+        assert!(principle_name_matches("80/20 Analysis (build high-value)", "80/20 Analysis"));
+        assert!(principle_name_matches("Incremental Migration", "Incremental Migration Strategy"));
+        assert!(!principle_name_matches("YAGNI", "Optimize Early"));
+        assert!(all_tensions().iter().any(|(_, t)| t.principle_b == "YAGNI"));
+    }
+
     #[test]
     fn test_get_templates_returns_all_12() {
         let templates = get_templates();
@@ -0,0 +1,125 @@
+//! Test Fixtures
+//!
+//! With the `testing` feature, crates that embed 100minds can get a ready
+//! engine in one line instead of importing a corpus:
+//!
+//! ```rust,ignore
+//! let engine = minds_mcp::testing::fixture()?.into_zesty();
+//! let response = engine.counsel(minds_mcp::testing::SAMPLE_QUESTION, None)?;
+//! ```
+//!
+//! The database is in memory and the signing key is never written to disk,
+//! so nothing outlives the test. The crate's own benches use it too.
+
+use crate::convenience::ZestyEngine;
+use crate::counsel::CounselEngine;
+use crate::provenance::Provenance;
+use crate::error::Result;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+/// A question the sample corpus has plenty to say about
+pub const SAMPLE_QUESTION: &str = "Should we rewrite the legacy billing system or refactor incrementally?";
+
+/// (name, description, domain tags as JSON)
+pub type SamplePrinciple = (&'static str, &'static str, &'static str);
+
+/// (thinker id, name, domain, principles); principle ids are
+/// `<thinker id>-<name in lowercase, spaces as dashes>`
+pub const SAMPLE_CORPUS: &[(&str, &str, &str, &[SamplePrinciple])] = &[
+    ("fowler", "Martin Fowler", "software", &[
+        ("Strangler Fig", "Replace a legacy system incrementally by routing features to new code until the old system can be removed", r#"["architecture","legacy"]"#),
+        ("Refactor Before Adding Features", "Restructure existing code in small behaviour-preserving steps so the next change is easy", r#"["refactoring","legacy"]"#),
+        ("Monolith First", "Start with a well-modularised monolith and split services out once boundaries are proven", r#"["architecture"]"#),
+    ]),
+    ("feathers", "Michael Feathers", "software", &[
+        ("Characterization Tests", "Pin down what legacy code actually does with tests before changing it", r#"["testing","legacy"]"#),
+        ("Seams", "Find places where behaviour can change without editing the code there, to get tangled code under test", r#"["testing","refactoring"]"#),
+    ]),
+    ("brooks", "Fred Brooks", "software", &[
+        ("Brooks's Law", "Adding engineers to a late project makes it later because of ramp-up and communication cost", r#"["management"]"#),
+        ("Second System Effect", "A rewrite tends to be over-engineered with every feature left out of the first system", r#"["architecture","legacy"]"#),
+        ("No Silver Bullet", "No single technique gives an order-of-magnitude gain in productivity or reliability", r#"["architecture"]"#),
+    ]),
+    ("spolsky", "Joel Spolsky", "software", &[
+        ("Never Rewrite From Scratch", "Rewriting throws away years of bug fixes embedded in the old code", r#"["legacy","architecture"]"#),
+        ("Build vs Buy", "Build what is core to the business and buy what is not", r#"["strategy"]"#),
+    ]),
+    ("knuth", "Donald Knuth", "software", &[
+        ("Premature Optimization", "Measure before optimising; most time is spent in a small part of the code", r#"["performance"]"#),
+    ]),
+    ("beck", "Kent Beck", "software", &[
+        ("YAGNI", "Don't build capability until it is needed", r#"["architecture"]"#),
+        ("Make It Work, Make It Right, Make It Fast", "Get it correct first, then clean, then fast", r#"["performance","refactoring"]"#),
+        ("Small Safe Steps", "Change software in steps small enough that each can be verified and reverted", r#"["refactoring","delivery"]"#),
+    ]),
+    ("nygard", "Michael Nygard", "software", &[
+        ("Circuit Breaker", "Stop calling a failing dependency so failures don't cascade", r#"["reliability"]"#),
+        ("Timeouts Everywhere", "Every remote call needs a timeout or a slow dependency stalls the caller", r#"["reliability","performance"]"#),
+    ]),
+];
+
+/// An in-memory database seeded with [`SAMPLE_CORPUS`], and a throwaway key
+pub struct Fixture {
+    pub conn: Connection,
+    pub provenance: Provenance,
+}
+
+impl Fixture {
+    pub fn counsel_engine(&self) -> CounselEngine<'_> {
+        CounselEngine::new(&self.conn, &self.provenance)
+    }
+
+    pub fn into_zesty(self) -> ZestyEngine {
+        ZestyEngine::from_parts(self.conn, self.provenance)
+    }
+}
+
+/// A fresh [`Fixture`]
+pub fn fixture() -> Result<Fixture> {
+    let conn = crate::db::init_db(Path::new(":memory:"))?;
+    seed(&conn)?;
+    Ok(Fixture { conn, provenance: Provenance::ephemeral() })
+}
+
+/// Add [`SAMPLE_CORPUS`] to a database of your own
+pub fn seed(conn: &Connection) -> Result<()> {
+    for (thinker_id, name, domain, principles) in SAMPLE_CORPUS {
+        conn.execute("INSERT INTO thinkers (id, name, domain) VALUES (?1, ?2, ?3)", params![thinker_id, name, domain])?;
+        for (principle, description, tags) in *principles {
+            conn.execute(
+                "INSERT INTO principles (id, thinker_id, name, description, domain_tags) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![principle_id(thinker_id, principle), thinker_id, principle, description, tags],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// The id [`seed`] gives a sample principle
+pub fn principle_id(thinker_id: &str, name: &str) -> String {
+    format!("{}-{}", thinker_id, name.to_lowercase().replace(' ', "-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_counsels_and_learns_in_memory() {
+        let engine = fixture().unwrap().into_zesty();
+        let response = engine.counsel(SAMPLE_QUESTION, Some("architecture")).unwrap();
+        let cited = ZestyEngine::extract_principle_ids(&response);
+        assert!(!cited.is_empty() && cited.iter().all(|id| id.contains('-')));
+
+        engine.record_outcome(&response.decision_id.to_string(), true, &cited, "shipped").unwrap();
+        let (decided, succeeded): (i64, i64) = engine
+            .conn()
+            .query_row("SELECT COUNT(*), SUM(outcome_success) FROM decisions", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((decided, succeeded), (1, 1));
+        assert_eq!(principle_id("brooks", "Brooks's Law"), "brooks-brooks's-law");
+    }
+}
@@ -0,0 +1,230 @@
+//! Decision Themes
+//!
+//! "What do we keep deciding about?" Recorded decision questions are turned
+//! into vectors (sentence embeddings when the semantic engine is available,
+//! TF-IDF over their keywords otherwise), grouped with k-means, and each
+//! group is labelled by its most distinctive keywords. Per-theme outcome
+//! counts and success rates show where decisions keep going wrong.
+//!
+//! k-means starts from farthest-first seeds rather than random ones, so the
+//! same history always gives the same themes.
+
+use crate::embeddings::SemanticEngine;
+use crate::search;
+use crate::error::Result;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Most themes a report will have, whatever the history size
+pub const MAX_THEMES: usize = 8;
+
+/// Themes with at least this many outcomes and a success rate under
+/// [`STRUGGLE_RATE`] are flagged
+const STRUGGLE_OUTCOMES: usize = 3;
+const STRUGGLE_RATE: f64 = 0.5;
+
+const KMEANS_ITERATIONS: usize = 50;
+
+/// One cluster of similar decision questions
+#[derive(Debug, Clone, Serialize)]
+pub struct Theme {
+    /// Top keywords, joined ("cache / redis / latency")
+    pub label: String,
+    pub keywords: Vec<String>,
+    pub decisions: usize,
+    /// Decisions with a recorded outcome
+    pub outcomes: usize,
+    pub successes: usize,
+    /// `None` until an outcome is recorded
+    pub success_rate: Option<f64>,
+    /// Enough outcomes, mostly failures
+    pub struggling: bool,
+    /// A few of the theme's questions, most recent first
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ThemeReport {
+    pub decisions: usize,
+    /// "embeddings" or "keywords"
+    pub vectors: String,
+    /// Largest theme first
+    pub themes: Vec<Theme>,
+}
+
+struct Decision {
+    question: String,
+    outcome: Option<bool>,
+}
+
+/// Cluster the recorded decisions into `k` themes (by default about
+/// √(n/2), at most [`MAX_THEMES`])
+pub fn analyze(conn: &Connection, semantic: Option<&mut SemanticEngine>, k: Option<usize>) -> Result<ThemeReport> {
+    let decisions: Vec<Decision> = conn
+        .prepare("SELECT question, outcome_success FROM decisions ORDER BY created_at DESC")?
+        .query_map([], |row| {
+            Ok(Decision { question: row.get(0)?, outcome: row.get::<_, Option<i64>>(1)?.map(|s| s != 0) })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    if decisions.is_empty() {
+        return Ok(ThemeReport { decisions: 0, vectors: "keywords".to_string(), themes: Vec::new() });
+    }
+
+    let keywords: Vec<Vec<String>> = decisions.iter().map(|d| search::keywords(&d.question)).collect();
+    let idf = inverse_document_frequency(&keywords);
+    let (vectors, kind) = match semantic {
+        Some(engine) => {
+            let embedded: Result<Vec<Vec<f32>>, _> = decisions.iter().map(|d| engine.embed(&d.question)).collect();
+            (embedded?, "embeddings")
+        }
+        None => (tfidf_vectors(&keywords, &idf), "keywords"),
+    };
+
+    let k = k.unwrap_or_else(|| ((decisions.len() as f64 / 2.0).sqrt().round() as usize).clamp(1, MAX_THEMES));
+    let assignments = kmeans(&vectors, k.clamp(1, decisions.len()));
+
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, &cluster) in assignments.iter().enumerate() {
+        members.entry(cluster).or_default().push(i);
+    }
+    let mut themes: Vec<Theme> = members.values().map(|ids| theme(ids, &decisions, &keywords, &idf)).collect();
+    themes.sort_by(|a, b| b.decisions.cmp(&a.decisions).then_with(|| a.label.cmp(&b.label)));
+    Ok(ThemeReport { decisions: decisions.len(), vectors: kind.to_string(), themes })
+}
+
+fn theme(ids: &[usize], decisions: &[Decision], keywords: &[Vec<String>], idf: &HashMap<String, f32>) -> Theme {
+    let mut weight: HashMap<&str, f32> = HashMap::new();
+    for &i in ids {
+        for word in &keywords[i] {
+            *weight.entry(word).or_default() += idf.get(word).copied().unwrap_or(0.0);
+        }
+    }
+    let mut ranked: Vec<(&str, f32)> = weight.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let top: Vec<String> = ranked.iter().take(3).map(|(w, _)| w.to_string()).collect();
+
+    let outcomes: Vec<bool> = ids.iter().filter_map(|&i| decisions[i].outcome).collect();
+    let successes = outcomes.iter().filter(|&&s| s).count();
+    let success_rate = (!outcomes.is_empty()).then(|| successes as f64 / outcomes.len() as f64);
+    Theme {
+        label: if top.is_empty() { "(no keywords)".to_string() } else { top.join(" / ") },
+        keywords: top,
+        decisions: ids.len(),
+        outcomes: outcomes.len(),
+        successes,
+        success_rate,
+        struggling: outcomes.len() >= STRUGGLE_OUTCOMES && success_rate.is_some_and(|r| r < STRUGGLE_RATE),
+        examples: ids.iter().take(3).map(|&i| decisions[i].question.clone()).collect(),
+    }
+}
+
+fn inverse_document_frequency(documents: &[Vec<String>]) -> HashMap<String, f32> {
+    let mut df: HashMap<&str, usize> = HashMap::new();
+    for words in documents {
+        let mut seen: Vec<&str> = words.iter().map(String::as_str).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        for word in seen {
+            *df.entry(word).or_default() += 1;
+        }
+    }
+    let n = documents.len() as f32;
+    df.into_iter().map(|(word, count)| (word.to_string(), (n / count as f32).ln() + 1.0)).collect()
+}
+
+/// Unit-length TF-IDF vectors over the whole vocabulary
+fn tfidf_vectors(documents: &[Vec<String>], idf: &HashMap<String, f32>) -> Vec<Vec<f32>> {
+    let mut vocabulary: Vec<&String> = idf.keys().collect();
+    vocabulary.sort();
+    let index: HashMap<&String, usize> = vocabulary.iter().enumerate().map(|(i, w)| (*w, i)).collect();
+    documents
+        .iter()
+        .map(|words| {
+            let mut v = vec![0.0f32; vocabulary.len()];
+            for word in words {
+                v[index[word]] += idf[word];
+            }
+            SemanticEngine::l2_normalize(&v)
+        })
+        .collect()
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// Cluster index per point; seeds are the first point, then repeatedly the
+/// point farthest from every seed so far
+fn kmeans(points: &[Vec<f32>], k: usize) -> Vec<usize> {
+    let mut centroids: Vec<Vec<f32>> = vec![points[0].clone()];
+    while centroids.len() < k {
+        let farthest = (0..points.len())
+            .map(|i| (i, centroids.iter().map(|c| distance(&points[i], c)).fold(f32::MAX, f32::min)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        centroids.push(points[farthest].clone());
+    }
+
+    let nearest = |p: &[f32], centroids: &[Vec<f32>]| {
+        (0..centroids.len()).min_by(|&a, &b| distance(p, &centroids[a]).total_cmp(&distance(p, &centroids[b]))).unwrap_or(0)
+    };
+    let mut assignments: Vec<usize> = points.iter().map(|p| nearest(p, &centroids)).collect();
+    for _ in 0..KMEANS_ITERATIONS {
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Vec<f32>> = points.iter().zip(&assignments).filter(|(_, &a)| a == c).map(|(p, _)| p).collect();
+            if members.is_empty() {
+                continue;
+            }
+            for (d, value) in centroid.iter_mut().enumerate() {
+                *value = members.iter().map(|m| m[d]).sum::<f32>() / members.len() as f32;
+            }
+        }
+        let next: Vec<usize> = points.iter().map(|p| nearest(p, &centroids)).collect();
+        if next == assignments {
+            break;
+        }
+        assignments = next;
+    }
+    assignments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_themes_group_questions_and_flag_struggles() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let questions = [
+            ("Should we add a redis cache for the catalog?", Some(0)),
+            ("Is a redis cache worth it for session lookups?", Some(0)),
+            ("Should the redis cache be write-through?", Some(0)),
+            ("Do we need a redis cache in front of search?", Some(1)),
+            ("Should we split the monolith into microservices?", Some(1)),
+            ("Are microservices right for the billing monolith?", Some(1)),
+            ("Should payments leave the monolith as microservices?", None),
+            ("Is the monolith ready to become microservices?", None),
+        ];
+        for (i, (question, outcome)) in questions.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success, created_at)
+                 VALUES (?1, ?2, '{}', 'h', 's', 'k', ?3, datetime('now', ?4))",
+                rusqlite::params![format!("d{}", i), question, outcome, format!("-{} minutes", i)],
+            )
+            .unwrap();
+        }
+
+        let report = analyze(&conn, None, Some(2)).unwrap();
+        assert_eq!((report.decisions, report.vectors.as_str()), (8, "keywords"));
+        let cache = report.themes.iter().find(|t| t.keywords.contains(&"redis".to_string())).unwrap();
+        let split = report.themes.iter().find(|t| t.keywords.contains(&"microservices".to_string())).unwrap();
+        assert_eq!((cache.decisions, cache.outcomes, cache.successes), (4, 4, 1));
+        assert!(cache.struggling);
+        assert_eq!((split.decisions, split.outcomes, split.success_rate), (4, 2, Some(1.0)));
+        assert!(!split.struggling);
+        assert_eq!(split.examples[0], "Should we split the monolith into microservices?");
+    }
+}
@@ -0,0 +1,144 @@
+//! Follow-up Threads
+//!
+//! Agents often come back with "ok, but what if the team is only 2
+//! people?". A follow-up re-counsels the parent decision's question with
+//! the refinement added to its context, and is stored as its own decision
+//! linked to the parent (`decisions.parent_decision_id`). Its provenance
+//! chains from the parent's content hash rather than the newest decision,
+//! so a thread reads as one branch of the chain.
+//!
+//! Team size and deadline stated in the refinement replace the parent's,
+//! even explicit ones; everything else in the refinement is appended to
+//! the parent's notes.
+
+use crate::error::{MindsError, Result};
+use crate::types::CounselContext;
+use rusqlite::{params, Connection, OptionalExtension};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Longest parent chain walked when finding a thread's root
+const MAX_DEPTH: usize = 100;
+
+/// Arguments of `counsel_followup`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FollowupRequest {
+    /// Decision being refined
+    pub parent_decision_id: String,
+    /// What changed or what to consider, e.g. 'what if the team is only 2 people?'
+    pub refinement: String,
+    /// Optional explicit decision ID for the follow-up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision_id: Option<String>,
+}
+
+/// Where a follow-up sits in its thread
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadInfo {
+    pub parent_decision_id: String,
+    /// The decision that started the thread
+    pub root_decision_id: String,
+    /// 1 for a follow-up to the root, 2 for a follow-up to that, ...
+    pub turn: usize,
+    pub refinement: String,
+}
+
+/// A stored decision a follow-up builds on
+#[derive(Debug, Clone)]
+pub struct Parent {
+    pub question: String,
+    pub context: CounselContext,
+    pub content_hash: String,
+    pub thread: ThreadInfo,
+}
+
+/// Load the parent decision and place the follow-up in its thread
+pub fn parent(conn: &Connection, request: &FollowupRequest) -> Result<Parent> {
+    if request.refinement.trim().is_empty() {
+        return Err(MindsError::Validation("refinement must not be blank".to_string()));
+    }
+    let id = &request.parent_decision_id;
+    let (question, context_json, content_hash) = conn
+        .query_row(
+            "SELECT question, context_json, content_hash FROM decisions WHERE id = ?1",
+            [id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, String>(2)?)),
+        )
+        .optional()?
+        .ok_or_else(|| MindsError::NotFound(format!("decision {}", id)))?;
+    let context = context_json.and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default();
+
+    let mut root = id.clone();
+    let mut turn = 1;
+    while turn <= MAX_DEPTH {
+        let up: Option<String> = conn
+            .query_row("SELECT parent_decision_id FROM decisions WHERE id = ?1", [&root], |row| row.get(0))
+            .optional()?
+            .flatten();
+        match up {
+            Some(up) => {
+                root = up;
+                turn += 1;
+            }
+            None => break,
+        }
+    }
+    Ok(Parent {
+        question,
+        context,
+        content_hash,
+        thread: ThreadInfo {
+            parent_decision_id: id.clone(),
+            root_decision_id: root,
+            turn,
+            refinement: request.refinement.trim().to_string(),
+        },
+    })
+}
+
+/// The parent's context with the refinement added
+pub fn refine_context(parent: &CounselContext, refinement: &str) -> CounselContext {
+    let stated = CounselContext { notes: Some(refinement.to_string()), ..Default::default() };
+    let mut context = parent.clone();
+    context.notes = Some(match parent.notes.as_deref().map(str::trim) {
+        Some(notes) if !notes.is_empty() => format!("{}; {}", notes, refinement),
+        _ => refinement.to_string(),
+    });
+    if let Some(team_size) = stated.effective_team_size() {
+        context.team_size = Some(team_size);
+    }
+    if let Some(deadline_days) = stated.effective_deadline_days() {
+        context.deadline_days = Some(deadline_days);
+    }
+    context
+}
+
+/// Link a stored follow-up to its parent
+pub fn link(conn: &Connection, decision_id: &str, parent_decision_id: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE decisions SET parent_decision_id = ?2 WHERE id = ?1",
+        params![decision_id, parent_decision_id],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refinement_overrides_team_size_and_appends_notes() {
+        let parent = CounselContext {
+            notes: Some("legacy Rails app".to_string()),
+            team_size: Some(8),
+            deadline_days: Some(90),
+            ..Default::default()
+        };
+        let refined = refine_context(&parent, "ok, but what if the team is only 2 people?");
+        assert_eq!((refined.team_size, refined.deadline_days), (Some(2), Some(90)));
+        assert_eq!(refined.notes.as_deref(), Some("legacy Rails app; ok, but what if the team is only 2 people?"));
+
+        let refined = refine_context(&CounselContext::default(), "deadline in 2 weeks");
+        assert_eq!((refined.team_size, refined.deadline_days), (None, Some(14)));
+    }
+}
@@ -0,0 +1,311 @@
+//! Tradeoff Frontier
+//!
+//! "Speed vs quality" questions aren't asking which principle is right but
+//! how much of one objective to give up for the other. When a question
+//! names two objectives ("X vs Y", "X versus Y", "the tradeoff between X
+//! and Y"), or the request lists them in `context.objectives`, each cited
+//! principle is tagged with the objective it serves:
+//!
+//! - an objective's keywords are its own words plus, for well-known axes
+//!   (speed, quality, cost, ...), their usual vocabulary
+//! - a principle serves the objective whose keywords its name and
+//!   description use more; "both" on a tie, nothing with no hits
+//!
+//! The dominant tension is a template-encoded tension between principles on
+//! opposite sides if the response has one, else the most confident
+//! principle on each side. Each axis then gets a gain/lose line: favor it,
+//! and you follow its principles while giving up what the other side's
+//! principles protect.
+
+use crate::search;
+use crate::types::ResponseTension;
+use serde::{Deserialize, Serialize};
+
+/// Most words kept from each side of "X vs Y"
+const MAX_SIDE_WORDS: usize = 3;
+
+/// Well-known objectives and the word stems (prefixes) that signal them
+const AXES: [(&str, &[&str]); 9] = [
+    ("speed", &["speed", "fast", "quick", "time", "velocit", "ship", "deliver", "iterat", "launch", "deadlin"]),
+    ("quality", &["qualit", "correct", "reliab", "robust", "test", "clean", "craft", "defect", "bug", "maintain"]),
+    ("cost", &["cost", "cheap", "budget", "spend", "money", "pric", "saving", "expens"]),
+    ("performance", &["perform", "scal", "latenc", "throughput", "optimi", "effici"]),
+    ("simplicity", &["simpl", "minimal", "yagni", "complex", "small", "lean"]),
+    ("flexibility", &["flexib", "extens", "option", "general", "futur", "adapt"]),
+    ("security", &["secur", "privac", "risk", "safe", "threat", "attack"]),
+    ("innovation", &["innovat", "experiment", "novel", "explor", "bet"]),
+    ("stability", &["stabl", "predictab", "proven", "boring", "matur", "incremental"]),
+];
+
+/// One of the two objectives being weighed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Objective {
+    pub name: String,
+    /// Well-known axis the objective was recognised as, if any
+    pub axis: Option<String>,
+    /// Word stems counted as serving it
+    pub keywords: Vec<String>,
+}
+
+/// A principle cited in the response, as input to [`analyze`]
+#[derive(Debug, Clone)]
+pub struct CitedPrinciple {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub thinker: String,
+    pub confidence: f64,
+}
+
+/// Which objective a cited principle serves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrincipleObjective {
+    pub principle_id: String,
+    pub name: String,
+    pub thinker: String,
+    /// An objective's name, "both", or None when it speaks to neither
+    pub serves: Option<String>,
+}
+
+/// The pull between the two objectives that matters most here
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DominantTension {
+    /// Principle serving the first objective
+    pub principle_a: String,
+    /// Principle serving the second objective
+    pub principle_b: String,
+    pub thinker_a: String,
+    pub thinker_b: String,
+    /// Template encoding the tension, or "positions"
+    pub source: String,
+}
+
+/// What favoring one objective buys and costs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AxisSummary {
+    pub objective: String,
+    /// Principles serving it (followed if it's favored)
+    pub served_by: Vec<String>,
+    /// Summed confidence of those principles
+    pub weight: f64,
+    pub gain: String,
+    pub lose: String,
+}
+
+/// Two-objective view of a response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tradeoff {
+    pub objectives: Vec<Objective>,
+    pub principles: Vec<PrincipleObjective>,
+    pub dominant_tension: Option<DominantTension>,
+    /// One per objective, in order
+    pub axes: Vec<AxisSummary>,
+    /// Objective the cited principles weigh toward, if either
+    pub lean: Option<String>,
+}
+
+/// Last (or first) content words next to a "vs", up to a stopword
+fn side(words: impl Iterator<Item = String>) -> Vec<String> {
+    words
+        .take_while(|w| !search::is_stopword(w))
+        .take(MAX_SIDE_WORDS)
+        .collect()
+}
+
+fn pair(a: Vec<String>, b: Vec<String>) -> Option<(String, String)> {
+    (!a.is_empty() && !b.is_empty()).then(|| (a.join(" "), b.join(" ")))
+}
+
+/// The two objectives a question weighs, from "X vs Y", "X versus Y" or
+/// "tradeoff between X and Y"
+pub fn objectives_in(question: &str) -> Option<(String, String)> {
+    let lower = question.to_lowercase();
+    let words = |text: &str| search::tokens(text).collect::<Vec<_>>();
+    if let Some((before, after)) = [" vs. ", " vs ", " versus "].iter().find_map(|m| lower.split_once(m)) {
+        let mut left = side(words(before).into_iter().rev());
+        left.reverse();
+        return pair(left, side(words(after).into_iter()));
+    }
+    let (_, rest) = lower.split_once("trade")?;
+    let (_, rest) = rest.split_once(" between ")?;
+    let (a, b) = rest.split_once(" and ")?;
+    pair(side(words(a).into_iter()), side(words(b).into_iter()))
+}
+
+fn objective(name: &str) -> Objective {
+    let words: Vec<String> = search::tokens(name).filter(|t| !search::is_stopword(t)).collect();
+    let axis = AXES.iter().find(|(axis, keywords)| {
+        words.iter().any(|w| w == axis || keywords.iter().any(|k| w.starts_with(k)))
+    });
+    let mut keywords = words.clone();
+    if let Some((_, axis_keywords)) = axis {
+        keywords.extend(axis_keywords.iter().map(|k| k.to_string()));
+    }
+    keywords.sort();
+    keywords.dedup();
+    Objective { name: name.to_string(), axis: axis.map(|(a, _)| a.to_string()), keywords }
+}
+
+/// Words of `text` that signal the objective
+fn hits(objective: &Objective, text: &str) -> usize {
+    search::tokens(text)
+        .filter(|t| !search::is_stopword(t))
+        .filter(|t| {
+            let stem = search::stem(t);
+            objective.keywords.iter().any(|k| t.starts_with(k.as_str()) || stem.starts_with(k.as_str()))
+        })
+        .count()
+}
+
+fn names_list(names: &[String]) -> String {
+    match names.len() {
+        0 => "no cited principle".to_string(),
+        _ => names.join(", "),
+    }
+}
+
+/// Tag the cited principles by objective and summarise the tradeoff; None
+/// unless two objectives were given or the question names them
+pub fn analyze(
+    question: &str,
+    objectives: &[String],
+    cited: &[CitedPrinciple],
+    tensions: &[ResponseTension],
+) -> Option<Tradeoff> {
+    let (a, b) = match objectives {
+        [a, b] if !a.trim().is_empty() && !b.trim().is_empty() => (a.trim().to_string(), b.trim().to_string()),
+        _ => objectives_in(question)?,
+    };
+    let objectives = [objective(&a), objective(&b)];
+
+    let mut principles: Vec<PrincipleObjective> = Vec::new();
+    let mut weights = [0.0, 0.0];
+    let mut sides: [Vec<&CitedPrinciple>; 2] = [Vec::new(), Vec::new()];
+    for p in cited {
+        if principles.iter().any(|t| t.principle_id == p.id) {
+            continue;
+        }
+        let text = format!("{} {}", p.name, p.description);
+        let (ha, hb) = (hits(&objectives[0], &text), hits(&objectives[1], &text));
+        let serves = match ha.cmp(&hb) {
+            _ if ha + hb == 0 => None,
+            std::cmp::Ordering::Greater => Some(0),
+            std::cmp::Ordering::Less => Some(1),
+            std::cmp::Ordering::Equal => Some(2),
+        };
+        if let Some(i @ (0 | 1)) = serves {
+            weights[i] += p.confidence;
+            sides[i].push(p);
+        }
+        principles.push(PrincipleObjective {
+            principle_id: p.id.clone(),
+            name: p.name.clone(),
+            thinker: p.thinker.clone(),
+            serves: serves.map(|i| if i == 2 { "both".to_string() } else { objectives[i].name.clone() }),
+        });
+    }
+
+    let side_of = |name: &str| {
+        principles.iter().find(|t| t.name == name).and_then(|t| t.serves.clone())
+    };
+    let from_template = tensions.iter().find_map(|t| {
+        let (sa, sb) = (side_of(&t.principle_a)?, side_of(&t.principle_b)?);
+        let tension = |pa: &str, ta: &str, pb: &str, tb: &str| DominantTension {
+            principle_a: pa.to_string(),
+            principle_b: pb.to_string(),
+            thinker_a: ta.to_string(),
+            thinker_b: tb.to_string(),
+            source: t.source_template.clone(),
+        };
+        if sa == a && sb == b {
+            Some(tension(&t.principle_a, &t.thinker_a, &t.principle_b, &t.thinker_b))
+        } else if sa == b && sb == a {
+            Some(tension(&t.principle_b, &t.thinker_b, &t.principle_a, &t.thinker_a))
+        } else {
+            None
+        }
+    });
+    let strongest = |side: &[&CitedPrinciple]| {
+        side.iter().max_by(|x, y| x.confidence.total_cmp(&y.confidence)).map(|p| (*p).clone())
+    };
+    let dominant_tension = from_template.or_else(|| {
+        let (pa, pb) = (strongest(&sides[0])?, strongest(&sides[1])?);
+        Some(DominantTension {
+            principle_a: pa.name,
+            principle_b: pb.name,
+            thinker_a: pa.thinker,
+            thinker_b: pb.thinker,
+            source: "positions".to_string(),
+        })
+    });
+
+    let served: [Vec<String>; 2] = [0, 1].map(|i| sides[i].iter().map(|p| p.name.clone()).collect());
+    let axes = [0, 1]
+        .map(|i| {
+            let (this, other) = (&objectives[i].name, &objectives[1 - i].name);
+            AxisSummary {
+                objective: this.clone(),
+                served_by: served[i].clone(),
+                weight: weights[i],
+                gain: format!("More {}: follow {}", this, names_list(&served[i])),
+                lose: format!("Less {}: set aside {}", other, names_list(&served[1 - i])),
+            }
+        })
+        .to_vec();
+    let lean = match weights[0].total_cmp(&weights[1]) {
+        std::cmp::Ordering::Greater => Some(a.clone()),
+        std::cmp::Ordering::Less => Some(b.clone()),
+        std::cmp::Ordering::Equal => None,
+    };
+
+    Some(Tradeoff { objectives: objectives.to_vec(), principles, dominant_tension, axes, lean })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cited(name: &str, description: &str, confidence: f64) -> CitedPrinciple {
+        CitedPrinciple {
+            id: name.to_lowercase().replace(' ', "-"),
+            name: name.to_string(),
+            description: description.to_string(),
+            thinker: format!("{} author", name),
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_principles_are_tagged_by_the_objective_they_serve() {
+        assert_eq!(
+            objectives_in("Should we optimize for speed vs quality on the checkout rewrite?"),
+            Some(("speed".to_string(), "quality".to_string()))
+        );
+        assert_eq!(
+            objectives_in("What's the tradeoff between time to market and reliability?"),
+            Some(("time".to_string(), "reliability".to_string()))
+        );
+        assert_eq!(objectives_in("Should we rewrite the billing system?"), None);
+
+        let principles = [
+            cited("Ship Early", "Release fast and iterate on what users do", 0.8),
+            cited("Characterization Tests", "Pin down behaviour with tests before changing code", 0.6),
+            cited("Zero Defects", "Quality is built in; fix every bug before new work", 0.9),
+            cited("Conway's Law", "Systems mirror the organisation that builds them", 0.7),
+        ];
+        let tradeoff = analyze("Speed vs quality for the checkout rewrite?", &[], &principles, &[]).unwrap();
+        let serves: Vec<Option<&str>> = tradeoff.principles.iter().map(|p| p.serves.as_deref()).collect();
+        assert_eq!(serves, [Some("speed"), Some("quality"), Some("quality"), None]);
+        assert_eq!(tradeoff.objectives[0].axis.as_deref(), Some("speed"));
+        let tension = tradeoff.dominant_tension.as_ref().unwrap();
+        assert_eq!((tension.principle_a.as_str(), tension.principle_b.as_str()), ("Ship Early", "Zero Defects"));
+        assert_eq!(tradeoff.axes[0].gain, "More speed: follow Ship Early");
+        assert_eq!(tradeoff.axes[0].lose, "Less quality: set aside Characterization Tests, Zero Defects");
+        assert_eq!(tradeoff.lean.as_deref(), Some("quality"));
+
+        // Explicit objectives win over the question's wording
+        let explicit = ["cost".to_string(), "security".to_string()];
+        let tradeoff = analyze("Speed vs quality?", &explicit, &principles, &[]).unwrap();
+        assert_eq!(tradeoff.objectives[1].name, "security");
+    }
+}
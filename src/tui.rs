@@ -0,0 +1,427 @@
+//! Terminal Dashboard
+//!
+//! A full-screen ratatui view over the flywheel: ask counsel, browse recent
+//! decisions, close out pending outcomes straight from the list, and watch
+//! principle confidence move. Questions asked in one sitting share a
+//! [`crate::session::Session`], whose id is stored on their decisions.
+//!
+//! Keys:
+//!   a or /     ask counsel (Enter submits, Esc cancels)
+//!   j/k, ↑/↓   move through decisions
+//!   s / f      record success / failure for the selected decision
+//!   p          toggle pending-only decision list
+//!   r          refresh
+//!   q          quit
+
+use crate::counsel::CounselEngine;
+use crate::outcome::{get_learning_stats, record_outcome_v2, LearningStats};
+use crate::provenance::Provenance;
+use crate::session::Session;
+use crate::types::RecordOutcomeRequest;
+use crate::error::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+use rusqlite::Connection;
+
+/// Decisions shown in the list pane
+const DECISION_LIMIT: usize = 50;
+
+/// One row in the decisions pane
+#[derive(Debug, Clone)]
+pub struct DecisionRow {
+    pub id: String,
+    pub question: String,
+    pub created_at: String,
+    pub outcome: Option<bool>,
+}
+
+/// Everything one frame shows
+#[derive(Debug, Clone)]
+pub struct DashboardData {
+    pub decisions: Vec<DecisionRow>,
+    pub pending_count: i64,
+    pub top_principles: Vec<(String, f64)>,
+    pub bottom_principles: Vec<(String, f64)>,
+    pub stats: LearningStats,
+}
+
+fn principles_by_confidence(conn: &Connection, order: &str, limit: usize) -> Result<Vec<(String, f64)>> {
+    let sql = format!(
+        "SELECT p.name || ' (' || t.name || ')', p.learned_confidence
+         FROM principles p JOIN thinkers t ON t.id = p.thinker_id
+         ORDER BY p.learned_confidence {}, p.name
+         LIMIT ?1",
+        order
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// Gather one frame's data
+pub fn load_dashboard(conn: &Connection, pending_only: bool, limit: usize) -> Result<DashboardData> {
+    let filter = if pending_only {
+        "WHERE outcome_success IS NULL AND content_hash != 'outcome-only'"
+    } else {
+        "WHERE content_hash != 'outcome-only'"
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, question, created_at, outcome_success FROM decisions {}
+         ORDER BY created_at DESC, rowid DESC LIMIT ?1",
+        filter
+    ))?;
+    let decisions = stmt
+        .query_map([limit as i64], |row| {
+            Ok(DecisionRow {
+                id: row.get(0)?,
+                question: row.get(1)?,
+                created_at: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                outcome: row.get(3)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let pending_count = conn.query_row(
+        "SELECT COUNT(*) FROM decisions WHERE outcome_success IS NULL AND content_hash != 'outcome-only'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    Ok(DashboardData {
+        decisions,
+        pending_count,
+        top_principles: principles_by_confidence(conn, "DESC", 5)?,
+        bottom_principles: principles_by_confidence(conn, "ASC", 5)?,
+        stats: get_learning_stats(conn)?,
+    })
+}
+
+/// What a keypress asks the run loop to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum TuiAction {
+    None,
+    Quit,
+    Refresh,
+    Ask(String),
+    Record { decision_id: String, success: bool },
+}
+
+/// Dashboard state between frames
+#[derive(Debug, Clone)]
+pub struct TuiApp {
+    pub data: DashboardData,
+    pub list: ListState,
+    pub pending_only: bool,
+    /// `Some` while typing a counsel question
+    pub input: Option<String>,
+    /// Last counsel answer or status message
+    pub message: Vec<String>,
+}
+
+impl TuiApp {
+    pub fn new(data: DashboardData) -> Self {
+        let mut list = ListState::default();
+        if !data.decisions.is_empty() {
+            list.select(Some(0));
+        }
+        Self {
+            data,
+            list,
+            pending_only: false,
+            input: None,
+            message: vec![],
+        }
+    }
+
+    /// Swap in fresh data, keeping the selection in range
+    pub fn set_data(&mut self, data: DashboardData) {
+        let len = data.decisions.len();
+        self.data = data;
+        self.list.select(match (len, self.list.selected()) {
+            (0, _) => None,
+            (_, Some(i)) => Some(i.min(len - 1)),
+            (_, None) => Some(0),
+        });
+    }
+
+    fn selected_decision(&self) -> Option<&DecisionRow> {
+        self.list.selected().and_then(|i| self.data.decisions.get(i))
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> TuiAction {
+        if let Some(input) = &mut self.input {
+            match key {
+                KeyCode::Enter => {
+                    let question = input.trim().to_string();
+                    self.input = None;
+                    if !question.is_empty() {
+                        return TuiAction::Ask(question);
+                    }
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return TuiAction::None;
+        }
+
+        let len = self.data.decisions.len();
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => TuiAction::Quit,
+            KeyCode::Char('a') | KeyCode::Char('/') => {
+                self.input = Some(String::new());
+                TuiAction::None
+            }
+            KeyCode::Char('j') | KeyCode::Down if len > 0 => {
+                self.list.select(Some(self.list.selected().map_or(0, |i| (i + 1).min(len - 1))));
+                TuiAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up if len > 0 => {
+                self.list.select(Some(self.list.selected().map_or(0, |i| i.saturating_sub(1))));
+                TuiAction::None
+            }
+            KeyCode::Char(c @ ('s' | 'f')) => match self.selected_decision() {
+                Some(d) => TuiAction::Record {
+                    decision_id: d.id.clone(),
+                    success: c == 's',
+                },
+                None => TuiAction::None,
+            },
+            KeyCode::Char('p') => {
+                self.pending_only = !self.pending_only;
+                TuiAction::Refresh
+            }
+            KeyCode::Char('r') => TuiAction::Refresh,
+            _ => TuiAction::None,
+        }
+    }
+}
+
+/// Render one frame
+pub fn draw(frame: &mut Frame, app: &mut TuiApp) {
+    let [stats_area, main_area, counsel_area, input_area] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Min(8),
+        Constraint::Length(10),
+        Constraint::Length(3),
+    ])
+    .areas(frame.area());
+    let [decisions_area, principles_area] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(main_area);
+    let [top_area, bottom_area] =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(principles_area);
+
+    let s = &app.data.stats;
+    let stats = Paragraph::new(vec![
+        Line::from(format!(
+            "{} outcomes ({:.0}% success) · {} awaiting an outcome",
+            s.total_outcomes,
+            s.success_rate * 100.0,
+            app.data.pending_count
+        )),
+        Line::from(format!(
+            "{} confidence adjustments across {} principles",
+            s.total_adjustments, s.principles_with_learning
+        )),
+    ])
+    .block(Block::bordered().title(" 🔄 Flywheel "));
+    frame.render_widget(stats, stats_area);
+
+    let items: Vec<ListItem> = app
+        .data
+        .decisions
+        .iter()
+        .map(|d| {
+            let mark = match d.outcome {
+                Some(true) => "✅",
+                Some(false) => "❌",
+                None => "⏳",
+            };
+            let date = d.created_at.get(..10).unwrap_or(&d.created_at);
+            ListItem::new(format!("{} {} {}", mark, date, d.question))
+        })
+        .collect();
+    let title = if app.pending_only {
+        " ⏳ Pending outcomes (s/f to record) "
+    } else {
+        " 📋 Recent decisions (s/f to record) "
+    };
+    let list = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, decisions_area, &mut app.list);
+
+    let confidence_list = |rows: &[(String, f64)], title: &'static str| {
+        List::new(
+            rows.iter()
+                .map(|(name, c)| ListItem::new(format!("{:>3.0}% {}", c * 100.0, name)))
+                .collect::<Vec<_>>(),
+        )
+        .block(Block::bordered().title(title))
+    };
+    frame.render_widget(confidence_list(&app.data.top_principles, " 📈 Top principles "), top_area);
+    frame.render_widget(confidence_list(&app.data.bottom_principles, " 📉 Bottom principles "), bottom_area);
+
+    let counsel = Paragraph::new(app.message.iter().map(|l| Line::from(l.as_str())).collect::<Vec<_>>())
+        .wrap(Wrap { trim: true })
+        .block(Block::bordered().title(" 🧠 Counsel "));
+    frame.render_widget(counsel, counsel_area);
+
+    let input = match &app.input {
+        Some(text) => Paragraph::new(format!("{}▏", text)).block(Block::bordered().title(" Ask (Enter/Esc) ")),
+        None => Paragraph::new("a ask · j/k move · s/f success/failure · p pending · r refresh · q quit")
+            .block(Block::bordered()),
+    };
+    frame.render_widget(input, input_area);
+}
+
+/// Run the dashboard until the user quits
+pub fn run(conn: &Connection, provenance: &Provenance) -> Result<()> {
+    let engine = CounselEngine::new(conn, provenance);
+    let mut session = Session::new();
+    let mut app = TuiApp::new(load_dashboard(conn, false, DECISION_LIMIT)?);
+    let mut terminal = ratatui::try_init()?;
+
+    let result = (|| -> Result<()> {
+        loop {
+            terminal.draw(|frame| draw(frame, &mut app))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match app.handle_key(key.code) {
+                TuiAction::None => continue,
+                TuiAction::Quit => return Ok(()),
+                TuiAction::Refresh => {}
+                TuiAction::Ask(question) => {
+                    app.message = match engine.counsel(&session.request(&question)) {
+                        Ok(response) => {
+                            session.record(&response);
+                            let mut lines = vec![response.question.clone()];
+                            for p in &response.positions {
+                                lines.push(format!(
+                                    "{:?} · {} ({:.0}%): {}",
+                                    p.stance,
+                                    p.thinker,
+                                    p.confidence * 100.0,
+                                    p.argument
+                                ));
+                            }
+                            lines.push(format!("Challenge · {}", response.challenge.argument));
+                            lines
+                        }
+                        Err(e) => vec![format!("Error: {}", e)],
+                    };
+                }
+                TuiAction::Record { decision_id, success } => {
+                    let request = RecordOutcomeRequest {
+                        decision_id,
+                        success,
+                        notes: Some("Recorded from TUI".to_string()),
+                        principle_ids: vec![],
+                        domain: None,
+                        confidence_score: None,
+                        failure_stage: None,
+                        failure_reasons: Vec::new(),
+                        idempotency_key: None,
+                        actor_id: None,
+                        template_id: None,
+                        template_path: vec![],
+                    };
+                    app.message = vec![match record_outcome_v2(conn, &request) {
+                        Ok(result) => format!(
+                            "{} recorded · {} principles adjusted",
+                            if success { "✅ Success" } else { "❌ Failure" },
+                            result.principles_adjusted.len()
+                        ),
+                        Err(e) => format!("Error: {}", e),
+                    }];
+                }
+            }
+
+            app.set_data(load_dashboard(conn, app.pending_only, DECISION_LIMIT)?);
+        }
+    })();
+
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn app_with_decisions(conn: &Connection) -> TuiApp {
+        crate::db::insert_decision(conn, "d1", "Should we cache?", None, "{}", None, "h1", "sig", "pk").unwrap();
+        crate::db::insert_decision(conn, "d2", "Should we shard?", None, "{}", None, "h2", "sig", "pk").unwrap();
+        TuiApp::new(load_dashboard(conn, false, 10).unwrap())
+    }
+
+    #[test]
+    fn test_keys_drive_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let mut app = app_with_decisions(&conn);
+
+        assert_eq!(app.list.selected(), Some(0));
+        app.handle_key(KeyCode::Down);
+        app.handle_key(KeyCode::Down);
+        assert_eq!(app.list.selected(), Some(1));
+
+        let selected = app.data.decisions[1].id.clone();
+        assert_eq!(
+            app.handle_key(KeyCode::Char('f')),
+            TuiAction::Record { decision_id: selected, success: false }
+        );
+
+        // Typing a question swallows command keys until Enter
+        app.handle_key(KeyCode::Char('a'));
+        for c in "q?".chars() {
+            assert_eq!(app.handle_key(KeyCode::Char(c)), TuiAction::None);
+        }
+        assert_eq!(app.handle_key(KeyCode::Enter), TuiAction::Ask("q?".to_string()));
+
+        assert_eq!(app.handle_key(KeyCode::Char('p')), TuiAction::Refresh);
+        assert!(app.pending_only);
+        assert_eq!(app.handle_key(KeyCode::Char('q')), TuiAction::Quit);
+    }
+
+    #[test]
+    fn test_draw_shows_panes() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        let mut app = app_with_decisions(&conn);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        for expected in ["Flywheel", "Recent decisions", "Top principles", "Counsel", "Should we cache?"] {
+            assert!(screen.contains(expected), "missing {}", expected);
+        }
+    }
+}
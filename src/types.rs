@@ -6,6 +6,7 @@
 //! - Clear and simple (Dijkstra/Feynman)
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -56,13 +57,30 @@ pub struct CounselPosition {
     pub argument: String,
     pub principles_cited: Vec<String>,
     pub confidence: f64,
+    /// `confidence` as stated, before calibration (`None` when none was applied)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_confidence: Option<f64>,
     /// What would prove this position wrong (Popper)
     pub falsifiable_if: Option<String>,
 }
 
+/// Shape of [`CounselResponse`] as serialized now. Older shapes:
+///
+/// - 1: the debate itself (question, positions, challenge, summary, provenance)
+/// - 2: plus `principle_ids`, `urgency_adjustment` and `causal_hints` for swarms
+/// - 3: plus everything since, and `schema_version`
+pub const SCHEMA_VERSION: u32 = 3;
+
+fn current_schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
 /// Full counsel response with adversarial debate
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CounselResponse {
+    /// [`SCHEMA_VERSION`] when the response was built
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     pub decision_id: String,
     pub question: String,
     pub positions: Vec<CounselPosition>,
@@ -75,11 +93,146 @@ pub struct CounselResponse {
     #[serde(default)]
     pub principle_ids: Vec<String>,
     /// Urgency adjustment suggestion: "escalate" | "defer" | null
+    /// (the level of `urgency`, kept for clients that read the string)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub urgency_adjustment: Option<String>,
+    /// Urgency call with the signals behind it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub urgency: Option<crate::urgency::Urgency>,
     /// Causal reasoning for why these principles were selected
     #[serde(default)]
     pub causal_hints: Vec<String>,
+    /// Known tensions between recommended principles, with when-to-pick guidance
+    #[serde(default)]
+    pub tensions: Vec<ResponseTension>,
+    /// Curated relations among the cited principles: prerequisites, conflicts, refinements
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relations: Vec<crate::principle_graph::RelationNote>,
+    /// Past decisions like this one that have outcomes, and what they credited or blamed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub similar_past_decisions: Vec<crate::history::SimilarDecision>,
+    /// Sub-decisions when the question bundled several (empty for simple questions)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<CounselPart>,
+    /// True when steps were skipped to meet `latency_budget_ms`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+    /// Names of the skipped steps
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_steps: Vec<String>,
+    /// Organization policy applied to this response (pinned/banned principles)
+    #[serde(default, skip_serializing_if = "crate::policy::PolicyReport::is_empty")]
+    pub policy: crate::policy::PolicyReport,
+    /// Exploration settings used, and how each position's principle was picked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exploration: Option<ExplorationLog>,
+    /// Language the question was asked in, and the English text retrieval used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<crate::language::NormalizedQuery>,
+    /// Reranking of the top candidates, when enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank: Option<crate::search::RerankReport>,
+    /// Search mode this response was produced in ("fts_only" when the
+    /// embedding model isn't available)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<crate::embeddings::Capabilities>,
+    /// Depth picked for a `depth: "auto"` request, with the signals behind it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<crate::depth::DepthChoice>,
+    /// Parent and root decision when this is a follow-up (`counsel_followup`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread: Option<crate::thread::ThreadInfo>,
+    /// Cited principles tagged by objective, for two-objective ("X vs Y") questions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tradeoff: Option<crate::tradeoff::Tradeoff>,
+    /// Cost-of-delay worksheet, for prioritization questions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_of_delay: Option<crate::cost_of_delay::Worksheet>,
+}
+
+/// [`CounselResponse`] in schema version 1
+#[derive(Debug, Serialize)]
+pub struct CounselResponseV1<'a> {
+    pub decision_id: &'a str,
+    pub question: &'a str,
+    pub positions: &'a [CounselPosition],
+    pub challenge: &'a CounselPosition,
+    pub summary: &'a str,
+    pub provenance: &'a ProvenanceInfo,
+    pub created_at: DateTime<Utc>,
+}
+
+/// [`CounselResponse`] in schema version 2
+#[derive(Debug, Serialize)]
+pub struct CounselResponseV2<'a> {
+    #[serde(flatten)]
+    pub v1: CounselResponseV1<'a>,
+    pub principle_ids: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urgency_adjustment: Option<&'a str>,
+    pub causal_hints: &'a [String],
+}
+
+/// Exploration overrides for one counsel call; unset fields fall back to
+/// `[learning]` in config.toml
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExplorationSettings {
+    /// Chance each position is picked from the exploration zone (ranks 5-20)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epsilon: Option<f64>,
+    /// FG-TS optimism constant (c in bonus = c / sqrt(n))
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimism: Option<f64>,
+    /// Largest random score noise, in points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+}
+
+/// Exploration as it was applied to one counsel response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExplorationLog {
+    /// Learning policy that valued the arms ("fgts", "ucb1", ...)
+    pub policy: String,
+    pub epsilon: f64,
+    pub optimism: f64,
+    pub temperature: f64,
+    /// One entry per principle-backed position, in order
+    #[serde(default)]
+    pub selections: Vec<ExplorationSelection>,
+}
+
+/// How one position's principle was picked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExplorationSelection {
+    pub principle_id: String,
+    /// "greedy" | "explore" (epsilon pick) | "pinned"
+    pub strategy: String,
+    /// Chance the position's epsilon-greedy roll had of picking this
+    /// principle, given that call's noisy scores (absent in older logs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub propensity: Option<f64>,
+}
+
+/// One part of a compound decision, counseled on its own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CounselPart {
+    pub question: String,
+    pub positions: Vec<CounselPosition>,
+    pub challenge: CounselPosition,
+}
+
+/// A known tension between two principles recommended in the same response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseTension {
+    pub principle_a: String,
+    pub principle_b: String,
+    /// Thinkers whose positions cited each side
+    pub thinker_a: String,
+    pub thinker_b: String,
+    pub when_to_pick_a: String,
+    pub when_to_pick_b: String,
+    /// Template that encodes this tension
+    pub source_template: String,
 }
 
 /// Provenance information for audit trail
@@ -92,7 +245,7 @@ pub struct ProvenanceInfo {
 }
 
 /// Request for counsel
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CounselRequest {
     pub question: String,
     #[serde(default)]
@@ -104,7 +257,7 @@ pub struct CounselRequest {
 }
 
 /// Context for counsel request
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct CounselContext {
     /// Domain of the decision (e.g., "architecture", "hiring")
     pub domain: Option<String>,
@@ -115,9 +268,137 @@ pub struct CounselContext {
     /// Depth of analysis
     #[serde(default)]
     pub depth: CounselDepth,
+    /// Free-text background (e.g. "team of 3, deadline in 2 weeks, legacy Rails app")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Code or doc excerpts relevant to the decision
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snippets: Vec<ContextSnippet>,
+    /// Engineers working on this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_size: Option<u32>,
+    /// Days until the deadline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_days: Option<u32>,
+    /// Technologies in play (e.g. ["rust", "postgres"])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stack: Vec<String>,
+    /// Signals from scanning the codebase the decision is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<crate::context::RepoSignals>,
+    /// Caller's time budget; expensive steps that don't fit are skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_budget_ms: Option<u64>,
+    /// Only counsel from principles with at least one of these domain tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_tags: Vec<String>,
+    /// Never counsel from principles with any of these domain tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_tags: Vec<String>,
+    /// Agent or user asking; ranking uses what was learned from their outcomes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<String>,
+    /// Exploration overrides (epsilon, FG-TS optimism, score noise)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exploration: Option<ExplorationSettings>,
+    /// REPL/TUI session the question was asked in (see [`crate::session`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Two objectives to weigh (e.g. ["speed", "quality"]); read from "X vs Y"
+    /// questions when not given (see [`crate::tradeoff`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objectives: Vec<String>,
+    /// Filled cost-of-delay worksheet items, evaluated into the response's
+    /// worksheet (see [`crate::cost_of_delay`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delay_items: Vec<crate::cost_of_delay::DelayItem>,
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+/// A code or doc excerpt supplied as decision context
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContextSnippet {
+    /// Where it came from (file path, URL, doc title)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub content: String,
+}
+
+impl CounselContext {
+    /// All free-text context joined for keyword matching
+    pub fn text(&self) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        if let Some(notes) = &self.notes {
+            parts.push(notes);
+        }
+        parts.extend(self.constraints.iter().map(|c| c.as_str()));
+        parts.extend(self.stack.iter().map(|s| s.as_str()));
+        parts.extend(self.snippets.iter().map(|s| s.content.as_str()));
+        parts.join(" ")
+    }
+
+    /// Whether a principle with these domain tags passes the tag filters
+    pub fn admits_tags(&self, tags: &[String]) -> bool {
+        let has = |wanted: &[String]| tags.iter().any(|t| wanted.iter().any(|w| w.eq_ignore_ascii_case(t)));
+        (self.include_tags.is_empty() || has(&self.include_tags)) && !has(&self.exclude_tags)
+    }
+
+    /// Team size, explicit or parsed from notes/constraints ("team of 3", "5 engineers")
+    pub fn effective_team_size(&self) -> Option<u32> {
+        if self.team_size.is_some() {
+            return self.team_size;
+        }
+        let words = self.free_text_words();
+        let people = ["engineers", "engineer", "developers", "developer", "devs", "people"];
+        let team_of = words
+            .windows(3)
+            .find(|w| w[0] == "team" && w[1] == "of")
+            .and_then(|w| w[2].parse().ok());
+        team_of.or_else(|| {
+            words
+                .windows(2)
+                .find(|w| people.contains(&w[1].as_str()))
+                .and_then(|w| w[0].parse().ok())
+        })
+    }
+
+    /// Days to deadline, explicit or parsed from notes/constraints ("deadline in 2 weeks")
+    pub fn effective_deadline_days(&self) -> Option<u32> {
+        if self.deadline_days.is_some() {
+            return self.deadline_days;
+        }
+        let words = self.free_text_words();
+        let mentions_deadline = words
+            .iter()
+            .any(|w| ["deadline", "due", "ship", "launch", "release"].contains(&w.as_str()));
+        if !mentions_deadline {
+            return None;
+        }
+        words.windows(2).find_map(|w| {
+            let n: u32 = w[0].parse().ok()?;
+            match w[1].trim_end_matches('s') {
+                "day" => Some(n),
+                "week" => n.checked_mul(7),
+                "month" => n.checked_mul(30),
+                _ => None,
+            }
+        })
+    }
+
+    fn free_text_words(&self) -> Vec<String> {
+        let mut text = self.notes.clone().unwrap_or_default();
+        for c in &self.constraints {
+            text.push(' ');
+            text.push_str(c);
+        }
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(String::from)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum CounselDepth {
     /// Quick counsel - 3 positions
@@ -127,6 +408,21 @@ pub enum CounselDepth {
     Standard,
     /// Deep counsel - 6+ positions with extensive analysis
     Deep,
+    /// Pick Quick, Standard or Deep from the question's complexity
+    /// (see [`crate::depth`]; the pick is reported in the response)
+    Auto,
+}
+
+impl CounselDepth {
+    /// Principle-backed positions to generate; Auto is resolved before
+    /// positions are built and counts as Standard otherwise
+    pub fn positions(self) -> usize {
+        match self {
+            CounselDepth::Quick => 3,
+            CounselDepth::Standard | CounselDepth::Auto => 4,
+            CounselDepth::Deep => 6,
+        }
+    }
 }
 
 /// A recorded decision with full provenance
@@ -150,10 +446,13 @@ pub struct Outcome {
 }
 
 /// Request to record an outcome
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RecordOutcomeRequest {
+    /// The decision ID from a previous counsel call
     pub decision_id: String,
+    /// Whether the decision led to a successful outcome
     pub success: bool,
+    /// What happened
     pub notes: Option<String>,
     // === SWARM INTEGRATION FIELDS (v2) ===
     /// Principle IDs that were used in this decision
@@ -165,14 +464,43 @@ pub struct RecordOutcomeRequest {
     /// Worker's self-reported confidence (0.0-1.0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence_score: Option<f64>,
-    /// Failure stage if not success: "lint" | "types" | "build" | "test"
+    /// Failure stage if not success: "planning" | "implementation" |
+    /// "integration" | "operations" | "external" (older spellings such as
+    /// "test" or "reverted" map onto these, see `stats::failures`)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub failure_stage: Option<String>,
+    /// Reason codes narrowing the failure stage down ("underestimated", "outage", ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failure_reasons: Vec<String>,
+    /// Client-chosen key; a retried request with the same decision and key
+    /// is a duplicate, not a second outcome
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Agent or user the outcome belongs to; defaults to the decision's actor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<String>,
+    /// Decision template that was followed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<String>,
+    /// Option chosen at each question of the template, by label or index
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(schema_with = "template_path_schema")]
+    pub template_path: Vec<String>,
+}
+
+/// Tool callers may give template steps as 0-based indexes as well as labels
+fn template_path_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    serde_json::from_value(serde_json::json!({
+        "type": "array",
+        "items": { "type": ["string", "integer"] }
+    }))
+    .expect("valid schema")
 }
 
 /// Batch outcome recording for catch-up sync
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RecordOutcomesBatchRequest {
+    /// Outcomes to record, in order
     pub outcomes: Vec<RecordOutcomeRequest>,
 }
 
@@ -271,11 +599,13 @@ impl CounselResponse {
     ) -> Self {
         let summary = Self::generate_summary(&positions, &challenge);
 
-        // Extract principle IDs from positions
-        let principle_ids: Vec<String> = positions
-            .iter()
-            .flat_map(|p| p.principles_cited.clone())
-            .collect();
+        // Extract principle IDs from positions (a synthesis re-cites its inputs, so dedupe)
+        let mut principle_ids: Vec<String> = Vec::new();
+        for id in positions.iter().flat_map(|p| p.principles_cited.iter()) {
+            if !principle_ids.contains(id) {
+                principle_ids.push(id.clone());
+            }
+        }
 
         // Generate causal hints explaining why principles were selected
         let causal_hints: Vec<String> = positions
@@ -298,6 +628,7 @@ impl CounselResponse {
         let resolved_id = decision_id.unwrap_or_else(|| Uuid::new_v4().to_string());
 
         Self {
+            schema_version: SCHEMA_VERSION,
             decision_id: resolved_id,
             question,
             positions,
@@ -307,10 +638,65 @@ impl CounselResponse {
             created_at: Utc::now(),
             principle_ids,
             urgency_adjustment: None,
+            urgency: None,
             causal_hints,
+            tensions: Vec::new(),
+            relations: Vec::new(),
+            similar_past_decisions: Vec::new(),
+            parts: Vec::new(),
+            degraded: false,
+            skipped_steps: Vec::new(),
+            policy: Default::default(),
+            exploration: None,
+            language: None,
+            rerank: None,
+            capabilities: None,
+            depth: None,
+            thread: None,
+            tradeoff: None,
+            cost_of_delay: None,
         }
     }
 
+    /// Schema version 1 of this response
+    pub fn v1(&self) -> CounselResponseV1<'_> {
+        CounselResponseV1 {
+            decision_id: &self.decision_id,
+            question: &self.question,
+            positions: &self.positions,
+            challenge: &self.challenge,
+            summary: &self.summary,
+            provenance: &self.provenance,
+            created_at: self.created_at,
+        }
+    }
+
+    /// Schema version 2 of this response
+    pub fn v2(&self) -> CounselResponseV2<'_> {
+        CounselResponseV2 {
+            v1: self.v1(),
+            principle_ids: &self.principle_ids,
+            urgency_adjustment: self.urgency_adjustment.as_deref(),
+            causal_hints: &self.causal_hints,
+        }
+    }
+
+    /// This response as JSON in the shape of schema `version`, for clients
+    /// that parse an older one
+    pub fn render(&self, version: u32) -> crate::error::Result<serde_json::Value> {
+        Ok(match version {
+            1 => serde_json::to_value(self.v1())?,
+            2 => serde_json::to_value(self.v2())?,
+            SCHEMA_VERSION => serde_json::to_value(self)?,
+            _ => {
+                return Err(crate::error::MindsError::Validation(format!(
+                    "Unknown response version {} (1 to {})",
+                    version, SCHEMA_VERSION
+                )))
+            }
+        })
+    }
+
     fn generate_summary(positions: &[CounselPosition], challenge: &CounselPosition) -> String {
         let for_count = positions.iter().filter(|p| p.stance == Stance::For).count();
         let against_count = positions
@@ -372,6 +758,7 @@ mod tests {
             argument: format!("{} argues this position", thinker),
             principles_cited: principles.iter().map(|s| s.to_string()).collect(),
             confidence: 0.8,
+            raw_confidence: None,
             falsifiable_if: Some("If conditions change".to_string()),
         }
     }
@@ -409,6 +796,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_context_parses_team_and_deadline() {
+        let context = CounselContext {
+            notes: Some("Team of 3, deadline in 2 weeks".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(context.effective_team_size(), Some(3));
+        assert_eq!(context.effective_deadline_days(), Some(14));
+
+        let context = CounselContext {
+            constraints: vec!["12 engineers".to_string()],
+            team_size: None,
+            deadline_days: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(context.effective_team_size(), Some(12));
+        assert_eq!(context.effective_deadline_days(), Some(5));
+
+        // Too far out to count in days: no deadline rather than an overflow
+        let context = CounselContext {
+            notes: Some("deadline in 999999999 weeks".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(context.effective_deadline_days(), None);
+
+        // Old payloads without the new fields still deserialize
+        let old: CounselContext = serde_json::from_str(
+            r#"{"domain":null,"constraints":[],"prefer_thinkers":[],"depth":"standard"}"#,
+        )
+        .unwrap();
+        assert!(old.snippets.is_empty() && old.effective_team_size().is_none());
+    }
+
+    #[test]
+    fn test_each_schema_version_keeps_its_shape() {
+        let mut response = CounselResponse::new(
+            "Should we split the monolith?".to_string(),
+            vec![mock_position(Stance::For, "Sam Newman", vec!["bounded-contexts"])],
+            mock_position(Stance::Challenge, "Nassim Taleb", vec![]),
+            mock_provenance(),
+            Some("d-1".to_string()),
+        );
+        response.created_at = "2026-01-01T00:00:00Z".parse().unwrap();
+        response.urgency_adjustment = Some("escalate".to_string());
+        response.degraded = true;
+        let keys = |version| {
+            let rendered = response.render(version).unwrap();
+            rendered.as_object().unwrap().keys().cloned().collect::<Vec<_>>()
+        };
+
+        let v1 = ["challenge", "created_at", "decision_id", "positions", "provenance", "question", "summary"];
+        assert_eq!(keys(1), v1);
+        let v2 = [
+            "causal_hints", "challenge", "created_at", "decision_id", "positions", "principle_ids", "provenance", "question",
+            "summary", "urgency_adjustment",
+        ];
+        assert_eq!(keys(2), v2);
+        let v3 = keys(SCHEMA_VERSION);
+        assert!(v2.iter().all(|k| v3.contains(&k.to_string())));
+        assert!(v3.contains(&"schema_version".to_string()) && v3.contains(&"degraded".to_string()));
+        assert!(response.render(0).is_err() && response.render(SCHEMA_VERSION + 1).is_err());
+
+        let v1 = response.render(1).unwrap();
+        assert_eq!(v1["created_at"], "2026-01-01T00:00:00Z");
+        assert_eq!(v1["positions"][0]["principles_cited"], serde_json::json!(["bounded-contexts"]));
+        assert_eq!(v1["provenance"]["content_hash"], "abc123");
+
+        // Older shapes still read as responses
+        let old: CounselResponse = serde_json::from_value(response.render(2).unwrap()).unwrap();
+        assert_eq!((old.schema_version, old.urgency_adjustment.as_deref()), (SCHEMA_VERSION, Some("escalate")));
+    }
+
     #[test]
     fn test_stance_deserialization() {
         let for_stance: Stance = serde_json::from_str("\"for\"").unwrap();
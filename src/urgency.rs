@@ -0,0 +1,290 @@
+//! Urgency Detection
+//!
+//! Flags a counsel question for escalation (needs senior or human review)
+//! or deferral (can wait for more information). The classifier combines:
+//!
+//! - keyword hits in the question, from per-deployment lists
+//! - the counsel itself: low average confidence on a high-stakes question,
+//!   or FOR and AGAINST positions nearly tied
+//! - optionally, embedding similarity to escalate/defer example questions,
+//!   when the caller plugs in an embedder with [`UrgencyClassifier::with_embedder`]
+//!
+//! Keyword lists, exemplars and thresholds come from `[urgency]` in
+//! config.toml:
+//!
+//! ```toml
+//! [urgency]
+//! escalate_keywords = ["security", "outage", "hipaa"]
+//! escalate_keyword_threshold = 2
+//! escalate_exemplars = ["Customer data may have leaked"]
+//! similarity_threshold = 0.75
+//! ```
+
+use crate::types::{CounselPosition, Stance};
+use crate::error::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UrgencyLevel {
+    Escalate,
+    Defer,
+}
+
+impl UrgencyLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UrgencyLevel::Escalate => "escalate",
+            UrgencyLevel::Defer => "defer",
+        }
+    }
+}
+
+/// An urgency call and what triggered it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Urgency {
+    pub level: UrgencyLevel,
+    pub reasons: Vec<String>,
+}
+
+/// The `[urgency]` table of config.toml
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct UrgencyConfig {
+    pub escalate_keywords: Vec<String>,
+    pub defer_keywords: Vec<String>,
+    /// Keyword hits that escalate on their own
+    pub escalate_keyword_threshold: usize,
+    pub defer_keyword_threshold: usize,
+    /// Average position confidence below which any escalate hit escalates
+    pub low_confidence: f64,
+    /// FOR and AGAINST confidence totals closer than this are contentious...
+    pub contention_margin: f64,
+    /// ...once they add up to at least this much
+    pub contention_min_total: f64,
+    pub escalate_exemplars: Vec<String>,
+    pub defer_exemplars: Vec<String>,
+    /// Cosine similarity at which a question counts as like an exemplar
+    pub similarity_threshold: f32,
+}
+
+impl Default for UrgencyConfig {
+    fn default() -> Self {
+        let words = |list: &[&str]| list.iter().map(|w| w.to_string()).collect();
+        UrgencyConfig {
+            escalate_keywords: words(&[
+                "security", "vulnerable", "breach", "hack", "data loss", "corruption", "production down",
+                "breaking change", "backwards compat", "legal", "compliance", "gdpr", "pii", "money",
+                "billing", "payment", "deadline", "blocker", "critical",
+            ]),
+            defer_keywords: words(&[
+                "future", "eventually", "someday", "maybe", "nice to have", "phase 2", "later",
+                "considering", "thinking about", "exploring", "research", "spike", "poc", "prototype",
+            ]),
+            escalate_keyword_threshold: 2,
+            defer_keyword_threshold: 2,
+            low_confidence: 0.5,
+            contention_margin: 0.2,
+            contention_min_total: 1.0,
+            escalate_exemplars: words(&[
+                "Customer data may have been exposed",
+                "Production is down and users can't log in",
+                "This change will break existing API clients",
+            ]),
+            defer_exemplars: words(&[
+                "Should we look into this someday when we have time?",
+                "We're exploring ideas for a later phase",
+            ]),
+            similarity_threshold: 0.75,
+        }
+    }
+}
+
+impl UrgencyConfig {
+    /// Read `[urgency]` from the config.toml beside the database; a missing file or table gives the defaults
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        crate::config::section(conn, "urgency")
+    }
+}
+
+/// Text → embedding; `None` when the text can't be embedded
+pub type EmbedFn = Arc<dyn Fn(&str) -> Option<Vec<f32>> + Send + Sync>;
+
+/// An exemplar question and its similarity to the one being classified
+type ExemplarMatch = (String, f32);
+
+struct Exemplars {
+    embed: EmbedFn,
+    escalate: Vec<(String, Vec<f32>)>,
+    defer: Vec<(String, Vec<f32>)>,
+}
+
+pub struct UrgencyClassifier {
+    config: UrgencyConfig,
+    exemplars: Option<Exemplars>,
+}
+
+impl UrgencyClassifier {
+    pub fn new(config: UrgencyConfig) -> Self {
+        UrgencyClassifier { config, exemplars: None }
+    }
+
+    /// Also score questions by similarity to the configured exemplars
+    pub fn with_embedder(mut self, embed: EmbedFn) -> Self {
+        let embed_all = |texts: &[String]| -> Vec<(String, Vec<f32>)> {
+            texts.iter().filter_map(|t| embed(t).map(|v| (t.clone(), v))).collect()
+        };
+        let escalate = embed_all(&self.config.escalate_exemplars);
+        let defer = embed_all(&self.config.defer_exemplars);
+        self.exemplars = Some(Exemplars { embed, escalate, defer });
+        self
+    }
+
+    pub fn config(&self) -> &UrgencyConfig {
+        &self.config
+    }
+
+    /// The closest exemplar of each kind at or above the similarity threshold
+    fn similar_exemplars(&self, question: &str) -> (Option<ExemplarMatch>, Option<ExemplarMatch>) {
+        let Some(exemplars) = &self.exemplars else { return (None, None) };
+        let Some(query) = (exemplars.embed)(question) else { return (None, None) };
+        let best = |list: &[(String, Vec<f32>)]| {
+            list.iter()
+                .map(|(text, v)| (text.clone(), crate::embeddings::SemanticEngine::cosine_similarity(&query, v)))
+                .filter(|(_, sim)| *sim >= self.config.similarity_threshold)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+        };
+        (best(&exemplars.escalate), best(&exemplars.defer))
+    }
+
+    /// Escalate, defer, or neither (`None`)
+    pub fn classify(&self, question: &str, positions: &[CounselPosition]) -> Option<Urgency> {
+        let config = &self.config;
+        let q = question.to_lowercase();
+        let hits = |keywords: &[String]| -> Vec<String> {
+            keywords.iter().filter(|kw| q.contains(&kw.to_lowercase())).cloned().collect()
+        };
+        let escalate_hits = hits(&config.escalate_keywords);
+        let defer_hits = hits(&config.defer_keywords);
+        let (like_escalate, like_defer) = self.similar_exemplars(question);
+        let like_escalate = like_escalate.filter(|e| like_defer.as_ref().is_none_or(|d| e.1 > d.1));
+        let like_defer = like_defer.filter(|_| like_escalate.is_none());
+
+        // An exemplar match counts as one more keyword hit
+        let escalate_score = escalate_hits.len() + like_escalate.is_some() as usize;
+        let defer_score = defer_hits.len() + like_defer.is_some() as usize;
+        let mut escalate_reasons = Vec::new();
+        if !escalate_hits.is_empty() {
+            escalate_reasons.push(format!("escalation keywords: {}", escalate_hits.join(", ")));
+        }
+        if let Some((text, sim)) = &like_escalate {
+            escalate_reasons.push(format!("similar to escalation example \"{}\" ({:.2})", text, sim));
+        }
+        let escalate = |mut reasons: Vec<String>, extra: Option<String>| {
+            reasons.extend(extra);
+            Some(Urgency { level: UrgencyLevel::Escalate, reasons })
+        };
+
+        let avg_confidence = if positions.is_empty() {
+            0.5
+        } else {
+            positions.iter().map(|p| p.confidence).sum::<f64>() / positions.len() as f64
+        };
+
+        // Low confidence + high stakes = escalate
+        if avg_confidence < config.low_confidence && escalate_score >= 1 {
+            let why = format!("average position confidence {:.2} is below {:.2}", avg_confidence, config.low_confidence);
+            return escalate(escalate_reasons, Some(why));
+        }
+
+        // Enough high-stakes signals escalate whatever the confidence
+        if escalate_score >= config.escalate_keyword_threshold {
+            return escalate(escalate_reasons, None);
+        }
+
+        if defer_score >= config.defer_keyword_threshold {
+            let mut reasons = Vec::new();
+            if !defer_hits.is_empty() {
+                reasons.push(format!("deferral keywords: {}", defer_hits.join(", ")));
+            }
+            if let Some((text, sim)) = &like_defer {
+                reasons.push(format!("similar to deferral example \"{}\" ({:.2})", text, sim));
+            }
+            return Some(Urgency { level: UrgencyLevel::Defer, reasons });
+        }
+
+        // FOR and AGAINST with similar confidence = contentious, escalate
+        let total = |stance: Stance| -> Option<f64> {
+            let mut matching = positions.iter().filter(|p| p.stance == stance).peekable();
+            matching.peek()?;
+            Some(matching.map(|p| p.confidence).sum())
+        };
+        if let (Some(for_confidence), Some(against_confidence)) = (total(Stance::For), total(Stance::Against)) {
+            let diff = (for_confidence - against_confidence).abs();
+            if diff < config.contention_margin && for_confidence + against_confidence > config.contention_min_total {
+                let why = format!(
+                    "FOR ({:.2}) and AGAINST ({:.2}) are within {:.2}: a contested call",
+                    for_confidence, against_confidence, config.contention_margin
+                );
+                return escalate(Vec::new(), Some(why));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(stance: Stance, confidence: f64) -> CounselPosition {
+        CounselPosition {
+            thinker: "Test".to_string(),
+            thinker_id: "test".to_string(),
+            stance,
+            argument: "Test argument".to_string(),
+            principles_cited: vec![],
+            confidence,
+            raw_confidence: None,
+            falsifiable_if: None,
+        }
+    }
+
+    #[test]
+    fn test_thresholds_and_keywords_are_configurable() {
+        let positions = vec![position(Stance::For, 0.8)];
+        let question = "Do we need HIPAA review before launch?";
+        assert_eq!(UrgencyClassifier::new(UrgencyConfig::default()).classify(question, &positions), None);
+
+        let config: UrgencyConfig =
+            toml::from_str("escalate_keywords = [\"hipaa\"]\nescalate_keyword_threshold = 1\n").unwrap();
+        assert_eq!(config.defer_keyword_threshold, 2);
+        let urgency = UrgencyClassifier::new(config).classify(question, &positions).unwrap();
+        assert_eq!(urgency.level, UrgencyLevel::Escalate);
+        assert_eq!(urgency.reasons, vec!["escalation keywords: hipaa"]);
+    }
+
+    #[test]
+    fn test_exemplar_similarity_adds_a_signal() {
+        // Toy embedder: one dimension per word family
+        let embed: EmbedFn = Arc::new(|text: &str| {
+            let t = text.to_lowercase();
+            Some(vec![
+                (t.contains("leak") || t.contains("exposed")) as u8 as f32,
+                (t.contains("someday") || t.contains("later")) as u8 as f32,
+                0.1,
+            ])
+        });
+        let classifier = UrgencyClassifier::new(UrgencyConfig::default()).with_embedder(embed);
+        let positions = vec![position(Stance::For, 0.8)];
+
+        // One keyword plus a close exemplar reaches the default threshold of two
+        let urgency = classifier.classify("A security token leaked in the logs", &positions).unwrap();
+        assert_eq!(urgency.level, UrgencyLevel::Escalate);
+        assert!(urgency.reasons[1].starts_with("similar to escalation example \"Customer data may have been exposed\""));
+        assert_eq!(classifier.classify("A security review is scheduled", &positions), None);
+    }
+}
@@ -0,0 +1,343 @@
+//! Tool Argument Validation
+//!
+//! Checks a tool call's arguments against the tool's `inputSchema` before
+//! its handler runs. A malformed call then fails with one JSON-RPC error
+//! naming every missing or invalid field, instead of running on defaults
+//! (an empty question, `success: false`).
+//!
+//! Only the JSON Schema keywords the tool schemas use are checked: `type`,
+//! `required`, `properties`, `items`, `enum`, `anyOf`/`oneOf` and
+//! `minimum`. Required strings must also be non-blank.
+
+use crate::error::MindsError;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt;
+
+/// JSON-RPC "Invalid params"
+pub const INVALID_PARAMS: i64 = -32602;
+/// JSON-RPC implementation-defined server error, for everything else
+pub const SERVER_ERROR: i64 = -32000;
+
+/// One problem with one argument
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    /// Path to the argument, e.g. `snippets[0].content`
+    pub field: String,
+    pub problem: String,
+}
+
+/// Everything wrong with a tool call's arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub tool: String,
+    pub errors: Vec<FieldError>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problems: Vec<String> = self.errors.iter().map(|e| format!("{} {}", e.field, e.problem)).collect();
+        write!(f, "Invalid arguments for {}: {}", self.tool, problems.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl ValidationError {
+    /// The JSON-RPC `error` object for this failure
+    pub fn to_jsonrpc(&self) -> Value {
+        json!({
+            "code": INVALID_PARAMS,
+            "message": self.to_string(),
+            "data": {"tool": self.tool, "errors": self.errors},
+        })
+    }
+}
+
+/// The JSON-RPC `error` object for a failed call: arguments rejected by the
+/// schema or by the library ([`MindsError::Validation`]) are invalid params
+pub fn jsonrpc_error(error: &(dyn std::error::Error + 'static)) -> Value {
+    if let Some(invalid) = error.downcast_ref::<ValidationError>() {
+        return invalid.to_jsonrpc();
+    }
+    let code = match error.downcast_ref::<MindsError>() {
+        Some(MindsError::Validation(_)) => INVALID_PARAMS,
+        Some(MindsError::Other(inner)) => return jsonrpc_error(inner.as_ref()),
+        _ => SERVER_ERROR,
+    };
+    json!({"code": code, "message": error.to_string()})
+}
+
+/// Check `args` against a tool's input schema
+pub fn validate(tool: &str, schema: &Value, args: &Value) -> Result<(), ValidationError> {
+    let mut errors = Vec::new();
+    if args.is_object() {
+        check(schema, args, "", &mut errors);
+    } else {
+        errors.push(FieldError {
+            field: "arguments".to_string(),
+            problem: format!("must be an object (got {})", type_name(args)),
+        });
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError { tool: tool.to_string(), errors })
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn has_type(value: &Value, wanted: &str) -> bool {
+    let actual = type_name(value);
+    actual == wanted || (wanted == "number" && actual == "integer")
+}
+
+fn child(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Every `enum` value reachable through `anyOf`/`oneOf`
+fn allowed_values(schema: &Value) -> Vec<String> {
+    let mut values: Vec<String> = schema
+        .get("enum")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .map(|v| v.as_str().map(String::from).unwrap_or_else(|| v.to_string()))
+        .collect();
+    for key in ["anyOf", "oneOf"] {
+        for branch in schema.get(key).and_then(|b| b.as_array()).into_iter().flatten() {
+            values.extend(allowed_values(branch));
+        }
+    }
+    values
+}
+
+fn check(schema: &Value, value: &Value, path: &str, errors: &mut Vec<FieldError>) {
+    let mut fail = |problem: String| errors.push(FieldError { field: path.to_string(), problem });
+
+    let branches: Vec<&Value> = ["anyOf", "oneOf"]
+        .iter()
+        .filter_map(|key| schema.get(*key).and_then(|b| b.as_array()))
+        .flatten()
+        .collect();
+    if !branches.is_empty() {
+        let matches = branches.iter().any(|branch| {
+            let mut branch_errors = Vec::new();
+            check(branch, value, path, &mut branch_errors);
+            branch_errors.is_empty()
+        });
+        if !matches {
+            let allowed = allowed_values(schema);
+            if allowed.is_empty() {
+                fail(format!("does not match any allowed form (got {})", type_name(value)));
+            } else {
+                fail(format!("must be one of: {}", allowed.join(", ")));
+            }
+            return;
+        }
+    }
+
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(|t| t.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+        let article = |t: &str| if t.starts_with(['a', 'e', 'i', 'o', 'u']) { "an" } else { "a" };
+        let wanted: Vec<String> = types.iter().filter(|t| **t != "null").map(|t| format!("{} {}", article(t), t)).collect();
+        fail(format!("must be {} (got {})", wanted.join(" or "), type_name(value)));
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            fail(format!("must be one of: {}", allowed_values(schema).join(", ")));
+            return;
+        }
+    }
+    if let (Some(minimum), Some(n)) = (schema.get("minimum").and_then(|m| m.as_f64()), value.as_f64()) {
+        if n < minimum {
+            fail(format!("must be at least {}", minimum));
+        }
+    }
+    if schema.get("format").and_then(|f| f.as_str()) == Some("uint32") && value.as_f64().is_some_and(|n| n > u32::MAX as f64) {
+        fail(format!("must be at most {}", u32::MAX));
+    }
+
+    match value {
+        Value::Object(fields) => {
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            let required: Vec<&str> =
+                schema.get("required").and_then(|r| r.as_array()).into_iter().flatten().filter_map(|r| r.as_str()).collect();
+            for &name in &required {
+                match fields.get(name) {
+                    None | Some(Value::Null) => errors.push(FieldError {
+                        field: child(path, name),
+                        problem: "is required".to_string(),
+                    }),
+                    Some(Value::String(s)) if s.trim().is_empty() => errors.push(FieldError {
+                        field: child(path, name),
+                        problem: "must not be blank".to_string(),
+                    }),
+                    _ => {}
+                }
+            }
+            for (name, field) in fields {
+                // A null or blank required field was already reported
+                let reported = field.is_null() || field.as_str().is_some_and(|s| s.trim().is_empty());
+                if reported && required.contains(&name.as_str()) {
+                    continue;
+                }
+                if let Some(field_schema) = properties.and_then(|p| p.get(name)) {
+                    check(field_schema, field, &child(path, name), errors);
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::{tool_schema, CounselArgs};
+    use crate::types::RecordOutcomeRequest;
+    use proptest::prelude::*;
+
+    fn fields(result: Result<(), ValidationError>) -> Vec<String> {
+        result.err().map(|e| e.errors.into_iter().map(|e| e.field).collect()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_errors_name_every_bad_field() {
+        let schema = tool_schema("counsel").unwrap();
+        let err = validate(
+            "counsel",
+            &schema,
+            &json!({"question": "  ", "depth": "forever", "team_size": -2, "snippets": [{"source": 1}]}),
+        )
+        .unwrap_err();
+        let problems: Vec<(&str, &str)> = err.errors.iter().map(|e| (e.field.as_str(), e.problem.as_str())).collect();
+        assert!(problems.contains(&("question", "must not be blank")));
+        assert!(problems.contains(&("depth", "must be one of: quick, standard, deep, auto")));
+        assert!(problems.contains(&("team_size", "must be at least 0")));
+        assert!(problems.contains(&("snippets[0].content", "is required")));
+        assert!(problems.contains(&("snippets[0].source", "must be a string (got integer)")));
+
+        let rpc = err.to_jsonrpc();
+        assert_eq!(rpc["code"], INVALID_PARAMS);
+        assert_eq!(rpc["data"]["tool"], "counsel");
+        assert!(rpc["message"].as_str().unwrap().starts_with("Invalid arguments for counsel: "));
+
+        let outcome = tool_schema("record_outcome").unwrap();
+        assert_eq!(fields(validate("record_outcome", &outcome, &json!({"decision_id": "d-1"}))), ["success"]);
+        assert_eq!(fields(validate("record_outcome", &outcome, &json!(["d-1", true]))), ["arguments"]);
+    }
+
+    #[test]
+    fn test_blank_required_field_is_one_error() {
+        let err = validate("counsel", &tool_schema("counsel").unwrap(), &json!({"question": ""})).unwrap_err();
+        assert_eq!(err.errors, [FieldError { field: "question".to_string(), problem: "must not be blank".to_string() }]);
+    }
+
+    #[test]
+    fn test_server_reports_invalid_input_as_invalid_params() {
+        let schema = tool_schema("counsel").unwrap();
+        let invalid = validate("counsel", &schema, &json!({"question": ""})).unwrap_err();
+        assert_eq!(jsonrpc_error(&invalid)["code"], INVALID_PARAMS);
+        let wrapped = MindsError::from(anyhow::Error::new(invalid));
+        assert_eq!(jsonrpc_error(&wrapped)["code"], INVALID_PARAMS);
+
+        // Passed the schema, rejected by the library
+        let request: RecordOutcomeRequest =
+            serde_json::from_value(json!({"decision_id": "d-1", "success": true, "confidence_score": 2.0})).unwrap();
+        let rejected = anyhow::Error::new(crate::outcome::validate_outcome_request(&request).unwrap_err());
+        let rpc = jsonrpc_error(rejected.as_ref());
+        assert_eq!(rpc["code"], INVALID_PARAMS);
+        assert_eq!(rpc["message"], "invalid input: confidence_score 2 is outside 0.0-1.0");
+
+        let missing = MindsError::NotFound("decision d-9".to_string());
+        assert_eq!(jsonrpc_error(&missing)["code"], SERVER_ERROR);
+        assert_eq!(jsonrpc_error(anyhow::anyhow!("disk full").as_ref())["code"], SERVER_ERROR);
+    }
+
+    fn json_value() -> impl Strategy<Value = Value> {
+        let key = prop_oneof![
+            Just("question".to_string()),
+            Just("depth".to_string()),
+            Just("snippets".to_string()),
+            Just("content".to_string()),
+            Just("team_size".to_string()),
+            "[a-z_]{1,10}",
+        ];
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::from),
+            any::<i64>().prop_map(Value::from),
+            any::<f64>().prop_map(Value::from),
+            ".{0,12}".prop_map(Value::from),
+        ];
+        leaf.prop_recursive(3, 24, 4, move |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+                prop::collection::vec((key.clone(), inner), 0..4).prop_map(|kv| Value::Object(kv.into_iter().collect())),
+            ]
+        })
+    }
+
+    proptest! {
+        /// Whatever arrives, validation decides without panicking, and
+        /// anything it accepts the handler's parser accepts too
+        #[test]
+        fn prop_accepted_counsel_args_parse(args in json_value()) {
+            let schema = tool_schema("counsel").unwrap();
+            if validate("counsel", &schema, &args).is_ok() {
+                prop_assert!(serde_json::from_value::<CounselArgs>(args).is_ok());
+            }
+        }
+
+        #[test]
+        fn prop_well_formed_outcomes_round_trip(
+            decision_id in "[a-z0-9-]{1,16}",
+            success in any::<bool>(),
+            notes in proptest::option::of(".{0,20}"),
+            principle_ids in prop::collection::vec("[a-z-]{1,10}", 0..4),
+        ) {
+            let schema = tool_schema("record_outcome").unwrap();
+            let args = json!({"decision_id": decision_id, "success": success, "notes": notes, "principle_ids": principle_ids});
+            prop_assert!(validate("record_outcome", &schema, &args).is_ok());
+            let request: RecordOutcomeRequest = serde_json::from_value(args).unwrap();
+            prop_assert_eq!((request.decision_id, request.success, request.principle_ids), (decision_id, success, principle_ids));
+        }
+
+        #[test]
+        fn prop_non_boolean_success_is_rejected(success in json_value().prop_filter("not a bool", |v| !v.is_boolean())) {
+            let schema = tool_schema("record_outcome").unwrap();
+            let errors = fields(validate("record_outcome", &schema, &json!({"decision_id": "d-1", "success": success})));
+            prop_assert_eq!(errors, vec!["success".to_string()]);
+        }
+    }
+}
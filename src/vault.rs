@@ -0,0 +1,347 @@
+//! Markdown Vault Export
+//!
+//! Writes the wisdom corpus as an Obsidian-compatible vault: one note per
+//! thinker and per principle, joined by `[[wikilinks]]`, with YAML
+//! frontmatter carrying live confidence and outcome stats.
+//!
+//! Exports are incremental: notes are only rewritten when their content
+//! changed, and notes for principles that no longer exist are removed.
+//! Only files marked `source: 100minds` are ever touched, so hand-written
+//! notes in the same vault are safe.
+
+use crate::db::reliability_factor;
+use crate::templates::{all_tensions, principle_name_matches};
+use crate::error::Result;
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const SOURCE_MARKER: &str = "source: 100minds";
+
+/// What an export run changed
+#[derive(Debug, Clone, Default)]
+pub struct VaultExportStats {
+    pub written: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+}
+
+struct ThinkerRow {
+    id: String,
+    name: String,
+    domain: String,
+    background: Option<String>,
+    successes: i64,
+    failures: i64,
+}
+
+struct PrincipleRow {
+    id: String,
+    thinker_id: String,
+    name: String,
+    description: String,
+    domain_tags: Vec<String>,
+    application_rule: Option<String>,
+    anti_pattern: Option<String>,
+    falsification: Option<String>,
+    action_prompt: Option<String>,
+    base_confidence: f64,
+    learned_confidence: f64,
+    successes: i64,
+    failures: i64,
+}
+
+/// Make a note title safe as a file name and wikilink target
+fn note_title(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => '-',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// YAML scalar (JSON strings are valid YAML)
+fn yaml_str(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+fn load_thinkers(conn: &Connection) -> Result<Vec<ThinkerRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.name, t.domain, t.background,
+                COALESCE(r.successes, 0), COALESCE(r.failures, 0)
+         FROM thinkers t
+         LEFT JOIN thinker_reliability r ON r.thinker_id = t.id
+         ORDER BY t.name",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(ThinkerRow {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                domain: row.get(2)?,
+                background: row.get(3)?,
+                successes: row.get(4)?,
+                failures: row.get(5)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+fn load_principles(conn: &Connection) -> Result<Vec<PrincipleRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT p.id, p.thinker_id, p.name, p.description, p.domain_tags,
+                p.application_rule, p.anti_pattern, p.falsification,
+                p.base_confidence, p.learned_confidence,
+                (SELECT COUNT(*) FROM framework_adjustments a WHERE a.principle_id = p.id AND a.adjustment > 0),
+                (SELECT COUNT(*) FROM framework_adjustments a WHERE a.principle_id = p.id AND a.adjustment < 0),
+                p.action_prompt
+         FROM principles p
+         ORDER BY p.name",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            let tags: Option<String> = row.get(4)?;
+            Ok(PrincipleRow {
+                id: row.get(0)?,
+                thinker_id: row.get(1)?,
+                name: row.get(2)?,
+                description: row.get(3)?,
+                domain_tags: tags
+                    .and_then(|t| serde_json::from_str(&t).ok())
+                    .unwrap_or_default(),
+                application_rule: row.get(5)?,
+                anti_pattern: row.get(6)?,
+                falsification: row.get(7)?,
+                action_prompt: row.get(12)?,
+                base_confidence: row.get::<_, Option<f64>>(8)?.unwrap_or(0.5),
+                learned_confidence: row.get::<_, Option<f64>>(9)?.unwrap_or(0.5),
+                successes: row.get(10)?,
+                failures: row.get(11)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// Write `content` unless the file already holds exactly that
+fn write_if_changed(path: &Path, content: &str, stats: &mut VaultExportStats) -> Result<()> {
+    if std::fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        stats.unchanged += 1;
+    } else {
+        std::fs::write(path, content)?;
+        stats.written += 1;
+    }
+    Ok(())
+}
+
+/// Delete generated notes in `dir` that this export didn't produce
+fn remove_stale(dir: &Path, keep: &HashSet<PathBuf>, stats: &mut VaultExportStats) -> Result<()> {
+    for entry in std::fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "md")
+            && !keep.contains(&path)
+            && std::fs::read_to_string(&path).is_ok_and(|c| c.contains(SOURCE_MARKER))
+        {
+            std::fs::remove_file(&path)?;
+            stats.removed += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Export (or refresh) the corpus as a Markdown vault under `dir`
+pub fn export_vault(conn: &Connection, dir: &Path) -> Result<VaultExportStats> {
+    let thinkers = load_thinkers(conn)?;
+    let principles = load_principles(conn)?;
+    let tensions = all_tensions();
+    let mut stats = VaultExportStats::default();
+
+    let thinker_dir = dir.join("Thinkers");
+    let principle_dir = dir.join("Principles");
+    std::fs::create_dir_all(&thinker_dir)?;
+    std::fs::create_dir_all(&principle_dir)?;
+
+    // Note titles; principle names only need the thinker suffix when they collide
+    let thinker_titles: HashMap<&str, String> =
+        thinkers.iter().map(|t| (t.id.as_str(), note_title(&t.name))).collect();
+    let mut name_counts: HashMap<String, usize> = HashMap::new();
+    for p in &principles {
+        *name_counts.entry(note_title(&p.name)).or_insert(0) += 1;
+    }
+    let principle_titles: HashMap<&str, String> = principles
+        .iter()
+        .map(|p| {
+            let title = note_title(&p.name);
+            let title = if name_counts[&title] > 1 {
+                let thinker = thinker_titles.get(p.thinker_id.as_str()).cloned().unwrap_or_default();
+                format!("{} ({})", title, thinker)
+            } else {
+                title
+            };
+            (p.id.as_str(), title)
+        })
+        .collect();
+
+    let mut kept: HashSet<PathBuf> = HashSet::new();
+
+    for p in &principles {
+        let title = &principle_titles[p.id.as_str()];
+        let thinker_title = thinker_titles.get(p.thinker_id.as_str()).cloned().unwrap_or_default();
+        let mut md = String::new();
+
+        writeln!(md, "---").unwrap();
+        writeln!(md, "id: {}", yaml_str(&p.id)).unwrap();
+        writeln!(md, "type: principle").unwrap();
+        writeln!(md, "thinker: {}", yaml_str(&format!("[[{}]]", thinker_title))).unwrap();
+        let domains: Vec<String> = p.domain_tags.iter().map(|d| yaml_str(d)).collect();
+        writeln!(md, "domains: [{}]", domains.join(", ")).unwrap();
+        writeln!(md, "confidence: {:.3}", p.learned_confidence).unwrap();
+        writeln!(md, "base_confidence: {:.3}", p.base_confidence).unwrap();
+        writeln!(md, "successes: {}", p.successes).unwrap();
+        writeln!(md, "failures: {}", p.failures).unwrap();
+        writeln!(md, "{}", SOURCE_MARKER).unwrap();
+        writeln!(md, "---\n").unwrap();
+
+        writeln!(md, "# {}\n", p.name).unwrap();
+        writeln!(md, "From [[{}]]\n", thinker_title).unwrap();
+        writeln!(md, "{}", p.description).unwrap();
+        for (heading, text) in [
+            ("Apply when", &p.application_rule),
+            ("Anti-pattern", &p.anti_pattern),
+            ("Falsified if", &p.falsification),
+            ("Action", &p.action_prompt),
+        ] {
+            if let Some(text) = text.as_deref().filter(|t| !t.is_empty()) {
+                writeln!(md, "\n## {}\n\n{}", heading, text).unwrap();
+            }
+        }
+
+        // Tensions from decision templates, linked when the other side is in the corpus
+        let mut tension_lines = Vec::new();
+        for (_, t) in &tensions {
+            let (other, when_this, when_other) = if principle_name_matches(&t.principle_a, &p.name) {
+                (&t.principle_b, &t.when_to_pick_a, &t.when_to_pick_b)
+            } else if principle_name_matches(&t.principle_b, &p.name) {
+                (&t.principle_a, &t.when_to_pick_b, &t.when_to_pick_a)
+            } else {
+                continue;
+            };
+            let other_link = principles
+                .iter()
+                .find(|o| o.id != p.id && principle_name_matches(other, &o.name))
+                .map(|o| format!("[[{}]]", principle_titles[o.id.as_str()]))
+                .unwrap_or_else(|| other.clone());
+            let line = format!("- vs {}: this when {}; that when {}", other_link, when_this, when_other);
+            if !tension_lines.contains(&line) {
+                tension_lines.push(line);
+            }
+        }
+        if !tension_lines.is_empty() {
+            writeln!(md, "\n## Tensions\n\n{}", tension_lines.join("\n")).unwrap();
+        }
+
+        let path = principle_dir.join(format!("{}.md", title));
+        write_if_changed(&path, &md, &mut stats)?;
+        kept.insert(path);
+    }
+
+    for t in &thinkers {
+        let title = &thinker_titles[t.id.as_str()];
+        let own: Vec<&PrincipleRow> = principles.iter().filter(|p| p.thinker_id == t.id).collect();
+        let mut md = String::new();
+
+        writeln!(md, "---").unwrap();
+        writeln!(md, "id: {}", yaml_str(&t.id)).unwrap();
+        writeln!(md, "type: thinker").unwrap();
+        writeln!(md, "domain: {}", yaml_str(&t.domain)).unwrap();
+        writeln!(md, "principles: {}", own.len()).unwrap();
+        writeln!(md, "successes: {}", t.successes).unwrap();
+        writeln!(md, "failures: {}", t.failures).unwrap();
+        writeln!(md, "reliability: {:.3}", reliability_factor(t.successes, t.failures)).unwrap();
+        writeln!(md, "{}", SOURCE_MARKER).unwrap();
+        writeln!(md, "---\n").unwrap();
+
+        writeln!(md, "# {}\n", t.name).unwrap();
+        if let Some(bg) = t.background.as_deref().filter(|b| !b.is_empty()) {
+            writeln!(md, "{}\n", bg).unwrap();
+        }
+        writeln!(md, "## Principles\n").unwrap();
+        for p in own {
+            writeln!(
+                md,
+                "- [[{}]] ({:.0}%)",
+                principle_titles[p.id.as_str()],
+                p.learned_confidence * 100.0
+            )
+            .unwrap();
+        }
+
+        let path = thinker_dir.join(format!("{}.md", title));
+        write_if_changed(&path, &md, &mut stats)?;
+        kept.insert(path);
+    }
+
+    // Index note
+    let mut index = String::new();
+    writeln!(index, "---\ntype: index\n{}\n---\n", SOURCE_MARKER).unwrap();
+    writeln!(index, "# 100minds\n").unwrap();
+    writeln!(index, "{} thinkers, {} principles\n", thinkers.len(), principles.len()).unwrap();
+    writeln!(index, "## Thinkers\n").unwrap();
+    for t in &thinkers {
+        writeln!(index, "- [[{}]] - {}", thinker_titles[t.id.as_str()], t.domain).unwrap();
+    }
+    let index_path = dir.join("100minds.md");
+    write_if_changed(&index_path, &index, &mut stats)?;
+
+    remove_stale(&principle_dir, &kept, &mut stats)?;
+    remove_stale(&thinker_dir, &kept, &mut stats)?;
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_vault_is_incremental() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('kb', 'Kent Beck', 'software');
+             INSERT INTO principles (id, thinker_id, name, description, domain_tags, learned_confidence)
+             VALUES ('yagni', 'kb', 'YAGNI', 'Build only what is needed', '[\"architecture\"]', 0.7),
+                    ('tdd', 'kb', 'Test-Driven Development', 'Tests first', '[]', 0.6);",
+        )
+        .unwrap();
+
+        let vault = dir.path().join("vault");
+        let stats = export_vault(&conn, &vault).unwrap();
+        assert_eq!(stats.written, 4);
+
+        let note = std::fs::read_to_string(vault.join("Principles/YAGNI.md")).unwrap();
+        assert!(note.contains("thinker: \"[[Kent Beck]]\""));
+        assert!(note.contains("confidence: 0.700"));
+        assert!(note.contains("domains: [\"architecture\"]"));
+        let thinker = std::fs::read_to_string(vault.join("Thinkers/Kent Beck.md")).unwrap();
+        assert!(thinker.contains("- [[YAGNI]] (70%)"));
+
+        // Nothing changed: nothing rewritten
+        let stats = export_vault(&conn, &vault).unwrap();
+        assert_eq!((stats.written, stats.unchanged), (0, 4));
+
+        // Removed principles disappear, hand-written notes stay
+        std::fs::write(vault.join("Principles/My notes.md"), "mine").unwrap();
+        conn.execute("DELETE FROM principles WHERE id = 'tdd'", []).unwrap();
+        let stats = export_vault(&conn, &vault).unwrap();
+        assert_eq!(stats.removed, 1);
+        assert!(vault.join("Principles/My notes.md").exists());
+    }
+}
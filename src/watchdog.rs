@@ -0,0 +1,301 @@
+//! Learning Watchdog
+//!
+//! The flywheel trusts every outcome it is given. The watchdog looks for
+//! learning dynamics that more likely come from a bug than from the world:
+//!
+//! - **single_source_gain**: a principle's confidence gains all come from one agent
+//! - **domain_failure_burst**: a domain suddenly fails far more often than it used to
+//! - **outcome_spike**: far more outcomes in the last hour than usual (a retry loop, a replayed queue)
+//!
+//! Alerts show in `--learning-stats` and `get_learning_stats`. The server
+//! re-checks after each outcome tool call and attaches alerts it hasn't
+//! raised before as MCP `notifications/message` entries. Thresholds come
+//! from `[watchdog]` in config.toml.
+
+use crate::error::Result;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The `[watchdog]` table of config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchdogConfig {
+    /// History the baselines are drawn from
+    pub lookback_days: i64,
+    /// Confidence gains a principle needs before their sources are checked
+    pub single_source_min_gains: usize,
+    /// Hours that count as "sudden" for domain failures
+    pub burst_hours: i64,
+    /// Failed decisions a domain needs in the burst window
+    pub burst_min_failures: usize,
+    /// How much the burst failure rate must exceed the baseline rate
+    pub burst_rate_increase: f64,
+    /// Outcomes in the last hour that can be a spike...
+    pub spike_min_outcomes: usize,
+    /// ...when they are this many times the usual hourly rate
+    pub spike_factor: f64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        WatchdogConfig {
+            lookback_days: 30,
+            single_source_min_gains: 5,
+            burst_hours: 24,
+            burst_min_failures: 5,
+            burst_rate_increase: 0.4,
+            spike_min_outcomes: 20,
+            spike_factor: 5.0,
+        }
+    }
+}
+
+impl WatchdogConfig {
+    /// Read `[watchdog]` from the config.toml beside the database; a missing file or table gives the defaults
+    pub fn beside(conn: &Connection) -> Result<Self> {
+        crate::config::section(conn, "watchdog")
+    }
+}
+
+/// One suspicious learning pattern
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    /// "single_source_gain", "domain_failure_burst" or "outcome_spike"
+    pub kind: String,
+    /// "warning", or "critical" for patterns that are already moving confidences
+    pub severity: String,
+    /// Principle id, domain tag, or "outcomes"
+    pub subject: String,
+    pub message: String,
+}
+
+impl Alert {
+    fn key(&self) -> String {
+        format!("{}:{}", self.kind, self.subject)
+    }
+}
+
+/// Every pattern present as of `now`
+pub fn scan(conn: &Connection, config: &WatchdogConfig, now: DateTime<Utc>) -> Result<Vec<Alert>> {
+    let mut alerts = single_source_gains(conn, config, now)?;
+    alerts.extend(domain_failure_bursts(conn, config, now)?);
+    alerts.extend(outcome_spike(conn, config, now)?);
+    Ok(alerts)
+}
+
+fn single_source_gains(conn: &Connection, config: &WatchdogConfig, now: DateTime<Utc>) -> Result<Vec<Alert>> {
+    let since = (now - Duration::days(config.lookback_days)).to_rfc3339();
+    let alerts = conn
+        .prepare(
+            "SELECT fa.principle_id, COALESCE(p.name, fa.principle_id), MIN(fa.actor_id), COUNT(*),
+                    COALESCE(p.learned_confidence - p.base_confidence, 0)
+             FROM framework_adjustments fa
+             LEFT JOIN principles p ON p.id = fa.principle_id
+             WHERE fa.adjustment > 0 AND julianday(fa.created_at) > julianday(?1)
+             GROUP BY fa.principle_id
+             HAVING COUNT(*) >= ?2 AND COUNT(DISTINCT COALESCE(fa.actor_id, '')) = 1 AND MIN(fa.actor_id) IS NOT NULL",
+        )?
+        .query_map(params![since, config.single_source_min_gains as i64], |row| {
+            let (id, name, actor, gains, drift): (String, String, String, i64, f64) =
+                (row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?);
+            Ok(Alert {
+                kind: "single_source_gain".to_string(),
+                severity: if drift > 0.0 { "critical" } else { "warning" }.to_string(),
+                subject: id,
+                message: format!("{}: all {} confidence gains in {} days came from {}", name, gains, config.lookback_days, actor),
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(alerts)
+}
+
+fn domain_failure_bursts(conn: &Connection, config: &WatchdogConfig, now: DateTime<Utc>) -> Result<Vec<Alert>> {
+    let since = (now - Duration::days(config.lookback_days)).to_rfc3339();
+    let burst_start = (now - Duration::hours(config.burst_hours)).to_rfc3339();
+    let rows: Vec<(String, Option<String>, bool, bool)> = conn
+        .prepare(
+            "SELECT DISTINCT d.id, p.domain_tags, d.outcome_success, julianday(fa.created_at) > julianday(?2)
+             FROM framework_adjustments fa
+             JOIN decisions d ON d.id = fa.decision_id
+             JOIN principles p ON p.id = fa.principle_id
+             WHERE d.outcome_success IS NOT NULL AND julianday(fa.created_at) > julianday(?1)",
+        )?
+        .query_map(params![since, burst_start], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get::<_, i64>(2)? != 0, row.get(3)?))
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+
+    // Domain → (burst, baseline) decision outcomes, each decision once
+    type Outcomes = HashMap<String, bool>;
+    let mut domains: HashMap<String, (Outcomes, Outcomes)> = HashMap::new();
+    for (decision, tags, success, in_burst) in rows {
+        let tags: Vec<String> = tags.and_then(|t| serde_json::from_str(&t).ok()).unwrap_or_default();
+        let unique: HashSet<String> = tags.into_iter().map(|t| t.to_lowercase()).collect();
+        for tag in unique {
+            let (burst, baseline) = domains.entry(tag).or_default();
+            if in_burst { burst } else { baseline }.insert(decision.clone(), success);
+        }
+    }
+
+    let failure_rate = |outcomes: &Outcomes| {
+        (!outcomes.is_empty()).then(|| outcomes.values().filter(|&&s| !s).count() as f64 / outcomes.len() as f64)
+    };
+    let mut alerts: Vec<Alert> = domains
+        .into_iter()
+        .filter_map(|(domain, (burst, baseline))| {
+            let failures = burst.values().filter(|&&s| !s).count();
+            let rate = failure_rate(&burst)?;
+            let usual = failure_rate(&baseline).unwrap_or(0.0);
+            (failures >= config.burst_min_failures && rate - usual >= config.burst_rate_increase).then(|| Alert {
+                kind: "domain_failure_burst".to_string(),
+                severity: "critical".to_string(),
+                message: format!(
+                    "{}: {} of {} decisions failed in the last {}h (usually {:.0}%)",
+                    domain,
+                    failures,
+                    burst.len(),
+                    config.burst_hours,
+                    usual * 100.0
+                ),
+                subject: domain,
+            })
+        })
+        .collect();
+    alerts.sort_by(|a, b| a.subject.cmp(&b.subject));
+    Ok(alerts)
+}
+
+fn outcome_spike(conn: &Connection, config: &WatchdogConfig, now: DateTime<Utc>) -> Result<Vec<Alert>> {
+    let hour_ago = (now - Duration::hours(1)).to_rfc3339();
+    let since = (now - Duration::days(config.lookback_days)).to_rfc3339();
+    let (recent, earlier): (i64, i64) = conn.query_row(
+        "SELECT COALESCE(SUM(julianday(outcome_recorded_at) > julianday(?1)), 0),
+                COALESCE(SUM(julianday(outcome_recorded_at) <= julianday(?1)), 0)
+         FROM decisions
+         WHERE outcome_recorded_at IS NOT NULL
+           AND julianday(outcome_recorded_at) > julianday(?2) AND julianday(outcome_recorded_at) <= julianday(?3)",
+        params![hour_ago, since, now.to_rfc3339()],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let hourly = earlier as f64 / (config.lookback_days * 24 - 1).max(1) as f64;
+    if (recent as usize) < config.spike_min_outcomes || (recent as f64) < config.spike_factor * hourly.max(1.0) {
+        return Ok(Vec::new());
+    }
+    Ok(vec![Alert {
+        kind: "outcome_spike".to_string(),
+        severity: "warning".to_string(),
+        subject: "outcomes".to_string(),
+        message: format!("{} outcomes recorded in the last hour (usually {:.1}/h)", recent, hourly),
+    }])
+}
+
+/// Remember `alerts` as raised; returns those not raised before
+pub fn raise(conn: &Connection, alerts: &[Alert], now: DateTime<Utc>) -> Result<Vec<Alert>> {
+    let mut new = Vec::new();
+    for alert in alerts {
+        let inserted = conn.execute(
+            "INSERT INTO watchdog_alerts (key, kind, severity, subject, message, first_seen, last_seen)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+             ON CONFLICT(key) DO NOTHING",
+            params![alert.key(), alert.kind, alert.severity, alert.subject, alert.message, now.to_rfc3339()],
+        )?;
+        if inserted == 0 {
+            conn.execute(
+                "UPDATE watchdog_alerts SET message = ?2, severity = ?3, last_seen = ?4 WHERE key = ?1",
+                params![alert.key(), alert.message, alert.severity, now.to_rfc3339()],
+            )?;
+        } else {
+            new.push(alert.clone());
+        }
+    }
+    Ok(new)
+}
+
+/// `alerts` as MCP logging notifications
+pub fn notifications(alerts: &[Alert]) -> Vec<serde_json::Value> {
+    alerts
+        .iter()
+        .map(|alert| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/message",
+                "params": {"level": alert.severity, "logger": "100minds.watchdog", "data": alert},
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO thinkers (id, name, domain) VALUES ('t', 'T', 'software');
+             INSERT INTO principles (id, thinker_id, name, description, domain_tags, base_confidence, learned_confidence) VALUES
+                ('yagni', 't', 'YAGNI', 'd', '[\"architecture\"]', 0.5, 0.75),
+                ('kiss', 't', 'KISS', 'd', '[\"testing\"]', 0.5, 0.5);",
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn outcome(conn: &Connection, id: &str, principle: &str, actor: &str, success: bool, at: DateTime<Utc>) {
+        conn.execute(
+            "INSERT INTO decisions (id, question, counsel_json, content_hash, signature, agent_pubkey, outcome_success, outcome_recorded_at)
+             VALUES (?1, 'q', '{}', 'h', 's', 'k', ?2, ?3)",
+            params![id, success, at.to_rfc3339()],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO framework_adjustments (principle_id, adjustment, decision_id, actor_id, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![principle, if success { 0.05 } else { -0.10 }, id, actor, at.format("%Y-%m-%d %H:%M:%S").to_string()],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_watchdog_flags_single_source_and_failure_burst() {
+        let (_dir, conn) = setup();
+        let now = Utc::now();
+        let config = WatchdogConfig::default();
+        for i in 0..5 {
+            outcome(&conn, &format!("g{}", i), "yagni", "worker-7", true, now - Duration::days(2 + i));
+        }
+        // A healthy testing baseline, then a burst of failures
+        for i in 0..6 {
+            outcome(&conn, &format!("ok{}", i), "kiss", &format!("agent-{}", i), true, now - Duration::days(5 + i));
+            outcome(&conn, &format!("f{}", i), "kiss", &format!("agent-{}", i), false, now - Duration::hours(2 + i));
+        }
+
+        let alerts = scan(&conn, &config, now).unwrap();
+        let kinds: Vec<(&str, &str, &str)> =
+            alerts.iter().map(|a| (a.kind.as_str(), a.subject.as_str(), a.severity.as_str())).collect();
+        assert_eq!(kinds, [("single_source_gain", "yagni", "critical"), ("domain_failure_burst", "testing", "critical")]);
+        assert!(alerts[0].message.contains("worker-7"));
+
+        // A second agent's gain clears the single-source alert
+        outcome(&conn, "g-other", "yagni", "agent-2", true, now - Duration::days(1));
+        assert!(!scan(&conn, &config, now).unwrap().iter().any(|a| a.kind == "single_source_gain"));
+    }
+
+    #[test]
+    fn test_outcome_spike_is_raised_once() {
+        let (_dir, conn) = setup();
+        let now = Utc::now();
+        for i in 0..25 {
+            outcome(&conn, &format!("s{}", i), "kiss", &format!("agent-{}", i), true, now - Duration::minutes(i));
+        }
+        let alerts = scan(&conn, &WatchdogConfig::default(), now).unwrap();
+        assert!(alerts.iter().any(|a| a.kind == "outcome_spike" && a.message.starts_with("25 outcomes")));
+
+        assert_eq!(raise(&conn, &alerts, now).unwrap(), alerts);
+        assert!(raise(&conn, &alerts, now).unwrap().is_empty());
+        let notes = notifications(&alerts);
+        assert_eq!(notes[0]["method"], "notifications/message");
+    }
+}
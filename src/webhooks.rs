@@ -0,0 +1,397 @@
+//! Forge Webhooks → Outcomes
+//!
+//! Server mode accepts GitHub (and GitLab) webhook deliveries and turns
+//! PR merges, reverts and reopens into outcome records for the decisions
+//! linked to them in the delivery's repository (see
+//! `git_outcomes::link_decision`). Each delivery records at most once per
+//! decision, so a forge redelivering it changes nothing.
+//!
+//! Deliveries must be signed: GitHub's `X-Hub-Signature-256` HMAC or
+//! GitLab's `X-Gitlab-Token`. Without a configured secret every delivery
+//! is rejected.
+//!
+//! Config lives in `webhooks.json` in the data directory:
+//!
+//! ```json
+//! { "secret": "...", "events": { "merged": "success", "reverted": "failure", "reopened": "failure" } }
+//! ```
+
+use crate::git_outcomes::links_for_pr;
+use crate::outcome::record_outcome_v2;
+use crate::types::RecordOutcomeRequest;
+use crate::error::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Environment variable that overrides the configured secret
+pub const SECRET_ENV: &str = "MINDS_WEBHOOK_SECRET";
+
+/// What a forge event means for the linked decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutcomeMapping {
+    Success,
+    Failure,
+    Ignore,
+}
+
+/// Event → outcome mapping
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventMappings {
+    pub merged: OutcomeMapping,
+    pub reverted: OutcomeMapping,
+    pub reopened: OutcomeMapping,
+}
+
+impl Default for EventMappings {
+    fn default() -> Self {
+        Self {
+            merged: OutcomeMapping::Success,
+            reverted: OutcomeMapping::Failure,
+            reopened: OutcomeMapping::Failure,
+        }
+    }
+}
+
+/// Webhook receiver configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub secret: Option<String>,
+    pub events: EventMappings,
+}
+
+impl WebhookConfig {
+    /// Load `webhooks.json` (missing file → defaults); `MINDS_WEBHOOK_SECRET` wins over the file
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut config: WebhookConfig = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(path)?)?
+        } else {
+            WebhookConfig::default()
+        };
+        if let Ok(secret) = std::env::var(SECRET_ENV) {
+            if !secret.is_empty() {
+                config.secret = Some(secret);
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// A forge event relevant to outcomes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForgeEvent {
+    /// PR merged; carries the head branch when known
+    Merged { number: u64, branch: Option<String> },
+    /// A revert PR merged, undoing PR `number`
+    Reverted { number: u64 },
+    /// PR reopened after being closed (or, on GitHub, where issues and PRs
+    /// share numbers, an issue)
+    Reopened { number: u64 },
+}
+
+impl ForgeEvent {
+    pub fn number(&self) -> u64 {
+        match self {
+            ForgeEvent::Merged { number, .. }
+            | ForgeEvent::Reverted { number }
+            | ForgeEvent::Reopened { number } => *number,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            ForgeEvent::Merged { .. } => "merged",
+            ForgeEvent::Reverted { .. } => "reverted",
+            ForgeEvent::Reopened { .. } => "reopened",
+        }
+    }
+}
+
+/// Verify GitHub's `X-Hub-Signature-256: sha256=<hex>` header
+pub fn verify_github_signature(secret: &str, body: &[u8], header: &str) -> bool {
+    let Some(hex_sig) = header.trim().strip_prefix("sha256=") else {
+        return false;
+    };
+    let Ok(expected) = hex::decode(hex_sig) else {
+        return false;
+    };
+    let Ok(expected): Result<[u8; 32], _> = expected.try_into() else {
+        return false;
+    };
+    hmac_sha256::HMAC::verify(body, secret.as_bytes(), &expected)
+}
+
+/// Verify GitLab's `X-Gitlab-Token` header (a shared secret, not a signature)
+pub fn verify_gitlab_token(secret: &str, header: &str) -> bool {
+    let (a, b) = (secret.as_bytes(), header.trim().as_bytes());
+    // Constant-time compare
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Find the PR a revert undoes: "Reverts owner/repo#12" or "Revert ... (#12)"
+fn reverted_pr_number(title: &str, body: &str) -> Option<u64> {
+    let digits_after = |s: &str, marker: &str| -> Option<u64> {
+        let rest = &s[s.find(marker)? + marker.len()..];
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    };
+
+    body.lines()
+        .find_map(|l| l.trim().strip_prefix("Reverts ").and_then(|r| digits_after(r, "#")))
+        .or_else(|| {
+            title
+                .strip_prefix("Revert")
+                .and_then(|t| t.rfind("(#").and_then(|i| digits_after(&t[i..], "(#")))
+        })
+}
+
+/// Parse a GitHub delivery (`X-GitHub-Event` + JSON payload)
+pub fn parse_github_event(event: &str, payload: &serde_json::Value) -> Option<ForgeEvent> {
+    let action = payload.get("action").and_then(|a| a.as_str()).unwrap_or("");
+
+    match event {
+        "pull_request" => {
+            let pr = payload.get("pull_request")?;
+            let number = pr.get("number").and_then(|n| n.as_u64())?;
+            if action == "reopened" {
+                return Some(ForgeEvent::Reopened { number });
+            }
+            if action != "closed" || !pr.get("merged").and_then(|m| m.as_bool()).unwrap_or(false) {
+                return None;
+            }
+
+            let title = pr.get("title").and_then(|t| t.as_str()).unwrap_or("");
+            let body = pr.get("body").and_then(|b| b.as_str()).unwrap_or("");
+            if title.starts_with("Revert") {
+                if let Some(original) = reverted_pr_number(title, body) {
+                    return Some(ForgeEvent::Reverted { number: original });
+                }
+            }
+
+            let branch = pr
+                .get("head")
+                .and_then(|h| h.get("ref"))
+                .and_then(|r| r.as_str())
+                .map(String::from);
+            Some(ForgeEvent::Merged { number, branch })
+        }
+        "issues" if action == "reopened" => {
+            let number = payload.get("issue")?.get("number").and_then(|n| n.as_u64())?;
+            Some(ForgeEvent::Reopened { number })
+        }
+        _ => None,
+    }
+}
+
+/// Parse a GitLab delivery (`X-Gitlab-Event` + JSON payload)
+pub fn parse_gitlab_event(event: &str, payload: &serde_json::Value) -> Option<ForgeEvent> {
+    let attrs = payload.get("object_attributes")?;
+    let action = attrs.get("action").and_then(|a| a.as_str()).unwrap_or("");
+    let number = attrs.get("iid").and_then(|n| n.as_u64())?;
+
+    match (event, action) {
+        ("Merge Request Hook", "merge") => {
+            let title = attrs.get("title").and_then(|t| t.as_str()).unwrap_or("");
+            let description = attrs.get("description").and_then(|d| d.as_str()).unwrap_or("");
+            if title.starts_with("Revert") {
+                // GitLab reverts reference the original as "!12"
+                let original = description.split_whitespace().find_map(|w| {
+                    let digits: String = w
+                        .strip_prefix('!')?
+                        .chars()
+                        .take_while(|c| c.is_ascii_digit())
+                        .collect();
+                    digits.parse().ok()
+                });
+                if let Some(original) = original {
+                    return Some(ForgeEvent::Reverted { number: original });
+                }
+            }
+            let branch = attrs.get("source_branch").and_then(|b| b.as_str()).map(String::from);
+            Some(ForgeEvent::Merged { number, branch })
+        }
+        // Issue iids are numbered apart from merge requests, so "Issue Hook" can't name a linked MR
+        ("Merge Request Hook", "reopen") => Some(ForgeEvent::Reopened { number }),
+        _ => None,
+    }
+}
+
+/// The "owner/name" a GitHub or GitLab payload came from
+pub fn repository(payload: &serde_json::Value) -> Option<&str> {
+    payload
+        .pointer("/repository/full_name")
+        .or_else(|| payload.pointer("/project/path_with_namespace"))
+        .and_then(|r| r.as_str())
+}
+
+/// Record outcomes for every decision linked to the event's PR in `repo`
+///
+/// A merge only records for decisions still awaiting an outcome; reverts and
+/// reopens record even after a success, since they are new evidence.
+/// `delivery_id` (`X-GitHub-Delivery` / `X-Gitlab-Event-UUID`) keys each
+/// outcome, so a redelivery returns without recording anything again.
+pub fn apply_event(
+    conn: &Connection,
+    config: &WebhookConfig,
+    repo: &str,
+    delivery_id: &str,
+    event: &ForgeEvent,
+) -> Result<Vec<String>> {
+    let mapping = match event {
+        ForgeEvent::Merged { .. } => config.events.merged,
+        ForgeEvent::Reverted { .. } => config.events.reverted,
+        ForgeEvent::Reopened { .. } => config.events.reopened,
+    };
+    if mapping == OutcomeMapping::Ignore {
+        return Ok(vec![]);
+    }
+
+    let branch = match event {
+        ForgeEvent::Merged { branch, .. } => branch.as_deref(),
+        _ => None,
+    };
+
+    let mut recorded = Vec::new();
+    for link in links_for_pr(conn, repo, event.number(), branch)? {
+        if matches!(event, ForgeEvent::Merged { .. }) {
+            let has_outcome: bool = conn
+                .query_row(
+                    "SELECT outcome_success IS NOT NULL FROM decisions WHERE id = ?1",
+                    [&link.decision_id],
+                    |row| row.get(0),
+                )
+                .unwrap_or(false);
+            if has_outcome {
+                continue;
+            }
+        }
+
+        let success = mapping == OutcomeMapping::Success;
+        let result = record_outcome_v2(
+            conn,
+            &RecordOutcomeRequest {
+                decision_id: link.decision_id.clone(),
+                success,
+                notes: Some(format!("Webhook: #{} {}", event.number(), event.kind())),
+                principle_ids: vec![],
+                domain: None,
+                confidence_score: None,
+                failure_stage: (!success).then(|| event.kind().to_string()),
+                failure_reasons: Vec::new(),
+                idempotency_key: Some(format!("webhook:{}:{}", delivery_id, link.decision_id)),
+                actor_id: None,
+                template_id: None,
+                template_path: vec![],
+            },
+        )?;
+        if !result.replayed {
+            recorded.push(link.decision_id);
+        }
+    }
+
+    Ok(recorded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_signature_verification() {
+        let body = br#"{"action":"closed"}"#;
+        let sig = format!("sha256={}", hex::encode(hmac_sha256::HMAC::mac(body, b"s3cret")));
+
+        assert!(verify_github_signature("s3cret", body, &sig));
+        assert!(!verify_github_signature("wrong", body, &sig));
+        assert!(!verify_github_signature("s3cret", b"tampered", &sig));
+        assert!(!verify_github_signature("s3cret", body, "sha1=abc"));
+
+        assert!(verify_gitlab_token("s3cret", "s3cret"));
+        assert!(!verify_gitlab_token("s3cret", "s3cre"));
+    }
+
+    #[test]
+    fn test_parse_github_events() {
+        let merged = json!({
+            "action": "closed",
+            "pull_request": {"number": 42, "merged": true, "title": "Add cache", "head": {"ref": "feature/cache"}}
+        });
+        assert_eq!(
+            parse_github_event("pull_request", &merged),
+            Some(ForgeEvent::Merged { number: 42, branch: Some("feature/cache".to_string()) })
+        );
+
+        let revert = json!({
+            "action": "closed",
+            "pull_request": {"number": 50, "merged": true, "title": "Revert \"Add cache\"", "body": "Reverts org/repo#42"}
+        });
+        assert_eq!(parse_github_event("pull_request", &revert), Some(ForgeEvent::Reverted { number: 42 }));
+
+        let closed = json!({"action": "closed", "pull_request": {"number": 42, "merged": false}});
+        assert_eq!(parse_github_event("pull_request", &closed), None);
+
+        let reopened = json!({"action": "reopened", "issue": {"number": 7}});
+        assert_eq!(parse_github_event("issues", &reopened), Some(ForgeEvent::Reopened { number: 7 }));
+
+        // GitLab issue iids overlap merge request iids, so issue reopens are ignored
+        let issue = json!({"object_attributes": {"action": "reopen", "iid": 3}});
+        assert_eq!(parse_gitlab_event("Issue Hook", &issue), None);
+        assert_eq!(parse_gitlab_event("Merge Request Hook", &issue), Some(ForgeEvent::Reopened { number: 3 }));
+
+        let gitlab = json!({"object_attributes": {"action": "merge", "iid": 3, "title": "Add cache", "source_branch": "cache"}});
+        assert_eq!(
+            parse_gitlab_event("Merge Request Hook", &gitlab),
+            Some(ForgeEvent::Merged { number: 3, branch: Some("cache".to_string()) })
+        );
+    }
+
+    #[test]
+    fn test_apply_event_respects_mapping() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        crate::git_outcomes::link_decision(&conn, "d1", Some("feature/cache"), Some(42), ".", Some("org/app")).unwrap();
+
+        let mut config = WebhookConfig::default();
+        config.events.merged = OutcomeMapping::Ignore;
+        let merged = ForgeEvent::Merged { number: 42, branch: None };
+        assert!(apply_event(&conn, &config, "org/app", "del-1", &merged).unwrap().is_empty());
+
+        let recorded = apply_event(&conn, &config, "org/app", "del-2", &ForgeEvent::Reverted { number: 42 }).unwrap();
+        assert_eq!(recorded, vec!["d1".to_string()]);
+        let reopened = ForgeEvent::Reopened { number: 99 };
+        assert!(apply_event(&conn, &config, "org/app", "del-3", &reopened).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_deliveries_are_scoped_to_their_repo_and_applied_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::init_db(&dir.path().join("test.db")).unwrap();
+        for (id, repo) in [("api-1", "org/api"), ("web-1", "org/web")] {
+            crate::db::insert_decision(&conn, id, "Add a cache?", None, "{}", None, "h", "s", "k").unwrap();
+            crate::git_outcomes::link_decision(&conn, id, None, Some(42), ".", Some(repo)).unwrap();
+        }
+        let config = WebhookConfig::default();
+        let reverted = ForgeEvent::Reverted { number: 42 };
+        let outcomes = |id: &str| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM outcome_keys WHERE decision_id = ?1", [id], |row| row.get(0)).unwrap()
+        };
+
+        assert_eq!(apply_event(&conn, &config, "org/api", "del-1", &reverted).unwrap(), vec!["api-1".to_string()]);
+        assert_eq!((outcomes("api-1"), outcomes("web-1")), (1, 0));
+
+        // The forge redelivers: nothing is recorded twice
+        assert!(apply_event(&conn, &config, "org/api", "del-1", &reverted).unwrap().is_empty());
+        assert_eq!(outcomes("api-1"), 1);
+
+        assert!(apply_event(&conn, &config, "org/other", "del-2", &reverted).unwrap().is_empty());
+        assert_eq!(apply_event(&conn, &config, "ORG/web", "del-3", &reverted).unwrap(), vec!["web-1".to_string()]);
+
+        let github = json!({"repository": {"full_name": "org/api"}});
+        let gitlab = json!({"project": {"path_with_namespace": "group/sub/web"}});
+        assert_eq!((repository(&github), repository(&gitlab)), (Some("org/api"), Some("group/sub/web")));
+    }
+}